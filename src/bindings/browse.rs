@@ -0,0 +1,163 @@
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+/// The lists the browser switches between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pane {
+    Paths,
+    Operations,
+    Schemas,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 3] = [Pane::Paths, Pane::Operations, Pane::Schemas];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Pane::Paths => "Paths",
+            Pane::Operations => "Operations",
+            Pane::Schemas => "Schemas",
+        }
+    }
+}
+
+/// One entry of a browser pane with its refs inlined for the preview.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrowseItem {
+    pub pane: Pane,
+    /// The name the other commands take, such as `listPets` or `/pets`.
+    /// Operations without an `operationId` are named by their method and
+    /// path.
+    pub name: String,
+    /// What the pane lists and searches, such as `GET /pets listPets`.
+    pub label: String,
+    pub preview: YamlNode,
+}
+
+pub trait BrowseParser {
+    fn get_browse_items(&self) -> Result<Vec<BrowseItem>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterBrowseParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterBrowseParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn browse_items(&self) -> Result<Vec<BrowseItem>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut items = vec![];
+        for path_item in resolver.entries("/paths")? {
+            items.push(BrowseItem {
+                pane: Pane::Paths,
+                name: path_item.name.to_owned(),
+                label: path_item.name,
+                preview: resolver.inline(&path_item.value, &path_item.file)?,
+            });
+        }
+        for operation in resolver.operations()? {
+            let (name, label) = match operation.operation.get("operationId") {
+                Some(id) => {
+                    let id = id.as_str().unwrap_or_default().to_string();
+                    let label = format!("{} {}", operation.describe(), id);
+                    (id, label)
+                }
+                None => (operation.describe(), operation.describe()),
+            };
+            items.push(BrowseItem {
+                pane: Pane::Operations,
+                name,
+                label,
+                preview: resolver.inline(&operation.operation, &operation.file)?,
+            });
+        }
+        for schema in resolver.entries("/components/schemas")? {
+            items.push(BrowseItem {
+                pane: Pane::Schemas,
+                name: schema.name.to_owned(),
+                label: schema.name,
+                preview: resolver.inline(&schema.value, &schema.file)?,
+            });
+        }
+        Ok(items)
+    }
+}
+
+impl BrowseParser for TreeSitterBrowseParser {
+    fn get_browse_items(&self) -> Result<Vec<BrowseItem>, OpenapiSchemerError> {
+        self.browse_items()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Browse))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::browse::{BrowseParser, Pane, TreeSitterBrowseParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_browse_items() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+    delete:
+      responses:
+        '204':
+          description: Gone
+components:
+  schemas:
+    Pet:
+      $ref: 'pet.yaml'
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  name:
+    type: string
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let items = TreeSitterBrowseParser::new(provider).get_browse_items()?;
+        let summary: Vec<(Pane, &str, &str)> = items
+            .iter()
+            .map(|item| (item.pane, item.name.as_str(), item.label.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Pane::Paths, "/pets", "/pets"),
+                (Pane::Operations, "listPets", "GET /pets listPets"),
+                (Pane::Operations, "DELETE /pets", "DELETE /pets"),
+                (Pane::Schemas, "Pet", "Pet"),
+            ]
+        );
+        let schema = items[1]
+            .preview
+            .pointer("/responses/200/content/application~1json/schema/type");
+        assert_eq!(schema.and_then(|node| node.as_str()), Some("object"));
+        Ok(())
+    }
+}
@@ -0,0 +1,684 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::{split_ref, Resolver},
+    yaml::{escape_pointer_segment, pointer_segments, YamlNode, YamlPair, YamlValue},
+};
+
+pub trait Bundler {
+    fn bundle(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+/// Where the targets of refs to other files end up in the bundle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RefRewrite {
+    /// Copied in place of the first ref to them, with later refs pointing at
+    /// that copy
+    #[default]
+    Inline,
+    /// Schemas are moved into `components/schemas` and refs to them point
+    /// there, anything else is inlined
+    Componentized,
+}
+
+/// How `TreeSitterBundler` writes refs into the bundle. The default only
+/// replaces refs to other files, each target being copied in once.
+#[derive(Clone, Debug, Default)]
+pub struct BundleOptions {
+    /// Replace refs by a copy of their target at every use, internal refs
+    /// included, up to this many refs deep. Refs below that are bundled as
+    /// usual.
+    pub max_depth: Option<usize>,
+    /// Leave refs within the file they are written in out of `max_depth`
+    /// copying.
+    pub keep_internal_refs: bool,
+    pub rewrite_refs: RefRewrite,
+    /// Mark each inlined block with a `# from: file:line` comment.
+    pub provenance: bool,
+}
+
+pub struct TreeSitterBundler {
+    provider: Box<dyn ContentProvider>,
+    options: BundleOptions,
+}
+
+/// Bookkeeping for a single bundle run.
+struct BundleState<'a> {
+    resolver: Resolver<'a>,
+    // Where a ref target (`file#/pointer`) was first inlined in the output,
+    // so later refs to the same target can point at it instead of copying it.
+    inlined: HashMap<String, String>,
+    // Schemas moved into `components/schemas` in the order they were found,
+    // and the names taken there.
+    hoisted: Vec<(String, YamlNode)>,
+    names: HashSet<String>,
+}
+
+impl BundleState<'_> {
+    /// Where the target `file#fragment` was written into the output, if it
+    /// was, either on its own or as part of its whole file.
+    fn location(&self, file: &Path, fragment: &str) -> Option<String> {
+        let target = format!("{}#{}", file.display(), fragment);
+        if let Some(location) = self.inlined.get(&target) {
+            return Some(location.to_owned());
+        }
+        let whole_file = format!("{}#", file.display());
+        self.inlined
+            .get(&whole_file)
+            .map(|location| format!("{}{}", location, fragment))
+    }
+
+    /// A name under `components/schemas` for the target `file#fragment`,
+    /// numbered when the plain name is taken.
+    fn schema_name(&mut self, file: &Path, fragment: &str) -> String {
+        let base = pointer_segments(fragment)
+            .and_then(|segments| segments.last().cloned())
+            .or_else(|| {
+                file.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| "Schema".to_string());
+        let mut name = base.to_owned();
+        let mut number = 1;
+        while self.names.contains(&name) {
+            number += 1;
+            name = format!("{}{}", base, number);
+        }
+        self.names.insert(name.to_owned());
+        name
+    }
+}
+
+/// How a node was reached while bundling.
+#[derive(Clone, Copy, Default)]
+struct Context {
+    /// The number of refs followed to get to it.
+    depth: usize,
+    /// Whether it is part of a schema.
+    schema: bool,
+}
+
+impl TreeSitterBundler {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self {
+            provider,
+            options: BundleOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: BundleOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    fn inline(
+        &self,
+        node: &YamlNode,
+        file: &Path,
+        pointer: &str,
+        context: Context,
+        state: &mut BundleState,
+    ) -> Result<YamlNode, OpenapiSchemerError> {
+        if let Some(reference) = node.reference() {
+            return self.inline_ref(node, reference, file, pointer, context, state);
+        }
+        let value = match &node.value {
+            YamlValue::Mapping(pairs) => {
+                let mut inlined = vec![];
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    let child_pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+                    let child_context = Context {
+                        schema: context.schema
+                            || key == "schema"
+                            || pointer == "/components/schemas",
+                        ..context
+                    };
+                    inlined.push(YamlPair {
+                        key: pair.key.to_owned(),
+                        value: self.inline(
+                            &pair.value,
+                            file,
+                            &child_pointer,
+                            child_context,
+                            state,
+                        )?,
+                    });
+                }
+                YamlValue::Mapping(inlined)
+            }
+            YamlValue::Sequence(items) => {
+                let mut inlined = vec![];
+                for (index, item) in items.iter().enumerate() {
+                    let child_pointer = format!("{}/{}", pointer, index);
+                    inlined.push(self.inline(item, file, &child_pointer, context, state)?);
+                }
+                YamlValue::Sequence(inlined)
+            }
+            other => other.to_owned(),
+        };
+        Ok(YamlNode {
+            value,
+            ..node.to_owned()
+        })
+    }
+
+    fn inline_ref(
+        &self,
+        node: &YamlNode,
+        reference: &str,
+        file: &Path,
+        pointer: &str,
+        context: Context,
+        state: &mut BundleState,
+    ) -> Result<YamlNode, OpenapiSchemerError> {
+        let (target_file, fragment) = split_ref(file, reference);
+        let target = format!("{}#{}", target_file.display(), fragment);
+        let external = target_file != Path::new("#");
+        let nested = Context {
+            depth: context.depth + 1,
+            ..context
+        };
+
+        if self.options.rewrite_refs == RefRewrite::Componentized && external && context.schema {
+            let location = match state.location(&target_file, fragment.as_str()) {
+                // A schema listed under `components/schemas` by its ref is
+                // written in place of the ref.
+                Some(location) if location == pointer => location,
+                Some(location) => return Ok(rewritten_ref(node, &location)),
+                None => {
+                    let name = state.schema_name(&target_file, &fragment);
+                    let location = format!("/components/schemas/{}", escape_pointer_segment(&name));
+                    state.inlined.insert(target, location.to_owned());
+                    // The slot is taken before the schema is inlined so the
+                    // schemas it refers to are listed after it.
+                    state.hoisted.push((name, YamlNode::null()));
+                    let index = state.hoisted.len() - 1;
+                    state.hoisted[index].1 =
+                        self.inline_target(file, reference, &location, nested, state)?;
+                    return Ok(rewritten_ref(node, &location));
+                }
+            };
+            return self.inline_target(file, reference, &location, nested, state);
+        }
+
+        let internal = target_file == file;
+        let copy = match self.options.max_depth {
+            Some(max_depth) => {
+                context.depth < max_depth && !(internal && self.options.keep_internal_refs)
+            }
+            None => false,
+        };
+        if copy {
+            if external {
+                state.inlined.entry(target).or_insert(pointer.to_string());
+            }
+            return self.inline_target(file, reference, pointer, nested, state);
+        }
+
+        // Any target that has already been written into the output, including
+        // one that is still being inlined, is referenced by its new location.
+        if let Some(location) = state.location(&target_file, &fragment) {
+            return Ok(rewritten_ref(node, &location));
+        }
+        state.inlined.insert(target, pointer.to_string());
+        self.inline_target(file, reference, pointer, nested, state)
+    }
+
+    /// Inlines the target of `reference` at `pointer`, noting the file and
+    /// line it came from with `provenance`.
+    fn inline_target(
+        &self,
+        file: &Path,
+        reference: &str,
+        pointer: &str,
+        context: Context,
+        state: &mut BundleState,
+    ) -> Result<YamlNode, OpenapiSchemerError> {
+        let (target, target_file) =
+            state
+                .resolver
+                .resolve_ref(file, reference)
+                .map_err(|error| {
+                    OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Bundle)
+                })?;
+        let mut inlined = self.inline(&target, &target_file, pointer, context, state)?;
+        if self.options.provenance {
+            inlined.comment = Some(format!("from: {}", self.origin(&target_file, &target)));
+        }
+        Ok(inlined)
+    }
+
+    /// Where `node` of `file` starts, as `resources/pets.yaml:12` with the
+    /// path relative to the root document.
+    fn origin(&self, file: &Path, node: &YamlNode) -> String {
+        let source = self.provider.source_path(file);
+        let root = self.provider.source_path(Path::new("#"));
+        let path = root
+            .parent()
+            .and_then(|directory| source.strip_prefix(directory).ok())
+            .unwrap_or(&source);
+        format!("{}:{}", path.display(), node.start.row + 1)
+    }
+
+    /// Claims the names under the root's `components/schemas`, and with
+    /// `Componentized` the targets of the refs listed there, so schemas
+    /// moved there don't clash with them.
+    fn reserve_schemas(&self, root: &YamlNode, state: &mut BundleState) {
+        let schemas = root
+            .get("components")
+            .and_then(|components| components.get("schemas"))
+            .and_then(|schemas| schemas.as_mapping())
+            .unwrap_or_default();
+        for pair in schemas {
+            let name = pair.key.as_str().unwrap_or_default();
+            state.names.insert(name.to_string());
+            if let Some(reference) = pair.value.reference() {
+                let (target_file, fragment) = split_ref(Path::new("#"), reference);
+                if target_file != Path::new("#") {
+                    let target = format!("{}#{}", target_file.display(), fragment);
+                    let location = format!("/components/schemas/{}", escape_pointer_segment(name));
+                    state.inlined.entry(target).or_insert(location);
+                }
+            }
+        }
+    }
+}
+
+/// Appends `schemas` to the `components/schemas` of `document`, adding the
+/// keys on the way there when they are missing.
+pub fn add_schemas(document: &mut YamlNode, schemas: Vec<(String, YamlNode)>) {
+    let mut node = document;
+    for key in ["components", "schemas"] {
+        if node.is_null() {
+            *node = YamlNode::mapping(vec![]);
+        }
+        let pairs = match &mut node.value {
+            YamlValue::Mapping(pairs) => pairs,
+            _ => return,
+        };
+        let index = match pairs.iter().position(|pair| pair.key.as_str() == Some(key)) {
+            Some(index) => index,
+            None => {
+                pairs.push(YamlPair {
+                    key: YamlNode::string(key),
+                    value: YamlNode::mapping(vec![]),
+                });
+                pairs.len() - 1
+            }
+        };
+        node = &mut pairs[index].value;
+    }
+    if node.is_null() {
+        *node = YamlNode::mapping(vec![]);
+    }
+    if let YamlValue::Mapping(pairs) = &mut node.value {
+        pairs.extend(schemas.into_iter().map(|(name, schema)| YamlPair {
+            key: YamlNode::string(&name),
+            value: schema,
+        }));
+    }
+}
+
+fn rewritten_ref(node: &YamlNode, location: &str) -> YamlNode {
+    let reference = format!("#{}", location);
+    if node.reference() == Some(reference.as_str()) {
+        return node.to_owned();
+    }
+    let mut rewritten = YamlNode::mapping(vec![("$ref", YamlNode::string(&reference))]);
+    rewritten.start = node.start;
+    rewritten.bytes = node.bytes.to_owned();
+    rewritten
+}
+
+impl Bundler for TreeSitterBundler {
+    fn bundle(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        let root_path = PathBuf::from("#");
+        let mut state = BundleState {
+            resolver: Resolver::new(self.provider.as_ref()),
+            inlined: HashMap::new(),
+            hoisted: vec![],
+            names: HashSet::new(),
+        };
+        let root = state
+            .resolver
+            .document(&root_path)
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Bundle))?
+            .to_owned();
+        // The root document keeps its internal refs as they are.
+        state.inlined.insert(String::from("##"), String::new());
+        if self.options.rewrite_refs == RefRewrite::Componentized {
+            self.reserve_schemas(&root, &mut state);
+        }
+        let mut bundled = self.inline(&root, &root_path, "", Context::default(), &mut state)?;
+        if !state.hoisted.is_empty() {
+            add_schemas(&mut bundled, state.hoisted);
+        }
+        Ok(bundled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::bundle::{BundleOptions, Bundler, RefRewrite, TreeSitterBundler},
+        content::ContentProviderMap,
+    };
+
+    fn bundle(contents: Vec<(&str, &str)>) -> String {
+        bundle_with(contents, BundleOptions::default())
+    }
+
+    fn bundle_with(contents: Vec<(&str, &str)>, options: BundleOptions) -> String {
+        let contents = contents
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_owned()))
+            .collect::<HashMap<PathBuf, String>>();
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let bundler = TreeSitterBundler::new(provider).with_options(options);
+        bundler.bundle().unwrap().to_yaml()
+    }
+
+    fn refs_content() -> Vec<(&'static str, &'static str)> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: 'schemas/Pet.yaml'
+        default:
+          $ref: '#/components/responses/Error'
+components:
+  schemas:
+    Tag:
+      type: string
+    Owner:
+      $ref: 'schemas/Owner.yaml'
+  responses:
+    Error:
+      description: Failed
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  tag:
+    $ref: 'Tag.yaml'
+  owner:
+    $ref: 'Owner.yaml'
+  parent:
+    $ref: '#'
+"#;
+        vec![
+            ("#", root_content),
+            ("schemas/Pet.yaml", pet_content),
+            ("schemas/Tag.yaml", "type: string\n"),
+            ("schemas/Owner.yaml", "type: object\n"),
+        ]
+    }
+
+    #[test]
+    fn bundle_no_refs() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          $ref: '#/components/responses/Pets'
+"#;
+        assert_eq!(bundle(vec![("#", root_content)]), root_content);
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_nested_external_refs() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+"#;
+        let pets_content = r#"
+get:
+  operationId: listPets
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas/Pet.yaml'
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  name:
+    type: string
+"#;
+        let expected = r#"paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  name:
+                    type: string
+"#;
+        let bundled = bundle(vec![
+            ("#", root_content),
+            ("resources/pets.yaml", pets_content),
+            ("schemas/Pet.yaml", pet_content),
+        ]);
+        assert_eq!(bundled, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_rewrites_pointers() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          $ref: 'responses.yaml#/Pets'
+        default:
+          $ref: 'responses.yaml#/Pets'
+components:
+  schemas:
+    Pet:
+      $ref: 'Pet.yaml'
+"#;
+        let responses_content = r#"
+Pets:
+  description: A list of pets
+  content:
+    application/json:
+      schema:
+        items:
+          $ref: 'Pet.yaml'
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  children:
+    type: array
+    items:
+      $ref: '#'
+"#;
+        let expected = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: A list of pets
+          content:
+            application/json:
+              schema:
+                items:
+                  type: object
+                  properties:
+                    children:
+                      type: array
+                      items:
+                        $ref: '#/paths/~1pets/get/responses/200/content/application~1json/schema/items'
+        default:
+          $ref: '#/paths/~1pets/get/responses/200'
+components:
+  schemas:
+    Pet:
+      $ref: '#/paths/~1pets/get/responses/200/content/application~1json/schema/items'
+"#;
+        let bundled = bundle(vec![
+            ("#", root_content),
+            ("responses.yaml", responses_content),
+            ("Pet.yaml", pet_content),
+        ]);
+        assert_eq!(bundled, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_componentized() -> Result<(), Box<dyn Error>> {
+        let options = BundleOptions {
+            rewrite_refs: RefRewrite::Componentized,
+            ..BundleOptions::default()
+        };
+        let expected = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+        default:
+          $ref: '#/components/responses/Error'
+components:
+  schemas:
+    Tag:
+      type: string
+    Owner:
+      type: object
+    Pet:
+      type: object
+      properties:
+        tag:
+          $ref: '#/components/schemas/Tag2'
+        owner:
+          $ref: '#/components/schemas/Owner'
+        parent:
+          $ref: '#/components/schemas/Pet'
+    Tag2:
+      type: string
+  responses:
+    Error:
+      description: Failed
+"#;
+        assert_eq!(bundle_with(refs_content(), options), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_max_depth() -> Result<(), Box<dyn Error>> {
+        let options = BundleOptions {
+            max_depth: Some(1),
+            ..BundleOptions::default()
+        };
+        let expected = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  tag:
+                    type: string
+                  owner:
+                    type: object
+                  parent:
+                    $ref: '#/paths/~1pets/get/responses/200/content/application~1json/schema'
+        default:
+          description: Failed
+components:
+  schemas:
+    Tag:
+      type: string
+    Owner:
+      type: object
+  responses:
+    Error:
+      description: Failed
+"#;
+        assert_eq!(bundle_with(refs_content(), options), expected);
+
+        let options = BundleOptions {
+            max_depth: Some(1),
+            keep_internal_refs: true,
+            ..BundleOptions::default()
+        };
+        let bundled = bundle_with(refs_content(), options);
+        assert!(
+            bundled.contains("        default:\n          $ref: '#/components/responses/Error'\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bundle_provenance() -> Result<(), Box<dyn Error>> {
+        let options = BundleOptions {
+            provenance: true,
+            ..BundleOptions::default()
+        };
+        let expected = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema: # from: schemas/Pet.yaml:2
+                type: object
+                properties:
+                  tag: # from: schemas/Tag.yaml:1
+                    type: string
+                  owner: # from: schemas/Owner.yaml:1
+                    type: object
+                  parent:
+                    $ref: '#/paths/~1pets/get/responses/200/content/application~1json/schema'
+        default:
+          $ref: '#/components/responses/Error'
+components:
+  schemas:
+    Tag:
+      type: string
+    Owner:
+      $ref: '#/paths/~1pets/get/responses/200/content/application~1json/schema/properties/owner'
+  responses:
+    Error:
+      description: Failed
+"#;
+        assert_eq!(bundle_with(refs_content(), options), expected);
+        Ok(())
+    }
+}
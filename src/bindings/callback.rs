@@ -0,0 +1,211 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, validate::HTTP_METHODS};
+
+/// One operation of a callback declared under an operation's `callbacks`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallbackNode {
+    /// The name of the callback, such as `onData`.
+    pub text: String,
+    /// The runtime expression the callback is sent to, such as
+    /// `{$request.query.callbackUrl}/data`.
+    pub expression: String,
+    pub method: String,
+    pub operation_id: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait CallbackParser {
+    /// The callbacks of the operation `operation_id`, or `None` when there is
+    /// no such operation.
+    fn get_callback_nodes(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<Vec<CallbackNode>>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterCallbackParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterCallbackParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn callback_nodes(&self, operation_id: &str) -> Result<Option<Vec<CallbackNode>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let operation = resolver.operations()?.into_iter().find(|operation| {
+            operation
+                .operation
+                .get("operationId")
+                .and_then(|id| id.as_str())
+                == Some(operation_id)
+        });
+        let operation = match operation {
+            Some(operation) => operation,
+            None => return Ok(None),
+        };
+        let (callbacks, callbacks_file) = match operation.operation.get("callbacks") {
+            Some(callbacks) => resolver.follow(callbacks, &operation.file)?,
+            None => return Ok(Some(vec![])),
+        };
+        let mut results = vec![];
+        for callback in callbacks.as_mapping().unwrap_or_default() {
+            let name = callback.key.as_str().unwrap_or_default();
+            let (callback, callback_file) = resolver.follow(&callback.value, &callbacks_file)?;
+            for expression in callback.as_mapping().unwrap_or_default() {
+                let (path_item, path_item_file) =
+                    resolver.follow(&expression.value, &callback_file)?;
+                for pair in path_item.as_mapping().unwrap_or_default() {
+                    let method = match pair.key.as_str() {
+                        Some(method) if HTTP_METHODS.contains(&method) => method,
+                        _ => continue,
+                    };
+                    let (nested, _) = resolver.follow(&pair.value, &path_item_file)?;
+                    results.push(CallbackNode {
+                        text: name.to_string(),
+                        expression: expression.key.as_str().unwrap_or_default().to_string(),
+                        method: method.to_string(),
+                        operation_id: nested
+                            .get("operationId")
+                            .and_then(|id| id.as_str())
+                            .map(str::to_owned),
+                        file: self.provider.source_path(&path_item_file),
+                        line: pair.key.start.row + 1,
+                        column: pair.key.start.column + 1,
+                    });
+                }
+            }
+        }
+        Ok(Some(results))
+    }
+}
+
+impl CallbackParser for TreeSitterCallbackParser {
+    fn get_callback_nodes(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<Vec<CallbackNode>>, OpenapiSchemerError> {
+        self.callback_nodes(operation_id).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationCallbacks)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::callback::{CallbackNode, CallbackParser, TreeSitterCallbackParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_callback_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths:
+  /subscriptions:
+    post:
+      operationId: subscribe
+      callbacks:
+        onData:
+          '{$request.query.callbackUrl}/data':
+            post:
+              operationId: onDataCallback
+              responses:
+                '202':
+                  description: Accepted
+            delete:
+              responses:
+                '204':
+                  description: Removed
+        onDone:
+          $ref: '#/components/callbacks/Done'
+      responses:
+        '201':
+          description: Subscribed
+    get:
+      operationId: listSubscriptions
+      responses:
+        '200':
+          description: OK
+components:
+  callbacks:
+    Done:
+      '{$request.body#/doneUrl}':
+        $ref: 'callbacks.yaml#/Done'
+"#;
+        let callbacks_content = r#"
+Done:
+  put:
+    operationId: onDoneCallback
+    responses:
+      '200':
+        description: OK
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("callbacks.yaml"),
+                callbacks_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterCallbackParser::new(provider);
+        let callback =
+            |text: &str, expression: &str, method: &str, id: Option<&str>, file, line| {
+                CallbackNode {
+                    text: text.to_string(),
+                    expression: expression.to_string(),
+                    method: method.to_string(),
+                    operation_id: id.map(str::to_owned),
+                    file: PathBuf::from(file),
+                    line,
+                    column: if file == "#" { 13 } else { 3 },
+                }
+            };
+        assert_eq!(
+            parser.get_callback_nodes("subscribe")?,
+            Some(vec![
+                callback(
+                    "onData",
+                    "{$request.query.callbackUrl}/data",
+                    "post",
+                    Some("onDataCallback"),
+                    "#",
+                    10
+                ),
+                callback(
+                    "onData",
+                    "{$request.query.callbackUrl}/data",
+                    "delete",
+                    None,
+                    "#",
+                    15
+                ),
+                callback(
+                    "onDone",
+                    "{$request.body#/doneUrl}",
+                    "put",
+                    Some("onDoneCallback"),
+                    "callbacks.yaml",
+                    3
+                ),
+            ])
+        );
+        assert_eq!(
+            parser.get_callback_nodes("listSubscriptions")?,
+            Some(vec![])
+        );
+        assert_eq!(parser.get_callback_nodes("missing")?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode, OperationNode};
+
+/// The media types a request body or response can be sent as.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BodyNode {
+    /// `request` for the request body, otherwise the status code of the
+    /// response such as `200` or `default`.
+    pub name: String,
+    pub media_types: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ContentTypeNode {
+    pub operation: OperationNode,
+    /// The request body and responses that have a `content`, in the order
+    /// they are written.
+    pub bodies: Vec<BodyNode>,
+}
+
+pub trait ContentTypeParser {
+    /// Every operation with the media types of its request body and
+    /// responses.
+    fn get_content_type_nodes(&self) -> Result<Vec<ContentTypeNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterContentTypeParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterContentTypeParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn content_type_nodes(&self) -> Result<Vec<ContentTypeNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for operation in resolver.operations()? {
+            let mut bodies = vec![];
+            if let Some(request_body) = operation.operation.get("requestBody") {
+                let body = body_node(&mut resolver, "request", request_body, &operation.file)?;
+                bodies.extend(body);
+            }
+            if let Some(responses) = operation.operation.get("responses") {
+                let (responses, responses_file) = resolver.follow(responses, &operation.file)?;
+                for pair in responses.as_mapping().unwrap_or_default() {
+                    let code = pair.key.as_str().unwrap_or_default();
+                    bodies.extend(body_node(
+                        &mut resolver,
+                        code,
+                        &pair.value,
+                        &responses_file,
+                    )?);
+                }
+            }
+            results.push(ContentTypeNode {
+                operation: operation.node(self.provider.as_ref()),
+                bodies,
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// The media types under the `content` of `body`, or `None` when it has no
+/// content such as a `204` response.
+fn body_node(
+    resolver: &mut Resolver,
+    name: &str,
+    body: &YamlNode,
+    file: &Path,
+) -> Result<Option<BodyNode>> {
+    let (body, body_file) = resolver.follow(body, file)?;
+    let content = match body.get("content") {
+        Some(content) => resolver.follow(content, &body_file)?.0,
+        None => return Ok(None),
+    };
+    let media_types: Vec<String> = content
+        .as_mapping()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pair| pair.key.as_str().map(str::to_owned))
+        .collect();
+    if media_types.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(BodyNode {
+        name: name.to_string(),
+        media_types,
+    }))
+}
+
+impl ContentTypeParser for TreeSitterContentTypeParser {
+    fn get_content_type_nodes(&self) -> Result<Vec<ContentTypeNode>, OpenapiSchemerError> {
+        self.content_type_nodes().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationContentTypes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::content_type::{BodyNode, ContentTypeParser, TreeSitterContentTypeParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_content_type_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets:
+    post:
+      operationId: createPets
+      requestBody:
+        $ref: '#/components/requestBodies/Pet'
+      responses:
+        '201':
+          description: Created
+          content:
+            application/json: {}
+        '204':
+          description: Nothing
+        default:
+          $ref: 'responses.yaml#/Error'
+    delete:
+      responses:
+        '204':
+          description: Deleted
+components:
+  requestBodies:
+    Pet:
+      content:
+        application/json: {}
+        application/xml: {}
+"##;
+        let responses_content = r#"Error:
+  description: Unexpected error
+  content:
+    application/problem+json: {}
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterContentTypeParser::new(provider);
+        let nodes = parser.get_content_type_nodes()?;
+        let body = |name: &str, media_types: &[&str]| BodyNode {
+            name: name.to_string(),
+            media_types: media_types
+                .iter()
+                .map(|media_type| media_type.to_string())
+                .collect(),
+        };
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].operation.text, "createPets");
+        assert_eq!(
+            nodes[0].bodies,
+            vec![
+                body("request", &["application/json", "application/xml"]),
+                body("201", &["application/json"]),
+                body("default", &["application/problem+json"]),
+            ]
+        );
+        assert_eq!(nodes[1].operation.describe(), "DELETE /pets");
+        assert_eq!(nodes[1].bodies, vec![]);
+        Ok(())
+    }
+}
@@ -0,0 +1,670 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    schema_check::check_instance_at,
+    validate::HTTP_METHODS,
+    yaml::{Scalar, ScalarStyle, YamlNode, YamlPair, YamlValue},
+};
+
+/// A request a client sent to an operation.
+#[derive(Clone, Debug, Default)]
+pub struct RequestSample {
+    /// The path, query, header and cookie parameters by name.
+    pub params: Vec<(String, String)>,
+    /// The body, parsed from JSON or YAML.
+    pub body: Option<YamlNode>,
+    /// The media type of the body. Without one the body is checked against
+    /// the `application/json` content of the operation, or its first.
+    pub content_type: Option<String>,
+}
+
+/// A response an operation answered with.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseSample {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<YamlNode>,
+    pub content_type: Option<String>,
+}
+
+/// Something in a request or response that the operation doesn't allow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractProblem {
+    pub message: String,
+    /// The line and column in the body, for problems with the body.
+    pub position: Option<(usize, usize)>,
+}
+
+impl ContractProblem {
+    fn new(message: String) -> Self {
+        ContractProblem {
+            message,
+            position: None,
+        }
+    }
+}
+
+/// The checks take the operation by its operationId, or by its method and
+/// path such as `GET /pets/{petId}`.
+pub trait ContractChecker {
+    /// What `request` gets wrong about the operation with `operation_id`, or
+    /// `None` when there is no such operation.
+    fn check_request(
+        &self,
+        operation_id: &str,
+        request: &RequestSample,
+    ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError>;
+
+    /// What `response` gets wrong about the operation with `operation_id`,
+    /// or `None` when there is no such operation.
+    fn check_response(
+        &self,
+        operation_id: &str,
+        response: &ResponseSample,
+    ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError>;
+}
+
+/// An operation with the parameters it takes, its own and those of its path
+/// item, each with the file it is defined in.
+struct FoundOperation {
+    operation: YamlNode,
+    file: PathBuf,
+    parameters: Vec<(YamlNode, PathBuf)>,
+}
+
+pub struct TreeSitterContractChecker {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterContractChecker {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn parameters(
+        resolver: &mut Resolver,
+        parameters: Option<&YamlNode>,
+        file: &Path,
+    ) -> Result<Vec<(YamlNode, PathBuf)>> {
+        let mut results = vec![];
+        for parameter in parameters
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default()
+        {
+            results.push(resolver.follow(parameter, file)?);
+        }
+        Ok(results)
+    }
+
+    fn find_operation(
+        resolver: &mut Resolver,
+        operation_id: &str,
+    ) -> Result<Option<FoundOperation>> {
+        for path_item in resolver.entries("/paths")? {
+            for pair in path_item.value.as_mapping().unwrap_or_default() {
+                let method = match pair.key.as_str() {
+                    Some(method) if HTTP_METHODS.contains(&method) => method,
+                    _ => continue,
+                };
+                let (operation, file) = resolver.follow(&pair.value, &path_item.file)?;
+                let described = format!("{} {}", method.to_uppercase(), path_item.name);
+                if operation.get("operationId").and_then(YamlNode::as_str) != Some(operation_id)
+                    && described != operation_id
+                {
+                    continue;
+                }
+                let mut parameters =
+                    Self::parameters(resolver, operation.get("parameters"), &file)?;
+                let shared =
+                    Self::parameters(resolver, path_item.value.get("parameters"), &path_item.file)?;
+                for (parameter, parameter_file) in shared {
+                    let overridden = parameters.iter().any(|(own, _)| {
+                        text(own, "name") == text(&parameter, "name")
+                            && text(own, "in") == text(&parameter, "in")
+                    });
+                    if !overridden {
+                        parameters.push((parameter, parameter_file));
+                    }
+                }
+                return Ok(Some(FoundOperation {
+                    operation,
+                    file,
+                    parameters,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn request_problems(
+        &self,
+        operation_id: &str,
+        request: &RequestSample,
+    ) -> Result<Option<Vec<ContractProblem>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let found = match Self::find_operation(&mut resolver, operation_id)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let mut problems = vec![];
+        for (name, _) in &request.params {
+            if !found
+                .parameters
+                .iter()
+                .any(|(parameter, _)| is_named(parameter, name))
+            {
+                problems.push(ContractProblem::new(format!(
+                    "No parameter `{}` is declared for `{}`",
+                    name, operation_id
+                )));
+            }
+        }
+        for (parameter, file) in &found.parameters {
+            let name = text(parameter, "name");
+            let values: Vec<&str> = request
+                .params
+                .iter()
+                .filter(|(given, _)| is_named(parameter, given))
+                .map(|(_, value)| value.as_str())
+                .collect();
+            // Path parameters are part of the URL the request was sent to,
+            // so they are only checked when given.
+            let location = text(parameter, "in");
+            if values.is_empty() {
+                if text(parameter, "required") == "true" && location != "path" {
+                    problems.push(ContractProblem::new(format!(
+                        "The required {} parameter `{}` is missing",
+                        location, name
+                    )));
+                }
+                continue;
+            }
+            if let Some(schema) = parameter.get("schema") {
+                problems.extend(check_values(&mut resolver, schema, file, &values, name)?);
+            }
+        }
+        let body = match found.operation.get("requestBody") {
+            Some(body) => Some(resolver.follow(body, &found.file)?),
+            None => None,
+        };
+        match (body, &request.body) {
+            (None, Some(_)) => problems.push(ContractProblem::new(format!(
+                "`{}` takes no request body",
+                operation_id
+            ))),
+            (Some((body, _)), None) => {
+                if text(&body, "required") == "true" {
+                    problems.push(ContractProblem::new(
+                        "The request body is required".to_string(),
+                    ));
+                }
+            }
+            (Some((body, file)), Some(instance)) => problems.extend(check_content(
+                &mut resolver,
+                body.get("content"),
+                &file,
+                instance,
+                request.content_type.as_deref(),
+            )?),
+            (None, None) => {}
+        }
+        Ok(Some(problems))
+    }
+
+    fn response_problems(
+        &self,
+        operation_id: &str,
+        response: &ResponseSample,
+    ) -> Result<Option<Vec<ContractProblem>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let found = match Self::find_operation(&mut resolver, operation_id)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let (responses, responses_file) = match found.operation.get("responses") {
+            Some(responses) => resolver.follow(responses, &found.file)?,
+            None => (YamlNode::mapping(vec![]), found.file.to_owned()),
+        };
+        let status = response.status.to_string();
+        let range = format!("{}XX", &status[..1]);
+        let pairs = responses.as_mapping().unwrap_or_default();
+        let find = |key: &str| {
+            pairs.iter().find(|pair| {
+                pair.key
+                    .as_str()
+                    .is_some_and(|code| code.eq_ignore_ascii_case(key))
+            })
+        };
+        let documented = match find(&status)
+            .or_else(|| find(&range))
+            .or_else(|| find("default"))
+        {
+            Some(documented) => documented,
+            None => {
+                return Ok(Some(vec![ContractProblem::new(format!(
+                    "Status {} is not documented for `{}`",
+                    status, operation_id
+                ))]))
+            }
+        };
+        let (documented, file) = resolver.follow(&documented.value, &responses_file)?;
+        let mut problems = vec![];
+        let headers = match documented.get("headers") {
+            Some(headers) => resolver.follow(headers, &file)?,
+            None => (YamlNode::mapping(vec![]), file.to_owned()),
+        };
+        for pair in headers.0.as_mapping().unwrap_or_default() {
+            let name = pair.key.as_str().unwrap_or_default();
+            let (header, header_file) = resolver.follow(&pair.value, &headers.1)?;
+            let values: Vec<&str> = response
+                .headers
+                .iter()
+                .filter(|(given, _)| given.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+                .collect();
+            if values.is_empty() {
+                if text(&header, "required") == "true" {
+                    problems.push(ContractProblem::new(format!(
+                        "The required header `{}` is missing",
+                        name
+                    )));
+                }
+                continue;
+            }
+            if let Some(schema) = header.get("schema") {
+                problems.extend(check_values(
+                    &mut resolver,
+                    schema,
+                    &header_file,
+                    &values,
+                    name,
+                )?);
+            }
+        }
+        if let Some(instance) = &response.body {
+            problems.extend(check_content(
+                &mut resolver,
+                documented.get("content"),
+                &file,
+                instance,
+                response.content_type.as_deref(),
+            )?);
+        }
+        Ok(Some(problems))
+    }
+}
+
+impl ContractChecker for TreeSitterContractChecker {
+    fn check_request(
+        &self,
+        operation_id: &str,
+        request: &RequestSample,
+    ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+        self.request_problems(operation_id, request)
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ValidateRequest)
+            })
+    }
+
+    fn check_response(
+        &self,
+        operation_id: &str,
+        response: &ResponseSample,
+    ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+        self.response_problems(operation_id, response)
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ValidateResponse)
+            })
+    }
+}
+
+fn text<'a>(node: &'a YamlNode, key: &str) -> &'a str {
+    node.get(key).and_then(YamlNode::as_str).unwrap_or_default()
+}
+
+/// Whether `name` refers to `parameter`. Header names are matched without
+/// regard to case, like HTTP does.
+fn is_named(parameter: &YamlNode, name: &str) -> bool {
+    match text(parameter, "in") {
+        "header" => text(parameter, "name").eq_ignore_ascii_case(name),
+        _ => text(parameter, "name") == name,
+    }
+}
+
+fn has_type(schema: &YamlNode, name: &str) -> bool {
+    match schema.get("type") {
+        Some(types) => match types.as_sequence() {
+            Some(types) => types.iter().any(|item| item.as_str() == Some(name)),
+            None => types.as_str() == Some(name),
+        },
+        None => false,
+    }
+}
+
+/// A parameter or header value as the node its schema expects. Values are
+/// strings on the wire, so they are only read as strings where the schema
+/// asks for one, and `integer` and the like can still be checked.
+fn value_node(text: &str, schema: &YamlNode) -> YamlNode {
+    let style = match has_type(schema, "string") {
+        true => ScalarStyle::DoubleQuoted,
+        false => ScalarStyle::Plain,
+    };
+    YamlNode::new(YamlValue::Scalar(Scalar {
+        text: text.to_string(),
+        style,
+    }))
+}
+
+/// Checks the values given for the parameter or header `name`. Arrays take
+/// every value given, or the comma separated items of a single one.
+fn check_values(
+    resolver: &mut Resolver,
+    schema: &YamlNode,
+    file: &Path,
+    values: &[&str],
+    name: &str,
+) -> Result<Vec<ContractProblem>> {
+    let (resolved, resolved_file) = resolver.follow(schema, file)?;
+    let instance = match has_type(&resolved, "array") {
+        true => {
+            let items = match resolved.get("items") {
+                Some(items) => resolver.follow(items, &resolved_file)?.0,
+                None => YamlNode::mapping(vec![]),
+            };
+            let values: Vec<&str> = match values {
+                [value] => value.split(',').collect(),
+                _ => values.to_vec(),
+            };
+            YamlNode::sequence(
+                values
+                    .iter()
+                    .map(|value| value_node(value, &items))
+                    .collect(),
+            )
+        }
+        false => value_node(values[0], &resolved),
+    };
+    Ok(check_instance_at(resolver, schema, file, &instance, name)
+        .into_iter()
+        .map(|violation| ContractProblem::new(violation.message))
+        .collect())
+}
+
+/// The media type of `content` a body of `content_type` is checked against,
+/// falling back to `type/*` and `*/*`.
+fn media_type<'a>(content: &'a [YamlPair], content_type: Option<&str>) -> Option<&'a YamlPair> {
+    let find = |key: &str| content.iter().find(|pair| pair.key.as_str() == Some(key));
+    match content_type {
+        Some(content_type) => {
+            let content_type = content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase();
+            let range = format!("{}/*", content_type.split('/').next().unwrap_or_default());
+            find(&content_type)
+                .or_else(|| find(&range))
+                .or_else(|| find("*/*"))
+        }
+        None => find("application/json").or_else(|| content.first()),
+    }
+}
+
+/// Checks a body against the schema of its media type in `content`.
+fn check_content(
+    resolver: &mut Resolver,
+    content: Option<&YamlNode>,
+    file: &Path,
+    instance: &YamlNode,
+    content_type: Option<&str>,
+) -> Result<Vec<ContractProblem>> {
+    let (content, file) = match content {
+        Some(content) => resolver.follow(content, file)?,
+        None => (YamlNode::mapping(vec![]), file.to_path_buf()),
+    };
+    let pairs = content.as_mapping().unwrap_or_default();
+    let media = match media_type(pairs, content_type) {
+        Some(media) => media,
+        None => {
+            let problem = match content_type {
+                Some(content_type) if !pairs.is_empty() => {
+                    let declared: Vec<&str> =
+                        pairs.iter().filter_map(|pair| pair.key.as_str()).collect();
+                    format!(
+                        "`{}` is not one of the media types `{}`",
+                        content_type,
+                        declared.join("`, `")
+                    )
+                }
+                _ => "No content is defined for the body".to_string(),
+            };
+            return Ok(vec![ContractProblem::new(problem)]);
+        }
+    };
+    let schema = match media.value.get("schema") {
+        Some(schema) => schema,
+        None => return Ok(vec![]),
+    };
+    Ok(check_instance_at(resolver, schema, &file, instance, "$")
+        .into_iter()
+        .map(|violation| ContractProblem {
+            message: violation.message,
+            position: Some((violation.line, violation.column)),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{
+            contract::{ContractChecker, RequestSample, ResponseSample, TreeSitterContractChecker},
+            yaml::parse_yaml,
+        },
+        content::ContentProviderMap,
+    };
+
+    const SPEC: &str = r##"openapi: 3.0.0
+paths:
+  /pets:
+    parameters:
+      - name: X-Request-Id
+        in: header
+        required: true
+        schema:
+          type: string
+    post:
+      operationId: createPet
+      parameters:
+        - name: limit
+          in: query
+          required: true
+          schema:
+            type: integer
+        - name: tags
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [cat, dog]
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        '201':
+          description: created
+          headers:
+            Location:
+              required: true
+              schema:
+                type: string
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+        4XX:
+          description: client error
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        age:
+          type: integer
+"##;
+
+    fn checker() -> TreeSitterContractChecker {
+        let contents = HashMap::from([(PathBuf::from("#"), SPEC.to_owned())]);
+        TreeSitterContractChecker::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    fn params(params: &[(&str, &str)]) -> Vec<(String, String)> {
+        params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn check_request() -> Result<(), Box<dyn Error>> {
+        let request = RequestSample {
+            params: params(&[("x-request-id", "42"), ("limit", "10"), ("tags", "cat,dog")]),
+            body: Some(parse_yaml("{\"name\": \"Rex\"}")?),
+            content_type: Some("application/json; charset=utf-8".to_string()),
+        };
+        assert_eq!(
+            checker().check_request("createPet", &request)?,
+            Some(vec![])
+        );
+
+        let request = RequestSample {
+            params: params(&[("limit", "ten"), ("tags", "bird"), ("sort", "name")]),
+            body: Some(parse_yaml("{\n  \"age\": \"two\"\n}")?),
+            content_type: None,
+        };
+        let problems: Vec<(String, Option<(usize, usize)>)> = checker()
+            .check_request("createPet", &request)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|problem| (problem.message, problem.position))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "No parameter `sort` is declared for `createPet`".to_string(),
+                    None
+                ),
+                (
+                    "`limit` should be of type `integer`, not `string`".to_string(),
+                    None
+                ),
+                ("`tags[0]` should be one of `cat`, `dog`".to_string(), None),
+                (
+                    "The required header parameter `X-Request-Id` is missing".to_string(),
+                    None
+                ),
+                (
+                    "`$` is missing the required property `name`".to_string(),
+                    Some((1, 1))
+                ),
+                (
+                    "`$.age` should be of type `integer`, not `string`".to_string(),
+                    Some((2, 10))
+                ),
+            ]
+        );
+        assert_eq!(
+            checker().check_request("deletePet", &RequestSample::default())?,
+            None
+        );
+        let messages: Vec<String> = checker()
+            .check_request("POST /pets", &RequestSample::default())?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The required query parameter `limit` is missing",
+                "The required header parameter `X-Request-Id` is missing",
+                "The request body is required"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_response() -> Result<(), Box<dyn Error>> {
+        let response = ResponseSample {
+            status: 201,
+            headers: params(&[("location", "/pets/1")]),
+            body: Some(parse_yaml("{\"name\": \"Rex\"}")?),
+            content_type: None,
+        };
+        assert_eq!(
+            checker().check_response("createPet", &response)?,
+            Some(vec![])
+        );
+
+        let response = ResponseSample {
+            status: 201,
+            headers: vec![],
+            body: Some(parse_yaml("{\"name\": \"Rex\"}")?),
+            content_type: Some("text/plain".to_string()),
+        };
+        let messages: Vec<String> = checker()
+            .check_response("createPet", &response)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The required header `Location` is missing",
+                "`text/plain` is not one of the media types `application/json`"
+            ]
+        );
+
+        let response = ResponseSample {
+            status: 404,
+            ..ResponseSample::default()
+        };
+        assert_eq!(
+            checker().check_response("createPet", &response)?,
+            Some(vec![])
+        );
+        let response = ResponseSample {
+            status: 500,
+            ..ResponseSample::default()
+        };
+        let messages: Vec<String> = checker()
+            .check_response("createPet", &response)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["Status 500 is not documented for `createPet`"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    bundle::{Bundler, TreeSitterBundler},
+    yaml::YamlNode,
+};
+
+pub trait ConvertParser {
+    /// The document to write in the other format.
+    fn get_document(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+/// Reads either the root document on its own, leaving its `$ref`s to other
+/// files as they are, or the whole spec bundled into a single document.
+pub enum TreeSitterConvertParser {
+    Root(Box<dyn ContentProvider>),
+    Bundle(TreeSitterBundler),
+}
+
+impl TreeSitterConvertParser {
+    pub fn new(provider: Box<dyn ContentProvider>, bundle: bool) -> Self {
+        match bundle {
+            true => Self::Bundle(TreeSitterBundler::new(provider)),
+            false => Self::Root(provider),
+        }
+    }
+}
+
+impl ConvertParser for TreeSitterConvertParser {
+    fn get_document(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        match self {
+            Self::Root(provider) => provider.get_document(PathBuf::from("#")).map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Convert)
+            }),
+            Self::Bundle(bundler) => bundler.bundle().map_err(|error| match error {
+                OpenapiSchemerError::Bundle(message) => OpenapiSchemerError::Convert(message),
+                error => error,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::convert::{ConvertParser, TreeSitterConvertParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser(bundle: bool) -> TreeSitterConvertParser {
+        let root_content = r#"
+openapi: 3.0.0
+components:
+  schemas:
+    Pet:
+      $ref: 'Pet.yaml'
+"#;
+        let pet_content = "type: object\n";
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("Pet.yaml"), pet_content.to_owned()),
+        ]);
+        TreeSitterConvertParser::new(Box::new(ContentProviderMap::from_map(contents)), bundle)
+    }
+
+    #[test]
+    fn get_document() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            parser(false).get_document()?.to_yaml(),
+            "openapi: 3.0.0\ncomponents:\n  schemas:\n    Pet:\n      $ref: 'Pet.yaml'\n"
+        );
+        assert_eq!(
+            parser(true).get_document()?.to_yaml(),
+            "openapi: 3.0.0\ncomponents:\n  schemas:\n    Pet:\n      type: object\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_document_aliases() -> Result<(), Box<dyn Error>> {
+        let content =
+            "openapi: 3.0.0\ncomponents:\n  schemas:\n    Pet: &pet\n      type: object\n    \
+                       Dog:\n      <<: *pet\n      title: Dog\n    Cat: *pet\n";
+        let expected =
+            "openapi: 3.0.0\ncomponents:\n  schemas:\n    Pet:\n      type: object\n    \
+                        Dog:\n      type: object\n      title: Dog\n    Cat:\n      type: object\n";
+        for bundle in [false, true] {
+            let contents = HashMap::from([(PathBuf::from("#"), content.to_owned())]);
+            let provider = Box::new(ContentProviderMap::from_map(contents));
+            let parser = TreeSitterConvertParser::new(provider, bundle);
+            assert_eq!(parser.get_document()?.to_yaml(), expected);
+        }
+        Ok(())
+    }
+}
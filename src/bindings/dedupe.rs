@@ -0,0 +1,917 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use tree_sitter::Point;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    bundle::add_schemas,
+    edit::FileEdits,
+    flatten::{is_object, pascal_case},
+    rename::KeyRenames,
+    resolve::Resolver,
+    validate::HTTP_METHODS,
+    yaml::{escape_pointer_segment, pointer_segments, YamlNode, YamlValue},
+};
+
+/// Keys that only document a schema, left out when comparing schemas.
+const DOCUMENTATION: [&str; 5] = [
+    "description",
+    "title",
+    "example",
+    "examples",
+    "externalDocs",
+];
+/// Keys whose lists are compared regardless of their order.
+const UNORDERED: [&str; 3] = ["required", "enum", "type"];
+/// Keys skipped when naming a schema after where it is written.
+const STRUCTURE: [&str; 16] = [
+    "paths",
+    "components",
+    "schemas",
+    "parameters",
+    "requestBody",
+    "responses",
+    "headers",
+    "content",
+    "schema",
+    "properties",
+    "items",
+    "additionalProperties",
+    "not",
+    "allOf",
+    "oneOf",
+    "anyOf",
+];
+
+/// A schema with an identical copy elsewhere in the root document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateSchema {
+    /// The name under `components/schemas`, for component schemas.
+    pub name: Option<String>,
+    /// The JSON pointer to the schema, such as `/components/schemas/Pet`.
+    pub pointer: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Schemas that are the same once their key order and documentation are left
+/// out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Duplicates {
+    /// The component schema the copies are consolidated into. It is created
+    /// when none of them is a component schema.
+    pub name: String,
+    pub schemas: Vec<DuplicateSchema>,
+}
+
+/// The spec once its identical schemas are consolidated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deduped {
+    /// The root document, with each group of identical schemas replaced by
+    /// refs to a single component schema.
+    pub document: YamlNode,
+    /// The edits pointing the refs in the other files of the spec at the
+    /// component schemas that are kept.
+    pub files: Vec<FileEdits>,
+}
+
+pub trait Deduplicator {
+    /// The groups of identical object schemas in the root document.
+    fn get_duplicates(&self) -> Result<Vec<Duplicates>, OpenapiSchemerError>;
+
+    /// The spec with each group of identical schemas replaced by refs to a
+    /// single component schema.
+    fn dedupe(&self) -> Result<Deduped, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterDeduplicator {
+    provider: Box<dyn ContentProvider>,
+}
+
+/// The documents of the spec that deduplicating looks at.
+struct Spec {
+    root: YamlNode,
+    /// The files other than the root document, which refs are rewritten in.
+    files: Vec<PathBuf>,
+    /// The component schemas whose names a discriminator depends on.
+    discriminated: HashSet<String>,
+}
+
+impl TreeSitterDeduplicator {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn spec(&self) -> Result<Spec> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root = resolver.document(Path::new("#"))?.to_owned();
+        // The root comes first, and is written out whole instead.
+        let files: Vec<PathBuf> = resolver.files()?.into_iter().skip(1).collect();
+        let mut discriminated = discriminated(&root);
+        for file in &files {
+            discriminated.extend(discriminated_by(resolver.document(file)?));
+        }
+        Ok(Spec {
+            root,
+            files,
+            discriminated,
+        })
+    }
+
+    fn deduped(&self) -> Result<Deduped> {
+        let spec = self.spec()?;
+        let (document, removed) = consolidate(&spec.root, &spec.discriminated);
+        let mut renames = KeyRenames::new(self.provider.as_ref());
+        for (name, kept) in removed {
+            let path = vec!["components".to_string(), "schemas".to_string(), name];
+            renames.retarget(path, &kept);
+        }
+        renames.rename_refs(&spec.files, true)?;
+        Ok(Deduped {
+            document,
+            files: renames.file_edits(&spec.files)?,
+        })
+    }
+}
+
+impl Deduplicator for TreeSitterDeduplicator {
+    fn get_duplicates(&self) -> Result<Vec<Duplicates>, OpenapiSchemerError> {
+        let spec = self.spec().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Dedupe)
+        })?;
+        let file = self.provider.source_path(Path::new("#"));
+        let duplicates = groups(&spec.root, &spec.discriminated)
+            .into_iter()
+            .map(|group| Duplicates {
+                name: group.name,
+                schemas: group
+                    .members
+                    .into_iter()
+                    .map(|member| DuplicateSchema {
+                        name: member.name,
+                        pointer: pointer(&member.segments),
+                        file: file.to_owned(),
+                        line: member.start.row + 1,
+                        column: member.start.column + 1,
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(duplicates)
+    }
+
+    fn dedupe(&self) -> Result<Deduped, OpenapiSchemerError> {
+        self.deduped()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Dedupe))
+    }
+}
+
+/// A schema compared against the others.
+struct Candidate {
+    segments: Vec<String>,
+    name: Option<String>,
+    /// The schema written out without documentation and with its keys
+    /// sorted, equal for identical schemas.
+    key: String,
+    start: Point,
+}
+
+struct Group {
+    name: String,
+    members: Vec<Candidate>,
+}
+
+/// The groups of identical object schemas in `root`, in the order their
+/// first schema is found. Groups made only of schemas nested in the
+/// duplicates of other groups are left out, they go away with those. The
+/// `discriminated` component schemas are never merged, since a discriminator
+/// tells them apart by name.
+fn groups(root: &YamlNode, discriminated: &HashSet<String>) -> Vec<Group> {
+    let mut candidates = vec![];
+    walk(root, &mut vec![], &mut candidates);
+    candidates.retain(|candidate| {
+        !candidate
+            .name
+            .as_ref()
+            .is_some_and(|name| discriminated.contains(name))
+    });
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut grouped: Vec<Vec<Candidate>> = vec![];
+    for candidate in candidates {
+        match index.get(&candidate.key) {
+            Some(&position) => grouped[position].push(candidate),
+            None => {
+                index.insert(candidate.key.to_owned(), grouped.len());
+                grouped.push(vec![candidate]);
+            }
+        }
+    }
+    grouped.retain(|members| members.len() > 1);
+
+    let duplicates: Vec<&[String]> = grouped
+        .iter()
+        .flatten()
+        .map(|member| member.segments.as_slice())
+        .collect();
+    let nested = |member: &Candidate| {
+        duplicates
+            .iter()
+            .any(|outer| outer.len() < member.segments.len() && member.segments.starts_with(outer))
+    };
+    let keep: Vec<bool> = grouped
+        .iter()
+        .map(|members| !members.iter().all(nested))
+        .collect();
+
+    let mut taken: HashSet<String> = root
+        .get("components")
+        .and_then(|components| components.get("schemas"))
+        .and_then(|schemas| schemas.as_mapping())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pair| pair.key.as_str().map(str::to_owned))
+        .collect();
+    let mut groups = vec![];
+    for (members, keep) in grouped.into_iter().zip(keep) {
+        if !keep {
+            continue;
+        }
+        let name = match members.iter().find_map(|member| member.name.to_owned()) {
+            Some(name) => name,
+            None => {
+                let base = schema_name(root, &members[0].segments);
+                let mut name = base.to_owned();
+                let mut number = 1;
+                while taken.contains(&name) {
+                    number += 1;
+                    name = format!("{}{}", base, number);
+                }
+                taken.insert(name.to_owned());
+                name
+            }
+        };
+        groups.push(Group { name, members });
+    }
+    groups
+}
+
+/// `root` with the copies in each group replaced by refs to one component
+/// schema. Component schemas that are copies are removed, and refs to them
+/// point at the one kept instead.
+pub fn dedupe_document(root: &YamlNode) -> YamlNode {
+    consolidate(root, &discriminated(root)).0
+}
+
+/// `root` deduplicated, with the names of the component schemas removed and
+/// the ones kept in their place.
+fn consolidate(
+    root: &YamlNode,
+    discriminated: &HashSet<String>,
+) -> (YamlNode, Vec<(String, String)>) {
+    let mut groups = groups(root, discriminated);
+    // Nested copies go first, so schemas moved into components already refer
+    // to the ones inside them.
+    groups.sort_by_key(|group| std::cmp::Reverse(group.members[0].segments.len()));
+    let mut document = root.to_owned();
+    let mut removed: Vec<(String, String)> = vec![];
+    let mut created = vec![];
+    for group in &groups {
+        let reference = format!(
+            "#/components/schemas/{}",
+            escape_pointer_segment(&group.name)
+        );
+        let kept = group
+            .members
+            .iter()
+            .position(|member| member.name.as_ref() == Some(&group.name));
+        if kept.is_none() {
+            if let Some(node) = node_mut(&mut document, &group.members[0].segments) {
+                created.push((group.name.to_owned(), node.to_owned()));
+            }
+        }
+        for (index, member) in group.members.iter().enumerate() {
+            if Some(index) == kept {
+                continue;
+            }
+            match &member.name {
+                Some(name) => removed.push((name.to_owned(), group.name.to_owned())),
+                None => {
+                    if let Some(node) = node_mut(&mut document, &member.segments) {
+                        let mut replaced =
+                            YamlNode::mapping(vec![("$ref", YamlNode::string(&reference))]);
+                        replaced.start = node.start;
+                        replaced.bytes = node.bytes.to_owned();
+                        *node = replaced;
+                    }
+                }
+            }
+        }
+    }
+    let schemas = document
+        .get_mut("components")
+        .and_then(|components| components.get_mut("schemas"));
+    if let Some(YamlNode {
+        value: YamlValue::Mapping(pairs),
+        ..
+    }) = schemas
+    {
+        pairs.retain(|pair| {
+            !removed
+                .iter()
+                .any(|(name, _)| pair.key.as_str() == Some(name))
+        });
+    }
+    retarget(&mut document, &removed, None);
+    if !created.is_empty() {
+        add_schemas(&mut document, created);
+    }
+    (document, removed)
+}
+
+/// The component schemas of `root` that a discriminator in it depends on:
+/// the ones it maps values to or chooses between, and the ones extending a
+/// schema with a discriminator, whose names are its values.
+fn discriminated(root: &YamlNode) -> HashSet<String> {
+    let mut names = discriminated_by(root);
+    let schemas = root
+        .get("components")
+        .and_then(|components| components.get("schemas"))
+        .and_then(|schemas| schemas.as_mapping())
+        .unwrap_or_default();
+    let bases: Vec<&str> = schemas
+        .iter()
+        .filter(|pair| pair.value.get("discriminator").is_some())
+        .filter_map(|pair| pair.key.as_str())
+        .collect();
+    for pair in schemas {
+        let extends = pair
+            .value
+            .get("allOf")
+            .and_then(|all_of| all_of.as_sequence())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|item| item.get("$ref")?.as_str())
+            .filter_map(component_schema)
+            .any(|name| bases.contains(&name.as_str()));
+        if let (true, Some(name)) = (extends, pair.key.as_str()) {
+            names.insert(name.to_string());
+        }
+    }
+    names
+}
+
+/// The component schemas the discriminators below `node` map values to or
+/// choose between with `oneOf` and `anyOf`.
+fn discriminated_by(node: &YamlNode) -> HashSet<String> {
+    let mut names = HashSet::new();
+    if let Some(discriminator) = node.get("discriminator") {
+        let mapped = discriminator
+            .get("mapping")
+            .and_then(|mapping| mapping.as_mapping())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|pair| pair.value.as_str())
+            // A mapping value without `#` names the schema instead.
+            .map(|value| match value.contains('#') {
+                true => component_schema(value),
+                false => Some(value.to_string()),
+            });
+        let chosen = ["oneOf", "anyOf"]
+            .iter()
+            .filter_map(|key| node.get(key)?.as_sequence())
+            .flatten()
+            .filter_map(|item| item.get("$ref")?.as_str())
+            .map(component_schema);
+        names.extend(mapped.chain(chosen).flatten());
+    }
+    let children: Vec<&YamlNode> = match &node.value {
+        YamlValue::Mapping(pairs) => pairs.iter().map(|pair| &pair.value).collect(),
+        YamlValue::Sequence(items) => items.iter().collect(),
+        _ => vec![],
+    };
+    for child in children {
+        names.extend(discriminated_by(child));
+    }
+    names
+}
+
+/// The name of the component schema `reference` points at, in any file.
+fn component_schema(reference: &str) -> Option<String> {
+    let (_, fragment) = reference.split_once('#')?;
+    match pointer_segments(fragment)?.as_slice() {
+        [components, schemas, name] if components == "components" && schemas == "schemas" => {
+            Some(name.to_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Collects the object schemas below `node`, which is at `segments` and is
+/// not a schema itself.
+fn walk(node: &YamlNode, segments: &mut Vec<String>, found: &mut Vec<Candidate>) {
+    let component_schemas = *segments == ["components", "schemas"];
+    match &node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                let key = match pair.key.as_str() {
+                    Some("example" | "examples") | None => continue,
+                    Some(key) => key,
+                };
+                segments.push(key.to_string());
+                match key {
+                    _ if component_schemas => schema(&pair.value, Some(key), segments, found),
+                    "schema" => schema(&pair.value, None, segments, found),
+                    _ => walk(&pair.value, segments, found),
+                }
+                segments.pop();
+            }
+        }
+        YamlValue::Sequence(items) => {
+            for (index, item) in items.iter().enumerate() {
+                segments.push(index.to_string());
+                walk(item, segments, found);
+                segments.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects `node`, a schema at `segments`, and the schemas nested in it.
+fn schema(
+    node: &YamlNode,
+    name: Option<&str>,
+    segments: &mut Vec<String>,
+    found: &mut Vec<Candidate>,
+) {
+    if is_object(node) {
+        found.push(Candidate {
+            segments: segments.to_owned(),
+            name: name.map(str::to_owned),
+            key: canonical(node),
+            start: node.start,
+        });
+    }
+    for pair in node.as_mapping().unwrap_or_default() {
+        let key = pair.key.as_str().unwrap_or_default();
+        segments.push(key.to_string());
+        match (key, &pair.value.value) {
+            ("properties" | "patternProperties", YamlValue::Mapping(properties)) => {
+                for property in properties {
+                    segments.push(property.key.as_str().unwrap_or_default().to_string());
+                    schema(&property.value, None, segments, found);
+                    segments.pop();
+                }
+            }
+            ("allOf" | "oneOf" | "anyOf", YamlValue::Sequence(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    segments.push(index.to_string());
+                    schema(item, None, segments, found);
+                    segments.pop();
+                }
+            }
+            ("items" | "additionalProperties" | "not", _) => {
+                schema(&pair.value, None, segments, found)
+            }
+            _ => {}
+        }
+        segments.pop();
+    }
+}
+
+/// `node` written out with its mapping keys sorted and without the keys that
+/// only document it, so identical schemas give the same text.
+fn canonical(node: &YamlNode) -> String {
+    match &node.value {
+        YamlValue::Mapping(pairs) => {
+            let mut entries: Vec<String> = pairs
+                .iter()
+                .filter(|pair| !DOCUMENTATION.contains(&pair.key.as_str().unwrap_or_default()))
+                .map(|pair| {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    let value = match &pair.value.value {
+                        YamlValue::Sequence(items) if UNORDERED.contains(&key) => {
+                            let mut items: Vec<String> = items.iter().map(canonical).collect();
+                            items.sort();
+                            format!("[{}]", items.join(","))
+                        }
+                        _ => canonical(&pair.value),
+                    };
+                    format!("{:?}:{}", key, value)
+                })
+                .collect();
+            entries.sort();
+            format!("{{{}}}", entries.join(","))
+        }
+        YamlValue::Sequence(items) => {
+            let items: Vec<String> = items.iter().map(canonical).collect();
+            format!("[{}]", items.join(","))
+        }
+        YamlValue::Scalar(scalar) => format!("{:?}", scalar.text),
+        YamlValue::Alias(alias) => format!("*{}", alias),
+    }
+}
+
+/// A name for a schema written at `segments` of `root`, after the property
+/// or parameter it is written in, such as `Address`.
+fn schema_name(root: &YamlNode, segments: &[String]) -> String {
+    let name = (0..segments.len()).rev().find_map(|index| {
+        let segment = segments[index].as_str();
+        if index > 0 && segments[index - 1] == "parameters" {
+            let parameter = root.pointer(&pointer(&segments[..=index]))?;
+            return parameter.get("name")?.as_str().map(str::to_owned);
+        }
+        let structural = STRUCTURE.contains(&segment)
+            || HTTP_METHODS.contains(&segment)
+            || segment.parse::<usize>().is_ok()
+            || segment.contains('/')
+            || segment == "default";
+        (!structural).then(|| segment.to_string())
+    });
+    match name.map(|name| pascal_case(&name)) {
+        Some(name) if !name.is_empty() => name,
+        _ => "Schema".to_string(),
+    }
+}
+
+/// Points the refs below `node`, the value of the key `parent`, at the kept
+/// schema when they point at a removed one.
+fn retarget(node: &mut YamlNode, removed: &[(String, String)], parent: Option<&str>) {
+    match &mut node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                let key = pair.key.as_str().unwrap_or_default().to_owned();
+                let mapping = parent == Some("mapping");
+                let retargeted = match pair.value.as_str() {
+                    Some(reference) if key == "$ref" || mapping => {
+                        retargeted(reference, removed, mapping)
+                    }
+                    _ => {
+                        retarget(&mut pair.value, removed, Some(&key));
+                        continue;
+                    }
+                };
+                if let Some(reference) = retargeted {
+                    let mut value = YamlNode::string(&reference);
+                    value.start = pair.value.start;
+                    value.bytes = pair.value.bytes.to_owned();
+                    pair.value = value;
+                }
+            }
+        }
+        YamlValue::Sequence(items) => {
+            for item in items {
+                retarget(item, removed, None);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `reference` pointing at the kept schema, when it points at a removed one.
+/// A discriminator `mapping` value may name the schema instead.
+fn retargeted(reference: &str, removed: &[(String, String)], mapping: bool) -> Option<String> {
+    if mapping && !reference.contains('#') {
+        let (_, kept) = removed.iter().find(|(name, _)| name == reference)?;
+        return Some(kept.to_owned());
+    }
+    let mut segments = pointer_segments(reference.strip_prefix('#')?)?;
+    if segments.len() < 3 || segments[..2] != ["components", "schemas"] {
+        return None;
+    }
+    let (_, kept) = removed.iter().find(|(name, _)| *name == segments[2])?;
+    segments[2] = kept.to_owned();
+    Some(format!("#{}", pointer(&segments)))
+}
+
+/// The JSON pointer made of `segments`.
+fn pointer(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("/{}", escape_pointer_segment(segment)))
+        .collect()
+}
+
+fn node_mut<'a>(node: &'a mut YamlNode, segments: &[String]) -> Option<&'a mut YamlNode> {
+    segments
+        .iter()
+        .try_fold(node, |node, segment| node.child_mut(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        error::Error,
+        path::{Path, PathBuf},
+    };
+
+    use mocktopus::mocking::*;
+
+    use crate::{
+        bindings::{
+            dedupe::{
+                dedupe_document, retargeted, Deduplicator, DuplicateSchema, TreeSitterDeduplicator,
+            },
+            yaml::parse_yaml,
+        },
+        content::{ContentProvider, ContentProviderMap},
+    };
+
+    const SPEC: &str = r##"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  owner:
+                    type: object
+                    required: [name, id]
+                    properties:
+                      id:
+                        type: integer
+                      name:
+                        type: string
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              properties:
+                name:
+                  type: string
+                  description: The name of the pet
+                id:
+                  type: integer
+              required: [id, name]
+              type: object
+components:
+  schemas:
+    Owner:
+      type: object
+      properties:
+        owner:
+          $ref: '#/components/schemas/Person'
+    Person:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+    Human:
+      title: A human
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+    Pet:
+      type: object
+      properties:
+        parent:
+          $ref: '#/components/schemas/Human'
+"##;
+
+    #[test]
+    fn get_duplicates() -> Result<(), Box<dyn Error>> {
+        let contents = HashMap::from([(PathBuf::from("#"), SPEC.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let duplicates = TreeSitterDeduplicator::new(provider).get_duplicates()?;
+        let schema = |name: Option<&str>, pointer: &str, line, column| DuplicateSchema {
+            name: name.map(str::to_owned),
+            pointer: pointer.to_string(),
+            file: PathBuf::from("#"),
+            line,
+            column,
+        };
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].name, "Person");
+        assert_eq!(
+            duplicates[0].schemas,
+            vec![
+                schema(
+                    None,
+                    "/paths/~1pets/get/responses/200/content/application~1json/schema/properties/owner",
+                    13,
+                    21
+                ),
+                schema(
+                    None,
+                    "/paths/~1pets/post/requestBody/content/application~1json/schema",
+                    25,
+                    15
+                ),
+                schema(Some("Person"), "/components/schemas/Person", 41, 7),
+                schema(Some("Human"), "/components/schemas/Human", 49, 7),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedupe_document() -> Result<(), Box<dyn Error>> {
+        let expected = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  owner:
+                    $ref: '#/components/schemas/Person'
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Person'
+components:
+  schemas:
+    Owner:
+      type: object
+      properties:
+        owner:
+          $ref: '#/components/schemas/Person'
+    Person:
+      type: object
+      required:
+        - id
+        - name
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+    Pet:
+      type: object
+      properties:
+        parent:
+          $ref: '#/components/schemas/Person'
+"#;
+        assert_eq!(dedupe_document(&parse_yaml(SPEC)?).to_yaml(), expected);
+
+        let root = parse_yaml(
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            type: object
+            properties:
+              tag:
+                type: string
+    delete:
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            properties:
+              tag:
+                type: string
+            type: object
+"#,
+        )?;
+        let expected = r#"paths:
+  /pets:
+    get:
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            $ref: '#/components/schemas/Filter'
+    delete:
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            $ref: '#/components/schemas/Filter'
+components:
+  schemas:
+    Filter:
+      type: object
+      properties:
+        tag:
+          type: string
+"#;
+        assert_eq!(dedupe_document(&root).to_yaml(), expected);
+        Ok(())
+    }
+    #[test]
+    fn dedupe_files() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"paths:
+  /pets:
+    $ref: 'pets.yaml'
+components:
+  schemas:
+    Person:
+      type: object
+      properties:
+        name:
+          type: string
+    Human:
+      type: object
+      properties:
+        name:
+          type: string
+    Cat:
+      type: object
+      properties:
+        lives:
+          type: integer
+    Kitten:
+      type: object
+      properties:
+        lives:
+          type: integer
+"#;
+        let pets_content = r#"get:
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            oneOf:
+              - $ref: 'openapi.yaml#/components/schemas/Cat'
+            discriminator:
+              propertyName: kind
+post:
+  requestBody:
+    content:
+      application/json:
+        schema:
+          $ref: 'openapi.yaml#/components/schemas/Human'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pets.yaml"), pets_content.to_owned()),
+        ]);
+        // Refs back to the root document name the file it is read from.
+        ContentProviderMap::source_path.mock_safe(|_, path: &Path| {
+            MockResult::Return(match path == Path::new("#") {
+                true => PathBuf::from("/spec/openapi.yaml"),
+                false => Path::new("/spec").join(path),
+            })
+        });
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let deduplicator = TreeSitterDeduplicator::new(provider);
+
+        let duplicates = deduplicator.get_duplicates()?;
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].name, "Person");
+
+        let deduped = deduplicator.dedupe()?;
+        let schemas: Vec<&str> = deduped
+            .document
+            .get("components")
+            .and_then(|components| components.get("schemas"))
+            .and_then(|schemas| schemas.as_mapping())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|pair| pair.key.as_str())
+            .collect();
+        assert_eq!(schemas, vec!["Person", "Cat", "Kitten"]);
+        assert_eq!(deduped.files.len(), 1);
+        assert_eq!(deduped.files[0].file, PathBuf::from("/spec/pets.yaml"));
+        assert_eq!(
+            deduped.files[0].content,
+            pets_content.replace("schemas/Human", "schemas/Person")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_retargeted() {
+        let removed = vec![("Human".to_string(), "Person".to_string())];
+        assert_eq!(
+            retargeted("#/components/schemas/Human", &removed, false),
+            Some("#/components/schemas/Person".to_string())
+        );
+        assert_eq!(
+            retargeted("Human", &removed, true),
+            Some("Person".to_string())
+        );
+        assert_eq!(retargeted("Human", &removed, false), None);
+        assert_eq!(retargeted("Cat", &removed, true), None);
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeprecatedKind {
+    Operation,
+    Parameter,
+    Schema,
+}
+
+impl DeprecatedKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeprecatedKind::Operation => "operation",
+            DeprecatedKind::Parameter => "parameter",
+            DeprecatedKind::Schema => "schema",
+        }
+    }
+}
+
+/// Something marked `deprecated: true`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedNode {
+    pub kind: DeprecatedKind,
+    /// `GET /pets` for operations, `limit in query` for parameters and
+    /// `Pet.name` for schemas and their properties.
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An operation that is not deprecated but uses a deprecated component
+/// schema, directly or through other schemas.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedUsage {
+    /// The operation as `GET /pets`.
+    pub operation: String,
+    pub schema: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait DeprecationParser {
+    /// The deprecated operations, parameters and component schemas, including
+    /// deprecated properties of those schemas.
+    fn get_deprecated_nodes(&self) -> Result<Vec<DeprecatedNode>, OpenapiSchemerError>;
+    fn get_deprecated_usages(&self) -> Result<Vec<DeprecatedUsage>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterDeprecationParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterDeprecationParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn node(
+        &self,
+        kind: DeprecatedKind,
+        name: String,
+        at: &YamlNode,
+        file: &Path,
+    ) -> DeprecatedNode {
+        DeprecatedNode {
+            kind,
+            name,
+            file: self.provider.source_path(file),
+            line: at.start.row + 1,
+            column: at.start.column + 1,
+        }
+    }
+
+    fn deprecated_nodes(&self) -> Result<Vec<DeprecatedNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut nodes = vec![];
+        let operations = resolver.operations()?;
+        for operation in &operations {
+            if is_deprecated(&operation.operation) {
+                let position = operation.node(self.provider.as_ref());
+                nodes.push(DeprecatedNode {
+                    kind: DeprecatedKind::Operation,
+                    name: operation.describe(),
+                    file: position.file,
+                    line: position.line,
+                    column: position.column,
+                });
+            }
+        }
+
+        // Parameters defined in components, then the ones written inline in
+        // path items and operations.
+        for entry in resolver.entries("/components/parameters")? {
+            if is_deprecated(&entry.value) {
+                nodes.push(self.node(
+                    DeprecatedKind::Parameter,
+                    parameter_name(&entry.value),
+                    &entry.key,
+                    &entry.key_file,
+                ));
+            }
+        }
+        let mut inline = vec![];
+        for path_item in resolver.entries("/paths")? {
+            inline.push((path_item.value, path_item.file));
+        }
+        inline.extend(
+            operations
+                .into_iter()
+                .map(|operation| (operation.operation, operation.file)),
+        );
+        for (node, file) in inline {
+            let parameters = node
+                .get("parameters")
+                .and_then(|parameters| parameters.as_sequence())
+                .unwrap_or_default();
+            for parameter in parameters {
+                if parameter.reference().is_none() && is_deprecated(parameter) {
+                    nodes.push(self.node(
+                        DeprecatedKind::Parameter,
+                        parameter_name(parameter),
+                        parameter,
+                        &file,
+                    ));
+                }
+            }
+        }
+
+        for entry in resolver.entries("/components/schemas")? {
+            if is_deprecated(&entry.value) {
+                nodes.push(self.node(
+                    DeprecatedKind::Schema,
+                    entry.name.to_owned(),
+                    &entry.key,
+                    &entry.key_file,
+                ));
+            }
+            self.deprecated_properties(&entry.value, &entry.name, &entry.file, &mut nodes);
+        }
+        Ok(nodes)
+    }
+
+    /// The deprecated properties below `schema`, named by their path from the
+    /// component. Refs are left alone, their targets are reported where they
+    /// are defined.
+    fn deprecated_properties(
+        &self,
+        schema: &YamlNode,
+        name: &str,
+        file: &Path,
+        nodes: &mut Vec<DeprecatedNode>,
+    ) {
+        if schema.reference().is_some() {
+            return;
+        }
+        let properties = schema
+            .get("properties")
+            .and_then(|properties| properties.as_mapping())
+            .unwrap_or_default();
+        for pair in properties {
+            let property = format!("{}.{}", name, pair.key.as_str().unwrap_or_default());
+            if pair.value.reference().is_none() && is_deprecated(&pair.value) {
+                nodes.push(self.node(DeprecatedKind::Schema, property.to_owned(), &pair.key, file));
+            }
+            self.deprecated_properties(&pair.value, &property, file, nodes);
+        }
+        if let Some(items) = schema.get("items") {
+            self.deprecated_properties(items, &format!("{}[]", name), file, nodes);
+        }
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            for subschema in schema
+                .get(keyword)
+                .and_then(|subschemas| subschemas.as_sequence())
+                .unwrap_or_default()
+            {
+                self.deprecated_properties(subschema, name, file, nodes);
+            }
+        }
+    }
+
+    fn deprecated_usages(&self) -> Result<Vec<DeprecatedUsage>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        // Schemas are told apart by where their node starts, so every ref that
+        // reaches one counts, whichever file it goes through.
+        let mut schemas = HashMap::new();
+        for entry in resolver.entries("/components/schemas")? {
+            if is_deprecated(&entry.value) {
+                let key = (
+                    self.provider.source_path(&entry.file),
+                    entry.value.bytes.start,
+                );
+                schemas.insert(key, entry.name);
+            }
+        }
+        let mut usages = vec![];
+        if schemas.is_empty() {
+            return Ok(usages);
+        }
+        for operation in resolver.operations()? {
+            if is_deprecated(&operation.operation) {
+                continue;
+            }
+            let mut seen = HashSet::new();
+            let mut used: Vec<&String> = vec![];
+            let mut pending = vec![(operation.operation.to_owned(), operation.file.to_owned())];
+            while let Some((node, file)) = pending.pop() {
+                for reference in node.references() {
+                    // Broken refs are left to `refs check`.
+                    let (target, target_file) = match resolver.resolve_ref(&file, reference) {
+                        Ok(target) => target,
+                        Err(_) => continue,
+                    };
+                    let key = (self.provider.source_path(&target_file), target.bytes.start);
+                    if !seen.insert(key.to_owned()) {
+                        continue;
+                    }
+                    if let Some(name) = schemas.get(&key) {
+                        if !used.contains(&name) {
+                            used.push(name);
+                        }
+                    }
+                    pending.push((target, target_file));
+                }
+            }
+            let position = operation.node(self.provider.as_ref());
+            for name in used {
+                usages.push(DeprecatedUsage {
+                    operation: operation.describe(),
+                    schema: name.to_owned(),
+                    file: position.file.to_owned(),
+                    line: position.line,
+                    column: position.column,
+                });
+            }
+        }
+        Ok(usages)
+    }
+}
+
+impl DeprecationParser for TreeSitterDeprecationParser {
+    fn get_deprecated_nodes(&self) -> Result<Vec<DeprecatedNode>, OpenapiSchemerError> {
+        self.deprecated_nodes().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::DeprecatedList)
+        })
+    }
+
+    fn get_deprecated_usages(&self) -> Result<Vec<DeprecatedUsage>, OpenapiSchemerError> {
+        self.deprecated_usages().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::DeprecatedList)
+        })
+    }
+}
+
+fn is_deprecated(node: &YamlNode) -> bool {
+    node.get("deprecated")
+        .and_then(|deprecated| deprecated.as_str())
+        == Some("true")
+}
+
+/// `limit in query` style name for a parameter.
+fn parameter_name(parameter: &YamlNode) -> String {
+    let text = |key: &str| {
+        parameter
+            .get(key)
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+    };
+    format!("{} in {}", text("name"), text("in"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::deprecated::{DeprecationParser, TreeSitterDeprecationParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterDeprecationParser {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets:
+    parameters:
+      - name: format
+        in: query
+        deprecated: true
+    get:
+      operationId: listPets
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pets'
+    delete:
+      operationId: deletePets
+      deprecated: true
+      responses:
+        '204':
+          content:
+            application/json:
+              schema:
+                $ref: 'schemas.yaml#/Pet'
+components:
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      deprecated: true
+  schemas:
+    $ref: 'schemas.yaml'
+"##;
+        let schemas_content = r#"Pets:
+  type: array
+  items:
+    $ref: '#/Pet'
+Pet:
+  deprecated: true
+  properties:
+    tag:
+      type: string
+      deprecated: true
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        TreeSitterDeprecationParser::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    #[test]
+    fn get_deprecated_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes: Vec<(&str, String, String, usize)> = parser()
+            .get_deprecated_nodes()?
+            .into_iter()
+            .map(|node| {
+                (
+                    node.kind.name(),
+                    node.name,
+                    node.file.display().to_string(),
+                    node.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("operation", "DELETE /pets".to_string(), "#".to_string(), 19),
+                (
+                    "parameter",
+                    "limit in query".to_string(),
+                    "#".to_string(),
+                    29
+                ),
+                (
+                    "parameter",
+                    "format in query".to_string(),
+                    "#".to_string(),
+                    5
+                ),
+                ("schema", "Pet".to_string(), "schemas.yaml".to_string(), 5),
+                (
+                    "schema",
+                    "Pet.tag".to_string(),
+                    "schemas.yaml".to_string(),
+                    8
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_deprecated_usages() -> Result<(), Box<dyn Error>> {
+        let usages: Vec<(String, String, usize)> = parser()
+            .get_deprecated_usages()?
+            .into_iter()
+            .map(|usage| (usage.operation, usage.schema, usage.line))
+            .collect();
+        assert_eq!(
+            usages,
+            vec![("GET /pets".to_string(), "Pet".to_string(), 9)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, validate::HTTP_METHODS, yaml::YamlNode};
+
+/// The parts of a spec that `diff` compares.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpecSnapshot {
+    pub paths: Vec<String>,
+    pub operations: Vec<OperationSnapshot>,
+    pub schemas: Vec<SchemaSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationSnapshot {
+    /// `GET /pets` style name for the operation.
+    pub text: String,
+    /// Path level parameters merged with the operation's own.
+    pub parameters: Vec<ParameterSnapshot>,
+    pub deprecated: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterSnapshot {
+    pub name: String,
+    pub location: String,
+    pub required: bool,
+    /// The `type` of the parameter's schema, see `describe_type`.
+    pub schema_type: String,
+}
+
+impl ParameterSnapshot {
+    /// `limit (query)`, which identifies a parameter within an operation.
+    pub fn describe(&self) -> String {
+        format!("{} ({})", self.name, self.location)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaSnapshot {
+    pub name: String,
+    /// Each property name with its type, see `describe_type`.
+    pub properties: Vec<(String, String)>,
+    pub deprecated: bool,
+}
+
+/// A short description of the type of `schema`, such as `integer/int64` or
+/// `string|null` for a list of types, or the `$ref` it is defined by.
+pub fn describe_type(schema: Option<&YamlNode>) -> String {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return "any".to_string(),
+    };
+    if let Some(reference) = schema.reference() {
+        return reference.to_string();
+    }
+    let schema_type = match schema.get("type") {
+        Some(schema_type) => match schema_type.as_sequence() {
+            Some(types) => types
+                .iter()
+                .filter_map(|schema_type| schema_type.as_str())
+                .collect::<Vec<&str>>()
+                .join("|"),
+            None => schema_type.as_str().unwrap_or("any").to_string(),
+        },
+        None => "any".to_string(),
+    };
+    match schema.get("format").and_then(|format| format.as_str()) {
+        Some(format) => format!("{}/{}", schema_type, format),
+        None => schema_type.to_string(),
+    }
+}
+
+fn is_deprecated(node: &YamlNode) -> bool {
+    node.get("deprecated")
+        .and_then(|deprecated| deprecated.as_str())
+        == Some("true")
+}
+
+pub trait DiffParser {
+    fn get_snapshot(&self) -> Result<SpecSnapshot, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterDiffParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterDiffParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn parameters(
+        resolver: &mut Resolver,
+        parameters: Option<&YamlNode>,
+        file: &Path,
+    ) -> Result<Vec<ParameterSnapshot>> {
+        let mut results = vec![];
+        for parameter in parameters
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default()
+        {
+            let (parameter, _) = resolver.follow(parameter, file)?;
+            let text = |key: &str| {
+                parameter
+                    .get(key)
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            results.push(ParameterSnapshot {
+                name: text("name"),
+                location: text("in"),
+                required: text("required") == "true",
+                schema_type: describe_type(parameter.get("schema")),
+            });
+        }
+        Ok(results)
+    }
+
+    fn snapshot(&self) -> Result<SpecSnapshot> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut snapshot = SpecSnapshot::default();
+        for path_item in resolver.entries("/paths")? {
+            let shared = Self::parameters(
+                &mut resolver,
+                path_item.value.get("parameters"),
+                &path_item.file,
+            )?;
+            for pair in path_item.value.as_mapping().unwrap_or_default() {
+                let method = match pair.key.as_str() {
+                    Some(method) if HTTP_METHODS.contains(&method) => method,
+                    _ => continue,
+                };
+                let (operation, file) = resolver.follow(&pair.value, &path_item.file)?;
+                let own = Self::parameters(&mut resolver, operation.get("parameters"), &file)?;
+                let mut parameters: Vec<ParameterSnapshot> = shared
+                    .iter()
+                    .filter(|parameter| {
+                        !own.iter()
+                            .any(|overridden| overridden.describe() == parameter.describe())
+                    })
+                    .cloned()
+                    .collect();
+                parameters.extend(own);
+                snapshot.operations.push(OperationSnapshot {
+                    text: format!("{} {}", method.to_uppercase(), path_item.name),
+                    parameters,
+                    deprecated: is_deprecated(&operation),
+                });
+            }
+            snapshot.paths.push(path_item.name);
+        }
+        for schema in resolver.entries("/components/schemas")? {
+            let mut properties = vec![];
+            if let Some(node) = schema.value.get("properties") {
+                let (node, _) = resolver.follow(node, &schema.file)?;
+                for pair in node.as_mapping().unwrap_or_default() {
+                    properties.push((
+                        pair.key.as_str().unwrap_or_default().to_string(),
+                        describe_type(Some(&pair.value)),
+                    ));
+                }
+            }
+            snapshot.schemas.push(SchemaSnapshot {
+                deprecated: is_deprecated(&schema.value),
+                name: schema.name,
+                properties,
+            });
+        }
+        Ok(snapshot)
+    }
+}
+
+impl DiffParser for TreeSitterDiffParser {
+    fn get_snapshot(&self) -> Result<SpecSnapshot, OpenapiSchemerError> {
+        self.snapshot()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Diff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::diff::{DiffParser, ParameterSnapshot, TreeSitterDiffParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_snapshot() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+      - name: verbose
+        in: query
+    get:
+      deprecated: true
+      parameters:
+        - name: verbose
+          in: query
+          required: true
+          schema:
+            type: boolean
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+        format: int64
+  schemas:
+    Pet:
+      properties:
+        id:
+          type: integer
+        name:
+          type: [string, 'null']
+        owner:
+          $ref: '#/components/schemas/Owner'
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterDiffParser::new(provider);
+        let snapshot = parser.get_snapshot()?;
+        assert_eq!(snapshot.paths, vec!["/pets/{petId}".to_string()]);
+        assert_eq!(snapshot.operations.len(), 1);
+        assert_eq!(snapshot.operations[0].text, "GET /pets/{petId}");
+        assert!(snapshot.operations[0].deprecated);
+        assert!(!snapshot.schemas[0].deprecated);
+        assert_eq!(
+            snapshot.operations[0].parameters,
+            vec![
+                ParameterSnapshot {
+                    name: "petId".to_string(),
+                    location: "path".to_string(),
+                    required: true,
+                    schema_type: "integer/int64".to_string(),
+                },
+                ParameterSnapshot {
+                    name: "verbose".to_string(),
+                    location: "query".to_string(),
+                    required: true,
+                    schema_type: "boolean".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            snapshot.schemas[0].properties,
+            vec![
+                ("id".to_string(), "integer".to_string()),
+                ("name".to_string(), "string|null".to_string()),
+                (
+                    "owner".to_string(),
+                    "#/components/schemas/Owner".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,383 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode, OperationNode};
+
+/// Everything the documentation page shows, gathered from the spec with its
+/// refs followed.
+#[derive(Clone, Debug)]
+pub struct DocsPage {
+    pub title: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub operations: Vec<DocsOperation>,
+    pub schemas: Vec<DocsSchema>,
+    /// The entries of `components/examples`.
+    pub examples: Vec<DocsExample>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DocsOperation {
+    pub operation: OperationNode,
+    pub description: Option<String>,
+    pub parameters: Vec<DocsParameter>,
+    pub request_body: Option<DocsBody>,
+    pub responses: Vec<DocsBody>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocsParameter {
+    pub name: String,
+    /// Where the parameter is sent, such as `query` or `path`.
+    pub location: String,
+    pub required: bool,
+    pub description: Option<String>,
+    pub schema: Option<DocsType>,
+}
+
+/// The request body or a response of an operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocsBody {
+    /// `request` for the request body, otherwise the status code of the
+    /// response such as `200` or `default`.
+    pub name: String,
+    pub description: Option<String>,
+    pub media_types: Vec<DocsMediaType>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocsMediaType {
+    pub name: String,
+    pub schema: Option<DocsType>,
+    /// The `example` of the media type, or the value of the first of its
+    /// `examples`.
+    pub example: Option<YamlNode>,
+}
+
+/// A short description of a schema such as `integer` or `array of Pet`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocsType {
+    pub array: bool,
+    /// The `type` of the schema, or the name of the component it refers to.
+    pub name: String,
+    /// Whether `name` is a component under `components/schemas`.
+    pub component: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocsSchema {
+    pub name: String,
+    pub description: Option<String>,
+    /// The schema as written, with its refs left in place.
+    pub schema: YamlNode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocsExample {
+    pub name: String,
+    pub summary: Option<String>,
+    pub value: YamlNode,
+}
+
+pub trait DocsParser {
+    fn get_docs_page(&self) -> Result<DocsPage, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterDocsParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterDocsParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn docs_page(&self) -> Result<DocsPage> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let info = resolver
+            .resolve_pointer(Path::new("#"), "/info")?
+            .map(|(info, _)| info);
+        let info_text = |key: &str| {
+            info.as_ref()
+                .and_then(|info| info.get(key))
+                .and_then(|value| value.as_str())
+                .map(str::to_owned)
+        };
+        let mut operations = vec![];
+        for entry in resolver.operations()? {
+            operations.push(DocsOperation {
+                operation: entry.node(self.provider.as_ref()),
+                description: text(&entry.operation, "description"),
+                parameters: parameters(&mut resolver, &entry.operation, &entry.file)?,
+                request_body: match entry.operation.get("requestBody") {
+                    Some(body) => Some(body_docs(&mut resolver, "request", body, &entry.file)?),
+                    None => None,
+                },
+                responses: responses(&mut resolver, &entry.operation, &entry.file)?,
+            });
+        }
+        let schemas = resolver
+            .entries("/components/schemas")?
+            .into_iter()
+            .map(|entry| DocsSchema {
+                name: entry.name,
+                description: text(&entry.value, "description"),
+                schema: entry.value,
+            })
+            .collect();
+        let examples = resolver
+            .entries("/components/examples")?
+            .into_iter()
+            .map(|entry| DocsExample {
+                name: entry.name,
+                summary: text(&entry.value, "summary"),
+                value: entry
+                    .value
+                    .get("value")
+                    .cloned()
+                    .unwrap_or_else(YamlNode::null),
+            })
+            .collect();
+        Ok(DocsPage {
+            title: info_text("title").unwrap_or_else(|| "API".to_string()),
+            version: info_text("version"),
+            description: info_text("description"),
+            operations,
+            schemas,
+            examples,
+        })
+    }
+}
+
+impl DocsParser for TreeSitterDocsParser {
+    fn get_docs_page(&self) -> Result<DocsPage, OpenapiSchemerError> {
+        self.docs_page()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::DocsHtml))
+    }
+}
+
+fn text(node: &YamlNode, key: &str) -> Option<String> {
+    node.get(key)
+        .and_then(|value| value.as_str())
+        .map(str::to_owned)
+}
+
+fn parameters(
+    resolver: &mut Resolver,
+    operation: &YamlNode,
+    file: &Path,
+) -> Result<Vec<DocsParameter>> {
+    let mut parameters = vec![];
+    let items = operation
+        .get("parameters")
+        .and_then(|items| items.as_sequence());
+    for item in items.unwrap_or_default() {
+        let (parameter, _) = resolver.follow(item, file)?;
+        parameters.push(DocsParameter {
+            name: text(&parameter, "name").unwrap_or_default(),
+            location: text(&parameter, "in").unwrap_or_default(),
+            required: text(&parameter, "required").as_deref() == Some("true"),
+            description: text(&parameter, "description"),
+            schema: parameter.get("schema").map(docs_type),
+        });
+    }
+    Ok(parameters)
+}
+
+fn responses(resolver: &mut Resolver, operation: &YamlNode, file: &Path) -> Result<Vec<DocsBody>> {
+    let (responses, responses_file) = match operation.get("responses") {
+        Some(responses) => resolver.follow(responses, file)?,
+        None => return Ok(vec![]),
+    };
+    let mut bodies = vec![];
+    for pair in responses.as_mapping().unwrap_or_default() {
+        let code = pair.key.as_str().unwrap_or_default();
+        bodies.push(body_docs(resolver, code, &pair.value, &responses_file)?);
+    }
+    Ok(bodies)
+}
+
+fn body_docs(
+    resolver: &mut Resolver,
+    name: &str,
+    body: &YamlNode,
+    file: &Path,
+) -> Result<DocsBody> {
+    let (body, body_file) = resolver.follow(body, file)?;
+    let mut media_types = vec![];
+    if let Some(content) = body.get("content") {
+        let (content, content_file) = resolver.follow(content, &body_file)?;
+        for pair in content.as_mapping().unwrap_or_default() {
+            let (media, media_file) = resolver.follow(&pair.value, &content_file)?;
+            media_types.push(DocsMediaType {
+                name: pair.key.as_str().unwrap_or_default().to_string(),
+                schema: media.get("schema").map(docs_type),
+                example: media_example(resolver, &media, &media_file)?,
+            });
+        }
+    }
+    Ok(DocsBody {
+        name: name.to_string(),
+        description: text(&body, "description"),
+        media_types,
+    })
+}
+
+fn media_example(
+    resolver: &mut Resolver,
+    media: &YamlNode,
+    file: &Path,
+) -> Result<Option<YamlNode>> {
+    if let Some(example) = media.get("example") {
+        return Ok(Some(example.to_owned()));
+    }
+    let first = media
+        .get("examples")
+        .and_then(|examples| examples.as_mapping())
+        .and_then(|examples| examples.first());
+    match first {
+        Some(pair) => {
+            let (example, _) = resolver.follow(&pair.value, file)?;
+            Ok(example.get("value").cloned())
+        }
+        None => Ok(None),
+    }
+}
+
+/// The short description of `schema`, naming the component a `$ref` leads to.
+pub fn docs_type(schema: &YamlNode) -> DocsType {
+    if let Some(reference) = schema.reference() {
+        let name = reference.rsplit('/').next().unwrap_or(reference);
+        return DocsType {
+            array: false,
+            name: name.to_string(),
+            component: reference.contains("#/components/schemas/"),
+        };
+    }
+    match text(schema, "type") {
+        Some(kind) if kind == "array" => match schema.get("items") {
+            Some(items) => DocsType {
+                array: true,
+                ..docs_type(items)
+            },
+            None => DocsType {
+                array: false,
+                name: kind,
+                component: false,
+            },
+        },
+        kind => DocsType {
+            array: false,
+            name: kind.unwrap_or_else(|| "object".to_string()),
+            component: false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::docs::{DocsParser, DocsType, TreeSitterDocsParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_docs_page() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      description: Lists every pet
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+      responses:
+        '200':
+          description: The pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Pet'
+              examples:
+                rex:
+                  $ref: '#/components/examples/Rex'
+        default:
+          $ref: 'responses.yaml#/Error'
+components:
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      required: true
+      schema:
+        type: integer
+  examples:
+    Rex:
+      summary: A dog
+      value:
+        - name: Rex
+  schemas:
+    Pet:
+      description: A pet
+      type: object
+"##;
+        let responses_content = r#"Error:
+  description: Unexpected error
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let page = TreeSitterDocsParser::new(provider).get_docs_page()?;
+        assert_eq!(page.title, "Petstore");
+        assert_eq!(page.version.as_deref(), Some("1.0.0"));
+
+        let operation = &page.operations[0];
+        assert_eq!(operation.operation.text, "listPets");
+        assert_eq!(operation.description.as_deref(), Some("Lists every pet"));
+        assert_eq!(operation.parameters[0].name, "limit");
+        assert!(operation.parameters[0].required);
+        assert_eq!(
+            operation.parameters[0].schema.as_ref().unwrap().name,
+            "integer"
+        );
+        assert!(operation.request_body.is_none());
+
+        let media = &operation.responses[0].media_types[0];
+        assert_eq!(media.name, "application/json");
+        assert_eq!(
+            media.schema,
+            Some(DocsType {
+                array: true,
+                name: "Pet".to_string(),
+                component: true,
+            })
+        );
+        assert_eq!(
+            media.example.as_ref().unwrap().to_json(),
+            "[\n  {\n    \"name\": \"Rex\"\n  }\n]"
+        );
+        let default = &operation.responses[1];
+        assert_eq!(default.name, "default");
+        assert_eq!(default.description.as_deref(), Some("Unexpected error"));
+
+        assert_eq!(page.schemas[0].name, "Pet");
+        assert_eq!(page.schemas[0].description.as_deref(), Some("A pet"));
+        assert_eq!(page.examples[0].name, "Rex");
+        assert_eq!(page.examples[0].summary.as_deref(), Some("A dog"));
+        Ok(())
+    }
+}
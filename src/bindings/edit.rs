@@ -0,0 +1,65 @@
+use std::{ops::Range, path::PathBuf};
+
+/// A replacement of part of the text of a file, found through the byte span of
+/// the tree-sitter node it replaces. Editing the text instead of writing out a
+/// changed `YamlNode` keeps the comments and formatting of the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub bytes: Range<usize>,
+    /// The value replaced and the one replacing it, for reporting the edit.
+    pub old: String,
+    pub new: String,
+    /// `new` as it is written into the file.
+    pub text: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The edits made to one file, with its content once they are made.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileEdits {
+    pub file: PathBuf,
+    pub edits: Vec<TextEdit>,
+    pub content: String,
+}
+
+/// `content` with `edits` made. Edits may be given in any order but must not
+/// overlap.
+pub fn apply_edits(content: &str, edits: &[TextEdit]) -> String {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.bytes.start);
+    let mut edited = String::with_capacity(content.len());
+    let mut end = 0;
+    for edit in edits {
+        edited.push_str(&content[end..edit.bytes.start]);
+        edited.push_str(&edit.text);
+        end = edit.bytes.end;
+    }
+    edited.push_str(&content[end..]);
+    edited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_edits, TextEdit};
+
+    #[test]
+    fn test_apply_edits() {
+        let content =
+            "operationId: listPets # all of them\nlinks: {GetPet: {operationId: 'listPets'}}\n";
+        let edit = |bytes, text: &str| TextEdit {
+            bytes,
+            old: "listPets".to_string(),
+            new: "listAll".to_string(),
+            text: text.to_string(),
+            line: 1,
+            column: 1,
+        };
+        let edits = [edit(66..76, "'listAll'"), edit(13..21, "listAll")];
+        assert_eq!(
+            apply_edits(content, &edits),
+            "operationId: listAll # all of them\nlinks: {GetPet: {operationId: 'listAll'}}\n"
+        );
+        assert_eq!(apply_edits(content, &[]), content);
+    }
+}
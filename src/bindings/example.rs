@@ -0,0 +1,262 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    child_pointer,
+    resolve::Resolver,
+    yaml::{YamlNode, YamlValue},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleNode {
+    /// The name of the example within its `examples` mapping.
+    pub text: String,
+    /// Where the example is defined as `file#/pointer`, relative to the root
+    /// document's directory, which `get_example` accepts back.
+    pub pointer: String,
+    pub summary: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait ExampleParser {
+    fn get_example_nodes(&self) -> Result<Vec<ExampleNode>, OpenapiSchemerError>;
+    /// The example at `pointer`, such as `#/components/examples/Pet` or
+    /// `responses.yaml#/Pets/content/application~1json/examples/Pets`, with
+    /// its `$ref` followed.
+    fn get_example(&self, pointer: &str) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+}
+
+/// Keys whose values are free-form data rather than spec objects, so an
+/// `examples` key below them is not an examples mapping.
+const DATA_KEYS: [&str; 5] = ["example", "default", "enum", "const", "value"];
+
+pub struct TreeSitterExampleParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterExampleParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Every example in an `examples` mapping below `node`, which lives at
+    /// `pointer` in `file`. This covers components, media types, parameters
+    /// and headers alike. An `examples` key naming a schema property, or the
+    /// JSON Schema `examples` array, is not an examples mapping.
+    fn collect_examples(
+        &self,
+        node: &YamlNode,
+        pointer: &str,
+        parent: Option<&str>,
+        file: &Path,
+        results: &mut Vec<ExampleNode>,
+    ) {
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    if DATA_KEYS.contains(&key) || key.starts_with("x-") {
+                        continue;
+                    }
+                    let key_pointer = child_pointer(pointer, key);
+                    let examples = match (key, parent, pair.value.as_mapping()) {
+                        ("examples", parent, Some(examples)) if parent != Some("properties") => {
+                            examples
+                        }
+                        _ => {
+                            self.collect_examples(
+                                &pair.value,
+                                &key_pointer,
+                                Some(key),
+                                file,
+                                results,
+                            );
+                            continue;
+                        }
+                    };
+                    for example in examples {
+                        let name = example.key.as_str().unwrap_or_default();
+                        results.push(ExampleNode {
+                            text: name.to_string(),
+                            pointer: reference(file, &child_pointer(&key_pointer, name)),
+                            summary: example
+                                .value
+                                .get("summary")
+                                .and_then(|summary| summary.as_str())
+                                .map(str::to_owned),
+                            file: self.provider.source_path(file),
+                            line: example.key.start.row + 1,
+                            column: example.key.start.column + 1,
+                        });
+                    }
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_pointer = format!("{}/{}", pointer, index);
+                    self.collect_examples(item, &item_pointer, None, file, results);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn example_nodes(&self) -> Result<Vec<ExampleNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for file in resolver.files()? {
+            let document = resolver.document(&file)?.to_owned();
+            self.collect_examples(&document, "", None, &file, &mut results);
+        }
+        Ok(results)
+    }
+}
+
+/// `pointer` in `file` written the way a `$ref` in the root document would
+/// refer to it.
+fn reference(file: &Path, pointer: &str) -> String {
+    match file == Path::new("#") {
+        true => format!("#{}", pointer),
+        false => format!("{}#{}", file.display(), pointer),
+    }
+}
+
+impl ExampleParser for TreeSitterExampleParser {
+    fn get_example_nodes(&self) -> Result<Vec<ExampleNode>, OpenapiSchemerError> {
+        self.example_nodes().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ExampleList)
+        })
+    }
+
+    fn get_example(&self, pointer: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ExampleShow)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let (file, fragment) = pointer.split_once('#').unwrap_or((pointer, ""));
+        let file = match file.is_empty() {
+            true => root_path,
+            false => PathBuf::from(file),
+        };
+        let (example, example_file) = match resolver
+            .resolve_pointer(&file, fragment)
+            .map_err(to_error)?
+        {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        resolver
+            .follow(&example, &example_file)
+            .map(|(example, _)| Some(example))
+            .map_err(to_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::example::{ExampleParser, TreeSitterExampleParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterExampleParser {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          examples:
+            small:
+              value: 10
+      responses:
+        '200':
+          $ref: 'responses.yaml#/Pets'
+components:
+  schemas:
+    Pet:
+      properties:
+        examples:
+          type: array
+      example:
+        examples:
+          notAnExample: {}
+  examples:
+    Pet:
+      summary: A single pet
+      $ref: 'examples/pet.yaml'
+"#;
+        let responses_content = r#"
+Pets:
+  content:
+    application/json:
+      examples:
+        Pets:
+          value:
+            - id: 1
+              examples:
+                nested: {}
+"#;
+        let pet_content = r#"
+summary: A single pet
+value:
+  id: 1
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+            (PathBuf::from("examples/pet.yaml"), pet_content.to_owned()),
+        ]);
+        TreeSitterExampleParser::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    #[test]
+    fn get_example_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_example_nodes()?;
+        let pointers: Vec<&str> = nodes.iter().map(|node| node.pointer.as_str()).collect();
+        assert_eq!(
+            pointers,
+            vec![
+                "#/paths/~1pets/get/parameters/0/examples/small",
+                "#/components/examples/Pet",
+                "responses.yaml#/Pets/content/application~1json/examples/Pets",
+            ]
+        );
+        assert_eq!(nodes[1].text, "Pet");
+        assert_eq!(nodes[1].summary.as_deref(), Some("A single pet"));
+        assert_eq!(nodes[2].file, PathBuf::from("responses.yaml"));
+        assert_eq!((nodes[2].line, nodes[2].column), (6, 9));
+        Ok(())
+    }
+
+    #[test]
+    fn get_example() -> Result<(), Box<dyn Error>> {
+        let parser = parser();
+        let example = parser.get_example("#/components/examples/Pet")?.unwrap();
+        assert_eq!(
+            example.to_yaml(),
+            "summary: A single pet\nvalue:\n  id: 1\n"
+        );
+        let example = parser
+            .get_example("responses.yaml#/Pets/content/application~1json/examples/Pets")?
+            .unwrap();
+        assert_eq!(
+            example.to_yaml(),
+            "value:\n  - id: 1\n    examples:\n      nested: {}\n"
+        );
+        assert!(parser.get_example("#/components/examples/Dog")?.is_none());
+        Ok(())
+    }
+}
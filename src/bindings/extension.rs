@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    yaml::{escape_pointer_segment, YamlNode, YamlValue},
+};
+
+/// The keys whose entries are named by the spec's author, so an entry called
+/// `x-something` below them is a property, header or variable rather than an
+/// extension.
+const NAMED_ENTRIES: [&str; 8] = [
+    "properties",
+    "patternProperties",
+    "headers",
+    "variables",
+    "mapping",
+    "scopes",
+    "content",
+    "encoding",
+];
+
+/// The keys whose values are instance data, which is never searched.
+const DATA_KEYS: [&str; 5] = ["example", "enum", "default", "const", "value"];
+
+/// A vendor extension, a key starting with `x-`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionNode {
+    pub key: String,
+    pub value: YamlNode,
+    /// The JSON pointer of the object the extension belongs to, within its
+    /// file.
+    pub pointer: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait ExtensionParser {
+    /// The extensions in every file reachable from the root document, in the
+    /// order they are written. The values of extensions aren't searched for
+    /// more of them.
+    fn get_extension_nodes(&self) -> Result<Vec<ExtensionNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterExtensionParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterExtensionParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn extensions(&self) -> Result<Vec<ExtensionNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut extensions = vec![];
+        for file in resolver.files()? {
+            let document = resolver.document(&file)?.to_owned();
+            self.walk(&document, &file, "", false, &mut extensions);
+        }
+        Ok(extensions)
+    }
+
+    /// Collects the extensions below `node`. `named` is set for the entries
+    /// of a mapping in `NAMED_ENTRIES`.
+    fn walk(
+        &self,
+        node: &YamlNode,
+        file: &Path,
+        pointer: &str,
+        named: bool,
+        extensions: &mut Vec<ExtensionNode>,
+    ) {
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    if !named && key.starts_with("x-") {
+                        extensions.push(ExtensionNode {
+                            key: key.to_string(),
+                            value: pair.value.to_owned(),
+                            pointer: pointer.to_string(),
+                            file: self.provider.source_path(file),
+                            line: pair.key.start.row + 1,
+                            column: pair.key.start.column + 1,
+                        });
+                        continue;
+                    }
+                    if !named && DATA_KEYS.contains(&key) {
+                        continue;
+                    }
+                    let child = format!("{}/{}", pointer, escape_pointer_segment(key));
+                    let named = !named && NAMED_ENTRIES.contains(&key);
+                    self.walk(&pair.value, file, &child, named, extensions);
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let child = format!("{}/{}", pointer, index);
+                    self.walk(item, file, &child, false, extensions);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ExtensionParser for TreeSitterExtensionParser {
+    fn get_extension_nodes(&self) -> Result<Vec<ExtensionNode>, OpenapiSchemerError> {
+        self.extensions().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ExtensionList)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::extension::{ExtensionParser, TreeSitterExtensionParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_extension_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+x-owner: platform
+paths:
+  /pets:
+    get:
+      x-internal: true
+      responses:
+        '200':
+          headers:
+            x-rate-limit:
+              schema:
+                type: integer
+          content:
+            application/json:
+              schema:
+                $ref: 'pet.yaml'
+              example:
+                x-not-an-extension: 1
+"##;
+        let pet_content = r#"type: object
+x-internal: false
+properties:
+  x-rating:
+    type: number
+    x-unit:
+      name: stars
+      x-nested: ignored
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let nodes = TreeSitterExtensionParser::new(provider).get_extension_nodes()?;
+        let summary: Vec<(&str, &str, String, usize)> = nodes
+            .iter()
+            .map(|node| {
+                (
+                    node.key.as_str(),
+                    node.pointer.as_str(),
+                    node.value.to_json(),
+                    node.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("x-owner", "", "\"platform\"".to_string(), 2),
+                ("x-internal", "/paths/~1pets/get", "true".to_string(), 6),
+                ("x-internal", "", "false".to_string(), 2),
+                (
+                    "x-unit",
+                    "/properties/x-rating",
+                    "{\n  \"name\": \"stars\",\n  \"x-nested\": \"ignored\"\n}".to_string(),
+                    6
+                ),
+            ]
+        );
+        assert_eq!(nodes[2].file, PathBuf::from("pet.yaml"));
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    validate::HTTP_METHODS,
+    yaml::{pointer_segments, YamlNode, YamlPair, YamlValue},
+};
+
+/// What `TreeSitterFilterer` removes from the spec. An operation matches
+/// when it has one of `tags` or sets one of `extensions` to anything but
+/// `false`, and a path item matches when it sets one of `extensions`.
+#[derive(Clone, Debug, Default)]
+pub struct FilterOptions {
+    pub tags: Vec<String>,
+    pub extensions: Vec<String>,
+}
+
+pub trait Filterer {
+    /// The root document without the matching operations and path items,
+    /// and without the components only they referred to.
+    fn filter(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterFilterer {
+    provider: Box<dyn ContentProvider>,
+    options: FilterOptions,
+}
+
+impl TreeSitterFilterer {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self {
+            provider,
+            options: FilterOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: FilterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    fn is_marked(&self, node: &YamlNode) -> bool {
+        self.options.extensions.iter().any(|extension| {
+            node.get(extension)
+                .is_some_and(|value| value.as_str() != Some("false"))
+        })
+    }
+
+    fn is_dropped(&self, operation: &YamlNode) -> bool {
+        let tagged = operation
+            .get("tags")
+            .and_then(YamlNode::as_sequence)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(YamlNode::as_str)
+            .any(|tag| self.options.tags.iter().any(|dropped| dropped == tag));
+        tagged || self.is_marked(operation)
+    }
+
+    /// The path items of `paths` or `webhooks` without the matching
+    /// operations. Path items left without operations are removed, those
+    /// that never had any, such as a `$ref` to another file, are kept.
+    fn path_items(&self, paths: &YamlNode) -> YamlNode {
+        let pairs = match paths.as_mapping() {
+            Some(pairs) => pairs,
+            None => return paths.to_owned(),
+        };
+        let mut kept = vec![];
+        for path in pairs {
+            if self.is_marked(&path.value) {
+                continue;
+            }
+            let item_pairs = path.value.as_mapping().unwrap_or_default();
+            let is_operation = |pair: &YamlPair| {
+                pair.key
+                    .as_str()
+                    .is_some_and(|key| HTTP_METHODS.contains(&key))
+            };
+            let operations = item_pairs.iter().filter(|pair| is_operation(pair)).count();
+            let item_pairs: Vec<YamlPair> = item_pairs
+                .iter()
+                .filter(|pair| !is_operation(pair) || !self.is_dropped(&pair.value))
+                .cloned()
+                .collect();
+            let remaining = item_pairs.iter().filter(|pair| is_operation(pair)).count();
+            if operations > 0 && remaining == 0 {
+                continue;
+            }
+            kept.push(YamlPair {
+                key: path.key.to_owned(),
+                value: with_pairs(&path.value, item_pairs),
+            });
+        }
+        with_pairs(paths, kept)
+    }
+}
+
+impl Filterer for TreeSitterFilterer {
+    fn filter(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        let root = self
+            .provider
+            .get_document(PathBuf::from("#"))
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Filter)
+            })?;
+        let used = used_components(&root);
+        let mut pairs = vec![];
+        for pair in root.as_mapping().unwrap_or_default() {
+            let value = match pair.key.as_str() {
+                Some("paths" | "webhooks") => self.path_items(&pair.value),
+                Some("tags") => {
+                    let tags: Vec<YamlNode> = pair
+                        .value
+                        .as_sequence()
+                        .unwrap_or_default()
+                        .iter()
+                        .filter(|tag| {
+                            let name = tag.get("name").and_then(YamlNode::as_str);
+                            !name.is_some_and(|name| self.options.tags.iter().any(|t| t == name))
+                        })
+                        .cloned()
+                        .collect();
+                    if tags.is_empty() {
+                        continue;
+                    }
+                    YamlNode {
+                        value: YamlValue::Sequence(tags),
+                        ..pair.value.to_owned()
+                    }
+                }
+                _ => pair.value.to_owned(),
+            };
+            pairs.push(YamlPair {
+                key: pair.key.to_owned(),
+                value,
+            });
+        }
+        let mut filtered = with_pairs(&root, pairs);
+        // Only components that lost every use are removed, those nothing
+        // referred to in the first place are left for `unused` to report.
+        let still_used = used_components(&filtered);
+        let removed: HashSet<&(String, String)> = used.difference(&still_used).collect();
+        if let Some(components) = filtered.get_mut("components") {
+            if let YamlValue::Mapping(sections) = &mut components.value {
+                for section in sections.iter_mut() {
+                    let kind = section.key.as_str().unwrap_or_default().to_string();
+                    if let YamlValue::Mapping(entries) = &mut section.value.value {
+                        entries.retain(|entry| {
+                            let name = entry.key.as_str().unwrap_or_default().to_string();
+                            !removed.contains(&(kind.to_owned(), name))
+                        });
+                    }
+                }
+            }
+        }
+        Ok(filtered)
+    }
+}
+
+/// `node` with its pairs replaced, keeping where it was written.
+fn with_pairs(node: &YamlNode, pairs: Vec<YamlPair>) -> YamlNode {
+    YamlNode {
+        value: YamlValue::Mapping(pairs),
+        ..node.to_owned()
+    }
+}
+
+/// The `(section, name)` of every component that the document outside
+/// `components` refers to, directly or through other components. Only refs
+/// within the document are followed.
+fn used_components(document: &YamlNode) -> HashSet<(String, String)> {
+    let mut pending: Vec<&YamlNode> = document
+        .as_mapping()
+        .unwrap_or_default()
+        .iter()
+        .filter(|pair| pair.key.as_str() != Some("components"))
+        .map(|pair| &pair.value)
+        .collect();
+    let mut used = HashSet::new();
+    while let Some(node) = pending.pop() {
+        for reference in node.references() {
+            let segments = reference
+                .strip_prefix('#')
+                .and_then(pointer_segments)
+                .unwrap_or_default();
+            let (section, name) = match segments.as_slice() {
+                [components, section, name, ..] if components == "components" => {
+                    (section.to_owned(), name.to_owned())
+                }
+                _ => continue,
+            };
+            let component = document
+                .get("components")
+                .and_then(|components| components.child(&section))
+                .and_then(|entries| entries.child(&name));
+            if used.insert((section, name)) {
+                pending.extend(component);
+            }
+        }
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::filter::{FilterOptions, Filterer, TreeSitterFilterer},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn filter() -> Result<(), Box<dyn Error>> {
+        let content = r##"openapi: 3.0.0
+tags:
+  - name: pets
+  - name: internal
+paths:
+  /pets:
+    get:
+      tags: [pets]
+      responses:
+        '200':
+          $ref: '#/components/responses/Pets'
+    delete:
+      tags: [internal]
+      responses:
+        '204':
+          $ref: '#/components/responses/Deleted'
+  /admin:
+    x-internal: true
+    get:
+      responses:
+        '200':
+          $ref: '#/components/responses/Deleted'
+  /health:
+    get:
+      x-internal: false
+      responses:
+        '200':
+          description: ok
+components:
+  responses:
+    Pets:
+      description: pets
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+    Deleted:
+      description: deleted
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Receipt'
+  schemas:
+    Pet:
+      type: object
+    Receipt:
+      type: object
+    Orphan:
+      type: object
+"##;
+        let contents = HashMap::from([(PathBuf::from("#"), content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let options = FilterOptions {
+            tags: vec!["internal".to_string()],
+            extensions: vec!["x-internal".to_string()],
+        };
+        let filtered = TreeSitterFilterer::new(provider)
+            .with_options(options)
+            .filter()?;
+        assert_eq!(
+            filtered.to_yaml(),
+            r##"openapi: 3.0.0
+tags:
+  - name: pets
+paths:
+  /pets:
+    get:
+      tags:
+        - pets
+      responses:
+        '200':
+          $ref: '#/components/responses/Pets'
+  /health:
+    get:
+      x-internal: false
+      responses:
+        '200':
+          description: ok
+components:
+  responses:
+    Pets:
+      description: pets
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+  schemas:
+    Pet:
+      type: object
+    Orphan:
+      type: object
+"##
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,352 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    bundle::add_schemas,
+    validate::HTTP_METHODS,
+    yaml::{escape_pointer_segment, YamlNode, YamlValue},
+};
+
+pub trait Flattener {
+    /// The root document with the inline schemas of its operations moved
+    /// into `components/schemas`.
+    fn flatten(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterFlattener {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterFlattener {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl Flattener for TreeSitterFlattener {
+    fn flatten(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        let root = self
+            .provider
+            .get_document(PathBuf::from("#"))
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Flatten)
+            })?;
+        Ok(flatten_document(&root))
+    }
+}
+
+/// Moves the inline object schemas of the request bodies, responses and
+/// parameters in `root` into `components/schemas` and refers to them there.
+/// Each is named after its operation, such as `ListPetsResponseBody`. Only
+/// the root document is changed, anything behind a `$ref` is left as it is.
+pub fn flatten_document(root: &YamlNode) -> YamlNode {
+    let mut document = root.to_owned();
+    let mut flatten = Flatten {
+        names: document
+            .get("components")
+            .and_then(|components| components.get("schemas"))
+            .and_then(|schemas| schemas.as_mapping())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|pair| pair.key.as_str().map(str::to_owned))
+            .collect(),
+        hoisted: vec![],
+    };
+    if let Some(YamlValue::Mapping(paths)) = document.get_mut("paths").map(|paths| &mut paths.value)
+    {
+        for path in paths {
+            let path_name = pascal_case(path.key.as_str().unwrap_or_default());
+            if let Some(parameters) = path.value.get_mut("parameters") {
+                flatten.parameters(parameters, &path_name);
+            }
+            for method in HTTP_METHODS {
+                let operation = match path.value.get_mut(method) {
+                    Some(operation) => operation,
+                    None => continue,
+                };
+                let name = match operation.get("operationId").and_then(|id| id.as_str()) {
+                    Some(id) => pascal_case(id),
+                    None => format!("{}{}", pascal_case(method), path_name),
+                };
+                flatten.operation(operation, &name);
+            }
+        }
+    }
+    if !flatten.hoisted.is_empty() {
+        add_schemas(&mut document, flatten.hoisted);
+    }
+    document
+}
+
+/// Bookkeeping for a single flatten run.
+struct Flatten {
+    /// The names taken under `components/schemas`.
+    names: HashSet<String>,
+    /// The schemas moved out so far, in the order they were found.
+    hoisted: Vec<(String, YamlNode)>,
+}
+
+impl Flatten {
+    fn operation(&mut self, operation: &mut YamlNode, name: &str) {
+        if let Some(parameters) = operation.get_mut("parameters") {
+            self.parameters(parameters, name);
+        }
+        if let Some(request_body) = operation.get_mut("requestBody") {
+            self.content(request_body, &format!("{}RequestBody", name));
+        }
+        let responses = match operation.get_mut("responses") {
+            Some(YamlNode {
+                value: YamlValue::Mapping(responses),
+                ..
+            }) => responses,
+            _ => return,
+        };
+        // The first successful response is the response body, the others are
+        // told apart by their status.
+        let success = responses
+            .iter()
+            .position(|pair| pair.key.as_str().unwrap_or_default().starts_with('2'));
+        for (index, response) in responses.iter_mut().enumerate() {
+            let status = match Some(index) == success {
+                true => String::new(),
+                false => pascal_case(response.key.as_str().unwrap_or_default()),
+            };
+            let schema_name = format!("{}{}ResponseBody", name, status);
+            self.content(&mut response.value, &schema_name);
+        }
+    }
+
+    fn parameters(&mut self, parameters: &mut YamlNode, name: &str) {
+        let parameters = match &mut parameters.value {
+            YamlValue::Sequence(parameters) => parameters,
+            _ => return,
+        };
+        for parameter in parameters {
+            let parameter_name = parameter
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default();
+            let schema_name = format!("{}{}Parameter", name, pascal_case(parameter_name));
+            if let Some(schema) = parameter.get_mut("schema") {
+                self.hoist(schema, &schema_name);
+            }
+            self.content(parameter, &schema_name);
+        }
+    }
+
+    /// Hoists the schema of every media type under the `content` of `node`.
+    fn content(&mut self, node: &mut YamlNode, name: &str) {
+        let media_types = match node.get_mut("content") {
+            Some(YamlNode {
+                value: YamlValue::Mapping(media_types),
+                ..
+            }) => media_types,
+            _ => return,
+        };
+        for media_type in media_types {
+            if let Some(schema) = media_type.value.get_mut("schema") {
+                self.hoist(schema, name);
+            }
+        }
+    }
+
+    /// Replaces `schema` by a ref to a copy of it under `components/schemas`
+    /// when it is an inline object or composition. An array of those has its
+    /// items moved instead.
+    fn hoist(&mut self, schema: &mut YamlNode, name: &str) {
+        if !is_object(schema) {
+            if schema.get("type").and_then(|kind| kind.as_str()) == Some("array") {
+                if let Some(items) = schema.get_mut("items") {
+                    self.hoist(items, &format!("{}Item", name));
+                }
+            }
+            return;
+        }
+        let mut unique = name.to_string();
+        let mut number = 1;
+        while self.names.contains(&unique) {
+            number += 1;
+            unique = format!("{}{}", name, number);
+        }
+        self.names.insert(unique.to_owned());
+        let reference = format!("#/components/schemas/{}", escape_pointer_segment(&unique));
+        let reference = YamlNode::mapping(vec![("$ref", YamlNode::string(&reference))]);
+        let hoisted = std::mem::replace(schema, reference);
+        self.hoisted.push((unique, hoisted));
+    }
+}
+
+/// Whether `schema` is written out in place as an object or a composition of
+/// schemas, the schemas generators need a name for.
+pub fn is_object(schema: &YamlNode) -> bool {
+    if schema.as_mapping().is_none() || schema.reference().is_some() {
+        return false;
+    }
+    schema.get("type").and_then(|kind| kind.as_str()) == Some("object")
+        || ["properties", "allOf", "oneOf", "anyOf"]
+            .iter()
+            .any(|key| schema.get(key).is_some())
+}
+
+/// `listPets` as `ListPets` and `/pets/{petId}` as `PetsPetId`.
+pub fn pascal_case(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::bindings::yaml::parse_yaml;
+
+    use super::{flatten_document, pascal_case};
+
+    #[test]
+    fn test_flatten_document() -> Result<(), Box<dyn Error>> {
+        let root = parse_yaml(
+            r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            type: object
+            properties:
+              tag:
+                type: string
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+        default:
+          description: Failed
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  code:
+                    type: integer
+  /pets/{petId}:
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              allOf:
+                - $ref: '#/components/schemas/Pet'
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+"#,
+        )?;
+        let expected = r#"paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - name: filter
+          in: query
+          schema:
+            $ref: '#/components/schemas/ListPetsFilterParameter'
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/ListPetsResponseBodyItem'
+        default:
+          description: Failed
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/ListPetsDefaultResponseBody'
+  /pets/{petId}:
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/PutPetsPetIdRequestBody'
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+    ListPetsFilterParameter:
+      type: object
+      properties:
+        tag:
+          type: string
+    ListPetsResponseBodyItem:
+      type: object
+      properties:
+        name:
+          type: string
+    ListPetsDefaultResponseBody:
+      type: object
+      properties:
+        code:
+          type: integer
+    PutPetsPetIdRequestBody:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+"#;
+        assert_eq!(flatten_document(&root).to_yaml(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("listPets"), "ListPets");
+        assert_eq!(pascal_case("/pets/{petId}"), "PetsPetId");
+        assert_eq!(pascal_case("X-Rate-Limit"), "XRateLimit");
+        assert_eq!(pascal_case("default"), "Default");
+    }
+}
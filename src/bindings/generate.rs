@@ -0,0 +1,239 @@
+use anyhow::Result;
+use std::{collections::HashMap, path::Path};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::{identity, Resolver},
+    yaml::{YamlNode, YamlPair, YamlValue},
+};
+
+pub const COMPONENT_PREFIX: &str = "#/components/schemas/";
+
+/// A schema of `components/schemas` ready for code generation. Every `$ref`
+/// in it is written as `#/components/schemas/Name` when it leads to another
+/// component, and any other ref is replaced by its target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentSchema {
+    pub name: String,
+    pub schema: YamlNode,
+}
+
+/// What a `$ref` in a [`ComponentSchema`] leads to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SchemaRef<'a> {
+    /// The component schema with this name.
+    Component(&'a str),
+    /// A recursive schema that isn't a component, left as a ref because
+    /// inlining it would never end.
+    Recursive,
+}
+
+impl<'a> SchemaRef<'a> {
+    /// The target of the `$ref` of `schema`, if it has one.
+    pub fn of(schema: &'a YamlNode) -> Option<Self> {
+        let reference = schema.reference()?;
+        Some(match reference.strip_prefix(COMPONENT_PREFIX) {
+            Some(name) => SchemaRef::Component(name),
+            None => SchemaRef::Recursive,
+        })
+    }
+}
+
+pub trait GenerateParser {
+    /// Every component schema in the order they are written.
+    fn get_component_schemas(&self) -> Result<Vec<ComponentSchema>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterGenerateParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterGenerateParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn component_schemas(&self) -> Result<Vec<ComponentSchema>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/schemas")?;
+        let mut names = HashMap::new();
+        for entry in &entries {
+            names
+                .entry(identity(self.provider.as_ref(), &entry.file, &entry.value))
+                .or_insert_with(|| entry.name.to_owned());
+        }
+        let mut schemas = vec![];
+        for entry in &entries {
+            let name = &names[&identity(self.provider.as_ref(), &entry.file, &entry.value)];
+            // A component that only refers to another one is an alias of it.
+            let schema = match name == &entry.name {
+                true => {
+                    let mut normalizer = Normalizer {
+                        parser: self,
+                        names: &names,
+                        stack: vec![],
+                    };
+                    normalizer.normalize(&mut resolver, &entry.value, &entry.file)?
+                }
+                false => component_ref(name),
+            };
+            schemas.push(ComponentSchema {
+                name: entry.name.to_owned(),
+                schema,
+            });
+        }
+        Ok(schemas)
+    }
+}
+
+impl GenerateParser for TreeSitterGenerateParser {
+    fn get_component_schemas(&self) -> Result<Vec<ComponentSchema>, OpenapiSchemerError> {
+        self.component_schemas()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Generate))
+    }
+}
+
+/// Rewrites the refs below a component schema.
+struct Normalizer<'a> {
+    parser: &'a TreeSitterGenerateParser,
+    /// The component name of each component schema by its identity.
+    names: &'a HashMap<String, String>,
+    /// The identities of the targets currently being inlined, so recursive
+    /// schemas that aren't components keep their ref instead of repeating.
+    stack: Vec<String>,
+}
+
+impl Normalizer<'_> {
+    fn normalize(
+        &mut self,
+        resolver: &mut Resolver,
+        node: &YamlNode,
+        file: &Path,
+    ) -> Result<YamlNode> {
+        if node.reference().is_some() {
+            let (target, target_file) = resolver.follow(node, file)?;
+            let id = identity(self.parser.provider.as_ref(), &target_file, &target);
+            if let Some(name) = self.names.get(&id) {
+                return Ok(component_ref(name));
+            }
+            if self.stack.contains(&id) {
+                return Ok(node.to_owned());
+            }
+            self.stack.push(id);
+            let inlined = self.normalize(resolver, &target, &target_file);
+            self.stack.pop();
+            return inlined;
+        }
+        let value = match &node.value {
+            YamlValue::Mapping(pairs) => {
+                let mut normalized = vec![];
+                for pair in pairs {
+                    normalized.push(YamlPair {
+                        key: pair.key.to_owned(),
+                        value: self.normalize(resolver, &pair.value, file)?,
+                    });
+                }
+                YamlValue::Mapping(normalized)
+            }
+            YamlValue::Sequence(items) => {
+                let mut normalized = vec![];
+                for item in items {
+                    normalized.push(self.normalize(resolver, item, file)?);
+                }
+                YamlValue::Sequence(normalized)
+            }
+            other => other.to_owned(),
+        };
+        Ok(YamlNode {
+            value,
+            ..node.to_owned()
+        })
+    }
+}
+
+fn component_ref(name: &str) -> YamlNode {
+    YamlNode::mapping(vec![(
+        "$ref",
+        YamlNode::string(&format!("{}{}", COMPONENT_PREFIX, name)),
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::generate::{GenerateParser, TreeSitterGenerateParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_component_schemas() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+components:
+  schemas:
+    Pet:
+      $ref: 'pet.yaml'
+    Animal:
+      $ref: '#/components/schemas/Pet'
+    Owner:
+      type: object
+      properties:
+        pets:
+          type: array
+          items:
+            $ref: 'pet.yaml'
+        address:
+          $ref: '#/components/x-shared/Address'
+  x-shared:
+    Address:
+      type: object
+      properties:
+        next:
+          $ref: '#/components/x-shared/Address'
+"##;
+        let pet_content = r#"type: object
+properties:
+  name:
+    type: string
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let schemas = TreeSitterGenerateParser::new(provider).get_component_schemas()?;
+        let yaml: Vec<(&str, String)> = schemas
+            .iter()
+            .map(|schema| (schema.name.as_str(), schema.schema.to_yaml()))
+            .collect();
+        assert_eq!(
+            yaml[0],
+            (
+                "Pet",
+                "type: object\nproperties:\n  name:\n    type: string\n".to_string()
+            )
+        );
+        assert_eq!(
+            yaml[1],
+            ("Animal", "$ref: '#/components/schemas/Pet'\n".to_string())
+        );
+        assert_eq!(
+            yaml[2].1,
+            r##"type: object
+properties:
+  pets:
+    type: array
+    items:
+      $ref: '#/components/schemas/Pet'
+  address:
+    type: object
+    properties:
+      next:
+        $ref: '#/components/x-shared/Address'
+"##
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+pub trait GetParser {
+    /// The node at `pointer`, such as `/paths/~1pets/get` or
+    /// `pets.yaml#/Pet`, with the refs along the way and at the node itself
+    /// followed. With `inline` the refs below it are replaced as well.
+    fn get_node(
+        &self,
+        pointer: &str,
+        inline: bool,
+    ) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterGetParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterGetParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn node(&self, pointer: &str, inline: bool) -> Result<Option<YamlNode>> {
+        let (file, fragment) = match pointer.split_once('#') {
+            Some(("", fragment)) => (PathBuf::from("#"), fragment),
+            Some((file, fragment)) => (PathBuf::from(file), fragment),
+            None => (PathBuf::from("#"), pointer),
+        };
+        if !fragment.is_empty() && !fragment.starts_with('/') {
+            bail!("`{}` is not a JSON pointer, those start with `/`", pointer);
+        }
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let (node, file) = match resolver.resolve_pointer(&file, fragment)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let (node, file) = resolver.follow(&node, &file)?;
+        match inline {
+            true => resolver.inline(&node, &file).map(Some),
+            false => Ok(Some(node)),
+        }
+    }
+}
+
+impl GetParser for TreeSitterGetParser {
+    fn get_node(
+        &self,
+        pointer: &str,
+        inline: bool,
+    ) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        self.node(pointer, inline)
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Get))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::get::{GetParser, TreeSitterGetParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_node() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets/{petId}:
+    $ref: 'paths.yaml#/Pet'
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+"##;
+        let paths_content = r##"Pet:
+  get:
+    operationId: showPet
+    tags: [pets, public]
+    responses:
+      '200':
+        content:
+          application/json:
+            schema:
+              $ref: 'openapi.yaml#/components/schemas/Pet'
+"##;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("openapi.yaml"), root_content.to_owned()),
+            (PathBuf::from("paths.yaml"), paths_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterGetParser::new(provider);
+        let get = |pointer: &str, inline| {
+            parser
+                .get_node(pointer, inline)
+                .unwrap()
+                .map(|node| node.to_yaml())
+        };
+        assert_eq!(
+            get("/paths/~1pets~1%7BpetId%7D/get/operationId", false),
+            Some("showPet\n".to_string())
+        );
+        assert_eq!(
+            get("#/paths/~1pets~1{petId}/get/tags/1", false),
+            Some("public\n".to_string())
+        );
+        let schema = "/paths/~1pets~1{petId}/get/responses/200/content/application~1json/schema";
+        assert_eq!(
+            get(schema, false),
+            Some("type: object\nproperties:\n  name:\n    type: string\n".to_string())
+        );
+        assert_eq!(
+            get("paths.yaml#/Pet/get/responses/200/content", false),
+            Some(
+                "application/json:\n  schema:\n    $ref: 'openapi.yaml#/components/schemas/Pet'\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            get("paths.yaml#/Pet/get/responses/200/content", true),
+            Some(
+                "application/json:\n  schema:\n    type: object\n    properties:\n      name:\n        type: string\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(get("/paths/~1pets/get", false), None);
+        assert_eq!(
+            parser.get_node("paths", false).unwrap_err().to_string(),
+            "`paths` is not a JSON pointer, those start with `/`"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{anyhow, Context, Result};
+
+use super::yaml::{parse_yaml, YamlNode};
+
+/// An HTTP exchange recorded in a HAR file, as browsers and proxies export
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HarEntry {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    pub response_headers: Vec<(String, String)>,
+    pub response_content_type: Option<String>,
+    /// The response body, when it was recorded as JSON or YAML text.
+    /// Base64 encoded and truncated bodies are left out.
+    pub response_body: Option<YamlNode>,
+}
+
+/// The entries of the HAR document `content`. Entries without a response,
+/// which HAR records with status 0, are skipped.
+pub fn parse_har(content: &str) -> Result<Vec<HarEntry>> {
+    let document = parse_yaml(content).context("Failed to parse HAR")?;
+    let entries = document
+        .get("log")
+        .and_then(|log| log.get("entries"))
+        .and_then(YamlNode::as_sequence)
+        .ok_or_else(|| anyhow!("Expected a HAR document with `log.entries`"))?;
+    let mut results = vec![];
+    for (index, entry) in entries.iter().enumerate() {
+        let field = |parent: &str, key: &str| {
+            entry
+                .get(parent)
+                .and_then(|parent| parent.get(key))
+                .and_then(YamlNode::as_str)
+                .map(str::to_owned)
+                .ok_or_else(|| {
+                    anyhow!("Entry {} of the HAR has no `{}.{}`", index + 1, parent, key)
+                })
+        };
+        let status = field("response", "status")?;
+        let status = status
+            .parse::<u16>()
+            .map_err(|_| anyhow!("Entry {} of the HAR has the status `{}`", index + 1, status))?;
+        if status == 0 {
+            continue;
+        }
+        let response = entry.get("response");
+        let headers = response
+            .and_then(|response| response.get("headers"))
+            .and_then(YamlNode::as_sequence)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|header| {
+                let name = header.get("name")?.as_str()?;
+                let value = header.get("value")?.as_str()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let content = response.and_then(|response| response.get("content"));
+        let content_type = content
+            .and_then(|content| content.get("mimeType"))
+            .and_then(YamlNode::as_str)
+            .filter(|mime_type| !mime_type.is_empty())
+            .map(str::to_owned);
+        let is_text = content
+            .and_then(|content| content.get("encoding"))
+            .is_none();
+        let is_structured = content_type
+            .as_deref()
+            .is_some_and(|mime_type| mime_type.contains("json") || mime_type.contains("yaml"));
+        let body = content
+            .and_then(|content| content.get("text"))
+            .and_then(YamlNode::as_str)
+            .filter(|text| is_text && is_structured && !text.trim().is_empty())
+            .and_then(|text| parse_yaml(text).ok());
+        results.push(HarEntry {
+            method: field("request", "method")?,
+            url: field("request", "url")?,
+            status,
+            response_headers: headers,
+            response_content_type: content_type,
+            response_body: body,
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::bindings::har::parse_har;
+
+    #[test]
+    fn parse_entries() -> Result<(), Box<dyn Error>> {
+        let content = r#"{
+  "log": {
+    "version": "1.2",
+    "entries": [
+      {
+        "request": {"method": "GET", "url": "http://localhost/v1/pets/1", "headers": []},
+        "response": {
+          "status": 200,
+          "headers": [{"name": "Content-Type", "value": "application/json"}],
+          "content": {"mimeType": "application/json", "text": "{\"id\": 1}"}
+        }
+      },
+      {
+        "request": {"method": "GET", "url": "http://localhost/v1/pets", "headers": []},
+        "response": {"status": 0, "headers": [], "content": {}}
+      },
+      {
+        "request": {"method": "GET", "url": "http://localhost/logo.png", "headers": []},
+        "response": {
+          "status": 200,
+          "headers": [],
+          "content": {"mimeType": "image/png", "text": "iVBORw0K", "encoding": "base64"}
+        }
+      }
+    ]
+  }
+}"#;
+        let entries = parse_har(content)?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            (
+                entries[0].method.as_str(),
+                entries[0].url.as_str(),
+                entries[0].status
+            ),
+            ("GET", "http://localhost/v1/pets/1", 200)
+        );
+        assert_eq!(
+            entries[0].response_headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(
+            entries[0].response_body.as_ref().map(|body| body.to_json()),
+            Some("{\n  \"id\": 1\n}".to_string())
+        );
+        assert_eq!(entries[1].response_body, None);
+        assert_eq!(
+            parse_har("{\"log\": {}}").unwrap_err().to_string(),
+            "Expected a HAR document with `log.entries`"
+        );
+        Ok(())
+    }
+}
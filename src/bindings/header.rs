@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderNode {
+    /// The key under `components/headers`, which is the name of the header.
+    pub text: String,
+    /// The `type` of the header's schema, if it has one.
+    pub schema_type: Option<String>,
+    pub required: bool,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait HeaderParser {
+    fn get_header_nodes(&self) -> Result<Vec<HeaderNode>, OpenapiSchemerError>;
+    fn get_header(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterHeaderParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterHeaderParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl HeaderParser for TreeSitterHeaderParser {
+    fn get_header_nodes(&self) -> Result<Vec<HeaderNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::HeaderList)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for entry in resolver.entries("/components/headers").map_err(to_error)? {
+            let schema_type = match entry.value.get("schema") {
+                Some(schema) => resolver
+                    .follow(schema, &entry.file)
+                    .map_err(to_error)?
+                    .0
+                    .get("type")
+                    .and_then(|schema_type| schema_type.as_str())
+                    .map(str::to_owned),
+                None => None,
+            };
+            results.push(HeaderNode {
+                schema_type,
+                required: entry
+                    .value
+                    .get("required")
+                    .and_then(|required| required.as_str())
+                    == Some("true"),
+                file: self.provider.source_path(&entry.key_file),
+                line: entry.key.start.row + 1,
+                column: entry.key.start.column + 1,
+                text: entry.name,
+            });
+        }
+        Ok(results)
+    }
+
+    fn get_header(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/headers").map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::HeaderShow)
+        })?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::header::{HeaderNode, HeaderParser, TreeSitterHeaderParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterHeaderParser {
+        let root_content = r#"
+components:
+  headers:
+    X-Rate-Limit:
+      required: true
+      schema:
+        type: integer
+    X-Request-Id:
+      $ref: 'headers/requestId.yaml'
+"#;
+        let request_id_content = r#"
+description: Echoes the id of the request
+schema:
+  $ref: '../schemas.yaml#/RequestId'
+"#;
+        let schemas_content = r#"
+RequestId:
+  type: string
+  format: uuid
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("headers/requestId.yaml"),
+                request_id_content.to_owned(),
+            ),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterHeaderParser::new(provider)
+    }
+
+    #[test]
+    fn get_header_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_header_nodes()?;
+        assert_eq!(
+            nodes,
+            vec![
+                HeaderNode {
+                    text: "X-Rate-Limit".to_string(),
+                    schema_type: Some("integer".to_string()),
+                    required: true,
+                    file: PathBuf::from("#"),
+                    line: 4,
+                    column: 5,
+                },
+                HeaderNode {
+                    text: "X-Request-Id".to_string(),
+                    schema_type: Some("string".to_string()),
+                    required: false,
+                    file: PathBuf::from("#"),
+                    line: 8,
+                    column: 5,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_header() -> Result<(), Box<dyn Error>> {
+        let header = parser().get_header("X-Request-Id")?.unwrap();
+        assert_eq!(
+            header.to_yaml(),
+            "description: Echoes the id of the request\nschema:\n  $ref: '../schemas.yaml#/RequestId'\n"
+        );
+        assert!(parser().get_header("missing")?.is_none());
+        Ok(())
+    }
+}
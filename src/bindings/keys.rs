@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    yaml::{YamlNode, YamlValue},
+};
+
+/// An entry of a mapping, or an item of a list, named by its key or index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyNode {
+    pub key: String,
+    /// The value as written, refs included.
+    pub value: YamlNode,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait KeyParser {
+    /// The children of the node at the dotted `path`, such as
+    /// `components.schemas`, following refs on the way. An empty path is the
+    /// root document. `None` when there is nothing at the path.
+    fn get_keys(&self, path: &str) -> Result<Option<Vec<KeyNode>>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterKeyParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterKeyParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn keys(&self, path: &str) -> Result<Option<Vec<KeyNode>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root = PathBuf::from("#");
+        let mut current = (resolver.document(&root)?.to_owned(), root);
+        for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+            let (node, file) = resolver.follow(&current.0, &current.1)?;
+            current = match node.child(segment) {
+                Some(child) => (child.to_owned(), file),
+                None => return Ok(None),
+            };
+        }
+        let (node, file) = resolver.follow(&current.0, &current.1)?;
+        let key_node = |key: &str, position: &YamlNode, value: &YamlNode| KeyNode {
+            key: key.to_string(),
+            value: value.to_owned(),
+            file: self.provider.source_path(&file),
+            line: position.start.row + 1,
+            column: position.start.column + 1,
+        };
+        let keys = match &node.value {
+            YamlValue::Mapping(pairs) => pairs
+                .iter()
+                .map(|pair| {
+                    key_node(
+                        pair.key.as_str().unwrap_or_default(),
+                        &pair.key,
+                        &pair.value,
+                    )
+                })
+                .collect(),
+            YamlValue::Sequence(items) => items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| key_node(&index.to_string(), item, item))
+                .collect(),
+            _ => bail!("`{}` is neither a mapping nor a list", path),
+        };
+        Ok(Some(keys))
+    }
+}
+
+impl KeyParser for TreeSitterKeyParser {
+    fn get_keys(&self, path: &str) -> Result<Option<Vec<KeyNode>>, OpenapiSchemerError> {
+        self.keys(path)
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::keys::{KeyParser, TreeSitterKeyParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_keys() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.0.0
+components:
+  schemas:
+    $ref: 'schemas.yaml'
+tags:
+  - name: pets
+  - name: owners
+"#;
+        let schemas_content = r#"Pet:
+  type: object
+Error:
+  $ref: 'error.yaml'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterKeyParser::new(provider);
+        let keys = |path: &str| -> Option<Vec<(String, String, usize, usize)>> {
+            parser.get_keys(path).unwrap().map(|keys| {
+                keys.into_iter()
+                    .map(|key| {
+                        (
+                            key.key,
+                            key.file.display().to_string(),
+                            key.line,
+                            key.column,
+                        )
+                    })
+                    .collect()
+            })
+        };
+        assert_eq!(
+            keys("components.schemas"),
+            Some(vec![
+                ("Pet".to_string(), "schemas.yaml".to_string(), 1, 1),
+                ("Error".to_string(), "schemas.yaml".to_string(), 3, 1),
+            ])
+        );
+        assert_eq!(
+            keys("tags"),
+            Some(vec![
+                ("0".to_string(), "#".to_string(), 6, 5),
+                ("1".to_string(), "#".to_string(), 7, 5),
+            ])
+        );
+        assert_eq!(keys("").map(|keys| keys.len()), Some(3));
+        assert_eq!(keys("components.parameters"), None);
+        assert_eq!(
+            parser.get_keys("openapi").unwrap_err().to_string(),
+            "`openapi` is neither a mapping nor a list"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkNode {
+    /// The key under `components/links`.
+    pub text: String,
+    /// The `operationId` the link points at, or its `operationRef`.
+    pub target: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait LinkParser {
+    fn get_link_nodes(&self) -> Result<Vec<LinkNode>, OpenapiSchemerError>;
+    fn get_link(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterLinkParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterLinkParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl LinkParser for TreeSitterLinkParser {
+    fn get_link_nodes(&self) -> Result<Vec<LinkNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/links").map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::LinkList)
+        })?;
+        let results = entries
+            .into_iter()
+            .map(|entry| LinkNode {
+                target: ["operationId", "operationRef"]
+                    .iter()
+                    .find_map(|key| entry.value.get(key).and_then(|target| target.as_str()))
+                    .map(str::to_owned),
+                file: self.provider.source_path(&entry.key_file),
+                line: entry.key.start.row + 1,
+                column: entry.key.start.column + 1,
+                text: entry.name,
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_link(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/links").map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::LinkShow)
+        })?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::link::{LinkNode, LinkParser, TreeSitterLinkParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterLinkParser {
+        let root_content = r#"
+components:
+  links:
+    GetPetById:
+      operationId: showPetById
+      parameters:
+        petId: $response.body#/id
+    GetOwner:
+      operationRef: '#/paths/~1owners~1{ownerId}/get'
+    Unfinished:
+      description: Points nowhere yet
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterLinkParser::new(provider)
+    }
+
+    #[test]
+    fn get_link_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_link_nodes()?;
+        let link = |text: &str, target: Option<&str>, line| LinkNode {
+            text: text.to_string(),
+            target: target.map(str::to_owned),
+            file: PathBuf::from("#"),
+            line,
+            column: 5,
+        };
+        assert_eq!(
+            nodes,
+            vec![
+                link("GetPetById", Some("showPetById"), 4),
+                link("GetOwner", Some("#/paths/~1owners~1{ownerId}/get"), 8),
+                link("Unfinished", None, 10),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_link() -> Result<(), Box<dyn Error>> {
+        let link = parser().get_link("GetPetById")?.unwrap();
+        assert_eq!(
+            link.to_yaml(),
+            "operationId: showPetById\nparameters:\n  petId: $response.body#/id\n"
+        );
+        assert!(parser().get_link("missing")?.is_none());
+        Ok(())
+    }
+}
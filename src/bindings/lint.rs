@@ -0,0 +1,1094 @@
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    edit::{FileEdits, TextEdit},
+    flatten::pascal_case,
+    rename::{file_edits, replace},
+    resolve::{split_ref, Resolver},
+    ruleset::CustomRule,
+    validate::{SpecVersion, HTTP_METHODS},
+    yaml::{ScalarStyle, YamlNode, YamlPair, YamlValue},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    /// Whether `lint --fix` can rewrite the spec so the problem goes away.
+    pub fixable: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Hint => "hint",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What running the lint rules found: the problems and the edits fixing
+/// them, by the file they are made to.
+struct LintRun {
+    diagnostics: Vec<LintDiagnostic>,
+    fixes: Vec<(PathBuf, TextEdit)>,
+}
+
+/// Collects the diagnostics of a single rule.
+pub struct Reporter<'a> {
+    provider: &'a dyn ContentProvider,
+    rule: &'a dyn LintRule,
+    diagnostics: Vec<LintDiagnostic>,
+    /// The edits fixing the reported problems, by the file they are made to.
+    fixes: Vec<(PathBuf, TextEdit)>,
+}
+
+impl<'a> Reporter<'a> {
+    pub fn report(&mut self, file: &Path, node: &YamlNode, message: String) {
+        self.push(file, node, message, false);
+    }
+
+    /// Reports a problem along with the edits to `file` that fix it.
+    pub fn report_fix(
+        &mut self,
+        file: &Path,
+        node: &YamlNode,
+        message: String,
+        edits: Vec<TextEdit>,
+    ) {
+        self.push(file, node, message, true);
+        self.fixes
+            .extend(edits.into_iter().map(|edit| (file.to_path_buf(), edit)));
+    }
+
+    /// The text of `file`, for rules that edit it.
+    pub fn content(&self, file: &Path) -> Result<String> {
+        Ok(self.provider.get_content(file.to_path_buf())?)
+    }
+
+    fn push(&mut self, file: &Path, node: &YamlNode, message: String, fixable: bool) {
+        self.diagnostics.push(LintDiagnostic {
+            rule: self.rule.name().to_string(),
+            severity: self.rule.severity(),
+            file: self.provider.source_path(file),
+            line: node.start.row + 1,
+            column: node.start.column + 1,
+            message,
+            fixable,
+        });
+    }
+}
+
+/// A single check that can be turned on and off by name. New rules only need
+/// to implement this trait and be added to `rules`.
+pub trait LintRule {
+    /// The kebab-case name used to enable or disable the rule.
+    fn name(&self) -> &str;
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()>;
+}
+
+/// Every rule known to the linter, all of which are enabled by default.
+pub fn rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(OperationIdCamelCase),
+        Box::new(OperationSummary),
+        Box::new(NoEmptyDescription),
+        Box::new(PathParametersDeclared),
+        Box::new(NullableStyle),
+        Box::new(OperationIdRequired),
+        Box::new(OperationTagsSorted),
+        Box::new(KeyTrailingWhitespace),
+        Box::new(AmbiguousBoolean),
+    ]
+}
+
+struct OperationIdCamelCase;
+
+impl LintRule for OperationIdCamelCase {
+    fn name(&self) -> &str {
+        "operation-id-camel-case"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for operation in resolver.operations()? {
+            let id = match operation.operation.get("operationId") {
+                Some(id) => id,
+                None => continue,
+            };
+            let text = id.as_str().unwrap_or_default();
+            let mut chars = text.chars();
+            let camel_case = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+                && chars.all(|c| c.is_ascii_alphanumeric());
+            if !camel_case {
+                reporter.report(
+                    &operation.file,
+                    id,
+                    format!("operationId `{}` should be camelCase", text),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+struct OperationSummary;
+
+impl LintRule for OperationSummary {
+    fn name(&self) -> &str {
+        "operation-summary"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for operation in resolver.operations()? {
+            let has_summary = operation
+                .operation
+                .get("summary")
+                .is_some_and(|summary| !summary.is_null());
+            if !has_summary {
+                reporter.report(
+                    &operation.file,
+                    &operation.operation,
+                    format!("Operation `{}` has no summary", operation.describe()),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+struct NoEmptyDescription;
+
+impl NoEmptyDescription {
+    fn walk(
+        &self,
+        node: &YamlNode,
+        file: &Path,
+        reporter: &mut Reporter,
+        files: &mut Vec<PathBuf>,
+    ) {
+        if let Some(reference) = node.reference() {
+            let (target_file, _) = split_ref(file, reference);
+            if !files.contains(&target_file) {
+                files.push(target_file);
+            }
+            return;
+        }
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    let empty = match pair.value.as_str() {
+                        Some(text) => text.trim().is_empty() || pair.value.is_null(),
+                        None => false,
+                    };
+                    if pair.key.as_str() == Some("description") && empty {
+                        reporter.report(file, &pair.key, "Description is empty".to_string());
+                    }
+                    self.walk(&pair.value, file, reporter, files);
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for item in items {
+                    self.walk(item, file, reporter, files);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl LintRule for NoEmptyDescription {
+    fn name(&self) -> &str {
+        "no-empty-description"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        // Every file reachable through refs is checked once, as a whole.
+        let mut files = vec![PathBuf::from("#")];
+        let mut index = 0;
+        while index < files.len() {
+            let file = files[index].to_owned();
+            let document = resolver.document(&file)?.to_owned();
+            self.walk(&document, &file, reporter, &mut files);
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+struct PathParametersDeclared;
+
+impl PathParametersDeclared {
+    fn path_parameter_names(
+        resolver: &mut Resolver,
+        parameters: Option<&YamlNode>,
+        file: &Path,
+    ) -> Result<HashSet<String>> {
+        let mut names = HashSet::new();
+        for parameter in parameters
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default()
+        {
+            let (parameter, _) = resolver.follow(parameter, file)?;
+            let location = parameter.get("in").and_then(|location| location.as_str());
+            if location == Some("path") {
+                if let Some(name) = parameter.get("name").and_then(|name| name.as_str()) {
+                    names.insert(name.to_string());
+                }
+            }
+        }
+        Ok(names)
+    }
+}
+
+impl LintRule for PathParametersDeclared {
+    fn name(&self) -> &str {
+        "path-parameters-declared"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for path_item in resolver.entries("/paths")? {
+            let templated: Vec<&str> = path_item
+                .name
+                .split('{')
+                .skip(1)
+                .filter_map(|segment| segment.split_once('}').map(|(name, _)| name))
+                .collect();
+            if templated.is_empty() {
+                continue;
+            }
+            let shared = Self::path_parameter_names(
+                resolver,
+                path_item.value.get("parameters"),
+                &path_item.file,
+            )?;
+            for pair in path_item.value.as_mapping().unwrap_or_default() {
+                let method = match pair.key.as_str() {
+                    Some(method) if HTTP_METHODS.contains(&method) => method,
+                    _ => continue,
+                };
+                let (operation, file) = resolver.follow(&pair.value, &path_item.file)?;
+                let declared =
+                    Self::path_parameter_names(resolver, operation.get("parameters"), &file)?;
+                for name in &templated {
+                    if !shared.contains(*name) && !declared.contains(*name) {
+                        reporter.report(
+                            &file,
+                            &operation,
+                            format!(
+                                "Path parameter `{}` is not declared for operation `{} {}`",
+                                name,
+                                method.to_uppercase(),
+                                path_item.name
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Flags the nullable schemas written in the style of the other OpenAPI 3
+/// version: `type: [T, 'null']` in a 3.0 spec and `nullable` in a 3.1 spec.
+struct NullableStyle;
+
+impl NullableStyle {
+    fn check_schema(
+        &self,
+        node: &YamlNode,
+        pairs: &[YamlPair],
+        file: &Path,
+        content: &str,
+        version: SpecVersion,
+        reporter: &mut Reporter,
+    ) {
+        let find = |name: &str| pairs.iter().find(|pair| pair.key.as_str() == Some(name));
+        let types = find("type");
+        let nullable = find("nullable");
+        match version {
+            SpecVersion::V3_1 => {
+                let nullable = match nullable {
+                    Some(nullable) => nullable,
+                    None => return,
+                };
+                let message = "`nullable` was removed in OpenAPI 3.1, add `null` to the list of types instead".to_string();
+                match Self::to_type_list(types, nullable, content) {
+                    Some(edits) => reporter.report_fix(file, &nullable.key, message, edits),
+                    None => reporter.report(file, &nullable.key, message),
+                }
+            }
+            _ => {
+                let types = match types {
+                    Some(types) => types,
+                    None => return,
+                };
+                let has_null = types
+                    .value
+                    .as_sequence()
+                    .is_some_and(|items| items.iter().any(|item| item.as_str() == Some("null")));
+                if !has_null {
+                    return;
+                }
+                let message =
+                    "`null` in a list of types needs OpenAPI 3.1, use `nullable: true` instead"
+                        .to_string();
+                match Self::to_nullable(node, types, nullable, content) {
+                    Some(edits) => reporter.report_fix(file, &types.key, message, edits),
+                    None => reporter.report(file, &types.key, message),
+                }
+            }
+        }
+    }
+
+    /// Rewrites `type: [T, 'null']` as `type: T` followed by
+    /// `nullable: true`. Only block mappings with a single other type are
+    /// fixed.
+    fn to_nullable(
+        node: &YamlNode,
+        types: &YamlPair,
+        nullable: Option<&YamlPair>,
+        content: &str,
+    ) -> Option<Vec<TextEdit>> {
+        if content.as_bytes().get(node.bytes.start) == Some(&b'{') {
+            return None;
+        }
+        let others: Vec<&YamlNode> = types
+            .value
+            .as_sequence()?
+            .iter()
+            .filter(|item| item.as_str() != Some("null"))
+            .collect();
+        let other = match others.as_slice() {
+            [other] => &content[other.bytes.to_owned()],
+            _ => return None,
+        };
+        let mut text = format!(": {}", other);
+        match nullable {
+            Some(nullable) if nullable.value.as_str() == Some("true") => {}
+            Some(_) => return None,
+            None => {
+                let indent = " ".repeat(types.key.start.column);
+                text.push_str(&format!("\n{}nullable: true", indent));
+            }
+        }
+        let bytes = types.key.bytes.end..types.value.bytes.end;
+        Some(vec![TextEdit {
+            old: content[bytes.to_owned()].to_string(),
+            new: text.to_owned(),
+            text,
+            bytes,
+            line: types.key.start.row + 1,
+            column: types.key.start.column + 1,
+        }])
+    }
+
+    /// Removes the `nullable` line and, when it was `true`, adds `null` to a
+    /// single `type`.
+    fn to_type_list(
+        types: Option<&YamlPair>,
+        nullable: &YamlPair,
+        content: &str,
+    ) -> Option<Vec<TextEdit>> {
+        let mut edits = vec![Self::remove_line(nullable, content)?];
+        if nullable.value.as_str() != Some("true") {
+            return Some(edits);
+        }
+        let types = &types?.value;
+        if let Some(items) = types.as_sequence() {
+            return match items.iter().any(|item| item.as_str() == Some("null")) {
+                true => Some(edits),
+                false => None,
+            };
+        }
+        if types.as_str()? != "null" {
+            let old = content[types.bytes.to_owned()].to_string();
+            let text = format!("[{}, 'null']", old);
+            edits.push(TextEdit {
+                bytes: types.bytes.to_owned(),
+                old,
+                new: text.to_owned(),
+                text,
+                line: types.start.row + 1,
+                column: types.start.column + 1,
+            });
+        }
+        Some(edits)
+    }
+
+    /// The edit removing the line of `pair`, as long as nothing but
+    /// indentation and a comment shares the line with it.
+    fn remove_line(pair: &YamlPair, content: &str) -> Option<TextEdit> {
+        let start = content[..pair.key.bytes.start]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        let end = content[pair.value.bytes.end..]
+            .find('\n')
+            .map_or(content.len(), |newline| pair.value.bytes.end + newline + 1);
+        let before = &content[start..pair.key.bytes.start];
+        let after = content[pair.value.bytes.end..end].trim();
+        if !before.trim().is_empty() || !(after.is_empty() || after.starts_with('#')) {
+            return None;
+        }
+        Some(TextEdit {
+            bytes: start..end,
+            old: content[start..end].to_string(),
+            new: String::new(),
+            text: String::new(),
+            line: pair.key.start.row + 1,
+            column: pair.key.start.column + 1,
+        })
+    }
+}
+
+impl LintRule for NullableStyle {
+    fn name(&self) -> &str {
+        "nullable-style"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        let root = resolver.document(Path::new("#"))?.to_owned();
+        let version = match SpecVersion::detect(&root) {
+            Some(SpecVersion::V2_0) => return Ok(()),
+            version => version.unwrap_or(SpecVersion::V3_0),
+        };
+        for (file, document) in documents(resolver)? {
+            let content = reporter.content(&file)?;
+            for node in nodes(&document) {
+                if let Some(pairs) = node.as_mapping() {
+                    self.check_schema(node, pairs, &file, &content, version, reporter);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct OperationIdRequired;
+
+impl LintRule for OperationIdRequired {
+    fn name(&self) -> &str {
+        "operation-id-required"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        let operations = resolver.operations()?;
+        let mut ids: HashSet<String> = operations
+            .iter()
+            .filter_map(|operation| operation.operation.get("operationId")?.as_str())
+            .map(str::to_owned)
+            .collect();
+        for operation in operations {
+            if operation.operation.get("operationId").is_some() {
+                continue;
+            }
+            let message = format!("Operation `{}` has no operationId", operation.describe());
+            // The id is added as the first key of a block mapping, unless an
+            // operation already has it.
+            let id = format!("{}{}", operation.method, pascal_case(&operation.path));
+            let content = reporter.content(&operation.file)?;
+            let start = operation.operation.bytes.start;
+            let block = operation
+                .operation
+                .as_mapping()
+                .is_some_and(|pairs| !pairs.is_empty())
+                && content.as_bytes().get(start) != Some(&b'{');
+            if !block || !ids.insert(id.to_owned()) {
+                reporter.report(&operation.file, &operation.operation, message);
+                continue;
+            }
+            let indent = " ".repeat(operation.operation.start.column);
+            let edit = TextEdit {
+                bytes: start..start,
+                old: String::new(),
+                text: format!("operationId: {}\n{}", id, indent),
+                new: id,
+                line: operation.operation.start.row + 1,
+                column: operation.operation.start.column + 1,
+            };
+            reporter.report_fix(&operation.file, &operation.operation, message, vec![edit]);
+        }
+        Ok(())
+    }
+}
+
+struct OperationTagsSorted;
+
+impl LintRule for OperationTagsSorted {
+    fn name(&self) -> &str {
+        "operation-tags-sorted"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for operation in resolver.operations()? {
+            let tags = match operation.operation.get("tags") {
+                Some(tags) => tags,
+                None => continue,
+            };
+            let items = tags.as_sequence().unwrap_or_default();
+            let mut sorted: Vec<&YamlNode> = items.iter().collect();
+            sorted.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            if items
+                .iter()
+                .zip(&sorted)
+                .all(|(tag, sorted)| tag.bytes == sorted.bytes)
+            {
+                continue;
+            }
+            // Each tag is replaced by the one sorted into its place, keeping
+            // the layout of the list.
+            let content = reporter.content(&operation.file)?;
+            let edits = items
+                .iter()
+                .zip(&sorted)
+                .filter(|(tag, sorted)| tag.bytes != sorted.bytes)
+                .map(|(tag, sorted)| TextEdit {
+                    bytes: tag.bytes.to_owned(),
+                    old: content[tag.bytes.to_owned()].to_string(),
+                    new: content[sorted.bytes.to_owned()].to_string(),
+                    text: content[sorted.bytes.to_owned()].to_string(),
+                    line: tag.start.row + 1,
+                    column: tag.start.column + 1,
+                })
+                .collect();
+            reporter.report_fix(
+                &operation.file,
+                tags,
+                format!(
+                    "Tags of operation `{}` are not sorted",
+                    operation.describe()
+                ),
+                edits,
+            );
+        }
+        Ok(())
+    }
+}
+
+struct KeyTrailingWhitespace;
+
+impl LintRule for KeyTrailingWhitespace {
+    fn name(&self) -> &str {
+        "key-trailing-whitespace"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for (file, document) in documents(resolver)? {
+            for pairs in nodes(&document)
+                .into_iter()
+                .filter_map(YamlNode::as_mapping)
+            {
+                for pair in pairs {
+                    let scalar = match &pair.key.value {
+                        YamlValue::Scalar(scalar) => scalar,
+                        _ => continue,
+                    };
+                    let trimmed = scalar.text.trim_end();
+                    if trimmed == scalar.text {
+                        continue;
+                    }
+                    let message = format!("Key `{}` has trailing whitespace", scalar.text);
+                    match pairs
+                        .iter()
+                        .any(|other| other.key.as_str() == Some(trimmed))
+                    {
+                        true => reporter.report(&file, &pair.key, message),
+                        false => reporter.report_fix(
+                            &file,
+                            &pair.key,
+                            message,
+                            vec![replace(&pair.key, scalar, trimmed)],
+                        ),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The plain scalars YAML 1.1 parsers read as booleans, unlike YAML 1.2 ones.
+const YAML_1_1_BOOLEANS: [&str; 16] = [
+    "y", "Y", "yes", "Yes", "YES", "n", "N", "no", "No", "NO", "on", "On", "ON", "off", "Off",
+    "OFF",
+];
+
+struct AmbiguousBoolean;
+
+impl LintRule for AmbiguousBoolean {
+    fn name(&self) -> &str {
+        "ambiguous-boolean"
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        for (file, document) in documents(resolver)? {
+            for node in nodes(&document) {
+                let scalar = match &node.value {
+                    YamlValue::Scalar(scalar) if scalar.style == ScalarStyle::Plain => scalar,
+                    _ => continue,
+                };
+                if YAML_1_1_BOOLEANS.contains(&scalar.text.as_str()) {
+                    reporter.report_fix(
+                        &file,
+                        node,
+                        format!(
+                            "`{}` is read as a boolean by YAML 1.1 parsers, quote it",
+                            scalar.text
+                        ),
+                        vec![replace(node, scalar, &scalar.text)],
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The documents of every file reachable from the root document through refs.
+fn documents(resolver: &mut Resolver) -> Result<Vec<(PathBuf, YamlNode)>> {
+    resolver
+        .files()?
+        .into_iter()
+        .map(|file| {
+            let document = resolver.document(&file)?.to_owned();
+            Ok((file, document))
+        })
+        .collect()
+}
+
+/// `node` and every value and item below it, parents before their children.
+/// Mapping keys are left out.
+fn nodes(node: &YamlNode) -> Vec<&YamlNode> {
+    let mut found = vec![node];
+    match &node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                found.extend(nodes(&pair.value));
+            }
+        }
+        YamlValue::Sequence(items) => {
+            for item in items {
+                found.extend(nodes(item));
+            }
+        }
+        _ => {}
+    }
+    found
+}
+
+pub trait LintParser {
+    /// Runs the rules named in `enabled`, in the order they are defined.
+    fn get_lint_diagnostics(
+        &self,
+        enabled: &[String],
+    ) -> Result<Vec<LintDiagnostic>, OpenapiSchemerError>;
+
+    /// The files changed by fixing the problems the rules named in `enabled`
+    /// can fix. Edits overlapping one made by an earlier rule are left out,
+    /// so the next run reports what they would have fixed.
+    fn get_lint_fixes(&self, enabled: &[String]) -> Result<Vec<FileEdits>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterLintParser {
+    provider: Box<dyn ContentProvider>,
+    custom: Vec<CustomRule>,
+}
+
+impl TreeSitterLintParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self {
+            provider,
+            custom: vec![],
+        }
+    }
+
+    /// Adds the rules of a ruleset, which run after the built-in rules.
+    pub fn with_rules(mut self, custom: Vec<CustomRule>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    /// The diagnostics and fixes of the rules named in `enabled`.
+    fn run(&self, enabled: &[String]) -> Result<LintRun, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut diagnostics = vec![];
+        let mut fixes = vec![];
+        let builtins = rules();
+        let custom = self.custom.iter().map(|rule| rule as &dyn LintRule);
+        for rule in builtins.iter().map(|rule| rule.as_ref()).chain(custom) {
+            if !enabled.iter().any(|name| name == rule.name()) {
+                continue;
+            }
+            let mut reporter = Reporter {
+                provider: self.provider.as_ref(),
+                rule,
+                diagnostics: vec![],
+                fixes: vec![],
+            };
+            rule.check(&mut resolver, &mut reporter).map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Lint)
+            })?;
+            diagnostics.extend(reporter.diagnostics);
+            fixes.extend(reporter.fixes);
+        }
+        Ok(LintRun { diagnostics, fixes })
+    }
+}
+
+impl LintParser for TreeSitterLintParser {
+    fn get_lint_diagnostics(
+        &self,
+        enabled: &[String],
+    ) -> Result<Vec<LintDiagnostic>, OpenapiSchemerError> {
+        Ok(self.run(enabled)?.diagnostics)
+    }
+
+    fn get_lint_fixes(&self, enabled: &[String]) -> Result<Vec<FileEdits>, OpenapiSchemerError> {
+        let fixes = self.run(enabled)?.fixes;
+        // Files are grouped by where they live on disk, the first path they
+        // were reached through is used to read them.
+        let mut files: Vec<(PathBuf, PathBuf, Vec<TextEdit>)> = vec![];
+        for (file, edit) in fixes {
+            let source = self.provider.source_path(&file);
+            let index = match files.iter().position(|(known, _, _)| *known == source) {
+                Some(index) => index,
+                None => {
+                    files.push((source, file, vec![]));
+                    files.len() - 1
+                }
+            };
+            let edits = &mut files[index].2;
+            // Insertions at the start of another edit count as overlapping,
+            // since the order they would be made in is ambiguous.
+            let overlaps = edits.iter().any(|other| {
+                (other.bytes.start < edit.bytes.end && edit.bytes.start < other.bytes.end)
+                    || other.bytes.start == edit.bytes.start
+            });
+            if !overlaps {
+                edits.push(edit);
+            }
+        }
+        files
+            .into_iter()
+            .map(|(_, file, edits)| file_edits(self.provider.as_ref(), &file, edits))
+            .collect::<Result<Vec<FileEdits>>>()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Lint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::lint::{rules, LintParser, TreeSitterLintParser},
+        content::ContentProviderMap,
+    };
+
+    fn lint_parser(contents: Vec<(&str, &str)>) -> TreeSitterLintParser {
+        let contents = contents
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_owned()))
+            .collect::<HashMap<PathBuf, String>>();
+        TreeSitterLintParser::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    fn lint(contents: Vec<(&str, &str)>, enabled: &[&str]) -> Vec<(String, usize, String)> {
+        let enabled: Vec<String> = enabled.iter().map(|name| name.to_string()).collect();
+        lint_parser(contents)
+            .get_lint_diagnostics(&enabled)
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.line, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn lint_operations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets/{petId}:
+    get:
+      operationId: show_pet
+      summary: Info for a specific pet
+      parameters:
+        - $ref: '#/components/parameters/PetId'
+    delete:
+      operationId: deletePet
+  /owners/{ownerId}:
+    parameters:
+      - name: ownerId
+        in: path
+    get:
+      operationId: showOwner
+      summary: Info for an owner
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+"#;
+        let rules = rules();
+        let all: Vec<&str> = rules.iter().map(|rule| rule.name()).collect();
+        assert_eq!(
+            lint(vec![("#", root_content)], &all),
+            vec![
+                (
+                    "operation-id-camel-case".to_string(),
+                    5,
+                    "operationId `show_pet` should be camelCase".to_string()
+                ),
+                (
+                    "operation-summary".to_string(),
+                    10,
+                    "Operation `DELETE /pets/{petId}` has no summary".to_string()
+                ),
+                (
+                    "path-parameters-declared".to_string(),
+                    10,
+                    "Path parameter `petId` is not declared for operation `DELETE /pets/{petId}`"
+                        .to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            lint(vec![("#", root_content)], &["operation-id-camel-case"]).len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_empty_descriptions() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+info:
+  description: ''
+paths:
+  /pets:
+    $ref: 'pets.yaml'
+"#;
+        let pets_content = r#"
+get:
+  description:
+  responses:
+    '200':
+      description: A list of pets
+"#;
+        assert_eq!(
+            lint(
+                vec![("#", root_content), ("pets.yaml", pets_content)],
+                &["no-empty-description"]
+            ),
+            vec![
+                (
+                    "no-empty-description".to_string(),
+                    3,
+                    "Description is empty".to_string()
+                ),
+                (
+                    "no-empty-description".to_string(),
+                    3,
+                    "Description is empty".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_nullable_style() -> Result<(), Box<dyn Error>> {
+        let enabled = vec!["nullable-style".to_string()];
+        let root_content = r#"openapi: 3.0.3
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: [string, 'null']
+        tag:
+          type: string
+          nullable: true
+        age: {type: [integer, 'null']}
+"#;
+        let parser = lint_parser(vec![("#", root_content)]);
+        let diagnostics: Vec<(usize, bool)> = parser
+            .get_lint_diagnostics(&enabled)?
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.fixable))
+            .collect();
+        assert_eq!(diagnostics, vec![(8, true), (12, false)]);
+        let fixes = parser.get_lint_fixes(&enabled)?;
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(
+            fixes[0].content,
+            root_content.replace(
+                "type: [string, 'null']",
+                "type: string\n          nullable: true"
+            )
+        );
+
+        let root_content = r#"openapi: 3.1.0
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+          nullable: true # may be unknown
+        tag:
+          type: [string, 'null']
+        age:
+          nullable: false
+          type: integer
+"#;
+        let parser = lint_parser(vec![("#", root_content)]);
+        assert_eq!(
+            lint(vec![("#", root_content)], &["nullable-style"]),
+            vec![
+                (
+                    "nullable-style".to_string(),
+                    9,
+                    "`nullable` was removed in OpenAPI 3.1, add `null` to the list of types instead"
+                        .to_string()
+                ),
+                (
+                    "nullable-style".to_string(),
+                    13,
+                    "`nullable` was removed in OpenAPI 3.1, add `null` to the list of types instead"
+                        .to_string()
+                ),
+            ]
+        );
+        let fixes = parser.get_lint_fixes(&enabled)?;
+        assert_eq!(
+            fixes[0].content,
+            r#"openapi: 3.1.0
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: [string, 'null']
+        tag:
+          type: [string, 'null']
+        age:
+          type: integer
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_fixes() -> Result<(), Box<dyn Error>> {
+        let enabled: Vec<String> = [
+            "operation-id-required",
+            "operation-tags-sorted",
+            "key-trailing-whitespace",
+            "ambiguous-boolean",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+        let root_content = r#"openapi: 3.0.3
+paths:
+  /pets/{petId}:
+    get:
+      summary: Info for a pet
+      tags: [pets, animals]
+      parameters:
+        - name: petId
+          in: path
+          'required ': true
+          schema:
+            type: string
+            enum: [y, n, 'yes']
+"#;
+        let parser = lint_parser(vec![("#", root_content)]);
+        let diagnostics: Vec<(String, usize, String)> = parser
+            .get_lint_diagnostics(&enabled)?
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.line, diagnostic.message))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    "operation-id-required".to_string(),
+                    5,
+                    "Operation `GET /pets/{petId}` has no operationId".to_string()
+                ),
+                (
+                    "operation-tags-sorted".to_string(),
+                    6,
+                    "Tags of operation `GET /pets/{petId}` are not sorted".to_string()
+                ),
+                (
+                    "key-trailing-whitespace".to_string(),
+                    10,
+                    "Key `required ` has trailing whitespace".to_string()
+                ),
+                (
+                    "ambiguous-boolean".to_string(),
+                    13,
+                    "`y` is read as a boolean by YAML 1.1 parsers, quote it".to_string()
+                ),
+                (
+                    "ambiguous-boolean".to_string(),
+                    13,
+                    "`n` is read as a boolean by YAML 1.1 parsers, quote it".to_string()
+                ),
+            ]
+        );
+        let fixes = parser.get_lint_fixes(&enabled)?;
+        assert_eq!(
+            fixes[0].content,
+            r#"openapi: 3.0.3
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPetsPetId
+      summary: Info for a pet
+      tags: [animals, pets]
+      parameters:
+        - name: petId
+          in: path
+          'required': true
+          schema:
+            type: string
+            enum: ['y', 'n', 'yes']
+"#
+        );
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@
 //! ```
 //! let code = "";
 //! let mut parser = tree_sitter::Parser::new();
-//! parser.set_language(tree_sitter_yaml::language()).expect("Error loading yaml grammar");
+//! parser.set_language(openapi_schemer::bindings::language()).expect("Error loading yaml grammar");
 //! let tree = parser.parse(code, None).unwrap();
 //! ```
 //!
@@ -15,20 +15,72 @@
 //! [Parser]: https://docs.rs/tree-sitter/*/tree_sitter/struct.Parser.html
 //! [tree-sitter]: https://tree-sitter.github.io/
 
+pub mod browse;
+pub mod bundle;
+pub mod callback;
+pub mod content_type;
+pub mod contract;
+pub mod convert;
+pub mod dedupe;
+pub mod deprecated;
+pub mod diff;
+pub mod docs;
+pub mod edit;
+pub mod example;
+pub mod extension;
+pub mod filter;
+pub mod flatten;
+pub mod generate;
+pub mod get;
+pub mod har;
+pub mod header;
+pub mod keys;
+pub mod link;
+pub mod lint;
 pub mod operation;
+pub mod overlay;
+pub mod parameter;
 pub mod path;
+pub mod pattern;
+pub mod query;
+pub mod refs;
+pub mod rename;
+pub mod resolve;
+pub mod response;
+pub mod ruleset;
 pub mod schema;
+pub mod schema_check;
+pub mod search;
+pub mod security;
+pub mod serve;
+pub mod server;
+pub mod split;
+pub mod stats;
+pub mod tag;
+pub mod template;
+pub mod unused;
+pub mod upgrade;
+pub mod validate;
+pub mod version;
+pub mod webhook;
+pub mod yaml;
 
-use anyhow::{Context, Error, Result};
-use std::collections::HashMap;
+use anyhow::{Error, Result};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
 
-use tree_sitter::{Language, Parser, Query, QueryCursor};
+use tree_sitter::{Language, Parser, Query, QueryCursor, Tree};
 
 #[cfg(test)]
 use mocktopus::macros::mockable;
 
 use crate::error::OpenapiSchemerError;
 
+use self::yaml::{escape_pointer_segment, YamlNode};
+
 extern "C" {
     fn tree_sitter_yaml() -> Language;
 }
@@ -78,183 +130,221 @@ fn create_ref_query() -> String {
     );
 }
 
-fn create_top_level_yaml_context_query() -> String {
-    return format!(
-        r#"
-        (document
-         (block_node
-          (block_mapping
-           (block_mapping_pair
-            key: (flow_node) @child-key
-            value: [(flow_node)(block_node)] @child-value
-           ) @child-context
-          )
-         )
-        )
-        "#
-    );
+/// Parsed trees keyed by a hash of the content they were parsed from. A
+/// query across every file of the spec runs over one tree per file, and
+/// files with the same content share a tree instead of being parsed again.
+#[derive(Default)]
+pub struct TreeCache {
+    /// Trees by content hash. The content is kept alongside each tree to tell
+    /// apart the rare contents that share a hash.
+    trees: HashMap<u64, Vec<(Vec<u8>, Tree)>>,
 }
 
-fn create_yaml_context_query(parent_key: &str) -> String {
-    return format!(
-        r#"
-        (
-            (block_mapping_pair
-             key: (flow_node) @parent-key
-             value: (
-                 block_node (
-                     block_mapping (
-                         block_mapping_pair
-                         key: (flow_node) @child-key
-                         value: [(flow_node)(block_node)] @child-value
-                     ) @child-context
-                 )
-             ) @parent-value
-            ) @parent-context
-            (#eq? @parent-key "{}")
-        )
-        "#,
-        parent_key
-    );
+impl TreeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The tree for `content`, parsing it the first time it is seen.
+    pub fn tree(&mut self, content: &[u8]) -> Result<&Tree> {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let entries = self.trees.entry(hasher.finish()).or_default();
+        let index = match entries.iter().position(|(cached, _)| cached == content) {
+            Some(index) => index,
+            None => {
+                let mut parser = Parser::new();
+                parser.set_language(language())?;
+                let tree = parser
+                    .parse(content, None)
+                    .ok_or_else(|| Error::msg("Could not parse tree"))?;
+                entries.push((content.to_vec(), tree));
+                entries.len() - 1
+            }
+        };
+        Ok(&entries[index].1)
+    }
+
+    /// How many distinct contents have been parsed.
+    pub fn len(&self) -> usize {
+        self.trees.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
-fn get_top_level_keys(content: &[u8]) -> Result<ChildrenOrRef> {
-    let language = language();
-    let mut parser = Parser::new();
-    parser.set_language(language)?;
-    let tree = parser
-        .parse(content.to_owned(), None)
-        .ok_or_else(|| Error::msg(format!("Could not parse tree")))?;
-    let query = create_top_level_yaml_context_query();
-    let query = Query::new(language, &query)
-        .with_context(|| format!("Could not construct query `{}`", query))?;
-    let mut qc = QueryCursor::new();
+/// Appends `key` to a JSON pointer.
+pub fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, escape_pointer_segment(key))
+}
 
-    let mut results: HashMap<String, String> = HashMap::new();
-
-    for qm in qc.matches(&query, tree.root_node(), content) {
-        let child_key_index = query
-            .capture_index_for_name("child-key")
-            .ok_or_else(|| Error::msg(format!("Could not find capture for `{}`", "child-key")))?;
-        let child_context_index =
-            query
-                .capture_index_for_name("child-context")
-                .ok_or_else(|| {
-                    Error::msg(format!("Could not find capture for `{}`", "child-context"))
-                })?;
-        let child_key_node = qm
-            .nodes_for_capture_index(child_key_index)
-            .last()
-            .ok_or_else(|| Error::msg(format!("Could not find node for `{}`", "child-key")))?;
-        if let Ok(key_text) = child_key_node.utf8_text(content) {
-            let child_context_node = qm
-                .nodes_for_capture_index(child_context_index)
-                .last()
-                .ok_or_else(|| {
-                    Error::msg(format!("Could not find node for `{}`", "child-context"))
-                })?;
-            results.insert(
-                key_text.to_string(),
-                child_context_node
-                    .utf8_text(content)
-                    .with_context(|| {
-                        format!("Could not extract value text for key `{}`", key_text)
-                    })?
-                    .to_string(),
-            );
-        }
+#[derive(Clone, Debug, Default)]
+pub struct OperationNode {
+    /// The `operationId`, or `GET /pets` style name when there is none.
+    pub text: String,
+    pub operation_id: Option<String>,
+    /// The method as written in the spec, such as `get`.
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+    pub deprecated: bool,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl OperationNode {
+    /// `GET /pets` style name for the operation.
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.method.to_uppercase(), self.path)
     }
+}
 
-    Ok(ChildrenOrRef::Children(results))
+/// Restricts which operations are returned by an `OperationParser`. Unset
+/// fields match every operation.
+#[derive(Clone, Debug, Default)]
+pub struct OperationFilter {
+    /// A glob where `*` matches any run of characters, e.g. `/pets/*`.
+    pub path: Option<String>,
+    pub method: Option<String>,
+    pub tag: Option<String>,
 }
 
-fn get_children_by_key(key: &str, content: &[u8]) -> Result<ChildrenOrRef> {
-    let language = language();
-    let mut parser = Parser::new();
-    parser.set_language(language)?;
-    let tree = parser
-        .parse(content.to_owned(), None)
-        .ok_or_else(|| Error::msg(format!("Could not parse tree for parent key `{}`", key)))?;
-    let query = create_yaml_context_query(key);
-    let query = Query::new(language, &query)
-        .with_context(|| format!("Could not construct query `{}`", query))?;
-    let mut qc = QueryCursor::new();
+impl OperationFilter {
+    pub fn matches_path(&self, path: &str) -> bool {
+        match &self.path {
+            Some(pattern) => glob_match(pattern, path),
+            None => true,
+        }
+    }
 
-    let mut results: HashMap<String, String> = HashMap::new();
-
-    for qm in qc.matches(&query, tree.root_node(), content) {
-        let child_key_index = query
-            .capture_index_for_name("child-key")
-            .ok_or_else(|| Error::msg(format!("Could not find capture for `{}`", "child-key")))?;
-        let child_value_index = query
-            .capture_index_for_name("child-value")
-            .ok_or_else(|| Error::msg(format!("Could not find capture for `{}`", "child-value")))?;
-        let child_context_index =
-            query
-                .capture_index_for_name("child-context")
-                .ok_or_else(|| {
-                    Error::msg(format!("Could not find capture for `{}`", "child-context"))
-                })?;
-        let child_key_node = qm
-            .nodes_for_capture_index(child_key_index)
-            .last()
-            .ok_or_else(|| {
-                Error::msg(format!("Could not find node for `{}`", "child-key-index"))
-            })?;
-        if let Ok(key_text) = child_key_node.utf8_text(content) {
-            if key_text == "$ref" {
-                let child_value_node_text = qm
-                    .nodes_for_capture_index(child_value_index)
-                    .last()
-                    .ok_or_else(|| {
-                        Error::msg(format!("Could not find node for `{}`", "child-value-index"))
-                    })?
-                    .utf8_text(content)
-                    .with_context(|| format!("Could not extract path for $ref node"))?
-                    // Prevent weird file names by removing quotes
-                    .replace("'", "")
-                    .replace("\"", "");
-                return Ok(ChildrenOrRef::Ref(child_value_node_text.to_owned()));
-            }
-            let child_context_node = qm
-                .nodes_for_capture_index(child_context_index)
-                .last()
-                .ok_or_else(|| {
-                    Error::msg(format!("Could not find node for `{}`", "child-context"))
-                })?;
-            results.insert(
-                key_text.to_string(),
-                child_context_node
-                    .utf8_text(content)
-                    .with_context(|| format!("Could not extract text for child context"))?
-                    .to_string(),
-            );
+    pub fn matches_method(&self, method: &str) -> bool {
+        match &self.method {
+            Some(expected) => expected.eq_ignore_ascii_case(method),
+            None => true,
         }
     }
 
-    Ok(ChildrenOrRef::Children(results))
+    pub fn matches_tags(&self, tags: &[String]) -> bool {
+        match &self.tag {
+            Some(expected) => tags.iter().any(|tag| tag == expected),
+            None => true,
+        }
+    }
 }
 
-#[derive(Debug)]
-pub enum ChildrenOrRef {
-    Children(HashMap<String, String>),
-    Ref(String),
+/// Matches `text` against a glob where `*` stands for any run of characters
+/// and `?` for a single character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` and the text index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, start)) => {
+                    p = star + 1;
+                    t = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
 }
 
-#[derive(Clone, Debug)]
-pub struct OperationNode {
-    pub text: String,
+/// Scores how well `query` matches `text` as a case insensitive subsequence,
+/// or `None` when it doesn't match. Characters matched right after each other
+/// or at the start of a word, such as after `/` or at a capital in camelCase,
+/// score higher. Whitespace in the query is ignored.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.chars().collect();
+    let (mut score, mut position) = (0, 0);
+    let mut previous: Option<usize> = None;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let index =
+            (position..text.len()).find(|&index| text[index].eq_ignore_ascii_case(&wanted))?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 2;
+        }
+        let word_start = index == 0
+            || !text[index - 1].is_alphanumeric()
+            || (text[index].is_uppercase() && text[index - 1].is_lowercase());
+        if word_start {
+            score += 3;
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+/// A single operation with every `$ref` below it resolved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationDefinition {
+    pub path: String,
+    pub method: String,
+    pub operation: YamlNode,
 }
 
 pub trait OperationParser {
     fn get_operation_nodes(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError>;
+    /// Calls `f` with each operation of `get_operation_nodes` as it is found,
+    /// so a huge spec can be handled an operation at a time.
+    fn for_each_operation_node(
+        &self,
+        f: &mut dyn FnMut(OperationNode),
+    ) -> Result<(), OpenapiSchemerError> {
+        self.get_operation_nodes()?.into_iter().for_each(f);
+        Ok(())
+    }
+    fn get_operation(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<OperationDefinition>, OpenapiSchemerError>;
 }
 
 #[cfg(test)]
 mod tests {
     use std::error::Error;
 
-    use super::find_refs;
+    use super::{find_refs, fuzzy_score, glob_match, TreeCache};
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/pets", "/pets"));
+        assert!(glob_match("/pets/*", "/pets/{petId}"));
+        assert!(glob_match("*/owners/*", "/pets/{petId}/owners/{ownerId}"));
+        assert!(glob_match("/pets?", "/pets2"));
+        assert!(!glob_match("/pets/*", "/pets"));
+        assert!(!glob_match("/pets", "/pets/{petId}"));
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "listPets"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "listPets"), None);
+        assert_eq!(fuzzy_score("pets", "/owners"), None);
+        // `lp` hits the start of both words of `listPets`, but only one of
+        // `leapfrog`.
+        assert!(fuzzy_score("lp", "listPets") > fuzzy_score("lp", "leapfrog"));
+        assert!(fuzzy_score("pet", "carpets") > fuzzy_score("pet", "computed"));
+    }
 
     #[test]
     fn test_can_load_grammar() {
@@ -264,6 +354,18 @@ mod tests {
             .expect("Error loading yaml language");
     }
 
+    #[test]
+    fn tree_cache() -> Result<(), Box<dyn Error>> {
+        let mut trees = TreeCache::new();
+        let first = trees.tree(b"paths: {}")?.root_node().to_sexp();
+        let second = trees.tree(b"paths: {}")?.root_node().to_sexp();
+        assert_eq!(first, second);
+        assert_eq!(trees.len(), 1);
+        trees.tree(b"components: {}")?;
+        assert_eq!(trees.len(), 2);
+        Ok(())
+    }
+
     #[test]
     fn test_find_refs() -> Result<(), Box<dyn Error>> {
         let content = r#"
@@ -1,79 +1,187 @@
-use anyhow::Context;
 use anyhow::Result;
 use std::path::PathBuf;
 
 use crate::{content::ContentProvider, error::OpenapiSchemerError};
 
-use super::{get_children_by_key, get_top_level_keys, ChildrenOrRef, OperationParser};
+use super::{
+    resolve::Resolver,
+    validate::HTTP_METHODS,
+    yaml::{YamlNode, YamlPair, YamlValue},
+    OperationDefinition, OperationFilter, OperationNode, OperationParser,
+};
 
 pub struct TreeSitterOperationParser {
     provider: Box<dyn ContentProvider>,
+    filter: OperationFilter,
 }
 
 impl TreeSitterOperationParser {
     pub fn new(provider: Box<dyn ContentProvider>) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            filter: OperationFilter::default(),
+        }
     }
-    fn get_children(
-        &self,
-        key: &str,
-        content: &[u8],
-    ) -> Result<ChildrenOrRef, OpenapiSchemerError> {
-        let mut children = get_children_by_key(key, content)
-            .with_context(|| format!("Failed to get children for yaml key `{}`", key))
-            .map_err(|error| OpenapiSchemerError::OperationList(error.to_string()))?;
-
-        match children {
-            ChildrenOrRef::Children(_) => Ok(children),
-            ChildrenOrRef::Ref(r) => {
-                let content = self.provider.get_content(PathBuf::from(r));
-                children = get_top_level_keys(content.as_bytes())
-                    .with_context(|| format!("Failed to get children for yaml key `{}`", key))
-                    .map_err(|error| OpenapiSchemerError::OperationList(error.to_string()))?;
-                match children {
-                    ChildrenOrRef::Ref(_) => Err(OpenapiSchemerError::OperationList(format!(
-                        "$ref cannot link to another $ref"
-                    ))),
-                    ChildrenOrRef::Children(_) => Ok(children),
-                }
+
+    /// Only return operations from `get_operation_nodes` that match `filter`.
+    pub fn with_filter(mut self, filter: OperationFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Calls `f` with the operations of the spec that match the filter, read
+    /// from the documents of the provider so aliases and merge keys are
+    /// followed like refs are.
+    fn operation_nodes(&self, f: &mut dyn FnMut(OperationNode)) -> Result<()> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        resolver.for_each_operation(&mut |operation| {
+            if !self.filter.matches_path(&operation.path)
+                || !self.filter.matches_method(&operation.method)
+            {
+                return;
+            }
+            let node = operation.node(self.provider.as_ref());
+            if self.filter.tag.is_none() || self.filter.matches_tags(&node.tags) {
+                f(node);
             }
+        })
+    }
+}
+
+/// Path level parameters apply to every operation below the path unless the
+/// operation overrides them with the same `name` and `in`.
+fn merge_parameters(path_parameters: Option<&YamlNode>, operation: YamlNode) -> YamlNode {
+    let path_parameters = match path_parameters.and_then(|node| node.as_sequence()) {
+        Some(parameters) if !parameters.is_empty() => parameters,
+        _ => return operation,
+    };
+    let operation_parameters = operation
+        .get("parameters")
+        .and_then(|node| node.as_sequence())
+        .unwrap_or_default();
+    let identity = |parameter: &YamlNode| {
+        (
+            parameter
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(str::to_owned),
+            parameter
+                .get("in")
+                .and_then(|name| name.as_str())
+                .map(str::to_owned),
+        )
+    };
+    let mut parameters: Vec<YamlNode> = path_parameters
+        .iter()
+        .filter(|parameter| {
+            !operation_parameters
+                .iter()
+                .any(|overridden| identity(overridden) == identity(parameter))
+        })
+        .cloned()
+        .collect();
+    parameters.extend(operation_parameters.iter().cloned());
+
+    let mut pairs: Vec<YamlPair> = operation.as_mapping().unwrap_or_default().to_vec();
+    let merged = YamlNode::sequence(parameters);
+    match pairs
+        .iter_mut()
+        .find(|pair| pair.key.as_str() == Some("parameters"))
+    {
+        Some(pair) => pair.value = merged,
+        None => {
+            // Keep parameters close to the top like most specs do
+            let index = pairs
+                .iter()
+                .position(|pair| {
+                    matches!(
+                        pair.key.as_str(),
+                        Some("requestBody") | Some("responses") | Some("callbacks")
+                    )
+                })
+                .unwrap_or(pairs.len());
+            pairs.insert(
+                index,
+                YamlPair {
+                    key: YamlNode::string("parameters"),
+                    value: merged,
+                },
+            );
         }
     }
+    YamlNode {
+        value: YamlValue::Mapping(pairs),
+        ..operation
+    }
 }
 
 impl OperationParser for TreeSitterOperationParser {
-    fn get_operation_nodes(&self) -> Result<Vec<super::OperationNode>, OpenapiSchemerError> {
-        let content = self.provider.get_content(PathBuf::from("#"));
-        let mut results: Vec<super::OperationNode> = vec![];
-
-        let paths_children = self.get_children("paths", content.as_bytes())?;
-        if let super::ChildrenOrRef::Children(children) = paths_children {
-            for (path, context) in children {
-                let methods = self.get_children(&path, context.as_bytes())?;
-                if let super::ChildrenOrRef::Children(children) = methods {
-                    for (operation, context) in children {
-                        let operation_child_keys =
-                            self.get_children(&operation, context.as_bytes())?;
-                        if let super::ChildrenOrRef::Children(children) = operation_child_keys {
-                            // This base case looks pretty gross and maybe it is, but the
-                            // resulting value of children["operationId"] is the string
-                            // "operationId: <whatever>". So I just do some string
-                            // splitting since it should definitely look like that right?
-                            let operation = children.get("operationId").unwrap().to_string();
-                            let operation = operation
-                                .split("operationId:")
-                                .into_iter()
-                                .last()
-                                .unwrap()
-                                .trim()
-                                .to_owned();
-                            results.push(super::OperationNode { text: operation })
-                        }
-                    }
+    fn get_operation(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<OperationDefinition>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationShow)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path).map_err(to_error)?.to_owned();
+        let paths = match root.get("paths") {
+            Some(paths) => paths,
+            None => return Ok(None),
+        };
+        let (paths, paths_file) = resolver.follow(paths, &root_path).map_err(to_error)?;
+        for pair in paths.as_mapping().unwrap_or_default() {
+            let path = pair.key.as_str().unwrap_or_default();
+            let (path_item, path_item_file) = resolver
+                .follow(&pair.value, &paths_file)
+                .map_err(to_error)?;
+            for method in HTTP_METHODS {
+                let operation = match path_item.get(method) {
+                    Some(operation) => operation,
+                    None => continue,
+                };
+                let (operation, operation_file) = resolver
+                    .follow(operation, &path_item_file)
+                    .map_err(to_error)?;
+                let id = operation.get("operationId").and_then(|id| id.as_str());
+                if id != Some(operation_id) {
+                    continue;
                 }
+                let operation = resolver
+                    .inline(&operation, &operation_file)
+                    .map_err(to_error)?;
+                let path_parameters = match path_item.get("parameters") {
+                    Some(parameters) => Some(
+                        resolver
+                            .inline(parameters, &path_item_file)
+                            .map_err(to_error)?,
+                    ),
+                    None => None,
+                };
+                return Ok(Some(OperationDefinition {
+                    path: path.to_string(),
+                    method: method.to_string(),
+                    operation: merge_parameters(path_parameters.as_ref(), operation),
+                }));
             }
         }
-        Ok(results)
+        Ok(None)
+    }
+
+    fn get_operation_nodes(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+        let mut nodes = vec![];
+        self.for_each_operation_node(&mut |node| nodes.push(node))?;
+        Ok(nodes)
+    }
+
+    fn for_each_operation_node(
+        &self,
+        f: &mut dyn FnMut(OperationNode),
+    ) -> Result<(), OpenapiSchemerError> {
+        self.operation_nodes(f).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationList)
+        })
     }
 }
 
@@ -86,7 +194,7 @@ mod tests {
     use mocktopus::mocking::MockResult;
     use mocktopus::mocking::Mockable;
 
-    use crate::bindings::{ChildrenOrRef, OperationParser};
+    use crate::bindings::{OperationFilter, OperationParser};
     use crate::content::ContentProvider;
     use crate::content::ContentProviderMap;
 
@@ -116,6 +224,217 @@ paths:
         Ok(())
     }
 
+    #[test]
+    fn get_operation_nodes_filtered() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags:
+        - pets
+    post:
+      operationId: createPets
+      tags:
+        - admin
+  /pets/{petId}:
+    $ref: 'pet.yaml'
+  /owners:
+    get:
+      operationId: listOwners
+"#;
+        let pet_content = r#"
+get:
+  operationId: showPetById
+  tags:
+    - pets
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let operation_ids = |filter: OperationFilter| -> Vec<String> {
+            let provider = Box::new(ContentProviderMap::from_map(contents.to_owned()));
+            let parser = TreeSitterOperationParser::new(provider).with_filter(filter);
+            let mut ids: Vec<String> = parser
+                .get_operation_nodes()
+                .unwrap()
+                .into_iter()
+                .map(|node| node.text)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let by_path = OperationFilter {
+            path: Some("/pets*".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation_ids(by_path),
+            vec!["createPets", "listPets", "showPetById"]
+        );
+        let by_method = OperationFilter {
+            method: Some("GET".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            operation_ids(by_method),
+            vec!["listOwners", "listPets", "showPetById"]
+        );
+        let by_tag = OperationFilter {
+            path: Some("/pets/*".to_string()),
+            tag: Some("pets".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(operation_ids(by_tag), vec!["showPetById"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation_nodes_locations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  $ref: Paths.yaml
+"#;
+        let paths_content = r#"
+/pets:
+  get:
+    summary: List all pets
+    operationId: listPets
+  post:
+    $ref: paths/pets/post.yaml
+"#;
+        let pets_post_content = r#"summary: Create a pet
+operationId: createPets
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("Paths.yaml"), paths_content.to_owned()),
+            (
+                PathBuf::from("paths/pets/post.yaml"),
+                pets_post_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterOperationParser::new(provider);
+        let nodes = parser.get_operation_nodes().unwrap();
+        let list_pets = nodes.iter().find(|node| node.text == "listPets").unwrap();
+        assert_eq!(list_pets.file, PathBuf::from("Paths.yaml"));
+        assert_eq!((list_pets.line, list_pets.column), (5, 18));
+        let create_pets = nodes.iter().find(|node| node.text == "createPets").unwrap();
+        assert_eq!(create_pets.file, PathBuf::from("paths/pets/post.yaml"));
+        assert_eq!((create_pets.line, create_pets.column), (2, 14));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation_nodes_block_scalars() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    description: |
+      get:
+        operationId: notAnOperation
+    parameters:
+      - name: limit
+        in: query
+    get:
+      description: >
+        Lists pets.
+        operationId: alsoNotAnOperation
+      operationId: "listPets"
+    post:
+      summary: Create a pet
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterOperationParser::new(provider);
+        let nodes = parser.get_operation_nodes()?;
+        assert_eq!(
+            (nodes[1].method.as_str(), nodes[1].path.as_str()),
+            ("post", "/pets")
+        );
+        assert_eq!(nodes[1].summary.as_deref(), Some("Create a pet"));
+        let nodes: Vec<(String, usize, usize)> = nodes
+            .into_iter()
+            .map(|node| (node.text, node.line, node.column))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("listPets".to_string(), 14, 20),
+                ("POST /pets".to_string(), 16, 7),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets/{petId}:
+    $ref: 'paths/pet.yaml'
+components:
+  schemas:
+    Pet:
+      type: object
+"#;
+        let pet_content = r#"
+parameters:
+  - name: petId
+    in: path
+    required: true
+  - name: verbose
+    in: query
+get:
+  operationId: showPetById
+  parameters:
+    - name: verbose
+      in: query
+      required: true
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Pet'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("openapi.yaml"), root_content.to_owned()),
+            (PathBuf::from("paths/pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterOperationParser::new(provider);
+        let definition = parser.get_operation("showPetById")?.unwrap();
+        assert_eq!(definition.path, "/pets/{petId}");
+        assert_eq!(definition.method, "get");
+        let expected = r#"operationId: showPetById
+parameters:
+  - name: petId
+    in: path
+    required: true
+  - name: verbose
+    in: query
+    required: true
+responses:
+  '200':
+    content:
+      application/json:
+        schema:
+          type: object
+"#;
+        assert_eq!(definition.operation.to_yaml(), expected);
+        assert!(parser.get_operation("missing")?.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn get_operation_nodes_with_ref() -> Result<(), Box<dyn Error>> {
         let root_path = PathBuf::from("#");
@@ -141,7 +460,7 @@ paths:
             (paths_path, paths_content.to_owned()),
         ]);
         ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
+            MockResult::Return(Ok(contents.get(&path).unwrap().to_owned()))
         });
         let provider = ContentProviderMap::new();
         let box_provider = Box::new(provider);
@@ -192,7 +511,7 @@ operationId: createPets
         ]);
 
         ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
+            MockResult::Return(Ok(contents.get(&path).unwrap().to_owned()))
         });
 
         let provider = ContentProviderMap::new();
@@ -205,87 +524,4 @@ operationId: createPets
 
         Ok(())
     }
-
-    #[test]
-    fn get_children_by_key_no_ref() -> Result<(), Box<dyn Error>> {
-        let results = super::get_children_by_key(
-            "test",
-            r#"
-test:
-  test1:
-    description: yes
-  test2:
-    description: no"#
-                .as_bytes(),
-        )
-        .unwrap();
-        return match results {
-            ChildrenOrRef::Children(children) => {
-                assert!(children.get("test1").unwrap() == "test1:\n    description: yes");
-                assert!(children.get("test2").unwrap() == "test2:\n    description: no");
-                Ok(())
-            }
-            ChildrenOrRef::Ref(_) => panic!("Test should have returned Children enum"),
-        };
-    }
-
-    #[test]
-    fn get_children_by_key_ref() -> Result<(), Box<dyn Error>> {
-        let results = super::get_children_by_key(
-            "test",
-            r#"
-test:
-  test1:
-    description: yes
-  test2:
-    description: no
-  $ref: '#/fake/ref'"#
-                .as_bytes(),
-        )
-        .unwrap();
-        return match results {
-            ChildrenOrRef::Children(_) => {
-                panic!("Test should have returned Ref enum")
-            }
-            ChildrenOrRef::Ref(r) => {
-                assert!(r == "#/fake/ref");
-                Ok(())
-            }
-        };
-    }
-
-    #[test]
-    fn get_children_by_key_child_contains_ref() -> Result<(), Box<dyn Error>> {
-        let results = super::get_children_by_key(
-            "test",
-            r#"
-test:
-  test1:
-    description: yes
-  test2:
-    description: no
-  test3:
-    $ref: '#/fake/ref'"#
-                .as_bytes(),
-        )
-        .unwrap();
-        return match results {
-            ChildrenOrRef::Children(children) => {
-                assert_eq!(
-                    children.get("test1").unwrap(),
-                    "test1:\n    description: yes"
-                );
-                assert_eq!(
-                    children.get("test2").unwrap(),
-                    "test2:\n    description: no"
-                );
-                assert_eq!(
-                    children.get("test3").unwrap(),
-                    "test3:\n    $ref: '#/fake/ref'"
-                );
-                Ok(())
-            }
-            ChildrenOrRef::Ref(_) => panic!("Test should have returned Children enum"),
-        };
-    }
 }
@@ -0,0 +1,376 @@
+use std::{cmp::Ordering, path::PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    ruleset::{parse_path, Step},
+    yaml::{parse_yaml, YamlNode, YamlValue},
+};
+
+/// An OpenAPI Overlay document, whose actions change the nodes their
+/// `target` selects:
+///
+/// ```yaml
+/// overlay: 1.0.0
+/// info:
+///   title: Production servers
+///   version: 1.0.0
+/// actions:
+///   - target: $.servers
+///     remove: true
+///   - target: $.info
+///     update:
+///       x-environment: production
+/// ```
+#[derive(Debug)]
+pub struct Overlay {
+    pub actions: Vec<OverlayAction>,
+}
+
+#[derive(Debug)]
+pub struct OverlayAction {
+    pub target: String,
+    steps: Vec<Step>,
+    /// Merged into every selected mapping, or appended to every selected
+    /// sequence.
+    pub update: Option<YamlNode>,
+    pub remove: bool,
+}
+
+impl Overlay {
+    pub fn parse(content: &str) -> Result<Overlay> {
+        let document = parse_yaml(content).context("Failed to parse overlay")?;
+        let version = document.get("overlay").and_then(YamlNode::as_str);
+        if !version.is_some_and(|version| version.starts_with("1.")) {
+            bail!("Expected an overlay document with `overlay: 1.0.0`");
+        }
+        let actions = document
+            .get("actions")
+            .and_then(YamlNode::as_sequence)
+            .ok_or_else(|| anyhow!("Expected a list of `actions` in the overlay"))?;
+        let mut overlay = Overlay { actions: vec![] };
+        for (index, action) in actions.iter().enumerate() {
+            let target = action
+                .get("target")
+                .and_then(YamlNode::as_str)
+                .ok_or_else(|| anyhow!("Action {} of the overlay has no `target`", index + 1))?;
+            let steps = parse_path(target).map_err(|error| {
+                anyhow!(
+                    "Invalid target `{}` in action {} of the overlay: {}",
+                    target,
+                    index + 1,
+                    error
+                )
+            })?;
+            overlay.actions.push(OverlayAction {
+                target: target.to_string(),
+                steps,
+                update: action.get("update").cloned(),
+                remove: action.get("remove").and_then(YamlNode::as_str) == Some("true"),
+            });
+        }
+        Ok(overlay)
+    }
+}
+
+pub trait OverlayApplier {
+    /// The root document with the actions of the overlay applied in order.
+    fn apply(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterOverlayApplier {
+    provider: Box<dyn ContentProvider>,
+    overlay: Overlay,
+}
+
+impl TreeSitterOverlayApplier {
+    pub fn new(provider: Box<dyn ContentProvider>, overlay: Overlay) -> Self {
+        Self { provider, overlay }
+    }
+
+    fn apply_overlay(&self) -> Result<YamlNode> {
+        let mut document = self.provider.get_document(PathBuf::from("#"))?;
+        for (index, action) in self.overlay.actions.iter().enumerate() {
+            let mut targets = select(&document, &action.steps);
+            if targets.is_empty() {
+                bail!(
+                    "Target `{}` of action {} selects nothing",
+                    action.target,
+                    index + 1
+                );
+            }
+            if action.remove {
+                // Later entries go first so removing them doesn't move the
+                // indexes of the others.
+                targets.sort_by(|a, b| compare_paths(b, a));
+                for path in targets {
+                    remove(&mut document, &path);
+                }
+                continue;
+            }
+            let update = match &action.update {
+                Some(update) => update,
+                None => continue,
+            };
+            for path in targets {
+                let node = node_at_mut(&mut document, &path)
+                    .ok_or_else(|| anyhow!("Target `{}` moved while applying", action.target))?;
+                match (&mut node.value, &update.value) {
+                    (YamlValue::Mapping(_), YamlValue::Mapping(_)) => merge(node, update),
+                    (YamlValue::Sequence(items), _) => items.push(update.to_owned()),
+                    _ => bail!(
+                        "Action {} updates `{}`, which is neither a mapping nor a sequence, \
+                         or isn't updated with a mapping",
+                        index + 1,
+                        action.target
+                    ),
+                }
+            }
+        }
+        Ok(document)
+    }
+}
+
+impl OverlayApplier for TreeSitterOverlayApplier {
+    fn apply(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        self.apply_overlay().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OverlayApply)
+        })
+    }
+}
+
+/// The keys and indexes leading to every node `steps` select, in document
+/// order. Refs are not followed, targets are written in the document itself.
+fn select(document: &YamlNode, steps: &[Step]) -> Vec<Vec<String>> {
+    let mut selected: Vec<Vec<String>> = vec![vec![]];
+    for step in steps {
+        let mut next = vec![];
+        for path in &selected {
+            let bases = match step.recursive {
+                true => descendants(document, path),
+                false => vec![path.to_owned()],
+            };
+            for base in bases {
+                let node = match node_at(document, &base) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                for name in child_names(node) {
+                    if step.selects(Some(&name)) {
+                        let mut path = base.to_owned();
+                        path.push(name);
+                        if !next.contains(&path) {
+                            next.push(path);
+                        }
+                    }
+                }
+            }
+        }
+        selected = next;
+    }
+    selected
+}
+
+/// `path` and the paths of every node below it.
+fn descendants(document: &YamlNode, path: &[String]) -> Vec<Vec<String>> {
+    let mut paths = vec![path.to_vec()];
+    if let Some(node) = node_at(document, path) {
+        for name in child_names(node) {
+            let mut child = path.to_vec();
+            child.push(name);
+            paths.extend(descendants(document, &child));
+        }
+    }
+    paths
+}
+
+fn child_names(node: &YamlNode) -> Vec<String> {
+    match &node.value {
+        YamlValue::Mapping(pairs) => pairs
+            .iter()
+            .map(|pair| pair.key.as_str().unwrap_or_default().to_string())
+            .collect(),
+        YamlValue::Sequence(items) => (0..items.len()).map(|index| index.to_string()).collect(),
+        _ => vec![],
+    }
+}
+
+fn node_at<'a>(document: &'a YamlNode, path: &[String]) -> Option<&'a YamlNode> {
+    path.iter()
+        .try_fold(document, |node, segment| node.child(segment))
+}
+
+fn node_at_mut<'a>(document: &'a mut YamlNode, path: &[String]) -> Option<&'a mut YamlNode> {
+    path.iter()
+        .try_fold(document, |node, segment| node.child_mut(segment))
+}
+
+/// Orders paths by their keys, comparing indexes as numbers.
+fn compare_paths(a: &[String], b: &[String]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<usize>(), b.parse::<usize>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn remove(document: &mut YamlNode, path: &[String]) {
+    let (last, parent) = match path.split_last() {
+        Some(split) => split,
+        // Removing the document itself leaves it empty.
+        None => {
+            *document = YamlNode::mapping(vec![]);
+            return;
+        }
+    };
+    match node_at_mut(document, parent).map(|node| &mut node.value) {
+        Some(YamlValue::Mapping(pairs)) => pairs.retain(|pair| pair.key.as_str() != Some(last)),
+        Some(YamlValue::Sequence(items)) => {
+            if let Ok(index) = last.parse::<usize>() {
+                if index < items.len() {
+                    items.remove(index);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Merges the entries of `update` into `target`. Mappings on both sides are
+/// merged in turn, any other value of `update` replaces the one in `target`.
+fn merge(target: &mut YamlNode, update: &YamlNode) {
+    let (pairs, updates) = match (&mut target.value, &update.value) {
+        (YamlValue::Mapping(pairs), YamlValue::Mapping(updates)) => (pairs, updates),
+        _ => {
+            *target = update.to_owned();
+            return;
+        }
+    };
+    for update in updates {
+        match pairs
+            .iter_mut()
+            .find(|pair| pair.key.as_str() == update.key.as_str())
+        {
+            Some(pair) => merge(&mut pair.value, &update.value),
+            None => pairs.push(update.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::overlay::{Overlay, OverlayApplier, TreeSitterOverlayApplier},
+        content::ContentProviderMap,
+    };
+
+    fn apply(content: &str, overlay: &str) -> Result<String, String> {
+        let contents = HashMap::from([(PathBuf::from("#"), content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let overlay = Overlay::parse(overlay).map_err(|error| error.to_string())?;
+        TreeSitterOverlayApplier::new(provider, overlay)
+            .apply()
+            .map(|document| document.to_yaml())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn apply_overlay() -> Result<(), Box<dyn Error>> {
+        let content = r#"openapi: 3.0.0
+info:
+  title: Petstore
+  contact:
+    name: Team
+servers:
+  - url: http://localhost
+  - url: http://staging
+paths:
+  /pets:
+    get:
+      x-internal: true
+      responses:
+        '200':
+          description: ok
+    post:
+      responses:
+        '200':
+          description: ok
+"#;
+        let overlay = r#"overlay: 1.0.0
+info:
+  title: Production
+  version: 1.0.0
+actions:
+  - target: $.info
+    update:
+      title: Public Petstore
+      contact:
+        email: team@example.com
+  - target: $.servers[0,1]
+    remove: true
+  - target: $.servers
+    update:
+      url: https://api.example.com
+  - target: $..x-internal
+    remove: true
+"#;
+        assert_eq!(
+            apply(content, overlay)?,
+            r#"openapi: 3.0.0
+info:
+  title: Public Petstore
+  contact:
+    name: Team
+    email: team@example.com
+servers:
+  - url: https://api.example.com
+paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          description: ok
+    post:
+      responses:
+        '200':
+          description: ok
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn apply_overlay_errors() {
+        let content = "openapi: 3.0.0\ninfo:\n  title: Petstore\n";
+        assert_eq!(
+            apply(content, "actions: []\n").unwrap_err(),
+            "Expected an overlay document with `overlay: 1.0.0`"
+        );
+        assert_eq!(
+            apply(
+                content,
+                "overlay: 1.0.0\nactions:\n  - target: $.paths\n    remove: true\n"
+            )
+            .unwrap_err(),
+            "Target `$.paths` of action 1 selects nothing"
+        );
+        assert_eq!(
+            apply(
+                content,
+                "overlay: 1.0.0\nactions:\n  - target: $.info.title\n    update: Pets\n"
+            )
+            .unwrap_err(),
+            "Action 1 updates `$.info.title`, which is neither a mapping nor a sequence, \
+             or isn't updated with a mapping"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterNode {
+    /// The key under `components/parameters`.
+    pub text: String,
+    pub name: String,
+    pub location: String,
+    pub required: bool,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait ParameterParser {
+    fn get_parameter_nodes(&self) -> Result<Vec<ParameterNode>, OpenapiSchemerError>;
+    fn get_parameter(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterParameterParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterParameterParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl ParameterParser for TreeSitterParameterParser {
+    fn get_parameter_nodes(&self) -> Result<Vec<ParameterNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver
+            .entries("/components/parameters")
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ParameterList)
+            })?;
+        let text = |node: &YamlNode, key: &str| {
+            node.get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let results = entries
+            .into_iter()
+            .map(|entry| ParameterNode {
+                name: text(&entry.value, "name"),
+                location: text(&entry.value, "in"),
+                // Path parameters are always required
+                required: text(&entry.value, "required") == "true"
+                    || text(&entry.value, "in") == "path",
+                file: self.provider.source_path(&entry.key_file),
+                line: entry.key.start.row + 1,
+                column: entry.key.start.column + 1,
+                text: entry.name,
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_parameter(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver
+            .entries("/components/parameters")
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ParameterShow)
+            })?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::parameter::{ParameterNode, ParameterParser, TreeSitterParameterParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterParameterParser {
+        let root_content = r#"
+components:
+  parameters:
+    limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+    petId:
+      $ref: 'parameters/petId.yaml'
+"#;
+        let pet_id_content = r#"
+name: petId
+in: path
+schema:
+  type: string
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("parameters/petId.yaml"),
+                pet_id_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterParameterParser::new(provider)
+    }
+
+    #[test]
+    fn get_parameter_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_parameter_nodes()?;
+        assert_eq!(
+            nodes,
+            vec![
+                ParameterNode {
+                    text: "limit".to_string(),
+                    name: "limit".to_string(),
+                    location: "query".to_string(),
+                    required: false,
+                    file: PathBuf::from("#"),
+                    line: 4,
+                    column: 5,
+                },
+                ParameterNode {
+                    text: "petId".to_string(),
+                    name: "petId".to_string(),
+                    location: "path".to_string(),
+                    required: true,
+                    file: PathBuf::from("#"),
+                    line: 9,
+                    column: 5,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_parameter() -> Result<(), Box<dyn Error>> {
+        let parameter = parser().get_parameter("petId")?.unwrap();
+        assert_eq!(
+            parameter.to_yaml(),
+            "name: petId\nin: path\nschema:\n  type: string\n"
+        );
+        assert!(parser().get_parameter("missing")?.is_none());
+        Ok(())
+    }
+}
@@ -1,17 +1,38 @@
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::Result;
+use std::{collections::HashSet, path::PathBuf};
 
 use crate::{content::ContentProvider, error::OpenapiSchemerError};
 
-use super::{get_children_by_key, get_top_level_keys, ChildrenOrRef};
+use super::{resolve::Resolver, validate::HTTP_METHODS};
 
 #[derive(Clone, Debug)]
 pub struct PathNode {
     pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A summary of one HTTP method defined for a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathMethod {
+    pub method: String,
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
 }
 
 pub trait PathParser {
     fn get_path_nodes(&self) -> Result<Vec<PathNode>, OpenapiSchemerError>;
+    /// Calls `f` with each path of `get_path_nodes` as it is found, so a huge
+    /// spec can be handled a path at a time.
+    fn for_each_path_node(&self, f: &mut dyn FnMut(PathNode)) -> Result<(), OpenapiSchemerError> {
+        self.get_path_nodes()?.into_iter().for_each(f);
+        Ok(())
+    }
+    /// The methods defined for `path`, or `None` when the path does not
+    /// exist.
+    fn get_path_methods(&self, path: &str) -> Result<Option<Vec<PathMethod>>, OpenapiSchemerError>;
 }
 
 pub struct TreeSitterPathParser {
@@ -25,34 +46,95 @@ impl TreeSitterPathParser {
 }
 
 impl PathParser for TreeSitterPathParser {
-    fn get_path_nodes(&self) -> Result<Vec<PathNode>, OpenapiSchemerError> {
-        let content = self.provider.get_content(PathBuf::from("#"));
-        let mut results: Vec<PathNode> = vec![];
-
-        let mut paths_children = get_children_by_key("paths", content.as_bytes())
-            .with_context(|| format!("Failed to get children for yaml key `paths`"))
-            .map_err(|error| OpenapiSchemerError::PathList(error.to_string()))?;
-        if let ChildrenOrRef::Ref(r) = paths_children {
-            let content = self.provider.get_content(PathBuf::from(r));
-            paths_children = get_top_level_keys(content.as_bytes())
-                .with_context(|| format!("Failed to get children for yaml key `paths`"))
-                .map_err(|error| OpenapiSchemerError::PathList(error.to_string()))?;
+    fn get_path_methods(&self, path: &str) -> Result<Option<Vec<PathMethod>>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::PathShow)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path).map_err(to_error)?.to_owned();
+        let paths = match root.get("paths") {
+            Some(paths) => paths,
+            None => return Ok(None),
+        };
+        let (paths, paths_file) = resolver.follow(paths, &root_path).map_err(to_error)?;
+        let path_item = match paths.get(path) {
+            Some(path_item) => path_item,
+            None => return Ok(None),
+        };
+        let (path_item, path_item_file) =
+            resolver.follow(path_item, &paths_file).map_err(to_error)?;
+
+        let mut methods = vec![];
+        for pair in path_item.as_mapping().unwrap_or_default() {
+            let method = match pair.key.as_str() {
+                Some(method) if HTTP_METHODS.contains(&method) => method,
+                _ => continue,
+            };
+            let (operation, _) = resolver
+                .follow(&pair.value, &path_item_file)
+                .map_err(to_error)?;
+            let text = |key: &str| {
+                operation
+                    .get(key)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_owned)
+            };
+            let tags = operation
+                .get("tags")
+                .and_then(|tags| tags.as_sequence())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|tag| tag.as_str().map(str::to_owned))
+                .collect();
+            methods.push(PathMethod {
+                method: method.to_string(),
+                operation_id: text("operationId"),
+                summary: text("summary"),
+                tags,
+            });
         }
-        match paths_children {
-            super::ChildrenOrRef::Ref(_) => {
-                return Err(OpenapiSchemerError::PathList(format!(
-                    "$ref cannot link to another $ref"
-                )));
-            }
-            super::ChildrenOrRef::Children(children) => {
-                for (path, _) in children {
-                    results.push(PathNode { text: path })
-                }
+        Ok(Some(methods))
+    }
+
+    fn get_path_nodes(&self) -> Result<Vec<PathNode>, OpenapiSchemerError> {
+        let mut results = vec![];
+        self.for_each_path_node(&mut |node| results.push(node))?;
+        Ok(results)
+    }
+
+    fn for_each_path_node(&self, f: &mut dyn FnMut(PathNode)) -> Result<(), OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::PathList)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path).map_err(to_error)?.to_owned();
+        let paths = match root.get("paths") {
+            Some(paths) => paths,
+            None => return Ok(()),
+        };
+        let (paths, paths_file) = resolver.follow(paths, &root_path).map_err(to_error)?;
+        let file = self.provider.source_path(&paths_file);
+        let mut seen = HashSet::new();
+        for pair in paths.as_mapping().unwrap_or_default() {
+            let path = pair.key.as_str().unwrap_or_default();
+            // A path written twice is listed once, `validate` reports the
+            // duplicate.
+            if !seen.insert(path) {
+                continue;
             }
+            f(PathNode {
+                text: path.to_string(),
+                file: file.to_owned(),
+                line: pair.key.start.row + 1,
+                column: pair.key.start.column + 1,
+            })
         }
-        Ok(results)
+        Ok(())
     }
 }
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashMap, error::Error, path::PathBuf};
@@ -60,11 +142,65 @@ mod tests {
     use mocktopus::mocking::*;
 
     use crate::{
-        bindings::path::{PathParser, TreeSitterPathParser},
+        bindings::path::{PathMethod, PathParser, TreeSitterPathParser},
         content::ContentProvider,
         content::ContentProviderMap,
     };
 
+    #[test]
+    fn get_path_methods() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets/{petId}:
+    $ref: 'paths/pet.yaml'
+"#;
+        let pet_content = r#"
+parameters:
+  - name: petId
+    in: path
+get:
+  operationId: showPetById
+  summary: Info for a specific pet
+  tags:
+    - pets
+delete:
+  $ref: 'delete.yaml'
+"#;
+        let delete_content = r#"
+operationId: deletePet
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("paths/pet.yaml"), pet_content.to_owned()),
+            (
+                PathBuf::from("paths/delete.yaml"),
+                delete_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterPathParser::new(provider);
+        let methods = parser.get_path_methods("/pets/{petId}")?.unwrap();
+        assert_eq!(
+            methods,
+            vec![
+                PathMethod {
+                    method: "get".to_string(),
+                    operation_id: Some("showPetById".to_string()),
+                    summary: Some("Info for a specific pet".to_string()),
+                    tags: vec!["pets".to_string()],
+                },
+                PathMethod {
+                    method: "delete".to_string(),
+                    operation_id: Some("deletePet".to_string()),
+                    summary: None,
+                    tags: vec![],
+                },
+            ]
+        );
+        assert!(parser.get_path_methods("/missing")?.is_none());
+        Ok(())
+    }
+
     #[test]
     fn get_path_nodes_no_ref() -> Result<(), Box<dyn Error>> {
         let root_path = PathBuf::from("#");
@@ -81,7 +217,7 @@ paths:
 "#;
         let contents = HashMap::from([(root_path, root_content.to_owned())]);
         ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
+            MockResult::Return(Ok(contents.get(&path).unwrap().to_owned()))
         });
         let provider = ContentProviderMap::new();
         let box_provider = Box::new(provider);
@@ -95,6 +231,39 @@ paths:
         Ok(())
     }
 
+    #[test]
+    fn get_path_nodes_locations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: getPets
+  /pets/{petId}:
+    get:
+      operationId: showPetById
+  /owners:
+    get:
+      operationId: listOwners
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterPathParser::new(provider);
+        let nodes = parser.get_path_nodes().unwrap();
+        let paths: Vec<&str> = nodes.iter().map(|node| node.text.as_str()).collect();
+        assert_eq!(paths, vec!["/pets", "/pets/{petId}", "/owners"]);
+        let pets = nodes.iter().find(|node| node.text == "/pets").unwrap();
+        assert_eq!(pets.file, PathBuf::from("#"));
+        assert_eq!((pets.line, pets.column), (4, 3));
+        let pet = nodes
+            .iter()
+            .find(|node| node.text == "/pets/{petId}")
+            .unwrap();
+        assert_eq!((pet.line, pet.column), (7, 3));
+
+        Ok(())
+    }
+
     #[test]
     fn get_path_nodes_ref() -> Result<(), Box<dyn Error>> {
         let root_path = PathBuf::from("#");
@@ -119,7 +288,7 @@ paths:
             (paths_path, paths_content.to_owned()),
         ]);
         ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
+            MockResult::Return(Ok(contents.get(&path).unwrap().to_owned()))
         });
         let provider = ContentProviderMap::new();
         let box_provider = Box::new(provider);
@@ -0,0 +1,409 @@
+use regex::{Regex, RegexBuilder};
+
+/// A regular expression for the `pattern` function of lint rulesets, written
+/// as `/expression/flags` where the `i` flag ignores case. The expression is
+/// parsed as ECMA-262 syntax, covering literals, `.`, classes such as `[a-z]`
+/// and `\d`, the anchors `^` and `$`, groups with `|`, and the quantifiers
+/// `*`, `+`, `?` and `{m,n}`, then translated to the syntax of the `regex`
+/// crate, which matches in linear time whatever the pattern or text a spec
+/// brings. `Pattern::check` reads the rest of ECMA-262 well enough to catch
+/// malformed expressions.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+impl Pattern {
+    pub fn new(expression: &str) -> Result<Pattern, String> {
+        let (source, flags) = match expression
+            .strip_prefix('/')
+            .and_then(|rest| rest.rsplit_once('/'))
+        {
+            Some((source, flags)) => (source, flags),
+            None => (expression, ""),
+        };
+        if let Some(flag) = flags.chars().find(|flag| *flag != 'i') {
+            return Err(format!("Unsupported flag `{}` in `{}`", flag, expression));
+        }
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            position: 0,
+            syntax_only: false,
+        };
+        let alternatives = parser
+            .alternatives()
+            .map_err(|error| format!("Invalid pattern `{}`: {}", expression, error))?;
+        if parser.position < parser.chars.len() {
+            return Err(format!("Invalid pattern `{}`: unmatched `)`", expression));
+        }
+        let mut source = String::new();
+        write_alternatives(&alternatives, &mut source);
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(flags.contains('i'))
+            .build()
+            .map_err(|error| format!("Invalid pattern `{}`: {}", expression, error))?;
+        Ok(Pattern { regex })
+    }
+
+    /// Checks that `source`, a bare expression such as the `pattern` of a
+    /// schema, is well formed. Lookarounds, named groups and `\b`, which
+    /// matching doesn't support, are accepted.
+    pub fn check(source: &str) -> Result<(), String> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            position: 0,
+            syntax_only: true,
+        };
+        parser.alternatives()?;
+        match parser.position < parser.chars.len() {
+            true => Err("unmatched `)`".to_string()),
+            false => Ok(()),
+        }
+    }
+
+    /// Whether the expression matches anywhere in `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// Whether the expression is only read to check it, so the features
+    /// matching doesn't support can be skipped over.
+    syntax_only: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = vec![];
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some('(') => {
+                self.group_prefix()?;
+                let alternatives = self.alternatives()?;
+                match self.next() {
+                    Some(')') => Ok(Node::Group(alternatives)),
+                    _ => Err("unclosed `(`".to_string()),
+                }
+            }
+            Some('[') => self.class(),
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('\\') => self.escape(),
+            Some(c @ ('*' | '+' | '?')) => Err(format!("nothing to repeat before `{}`", c)),
+            Some(c) => Ok(Node::Char(c)),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+
+    /// Skips the `?:` of a non-capturing group and, when only checking the
+    /// syntax, the prefixes of lookarounds and named groups.
+    fn group_prefix(&mut self) -> Result<(), String> {
+        let rest = &self.chars[self.position..];
+        let skip = match rest {
+            ['?', ':', ..] => 2,
+            ['?', '=' | '!', ..] if self.syntax_only => 2,
+            ['?', '<', '=' | '!', ..] if self.syntax_only => 3,
+            ['?', '<', ..] if self.syntax_only => match rest.iter().position(|c| *c == '>') {
+                Some(end) if end > 2 => end + 1,
+                _ => return Err("unclosed group name".to_string()),
+            },
+            _ => 0,
+        };
+        self.position += skip;
+        Ok(())
+    }
+
+    fn escape(&mut self) -> Result<Node, String> {
+        let c = self.next().ok_or("trailing `\\`")?;
+        Ok(match shorthand(c) {
+            Some((ranges, negated)) => Node::Class { ranges, negated },
+            None => match c {
+                // A word boundary is an anchor like `^` as far as what may
+                // follow it goes.
+                'b' | 'B' if self.syntax_only => Node::Start,
+                'b' | 'B' => return Err(format!("`\\{}` is not supported", c)),
+                c => Node::Char(escaped(c)),
+            },
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.position += 1;
+        }
+        let mut ranges = vec![];
+        let mut first = true;
+        loop {
+            let c = match self.next() {
+                Some(']') if !first => break,
+                Some(c) => c,
+                None => return Err("unclosed `[`".to_string()),
+            };
+            first = false;
+            let start = match c {
+                '\\' => {
+                    let c = self.next().ok_or("trailing `\\`")?;
+                    match shorthand(c) {
+                        Some((shorthand, false)) => {
+                            ranges.extend(shorthand);
+                            continue;
+                        }
+                        Some(_) => return Err(format!("`\\{}` is not supported in `[]`", c)),
+                        None => escaped(c),
+                    }
+                }
+                c => c,
+            };
+            let range = self.peek() == Some('-') && self.chars.get(self.position + 1) != Some(&']');
+            if !range {
+                ranges.push((start, start));
+                continue;
+            }
+            self.position += 1;
+            let end = match self.next() {
+                Some('\\') => escaped(self.next().ok_or("trailing `\\`")?),
+                Some(end) => end,
+                None => return Err("unclosed `[`".to_string()),
+            };
+            if end < start {
+                return Err(format!("invalid range `{}-{}`", start, end));
+            }
+            ranges.push((start, end));
+        }
+        Ok(Node::Class { ranges, negated })
+    }
+
+    fn quantified(&mut self, node: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.bounds() {
+                Some((min, Some(max))) if max < min => {
+                    return Err(format!("numbers out of order in `{{{},{}}}`", min, max))
+                }
+                Some(bounds) => bounds,
+                None => return Ok(node),
+            },
+            _ => return Ok(node),
+        };
+        self.position += 1;
+        // Lazy quantifiers match the same texts.
+        if self.peek() == Some('?') {
+            self.position += 1;
+        }
+        if matches!(node, Node::Start | Node::End) {
+            return Err("nothing to repeat".to_string());
+        }
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    /// The bounds of `{m}`, `{m,}` or `{m,n}`, leaving the closing `}` to be
+    /// skipped. `None` for a `{` that is not a quantifier.
+    fn bounds(&mut self) -> Option<(usize, Option<usize>)> {
+        let rest: String = self.chars[self.position + 1..].iter().collect();
+        let (inside, _) = rest.split_once('}')?;
+        let bounds = match inside.split_once(',') {
+            Some((min, "")) => (min.parse().ok()?, None),
+            Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+            None => {
+                let count = inside.parse().ok()?;
+                (count, Some(count))
+            }
+        };
+        self.position += inside.chars().count() + 1;
+        Some(bounds)
+    }
+}
+
+/// The ranges of `\d`, `\w` and `\s`, and whether they are negated as in
+/// `\D`.
+fn shorthand(c: char) -> Option<(Vec<(char, char)>, bool)> {
+    let ranges = match c.to_ascii_lowercase() {
+        'd' => vec![('0', '9')],
+        'w' => vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+        's' => vec![(' ', ' '), ('\t', '\r')],
+        _ => return None,
+    };
+    Some((ranges, c.is_ascii_uppercase()))
+}
+
+fn escaped(c: char) -> char {
+    match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        c => c,
+    }
+}
+
+/// Writes `alternatives` in the syntax of the `regex` crate, escaping every
+/// literal so ECMA-262 leniencies such as a bare `{` keep their meaning.
+fn write_alternatives(alternatives: &[Vec<Node>], out: &mut String) {
+    for (index, alternative) in alternatives.iter().enumerate() {
+        if index > 0 {
+            out.push('|');
+        }
+        for node in alternative {
+            write_node(node, out);
+        }
+    }
+}
+
+fn write_node(node: &Node, out: &mut String) {
+    match node {
+        Node::Char(c) => out.push_str(&regex::escape(&c.to_string())),
+        Node::Any => out.push('.'),
+        Node::Class { ranges, negated } => {
+            out.push('[');
+            if *negated {
+                out.push('^');
+            }
+            for (start, end) in ranges {
+                out.push_str(&regex::escape(&start.to_string()));
+                if start != end {
+                    out.push('-');
+                    out.push_str(&regex::escape(&end.to_string()));
+                }
+            }
+            out.push(']');
+        }
+        Node::Start => out.push('^'),
+        Node::End => out.push('$'),
+        Node::Group(alternatives) => {
+            out.push_str("(?:");
+            write_alternatives(alternatives, out);
+            out.push(')');
+        }
+        Node::Repeat { node, min, max } => {
+            write_node(node, out);
+            match max {
+                Some(max) => out.push_str(&format!("{{{},{}}}", min, max)),
+                None => out.push_str(&format!("{{{},}}", min)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    #[test]
+    fn test_is_match() {
+        let matches = |pattern: &str, text: &str| Pattern::new(pattern).unwrap().is_match(text);
+        assert!(matches("^[a-z][a-zA-Z0-9]*$", "listPets"));
+        assert!(!matches("^[a-z][a-zA-Z0-9]*$", "ListPets"));
+        assert!(matches("pets", "/v1/pets/{id}"));
+        assert!(matches("^/(pets|users)(/\\{\\w+\\})?$", "/users/{userId}"));
+        assert!(!matches("^/(pets|users)(/\\{\\w+\\})?$", "/users/"));
+        assert!(matches("^\\d{3}$", "200"));
+        assert!(!matches("^\\d{3}$", "2000"));
+        assert!(matches("^a{2,}b?$", "aaa"));
+        assert!(matches("^[^\\s]+$", "no-spaces"));
+        assert!(!matches("^[^\\s]+$", "has spaces"));
+        assert!(matches("/^[a-z]+$/i", "PETS"));
+        assert!(matches("^(a*)*b$", "aaab"));
+        assert!(matches("^[-._a-z]+$", "x-rate.limit_"));
+        assert!(matches("^/pets/{petId}$", "/pets/{petId}"));
+        assert!(matches("/^[^A-Z]+$/i", "123"));
+    }
+
+    #[test]
+    fn test_is_match_linear() {
+        // Patterns that backtrack exponentially, or recurse once per
+        // character, in a backtracking matcher.
+        let nested = Pattern::new("^(a+)+$").unwrap();
+        assert!(!nested.is_match(&format!("{}!", "a".repeat(64))));
+        let any = Pattern::new("^.*$").unwrap();
+        assert!(any.is_match(&"x".repeat(100_000)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |pattern: &str| Pattern::new(pattern).unwrap_err();
+        assert_eq!(error("(pets"), "Invalid pattern `(pets`: unclosed `(`");
+        assert_eq!(error("pets)"), "Invalid pattern `pets)`: unmatched `)`");
+        assert_eq!(error("[a-"), "Invalid pattern `[a-`: unclosed `[`");
+        assert_eq!(
+            error("*a"),
+            "Invalid pattern `*a`: nothing to repeat before `*`"
+        );
+        assert_eq!(error("/a/g"), "Unsupported flag `g` in `/a/g`");
+        assert_eq!(
+            error("a{3,1}"),
+            "Invalid pattern `a{3,1}`: numbers out of order in `{3,1}`"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            Pattern::check("^/api/v1/(?<id>\\d+)\\b(?=x)(?<!y)$"),
+            Ok(())
+        );
+        assert_eq!(Pattern::check("^[a-z"), Err("unclosed `[`".to_string()));
+        assert_eq!(Pattern::check("a)"), Err("unmatched `)`".to_string()));
+        assert_eq!(Pattern::check("\\b+"), Err("nothing to repeat".to_string()));
+        assert_eq!(
+            Pattern::check("(?<id\\d+)"),
+            Err("unclosed group name".to_string())
+        );
+    }
+}
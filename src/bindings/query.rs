@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, Result};
+use tree_sitter::{Query, QueryCursor};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{language, resolve::Resolver, TreeCache};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryCapture {
+    /// The capture name without its `@`.
+    pub name: String,
+    /// The source text of the captured node.
+    pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait QueryParser {
+    /// The captures of the tree-sitter query `source` in document order,
+    /// within the root document or, with `all_files`, within every file the
+    /// spec refers to.
+    fn get_captures(
+        &self,
+        source: &str,
+        all_files: bool,
+    ) -> Result<Vec<QueryCapture>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterQueryParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterQueryParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn captures(&self, source: &str, all_files: bool) -> Result<Vec<QueryCapture>> {
+        // tree-sitter panics on an unknown node name that ends the query, so
+        // a newline always follows it.
+        let query = Query::new(language(), &format!("{}\n", source))
+            .map_err(|error| Error::msg(format!("Invalid query: {}", error)))?;
+        let files = match all_files {
+            true => Resolver::new(self.provider.as_ref()).files()?,
+            false => vec![PathBuf::from("#")],
+        };
+        let mut trees = TreeCache::new();
+        let mut captures = vec![];
+        for file in files {
+            let content = self.provider.get_content(file.to_owned())?;
+            captures.extend(self.file_captures(&query, &file, content.as_bytes(), &mut trees)?);
+        }
+        Ok(captures)
+    }
+
+    fn file_captures(
+        &self,
+        query: &Query,
+        file: &Path,
+        content: &[u8],
+        trees: &mut TreeCache,
+    ) -> Result<Vec<QueryCapture>> {
+        let tree = trees.tree(content)?;
+        let mut cursor = QueryCursor::new();
+        let mut captures = vec![];
+        for (found, index) in cursor.captures(query, tree.root_node(), content) {
+            let capture = found.captures[index];
+            let position = capture.node.start_position();
+            captures.push(QueryCapture {
+                name: query.capture_names()[capture.index as usize].to_owned(),
+                text: capture.node.utf8_text(content)?.to_string(),
+                file: self.provider.source_path(file),
+                line: position.row + 1,
+                column: position.column + 1,
+            });
+        }
+        Ok(captures)
+    }
+}
+
+impl QueryParser for TreeSitterQueryParser {
+    fn get_captures(
+        &self,
+        source: &str,
+        all_files: bool,
+    ) -> Result<Vec<QueryCapture>, OpenapiSchemerError> {
+        self.captures(source, all_files)
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::query::{QueryParser, TreeSitterQueryParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn query_captures() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          $ref: 'responses.yaml#/Pets'
+"#;
+        let responses_content = r#"Pets:
+  description: A list of pets
+  x-internal: true
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterQueryParser::new(provider);
+        let query = r#"(block_mapping_pair
+  key: (flow_node) @key (#match? @key "^(operationId|x-.*)$")
+  value: (flow_node) @value)"#;
+        let captures = |all_files| -> Vec<(String, String, usize, usize)> {
+            parser
+                .get_captures(query, all_files)
+                .unwrap()
+                .into_iter()
+                .map(|capture| (capture.name, capture.text, capture.line, capture.column))
+                .collect()
+        };
+        let root = vec![
+            ("key".to_string(), "operationId".to_string(), 5, 7),
+            ("value".to_string(), "listPets".to_string(), 5, 20),
+        ];
+        assert_eq!(captures(false), root);
+        let mut all = root;
+        all.extend([
+            ("key".to_string(), "x-internal".to_string(), 3, 3),
+            ("value".to_string(), "true".to_string(), 3, 15),
+        ]);
+        assert_eq!(captures(true), all);
+
+        let error = |source: &str| parser.get_captures(source, false).unwrap_err().to_string();
+        assert_eq!(
+            error("(mapping"),
+            "Invalid query: Query error at 1:2. Invalid node type mapping"
+        );
+        assert!(error("(block_mapping_pair").starts_with("Invalid query: Query error at 2:1."));
+        Ok(())
+    }
+}
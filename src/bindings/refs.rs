@@ -0,0 +1,633 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    child_pointer,
+    resolve::{split_ref, Resolver},
+    yaml::{pointer_segments, YamlNode, YamlValue},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefNode {
+    /// The `$ref` as written in the spec.
+    pub text: String,
+    /// The object holding the `$ref`, as `file#/pointer`.
+    pub source: String,
+    /// What the `$ref` points at, as `file#/pointer`.
+    pub target: String,
+    /// Whether the target lives in another file.
+    pub external: bool,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BrokenRefNode {
+    /// The `$ref` as written in the spec.
+    pub text: String,
+    /// Why the ref could not be resolved.
+    pub reason: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Where the `$ref` at a position in the spec points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefTarget {
+    /// The `$ref` as written in the spec.
+    pub text: String,
+    /// What the `$ref` points at, as `file#/pointer`.
+    pub target: String,
+    /// The file, line and column of the key the target is written under.
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait RefParser {
+    fn get_ref_nodes(&self) -> Result<Vec<RefNode>, OpenapiSchemerError>;
+    fn get_broken_refs(&self) -> Result<Vec<BrokenRefNode>, OpenapiSchemerError>;
+
+    /// The target of the `$ref` written at the 1-based `line` and `column`
+    /// of `file`, if there is one. A position on the `$ref` key counts as
+    /// well as one on its value.
+    fn get_ref_target(
+        &self,
+        file: &Path,
+        line: usize,
+        column: usize,
+    ) -> Result<Option<RefTarget>, OpenapiSchemerError>;
+
+    /// Every `$ref` that resolves to the node at `pointer`, which is
+    /// relative to the root document, however the `$ref` spells its path.
+    fn get_refs_to(&self, pointer: &str) -> Result<Vec<RefNode>, OpenapiSchemerError>;
+}
+
+/// A `$ref` found while walking the spec.
+struct FoundRef {
+    reference: String,
+    /// Pointer to the object holding the `$ref`.
+    pointer: String,
+    file: PathBuf,
+    node: YamlNode,
+}
+
+pub struct TreeSitterRefParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterRefParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Every `$ref` value in `node`, which lives at `pointer` in `file`.
+    fn collect_refs(node: &YamlNode, pointer: &str, file: &Path, refs: &mut Vec<FoundRef>) {
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    match (key, pair.value.as_str()) {
+                        ("$ref", Some(reference)) => refs.push(FoundRef {
+                            reference: reference.to_string(),
+                            pointer: pointer.to_string(),
+                            file: file.to_path_buf(),
+                            node: pair.value.to_owned(),
+                        }),
+                        _ => Self::collect_refs(
+                            &pair.value,
+                            &child_pointer(pointer, key),
+                            file,
+                            refs,
+                        ),
+                    }
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    Self::collect_refs(item, &format!("{}/{}", pointer, index), file, refs);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Every `$ref` in the files reachable from the root document.
+    fn refs(&self, resolver: &mut Resolver) -> Result<Vec<FoundRef>> {
+        let mut results = vec![];
+        for file in resolver.files()? {
+            let document = resolver.document(&file)?.to_owned();
+            Self::collect_refs(&document, "", &file, &mut results);
+        }
+        Ok(results)
+    }
+
+    fn ref_node(&self, resolver: &Resolver, found: FoundRef) -> RefNode {
+        let (target_file, fragment) = split_ref(&found.file, &found.reference);
+        RefNode {
+            source: resolver.location(&found.file, &found.pointer),
+            target: resolver.location(&target_file, &fragment),
+            external: target_file != found.file,
+            file: self.provider.source_path(&found.file),
+            line: found.node.start.row + 1,
+            column: found.node.start.column + 1,
+            text: found.reference,
+        }
+    }
+
+    fn ref_nodes(&self) -> Result<Vec<RefNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for found in self.refs(&mut resolver)? {
+            results.push(self.ref_node(&resolver, found));
+        }
+        Ok(results)
+    }
+
+    /// The path of `file` on disk, with `..` and links resolved when the
+    /// file exists, so differently spelled paths to it compare equal.
+    fn canonical_path(&self, file: &Path) -> PathBuf {
+        let source = self.provider.source_path(file);
+        std::fs::canonicalize(&source).unwrap_or(source)
+    }
+
+    /// The file and bytes of the node `fragment` of `file` ends up at once
+    /// every `$ref` on the way is followed.
+    fn node_id(
+        &self,
+        resolver: &mut Resolver,
+        file: &Path,
+        fragment: &str,
+    ) -> Result<Option<(PathBuf, Range<usize>)>> {
+        let (node, node_file) = match resolver.resolve_pointer(file, fragment)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let (node, node_file) = resolver.follow(&node, &node_file)?;
+        Ok(Some((self.canonical_path(&node_file), node.bytes)))
+    }
+
+    fn refs_to(&self, pointer: &str) -> Result<Vec<RefNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let (file, fragment) = split_ref(Path::new("#"), pointer);
+        let wanted = match self.node_id(&mut resolver, &file, &fragment)? {
+            Some(wanted) => wanted,
+            None => bail!("`{}` does not exist", pointer),
+        };
+        let mut results = vec![];
+        for found in self.refs(&mut resolver)? {
+            let (target_file, fragment) = split_ref(&found.file, &found.reference);
+            // Broken refs point at nothing, so they are left to `refs check`.
+            let target = self.node_id(&mut resolver, &target_file, &fragment);
+            if target.ok().flatten().as_ref() == Some(&wanted) {
+                results.push(self.ref_node(&resolver, found));
+            }
+        }
+        Ok(results)
+    }
+
+    fn check(
+        &self,
+        resolver: &mut Resolver,
+        file: &Path,
+        reference: &str,
+    ) -> Result<Option<String>> {
+        let (target_file, fragment) = split_ref(file, reference);
+        if let Err(error) = self.provider.get_content(target_file.to_owned()) {
+            return Ok(Some(format!("$ref `{}`: {}", reference, error)));
+        }
+        match resolver.resolve_pointer(&target_file, &fragment) {
+            // The target can itself be a ref, so the rest of the chain is
+            // followed to catch refs that end up pointing back at themselves.
+            Ok(Some((node, node_file))) => match resolver.follow(&node, &node_file) {
+                Err(error) => match error.downcast::<OpenapiSchemerError>() {
+                    Ok(circular) => Ok(Some(format!("$ref `{}`: {}", reference, circular))),
+                    Err(_) => Ok(None),
+                },
+                Ok(_) => Ok(None),
+            },
+            Ok(None) => Ok(Some(format!(
+                "$ref `{}` points at a key that does not exist",
+                reference
+            ))),
+            Err(error) => Ok(Some(format!("$ref `{}`: {}", reference, error))),
+        }
+    }
+
+    /// Where the node at `fragment` of `file` is written. Mapping entries
+    /// are located at their key, which is where an editor should jump to.
+    fn target_location(
+        &self,
+        resolver: &mut Resolver,
+        file: &Path,
+        fragment: &str,
+    ) -> Result<Option<(PathBuf, usize, usize)>> {
+        let last = match pointer_segments(fragment).and_then(|mut segments| segments.pop()) {
+            Some(last) => last,
+            None => return Ok(Some((file.to_path_buf(), 1, 1))),
+        };
+        let parent = &fragment[..fragment.rfind('/').unwrap_or_default()];
+        let (parent, parent_file) = match resolver.resolve_pointer(file, parent)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let (parent, parent_file) = resolver.follow(&parent, &parent_file)?;
+        let start = match &parent.value {
+            YamlValue::Mapping(pairs) => pairs
+                .iter()
+                .find(|pair| pair.key.as_str() == Some(last.as_str()))
+                .map(|pair| pair.key.start),
+            _ => parent.child(&last).map(|child| child.start),
+        };
+        Ok(start.map(|start| (parent_file, start.row + 1, start.column + 1)))
+    }
+
+    fn ref_target(&self, file: &Path, line: usize, column: usize) -> Result<Option<RefTarget>> {
+        let wanted = std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        // Of the refs written on the line, the first one ending at or after
+        // the column is taken, so both the key and the value of a `$ref`
+        // lead to it.
+        let found = self
+            .refs(&mut resolver)?
+            .into_iter()
+            .filter(|found| {
+                self.canonical_path(&found.file) == wanted && found.node.start.row + 1 == line
+            })
+            .filter(|found| column <= found.node.start.column + found.node.bytes.len())
+            .min_by_key(|found| found.node.start.column);
+        let found = match found {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let (target_file, fragment) = split_ref(&found.file, &found.reference);
+        let location = self.target_location(&mut resolver, &target_file, &fragment)?;
+        let (file, line, column) = match location {
+            Some(location) => location,
+            None => bail!(
+                "$ref `{}` points at a key that does not exist",
+                found.reference
+            ),
+        };
+        Ok(Some(RefTarget {
+            target: resolver.location(&target_file, &fragment),
+            file: self.provider.source_path(&file),
+            line,
+            column,
+            text: found.reference,
+        }))
+    }
+
+    fn broken_refs(&self) -> Result<Vec<BrokenRefNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for found in self.refs(&mut resolver)? {
+            if let Some(reason) = self.check(&mut resolver, &found.file, &found.reference)? {
+                results.push(BrokenRefNode {
+                    text: found.reference,
+                    reason,
+                    file: self.provider.source_path(&found.file),
+                    line: found.node.start.row + 1,
+                    column: found.node.start.column + 1,
+                });
+            }
+        }
+        Ok(results)
+    }
+}
+
+impl RefParser for TreeSitterRefParser {
+    fn get_ref_nodes(&self) -> Result<Vec<RefNode>, OpenapiSchemerError> {
+        self.ref_nodes()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::RefsList))
+    }
+
+    fn get_broken_refs(&self) -> Result<Vec<BrokenRefNode>, OpenapiSchemerError> {
+        self.broken_refs().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::RefsCheck)
+        })
+    }
+
+    fn get_ref_target(
+        &self,
+        file: &Path,
+        line: usize,
+        column: usize,
+    ) -> Result<Option<RefTarget>, OpenapiSchemerError> {
+        self.ref_target(file, line, column).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::RefsResolve)
+        })
+    }
+
+    fn get_refs_to(&self, pointer: &str) -> Result<Vec<RefNode>, OpenapiSchemerError> {
+        self.refs_to(pointer)
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::RefsTo))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        error::Error,
+        path::{Path, PathBuf},
+    };
+
+    use crate::{
+        bindings::refs::{RefParser, TreeSitterRefParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn ref_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+components:
+  schemas:
+    Pets:
+      type: array
+      items:
+        $ref: '#/components/schemas/Pet'
+    Pet:
+      type: object
+"#;
+        let pets_content = r#"
+get:
+  parameters:
+    - $ref: '../openapi.yaml#/components/parameters/Limit'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("resources/pets.yaml"),
+                pets_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterRefParser::new(provider);
+        let nodes: Vec<(String, String, bool, usize)> = parser
+            .get_ref_nodes()?
+            .into_iter()
+            .map(|node| (node.source, node.target, node.external, node.line))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                (
+                    "#/paths/~1pets".to_string(),
+                    "resources/pets.yaml".to_string(),
+                    true,
+                    4
+                ),
+                (
+                    "#/components/schemas/Pets/items".to_string(),
+                    "#/components/schemas/Pet".to_string(),
+                    false,
+                    10
+                ),
+                (
+                    "resources/pets.yaml#/get/parameters/0".to_string(),
+                    "openapi.yaml#/components/parameters/Limit".to_string(),
+                    true,
+                    4
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ref_target() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+components:
+  schemas:
+    Pets:
+      type: array
+      items: { $ref: '#/components/schemas/Pet' }
+    Pet:
+      type: object
+"#;
+        let pets_content = r#"get:
+  parameters:
+    - $ref: '#/components/schemas/Missing'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("resources/pets.yaml"),
+                pets_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterRefParser::new(provider);
+        let target = |line, column| {
+            parser
+                .get_ref_target(Path::new("#"), line, column)
+                .map(|target| {
+                    target.map(|target| {
+                        (
+                            target.target,
+                            target.file.display().to_string(),
+                            target.line,
+                            target.column,
+                        )
+                    })
+                })
+        };
+        assert_eq!(
+            target(3, 5)?,
+            Some((
+                "resources/pets.yaml".to_string(),
+                "resources/pets.yaml".to_string(),
+                1,
+                1
+            ))
+        );
+        assert_eq!(
+            target(8, 30)?,
+            Some((
+                "#/components/schemas/Pet".to_string(),
+                "#".to_string(),
+                9,
+                5
+            ))
+        );
+        assert_eq!(target(8, 50)?, None);
+        assert_eq!(target(7, 7)?, None);
+        assert_eq!(
+            parser
+                .get_ref_target(Path::new("resources/pets.yaml"), 3, 7)
+                .unwrap_err()
+                .to_string(),
+            "$ref `#/components/schemas/Missing` points at a key that does not exist"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refs_to() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+components:
+  schemas:
+    Pets:
+      type: array
+      items:
+        $ref: '#/components/schemas/Pet'
+    Pet:
+      $ref: 'schemas.yaml#/Pet'
+    Missing:
+      $ref: '#/components/schemas/Unknown'
+"#;
+        let pets_content = r#"get:
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas.yaml#/Pet'
+    default:
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas.yaml#/Error'
+"#;
+        let schemas_content = r#"Pet:
+  type: object
+Error:
+  type: object
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("resources/pets.yaml"),
+                pets_content.to_owned(),
+            ),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterRefParser::new(provider);
+        let sources: Vec<(String, usize)> = parser
+            .get_refs_to("#/components/schemas/Pet")?
+            .into_iter()
+            .map(|node| (node.source, node.line))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("#/components/schemas/Pets/items".to_string(), 9),
+                ("#/components/schemas/Pet".to_string(), 11),
+                (
+                    "resources/pets.yaml#/get/responses/200/content/application~1json/schema"
+                        .to_string(),
+                    7
+                ),
+            ]
+        );
+        assert_eq!(
+            parser
+                .get_refs_to("#/components/schemas/Unknown")
+                .unwrap_err()
+                .to_string(),
+            "`#/components/schemas/Unknown` does not exist"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn broken_refs() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+  /owners:
+    $ref: 'resources/owners.yaml'
+components:
+  schemas:
+    Pet:
+      type: object
+    Node:
+      $ref: '#/components/schemas/Node'
+"#;
+        let pets_content = r#"
+get:
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas.yaml#/Pet'
+    default:
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Error'
+"#;
+        let schemas_content = r#"
+Pet:
+  type: object
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("resources/pets.yaml"),
+                pets_content.to_owned(),
+            ),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterRefParser::new(provider);
+        let nodes = parser.get_broken_refs()?;
+        let nodes: Vec<(String, usize, usize, String)> = nodes
+            .into_iter()
+            .map(|node| {
+                (
+                    node.file.display().to_string(),
+                    node.line,
+                    node.column,
+                    node.reason,
+                )
+            })
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                (
+                    "#".to_string(),
+                    6,
+                    11,
+                    "$ref `resources/owners.yaml`: Could not read `resources/owners.yaml`"
+                        .to_string()
+                ),
+                (
+                    "#".to_string(),
+                    12,
+                    13,
+                    "$ref `#/components/schemas/Node`: Circular $ref: #/components/schemas/Node -> #/components/schemas/Node"
+                        .to_string()
+                ),
+                (
+                    "resources/pets.yaml".to_string(),
+                    13,
+                    19,
+                    "$ref `#/components/schemas/Error` points at a key that does not exist"
+                        .to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,818 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError, http::encode};
+
+use super::{
+    edit::{apply_edits, FileEdits, TextEdit},
+    resolve::{split_ref, MappingEntry, Resolver},
+    validate::HTTP_METHODS,
+    yaml::{escape_pointer_segment, pointer_segments, scalar_like, Scalar, YamlNode, YamlValue},
+};
+
+pub trait OperationRenamer {
+    /// The edits that rename the operation `from` to `to` in every file of the
+    /// spec, or `None` when there is no operation `from`.
+    fn get_rename_edits(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterOperationRenamer {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterOperationRenamer {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn rename_edits(&self, from: &str, to: &str) -> Result<Option<Vec<FileEdits>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut found = false;
+        let mut results = vec![];
+        for file in resolver.files()? {
+            // The document as it is written. Its aliases are not resolved, so
+            // every id is found once, in the text it has to be changed in.
+            let document = self.provider.get_source_document(file.to_owned())?;
+            let mut ids = vec![];
+            operation_ids(&document, &mut vec![], &mut ids);
+            for id in &ids {
+                if id.operation && id.scalar.text == to {
+                    bail!("An operation `{}` already exists", to);
+                }
+                found |= id.operation && id.scalar.text == from;
+            }
+            let edits: Vec<TextEdit> = ids
+                .into_iter()
+                .filter(|id| id.scalar.text == from)
+                .map(|id| replace(id.node, id.scalar, to))
+                .collect();
+            if !edits.is_empty() {
+                results.push(file_edits(self.provider.as_ref(), &file, edits)?);
+            }
+        }
+        Ok(found.then_some(results))
+    }
+}
+
+impl OperationRenamer for TreeSitterOperationRenamer {
+    fn get_rename_edits(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+        self.rename_edits(from, to).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationRename)
+        })
+    }
+}
+
+pub trait SchemaRenamer {
+    /// The edits that rename the component schema `from` to `to`, along with
+    /// every ref to it, in every file of the spec. `None` when there is no
+    /// schema `from`.
+    fn get_rename_edits(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterSchemaRenamer {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterSchemaRenamer {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn rename_edits(&self, from: &str, to: &str) -> Result<Option<Vec<FileEdits>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let schemas = resolver.entries("/components/schemas")?;
+        if schemas.iter().any(|schema| schema.name == to) {
+            bail!("A schema `{}` already exists", to);
+        }
+        let schema = match schemas.into_iter().find(|schema| schema.name == from) {
+            Some(schema) => schema,
+            None => return Ok(None),
+        };
+        let mut renames = KeyRenames::new(self.provider.as_ref());
+        renames.rename(&schema, &["components", "schemas"], to)?;
+        let files = resolver.files()?;
+        renames.rename_refs(&files, true)?;
+        renames.file_edits(&files).map(Some)
+    }
+}
+
+impl SchemaRenamer for TreeSitterSchemaRenamer {
+    fn get_rename_edits(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+        self.rename_edits(from, to).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaRename)
+        })
+    }
+}
+
+/// How `path rename` changes the paths of a spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathRename {
+    /// Renames the path `from` to `to`.
+    Path { from: String, to: String },
+    /// Puts the prefix in front of every path, such as `/v2`.
+    Prefix(String),
+}
+
+impl PathRename {
+    /// The new name of `path`, or `None` when it stays as it is.
+    pub fn apply(&self, path: &str) -> Option<String> {
+        match self {
+            PathRename::Path { from, to } => (path == from).then(|| to.to_owned()),
+            PathRename::Prefix(prefix) => Some(format!("{}{}", prefix.trim_end_matches('/'), path)),
+        }
+    }
+}
+
+pub trait PathRenamer {
+    /// The edits that rename the paths of the spec along with the refs and
+    /// `operationRef`s pointing into them. `None` when the path to rename
+    /// does not exist.
+    fn get_rename_edits(
+        &self,
+        rename: &PathRename,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterPathRenamer {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterPathRenamer {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn rename_edits(&self, rename: &PathRename) -> Result<Option<Vec<FileEdits>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let paths = resolver.entries("/paths")?;
+        let mut names = HashSet::new();
+        for path in &paths {
+            let name = rename.apply(&path.name).unwrap_or(path.name.to_owned());
+            if !names.insert(name.to_owned()) {
+                bail!("A path `{}` already exists", name);
+            }
+        }
+        let mut renames = KeyRenames::new(self.provider.as_ref());
+        for path in &paths {
+            if let Some(to) = rename.apply(&path.name) {
+                renames.rename(path, &["paths"], &to)?;
+            }
+        }
+        if renames.targets.is_empty() && matches!(rename, PathRename::Path { .. }) {
+            return Ok(None);
+        }
+        let files = resolver.files()?;
+        renames.rename_refs(&files, false)?;
+        renames.file_edits(&files).map(Some)
+    }
+}
+
+impl PathRenamer for TreeSitterPathRenamer {
+    fn get_rename_edits(
+        &self,
+        rename: &PathRename,
+    ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+        self.rename_edits(rename).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::PathRename)
+        })
+    }
+}
+
+/// A renamed key that refs point through, such as the name of a schema.
+struct RenamedKey {
+    /// The file on disk the refs point into.
+    file: PathBuf,
+    /// The keys leading from the top of `file` to the renamed one, itself
+    /// included.
+    path: Vec<String>,
+    to: String,
+}
+
+/// The edits of renaming keys of the spec and the refs through them,
+/// gathered per file.
+pub(crate) struct KeyRenames<'a> {
+    provider: &'a dyn ContentProvider,
+    edits: HashMap<PathBuf, Vec<TextEdit>>,
+    targets: Vec<RenamedKey>,
+}
+
+impl<'a> KeyRenames<'a> {
+    pub(crate) fn new(provider: &'a dyn ContentProvider) -> Self {
+        Self {
+            provider,
+            edits: HashMap::new(),
+            targets: vec![],
+        }
+    }
+
+    /// Points refs through the keys `path` of the root document at `to`
+    /// instead, without renaming the key itself, such as when it is removed.
+    pub(crate) fn retarget(&mut self, path: Vec<String>, to: &str) {
+        self.targets.push(RenamedKey {
+            file: self.provider.source_path(Path::new("#")),
+            path,
+            to: to.to_string(),
+        });
+    }
+
+    /// Renames the key of `entry`, an entry of the mapping at `parent` in the
+    /// root document, to `to`.
+    fn rename(&mut self, entry: &MappingEntry, parent: &[&str], to: &str) -> Result<()> {
+        let key = match &entry.key.value {
+            YamlValue::Scalar(key) => key,
+            _ => return Ok(()),
+        };
+        self.edits
+            .entry(entry.key_file.to_owned())
+            .or_default()
+            .push(replace(&entry.key, key, to));
+        // Refs reach the key through the root document, and straight
+        // through the file it is written in when that is another one.
+        let mut path: Vec<String> = parent.iter().map(|key| key.to_string()).collect();
+        path.push(entry.name.to_owned());
+        self.retarget(path, to);
+        let document = self
+            .provider
+            .get_source_document(entry.key_file.to_owned())?;
+        if let Some(path) = key_path(&document, &entry.key.bytes) {
+            self.targets.push(RenamedKey {
+                file: self.provider.source_path(&entry.key_file),
+                path,
+                to: to.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Points the refs in `files` through the renamed keys at their new
+    /// names. With `names` the schemas named by discriminator mappings are
+    /// renamed as well.
+    pub(crate) fn rename_refs(&mut self, files: &[PathBuf], names: bool) -> Result<()> {
+        for file in files {
+            let document = self.provider.get_source_document(file.to_owned())?;
+            let mut refs = vec![];
+            references(&document, None, &mut refs);
+            for reference in refs {
+                let text = &reference.scalar.text;
+                let renamed = match reference.name_only {
+                    true if names => self
+                        .targets
+                        .iter()
+                        .find(|target| target.path.last() == Some(text))
+                        .map(|target| target.to.to_owned()),
+                    true => None,
+                    false => self.renamed_ref(file, text),
+                };
+                if let Some(renamed) = renamed {
+                    self.edits.entry(file.to_owned()).or_default().push(replace(
+                        reference.node,
+                        reference.scalar,
+                        &renamed,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `reference`, found in `file`, pointing at the new name instead when it
+    /// points through a renamed key.
+    fn renamed_ref(&self, file: &Path, reference: &str) -> Option<String> {
+        let (target_file, fragment) = split_ref(file, reference);
+        let segments = pointer_segments(&fragment)?;
+        let source = self.provider.source_path(&target_file);
+        let target = self
+            .targets
+            .iter()
+            .find(|target| target.file == source && segments.starts_with(&target.path))?;
+        let (target_file, fragment) = reference.split_once('#')?;
+        let mut raw: Vec<String> = fragment.split('/').map(str::to_owned).collect();
+        // The first raw segment is the empty one before the leading `/`.
+        let segment = &mut raw[target.path.len()];
+        *segment = match segment.contains('%') {
+            true => encode(&escape_pointer_segment(&target.to)),
+            false => escape_pointer_segment(&target.to),
+        };
+        Some(format!("{}#{}", target_file, raw.join("/")))
+    }
+
+    /// The edits made to each of `files`, in the order of `files`.
+    pub(crate) fn file_edits(mut self, files: &[PathBuf]) -> Result<Vec<FileEdits>> {
+        let mut results = vec![];
+        for file in files {
+            if let Some(edits) = self.edits.remove(file) {
+                results.push(file_edits(self.provider, file, edits)?);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// The edit replacing the scalar `node` by `text`, quoted the way it was.
+pub(crate) fn replace(node: &YamlNode, scalar: &Scalar, text: &str) -> TextEdit {
+    TextEdit {
+        bytes: node.bytes.to_owned(),
+        text: scalar_like(scalar, text),
+        line: node.start.row + 1,
+        column: node.start.column + 1,
+        old: scalar.text.to_owned(),
+        new: text.to_string(),
+    }
+}
+
+/// `edits` made to the content of `file`, in the order they appear in it.
+pub(crate) fn file_edits(
+    provider: &dyn ContentProvider,
+    file: &Path,
+    mut edits: Vec<TextEdit>,
+) -> Result<FileEdits> {
+    edits.sort_by_key(|edit| edit.bytes.start);
+    let content = provider.get_content(file.to_path_buf())?;
+    Ok(FileEdits {
+        file: provider.source_path(file),
+        content: apply_edits(&content, &edits),
+        edits,
+    })
+}
+
+/// The keys leading from `node` to the mapping key spanning `bytes`, the key
+/// included.
+fn key_path(node: &YamlNode, bytes: &Range<usize>) -> Option<Vec<String>> {
+    let children: Vec<(String, &YamlNode)> = match &node.value {
+        YamlValue::Mapping(pairs) => {
+            if let Some(pair) = pairs.iter().find(|pair| pair.key.bytes == *bytes) {
+                return Some(vec![pair.key.as_str().unwrap_or_default().to_string()]);
+            }
+            pairs
+                .iter()
+                .map(|pair| {
+                    (
+                        pair.key.as_str().unwrap_or_default().to_string(),
+                        &pair.value,
+                    )
+                })
+                .collect()
+        }
+        YamlValue::Sequence(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item))
+            .collect(),
+        _ => return None,
+    };
+    children.into_iter().find_map(|(key, child)| {
+        let mut path = key_path(child, bytes)?;
+        path.insert(0, key);
+        Some(path)
+    })
+}
+
+/// A `$ref` or `operationRef`, or a value of a discriminator `mapping`, which
+/// is either a ref or the name of a component schema.
+struct Reference<'a> {
+    node: &'a YamlNode,
+    scalar: &'a Scalar,
+    /// Whether it names a schema instead of being a ref to it.
+    name_only: bool,
+}
+
+/// Collects the refs below `node`, which is the value of the key `parent`.
+fn references<'a>(node: &'a YamlNode, parent: Option<&str>, refs: &mut Vec<Reference<'a>>) {
+    match &node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                let key = pair.key.as_str().unwrap_or_default();
+                let discriminator = parent == Some("discriminator") && key == "mapping";
+                if discriminator {
+                    for mapping in pair.value.as_mapping().unwrap_or_default() {
+                        if let YamlValue::Scalar(scalar) = &mapping.value.value {
+                            refs.push(Reference {
+                                node: &mapping.value,
+                                scalar,
+                                name_only: !scalar.text.contains('#'),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                match (key, &pair.value.value) {
+                    ("$ref" | "operationRef", YamlValue::Scalar(scalar)) => refs.push(Reference {
+                        node: &pair.value,
+                        scalar,
+                        name_only: false,
+                    }),
+                    _ => references(&pair.value, Some(key), refs),
+                }
+            }
+        }
+        YamlValue::Sequence(items) => {
+            for item in items {
+                references(item, None, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An `operationId` found in a document.
+struct OperationId<'a> {
+    node: &'a YamlNode,
+    scalar: &'a Scalar,
+    /// Whether it names the operation it is on, rather than being a link to
+    /// one.
+    operation: bool,
+}
+
+/// Collects the `operationId`s of the operations below `node`, the mappings
+/// under an HTTP method, and of the links, the mappings under `links`. That
+/// covers operations of paths, webhooks and callbacks alike. `keys` are the
+/// keys leading to `node`.
+fn operation_ids<'a>(node: &'a YamlNode, keys: &mut Vec<String>, ids: &mut Vec<OperationId<'a>>) {
+    match &node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                let key = pair.key.as_str().unwrap_or_default();
+                if let ("operationId", YamlValue::Scalar(scalar)) = (key, &pair.value.value) {
+                    let operation = keys
+                        .last()
+                        .is_some_and(|parent| HTTP_METHODS.contains(&parent.as_str()));
+                    let link = keys.len() >= 2 && keys[keys.len() - 2] == "links";
+                    if operation || link {
+                        ids.push(OperationId {
+                            node: &pair.value,
+                            scalar,
+                            operation,
+                        });
+                    }
+                    continue;
+                }
+                keys.push(key.to_string());
+                operation_ids(&pair.value, keys, ids);
+                keys.pop();
+            }
+        }
+        YamlValue::Sequence(items) => {
+            for item in items {
+                keys.push(String::new());
+                operation_ids(item, keys, ids);
+                keys.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{
+            edit::{FileEdits, TextEdit},
+            rename::{
+                OperationRenamer, PathRename, PathRenamer, SchemaRenamer,
+                TreeSitterOperationRenamer, TreeSitterPathRenamer, TreeSitterSchemaRenamer,
+            },
+        },
+        content::ContentProviderMap,
+    };
+
+    fn renamer() -> TreeSitterOperationRenamer {
+        let root_content = r#"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets  # The pets of the store
+      responses:
+        '200':
+          description: OK
+          links:
+            Next:
+              operationId: "listPets"
+    post:
+      operationId: createPets
+      callbacks:
+        onCreated:
+          '{$request.body#/url}':
+            post:
+              operationId: listPets
+      responses:
+        '201':
+          $ref: 'responses.yaml#/Created'
+"#;
+        let responses_content = r#"Created:
+  description: Created
+  links:
+    All:
+      operationId: listPets
+  content:
+    application/json:
+      example:
+        operationId: listPets
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+        ]);
+        TreeSitterOperationRenamer::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    #[test]
+    fn get_rename_edits() -> Result<(), Box<dyn Error>> {
+        let edit = |bytes, text: &str, line, column| TextEdit {
+            bytes,
+            old: "listPets".to_string(),
+            new: "listAll".to_string(),
+            text: text.to_string(),
+            line,
+            column,
+        };
+        let edits = renamer().get_rename_edits("listPets", "listAll")?.unwrap();
+        assert_eq!(
+            edits,
+            vec![
+                FileEdits {
+                    file: PathBuf::from("#"),
+                    edits: vec![
+                        edit(59..67, "listAll", 5, 20),
+                        edit(213..223, "\"listAll\"", 11, 28),
+                        edit(379..387, "listAll", 18, 28),
+                    ],
+                    content: r#"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listAll  # The pets of the store
+      responses:
+        '200':
+          description: OK
+          links:
+            Next:
+              operationId: "listAll"
+    post:
+      operationId: createPets
+      callbacks:
+        onCreated:
+          '{$request.body#/url}':
+            post:
+              operationId: listAll
+      responses:
+        '201':
+          $ref: 'responses.yaml#/Created'
+"#
+                    .to_string(),
+                },
+                FileEdits {
+                    file: PathBuf::from("responses.yaml"),
+                    edits: vec![edit(69..77, "listAll", 5, 20)],
+                    content: r#"Created:
+  description: Created
+  links:
+    All:
+      operationId: listAll
+  content:
+    application/json:
+      example:
+        operationId: listPets
+"#
+                    .to_string(),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_rename_edits_errors() -> Result<(), Box<dyn Error>> {
+        assert_eq!(renamer().get_rename_edits("missing", "listAll")?, None);
+        assert_eq!(
+            renamer()
+                .get_rename_edits("listPets", "createPets")
+                .unwrap_err()
+                .to_string(),
+            "An operation `createPets` already exists"
+        );
+        Ok(())
+    }
+
+    fn schema_renamer() -> TreeSitterSchemaRenamer {
+        let root_content = r##"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+components:
+  schemas:
+    $ref: 'schemas.yaml'
+"##;
+        let schemas_content = r#"Pet:
+  type: object
+  discriminator:
+    propertyName: kind
+    mapping:
+      cat: Pet
+      dog: '#/Pet/properties/dog'
+  properties:
+    dog:
+      type: object
+    pets:
+      $ref: '#/Petty'
+Pets:
+  type: array
+  items: {$ref: '#/Pet'}
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        TreeSitterSchemaRenamer::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    #[test]
+    fn get_schema_rename_edits() -> Result<(), Box<dyn Error>> {
+        let files = schema_renamer().get_rename_edits("Pet", "Animal")?.unwrap();
+        let contents: Vec<(PathBuf, &str)> = files
+            .iter()
+            .map(|file| (file.file.to_owned(), file.content.as_str()))
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                (
+                    PathBuf::from("#"),
+                    r##"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Animal"
+components:
+  schemas:
+    $ref: 'schemas.yaml'
+"##
+                ),
+                (
+                    PathBuf::from("schemas.yaml"),
+                    r#"Animal:
+  type: object
+  discriminator:
+    propertyName: kind
+    mapping:
+      cat: Animal
+      dog: '#/Animal/properties/dog'
+  properties:
+    dog:
+      type: object
+    pets:
+      $ref: '#/Petty'
+Pets:
+  type: array
+  items: {$ref: '#/Animal'}
+"#
+                ),
+            ]
+        );
+        let lines: Vec<(usize, usize)> = files[1]
+            .edits
+            .iter()
+            .map(|edit| (edit.line, edit.column))
+            .collect();
+        assert_eq!(lines, vec![(1, 1), (6, 12), (7, 12), (15, 17)]);
+
+        let renamer = schema_renamer();
+        assert_eq!(renamer.get_rename_edits("Missing", "Animal")?, None);
+        assert_eq!(
+            renamer
+                .get_rename_edits("Pet", "Pets")
+                .unwrap_err()
+                .to_string(),
+            "A schema `Pets` already exists"
+        );
+        Ok(())
+    }
+
+    fn path_renamer() -> TreeSitterPathRenamer {
+        let root_content = r#"paths:
+  /pets:
+    get:
+      responses:
+        '200':
+          links:
+            pet:
+              operationRef: '#/paths/~1pets~1{petId}/get'
+            encoded:
+              operationRef: '#/paths/~1pets~1%7BpetId%7D/get'
+  /pets/{petId}:
+    $ref: 'pet.yaml'
+"#;
+        let pet_content = r#"get:
+  responses:
+    '200':
+      links:
+        self:
+          operationRef: '#/get'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        TreeSitterPathRenamer::new(Box::new(ContentProviderMap::from_map(contents)))
+    }
+
+    #[test]
+    fn get_path_rename_edits() -> Result<(), Box<dyn Error>> {
+        let prefix = PathRename::Prefix("/v2/".to_string());
+        let files = path_renamer().get_rename_edits(&prefix)?.unwrap();
+        let contents: Vec<(PathBuf, &str)> = files
+            .iter()
+            .map(|file| (file.file.to_owned(), file.content.as_str()))
+            .collect();
+        assert_eq!(
+            contents,
+            vec![(
+                PathBuf::from("#"),
+                r#"paths:
+  /v2/pets:
+    get:
+      responses:
+        '200':
+          links:
+            pet:
+              operationRef: '#/paths/~1v2~1pets~1{petId}/get'
+            encoded:
+              operationRef: '#/paths/~1v2~1pets~1%7BpetId%7D/get'
+  /v2/pets/{petId}:
+    $ref: 'pet.yaml'
+"#
+            )]
+        );
+
+        let renamer = path_renamer();
+        let rename = |from: &str, to: &str| PathRename::Path {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let files = renamer
+            .get_rename_edits(&rename("/pets/{petId}", "/pets/{id}"))?
+            .unwrap();
+        let edits: Vec<(&str, &str)> = files
+            .iter()
+            .flat_map(|file| &file.edits)
+            .map(|edit| (edit.old.as_str(), edit.new.as_str()))
+            .collect();
+        assert_eq!(
+            edits,
+            vec![
+                ("#/paths/~1pets~1{petId}/get", "#/paths/~1pets~1{id}/get"),
+                (
+                    "#/paths/~1pets~1%7BpetId%7D/get",
+                    "#/paths/~1pets~1%7Bid%7D/get"
+                ),
+                ("/pets/{petId}", "/pets/{id}"),
+            ]
+        );
+        assert_eq!(
+            renamer.get_rename_edits(&rename("/missing", "/found"))?,
+            None
+        );
+        assert_eq!(
+            renamer
+                .get_rename_edits(&rename("/pets", "/pets/{petId}"))
+                .unwrap_err()
+                .to_string(),
+            "A path `/pets/{petId}` already exists"
+        );
+        Ok(())
+    }
+}
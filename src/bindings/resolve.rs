@@ -0,0 +1,573 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Error, Result};
+
+use crate::{
+    content::{resolve_ref_path, ContentProvider},
+    error::OpenapiSchemerError,
+};
+
+use super::{
+    validate::HTTP_METHODS,
+    yaml::{pointer_segments, YamlNode, YamlPair, YamlValue},
+    OperationNode,
+};
+
+/// An operation found under `paths`, with the path item and operation refs
+/// already followed.
+#[derive(Clone, Debug)]
+pub struct OperationEntry {
+    pub path: String,
+    pub method: String,
+    pub operation: YamlNode,
+    /// The file the operation is defined in.
+    pub file: PathBuf,
+}
+
+impl OperationEntry {
+    /// The operation as an `OperationNode` located at its `operationId`.
+    /// Operations without an `operationId` are named by their method and
+    /// path.
+    pub fn node(&self, provider: &dyn ContentProvider) -> OperationNode {
+        let id = self.operation.get("operationId");
+        let operation_id = id.map(|id| id.as_str().unwrap_or_default().to_string());
+        let (text, position) = match &operation_id {
+            Some(text) => (text.to_owned(), id.unwrap_or(&self.operation)),
+            None => (self.describe(), &self.operation),
+        };
+        OperationNode {
+            text,
+            operation_id,
+            method: self.method.to_owned(),
+            path: self.path.to_owned(),
+            summary: self
+                .operation
+                .get("summary")
+                .and_then(|summary| summary.as_str())
+                .map(str::to_owned),
+            tags: self.tags(),
+            deprecated: self
+                .operation
+                .get("deprecated")
+                .and_then(|deprecated| deprecated.as_str())
+                == Some("true"),
+            file: provider.source_path(&self.file),
+            line: position.start.row + 1,
+            column: position.start.column + 1,
+        }
+    }
+
+    /// `GET /pets` style name for the operation.
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.method.to_uppercase(), self.path)
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.operation
+            .get("tags")
+            .and_then(|tags| tags.as_sequence())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|tag| tag.as_str().map(str::to_owned))
+            .collect()
+    }
+}
+
+/// A named entry of a mapping such as `components/parameters`.
+#[derive(Clone, Debug)]
+pub struct MappingEntry {
+    pub name: String,
+    /// The key and the file it is written in, used to report locations.
+    pub key: YamlNode,
+    pub key_file: PathBuf,
+    /// The value with its ref followed and the file it is defined in.
+    pub value: YamlNode,
+    pub file: PathBuf,
+}
+
+/// Follows `$ref`s across the files of a spec. Documents are parsed once and
+/// kept for the lifetime of the resolver.
+pub struct Resolver<'a> {
+    provider: &'a dyn ContentProvider,
+    documents: HashMap<PathBuf, YamlNode>,
+    /// The targets of the refs currently being resolved, innermost last. A
+    /// pointer that passes through a ref resolves it while the outer ref is
+    /// still on the stack, which is how cycles between files are caught.
+    stack: Vec<String>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(provider: &'a dyn ContentProvider) -> Self {
+        Self {
+            provider,
+            documents: HashMap::new(),
+            stack: vec![],
+        }
+    }
+
+    /// The parsed document for `file`, where `#` is the root document, with
+    /// its aliases and merge keys resolved.
+    pub fn document(&mut self, file: &Path) -> Result<&YamlNode> {
+        if !self.documents.contains_key(file) {
+            let document = self.provider.get_document(file.to_path_buf())?;
+            self.documents.insert(file.to_path_buf(), document);
+        }
+        Ok(&self.documents[file])
+    }
+
+    /// Resolves `reference`, found in `file`, to the node it points at and the
+    /// file that node lives in.
+    pub fn resolve_ref(&mut self, file: &Path, reference: &str) -> Result<(YamlNode, PathBuf)> {
+        let depth = self.stack.len();
+        let resolved = self.resolve_target(file, reference);
+        self.stack.truncate(depth);
+        resolved
+    }
+
+    /// Resolves `reference` with its target pushed on the ref stack. The
+    /// caller pops it once the target is no longer being resolved.
+    fn resolve_target(&mut self, file: &Path, reference: &str) -> Result<(YamlNode, PathBuf)> {
+        let (target_file, fragment) = split_ref(file, reference);
+        let target = self.location(&target_file, &fragment);
+        if let Some(start) = self.stack.iter().position(|seen| *seen == target) {
+            return Err(circular_ref(&self.stack[start..], &target));
+        }
+        self.stack.push(target);
+        self.resolve_pointer(&target_file, &fragment)?
+            .ok_or_else(|| {
+                Error::msg(format!(
+                    "Could not resolve $ref `{}` in `{}`",
+                    reference,
+                    file.display()
+                ))
+            })
+    }
+
+    /// Walks the JSON pointer `pointer` from the root of `file`. A `$ref`
+    /// met along the way is followed before the next segment is taken, so
+    /// `#/components/schemas/Pet` resolves even when `components` lives in
+    /// another file.
+    pub fn resolve_pointer(
+        &mut self,
+        file: &Path,
+        pointer: &str,
+    ) -> Result<Option<(YamlNode, PathBuf)>> {
+        let segments = match pointer_segments(pointer) {
+            Some(segments) => segments,
+            None => return Ok(None),
+        };
+        let mut current = (self.document(file)?.to_owned(), file.to_path_buf());
+        for segment in segments {
+            let (node, node_file) = self.follow(&current.0, &current.1)?;
+            current = match node.child(&segment) {
+                Some(child) => (child.to_owned(), node_file),
+                None => return Ok(None),
+            };
+        }
+        Ok(Some(current))
+    }
+
+    /// Returns `node` itself, or its target when it is a `$ref`. Chains of
+    /// refs are followed until a non-ref node is found.
+    pub fn follow(&mut self, node: &YamlNode, file: &Path) -> Result<(YamlNode, PathBuf)> {
+        // Every ref of the chain stays on the stack until the end, so a chain
+        // that comes back to one of its own targets is reported as a cycle.
+        let depth = self.stack.len();
+        let mut current = Ok((node.to_owned(), file.to_path_buf()));
+        while let Ok((node, file)) = &current {
+            let reference = match node.reference() {
+                Some(reference) => reference.to_owned(),
+                None => break,
+            };
+            current = self.resolve_target(&file.to_owned(), &reference);
+        }
+        self.stack.truncate(depth);
+        current
+    }
+
+    /// Every operation in the spec in document order.
+    pub fn operations(&mut self) -> Result<Vec<OperationEntry>> {
+        let mut operations = vec![];
+        self.for_each_operation(&mut |operation| operations.push(operation))?;
+        Ok(operations)
+    }
+
+    /// Calls `f` with every operation in the spec in document order, as each
+    /// is found.
+    pub fn for_each_operation(&mut self, f: &mut dyn FnMut(OperationEntry)) -> Result<()> {
+        self.for_each_entry("/paths", &mut |resolver, path_item| {
+            for pair in path_item.value.as_mapping().unwrap_or_default() {
+                let method = match pair.key.as_str() {
+                    Some(method) if HTTP_METHODS.contains(&method) => method,
+                    _ => continue,
+                };
+                let (operation, file) = resolver.follow(&pair.value, &path_item.file)?;
+                f(OperationEntry {
+                    path: path_item.name.to_owned(),
+                    method: method.to_string(),
+                    operation,
+                    file,
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// The entries of the mapping at `pointer` in the root document, such as
+    /// `/components/parameters`, with the ref of each entry followed.
+    pub fn entries(&mut self, pointer: &str) -> Result<Vec<MappingEntry>> {
+        let mut entries = vec![];
+        self.for_each_entry(pointer, &mut |_, entry| {
+            entries.push(entry);
+            Ok(())
+        })?;
+        Ok(entries)
+    }
+
+    /// Calls `f` with each of the `entries` at `pointer` as it is found,
+    /// along with the resolver to follow the refs within it.
+    pub fn for_each_entry(
+        &mut self,
+        pointer: &str,
+        f: &mut dyn FnMut(&mut Self, MappingEntry) -> Result<()>,
+    ) -> Result<()> {
+        let root_path = PathBuf::from("#");
+        let (mapping, mapping_file) = match self.resolve_pointer(&root_path, pointer)? {
+            Some((mapping, mapping_file)) => self.follow(&mapping, &mapping_file)?,
+            None => return Ok(()),
+        };
+        for pair in mapping.as_mapping().unwrap_or_default() {
+            let (value, file) = self.follow(&pair.value, &mapping_file)?;
+            let entry = MappingEntry {
+                name: pair.key.as_str().unwrap_or_default().to_string(),
+                key: pair.key.to_owned(),
+                key_file: mapping_file.to_owned(),
+                value,
+                file,
+            };
+            f(self, entry)?;
+        }
+        Ok(())
+    }
+
+    /// Every file reachable from the root document through `$ref`s, root
+    /// first. Files are compared by where they live on disk so a ref back to
+    /// the root document by name doesn't list it twice, and files that could
+    /// not be read are left out.
+    pub fn files(&mut self) -> Result<Vec<PathBuf>> {
+        let mut files = vec![PathBuf::from("#")];
+        let mut seen = vec![self.provider.source_path(Path::new("#"))];
+        let mut index = 0;
+        while index < files.len() {
+            let file = files[index].to_owned();
+            index += 1;
+            let references: Vec<String> = self
+                .document(&file)?
+                .references()
+                .into_iter()
+                .map(str::to_owned)
+                .collect();
+            for reference in references {
+                let (target_file, _) = split_ref(&file, &reference);
+                let source = self.provider.source_path(&target_file);
+                let readable = self.provider.get_content(target_file.to_owned()).is_ok();
+                if readable && !seen.contains(&source) {
+                    seen.push(source);
+                    files.push(target_file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// `file#/pointer` naming a node by where it lives on disk, so the same
+    /// file reached through different relative paths compares equal.
+    pub fn location(&self, file: &Path, fragment: &str) -> String {
+        location(self.provider, file, fragment)
+    }
+
+    /// Replaces every `$ref` below `node` with the node it points at. Refs
+    /// that would recurse into a target that is already being inlined are
+    /// left as they are, so recursive schemas stay finite.
+    pub fn inline(&mut self, node: &YamlNode, file: &Path) -> Result<YamlNode> {
+        let mut stack = vec![];
+        self.inline_with_stack(node, file, &mut stack)
+    }
+
+    fn inline_with_stack(
+        &mut self,
+        node: &YamlNode,
+        file: &Path,
+        stack: &mut Vec<String>,
+    ) -> Result<YamlNode> {
+        if let Some(reference) = node.reference() {
+            let (target_file, fragment) = split_ref(file, reference);
+            let key = format!("{}#{}", target_file.display(), fragment);
+            if stack.contains(&key) {
+                return Ok(node.to_owned());
+            }
+            let (target, target_file) = self.resolve_ref(file, reference)?;
+            stack.push(key);
+            let inlined = self.inline_with_stack(&target, &target_file, stack);
+            stack.pop();
+            return inlined;
+        }
+        let value = match &node.value {
+            YamlValue::Mapping(pairs) => {
+                let mut inlined = vec![];
+                for pair in pairs {
+                    inlined.push(YamlPair {
+                        key: pair.key.to_owned(),
+                        value: self.inline_with_stack(&pair.value, file, stack)?,
+                    });
+                }
+                YamlValue::Mapping(inlined)
+            }
+            YamlValue::Sequence(items) => {
+                let mut inlined = vec![];
+                for item in items {
+                    inlined.push(self.inline_with_stack(item, file, stack)?);
+                }
+                YamlValue::Sequence(inlined)
+            }
+            other => other.to_owned(),
+        };
+        Ok(YamlNode {
+            value,
+            ..node.to_owned()
+        })
+    }
+}
+
+/// `fragment` of `file` written as `file#fragment`, using where the file lives
+/// on disk.
+pub fn location(provider: &dyn ContentProvider, file: &Path, fragment: &str) -> String {
+    let source = provider.source_path(file);
+    match (source == Path::new("#"), fragment.is_empty()) {
+        (true, _) => format!("#{}", fragment),
+        (false, true) => source.display().to_string(),
+        (false, false) => format!("{}#{}", source.display(), fragment),
+    }
+}
+
+/// Names `node` of `file` by where the file lives on disk and where the node
+/// starts, so a node reached through different refs is recognised as the
+/// same one.
+pub(crate) fn identity(provider: &dyn ContentProvider, file: &Path, node: &YamlNode) -> String {
+    format!(
+        "{}:{}:{}",
+        provider.source_path(file).display(),
+        node.start.row,
+        node.start.column
+    )
+}
+
+pub fn circular_ref(cycle: &[String], target: &str) -> Error {
+    let mut cycle = cycle.to_vec();
+    cycle.push(target.to_string());
+    OpenapiSchemerError::CircularRef(format!("Circular $ref: {}", cycle.join(" -> "))).into()
+}
+
+/// Splits a `$ref` into the file it points at, relative to the root
+/// document's directory, and the JSON pointer within that file.
+pub fn split_ref(file: &Path, reference: &str) -> (PathBuf, String) {
+    let (target_file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+    let target_file = if target_file.is_empty() {
+        file.to_path_buf()
+    } else {
+        resolve_ref_path(file, target_file)
+    };
+    (target_file, fragment.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{bindings::yaml::parse_yaml, content::ContentProviderMap};
+
+    use super::Resolver;
+
+    #[test]
+    fn inline_refs() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  schemas:
+    Pets:
+      type: array
+      items:
+        $ref: 'schemas/Pet.yaml'
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  tag:
+    $ref: '#/definitions/Tag'
+  parent:
+    $ref: '#'
+definitions:
+  Tag:
+    type: string
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("schemas/Pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let root_path = PathBuf::from("#");
+        let pets = resolver
+            .document(&root_path)?
+            .pointer("/components/schemas/Pets")
+            .unwrap()
+            .to_owned();
+        let inlined = resolver.inline(&pets, &root_path)?;
+        let expected = parse_yaml(
+            r#"
+type: array
+items:
+  type: object
+  properties:
+    tag:
+      type: string
+    parent:
+      $ref: '#'
+  definitions:
+    Tag:
+      type: string
+"#,
+        )?;
+        assert_eq!(inlined.to_yaml(), expected.to_yaml());
+        Ok(())
+    }
+
+    #[test]
+    fn document_resolves_aliases() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  schemas:
+    Pet: &pet
+      type: object
+    Pets:
+      type: array
+      items: *pet
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let items = resolver
+            .document(&PathBuf::from("#"))?
+            .pointer("/components/schemas/Pets/items/type")
+            .and_then(|kind| kind.as_str());
+        assert_eq!(items, Some("object"));
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_pointer_through_refs() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets/{petId}:
+    $ref: 'paths.yaml#/~1pets~1%7BpetId%7D'
+components:
+  $ref: 'components.yaml'
+"#;
+        let paths_content = r#"
+/pets/{petId}:
+  get:
+    operationId: showPetById
+"#;
+        let components_content = r#"
+schemas:
+  Pet:
+    type: object
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("paths.yaml"), paths_content.to_owned()),
+            (
+                PathBuf::from("components.yaml"),
+                components_content.to_owned(),
+            ),
+        ]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let root_path = PathBuf::from("#");
+
+        let (pet, pet_file) = resolver.resolve_ref(&root_path, "#/components/schemas/Pet")?;
+        assert_eq!(pet.get("type").unwrap().as_str(), Some("object"));
+        assert_eq!(pet_file, PathBuf::from("components.yaml"));
+
+        let (id, id_file) = resolver
+            .resolve_pointer(&root_path, "/paths/~1pets~1{petId}/get/operationId")?
+            .unwrap();
+        assert_eq!(id.as_str(), Some("showPetById"));
+        assert_eq!(id_file, PathBuf::from("paths.yaml"));
+
+        assert!(resolver
+            .resolve_ref(&root_path, "#/components/schemas/Missing")
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn follow_ref_chain() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+a:
+  $ref: '#/b'
+b:
+  $ref: '#/c'
+c:
+  type: string
+loop:
+  $ref: '#/loop'
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path)?.to_owned();
+        let (target, _) = resolver.follow(root.get("a").unwrap(), &root_path)?;
+        assert_eq!(target.get("type").unwrap().as_str(), Some("string"));
+        let error = resolver
+            .follow(root.get("loop").unwrap(), &root_path)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Circular $ref: #/loop -> #/loop");
+        Ok(())
+    }
+
+    #[test]
+    fn circular_refs_between_files() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  schemas:
+    $ref: 'a.yaml#/schemas'
+"#;
+        let a_content = r#"
+schemas:
+  $ref: 'b.yaml#/schemas'
+"#;
+        let b_content = r#"
+schemas:
+  $ref: 'a.yaml#/schemas/Pet'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("a.yaml"), a_content.to_owned()),
+            (PathBuf::from("b.yaml"), b_content.to_owned()),
+        ]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let error = resolver
+            .resolve_ref(&PathBuf::from("#"), "#/components/schemas/Pet")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Circular $ref: b.yaml#/schemas -> a.yaml#/schemas/Pet -> b.yaml#/schemas"
+        );
+        Ok(())
+    }
+}
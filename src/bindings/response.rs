@@ -0,0 +1,213 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode, OperationNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResponseNode {
+    pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The status codes an operation declares responses for.
+#[derive(Clone, Debug)]
+pub struct OperationResponses {
+    pub operation: OperationNode,
+    /// The keys of `responses` as written, such as `200`, `4XX` or
+    /// `default`.
+    pub codes: Vec<String>,
+}
+
+pub trait ResponseParser {
+    fn get_response_nodes(&self) -> Result<Vec<ResponseNode>, OpenapiSchemerError>;
+    fn get_response(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+    fn get_operation_responses(&self) -> Result<Vec<OperationResponses>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterResponseParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterResponseParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl ResponseParser for TreeSitterResponseParser {
+    fn get_response_nodes(&self) -> Result<Vec<ResponseNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/responses").map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ResponseList)
+        })?;
+        let results = entries
+            .into_iter()
+            .map(|entry| ResponseNode {
+                file: self.provider.source_path(&entry.key_file),
+                line: entry.key.start.row + 1,
+                column: entry.key.start.column + 1,
+                text: entry.name,
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_response(&self, name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver.entries("/components/responses").map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ResponseShow)
+        })?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value))
+    }
+
+    fn get_operation_responses(&self) -> Result<Vec<OperationResponses>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OperationResponses)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for operation in resolver.operations().map_err(to_error)? {
+            let codes = match operation.operation.get("responses") {
+                Some(responses) => resolver
+                    .follow(responses, &operation.file)
+                    .map_err(to_error)?
+                    .0
+                    .as_mapping()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|pair| pair.key.as_str().map(str::to_owned))
+                    .collect(),
+                None => vec![],
+            };
+            results.push(OperationResponses {
+                operation: operation.node(self.provider.as_ref()),
+                codes,
+            });
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::response::{ResponseParser, TreeSitterResponseParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterResponseParser {
+        let root_content = r#"
+components:
+  $ref: 'components.yaml'
+"#;
+        let components_content = r#"
+responses:
+  NotFound:
+    description: The resource was not found
+  Error:
+    $ref: 'responses/Error.yaml'
+"#;
+        let error_content = r#"
+description: Unexpected error
+content:
+  application/json:
+    schema:
+      $ref: '../components.yaml#/schemas/Error'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("components.yaml"),
+                components_content.to_owned(),
+            ),
+            (
+                PathBuf::from("responses/Error.yaml"),
+                error_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterResponseParser::new(provider)
+    }
+
+    #[test]
+    fn get_response_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_response_nodes()?;
+        let texts: Vec<&str> = nodes.iter().map(|node| node.text.as_str()).collect();
+        assert_eq!(texts, vec!["NotFound", "Error"]);
+        assert_eq!(nodes[1].file, PathBuf::from("components.yaml"));
+        assert_eq!((nodes[1].line, nodes[1].column), (5, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn get_response() -> Result<(), Box<dyn Error>> {
+        let response = parser().get_response("Error")?.unwrap();
+        assert_eq!(
+            response.get("description").and_then(|node| node.as_str()),
+            Some("Unexpected error")
+        );
+        assert!(parser().get_response("missing")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation_responses() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          description: OK
+        default:
+          description: Error
+    post:
+      operationId: createPets
+      responses:
+        $ref: 'responses.yaml'
+"#;
+        let responses_content = r#"
+'201':
+  description: Created
+4XX:
+  description: Bad request
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("responses.yaml"),
+                responses_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterResponseParser::new(provider);
+        let responses: Vec<(String, Vec<String>)> = parser
+            .get_operation_responses()?
+            .into_iter()
+            .map(|responses| (responses.operation.text, responses.codes))
+            .collect();
+        assert_eq!(
+            responses,
+            vec![
+                (
+                    "listPets".to_string(),
+                    vec!["200".to_string(), "default".to_string()]
+                ),
+                (
+                    "createPets".to_string(),
+                    vec!["201".to_string(), "4XX".to_string()]
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,840 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use super::{
+    lint::{LintRule, Reporter, Severity},
+    pattern::Pattern,
+    resolve::{split_ref, Resolver},
+    yaml::{parse_yaml, ScalarStyle, YamlNode, YamlValue},
+};
+
+/// The rules of a Spectral style ruleset file. A rule set to a scalar turns a
+/// built-in rule on or off, a mapping defines a new rule:
+///
+/// ```yaml
+/// rules:
+///   operation-summary: off
+///   operation-tags:
+///     description: Operations must have tags
+///     severity: error
+///     given: $.paths[*][get,put,post,delete]
+///     then:
+///       field: tags
+///       function: truthy
+/// ```
+#[derive(Debug)]
+pub struct Ruleset {
+    /// Rules turned on or off, including the defined rules that are `off`.
+    pub toggles: Vec<(String, bool)>,
+    pub rules: Vec<CustomRule>,
+}
+
+impl Ruleset {
+    pub fn parse(content: &str) -> Result<Ruleset> {
+        let document = parse_yaml(content).context("Failed to parse ruleset")?;
+        let mut ruleset = Ruleset {
+            toggles: vec![],
+            rules: vec![],
+        };
+        let rules = document
+            .get("rules")
+            .and_then(|rules| rules.as_mapping())
+            .unwrap_or_default();
+        for pair in rules {
+            let name = pair.key.as_str().unwrap_or_default();
+            if pair.value.as_mapping().is_some() {
+                let (rule, enabled) = CustomRule::parse(name, &pair.value)?;
+                if !enabled {
+                    ruleset.toggles.push((name.to_string(), false));
+                }
+                ruleset.rules.push(rule);
+                continue;
+            }
+            let enabled = match pair.value.as_str() {
+                Some("on" | "true" | "error" | "warn" | "info" | "hint") => true,
+                Some("off" | "false") => false,
+                _ => bail!(
+                    "Expected `on`, `off` or a rule definition for lint rule `{}`",
+                    name
+                ),
+            };
+            ruleset.toggles.push((name.to_string(), enabled));
+        }
+        Ok(ruleset)
+    }
+}
+
+/// A rule defined by a ruleset, which applies its functions to every node the
+/// `given` paths select.
+#[derive(Debug)]
+pub struct CustomRule {
+    name: String,
+    description: Option<String>,
+    /// Replaces the message of the function. `{{error}}`, `{{property}}`,
+    /// `{{value}}`, `{{path}}` and `{{description}}` are filled in.
+    message: Option<String>,
+    severity: Severity,
+    given: Vec<Vec<Step>>,
+    then: Vec<Then>,
+}
+
+impl CustomRule {
+    /// The rule and whether it is enabled, which it is unless its severity
+    /// is `off`.
+    fn parse(name: &str, node: &YamlNode) -> Result<(CustomRule, bool)> {
+        let text = |key: &str| node.get(key).and_then(|value| value.as_str());
+        let severity = match text("severity") {
+            None | Some("warn" | "warning" | "1") => Some(Severity::Warning),
+            Some("error" | "0") => Some(Severity::Error),
+            Some("info" | "2") => Some(Severity::Info),
+            Some("hint" | "3") => Some(Severity::Hint),
+            Some("off" | "-1") => None,
+            Some(severity) => bail!(
+                "Unknown severity `{}` for rule `{}`, expected error, warn, info, hint or off",
+                severity,
+                name
+            ),
+        };
+        let given = match node.get("given") {
+            Some(given) => strings(given).with_context(|| {
+                format!(
+                    "Expected a path or a list of paths for `given` of rule `{}`",
+                    name
+                )
+            })?,
+            None => bail!("Rule `{}` has no `given`", name),
+        };
+        let given = given
+            .iter()
+            .map(|path| {
+                parse_path(path).map_err(|error| {
+                    anyhow::Error::msg(format!(
+                        "Invalid path `{}` in rule `{}`: {}",
+                        path, name, error
+                    ))
+                })
+            })
+            .collect::<Result<_>>()?;
+        let then = match node.get("then").map(|then| &then.value) {
+            Some(YamlValue::Sequence(items)) => items.iter().collect(),
+            Some(YamlValue::Mapping(_)) => vec![node.get("then").unwrap()],
+            _ => bail!("Rule `{}` has no `then`", name),
+        };
+        let rule = CustomRule {
+            name: name.to_string(),
+            description: text("description").map(str::to_owned),
+            message: text("message").map(str::to_owned),
+            severity: severity.unwrap_or(Severity::Warning),
+            given,
+            then: then
+                .into_iter()
+                .map(|then| Then::parse(name, then))
+                .collect::<Result<_>>()?,
+        };
+        Ok((rule, severity.is_some()))
+    }
+
+    /// Every node `steps` selects, starting at the root document.
+    fn targets(&self, resolver: &mut Resolver, steps: &[Step]) -> Result<Vec<Target>> {
+        let root = Target {
+            node: resolver.document(Path::new("#"))?.to_owned(),
+            file: PathBuf::from("#"),
+            path: vec![],
+        };
+        let mut targets = vec![root];
+        for step in steps {
+            let mut selected = vec![];
+            for target in targets {
+                let parents = match step.recursive {
+                    true => descendants(resolver, target)?,
+                    false => vec![target],
+                };
+                for parent in parents {
+                    selected.extend(
+                        children(resolver, &parent)?
+                            .into_iter()
+                            .filter(|child| step.selects(child.path.last())),
+                    );
+                }
+            }
+            targets = selected;
+        }
+        Ok(targets)
+    }
+
+    fn message(&self, error: String, subject: &Subject, target: &Target) -> String {
+        let template = match (&self.message, &self.description) {
+            (Some(message), _) => message,
+            (None, Some(description)) => description,
+            (None, None) => return error,
+        };
+        let value = subject
+            .value
+            .as_ref()
+            .and_then(|value| value.as_str())
+            .unwrap_or_default();
+        template
+            .replace("{{error}}", &error)
+            .replace("{{property}}", &subject.property)
+            .replace("{{value}}", value)
+            .replace("{{path}}", &target.path.join("."))
+            .replace(
+                "{{description}}",
+                self.description.as_deref().unwrap_or_default(),
+            )
+    }
+}
+
+impl LintRule for CustomRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, resolver: &mut Resolver, reporter: &mut Reporter) -> Result<()> {
+        // Nodes reached through more than one ref are only reported once.
+        let mut reported = HashSet::new();
+        for steps in &self.given {
+            for target in self.targets(resolver, steps)? {
+                let (node, file) = resolver.follow(&target.node, &target.file)?;
+                for then in &self.then {
+                    for subject in then.subjects(resolver, &target, &node, &file)? {
+                        let error = match then.function.check(&subject) {
+                            Some(error) => error,
+                            None => continue,
+                        };
+                        let message = self.message(error, &subject, &target);
+                        let at = &subject.at;
+                        if reported.insert((subject.file.to_owned(), at.start, message.to_owned()))
+                        {
+                            reporter.report(&subject.file, at, message);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A step of a path such as `$.paths[*]..description`.
+#[derive(Debug)]
+pub(crate) struct Step {
+    /// Whether the step is `..`, which selects at any depth.
+    pub(crate) recursive: bool,
+    /// The keys or indexes selected, all of them for `*`.
+    pub(crate) names: Option<Vec<String>>,
+}
+
+impl Step {
+    pub(crate) fn selects(&self, name: Option<&String>) -> bool {
+        match (&self.names, name) {
+            (None, _) => true,
+            (Some(names), Some(name)) => names.contains(name),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Parses the JSONPath subset rulesets use: `.name`, `['name']`, `[0]`,
+/// `[a,b]`, `*` and `..`. Filter expressions are not supported.
+pub(crate) fn parse_path(path: &str) -> Result<Vec<Step>, String> {
+    let mut chars = path
+        .strip_prefix('$')
+        .ok_or("paths start with `$`")?
+        .chars()
+        .peekable();
+    let mut steps = vec![];
+    while let Some(c) = chars.next() {
+        let recursive = c == '.' && chars.peek() == Some(&'.');
+        let c = match recursive {
+            true => {
+                chars.next();
+                match chars.peek() {
+                    Some('[') => chars.next().unwrap_or_default(),
+                    _ => '.',
+                }
+            }
+            false => c,
+        };
+        let names = match c {
+            '.' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                    name.push(c);
+                }
+                match name.as_str() {
+                    "" => return Err("expected a name after `.`".to_string()),
+                    "*" => None,
+                    _ => Some(vec![name]),
+                }
+            }
+            '[' => {
+                if chars.peek() == Some(&'?') {
+                    return Err("filter expressions are not supported".to_string());
+                }
+                let mut names = vec![];
+                let mut wildcard = false;
+                loop {
+                    while chars.next_if_eq(&' ').is_some() {}
+                    let name = match chars.next() {
+                        Some(quote @ ('\'' | '"')) => {
+                            let mut name = String::new();
+                            loop {
+                                match chars.next() {
+                                    Some(c) if c == quote => break,
+                                    Some('\\') => name.extend(chars.next()),
+                                    Some(c) => name.push(c),
+                                    None => return Err("unclosed quote".to_string()),
+                                }
+                            }
+                            name
+                        }
+                        Some(c) => {
+                            let mut name = c.to_string();
+                            while let Some(c) = chars.next_if(|c| *c != ',' && *c != ']') {
+                                name.push(c);
+                            }
+                            let name = name.trim().to_string();
+                            wildcard |= name == "*";
+                            name
+                        }
+                        None => return Err("unclosed `[`".to_string()),
+                    };
+                    names.push(name);
+                    while chars.next_if_eq(&' ').is_some() {}
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(']') => break,
+                        _ => return Err("unclosed `[`".to_string()),
+                    }
+                }
+                match wildcard {
+                    true => None,
+                    false => Some(names),
+                }
+            }
+            c => return Err(format!("unexpected `{}`", c)),
+        };
+        steps.push(Step { recursive, names });
+    }
+    Ok(steps)
+}
+
+/// A node selected by a path.
+struct Target {
+    node: YamlNode,
+    file: PathBuf,
+    /// The keys and indexes leading to the node.
+    path: Vec<String>,
+}
+
+/// The entries of `target`, following it first when it is a `$ref`.
+fn children(resolver: &mut Resolver, target: &Target) -> Result<Vec<Target>> {
+    let (node, file) = resolver.follow(&target.node, &target.file)?;
+    let child = |key: String, node: &YamlNode| {
+        let mut path = target.path.to_owned();
+        path.push(key);
+        Target {
+            node: node.to_owned(),
+            file: file.to_owned(),
+            path,
+        }
+    };
+    Ok(match &node.value {
+        YamlValue::Mapping(pairs) => pairs
+            .iter()
+            .map(|pair| {
+                child(
+                    pair.key.as_str().unwrap_or_default().to_string(),
+                    &pair.value,
+                )
+            })
+            .collect(),
+        YamlValue::Sequence(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| child(index.to_string(), item))
+            .collect(),
+        _ => vec![],
+    })
+}
+
+/// `target` and every node below it in document order. Each ref is entered
+/// once, so circular refs end.
+fn descendants(resolver: &mut Resolver, target: Target) -> Result<Vec<Target>> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![target];
+    let mut found = vec![];
+    while let Some(target) = stack.pop() {
+        if let Some(reference) = target.node.reference() {
+            let (file, fragment) = split_ref(&target.file, reference);
+            if !seen.insert(resolver.location(&file, &fragment)) {
+                continue;
+            }
+        }
+        let mut children = children(resolver, &target)?;
+        children.reverse();
+        stack.extend(children);
+        found.push(target);
+    }
+    Ok(found)
+}
+
+#[derive(Debug)]
+struct Then {
+    field: Option<Field>,
+    function: Function,
+}
+
+#[derive(Debug)]
+enum Field {
+    /// `@key`, the keys of the target.
+    Key,
+    Name(String),
+}
+
+impl Then {
+    fn parse(rule: &str, node: &YamlNode) -> Result<Then> {
+        let field = match node.get("field").and_then(|field| field.as_str()) {
+            Some("@key") => Some(Field::Key),
+            Some(field) => Some(Field::Name(field.to_string())),
+            None => None,
+        };
+        let function = node
+            .get("function")
+            .and_then(|function| function.as_str())
+            .with_context(|| format!("Rule `{}` has no `then.function`", rule))?;
+        let options = node.get("functionOptions");
+        let option = |key: &str| options.and_then(|options| options.get(key));
+        let function = match function {
+            "truthy" => Function::Truthy,
+            "pattern" => {
+                let compile = |key: &str| -> Result<Option<(String, Pattern)>> {
+                    let source = match option(key).and_then(|source| source.as_str()) {
+                        Some(source) => source,
+                        None => return Ok(None),
+                    };
+                    let pattern = Pattern::new(source).map_err(|error| {
+                        anyhow::Error::msg(format!("{} in rule `{}`", error, rule))
+                    })?;
+                    Ok(Some((source.to_string(), pattern)))
+                };
+                let (matches, not_matches) = (compile("match")?, compile("notMatch")?);
+                if matches.is_none() && not_matches.is_none() {
+                    bail!(
+                        "The `pattern` function of rule `{}` needs a `match` or `notMatch` option",
+                        rule
+                    );
+                }
+                Function::Pattern {
+                    matches,
+                    not_matches,
+                }
+            }
+            "enum" => {
+                let values = option("values").and_then(strings).with_context(|| {
+                    format!(
+                        "The `enum` function of rule `{}` needs a `values` list",
+                        rule
+                    )
+                })?;
+                Function::Enum(values)
+            }
+            "length" => {
+                let bound = |key: &str| -> Result<Option<f64>> {
+                    let bound = match option(key) {
+                        Some(bound) => bound,
+                        None => return Ok(None),
+                    };
+                    let number = bound.as_str().and_then(|bound| bound.parse().ok());
+                    number.map(Some).with_context(|| {
+                        format!("Expected a number for `{}` of rule `{}`", key, rule)
+                    })
+                };
+                let (min, max) = (bound("min")?, bound("max")?);
+                if min.is_none() && max.is_none() {
+                    bail!(
+                        "The `length` function of rule `{}` needs a `min` or `max` option",
+                        rule
+                    );
+                }
+                Function::Length { min, max }
+            }
+            function => bail!(
+                "Unknown function `{}` in rule `{}`, expected truthy, pattern, enum or length",
+                function,
+                rule
+            ),
+        };
+        Ok(Then { field, function })
+    }
+
+    /// What the function is applied to for the node `node`, which `target`
+    /// resolved to in `file`.
+    fn subjects(
+        &self,
+        resolver: &mut Resolver,
+        target: &Target,
+        node: &YamlNode,
+        file: &Path,
+    ) -> Result<Vec<Subject>> {
+        let subject =
+            |value: Option<YamlNode>, property: &str, at: &YamlNode, file: &Path| Subject {
+                value,
+                property: property.to_string(),
+                at: at.to_owned(),
+                file: file.to_path_buf(),
+            };
+        Ok(match &self.field {
+            None => {
+                let property = target.path.last().map(String::as_str).unwrap_or("$");
+                vec![subject(Some(node.to_owned()), property, node, file)]
+            }
+            Some(Field::Key) => node
+                .as_mapping()
+                .unwrap_or_default()
+                .iter()
+                .map(|pair| {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    subject(Some(pair.key.to_owned()), key, &pair.key, file)
+                })
+                .collect(),
+            Some(Field::Name(name)) => match node.get(name) {
+                Some(value) => {
+                    let (value, file) = resolver.follow(value, file)?;
+                    vec![subject(Some(value.to_owned()), name, &value, &file)]
+                }
+                None => vec![subject(None, name, node, file)],
+            },
+        })
+    }
+}
+
+/// A value a function is applied to.
+struct Subject {
+    /// `None` when the field is missing.
+    value: Option<YamlNode>,
+    /// The field, key or last path segment the value was found at.
+    property: String,
+    /// Where problems are reported.
+    at: YamlNode,
+    file: PathBuf,
+}
+
+#[derive(Debug)]
+enum Function {
+    Truthy,
+    Pattern {
+        matches: Option<(String, Pattern)>,
+        not_matches: Option<(String, Pattern)>,
+    },
+    Enum(Vec<String>),
+    Length {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+impl Function {
+    /// The problem with the subject, if any. Only `truthy` reports missing
+    /// fields.
+    fn check(&self, subject: &Subject) -> Option<String> {
+        let property = &subject.property;
+        let value = match (self, &subject.value) {
+            (Function::Truthy, value) => {
+                let truthy = value.as_ref().is_some_and(is_truthy);
+                return (!truthy).then(|| format!("`{}` property must be truthy", property));
+            }
+            (_, Some(value)) => value,
+            (_, None) => return None,
+        };
+        match self {
+            Function::Truthy => None,
+            Function::Pattern {
+                matches,
+                not_matches,
+            } => {
+                let text = value.as_str()?;
+                if let Some((source, pattern)) = matches {
+                    if !pattern.is_match(text) {
+                        return Some(format!("`{}` must match the pattern `{}`", text, source));
+                    }
+                }
+                match not_matches {
+                    Some((source, pattern)) if pattern.is_match(text) => Some(format!(
+                        "`{}` must not match the pattern `{}`",
+                        text, source
+                    )),
+                    _ => None,
+                }
+            }
+            Function::Enum(values) => {
+                let text = value.as_str()?;
+                if values.iter().any(|allowed| allowed == text) {
+                    return None;
+                }
+                let values: Vec<String> =
+                    values.iter().map(|value| format!("`{}`", value)).collect();
+                Some(format!("`{}` must be one of {}", text, values.join(", ")))
+            }
+            Function::Length { min, max } => {
+                let length = length(value);
+                match (min, max) {
+                    (Some(min), _) if length < *min => Some(format!(
+                        "`{}` must have a length of at least {}",
+                        property, min
+                    )),
+                    (_, Some(max)) if length > *max => Some(format!(
+                        "`{}` must have a length of at most {}",
+                        property, max
+                    )),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Whether JavaScript would consider the value true, as Spectral does.
+fn is_truthy(node: &YamlNode) -> bool {
+    match &node.value {
+        YamlValue::Scalar(scalar) if scalar.style == ScalarStyle::Plain => {
+            !node.is_null() && !matches!(scalar.text.as_str(), "false" | "0" | "0.0")
+        }
+        YamlValue::Scalar(scalar) => !scalar.text.is_empty(),
+        _ => true,
+    }
+}
+
+/// The number of characters, items or entries of the value, or the value of
+/// a number.
+fn length(node: &YamlNode) -> f64 {
+    match &node.value {
+        YamlValue::Mapping(pairs) => pairs.len() as f64,
+        YamlValue::Sequence(items) => items.len() as f64,
+        YamlValue::Scalar(scalar) => match scalar.text.parse() {
+            Ok(number) if scalar.style == ScalarStyle::Plain => number,
+            _ => scalar.text.chars().count() as f64,
+        },
+        YamlValue::Alias(_) => 0.0,
+    }
+}
+
+/// A string, or the strings of a list.
+fn strings(node: &YamlNode) -> Option<Vec<String>> {
+    match &node.value {
+        YamlValue::Sequence(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_owned))
+            .collect(),
+        _ => node.as_str().map(|text| vec![text.to_string()]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{
+            lint::{LintParser, TreeSitterLintParser},
+            ruleset::{parse_path, Ruleset},
+        },
+        content::ContentProviderMap,
+    };
+
+    fn lint(root_content: &str, ruleset: &str) -> Vec<(String, usize, String)> {
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let ruleset = Ruleset::parse(ruleset).unwrap();
+        let enabled: Vec<String> = ruleset
+            .rules
+            .iter()
+            .map(|rule| rule.name.to_owned())
+            .filter(|name| !ruleset.toggles.contains(&(name.to_owned(), false)))
+            .collect();
+        TreeSitterLintParser::new(provider)
+            .with_rules(ruleset.rules)
+            .get_lint_diagnostics(&enabled)
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| {
+                (
+                    format!("{} {}", diagnostic.severity, diagnostic.rule),
+                    diagnostic.line,
+                    diagnostic.message,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lint_ruleset() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags: [pets]
+    post:
+      operationId: Create_Pet
+      tags: []
+  /Pets/{petId}:
+    $ref: '#/components/pathItems/Pet'
+components:
+  pathItems:
+    Pet:
+      get:
+        operationId: showPet
+        description: ''
+  schemas:
+    Pet:
+      type: object
+      properties:
+        kind:
+          type: strin
+          description: The kind
+"##;
+        let ruleset = r#"
+rules:
+  operation-tags:
+    description: Operations must have tags
+    severity: error
+    given: $.paths[*][get,post]
+    then:
+      field: tags
+      function: length
+      functionOptions:
+        min: 1
+  operation-id-pascal:
+    severity: info
+    given: $.paths.*.*.operationId
+    then:
+      function: pattern
+      functionOptions:
+        match: '^[a-z][a-zA-Z]*$'
+  lowercase-paths:
+    message: 'Path {{property}} is not lowercase'
+    given: $.paths
+    then:
+      field: '@key'
+      function: pattern
+      functionOptions:
+        notMatch: '[A-Z]'
+  description-set:
+    severity: hint
+    given: $..description
+    then:
+      function: truthy
+  types:
+    given: "$..properties[*]"
+    then:
+      - field: type
+        function: enum
+        functionOptions:
+          values: [string, number, integer, boolean, array, object]
+      - field: description
+        function: truthy
+  disabled:
+    severity: off
+    given: $.openapi
+    then:
+      function: truthy
+"#;
+        assert_eq!(
+            lint(root_content, ruleset),
+            vec![
+                (
+                    "error operation-tags".to_string(),
+                    9,
+                    "Operations must have tags".to_string()
+                ),
+                (
+                    "info operation-id-pascal".to_string(),
+                    8,
+                    "`Create_Pet` must match the pattern `^[a-z][a-zA-Z]*$`".to_string()
+                ),
+                (
+                    "warning lowercase-paths".to_string(),
+                    10,
+                    "Path /Pets/{petId} is not lowercase".to_string()
+                ),
+                (
+                    "hint description-set".to_string(),
+                    17,
+                    "`description` property must be truthy".to_string()
+                ),
+                (
+                    "warning types".to_string(),
+                    23,
+                    "`strin` must be one of `string`, `number`, `integer`, `boolean`, `array`, `object`"
+                        .to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ruleset_errors() {
+        let error = |ruleset: &str| Ruleset::parse(ruleset).unwrap_err().to_string();
+        assert_eq!(
+            error("rules:\n  a:\n    given: $.paths\n    then:\n      function: schema\n"),
+            "Unknown function `schema` in rule `a`, expected truthy, pattern, enum or length"
+        );
+        assert_eq!(
+            error("rules:\n  a:\n    given: paths\n    then:\n      function: truthy\n"),
+            "Invalid path `paths` in rule `a`: paths start with `$`"
+        );
+        assert_eq!(
+            error("rules:\n  a:\n    given: $.paths\n    then:\n      function: pattern\n"),
+            "The `pattern` function of rule `a` needs a `match` or `notMatch` option"
+        );
+        assert_eq!(
+            error("rules:\n  a:\n    severity: fatal\n"),
+            "Unknown severity `fatal` for rule `a`, expected error, warn, info, hint or off"
+        );
+        assert_eq!(
+            error("rules:\n  operation-summary: maybe\n"),
+            "Expected `on`, `off` or a rule definition for lint rule `operation-summary`"
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let names = |path: &str| -> Vec<(bool, Option<Vec<String>>)> {
+            parse_path(path)
+                .unwrap()
+                .into_iter()
+                .map(|step| (step.recursive, step.names))
+                .collect()
+        };
+        let name = |name: &str| Some(vec![name.to_string()]);
+        assert_eq!(
+            names("$.paths['/pets'][get, 'put']..schema"),
+            vec![
+                (false, name("paths")),
+                (false, name("/pets")),
+                (false, Some(vec!["get".to_string(), "put".to_string()])),
+                (true, name("schema")),
+            ]
+        );
+        assert_eq!(
+            names("$..[*].tags[0]"),
+            vec![(true, None), (false, name("tags")), (false, name("0"))]
+        );
+        assert_eq!(names("$"), vec![]);
+        assert_eq!(
+            parse_path("$.paths[?(@.get)]").unwrap_err(),
+            "filter expressions are not supported"
+        );
+    }
+}
@@ -1,18 +1,99 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
-use anyhow::Context;
+use anyhow::Result;
 
 use crate::{content::ContentProvider, error::OpenapiSchemerError};
 
-use super::{get_children_by_key, get_top_level_keys, ChildrenOrRef};
+use super::{
+    resolve::{identity, MappingEntry, Resolver},
+    yaml::{escape_pointer_segment, YamlNode},
+    OperationNode,
+};
+
+/// The parts of an operation that can refer to schemas.
+const OPERATION_KEYS: [&str; 3] = ["parameters", "requestBody", "responses"];
 
 #[derive(Clone, Debug)]
 pub struct SchemaNode {
     pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A schema restricted to a set of values by `enum` or `const`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumNode {
+    /// Where the schema sits, as `Pet.status` or `Pet.tags[]`.
+    pub name: String,
+    /// The allowed values, a single one for `const`.
+    pub values: Vec<YamlNode>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A mistake in the `required` of a component schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequiredProblem {
+    pub schema: String,
+    pub message: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
 }
 
 pub trait SchemaParser {
-    fn get_schema_nodes(&self) -> Result<Vec<SchemaNode>, OpenapiSchemerError>;
+    /// The entries of the section of `components` called `section`, such as
+    /// `schemas` or `requestBodies`. A spec without the section has none.
+    fn get_component_nodes(&self, section: &str) -> Result<Vec<SchemaNode>, OpenapiSchemerError>;
+
+    /// Calls `f` with each entry of `get_component_nodes` as it is found, so
+    /// a huge spec can be handled an entry at a time.
+    fn for_each_component_node(
+        &self,
+        section: &str,
+        f: &mut dyn FnMut(SchemaNode),
+    ) -> Result<(), OpenapiSchemerError> {
+        self.get_component_nodes(section)?.into_iter().for_each(f);
+        Ok(())
+    }
+
+    fn get_schema_nodes(&self) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
+        self.get_component_nodes("schemas")
+    }
+
+    /// The definition of the schema called `name`. Nested `$ref`s are only
+    /// replaced by their targets when `resolve` is set.
+    fn get_schema(
+        &self,
+        name: &str,
+        resolve: bool,
+    ) -> Result<Option<YamlNode>, OpenapiSchemerError>;
+
+    /// The operations whose parameters, request body or responses refer to
+    /// the schema called `name`, directly or through other refs. `None` when
+    /// there is no such schema.
+    fn get_schema_usages(
+        &self,
+        name: &str,
+    ) -> Result<Option<Vec<OperationNode>>, OpenapiSchemerError>;
+
+    /// The enums of every component schema and their properties, or only of
+    /// the schema called `name` and everything it refers to. `None` when
+    /// there is no schema called `name`.
+    fn get_schema_enums(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Option<Vec<EnumNode>>, OpenapiSchemerError>;
+
+    /// The component schemas whose `required` lists properties they don't
+    /// declare, that require none of their properties, or that require a
+    /// `readOnly` property while a request body refers to them.
+    fn get_required_problems(&self) -> Result<Vec<RequiredProblem>, OpenapiSchemerError>;
 }
 
 pub struct TreeSitterSchemaParser {
@@ -23,63 +104,748 @@ impl TreeSitterSchemaParser {
     pub fn new(provider: Box<dyn ContentProvider>) -> Self {
         Self { provider }
     }
-}
 
-impl SchemaParser for TreeSitterSchemaParser {
-    fn get_schema_nodes(&self) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
-        let content = self.provider.get_content(PathBuf::from("#"));
-        let mut results: Vec<SchemaNode> = vec![];
-
-        let mut components_children = get_children_by_key("components", content.as_bytes())
-            .with_context(|| format!("Failed to get children for yaml key `components`"))
-            .map_err(|error| OpenapiSchemerError::SchemaList(error.to_string()))?;
-        if let ChildrenOrRef::Ref(r) = components_children {
-            let content = self.provider.get_content(PathBuf::from(r));
-            components_children = get_top_level_keys(content.as_bytes())
-                .with_context(|| format!("Failed to get children for yaml key `components`"))
-                .map_err(|error| OpenapiSchemerError::SchemaList(error.to_string()))?;
+    /// Whether a `$ref` below one of `nodes` leads to the node `target`,
+    /// directly or through other refs.
+    fn reaches(
+        &self,
+        resolver: &mut Resolver,
+        nodes: Vec<(YamlNode, PathBuf)>,
+        target: &str,
+    ) -> bool {
+        let mut pending = nodes;
+        let mut reached = HashSet::new();
+        while let Some((node, file)) = pending.pop() {
+            let refs: Vec<String> = node.references().into_iter().map(str::to_owned).collect();
+            for reference in refs {
+                // Broken refs are reported by `refs check`, here they simply
+                // don't reach anything.
+                let (node, node_file) = match resolver.resolve_ref(&file, &reference) {
+                    Ok(resolved) => resolved,
+                    Err(_) => continue,
+                };
+                let id = identity(self.provider.as_ref(), &node_file, &node);
+                if id == target {
+                    return true;
+                }
+                if reached.insert(id) {
+                    pending.push((node, node_file));
+                }
+            }
+        }
+        false
+    }
+
+    fn schema_usages(&self, name: &str) -> Result<Option<Vec<OperationNode>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let schema = resolver
+            .entries("/components/schemas")?
+            .into_iter()
+            .find(|entry| entry.name == name);
+        let target = match schema {
+            Some(schema) => identity(self.provider.as_ref(), &schema.file, &schema.value),
+            None => return Ok(None),
+        };
+        // Parameters declared on a path item apply to each of its operations.
+        let path_parameters: HashMap<String, (YamlNode, PathBuf)> = resolver
+            .entries("/paths")?
+            .into_iter()
+            .filter_map(|path_item| {
+                let parameters = path_item.value.get("parameters")?.to_owned();
+                Some((path_item.name, (parameters, path_item.file)))
+            })
+            .collect();
+        let mut results = vec![];
+        for operation in resolver.operations()? {
+            let mut nodes: Vec<(YamlNode, PathBuf)> = OPERATION_KEYS
+                .iter()
+                .filter_map(|key| operation.operation.get(key))
+                .map(|node| (node.to_owned(), operation.file.to_owned()))
+                .collect();
+            nodes.extend(path_parameters.get(&operation.path).cloned());
+            if self.reaches(&mut resolver, nodes, &target) {
+                results.push(operation.node(self.provider.as_ref()));
+            }
+        }
+        Ok(Some(results))
+    }
+
+    fn schema_enums(&self, name: Option<&str>) -> Result<Option<Vec<EnumNode>>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let schemas: Vec<MappingEntry> = resolver
+            .entries("/components/schemas")?
+            .into_iter()
+            .filter(|entry| name.is_none() || name == Some(entry.name.as_str()))
+            .collect();
+        if name.is_some() && schemas.is_empty() {
+            return Ok(None);
+        }
+        let mut walk = EnumWalk {
+            follow_all: name.is_some(),
+            ancestors: vec![],
+            enums: vec![],
+        };
+        for schema in schemas {
+            walk.ancestors = vec![identity(
+                self.provider.as_ref(),
+                &schema.file,
+                &schema.value,
+            )];
+            self.collect_enums(
+                &mut resolver,
+                &mut walk,
+                &schema.value,
+                &schema.file,
+                &schema.name,
+            );
         }
-        match components_children {
-            ChildrenOrRef::Ref(_) => {
-                return Err(OpenapiSchemerError::SchemaList(format!(
-                    "$ref cannot link to another $ref"
-                )))
+        Ok(Some(walk.enums))
+    }
+
+    /// Adds the enums of `schema` and its subschemas to `walk`.
+    fn collect_enums(
+        &self,
+        resolver: &mut Resolver,
+        walk: &mut EnumWalk,
+        schema: &YamlNode,
+        file: &Path,
+        name: &str,
+    ) {
+        if schema.reference().is_some() {
+            // Broken refs are reported by `refs check`.
+            let (target, target_file) = match resolver.follow(schema, file) {
+                Ok(target) => target,
+                Err(_) => return,
+            };
+            let id = identity(self.provider.as_ref(), &target_file, &target);
+            if (!walk.follow_all && enum_values(&target).is_none()) || walk.ancestors.contains(&id)
+            {
+                return;
             }
-            ChildrenOrRef::Children(children) => {
-                let schemas_context = children.get("schemas").unwrap();
-                let schemas_children =
-                    get_children_by_key("schemas", schemas_context.as_bytes()).unwrap();
-                match schemas_children {
-                    ChildrenOrRef::Ref(_) => {
-                        return Err(OpenapiSchemerError::SchemaList(format!(
-                            "Expected structs under schemas key but found $ref instead"
-                        )));
-                    }
-                    ChildrenOrRef::Children(children) => {
-                        for (schema_child, _) in children {
-                            results.push(SchemaNode { text: schema_child })
-                        }
+            walk.ancestors.push(id);
+            self.collect_enums(resolver, walk, &target, &target_file, name);
+            walk.ancestors.pop();
+            return;
+        }
+        if let Some((keyword, values)) = enum_values(schema) {
+            walk.enums.push(EnumNode {
+                name: name.to_string(),
+                values,
+                file: self.provider.source_path(file),
+                line: keyword.start.row + 1,
+                column: keyword.start.column + 1,
+            });
+        }
+        let mut subschemas = vec![];
+        for pair in schema
+            .get("properties")
+            .and_then(|properties| properties.as_mapping())
+            .unwrap_or_default()
+        {
+            let property = pair.key.as_str().unwrap_or_default();
+            subschemas.push((&pair.value, format!("{}.{}", name, property)));
+        }
+        if let Some(items) = schema.get("items") {
+            subschemas.push((items, format!("{}[]", name)));
+        }
+        if let Some(values) = schema.get("additionalProperties") {
+            subschemas.push((values, format!("{}.*", name)));
+        }
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            for subschema in schema
+                .get(keyword)
+                .and_then(|subschemas| subschemas.as_sequence())
+                .unwrap_or_default()
+            {
+                subschemas.push((subschema, name.to_string()));
+            }
+        }
+        for (subschema, name) in subschemas {
+            self.collect_enums(resolver, walk, subschema, file, &name);
+        }
+    }
+
+    fn required_problems(&self) -> Result<Vec<RequiredProblem>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let request_bodies: Vec<(String, (YamlNode, PathBuf))> = resolver
+            .operations()?
+            .into_iter()
+            .filter_map(|operation| {
+                let body = operation.operation.get("requestBody")?.to_owned();
+                let name = operation.node(self.provider.as_ref()).text;
+                Some((name, (body, operation.file)))
+            })
+            .collect();
+        let mut problems = vec![];
+        let mut seen = HashSet::new();
+        for entry in resolver.entries("/components/schemas")? {
+            let id = identity(self.provider.as_ref(), &entry.file, &entry.value);
+            if !seen.insert(id.to_owned()) {
+                continue;
+            }
+            let shape = self.required_shape(&mut resolver, &entry.value, &entry.file);
+            let problem = |message: String, file: &Path, node: &YamlNode| RequiredProblem {
+                schema: entry.name.to_owned(),
+                message,
+                file: self.provider.source_path(file),
+                line: node.start.row + 1,
+                column: node.start.column + 1,
+            };
+            if !shape.properties.is_empty() && shape.required.is_empty() {
+                problems.push(problem(
+                    "none of its properties are required".to_string(),
+                    &entry.key_file,
+                    &entry.key,
+                ));
+            }
+            let mut read_only = vec![];
+            for required in shape.required.iter().filter(|required| required.own) {
+                let property = shape
+                    .properties
+                    .iter()
+                    .find(|(name, _, _)| *name == required.name);
+                let (property, property_file) = match property {
+                    Some((_, property, file)) => (property, file),
+                    None => {
+                        problems.push(problem(
+                            format!(
+                                "`required` lists `{}`, which is not one of its properties",
+                                required.name
+                            ),
+                            &required.file,
+                            &required.node,
+                        ));
+                        continue;
                     }
+                };
+                // Broken refs are reported by `refs check`.
+                let read_only_property = resolver
+                    .follow(property, property_file)
+                    .map(|(property, _)| {
+                        property
+                            .get("readOnly")
+                            .and_then(|read_only| read_only.as_str())
+                            == Some("true")
+                    })
+                    .unwrap_or_default();
+                if read_only_property {
+                    read_only.push(required);
                 }
             }
+            if read_only.is_empty() {
+                continue;
+            }
+            let operations: Vec<String> = request_bodies
+                .iter()
+                .filter(|(_, body)| self.reaches(&mut resolver, vec![body.to_owned()], &id))
+                .map(|(name, _)| format!("`{}`", name))
+                .collect();
+            if operations.is_empty() {
+                continue;
+            }
+            for required in read_only {
+                problems.push(problem(
+                    format!(
+                        "`{}` is readOnly but required, and the request body of {} refers to it",
+                        required.name,
+                        operations.join(", ")
+                    ),
+                    &required.file,
+                    &required.node,
+                ));
+            }
+        }
+        Ok(problems)
+    }
+
+    /// The properties `schema` declares and the names it requires, counting
+    /// what it inherits through `allOf`. Properties come with their schema
+    /// and the file it is written in.
+    fn required_shape(
+        &self,
+        resolver: &mut Resolver,
+        schema: &YamlNode,
+        file: &Path,
+    ) -> RequiredShape {
+        let mut shape = RequiredShape {
+            properties: vec![],
+            required: vec![],
+        };
+        let mut pending = vec![(schema.to_owned(), file.to_path_buf(), true)];
+        let mut visited = HashSet::new();
+        while let Some((schema, schema_file, own)) = pending.pop() {
+            let own = own && schema.reference().is_none();
+            let (schema, schema_file) = match resolver.follow(&schema, &schema_file) {
+                Ok(schema) => schema,
+                Err(_) => continue,
+            };
+            if !visited.insert(identity(self.provider.as_ref(), &schema_file, &schema)) {
+                continue;
+            }
+            for pair in schema
+                .get("properties")
+                .and_then(|properties| properties.as_mapping())
+                .unwrap_or_default()
+            {
+                let name = pair.key.as_str().unwrap_or_default().to_string();
+                shape
+                    .properties
+                    .push((name, pair.value.to_owned(), schema_file.to_owned()));
+            }
+            for node in schema
+                .get("required")
+                .and_then(|required| required.as_sequence())
+                .unwrap_or_default()
+            {
+                shape.required.push(RequiredName {
+                    name: node.as_str().unwrap_or_default().to_string(),
+                    node: node.to_owned(),
+                    file: schema_file.to_owned(),
+                    own,
+                });
+            }
+            let branches = schema
+                .get("allOf")
+                .and_then(|branches| branches.as_sequence())
+                .unwrap_or_default();
+            for branch in branches.iter().rev() {
+                pending.push((branch.to_owned(), schema_file.to_owned(), own));
+            }
         }
+        shape
+    }
+}
+
+/// What `required_shape` finds in a schema and its `allOf` branches.
+struct RequiredShape {
+    properties: Vec<(String, YamlNode, PathBuf)>,
+    required: Vec<RequiredName>,
+}
 
+/// A name listed by `required`. It is `own` when written in the schema or
+/// an inline `allOf` branch rather than in a schema it refers to, which is
+/// audited on its own.
+struct RequiredName {
+    name: String,
+    node: YamlNode,
+    file: PathBuf,
+    own: bool,
+}
+
+/// The state of collecting enums. Listing every component, only refs to enums
+/// are followed since the other targets are listed on their own. `ancestors`
+/// keeps recursive schemas from being walked forever.
+struct EnumWalk {
+    follow_all: bool,
+    ancestors: Vec<String>,
+    enums: Vec<EnumNode>,
+}
+
+/// The `enum` or `const` key of `schema` and the values it allows.
+fn enum_values(schema: &YamlNode) -> Option<(&YamlNode, Vec<YamlNode>)> {
+    let pairs = schema.as_mapping()?;
+    if let Some(pair) = pairs.iter().find(|pair| pair.key.as_str() == Some("enum")) {
+        let values = pair.value.as_sequence().unwrap_or_default().to_vec();
+        return Some((&pair.key, values));
+    }
+    let pair = pairs
+        .iter()
+        .find(|pair| pair.key.as_str() == Some("const"))?;
+    Some((&pair.key, vec![pair.value.to_owned()]))
+}
+
+impl SchemaParser for TreeSitterSchemaParser {
+    fn get_schema(
+        &self,
+        name: &str,
+        resolve: bool,
+    ) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaShow)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path).map_err(to_error)?.to_owned();
+        let components = match root.get("components") {
+            Some(components) => components,
+            None => return Ok(None),
+        };
+        let (components, components_file) =
+            resolver.follow(components, &root_path).map_err(to_error)?;
+        let schemas = match components.get("schemas") {
+            Some(schemas) => schemas,
+            None => return Ok(None),
+        };
+        let (schemas, schemas_file) = resolver
+            .follow(schemas, &components_file)
+            .map_err(to_error)?;
+        let schema = match schemas.get(name) {
+            Some(schema) => schema,
+            None => return Ok(None),
+        };
+        // A schema that is only a $ref to its definition in another file is
+        // shown as that definition.
+        let (schema, schema_file) = resolver.follow(schema, &schemas_file).map_err(to_error)?;
+        if !resolve {
+            return Ok(Some(schema));
+        }
+        resolver
+            .inline(&schema, &schema_file)
+            .map(Some)
+            .map_err(to_error)
+    }
+
+    fn get_schema_usages(
+        &self,
+        name: &str,
+    ) -> Result<Option<Vec<OperationNode>>, OpenapiSchemerError> {
+        self.schema_usages(name).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaUsages)
+        })
+    }
+
+    fn get_schema_enums(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Option<Vec<EnumNode>>, OpenapiSchemerError> {
+        self.schema_enums(name).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaEnums)
+        })
+    }
+
+    fn get_required_problems(&self) -> Result<Vec<RequiredProblem>, OpenapiSchemerError> {
+        self.required_problems().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaAuditRequired)
+        })
+    }
+
+    fn get_component_nodes(&self, section: &str) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
+        let mut results = vec![];
+        self.for_each_component_node(section, &mut |node| results.push(node))?;
         Ok(results)
     }
+
+    fn for_each_component_node(
+        &self,
+        section: &str,
+        f: &mut dyn FnMut(SchemaNode),
+    ) -> Result<(), OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let pointer = format!("/components/{}", escape_pointer_segment(section));
+        resolver
+            .for_each_entry(&pointer, &mut |_, entry| {
+                f(SchemaNode {
+                    file: self.provider.source_path(&entry.key_file),
+                    line: entry.key.start.row + 1,
+                    column: entry.key.start.column + 1,
+                    text: entry.name,
+                });
+                Ok(())
+            })
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SchemaList)
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::{collections::HashMap, error::Error, path::PathBuf};
 
-    use mocktopus::mocking::*;
-
     use crate::{
         bindings::schema::{SchemaParser, TreeSitterSchemaParser},
-        content::ContentProvider,
         content::ContentProviderMap,
     };
 
+    #[test]
+    fn get_schema() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  schemas:
+    Pet:
+      $ref: 'schemas/Pet.yaml'
+    Tag:
+      type: string
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  tag:
+    $ref: '../openapi.yaml#/components/schemas/Tag'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("openapi.yaml"), root_content.to_owned()),
+            (PathBuf::from("schemas/Pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+
+        let schema = parser.get_schema("Pet", false)?.unwrap();
+        assert_eq!(
+            schema.to_yaml(),
+            "type: object\nproperties:\n  tag:\n    $ref: '../openapi.yaml#/components/schemas/Tag'\n"
+        );
+        let schema = parser.get_schema("Pet", true)?.unwrap();
+        assert_eq!(
+            schema.to_yaml(),
+            "type: object\nproperties:\n  tag:\n    type: string\n"
+        );
+        assert!(parser.get_schema("Missing", true)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_schema_usages() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pets'
+    post:
+      operationId: createPet
+      requestBody:
+        $ref: '#/components/requestBodies/NewPet'
+      responses:
+        '201':
+          description: Created
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema:
+          $ref: '#/components/schemas/PetId'
+    delete:
+      responses:
+        '204':
+          description: Deleted
+  /tags:
+    get:
+      operationId: listTags
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    Pets:
+      type: array
+      items:
+        $ref: '#/components/schemas/Pet'
+    Pet:
+      $ref: 'schemas/Pet.yaml'
+    PetId:
+      type: integer
+  requestBodies:
+    NewPet:
+      content:
+        application/json:
+          schema:
+            $ref: 'schemas/Pet.yaml'
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("schemas/Pet.yaml"),
+                "type: object\n".to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+        let usages = |name| -> Result<Option<Vec<String>>, Box<dyn Error>> {
+            let usages = parser.get_schema_usages(name)?;
+            Ok(usages.map(|usages| usages.into_iter().map(|usage| usage.text).collect()))
+        };
+        assert_eq!(
+            usages("Pet")?,
+            Some(vec!["listPets".to_string(), "createPet".to_string()])
+        );
+        assert_eq!(usages("Pets")?, Some(vec!["listPets".to_string()]));
+        assert_eq!(
+            usages("PetId")?,
+            Some(vec!["DELETE /pets/{petId}".to_string()])
+        );
+        assert_eq!(usages("Missing")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn get_schema_enums() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        status:
+          $ref: 'enums.yaml#/Status'
+        kind:
+          const: pet
+        owner:
+          $ref: '#/components/schemas/Owner'
+        parent:
+          $ref: '#/components/schemas/Pet'
+    Owner:
+      type: object
+      properties:
+        roles:
+          type: array
+          items:
+            type: string
+            enum: [admin, member]
+"##;
+        let enums_content = r#"Status:
+  type: string
+  enum:
+    - available
+    - sold
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("enums.yaml"), enums_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+        let enums = |name| -> Vec<(String, Vec<String>, String, usize)> {
+            parser
+                .get_schema_enums(name)
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|node| {
+                    let values = node
+                        .values
+                        .iter()
+                        .map(|value| value.as_str().unwrap_or_default().to_string())
+                        .collect();
+                    (
+                        node.name,
+                        values,
+                        node.file.display().to_string(),
+                        node.line,
+                    )
+                })
+                .collect()
+        };
+        let status = (
+            "Pet.status".to_string(),
+            vec!["available".to_string(), "sold".to_string()],
+            "enums.yaml".to_string(),
+            3,
+        );
+        let kind = (
+            "Pet.kind".to_string(),
+            vec!["pet".to_string()],
+            "#".to_string(),
+            10,
+        );
+        let roles = |name: &str| {
+            (
+                name.to_string(),
+                vec!["admin".to_string(), "member".to_string()],
+                "#".to_string(),
+                22,
+            )
+        };
+        assert_eq!(
+            enums(None),
+            vec![status.to_owned(), kind.to_owned(), roles("Owner.roles[]")]
+        );
+        assert_eq!(
+            enums(Some("Pet")),
+            vec![status, kind, roles("Pet.owner.roles[]")]
+        );
+        assert!(parser.get_schema_enums(Some("Missing"))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_required_problems() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"
+paths:
+  /pets:
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/NewPet'
+      responses:
+        '201':
+          description: Created
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, nickname]
+      properties:
+        id:
+          type: integer
+          readOnly: true
+        name:
+          type: string
+    NewPet:
+      allOf:
+        - $ref: '#/components/schemas/Base'
+        - type: object
+          required: [id]
+    Base:
+      type: object
+      required: [name]
+      properties:
+        id:
+          $ref: '#/components/schemas/Id'
+        name:
+          type: string
+    Id:
+      type: integer
+      readOnly: true
+    Filter:
+      type: object
+      properties:
+        tag:
+          type: string
+"##;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+        let problems: Vec<(String, String, usize)> = parser
+            .get_required_problems()?
+            .into_iter()
+            .map(|problem| (problem.schema, problem.message, problem.line))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "Pet".to_string(),
+                    "`required` lists `nickname`, which is not one of its properties".to_string(),
+                    18
+                ),
+                (
+                    "NewPet".to_string(),
+                    "`id` is readOnly but required, and the request body of `createPet` refers to it"
+                        .to_string(),
+                    29
+                ),
+                (
+                    "Filter".to_string(),
+                    "none of its properties are required".to_string(),
+                    41
+                ),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn get_schema_nodes_no_refs() -> Result<(), Box<dyn Error>> {
         let root_path = PathBuf::from("#");
@@ -110,13 +876,7 @@ components:
           type: string
             "#;
         let contents = HashMap::from([(root_path, root_content.to_owned())]);
-
-        ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
-        });
-
-        let provider = ContentProviderMap::new();
-        let box_provider = Box::new(provider);
+        let box_provider = Box::new(ContentProviderMap::from_map(contents));
         let parser = TreeSitterSchemaParser::new(box_provider);
         let result = parser.get_schema_nodes().unwrap();
         let node_texts: Vec<String> = result.into_iter().map(|node| node.text).collect();
@@ -140,6 +900,36 @@ components:
         Ok(())
     }
 
+    #[test]
+    fn get_schema_nodes_locations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  $ref: 'Components.yaml'
+"#;
+        let components_content = r#"schemas:
+  Pet:
+    type: object
+  Error:
+    type: object
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("Components.yaml"),
+                components_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+        let nodes = parser.get_schema_nodes().unwrap();
+        let pet = nodes.iter().find(|node| node.text == "Pet").unwrap();
+        assert_eq!(pet.file, PathBuf::from("Components.yaml"));
+        assert_eq!((pet.line, pet.column), (2, 3));
+        let error = nodes.iter().find(|node| node.text == "Error").unwrap();
+        assert_eq!(error.line, 4);
+        Ok(())
+    }
+
     #[test]
     fn get_schema_nodes_ref_schemas() -> Result<(), Box<dyn Error>> {
         let root_path = PathBuf::from("#");
@@ -177,13 +967,7 @@ schemas:
             (root_path, root_content.to_owned()),
             (schemas_path, schemas_content.to_owned()),
         ]);
-
-        ContentProviderMap::get_content.mock_safe(move |_, path: PathBuf| {
-            MockResult::Return(contents.get(&path).unwrap().to_owned())
-        });
-
-        let provider = ContentProviderMap::new();
-        let box_provider = Box::new(provider);
+        let box_provider = Box::new(ContentProviderMap::from_map(contents));
         let parser = TreeSitterSchemaParser::new(box_provider);
         let result = parser.get_schema_nodes().unwrap();
         let node_texts: Vec<String> = result.into_iter().map(|node| node.text).collect();
@@ -206,4 +990,47 @@ schemas:
         );
         Ok(())
     }
+
+    #[test]
+    fn get_component_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths: {}
+components:
+  requestBodies:
+    NewPet:
+      $ref: 'bodies.yaml#/NewPet'
+  headers:
+    X-Rate-Limit:
+      schema:
+        type: integer
+"#;
+        let bodies_content = r#"
+NewPet:
+  content: {}
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("bodies.yaml"), bodies_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterSchemaParser::new(provider);
+        let summary = |section| -> Result<Vec<(String, usize)>, Box<dyn Error>> {
+            let nodes = parser.get_component_nodes(section)?;
+            Ok(nodes
+                .into_iter()
+                .map(|node| (node.text, node.line))
+                .collect())
+        };
+        assert_eq!(summary("requestBodies")?, vec![("NewPet".to_string(), 6)]);
+        assert_eq!(summary("headers")?, vec![("X-Rate-Limit".to_string(), 9)]);
+        assert_eq!(summary("schemas")?, vec![]);
+
+        let contents = HashMap::from([(PathBuf::from("#"), "openapi: 3.0.0\n".to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        assert!(TreeSitterSchemaParser::new(provider)
+            .get_schema_nodes()?
+            .is_empty());
+        Ok(())
+    }
 }
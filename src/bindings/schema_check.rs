@@ -0,0 +1,488 @@
+use std::path::Path;
+
+use super::{
+    resolve::Resolver,
+    yaml::{YamlNode, YamlValue},
+};
+
+/// How many schemas deep an instance is checked, so recursive schemas end.
+const MAX_DEPTH: usize = 64;
+
+/// The formats of the OpenAPI Format Registry and of JSON Schema.
+pub const KNOWN_FORMATS: [&str; 36] = [
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "uint8",
+    "float",
+    "double",
+    "decimal",
+    "decimal128",
+    "byte",
+    "binary",
+    "base64url",
+    "char",
+    "date",
+    "date-time",
+    "time",
+    "duration",
+    "password",
+    "email",
+    "idn-email",
+    "hostname",
+    "idn-hostname",
+    "ipv4",
+    "ipv6",
+    "uri",
+    "uri-reference",
+    "uri-template",
+    "iri",
+    "iri-reference",
+    "uuid",
+    "json-pointer",
+    "relative-json-pointer",
+    "regex",
+    "commonmark",
+    "html",
+    "media-range",
+];
+
+/// A part of an instance that its schema doesn't allow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Where the part sits in the instance, such as `$.tags[0]`.
+    pub path: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Checks `instance` against the `type`, `nullable`, `required`, `enum`,
+/// `const` and `format` of `schema`, found in `file`, and of the schemas of
+/// its properties and items. `allOf` subschemas must all match, `anyOf` and
+/// `oneOf` ones at least one. Broken refs are left to `refs check`.
+pub fn check_instance(
+    resolver: &mut Resolver,
+    schema: &YamlNode,
+    file: &Path,
+    instance: &YamlNode,
+) -> Vec<Violation> {
+    check_instance_at(resolver, schema, file, instance, "$")
+}
+
+/// Like `check_instance`, calling the instance `path` in the violations,
+/// such as `limit` for the value of a parameter.
+pub fn check_instance_at(
+    resolver: &mut Resolver,
+    schema: &YamlNode,
+    file: &Path,
+    instance: &YamlNode,
+    path: &str,
+) -> Vec<Violation> {
+    let mut violations = vec![];
+    check(resolver, schema, file, instance, path, 0, &mut violations);
+    violations
+}
+
+fn check(
+    resolver: &mut Resolver,
+    schema: &YamlNode,
+    file: &Path,
+    instance: &YamlNode,
+    path: &str,
+    depth: usize,
+    violations: &mut Vec<Violation>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let (schema, file) = match resolver.follow(schema, file) {
+        Ok(resolved) => resolved,
+        Err(_) => return,
+    };
+    let violation = |message: String| Violation {
+        path: path.to_string(),
+        message,
+        line: instance.start.row + 1,
+        column: instance.start.column + 1,
+    };
+    let subschemas = |keyword: &str| -> Vec<YamlNode> {
+        schema
+            .get(keyword)
+            .and_then(|subschemas| subschemas.as_sequence())
+            .unwrap_or_default()
+            .to_vec()
+    };
+
+    for subschema in subschemas("allOf") {
+        check(
+            resolver,
+            &subschema,
+            &file,
+            instance,
+            path,
+            depth + 1,
+            violations,
+        );
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        let options = subschemas(keyword);
+        let matched = options.iter().any(|option| {
+            let mut found = vec![];
+            check(
+                resolver,
+                option,
+                &file,
+                instance,
+                path,
+                depth + 1,
+                &mut found,
+            );
+            found.is_empty()
+        });
+        if !options.is_empty() && !matched {
+            violations.push(violation(format!(
+                "`{}` matches none of the `{}` schemas",
+                path, keyword
+            )));
+        }
+    }
+
+    let actual = instance.json_type();
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected.as_sequence() {
+            Some(types) => types.iter().filter_map(|name| name.as_str()).collect(),
+            None => expected.as_str().into_iter().collect(),
+        };
+        let nullable = schema
+            .get("nullable")
+            .and_then(|nullable| nullable.as_str())
+            == Some("true");
+        let matches = types
+            .iter()
+            .any(|name| *name == actual || (*name == "number" && actual == "integer"))
+            || (nullable && actual == "null");
+        if !types.is_empty() && !matches {
+            violations.push(violation(format!(
+                "`{}` should be of type `{}`, not `{}`",
+                path,
+                types.join("` or `"),
+                actual
+            )));
+            // The other keywords only make sense for the expected type.
+            return;
+        }
+    }
+    if let Some(values) = schema.get("enum").and_then(|values| values.as_sequence()) {
+        let json = instance.to_json();
+        if !values.iter().any(|value| value.to_json() == json) {
+            let allowed: Vec<String> = values
+                .iter()
+                .map(|value| format!("`{}`", value.as_str().unwrap_or_default()))
+                .collect();
+            violations.push(violation(format!(
+                "`{}` should be one of {}",
+                path,
+                allowed.join(", ")
+            )));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant.to_json() != instance.to_json() {
+            violations.push(violation(format!(
+                "`{}` should be `{}`",
+                path,
+                constant.as_str().unwrap_or_default()
+            )));
+        }
+    }
+    if let Some(format) = schema.get("format").and_then(|format| format.as_str()) {
+        if !matches_format(format, instance) {
+            violations.push(violation(format!("`{}` is not a valid `{}`", path, format)));
+        }
+    }
+
+    match &instance.value {
+        YamlValue::Mapping(pairs) => {
+            for required in subschemas("required") {
+                let key = required.as_str().unwrap_or_default();
+                if !pairs.iter().any(|pair| pair.key.as_str() == Some(key)) {
+                    violations.push(violation(format!(
+                        "`{}` is missing the required property `{}`",
+                        path, key
+                    )));
+                }
+            }
+            let properties = match schema.get("properties") {
+                Some(properties) => properties,
+                None => return,
+            };
+            for pair in pairs {
+                let key = pair.key.as_str().unwrap_or_default();
+                if let Some(property) = properties.get(key) {
+                    let path = format!("{}.{}", path, key);
+                    check(
+                        resolver,
+                        property,
+                        &file,
+                        &pair.value,
+                        &path,
+                        depth + 1,
+                        violations,
+                    );
+                }
+            }
+        }
+        YamlValue::Sequence(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    let path = format!("{}[{}]", path, index);
+                    check(
+                        resolver,
+                        item_schema,
+                        &file,
+                        item,
+                        &path,
+                        depth + 1,
+                        violations,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `instance` is valid for `format`. Values of another type and
+/// formats without a fixed syntax, such as `password`, always are.
+fn matches_format(format: &str, instance: &YamlNode) -> bool {
+    let text = instance.as_str().unwrap_or_default();
+    match (format, instance.json_type()) {
+        ("int32", "integer") => text.parse::<i32>().is_ok(),
+        ("int64", "integer") => text.parse::<i64>().is_ok(),
+        (_, "string") => match format {
+            "date" => is_date(text),
+            "date-time" => is_date_time(text),
+            "email" => is_email(text),
+            "uuid" => is_uuid(text),
+            "uri" => is_uri(text),
+            "ipv4" => is_ipv4(text),
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+/// Whether `text` is a number of exactly `count` digits between `min` and
+/// `max`.
+fn is_number_within(text: &str, count: usize, min: u32, max: u32) -> bool {
+    text.len() == count
+        && text.chars().all(|c| c.is_ascii_digit())
+        && text
+            .parse::<u32>()
+            .is_ok_and(|number| min <= number && number <= max)
+}
+
+/// `2024-02-29` style full dates.
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    matches!(parts.as_slice(), [year, month, day]
+        if is_number_within(year, 4, 0, 9999)
+            && is_number_within(month, 2, 1, 12)
+            && is_number_within(day, 2, 1, 31))
+}
+
+/// `2024-02-29T12:30:00Z` style timestamps, with optional fractions of a
+/// second and a `Z` or `+01:00` style offset.
+fn is_date_time(text: &str) -> bool {
+    let (date, time) = match text.split_once(['T', 't']) {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let (time, offset) = match time.strip_suffix(['Z', 'z']) {
+        Some(time) => (time, "+00:00"),
+        None => match time.rfind(['+', '-']) {
+            Some(index) => (&time[..index], &time[index + 1..]),
+            None => return false,
+        },
+    };
+    let offset = offset.trim_start_matches('+');
+    let (time, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let clock: Vec<&str> = time.split(':').collect();
+    let zone: Vec<&str> = offset.split(':').collect();
+    is_date(date)
+        && !fraction.is_empty()
+        && fraction.chars().all(|c| c.is_ascii_digit())
+        && matches!(clock.as_slice(), [hour, minute, second]
+            if is_number_within(hour, 2, 0, 23)
+                && is_number_within(minute, 2, 0, 59)
+                && is_number_within(second, 2, 0, 60))
+        && matches!(zone.as_slice(), [hour, minute]
+            if is_number_within(hour, 2, 0, 23) && is_number_within(minute, 2, 0, 59))
+}
+
+fn is_email(text: &str) -> bool {
+    match text.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !text.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn is_uuid(text: &str) -> bool {
+    let groups: Vec<&str> = text.split('-').collect();
+    groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+        && groups
+            .iter()
+            .all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Absolute URIs, which start with a scheme such as `https:`.
+fn is_uri(text: &str) -> bool {
+    match text.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                && !text.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn is_ipv4(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('.').collect();
+    parts.len() == 4
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part.len() <= 3
+                && !(part.len() > 1 && part.starts_with('0'))
+                && is_number_within(part, part.len(), 0, 255)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{resolve::Resolver, schema_check::check_instance, yaml::parse_yaml},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn test_check_instance() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+          format: int64
+        name:
+          type: string
+        status:
+          type: string
+          enum: [available, sold]
+        born:
+          type: string
+          format: date
+        owner:
+          $ref: '#/components/schemas/Owner'
+        tags:
+          type: array
+          items:
+            type: string
+    Owner:
+      oneOf:
+        - type: string
+          format: email
+        - type: integer
+"##;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = ContentProviderMap::from_map(contents);
+        let mut resolver = Resolver::new(&provider);
+        let root = PathBuf::from("#");
+        let pet = resolver
+            .resolve_pointer(&root, "/components/schemas/Pet")?
+            .unwrap()
+            .0;
+        let mut violations = |instance: &str| -> Vec<(String, String, usize)> {
+            let instance = parse_yaml(instance).unwrap();
+            check_instance(&mut resolver, &pet, &root, &instance)
+                .into_iter()
+                .map(|violation| (violation.path, violation.message, violation.line))
+                .collect()
+        };
+
+        let valid = "id: 1\nname: Rex\nstatus: sold\nborn: 2020-02-29\nowner: rex@example.com\ntags: [good]\n";
+        assert_eq!(violations(valid), vec![]);
+        let invalid = "id: one\nstatus: lost\nborn: 2020-13-01\nowner: [1]\ntags: [good, 2]\n";
+        assert_eq!(
+            violations(invalid),
+            vec![
+                (
+                    "$".to_string(),
+                    "`$` is missing the required property `name`".to_string(),
+                    1
+                ),
+                (
+                    "$.id".to_string(),
+                    "`$.id` should be of type `integer`, not `string`".to_string(),
+                    1
+                ),
+                (
+                    "$.status".to_string(),
+                    "`$.status` should be one of `available`, `sold`".to_string(),
+                    2
+                ),
+                (
+                    "$.born".to_string(),
+                    "`$.born` is not a valid `date`".to_string(),
+                    3
+                ),
+                (
+                    "$.owner".to_string(),
+                    "`$.owner` matches none of the `oneOf` schemas".to_string(),
+                    4
+                ),
+                (
+                    "$.tags[1]".to_string(),
+                    "`$.tags[1]` should be of type `string`, not `integer`".to_string(),
+                    5
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_formats() {
+        use super::matches_format;
+        let valid = |format: &str, text: &str| {
+            matches_format(format, &crate::bindings::yaml::YamlNode::string(text))
+        };
+        assert!(valid("date-time", "2024-02-29T12:30:00Z"));
+        assert!(valid("date-time", "2024-02-29T12:30:00.123+01:00"));
+        assert!(!valid("date-time", "2024-02-29 12:30:00"));
+        assert!(valid("uuid", "123e4567-e89b-12d3-a456-426614174000"));
+        assert!(!valid("uuid", "123e4567e89b12d3a456426614174000"));
+        assert!(valid("uri", "https://example.com/pets"));
+        assert!(!valid("uri", "/pets"));
+        assert!(valid("ipv4", "192.168.0.1"));
+        assert!(!valid("ipv4", "192.168.0.256"));
+        assert!(!valid("email", "not an email"));
+        assert!(valid("password", "anything"));
+    }
+}
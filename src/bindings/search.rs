@@ -0,0 +1,225 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    yaml::{YamlNode, YamlValue},
+};
+
+/// Where parameters can live, used to tell parameter objects apart from
+/// other mappings with a `name`.
+const PARAMETER_LOCATIONS: [&str; 4] = ["query", "header", "path", "cookie"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchKind {
+    Operation,
+    Path,
+    Schema,
+    Parameter,
+    Description,
+}
+
+impl SearchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchKind::Operation => "operation",
+            SearchKind::Path => "path",
+            SearchKind::Schema => "schema",
+            SearchKind::Parameter => "parameter",
+            SearchKind::Description => "description",
+        }
+    }
+}
+
+/// A name or description of the spec that `search` matches against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchEntry {
+    pub kind: SearchKind,
+    pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait SearchParser {
+    /// Every operationId, path, schema name, parameter name and description
+    /// in the files of the spec.
+    fn get_search_entries(&self) -> Result<Vec<SearchEntry>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterSearchParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterSearchParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn entry(&self, kind: SearchKind, text: &str, node: &YamlNode, file: &Path) -> SearchEntry {
+        SearchEntry {
+            kind,
+            text: text.to_string(),
+            file: self.provider.source_path(file),
+            line: node.start.row + 1,
+            column: node.start.column + 1,
+        }
+    }
+
+    /// The parameter names and descriptions below `node`. Examples are
+    /// skipped since their values are data rather than part of the API.
+    fn walk(&self, node: &YamlNode, file: &Path, entries: &mut Vec<SearchEntry>) {
+        match &node.value {
+            YamlValue::Mapping(_) => {
+                let parameter = node
+                    .get("in")
+                    .and_then(|location| location.as_str())
+                    .is_some_and(|location| PARAMETER_LOCATIONS.contains(&location));
+                if let (true, Some(name)) = (parameter, node.get("name")) {
+                    let text = name.as_str().unwrap_or_default();
+                    entries.push(self.entry(SearchKind::Parameter, text, name, file));
+                }
+                for pair in node.as_mapping().unwrap_or_default() {
+                    match (pair.key.as_str(), pair.value.as_str()) {
+                        (Some("example") | Some("examples"), _) => {}
+                        (Some("description"), Some(text)) if !pair.value.is_null() => entries
+                            .push(self.entry(SearchKind::Description, text, &pair.value, file)),
+                        _ => self.walk(&pair.value, file, entries),
+                    }
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for item in items {
+                    self.walk(item, file, entries);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn search_entries(&self) -> Result<Vec<SearchEntry>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut entries = vec![];
+        for operation in resolver.operations()? {
+            if let Some(id) = operation.operation.get("operationId") {
+                let text = id.as_str().unwrap_or_default();
+                entries.push(self.entry(SearchKind::Operation, text, id, &operation.file));
+            }
+        }
+        for path_item in resolver.entries("/paths")? {
+            let entry = self.entry(
+                SearchKind::Path,
+                &path_item.name,
+                &path_item.key,
+                &path_item.key_file,
+            );
+            entries.push(entry);
+        }
+        for schema in resolver.entries("/components/schemas")? {
+            let entry = self.entry(
+                SearchKind::Schema,
+                &schema.name,
+                &schema.key,
+                &schema.key_file,
+            );
+            entries.push(entry);
+        }
+        for file in resolver.files()? {
+            let document = resolver.document(&file)?.to_owned();
+            self.walk(&document, &file, &mut entries);
+        }
+        Ok(entries)
+    }
+}
+
+impl SearchParser for TreeSitterSearchParser {
+    fn get_search_entries(&self) -> Result<Vec<SearchEntry>, OpenapiSchemerError> {
+        self.search_entries()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Search))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::search::{SearchKind, SearchParser, TreeSitterSearchParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_search_entries() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      description: List all pets
+      parameters:
+        - $ref: 'parameters.yaml#/limit'
+      responses:
+        '200':
+          description: A page of pets
+          content:
+            application/json:
+              example:
+                description: Not part of the API
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        description:
+          type: string
+"#;
+        let parameters_content = r#"
+limit:
+  name: limit
+  in: query
+  description: How many pets to return
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("parameters.yaml"),
+                parameters_content.to_owned(),
+            ),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let entries = TreeSitterSearchParser::new(provider).get_search_entries()?;
+        let summary: Vec<(SearchKind, &str, &str, usize)> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.kind,
+                    entry.text.as_str(),
+                    entry.file.to_str().unwrap_or_default(),
+                    entry.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (SearchKind::Operation, "listPets", "#", 6),
+                (SearchKind::Path, "/pets", "#", 4),
+                (SearchKind::Schema, "Pet", "#", 19),
+                (SearchKind::Description, "List all pets", "#", 7),
+                (SearchKind::Description, "A page of pets", "#", 12),
+                (SearchKind::Parameter, "limit", "parameters.yaml", 3),
+                (
+                    SearchKind::Description,
+                    "How many pets to return",
+                    "parameters.yaml",
+                    5
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
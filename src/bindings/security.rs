@@ -0,0 +1,417 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode, OperationNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecuritySchemeNode {
+    pub text: String,
+    /// One of `apiKey`, `http`, `oauth2`, `openIdConnect` or `mutualTLS`.
+    pub scheme_type: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A scope declared by a flow of an oauth2 security scheme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeNode {
+    /// The security scheme declaring the scope.
+    pub scheme: String,
+    pub text: String,
+    pub description: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A security scheme named by a security requirement, with the scopes the
+/// requirement asks for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequiredScheme {
+    pub name: String,
+    /// The entry of `components/securitySchemes`, or `None` when nothing is
+    /// declared under that name.
+    pub scheme: Option<YamlNode>,
+    pub scopes: Vec<String>,
+}
+
+/// The security requirements that apply to an operation. Any one of
+/// `requirements` is enough to call the operation, and each needs all of
+/// its schemes.
+#[derive(Clone, Debug)]
+pub struct OperationSecurity {
+    pub operation: OperationNode,
+    pub requirements: Vec<Vec<RequiredScheme>>,
+    /// Whether the requirements come from the top level `security` because
+    /// the operation declares none of its own.
+    pub inherited: bool,
+}
+
+pub trait SecurityParser {
+    fn get_security_scheme_nodes(&self) -> Result<Vec<SecuritySchemeNode>, OpenapiSchemerError>;
+    /// Operations that can be called without any security requirement.
+    fn get_unsecured_operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError>;
+    /// Every operation with the security requirements that apply to it.
+    fn get_operation_security(&self) -> Result<Vec<OperationSecurity>, OpenapiSchemerError>;
+    /// The scopes of the flows of every oauth2 security scheme, once per
+    /// scheme when several flows declare the same scope.
+    fn get_declared_scopes(&self) -> Result<Vec<ScopeNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterSecurityParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterSecurityParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl SecurityParser for TreeSitterSecurityParser {
+    fn get_security_scheme_nodes(&self) -> Result<Vec<SecuritySchemeNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let entries = resolver
+            .entries("/components/securitySchemes")
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SecurityList)
+            })?;
+        let results = entries
+            .into_iter()
+            .map(|entry| SecuritySchemeNode {
+                scheme_type: entry
+                    .value
+                    .get("type")
+                    .and_then(|scheme_type| scheme_type.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                file: self.provider.source_path(&entry.key_file),
+                line: entry.key.start.row + 1,
+                column: entry.key.start.column + 1,
+                text: entry.name,
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_unsecured_operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SecurityAudit)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root = resolver
+            .document(&PathBuf::from("#"))
+            .map_err(to_error)?
+            .to_owned();
+        let operations = resolver.operations().map_err(to_error)?;
+
+        // An operation's `security` replaces the top level one entirely, so an
+        // empty list there opts the operation out. A requirement that is an
+        // empty object (`- {}`) makes security optional.
+        let is_secured = |security: &YamlNode| match security.as_sequence() {
+            Some(requirements) => {
+                !requirements.is_empty()
+                    && requirements
+                        .iter()
+                        .all(|requirement| !requirement.as_mapping().unwrap_or_default().is_empty())
+            }
+            None => false,
+        };
+        let results = operations
+            .into_iter()
+            .filter(|operation| match operation.operation.get("security") {
+                Some(security) => !is_secured(security),
+                None => !root.get("security").is_some_and(is_secured),
+            })
+            .map(|operation| {
+                let mut node = operation.node(self.provider.as_ref());
+                if node.text != operation.describe() {
+                    node.text = format!("{} ({})", operation.describe(), node.text);
+                }
+                node
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_operation_security(&self) -> Result<Vec<OperationSecurity>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SecurityAudit)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root = resolver
+            .document(&PathBuf::from("#"))
+            .map_err(to_error)?
+            .to_owned();
+        let schemes = resolver
+            .entries("/components/securitySchemes")
+            .map_err(to_error)?;
+        let operations = resolver.operations().map_err(to_error)?;
+        let requirements = |security: Option<&YamlNode>| -> Vec<Vec<RequiredScheme>> {
+            let requirements = security.and_then(|security| security.as_sequence());
+            requirements
+                .unwrap_or_default()
+                .iter()
+                .map(|requirement| {
+                    let pairs = requirement.as_mapping().unwrap_or_default();
+                    pairs
+                        .iter()
+                        .map(|pair| {
+                            let name = pair.key.as_str().unwrap_or_default().to_string();
+                            let scopes = pair.value.as_sequence().unwrap_or_default();
+                            RequiredScheme {
+                                scheme: schemes
+                                    .iter()
+                                    .find(|scheme| scheme.name == name)
+                                    .map(|scheme| scheme.value.to_owned()),
+                                scopes: scopes
+                                    .iter()
+                                    .filter_map(|scope| scope.as_str().map(str::to_owned))
+                                    .collect(),
+                                name,
+                            }
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        let results = operations
+            .into_iter()
+            .map(|operation| {
+                let own = operation.operation.get("security");
+                OperationSecurity {
+                    operation: operation.node(self.provider.as_ref()),
+                    requirements: requirements(own.or_else(|| root.get("security"))),
+                    inherited: own.is_none(),
+                }
+            })
+            .collect();
+        Ok(results)
+    }
+
+    fn get_declared_scopes(&self) -> Result<Vec<ScopeNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::SecurityScopes)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let schemes = resolver
+            .entries("/components/securitySchemes")
+            .map_err(to_error)?;
+        let mut scopes: Vec<ScopeNode> = vec![];
+        for scheme in schemes {
+            if scheme.value.get("type").and_then(|kind| kind.as_str()) != Some("oauth2") {
+                continue;
+            }
+            let flows = scheme
+                .value
+                .get("flows")
+                .and_then(|flows| flows.as_mapping())
+                .unwrap_or_default();
+            for flow in flows {
+                let declared = flow
+                    .value
+                    .get("scopes")
+                    .and_then(|scopes| scopes.as_mapping())
+                    .unwrap_or_default();
+                for pair in declared {
+                    let text = pair.key.as_str().unwrap_or_default().to_string();
+                    if scopes
+                        .iter()
+                        .any(|scope| scope.scheme == scheme.name && scope.text == text)
+                    {
+                        continue;
+                    }
+                    scopes.push(ScopeNode {
+                        scheme: scheme.name.to_owned(),
+                        description: pair.value.as_str().map(str::to_owned),
+                        file: self.provider.source_path(&scheme.file),
+                        line: pair.key.start.row + 1,
+                        column: pair.key.start.column + 1,
+                        text,
+                    });
+                }
+            }
+        }
+        Ok(scopes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::security::{SecurityParser, TreeSitterSecurityParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser(root_content: &str) -> TreeSitterSecurityParser {
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterSecurityParser::new(provider)
+    }
+
+    #[test]
+    fn get_security_scheme_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  securitySchemes:
+    ApiKey:
+      type: apiKey
+      in: header
+      name: X-API-Key
+    OAuth:
+      type: oauth2
+"#;
+        let nodes = parser(root_content).get_security_scheme_nodes()?;
+        let schemes: Vec<(&str, &str)> = nodes
+            .iter()
+            .map(|node| (node.text.as_str(), node.scheme_type.as_str()))
+            .collect();
+        assert_eq!(schemes, vec![("ApiKey", "apiKey"), ("OAuth", "oauth2")]);
+        assert_eq!((nodes[1].line, nodes[1].column), (8, 5));
+        Ok(())
+    }
+
+    #[test]
+    fn get_unsecured_operations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+security:
+  - ApiKey: []
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      operationId: createPets
+      security: []
+  /health:
+    get:
+      security:
+        - {}
+        - ApiKey: []
+"#;
+        let nodes = parser(root_content).get_unsecured_operations()?;
+        let texts: Vec<&str> = nodes.iter().map(|node| node.text.as_str()).collect();
+        assert_eq!(texts, vec!["POST /pets (createPets)", "GET /health"]);
+
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      operationId: createPets
+      security:
+        - ApiKey: []
+"#;
+        let nodes = parser(root_content).get_unsecured_operations()?;
+        let texts: Vec<&str> = nodes.iter().map(|node| node.text.as_str()).collect();
+        assert_eq!(texts, vec!["GET /pets (listPets)"]);
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation_security() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+security:
+  - ApiKey: []
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      operationId: createPets
+      security:
+        - OAuth: [write:pets, read:pets]
+          Missing: []
+        - {}
+components:
+  securitySchemes:
+    ApiKey:
+      type: apiKey
+      in: header
+      name: X-API-Key
+    OAuth:
+      type: oauth2
+"#;
+        let operations = parser(root_content).get_operation_security()?;
+        let summary: Vec<String> = operations
+            .iter()
+            .map(|operation| {
+                let requirements: Vec<String> = operation
+                    .requirements
+                    .iter()
+                    .map(|requirement| {
+                        let schemes: Vec<String> = requirement
+                            .iter()
+                            .map(|scheme| match &scheme.scheme {
+                                Some(_) => format!("{}{:?}", scheme.name, scheme.scopes),
+                                None => format!("{}?", scheme.name),
+                            })
+                            .collect();
+                        schemes.join(" + ")
+                    })
+                    .collect();
+                format!(
+                    "{} {}: {}",
+                    operation.operation.text,
+                    operation.inherited,
+                    requirements.join(" | ")
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "listPets true: ApiKey[]",
+                "createPets false: OAuth[\"write:pets\", \"read:pets\"] + Missing? | ",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_declared_scopes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+components:
+  securitySchemes:
+    ApiKey:
+      type: apiKey
+    OAuth:
+      type: oauth2
+      flows:
+        implicit:
+          scopes:
+            read:pets: Read your pets
+            write:pets: Modify pets
+        clientCredentials:
+          scopes:
+            read:pets: Read your pets
+            admin: {}
+"#;
+        let scopes = parser(root_content).get_declared_scopes()?;
+        let texts: Vec<(&str, &str, Option<&str>, usize)> = scopes
+            .iter()
+            .map(|scope| {
+                (
+                    scope.scheme.as_str(),
+                    scope.text.as_str(),
+                    scope.description.as_deref(),
+                    scope.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                ("OAuth", "read:pets", Some("Read your pets"), 11),
+                ("OAuth", "write:pets", Some("Modify pets"), 12),
+                ("OAuth", "admin", None, 16),
+            ]
+        );
+        Ok(())
+    }
+}
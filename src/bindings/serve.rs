@@ -0,0 +1,399 @@
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    yaml::{Scalar, ScalarStyle, YamlNode, YamlPair, YamlValue},
+};
+
+/// An operation the mock server answers, with every `$ref` in its responses
+/// inlined.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockRoute {
+    /// The method as written in the spec, such as `get`.
+    pub method: String,
+    /// The path template, such as `/pets/{petId}`.
+    pub path: String,
+    pub responses: YamlNode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Option<YamlNode>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16) -> Self {
+        MockResponse {
+            status,
+            content_type: None,
+            body: None,
+        }
+    }
+}
+
+pub trait ServeParser {
+    fn get_routes(&self) -> Result<Vec<MockRoute>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterServeParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterServeParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn routes(&self) -> Result<Vec<MockRoute>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut routes = vec![];
+        for operation in resolver.operations()? {
+            let responses = match operation.operation.get("responses") {
+                Some(responses) => resolver.inline(responses, &operation.file)?,
+                None => YamlNode::mapping(vec![]),
+            };
+            routes.push(MockRoute {
+                method: operation.method,
+                path: operation.path,
+                responses,
+            });
+        }
+        Ok(routes)
+    }
+}
+
+impl ServeParser for TreeSitterServeParser {
+    fn get_routes(&self) -> Result<Vec<MockRoute>, OpenapiSchemerError> {
+        self.routes()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Serve))
+    }
+}
+
+impl MockRoute {
+    /// The response to a request accepting the media ranges in `accept`.
+    /// The lowest success response is used, then `default`, then whichever
+    /// response is declared first. A response with content none of which is
+    /// acceptable is answered with 406.
+    pub fn respond(&self, accept: Option<&str>) -> MockResponse {
+        let responses = self.responses.as_mapping().unwrap_or_default();
+        let success = responses
+            .iter()
+            .filter_map(|pair| Some((status_code(pair.key.as_str()?)?, pair)))
+            .filter(|(status, _)| (200..300).contains(status))
+            .min_by_key(|(status, _)| *status);
+        let default = || {
+            responses
+                .iter()
+                .find(|pair| pair.key.as_str() == Some("default"))
+                .map(|pair| (200, pair))
+        };
+        let first = || {
+            responses
+                .first()
+                .map(|pair| (pair.key.as_str().and_then(status_code).unwrap_or(200), pair))
+        };
+        let (status, response) = match success.or_else(default).or_else(first) {
+            Some((status, pair)) => (status, &pair.value),
+            None => return MockResponse::new(200),
+        };
+        let content = match response
+            .get("content")
+            .and_then(|content| content.as_mapping())
+        {
+            Some(content) if !content.is_empty() => content,
+            _ => return MockResponse::new(status),
+        };
+        let media = match negotiate(content, accept) {
+            Some(media) => media,
+            None => return MockResponse::new(406),
+        };
+        MockResponse {
+            status,
+            content_type: media.key.as_str().map(str::to_owned),
+            body: media_body(&media.value),
+        }
+    }
+}
+
+/// `200` for `200`, and the lowest code of the range for `2XX`.
+fn status_code(key: &str) -> Option<u16> {
+    match key.strip_suffix("XX") {
+        Some(class) => class.parse::<u16>().ok().map(|class| class * 100),
+        None => key.parse().ok(),
+    }
+}
+
+/// The media type in `content` the request prefers, or the first one when
+/// the request has no `Accept` header. Quality values are not weighed, the
+/// ranges are tried in the order they were sent.
+fn negotiate<'a>(content: &'a [YamlPair], accept: Option<&str>) -> Option<&'a YamlPair> {
+    let accept = match accept {
+        Some(accept) if !accept.trim().is_empty() => accept,
+        _ => return content.first(),
+    };
+    accept
+        .split(',')
+        .map(|range| range.split(';').next().unwrap_or_default().trim())
+        .find_map(|range| {
+            content.iter().find(|pair| {
+                let media_type = pair.key.as_str().unwrap_or_default();
+                match range.strip_suffix("/*") {
+                    Some("*") => true,
+                    Some(kind) => media_type
+                        .split('/')
+                        .next()
+                        .is_some_and(|media_kind| media_kind.eq_ignore_ascii_case(kind)),
+                    None => media_type.eq_ignore_ascii_case(range),
+                }
+            })
+        })
+}
+
+/// The media type's `example`, the value of its first `examples` entry, or
+/// a value generated from its schema.
+pub fn media_body(media: &YamlNode) -> Option<YamlNode> {
+    if let Some(example) = media.get("example") {
+        return Some(example.to_owned());
+    }
+    let first_example = media
+        .get("examples")
+        .and_then(|examples| examples.as_mapping())
+        .and_then(|examples| examples.first())
+        .and_then(|example| example.value.get("value"));
+    if let Some(value) = first_example {
+        return Some(value.to_owned());
+    }
+    media.get("schema").map(generate)
+}
+
+/// A value that satisfies `schema`, preferring the schema's own `const`,
+/// `example`, `default` or first `enum` value. A `$ref` left in place because the
+/// schema is recursive generates `null`.
+pub fn generate(schema: &YamlNode) -> YamlNode {
+    for key in ["const", "example", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.to_owned();
+        }
+    }
+    let first = |key: &str| {
+        schema
+            .get(key)
+            .and_then(|items| items.as_sequence())
+            .and_then(|items| items.first())
+    };
+    if let Some(value) = first("enum") {
+        return value.to_owned();
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(|all_of| all_of.as_sequence()) {
+        let mut pairs = vec![];
+        for member in all_of {
+            if let YamlValue::Mapping(member_pairs) = generate(member).value {
+                pairs.extend(member_pairs);
+            }
+        }
+        return YamlNode::new(YamlValue::Mapping(pairs));
+    }
+    if let Some(member) = first("oneOf").or_else(|| first("anyOf")) {
+        return generate(member);
+    }
+    // A list of types, as OpenAPI 3.1 allows, generates its first type other
+    // than `null`.
+    let kind = schema
+        .get("type")
+        .and_then(|kind| match kind.as_sequence() {
+            Some(kinds) => kinds
+                .iter()
+                .filter_map(|kind| kind.as_str())
+                .find(|kind| *kind != "null"),
+            None => kind.as_str(),
+        });
+    let properties = schema
+        .get("properties")
+        .and_then(|properties| properties.as_mapping());
+    match (kind, properties) {
+        (Some("object"), _) | (None, Some(_)) => YamlNode::new(YamlValue::Mapping(
+            properties
+                .unwrap_or_default()
+                .iter()
+                .map(|pair| YamlPair {
+                    key: pair.key.to_owned(),
+                    value: generate(&pair.value),
+                })
+                .collect(),
+        )),
+        (Some("array"), _) => {
+            YamlNode::sequence(schema.get("items").map(generate).into_iter().collect())
+        }
+        (Some("string"), _) => {
+            let format = schema.get("format").and_then(|format| format.as_str());
+            YamlNode::string(match format {
+                Some("date-time") => "1970-01-01T00:00:00Z",
+                Some("date") => "1970-01-01",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                Some("email") => "user@example.com",
+                _ => "string",
+            })
+        }
+        (Some("integer"), _) => YamlNode::integer(0),
+        (Some("number"), _) => YamlNode::new(YamlValue::Scalar(Scalar {
+            text: "0.0".to_string(),
+            style: ScalarStyle::Plain,
+        })),
+        (Some("boolean"), _) => YamlNode::boolean(true),
+        _ => YamlNode::null(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{
+            serve::{generate, ServeParser, TreeSitterServeParser},
+            yaml::parse_yaml,
+        },
+        content::ContentProviderMap,
+    };
+
+    use super::MockRoute;
+
+    fn routes() -> Result<Vec<MockRoute>, Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      responses:
+        default:
+          description: unexpected error
+        '200':
+          description: A list of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Pet'
+            text/plain:
+              example: Rex
+  /pets/{petId}:
+    get:
+      responses:
+        2XX:
+          description: A pet
+          content:
+            application/json:
+              examples:
+                Rex:
+                  value:
+                    id: 1
+                    name: Rex
+    delete:
+      responses:
+        '204':
+          description: Deleted
+components:
+  schemas:
+    Pet:
+      $ref: 'Pet.yaml'
+"#;
+        let pet_content = r#"
+required:
+  - id
+properties:
+  id:
+    type: integer
+  name:
+    type: string
+  tag:
+    type: string
+    enum:
+      - dog
+      - cat
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("Pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        Ok(TreeSitterServeParser::new(provider).get_routes()?)
+    }
+
+    #[test]
+    fn get_routes() -> Result<(), Box<dyn Error>> {
+        let routes = routes()?;
+        let names: Vec<(&str, &str)> = routes
+            .iter()
+            .map(|route| (route.method.as_str(), route.path.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("get", "/pets"),
+                ("get", "/pets/{petId}"),
+                ("delete", "/pets/{petId}")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn respond() -> Result<(), Box<dyn Error>> {
+        let routes = routes()?;
+        let response = routes[0].respond(None);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type.as_deref(), Some("application/json"));
+        assert_eq!(
+            response.body.unwrap().to_json(),
+            "[\n  {\n    \"id\": 0,\n    \"name\": \"string\",\n    \"tag\": \"dog\"\n  }\n]"
+        );
+
+        let response = routes[0].respond(Some("text/html, text/*;q=0.8"));
+        assert_eq!(response.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(response.body.unwrap().as_str(), Some("Rex"));
+        assert_eq!(routes[0].respond(Some("image/png")).status, 406);
+
+        let response = routes[1].respond(Some("*/*"));
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body.unwrap().to_yaml(), "id: 1\nname: Rex\n");
+
+        let response = routes[2].respond(None);
+        assert_eq!((response.status, response.body), (204, None));
+        Ok(())
+    }
+
+    #[test]
+    fn generate_schema_values() -> Result<(), Box<dyn Error>> {
+        let schema = parse_yaml(
+            r#"
+allOf:
+  - properties:
+      id:
+        type: string
+        format: uuid
+  - properties:
+      price:
+        type: number
+      available:
+        type: [boolean, 'null']
+      kind:
+        const: pet
+      owner:
+        oneOf:
+          - type: object
+            properties:
+              name:
+                default: Sam
+"#,
+        )?;
+        assert_eq!(
+            generate(&schema).to_yaml(),
+            "id: 00000000-0000-0000-0000-000000000000\nprice: 0.0\navailable: true\nkind: pet\nowner:\n  name: Sam\n"
+        );
+        Ok(())
+    }
+}
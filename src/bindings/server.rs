@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerNode {
+    /// The server url with every variable replaced by its default.
+    pub text: String,
+    pub description: Option<String>,
+    /// The path (`/pets`) or operation (`GET /pets`) the server overrides the
+    /// top level servers for, or `None` for a top level server.
+    pub scope: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait ServerParser {
+    fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterServerParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterServerParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn push_servers(
+        &self,
+        servers: Option<&YamlNode>,
+        file: &Path,
+        scope: Option<String>,
+        results: &mut Vec<ServerNode>,
+    ) {
+        for server in servers
+            .and_then(|servers| servers.as_sequence())
+            .unwrap_or_default()
+        {
+            let url = match server.get("url") {
+                Some(url) => url,
+                None => continue,
+            };
+            results.push(ServerNode {
+                text: expand_variables(url.as_str().unwrap_or_default(), server),
+                description: server
+                    .get("description")
+                    .and_then(|description| description.as_str())
+                    .map(str::to_owned),
+                scope: scope.to_owned(),
+                file: self.provider.source_path(file),
+                line: url.start.row + 1,
+                column: url.start.column + 1,
+            });
+        }
+    }
+}
+
+/// Replaces each `{variable}` in `url` with the default from the server's
+/// `variables`. Variables without a default are left as they are.
+fn expand_variables(url: &str, server: &YamlNode) -> String {
+    let mut expanded = url.to_string();
+    for pair in server
+        .get("variables")
+        .and_then(|variables| variables.as_mapping())
+        .unwrap_or_default()
+    {
+        let name = pair.key.as_str().unwrap_or_default();
+        if let Some(default) = pair.value.get("default").and_then(|value| value.as_str()) {
+            expanded = expanded.replace(&format!("{{{}}}", name), default);
+        }
+    }
+    expanded
+}
+
+impl ServerParser for TreeSitterServerParser {
+    fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::ServerList)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let mut results: Vec<ServerNode> = vec![];
+
+        let root = resolver.document(&root_path).map_err(to_error)?.to_owned();
+        self.push_servers(root.get("servers"), &root_path, None, &mut results);
+        for path_item in resolver.entries("/paths").map_err(to_error)? {
+            self.push_servers(
+                path_item.value.get("servers"),
+                &path_item.file,
+                Some(path_item.name.to_owned()),
+                &mut results,
+            );
+        }
+        for operation in resolver.operations().map_err(to_error)? {
+            self.push_servers(
+                operation.operation.get("servers"),
+                &operation.file,
+                Some(operation.describe()),
+                &mut results,
+            );
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::server::{ServerParser, TreeSitterServerParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_server_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+servers:
+  - url: https://{environment}.example.com/{version}
+    description: Main API
+    variables:
+      environment:
+        default: api
+        enum:
+          - api
+          - staging
+      version:
+        default: v1
+paths:
+  /pets:
+    servers:
+      - url: https://pets.example.com
+    get:
+      operationId: listPets
+  /files:
+    $ref: 'files.yaml'
+"#;
+        let files_content = r#"
+post:
+  operationId: uploadFile
+  servers:
+    - url: https://uploads.example.com
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("files.yaml"), files_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterServerParser::new(provider);
+        let nodes = parser.get_server_nodes()?;
+        let servers: Vec<(&str, Option<&str>)> = nodes
+            .iter()
+            .map(|node| (node.text.as_str(), node.scope.as_deref()))
+            .collect();
+        assert_eq!(
+            servers,
+            vec![
+                ("https://api.example.com/v1", None),
+                ("https://pets.example.com", Some("/pets")),
+                ("https://uploads.example.com", Some("POST /files")),
+            ]
+        );
+        assert_eq!(nodes[0].description.as_deref(), Some("Main API"));
+        assert_eq!(nodes[2].file, PathBuf::from("files.yaml"));
+        assert_eq!((nodes[2].line, nodes[2].column), (5, 12));
+        Ok(())
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    collections::HashSet,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::Resolver,
+    yaml::{escape_pointer_segment, pointer_segments, YamlNode, YamlValue},
+};
+
+/// A file of a split spec.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitFile {
+    /// Where the file goes, relative to the directory the spec is split into.
+    pub path: PathBuf,
+    pub document: YamlNode,
+}
+
+pub trait Splitter {
+    /// The root document, followed by the files its path items and component
+    /// schemas are moved into. The root refers to them with `$ref`s.
+    fn split(&self) -> Result<Vec<SplitFile>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterSplitter {
+    provider: Box<dyn ContentProvider>,
+    root: PathBuf,
+}
+
+impl TreeSitterSplitter {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self {
+            provider,
+            root: PathBuf::from("openapi.yaml"),
+        }
+    }
+
+    /// Names the root document `root` instead of `openapi.yaml`. The other
+    /// files take its extension.
+    pub fn with_root(mut self, root: PathBuf) -> Self {
+        self.root = root;
+        self
+    }
+
+    fn split_files(&self) -> Result<Vec<SplitFile>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        if let Some(file) = resolver.files()?.get(1) {
+            bail!(
+                "Only a spec in a single file can be split, this one refers to `{}`",
+                self.provider.source_path(file).display()
+            );
+        }
+        let mut root = resolver.document(Path::new("#"))?.to_owned();
+        let extension = match self.root.extension() {
+            Some(extension) => extension.to_string_lossy().into_owned(),
+            None => "yaml".to_string(),
+        };
+        let mut split = Split {
+            root: self.root.to_owned(),
+            targets: vec![],
+        };
+        let mut taken = HashSet::new();
+        for (parent, directory) in [
+            (vec!["paths"], "paths"),
+            (vec!["components", "schemas"], "components/schemas"),
+        ] {
+            let entries = parent
+                .iter()
+                .try_fold(&root, |node, key| node.get(key))
+                .and_then(|node| node.as_mapping())
+                .unwrap_or_default();
+            for entry in entries {
+                let key = match entry.key.as_str() {
+                    Some(key) if entry.value.reference().is_none() => key,
+                    _ => continue,
+                };
+                let stem = file_stem(key);
+                let mut file = PathBuf::from(format!("{}/{}.{}", directory, stem, extension));
+                let mut number = 1;
+                while !taken.insert(file.to_owned()) {
+                    number += 1;
+                    file =
+                        PathBuf::from(format!("{}/{}_{}.{}", directory, stem, number, extension));
+                }
+                let mut pointer: Vec<String> = parent.iter().map(|key| key.to_string()).collect();
+                pointer.push(key.to_string());
+                split.targets.push(Target { pointer, file });
+            }
+        }
+
+        let mut files = vec![];
+        for target in &split.targets {
+            let node = match node_mut(&mut root, &target.pointer) {
+                Some(node) => node,
+                None => continue,
+            };
+            let reference = relative_path(&split.root, &target.file);
+            let reference = YamlNode::mapping(vec![("$ref", YamlNode::string(&reference))]);
+            let mut document = std::mem::replace(node, reference);
+            split.rewrite(&mut document, &target.file, None);
+            files.push(SplitFile {
+                path: target.file.to_owned(),
+                document,
+            });
+        }
+        split.rewrite(&mut root, &split.root, None);
+        files.insert(
+            0,
+            SplitFile {
+                path: split.root.to_owned(),
+                document: root,
+            },
+        );
+        Ok(files)
+    }
+}
+
+impl Splitter for TreeSitterSplitter {
+    fn split(&self) -> Result<Vec<SplitFile>, OpenapiSchemerError> {
+        self.split_files()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Split))
+    }
+}
+
+/// A path item or component schema moved into a file of its own.
+struct Target {
+    /// The keys leading to it in the root document.
+    pointer: Vec<String>,
+    file: PathBuf,
+}
+
+/// Bookkeeping for a single split run.
+struct Split {
+    /// The file the root document is written to.
+    root: PathBuf,
+    targets: Vec<Target>,
+}
+
+impl Split {
+    /// Points the refs below `node`, which is the value of the key `parent`
+    /// and is written to `file`, at wherever their targets are written.
+    fn rewrite(&self, node: &mut YamlNode, file: &Path, parent: Option<&str>) {
+        match &mut node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default().to_owned();
+                    let reference = match pair.value.as_str() {
+                        // Discriminator mappings hold refs, or names that are
+                        // left as they are.
+                        Some(value) if parent == Some("mapping") && value.contains('#') => value,
+                        Some(value) if key == "$ref" || key == "operationRef" => value,
+                        _ => {
+                            self.rewrite(&mut pair.value, file, Some(&key));
+                            continue;
+                        }
+                    };
+                    let rewritten = self.rewritten(reference, file);
+                    if rewritten != reference {
+                        let mut value = YamlNode::string(&rewritten);
+                        value.start = pair.value.start;
+                        value.bytes = pair.value.bytes.to_owned();
+                        pair.value = value;
+                    }
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for item in items {
+                    self.rewrite(item, file, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `reference`, written in the root document, as written in `file`.
+    fn rewritten(&self, reference: &str, file: &Path) -> String {
+        let fragment = match reference.strip_prefix('#') {
+            Some(fragment) => fragment,
+            // Only refs to URLs are left, which do not move.
+            None => return reference.to_string(),
+        };
+        let segments = pointer_segments(fragment).unwrap_or_default();
+        let target = self
+            .targets
+            .iter()
+            .find(|target| segments.starts_with(&target.pointer));
+        match target {
+            Some(target) => {
+                let mut reference = relative_path(file, &target.file);
+                let rest = &segments[target.pointer.len()..];
+                if !rest.is_empty() {
+                    let rest: Vec<String> = rest
+                        .iter()
+                        .map(|segment| escape_pointer_segment(segment))
+                        .collect();
+                    reference.push_str(&format!("#/{}", rest.join("/")));
+                }
+                reference
+            }
+            None if file == self.root => reference.to_string(),
+            None => format!("{}#{}", relative_path(file, &self.root), fragment),
+        }
+    }
+}
+
+/// The node at the keys `pointer` below `node`.
+fn node_mut<'a>(node: &'a mut YamlNode, pointer: &[String]) -> Option<&'a mut YamlNode> {
+    pointer.iter().try_fold(node, |node, key| node.get_mut(key))
+}
+
+/// The name of the file a path item or schema is written to, such as
+/// `pets_{petId}` for `/pets/{petId}`.
+fn file_stem(key: &str) -> String {
+    let stem: String = key
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || "-_.{}".contains(c) {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    match stem.is_empty() {
+        true => "root".to_string(),
+        false => stem,
+    }
+}
+
+/// The ref from the file `from` to the file `to`, both relative to the same
+/// directory.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let directory: Vec<Component> = from
+        .parent()
+        .map(|parent| parent.components().collect())
+        .unwrap_or_default();
+    let to: Vec<Component> = to.components().collect();
+    let common = directory
+        .iter()
+        .zip(&to)
+        .take_while(|(from, to)| from == to)
+        .count();
+    let mut parts = vec!["..".to_string(); directory.len() - common];
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::split::{relative_path, Splitter, TreeSitterSplitter},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn split() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          $ref: '#/components/responses/Pets'
+  /pets/{petId}:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet/properties/name'
+          links:
+            pets:
+              operationRef: '#/paths/~1pets/get'
+  /:
+    $ref: '#/paths/~1pets'
+components:
+  responses:
+    Pets:
+      description: OK
+      content:
+        application/json:
+          schema:
+            type: array
+            items:
+              $ref: '#/components/schemas/Pet'
+  schemas:
+    Pet:
+      type: object
+      discriminator:
+        propertyName: kind
+        mapping:
+          cat: '#/components/schemas/Cat'
+          dog: Dog
+      properties:
+        name:
+          type: string
+    Cat:
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+"##;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let splitter = TreeSitterSplitter::new(provider).with_root(PathBuf::from("spec.yaml"));
+        let files: Vec<(String, String)> = splitter
+            .split()?
+            .into_iter()
+            .map(|file| (file.path.display().to_string(), file.document.to_yaml()))
+            .collect();
+        let expected = vec![
+            (
+                "spec.yaml",
+                r#"openapi: 3.0.0
+paths:
+  /pets:
+    $ref: paths/pets.yaml
+  /pets/{petId}:
+    $ref: paths/pets_{petId}.yaml
+  /:
+    $ref: paths/pets.yaml
+components:
+  responses:
+    Pets:
+      description: OK
+      content:
+        application/json:
+          schema:
+            type: array
+            items:
+              $ref: components/schemas/Pet.yaml
+  schemas:
+    Pet:
+      $ref: components/schemas/Pet.yaml
+    Cat:
+      $ref: components/schemas/Cat.yaml
+"#,
+            ),
+            (
+                "paths/pets.yaml",
+                r#"get:
+  operationId: listPets
+  responses:
+    '200':
+      $ref: ../spec.yaml#/components/responses/Pets
+"#,
+            ),
+            (
+                "paths/pets_{petId}.yaml",
+                r#"get:
+  responses:
+    '200':
+      description: OK
+      content:
+        application/json:
+          schema:
+            $ref: ../components/schemas/Pet.yaml#/properties/name
+      links:
+        pets:
+          operationRef: pets.yaml#/get
+"#,
+            ),
+            (
+                "components/schemas/Pet.yaml",
+                r#"type: object
+discriminator:
+  propertyName: kind
+  mapping:
+    cat: Cat.yaml
+    dog: Dog
+properties:
+  name:
+    type: string
+"#,
+            ),
+            (
+                "components/schemas/Cat.yaml",
+                r#"allOf:
+  - $ref: Pet.yaml
+"#,
+            ),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .into_iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect();
+        assert_eq!(files, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn split_refuses_external_refs() {
+        let contents = HashMap::from([
+            (
+                PathBuf::from("#"),
+                "paths:\n  /pets:\n    $ref: 'pets.yaml'\n".to_owned(),
+            ),
+            (PathBuf::from("pets.yaml"), "get: {}\n".to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        assert_eq!(
+            TreeSitterSplitter::new(provider)
+                .split()
+                .unwrap_err()
+                .to_string(),
+            "Only a spec in a single file can be split, this one refers to `pets.yaml`"
+        );
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let path = |from: &str, to: &str| relative_path(&PathBuf::from(from), &PathBuf::from(to));
+        assert_eq!(path("spec.yaml", "paths/pets.yaml"), "paths/pets.yaml");
+        assert_eq!(path("paths/pets.yaml", "spec.yaml"), "../spec.yaml");
+        assert_eq!(path("paths/a.yaml", "paths/b.yaml"), "b.yaml");
+        assert_eq!(
+            path("paths/a.yaml", "components/schemas/Pet.yaml"),
+            "../components/schemas/Pet.yaml"
+        );
+    }
+}
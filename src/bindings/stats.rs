@@ -0,0 +1,241 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    resolve::{split_ref, Resolver},
+    validate::HTTP_METHODS,
+    yaml::{YamlNode, YamlValue},
+};
+
+/// Keys whose values are schemas nested in a schema.
+const NESTED_SCHEMAS: [&str; 3] = ["items", "additionalProperties", "not"];
+/// Keys whose values are mappings or lists of schemas nested in a schema.
+const NESTED_SCHEMA_GROUPS: [&str; 4] = ["properties", "allOf", "oneOf", "anyOf"];
+
+/// Counts describing the size and shape of a spec.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub paths: usize,
+    /// The number of operations per method, in the order methods are listed
+    /// in a path item. Methods without operations are left out.
+    pub methods: Vec<(String, usize)>,
+    /// Operations with a `description`.
+    pub described_operations: usize,
+    pub schemas: usize,
+    /// `$ref`s pointing into the file they are written in.
+    pub internal_refs: usize,
+    /// `$ref`s pointing into another file.
+    pub external_refs: usize,
+    /// Files reachable from the root document through `$ref`s, including it.
+    pub files: usize,
+    /// The component schema with the most levels of nested schemas, and how
+    /// many levels it has. Refs are inlined, and recursive schemas are
+    /// followed around once.
+    pub deepest_schema: Option<(String, usize)>,
+}
+
+impl Stats {
+    pub fn operations(&self) -> usize {
+        self.methods.iter().map(|(_, count)| count).sum()
+    }
+}
+
+pub trait StatsParser {
+    fn get_stats(&self) -> Result<Stats, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterStatsParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterStatsParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// How many levels of schemas `schema` is made of, counting itself.
+    fn depth(schema: &YamlNode) -> usize {
+        let mut nested = vec![];
+        for key in NESTED_SCHEMAS {
+            nested.extend(schema.get(key));
+        }
+        for key in NESTED_SCHEMA_GROUPS {
+            match schema.get(key).map(|group| &group.value) {
+                Some(YamlValue::Mapping(pairs)) => {
+                    nested.extend(pairs.iter().map(|pair| &pair.value))
+                }
+                Some(YamlValue::Sequence(items)) => nested.extend(items),
+                _ => {}
+            }
+        }
+        let deepest = nested
+            .into_iter()
+            .filter(|schema| schema.as_mapping().is_some() && schema.reference().is_none())
+            .map(Self::depth)
+            .max();
+        deepest.unwrap_or_default() + 1
+    }
+
+    /// Adds the `$ref`s below `node`, which lives in `file`, to the internal
+    /// and external counts.
+    fn count_refs(node: &YamlNode, file: &Path, stats: &mut Stats) {
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    match (pair.key.as_str(), pair.value.as_str()) {
+                        (Some("$ref"), Some(reference)) => {
+                            match split_ref(file, reference).0 == file {
+                                true => stats.internal_refs += 1,
+                                false => stats.external_refs += 1,
+                            }
+                        }
+                        _ => Self::count_refs(&pair.value, file, stats),
+                    }
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for item in items {
+                    Self::count_refs(item, file, stats);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn stats(&self) -> Result<Stats> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut stats = Stats {
+            paths: resolver.entries("/paths")?.len(),
+            ..Stats::default()
+        };
+
+        let operations = resolver.operations()?;
+        for method in HTTP_METHODS {
+            let count = operations
+                .iter()
+                .filter(|operation| operation.method == method)
+                .count();
+            if count > 0 {
+                stats.methods.push((method.to_string(), count));
+            }
+        }
+        stats.described_operations = operations
+            .iter()
+            .filter(|operation| {
+                operation
+                    .operation
+                    .get("description")
+                    .is_some_and(|description| !description.is_null())
+            })
+            .count();
+
+        let schemas = resolver.entries("/components/schemas")?;
+        stats.schemas = schemas.len();
+        for schema in schemas {
+            let depth = Self::depth(&resolver.inline(&schema.value, &schema.file)?);
+            let deeper = match &stats.deepest_schema {
+                Some((_, deepest)) => depth > *deepest,
+                None => true,
+            };
+            if deeper {
+                stats.deepest_schema = Some((schema.name, depth));
+            }
+        }
+
+        let files = resolver.files()?;
+        stats.files = files.len();
+        for file in files {
+            let document = resolver.document(&file)?.to_owned();
+            Self::count_refs(&document, &file, &mut stats);
+        }
+        Ok(stats)
+    }
+}
+
+impl StatsParser for TreeSitterStatsParser {
+    fn get_stats(&self) -> Result<Stats, OpenapiSchemerError> {
+        self.stats()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::stats::{Stats, StatsParser, TreeSitterStatsParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_stats() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      description: List all pets
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pets'
+    post:
+      responses:
+        '201':
+          description: Created
+  /pets/{petId}:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    Pets:
+      type: array
+      items:
+        $ref: 'pet.yaml'
+    Node:
+      type: object
+      properties:
+        children:
+          type: array
+          items:
+            $ref: '#/components/schemas/Node'
+"#;
+        let pet_content = r#"
+type: object
+properties:
+  name:
+    type: string
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let stats = TreeSitterStatsParser::new(provider).get_stats()?;
+        assert_eq!(
+            stats,
+            Stats {
+                paths: 2,
+                methods: vec![("get".to_string(), 2), ("post".to_string(), 1)],
+                described_operations: 1,
+                schemas: 2,
+                internal_refs: 2,
+                external_refs: 1,
+                files: 2,
+                deepest_schema: Some(("Node".to_string(), 4)),
+            }
+        );
+        assert_eq!(stats.operations(), 3);
+        Ok(())
+    }
+}
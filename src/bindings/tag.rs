@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, yaml::YamlNode, OperationNode};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagNode {
+    pub text: String,
+    pub description: Option<String>,
+    /// Whether the tag is listed under the top level `tags` key rather than
+    /// only being used by operations.
+    pub declared: bool,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait TagParser {
+    fn get_tag_nodes(&self) -> Result<Vec<TagNode>, OpenapiSchemerError>;
+    fn get_tag_operations(&self, tag: &str) -> Result<Vec<OperationNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterTagParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterTagParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn node(&self, text: &str, file: &Path, position: &YamlNode) -> TagNode {
+        TagNode {
+            text: text.to_string(),
+            description: None,
+            declared: false,
+            file: self.provider.source_path(file),
+            line: position.start.row + 1,
+            column: position.start.column + 1,
+        }
+    }
+}
+
+impl TagParser for TreeSitterTagParser {
+    fn get_tag_nodes(&self) -> Result<Vec<TagNode>, OpenapiSchemerError> {
+        let to_error = |error: anyhow::Error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::TagList)
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let root_path = PathBuf::from("#");
+        let mut results: Vec<TagNode> = vec![];
+
+        if let Some((tags, tags_file)) = resolver
+            .resolve_pointer(&root_path, "/tags")
+            .map_err(to_error)?
+        {
+            let (tags, tags_file) = resolver.follow(&tags, &tags_file).map_err(to_error)?;
+            for tag in tags.as_sequence().unwrap_or_default() {
+                let (tag, tag_file) = resolver.follow(tag, &tags_file).map_err(to_error)?;
+                let name = match tag.get("name") {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let mut node = self.node(name.as_str().unwrap_or_default(), &tag_file, name);
+                node.declared = true;
+                node.description = tag
+                    .get("description")
+                    .and_then(|description| description.as_str())
+                    .map(|description| description.trim_end().to_string());
+                results.push(node);
+            }
+        }
+
+        // Tags that are used without being declared are reported where they
+        // are first used.
+        for operation in resolver.operations().map_err(to_error)? {
+            let tags = match operation.operation.get("tags") {
+                Some(tags) => tags,
+                None => continue,
+            };
+            for tag in tags.as_sequence().unwrap_or_default() {
+                let name = tag.as_str().unwrap_or_default();
+                if !results.iter().any(|node| node.text == name) {
+                    results.push(self.node(name, &operation.file, tag));
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    fn get_tag_operations(&self, tag: &str) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let operations = resolver.operations().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::TagShow)
+        })?;
+        let results = operations
+            .into_iter()
+            .filter(|operation| operation.tags().iter().any(|name| name == tag))
+            .map(|operation| operation.node(self.provider.as_ref()))
+            .collect();
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::tag::{TagParser, TreeSitterTagParser},
+        content::ContentProviderMap,
+    };
+
+    fn parser() -> TreeSitterTagParser {
+        let root_content = r#"
+tags:
+  - name: pets
+    description: Everything about your pets
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      tags:
+        - pets
+    post:
+      tags:
+        - pets
+        - admin
+  /pets/{petId}:
+    $ref: 'pet.yaml'
+"#;
+        let pet_content = r#"
+get:
+  operationId: showPetById
+  tags:
+    - pets
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("pet.yaml"), pet_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        TreeSitterTagParser::new(provider)
+    }
+
+    #[test]
+    fn get_tag_nodes() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_tag_nodes()?;
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].text, "pets");
+        assert!(nodes[0].declared);
+        assert_eq!(
+            nodes[0].description.as_deref(),
+            Some("Everything about your pets")
+        );
+        assert_eq!((nodes[0].line, nodes[0].column), (3, 11));
+        assert_eq!(nodes[1].text, "admin");
+        assert!(!nodes[1].declared);
+        assert_eq!((nodes[1].line, nodes[1].column), (14, 11));
+        Ok(())
+    }
+
+    #[test]
+    fn get_tag_operations() -> Result<(), Box<dyn Error>> {
+        let nodes = parser().get_tag_operations("pets")?;
+        let texts: Vec<&str> = nodes.iter().map(|node| node.text.as_str()).collect();
+        assert_eq!(texts, vec!["listPets", "POST /pets", "showPetById"]);
+        assert_eq!(nodes[2].file, PathBuf::from("pet.yaml"));
+        assert_eq!(nodes[2].line, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,506 @@
+use super::yaml::{YamlNode, YamlValue};
+
+/// The helpers a tag such as `{{pascalCase name}}` can call on its argument.
+const HELPERS: [&str; 7] = [
+    "upperCase",
+    "lowerCase",
+    "pascalCase",
+    "camelCase",
+    "snakeCase",
+    "kebabCase",
+    "json",
+];
+
+/// A template in the subset of Handlebars that code generators need:
+/// `{{path}}` (or `{{{path}}}`, which is the same since nothing is
+/// HTML-escaped), the blocks `#each`, `#if`, `#unless` and `#with` with
+/// `{{else}}`, comments, `this`, `../`, `@root`, the `@index`, `@key`,
+/// `@first` and `@last` of `#each`, and the helpers in `HELPERS`. Block tags
+/// alone on their line don't leave an empty line behind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Value(Expression),
+    Block {
+        name: String,
+        argument: Expression,
+        body: Vec<Node>,
+        inverse: Vec<Node>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Expression {
+    helper: Option<String>,
+    argument: Argument,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Argument {
+    Path(String),
+    Literal(String),
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    /// The inside of a tag and the line it starts on.
+    Tag(String, usize),
+}
+
+impl Template {
+    pub fn new(source: &str) -> Result<Template, String> {
+        let tokens = standalone(tokenize(source)?);
+        let mut tokens = tokens.into_iter();
+        let (nodes, end) = parse(&mut tokens)?;
+        match end {
+            Some((tag, line)) => Err(format!("line {}: unexpected `{{{{{}}}}}`", line, tag)),
+            None => Ok(Template { nodes }),
+        }
+    }
+
+    pub fn render(&self, context: &YamlNode) -> String {
+        let mut output = String::new();
+        let mut frames = vec![Frame {
+            value: context.to_owned(),
+            data: None,
+        }];
+        render_nodes(&self.nodes, &mut frames, &mut output);
+        output
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = source;
+    let line = |rest: &str| source[..source.len() - rest.len()].matches('\n').count() + 1;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let tag = &rest[start..];
+        let (open, close) = match tag {
+            tag if tag.starts_with("{{{") => ("{{{", "}}}"),
+            tag if tag.starts_with("{{!--") => ("{{!--", "--}}"),
+            _ => ("{{", "}}"),
+        };
+        let end = tag[open.len()..]
+            .find(close)
+            .ok_or_else(|| format!("line {}: unclosed `{}`", line(tag), open))?;
+        let inside = &tag[open.len()..open.len() + end];
+        let inside = match open {
+            "{{!--" => format!("!{}", inside),
+            _ => inside.trim().to_string(),
+        };
+        tokens.push(Token::Tag(inside, line(tag)));
+        rest = &tag[open.len() + end + close.len()..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+/// Whether a tag only controls the output and writes nothing itself.
+fn is_control(tag: &str) -> bool {
+    tag.starts_with(['#', '/', '!']) || tag == "else"
+}
+
+/// Drops the indentation before and the line break after control tags that
+/// are alone on their line.
+fn standalone(tokens: Vec<Token>) -> Vec<Token> {
+    let text = |index: usize| match tokens.get(index) {
+        Some(Token::Text(text)) => Some(text.as_str()),
+        _ => None,
+    };
+    // How much to cut from the start and end of each text token
+    let mut cuts: Vec<(usize, usize)> = tokens
+        .iter()
+        .map(|token| match token {
+            Token::Text(text) => (0, text.len()),
+            Token::Tag(..) => (0, 0),
+        })
+        .collect();
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Tag(tag, _) if is_control(tag) => {}
+            _ => continue,
+        }
+        let before = match index.checked_sub(1) {
+            None => Some(0),
+            Some(previous) => text(previous).and_then(|before| {
+                let line_start = before.rfind('\n').map(|newline| newline + 1);
+                let line_start = match (line_start, previous) {
+                    (Some(line_start), _) => line_start,
+                    (None, 0) => 0,
+                    (None, _) => return None,
+                };
+                before[line_start..].trim().is_empty().then_some(line_start)
+            }),
+        };
+        let after = match tokens.get(index + 1) {
+            None => Some(0),
+            Some(_) => text(index + 1).and_then(|after| {
+                let line_end = after.find('\n').map(|newline| newline + 1);
+                let line_end = match line_end {
+                    Some(line_end) => line_end,
+                    None if index + 2 == tokens.len() => after.len(),
+                    None => return None,
+                };
+                after[..line_end].trim().is_empty().then_some(line_end)
+            }),
+        };
+        if let (Some(before), Some(after)) = (before, after) {
+            if index > 0 {
+                cuts[index - 1].1 = cuts[index - 1].1.min(before);
+            }
+            if index + 1 < tokens.len() {
+                cuts[index + 1].0 = cuts[index + 1].0.max(after);
+            }
+        }
+    }
+    tokens
+        .into_iter()
+        .zip(cuts)
+        .filter_map(|(token, (start, end))| match token {
+            Token::Text(text) if start < end => Some(Token::Text(text[start..end].to_string())),
+            Token::Text(_) => None,
+            tag => Some(tag),
+        })
+        .collect()
+}
+
+/// The nodes parsed and the closing or `else` tag that ended them, with its
+/// line.
+type Parsed = (Vec<Node>, Option<(String, usize)>);
+
+/// Parses nodes until the end of the template or a `{{/...}}` or `{{else}}`
+/// tag, which is returned with its line for the caller to check.
+fn parse(tokens: &mut std::vec::IntoIter<Token>) -> Result<Parsed, String> {
+    let mut nodes = vec![];
+    while let Some(token) = tokens.next() {
+        let (tag, line) = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag, line) => (tag, line),
+        };
+        if tag.starts_with('!') {
+            continue;
+        }
+        if tag.starts_with('/') || tag == "else" {
+            return Ok((nodes, Some((tag, line))));
+        }
+        let error = |message: String| format!("line {}: {}", line, message);
+        if let Some(open) = tag.strip_prefix('#') {
+            let (name, argument) = open.split_once(char::is_whitespace).unwrap_or((open, ""));
+            if !["each", "if", "unless", "with"].contains(&name) {
+                return Err(error(format!("unknown block `#{}`", name)));
+            }
+            let argument = expression(argument.trim()).map_err(error)?;
+            let (body, end) = parse(tokens)?;
+            let (inverse, end) = match end {
+                Some((end, _)) if end == "else" => parse(tokens)?,
+                end => (vec![], end),
+            };
+            match end {
+                Some((end, _)) if end.trim_start_matches('/').trim() == name => {}
+                _ => return Err(error(format!("unclosed `{{{{#{}}}}}`", name))),
+            }
+            nodes.push(Node::Block {
+                name: name.to_string(),
+                argument,
+                body,
+                inverse,
+            });
+            continue;
+        }
+        nodes.push(Node::Value(expression(&tag).map_err(error)?));
+    }
+    Ok((nodes, None))
+}
+
+fn expression(text: &str) -> Result<Expression, String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (helper, argument) = match words.as_slice() {
+        [argument] => (None, *argument),
+        [helper, argument] => {
+            if !HELPERS.contains(helper) {
+                return Err(format!("unknown helper `{}`", helper));
+            }
+            (Some(helper.to_string()), *argument)
+        }
+        _ => return Err(format!("expected a path but found `{}`", text)),
+    };
+    let literal = ['"', '\''].iter().find_map(|quote| {
+        argument
+            .strip_prefix(*quote)
+            .and_then(|argument| argument.strip_suffix(*quote))
+    });
+    let argument = match literal {
+        Some(literal) => Argument::Literal(literal.to_string()),
+        None => Argument::Path(argument.to_string()),
+    };
+    Ok(Expression { helper, argument })
+}
+
+struct Frame {
+    value: YamlNode,
+    /// The `@index`, `@key` and item count of the `#each` that pushed it.
+    data: Option<(usize, Option<String>, usize)>,
+}
+
+fn render_nodes(nodes: &[Node], frames: &mut Vec<Frame>, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Value(expression) => output.push_str(&evaluate(expression, frames)),
+            Node::Block {
+                name,
+                argument,
+                body,
+                inverse,
+            } => {
+                let value = match &argument.argument {
+                    Argument::Path(path) => lookup(path, frames),
+                    Argument::Literal(literal) => Some(YamlNode::string(literal)),
+                };
+                let truthy = value.as_ref().is_some_and(is_truthy);
+                match (name.as_str(), value) {
+                    ("each", Some(value)) if truthy => {
+                        let items: Vec<(Option<String>, YamlNode)> = match &value.value {
+                            YamlValue::Sequence(items) => {
+                                items.iter().map(|item| (None, item.to_owned())).collect()
+                            }
+                            YamlValue::Mapping(pairs) => pairs
+                                .iter()
+                                .map(|pair| {
+                                    let key = pair.key.as_str().map(str::to_owned);
+                                    (key, pair.value.to_owned())
+                                })
+                                .collect(),
+                            _ => vec![],
+                        };
+                        let count = items.len();
+                        for (index, (key, item)) in items.into_iter().enumerate() {
+                            frames.push(Frame {
+                                value: item,
+                                data: Some((index, key, count)),
+                            });
+                            render_nodes(body, frames, output);
+                            frames.pop();
+                        }
+                    }
+                    ("with", Some(value)) if truthy => {
+                        frames.push(Frame { value, data: None });
+                        render_nodes(body, frames, output);
+                        frames.pop();
+                    }
+                    ("if", _) if truthy => render_nodes(body, frames, output),
+                    ("unless", _) if !truthy => render_nodes(body, frames, output),
+                    _ => render_nodes(inverse, frames, output),
+                }
+            }
+        }
+    }
+}
+
+fn evaluate(expression: &Expression, frames: &[Frame]) -> String {
+    let value = match &expression.argument {
+        Argument::Path(path) => lookup(path, frames),
+        Argument::Literal(literal) => Some(YamlNode::string(literal)),
+    };
+    let value = match value {
+        Some(value) => value,
+        None => return String::new(),
+    };
+    let text = match (&value.value, value.is_null()) {
+        (_, true) => String::new(),
+        (YamlValue::Scalar(scalar), _) => scalar.text.to_owned(),
+        _ => value.to_json(),
+    };
+    match expression.helper.as_deref() {
+        Some("upperCase") => text.to_uppercase(),
+        Some("lowerCase") => text.to_lowercase(),
+        Some("pascalCase") => words(&text).iter().map(|word| capitalize(word)).collect(),
+        Some("camelCase") => {
+            let words = words(&text);
+            let mut camel = words.first().cloned().unwrap_or_default();
+            camel.extend(words.iter().skip(1).map(|word| capitalize(word)));
+            camel
+        }
+        Some("snakeCase") => words(&text).join("_"),
+        Some("kebabCase") => words(&text).join("-"),
+        Some("json") => value.to_json(),
+        _ => text,
+    }
+}
+
+/// The value at a path such as `name`, `schema.type`, `../name`, `this`,
+/// `@index` or `@root.title`.
+fn lookup(path: &str, frames: &[Frame]) -> Option<YamlNode> {
+    let data = frames.iter().rev().find_map(|frame| frame.data.as_ref());
+    match path {
+        "@index" => return data.map(|(index, _, _)| YamlNode::integer(*index)),
+        "@key" => {
+            return data.and_then(|(_, key, _)| key.as_deref().map(YamlNode::string));
+        }
+        "@first" => return data.map(|(index, _, _)| YamlNode::boolean(*index == 0)),
+        "@last" => {
+            return data.map(|(index, _, count)| YamlNode::boolean(index + 1 == *count));
+        }
+        _ => {}
+    }
+    let (mut depth, mut rest) = (frames.len() - 1, path);
+    if let Some(root) = rest.strip_prefix("@root") {
+        depth = 0;
+        rest = root.trim_start_matches('.');
+    }
+    while let Some(parent) = rest.strip_prefix("../") {
+        depth = depth.saturating_sub(1);
+        rest = parent;
+    }
+    let rest = match rest {
+        "this" | "." => "",
+        rest => rest.strip_prefix("this.").unwrap_or(rest),
+    };
+    let mut value = &frames[depth].value;
+    for segment in rest.split('.').filter(|segment| !segment.is_empty()) {
+        value = match &value.value {
+            YamlValue::Sequence(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => value.get(segment)?,
+        };
+    }
+    Some(value.to_owned())
+}
+
+/// Whether `#if` takes its body. Missing values, `null`, `false`, `0`,
+/// empty strings and empty lists are false.
+fn is_truthy(value: &YamlNode) -> bool {
+    match &value.value {
+        YamlValue::Sequence(items) => !items.is_empty(),
+        YamlValue::Mapping(_) => true,
+        YamlValue::Scalar(scalar) => match value.json_type() {
+            "null" => false,
+            "boolean" => scalar.text == "true",
+            "integer" | "number" => scalar.text.parse::<f64>().is_ok_and(|number| number != 0.0),
+            _ => !scalar.text.is_empty(),
+        },
+        _ => true,
+    }
+}
+
+/// The lowercase words of `text`, split at anything but letters and digits
+/// and where a lowercase letter is followed by an uppercase one.
+pub(crate) fn words(text: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric()) {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        previous = Some(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bindings::yaml::parse_yaml;
+
+    use super::Template;
+
+    #[test]
+    fn test_render() {
+        let context = parse_yaml(
+            r#"
+title: Petstore
+operations:
+  - operationId: listPets
+    method: get
+    parameters:
+      - name: limit
+      - name: page_size
+  - operationId: show-pet-by-id
+    method: get
+    parameters: []
+servers:
+  prod: https://example.com
+"#,
+        )
+        .unwrap();
+        let render = |source: &str| Template::new(source).unwrap().render(&context);
+        assert_eq!(
+            render(
+                r#"// {{title}}
+{{#each operations}}
+export function {{camelCase operationId}}({{#each parameters}}{{camelCase name}}{{#unless @last}}, {{/unless}}{{/each}}) {
+  {{!-- the method --}}
+  return "{{upperCase method}} {{@index}} {{@root.title}} {{../title}}";
+}
+{{/each}}
+"#
+            ),
+            r#"// Petstore
+export function listPets(limit, pageSize) {
+  return "GET 0 Petstore Petstore";
+}
+export function showPetById() {
+  return "GET 1 Petstore Petstore";
+}
+"#
+        );
+        assert_eq!(
+            render("{{#each servers}}{{@key}}={{this}}{{/each}}"),
+            "prod=https://example.com"
+        );
+        assert_eq!(
+            render("{{#if missing}}yes{{else}}no{{/if}} {{#with operations.0}}{{{snakeCase operationId}}}{{/with}}"),
+            "no list_pets"
+        );
+        assert_eq!(
+            render("{{#if operations.1.parameters}}some{{else}}none{{/if}}"),
+            "none"
+        );
+        assert_eq!(render("{{pascalCase 'pet-status'}}"), "PetStatus");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source: &str| Template::new(source).unwrap_err();
+        assert_eq!(
+            error("{{#each items}}\n{{name}}"),
+            "line 1: unclosed `{{#each}}`"
+        );
+        assert_eq!(error("a\n{{/if}}"), "line 2: unexpected `{{/if}}`");
+        assert_eq!(error("{{#loop items}}"), "line 1: unknown block `#loop`");
+        assert_eq!(error("{{shout name}}"), "line 1: unknown helper `shout`");
+        assert_eq!(error("{{name"), "line 1: unclosed `{{`");
+        assert_eq!(error("{{#if a}}{{/each}}"), "line 1: unclosed `{{#if}}`");
+    }
+}
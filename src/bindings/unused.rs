@@ -0,0 +1,226 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    child_pointer,
+    resolve::{identity, Resolver},
+    yaml::YamlNode,
+};
+
+/// The sections of `components` checked for entries nothing refers to.
+pub const COMPONENT_KINDS: [(&str, &str); 4] = [
+    ("schemas", "schema"),
+    ("parameters", "parameter"),
+    ("responses", "response"),
+    ("examples", "example"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnusedNode {
+    pub text: String,
+    /// The kind of component, such as `schema`.
+    pub kind: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait UnusedParser {
+    fn get_unused_components(&self) -> Result<Vec<UnusedNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterUnusedParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterUnusedParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Every node that a `$ref` reachable from `paths` or `webhooks` points
+    /// at, directly or through other refs.
+    fn reachable(&self, resolver: &mut Resolver) -> Result<HashSet<String>> {
+        let root_path = PathBuf::from("#");
+        let root = resolver.document(&root_path)?.to_owned();
+        let mut pending: Vec<(YamlNode, PathBuf)> = ["paths", "webhooks"]
+            .iter()
+            .filter_map(|key| root.get(key))
+            .map(|node| (node.to_owned(), root_path.to_owned()))
+            .collect();
+        let mut reached = HashSet::new();
+        while let Some((node, file)) = pending.pop() {
+            let refs: Vec<String> = node.references().into_iter().map(str::to_owned).collect();
+            for reference in refs {
+                // Broken refs are reported by `refs check`, here they simply
+                // don't reach anything.
+                let (target, target_file) = match resolver.resolve_ref(&file, &reference) {
+                    Ok(resolved) => resolved,
+                    Err(_) => continue,
+                };
+                if reached.insert(identity(self.provider.as_ref(), &target_file, &target)) {
+                    pending.push((target, target_file));
+                }
+            }
+        }
+        Ok(reached)
+    }
+
+    fn unused_components(&self) -> Result<Vec<UnusedNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let reached = self.reachable(&mut resolver)?;
+        let root_path = PathBuf::from("#");
+        let mut results = vec![];
+        for (section, kind) in COMPONENT_KINDS {
+            let pointer = format!("/components/{}", section);
+            for entry in resolver.entries(&pointer)? {
+                let component =
+                    resolver.resolve_pointer(&root_path, &child_pointer(&pointer, &entry.name))?;
+                let used = component.is_some_and(|(node, file)| {
+                    reached.contains(&identity(self.provider.as_ref(), &file, &node))
+                });
+                if !used {
+                    results.push(UnusedNode {
+                        text: entry.name,
+                        kind: kind.to_string(),
+                        file: self.provider.source_path(&entry.key_file),
+                        line: entry.key.start.row + 1,
+                        column: entry.key.start.column + 1,
+                    });
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+impl UnusedParser for TreeSitterUnusedParser {
+    fn get_unused_components(&self) -> Result<Vec<UnusedNode>, OpenapiSchemerError> {
+        self.unused_components()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Unused))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::unused::{TreeSitterUnusedParser, UnusedParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_unused_components() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+  /owners:
+    get:
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+webhooks:
+  newPet:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/NewPet'
+components:
+  schemas:
+    $ref: 'schemas.yaml'
+  parameters:
+    Limit:
+      name: limit
+      in: query
+    Offset:
+      name: offset
+      in: query
+  responses:
+    NotFound:
+      description: Not found
+  examples:
+    Cat:
+      value:
+        name: Tom
+"#;
+        let pets_content = r#"
+get:
+  responses:
+    '200':
+      content:
+        application/json:
+          schema:
+            $ref: '../schemas.yaml#/Pets'
+"#;
+        let schemas_content = r#"
+Pets:
+  type: array
+  items:
+    $ref: '#/Pet'
+Pet:
+  type: object
+NewPet:
+  type: object
+Owner:
+  type: object
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (
+                PathBuf::from("resources/pets.yaml"),
+                pets_content.to_owned(),
+            ),
+            (PathBuf::from("schemas.yaml"), schemas_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterUnusedParser::new(provider);
+        let nodes: Vec<(String, String, String, usize)> = parser
+            .get_unused_components()?
+            .into_iter()
+            .map(|node| {
+                (
+                    node.text,
+                    node.kind,
+                    node.file.display().to_string(),
+                    node.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                (
+                    "Owner".to_string(),
+                    "schema".to_string(),
+                    "schemas.yaml".to_string(),
+                    10
+                ),
+                (
+                    "Offset".to_string(),
+                    "parameter".to_string(),
+                    "#".to_string(),
+                    24
+                ),
+                (
+                    "NotFound".to_string(),
+                    "response".to_string(),
+                    "#".to_string(),
+                    28
+                ),
+                (
+                    "Cat".to_string(),
+                    "example".to_string(),
+                    "#".to_string(),
+                    31
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
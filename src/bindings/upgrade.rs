@@ -0,0 +1,806 @@
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    validate::{SpecVersion, HTTP_METHODS},
+    yaml::{YamlNode, YamlPair, YamlValue},
+};
+
+/// The media type assumed when neither the document nor the operation
+/// declares `consumes` or `produces`.
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+/// Keys of a Swagger 2.0 parameter that stay on the OpenAPI 3 parameter,
+/// every other key describes its value and moves into `schema`.
+const PARAMETER_KEYS: [&str; 5] = ["name", "in", "description", "required", "allowEmptyValue"];
+/// Where the components of a Swagger 2.0 document live in OpenAPI 3.
+const REF_PREFIXES: [(&str, &str); 3] = [
+    ("#/definitions/", "#/components/schemas/"),
+    ("#/parameters/", "#/components/parameters/"),
+    ("#/responses/", "#/components/responses/"),
+];
+
+pub trait Upgrader {
+    /// The root document converted from Swagger 2.0 to OpenAPI 3.0.
+    fn upgrade(&self) -> Result<YamlNode, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterUpgrader {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterUpgrader {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl Upgrader for TreeSitterUpgrader {
+    fn upgrade(&self) -> Result<YamlNode, OpenapiSchemerError> {
+        let root = self
+            .provider
+            .get_document(PathBuf::from("#"))
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Upgrade)
+            })?;
+        match SpecVersion::detect(&root) {
+            Some(SpecVersion::V2_0) => Ok(upgrade_document(&root)),
+            _ => Err(OpenapiSchemerError::Upgrade(
+                "Expected a Swagger 2.0 document with `swagger: \"2.0\"`".to_string(),
+            )),
+        }
+    }
+}
+
+/// Converts a Swagger 2.0 document to OpenAPI 3.0. Keys are kept in the
+/// order they were written in wherever OpenAPI 3 has a place for them. Only
+/// local `$ref`s are rewritten, files a ref points into are not converted.
+pub fn upgrade_document(root: &YamlNode) -> YamlNode {
+    let upgrade = Upgrade {
+        root,
+        consumes: media_types(root.get("consumes")),
+        produces: media_types(root.get("produces")),
+    };
+    let mut pairs = vec![pair("openapi", YamlNode::string("3.0.3"))];
+    if let Some(info) = root.get("info") {
+        pairs.push(pair("info", info.to_owned()));
+    }
+    if let Some(servers) = servers(root) {
+        pairs.push(pair("servers", servers));
+    }
+    for root_pair in root.as_mapping().unwrap_or_default() {
+        let key = root_pair.key.as_str().unwrap_or_default();
+        match key {
+            "paths" => pairs.push(pair("paths", upgrade.paths(&root_pair.value))),
+            "tags" | "security" | "externalDocs" => pairs.push(root_pair.to_owned()),
+            _ if key.starts_with("x-") => pairs.push(root_pair.to_owned()),
+            _ => {}
+        }
+    }
+    let components = upgrade.components();
+    if !components.is_empty() {
+        pairs.push(pair("components", mapping(components)));
+    }
+    let mut document = mapping(pairs);
+    rewrite_refs(&mut document);
+    document
+}
+
+struct Upgrade<'a> {
+    root: &'a YamlNode,
+    consumes: Vec<String>,
+    produces: Vec<String>,
+}
+
+impl Upgrade<'_> {
+    fn paths(&self, paths: &YamlNode) -> YamlNode {
+        let pairs = paths
+            .as_mapping()
+            .unwrap_or_default()
+            .iter()
+            .map(|path| YamlPair {
+                key: path.key.to_owned(),
+                value: self.path_item(&path.value),
+            })
+            .collect();
+        mapping(pairs)
+    }
+
+    /// Body and form parameters shared by a path item become part of the
+    /// request body of each of its operations.
+    fn path_item(&self, path_item: &YamlNode) -> YamlNode {
+        let shared = self.parameters(path_item.get("parameters"));
+        let (shared, shared_body): (Vec<YamlNode>, Vec<YamlNode>) = shared
+            .into_iter()
+            .partition(|parameter| !is_body(parameter));
+        let mut pairs = vec![];
+        for path_pair in path_item.as_mapping().unwrap_or_default() {
+            let key = path_pair.key.as_str().unwrap_or_default();
+            if HTTP_METHODS.contains(&key) {
+                let operation = self.operation(&path_pair.value, &shared_body);
+                pairs.push(YamlPair {
+                    key: path_pair.key.to_owned(),
+                    value: operation,
+                });
+            } else if key == "parameters" {
+                if !shared.is_empty() {
+                    let parameters = shared.iter().map(|parameter| self.parameter(parameter));
+                    pairs.push(pair("parameters", YamlNode::sequence(parameters.collect())));
+                }
+            } else {
+                pairs.push(path_pair.to_owned());
+            }
+        }
+        mapping(pairs)
+    }
+
+    fn operation(&self, operation: &YamlNode, shared_body: &[YamlNode]) -> YamlNode {
+        let consumes = match operation.get("consumes") {
+            Some(consumes) => media_types(Some(consumes)),
+            None => self.consumes.to_owned(),
+        };
+        let produces = match operation.get("produces") {
+            Some(produces) => media_types(Some(produces)),
+            None => self.produces.to_owned(),
+        };
+        let mut body = shared_body.to_vec();
+        let mut parameters = vec![];
+        for parameter in self.parameters(operation.get("parameters")) {
+            match is_body(&parameter) {
+                true => body.push(parameter),
+                false => parameters.push(self.parameter(&parameter)),
+            }
+        }
+        let mut request_body = self.request_body(&body, &consumes);
+
+        let mut pairs = vec![];
+        for operation_pair in operation.as_mapping().unwrap_or_default() {
+            let key = operation_pair.key.as_str().unwrap_or_default();
+            match key {
+                "consumes" | "produces" | "schemes" => {}
+                "parameters" => {
+                    if !parameters.is_empty() {
+                        pairs.push(pair(
+                            "parameters",
+                            YamlNode::sequence(parameters.to_owned()),
+                        ));
+                    }
+                    if let Some(request_body) = request_body.take() {
+                        pairs.push(pair("requestBody", request_body));
+                    }
+                }
+                "responses" => {
+                    if let Some(request_body) = request_body.take() {
+                        pairs.push(pair("requestBody", request_body));
+                    }
+                    pairs.push(pair(
+                        "responses",
+                        self.responses(&operation_pair.value, &produces),
+                    ));
+                }
+                _ => pairs.push(operation_pair.to_owned()),
+            }
+        }
+        if let Some(request_body) = request_body {
+            pairs.push(pair("requestBody", request_body));
+        }
+        mapping(pairs)
+    }
+
+    /// The parameters in `parameters`, where a `$ref` to a body or form
+    /// parameter is replaced by the parameter itself since OpenAPI 3 has no
+    /// such parameters to refer to.
+    fn parameters(&self, parameters: Option<&YamlNode>) -> Vec<YamlNode> {
+        let parameters = parameters
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default();
+        parameters
+            .iter()
+            .map(|parameter| {
+                let referenced = parameter
+                    .reference()
+                    .and_then(|reference| reference.strip_prefix("#/parameters/"))
+                    .and_then(|name| self.root.get("parameters")?.child(name));
+                match referenced {
+                    Some(referenced) if is_body(referenced) => referenced.to_owned(),
+                    _ => parameter.to_owned(),
+                }
+            })
+            .collect()
+    }
+
+    /// A path, query, header or cookie parameter with its type moved into
+    /// `schema` and its `collectionFormat` turned into a `style`.
+    fn parameter(&self, parameter: &YamlNode) -> YamlNode {
+        if parameter.reference().is_some() {
+            return parameter.to_owned();
+        }
+        let mut pairs = vec![];
+        let mut schema = vec![];
+        for parameter_pair in parameter.as_mapping().unwrap_or_default() {
+            let key = parameter_pair.key.as_str().unwrap_or_default();
+            if PARAMETER_KEYS.contains(&key) || key.starts_with("x-") {
+                pairs.push(parameter_pair.to_owned());
+            } else if key != "collectionFormat" {
+                schema.push(parameter_pair.to_owned());
+            }
+        }
+        let is_array = parameter.get("type").and_then(|kind| kind.as_str()) == Some("array");
+        let location = parameter.get("in").and_then(|location| location.as_str());
+        let format = parameter
+            .get("collectionFormat")
+            .and_then(|format| format.as_str())
+            .unwrap_or("csv");
+        let style = match (is_array, location, format) {
+            (true, Some("query"), "csv") => Some(("form", false)),
+            (true, Some("query"), "ssv") => Some(("spaceDelimited", false)),
+            (true, Some("query"), "pipes") => Some(("pipeDelimited", false)),
+            (true, Some("query"), "multi") => Some(("form", true)),
+            _ => None,
+        };
+        if let Some((style, explode)) = style {
+            pairs.push(pair("style", YamlNode::string(style)));
+            pairs.push(pair("explode", YamlNode::boolean(explode)));
+        }
+        if !schema.is_empty() {
+            pairs.push(pair("schema", upgrade_schema(&mapping(schema))));
+        }
+        mapping(pairs)
+    }
+
+    /// The request body described by `body` and `formData` parameters. A
+    /// body parameter wins over form parameters, since Swagger 2.0 doesn't
+    /// allow both.
+    fn request_body(&self, parameters: &[YamlNode], consumes: &[String]) -> Option<YamlNode> {
+        let location = |parameter: &YamlNode| {
+            parameter
+                .get("in")
+                .and_then(|location| location.as_str())
+                .map(str::to_owned)
+        };
+        if let Some(body) = parameters
+            .iter()
+            .find(|parameter| location(parameter).as_deref() == Some("body"))
+        {
+            let schema = body
+                .get("schema")
+                .map(upgrade_schema)
+                .unwrap_or_else(|| mapping(vec![]));
+            let content = consumes
+                .iter()
+                .map(|media_type| {
+                    pair(media_type, mapping(vec![pair("schema", schema.to_owned())]))
+                })
+                .collect();
+            let mut pairs = vec![];
+            if let Some(description) = body.get("description") {
+                pairs.push(pair("description", description.to_owned()));
+            }
+            pairs.push(pair("content", mapping(content)));
+            if let Some(required) = body.get("required") {
+                pairs.push(pair("required", required.to_owned()));
+            }
+            return Some(mapping(pairs));
+        }
+
+        let form: Vec<&YamlNode> = parameters
+            .iter()
+            .filter(|parameter| location(parameter).as_deref() == Some("formData"))
+            .collect();
+        if form.is_empty() {
+            return None;
+        }
+        let has_file = form
+            .iter()
+            .any(|parameter| parameter.get("type").and_then(|kind| kind.as_str()) == Some("file"));
+        let media_type =
+            match has_file || consumes.iter().any(|media| media == "multipart/form-data") {
+                true => "multipart/form-data",
+                false => "application/x-www-form-urlencoded",
+            };
+        let mut properties = vec![];
+        let mut required = vec![];
+        for parameter in &form {
+            let name = parameter
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default();
+            let schema: Vec<YamlPair> = parameter
+                .as_mapping()
+                .unwrap_or_default()
+                .iter()
+                .filter(|parameter_pair| {
+                    let key = parameter_pair.key.as_str().unwrap_or_default();
+                    key == "description" || !PARAMETER_KEYS.contains(&key)
+                })
+                .filter(|parameter_pair| parameter_pair.key.as_str() != Some("collectionFormat"))
+                .cloned()
+                .collect();
+            properties.push(pair(name, upgrade_schema(&mapping(schema))));
+            if parameter
+                .get("required")
+                .and_then(|required| required.as_str())
+                == Some("true")
+            {
+                required.push(YamlNode::string(name));
+            }
+        }
+        let mut schema = vec![
+            pair("type", YamlNode::string("object")),
+            pair("properties", mapping(properties)),
+        ];
+        if !required.is_empty() {
+            schema.push(pair("required", YamlNode::sequence(required)));
+        }
+        let content = mapping(vec![pair(
+            media_type,
+            mapping(vec![pair("schema", mapping(schema))]),
+        )]);
+        Some(mapping(vec![pair("content", content)]))
+    }
+
+    fn responses(&self, responses: &YamlNode, produces: &[String]) -> YamlNode {
+        let pairs = responses
+            .as_mapping()
+            .unwrap_or_default()
+            .iter()
+            .map(|response| YamlPair {
+                key: response.key.to_owned(),
+                value: self.response(&response.value, produces),
+            })
+            .collect();
+        mapping(pairs)
+    }
+
+    /// A response with its `schema` and `examples` moved into `content`,
+    /// one entry per media type the operation produces.
+    fn response(&self, response: &YamlNode, produces: &[String]) -> YamlNode {
+        if response.reference().is_some() {
+            return response.to_owned();
+        }
+        let examples = response
+            .get("examples")
+            .and_then(|examples| examples.as_mapping())
+            .unwrap_or_default();
+        let mut media_types = produces.to_vec();
+        for example in examples {
+            let media_type = example.key.as_str().unwrap_or_default();
+            if !media_types.iter().any(|known| known == media_type) {
+                media_types.push(media_type.to_string());
+            }
+        }
+        let schema = response.get("schema").map(upgrade_schema);
+        let content: Vec<YamlPair> = match schema.is_some() || !examples.is_empty() {
+            true => media_types
+                .iter()
+                .map(|media_type| {
+                    let mut media = vec![];
+                    if let Some(schema) = &schema {
+                        media.push(pair("schema", schema.to_owned()));
+                    }
+                    if let Some(example) = examples
+                        .iter()
+                        .find(|example| example.key.as_str() == Some(media_type))
+                    {
+                        media.push(pair("example", example.value.to_owned()));
+                    }
+                    pair(media_type, mapping(media))
+                })
+                .collect(),
+            false => vec![],
+        };
+
+        let mut pairs = vec![];
+        for response_pair in response.as_mapping().unwrap_or_default() {
+            match response_pair.key.as_str().unwrap_or_default() {
+                "schema" | "examples" => {}
+                "headers" => pairs.push(pair("headers", headers(&response_pair.value))),
+                _ => pairs.push(response_pair.to_owned()),
+            }
+        }
+        if !content.is_empty() {
+            pairs.push(pair("content", mapping(content)));
+        }
+        mapping(pairs)
+    }
+
+    /// `definitions`, the reusable path, query, header and cookie
+    /// `parameters`, `responses` and `securityDefinitions`, under their
+    /// OpenAPI 3 names. Body and form parameters are left out, they are
+    /// inlined wherever they are used.
+    fn components(&self) -> Vec<YamlPair> {
+        let entries = |key: &str| {
+            self.root
+                .get(key)
+                .and_then(|entries| entries.as_mapping())
+                .unwrap_or_default()
+        };
+        let mut components = vec![];
+        let schemas: Vec<YamlPair> = entries("definitions")
+            .iter()
+            .map(|schema| YamlPair {
+                key: schema.key.to_owned(),
+                value: upgrade_schema(&schema.value),
+            })
+            .collect();
+        if !schemas.is_empty() {
+            components.push(pair("schemas", mapping(schemas)));
+        }
+        let parameters: Vec<YamlPair> = entries("parameters")
+            .iter()
+            .filter(|parameter| !is_body(&parameter.value))
+            .map(|parameter| YamlPair {
+                key: parameter.key.to_owned(),
+                value: self.parameter(&parameter.value),
+            })
+            .collect();
+        if !parameters.is_empty() {
+            components.push(pair("parameters", mapping(parameters)));
+        }
+        let responses: Vec<YamlPair> = entries("responses")
+            .iter()
+            .map(|response| YamlPair {
+                key: response.key.to_owned(),
+                value: self.response(&response.value, &self.produces),
+            })
+            .collect();
+        if !responses.is_empty() {
+            components.push(pair("responses", mapping(responses)));
+        }
+        let schemes: Vec<YamlPair> = entries("securityDefinitions")
+            .iter()
+            .map(|scheme| YamlPair {
+                key: scheme.key.to_owned(),
+                value: security_scheme(&scheme.value),
+            })
+            .collect();
+        if !schemes.is_empty() {
+            components.push(pair("securitySchemes", mapping(schemes)));
+        }
+        components
+    }
+}
+
+/// `servers` built from `schemes`, `host` and `basePath`. Without `schemes`
+/// the servers are assumed to speak https.
+fn servers(root: &YamlNode) -> Option<YamlNode> {
+    let host = root.get("host").and_then(|host| host.as_str());
+    let base_path = root
+        .get("basePath")
+        .and_then(|base_path| base_path.as_str())
+        .unwrap_or_default();
+    let urls = match host {
+        Some(host) => {
+            let schemes = strings(root.get("schemes"));
+            let schemes = match schemes.is_empty() {
+                true => vec!["https".to_string()],
+                false => schemes,
+            };
+            schemes
+                .iter()
+                .map(|scheme| format!("{}://{}{}", scheme, host, base_path))
+                .collect()
+        }
+        None if !base_path.is_empty() => vec![base_path.to_string()],
+        None => return None,
+    };
+    let servers = urls
+        .iter()
+        .map(|url| mapping(vec![pair("url", YamlNode::string(url))]))
+        .collect();
+    Some(YamlNode::sequence(servers))
+}
+
+fn headers(headers: &YamlNode) -> YamlNode {
+    let pairs = headers
+        .as_mapping()
+        .unwrap_or_default()
+        .iter()
+        .map(|header| {
+            let mut pairs = vec![];
+            let mut schema = vec![];
+            for header_pair in header.value.as_mapping().unwrap_or_default() {
+                let key = header_pair.key.as_str().unwrap_or_default();
+                if key == "description" || key.starts_with("x-") {
+                    pairs.push(header_pair.to_owned());
+                } else if key != "collectionFormat" {
+                    schema.push(header_pair.to_owned());
+                }
+            }
+            if !schema.is_empty() {
+                pairs.push(pair("schema", upgrade_schema(&mapping(schema))));
+            }
+            YamlPair {
+                key: header.key.to_owned(),
+                value: mapping(pairs),
+            }
+        })
+        .collect();
+    mapping(pairs)
+}
+
+fn security_scheme(scheme: &YamlNode) -> YamlNode {
+    let kind = scheme.get("type").and_then(|kind| kind.as_str());
+    let text = |key: &str| scheme.get(key).map(|value| value.to_owned());
+    let mut pairs = vec![];
+    match kind {
+        Some("basic") => {
+            pairs.push(pair("type", YamlNode::string("http")));
+            pairs.push(pair("scheme", YamlNode::string("basic")));
+        }
+        Some("oauth2") => {
+            pairs.push(pair("type", YamlNode::string("oauth2")));
+            let flow = match scheme.get("flow").and_then(|flow| flow.as_str()) {
+                Some("application") => "clientCredentials",
+                Some("accessCode") => "authorizationCode",
+                Some(flow) => flow,
+                None => "implicit",
+            };
+            let mut flow_pairs = vec![];
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = text(key) {
+                    flow_pairs.push(pair(key, url));
+                }
+            }
+            flow_pairs.push(pair(
+                "scopes",
+                text("scopes").unwrap_or_else(|| mapping(vec![])),
+            ));
+            pairs.push(pair(
+                "flows",
+                mapping(vec![pair(flow, mapping(flow_pairs))]),
+            ));
+        }
+        _ => {
+            for scheme_pair in scheme.as_mapping().unwrap_or_default() {
+                let key = scheme_pair.key.as_str().unwrap_or_default();
+                if key != "description" && !key.starts_with("x-") {
+                    pairs.push(scheme_pair.to_owned());
+                }
+            }
+        }
+    }
+    for scheme_pair in scheme.as_mapping().unwrap_or_default() {
+        let key = scheme_pair.key.as_str().unwrap_or_default();
+        if key == "description" || key.starts_with("x-") {
+            pairs.push(scheme_pair.to_owned());
+        }
+    }
+    mapping(pairs)
+}
+
+/// A Swagger 2.0 schema in OpenAPI 3.0 terms: `type: file` becomes a binary
+/// string, `x-nullable` becomes `nullable` and a `discriminator` naming a
+/// property becomes a discriminator object.
+fn upgrade_schema(schema: &YamlNode) -> YamlNode {
+    let pairs = match schema.as_mapping() {
+        Some(pairs) => pairs,
+        None => return schema.to_owned(),
+    };
+    let mut upgraded = vec![];
+    for schema_pair in pairs {
+        let key = schema_pair.key.as_str().unwrap_or_default();
+        let value = &schema_pair.value;
+        match key {
+            "type" if value.as_str() == Some("file") => {
+                upgraded.push(pair("type", YamlNode::string("string")));
+                upgraded.push(pair("format", YamlNode::string("binary")));
+            }
+            "x-nullable" => upgraded.push(pair("nullable", value.to_owned())),
+            "discriminator" if value.as_str().is_some() => upgraded.push(pair(
+                "discriminator",
+                mapping(vec![pair("propertyName", value.to_owned())]),
+            )),
+            "properties" => upgraded.push(YamlPair {
+                key: schema_pair.key.to_owned(),
+                value: mapping(
+                    value
+                        .as_mapping()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|property| YamlPair {
+                            key: property.key.to_owned(),
+                            value: upgrade_schema(&property.value),
+                        })
+                        .collect(),
+                ),
+            }),
+            "items" | "additionalProperties" | "not" => upgraded.push(YamlPair {
+                key: schema_pair.key.to_owned(),
+                value: upgrade_schema(value),
+            }),
+            "allOf" | "anyOf" | "oneOf" => upgraded.push(YamlPair {
+                key: schema_pair.key.to_owned(),
+                value: YamlNode::sequence(
+                    value
+                        .as_sequence()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(upgrade_schema)
+                        .collect(),
+                ),
+            }),
+            _ => upgraded.push(schema_pair.to_owned()),
+        }
+    }
+    mapping(upgraded)
+}
+
+/// Points local `$ref`s at the components the definitions, parameters and
+/// responses they referred to have moved to.
+fn rewrite_refs(node: &mut YamlNode) {
+    match &mut node.value {
+        YamlValue::Mapping(pairs) => {
+            for ref_pair in pairs {
+                if ref_pair.key.as_str() == Some("$ref") {
+                    if let YamlValue::Scalar(scalar) = &mut ref_pair.value.value {
+                        for (old, new) in REF_PREFIXES {
+                            if let Some(rest) = scalar.text.strip_prefix(old) {
+                                scalar.text = format!("{}{}", new, rest);
+                            }
+                        }
+                    }
+                } else {
+                    rewrite_refs(&mut ref_pair.value);
+                }
+            }
+        }
+        YamlValue::Sequence(items) => items.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+/// Whether `parameter` is a `body` or `formData` parameter, which OpenAPI 3
+/// describes with a request body instead.
+fn is_body(parameter: &YamlNode) -> bool {
+    matches!(
+        parameter.get("in").and_then(|location| location.as_str()),
+        Some("body" | "formData")
+    )
+}
+
+/// The media types of a `consumes` or `produces` list, defaulting to JSON.
+fn media_types(list: Option<&YamlNode>) -> Vec<String> {
+    let media_types = strings(list);
+    match media_types.is_empty() {
+        true => vec![DEFAULT_MEDIA_TYPE.to_string()],
+        false => media_types,
+    }
+}
+
+fn strings(list: Option<&YamlNode>) -> Vec<String> {
+    list.and_then(|list| list.as_sequence())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| item.as_str())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn pair(key: &str, value: YamlNode) -> YamlPair {
+    YamlPair {
+        key: YamlNode::string(key),
+        value,
+    }
+}
+
+fn mapping(pairs: Vec<YamlPair>) -> YamlNode {
+    YamlNode::new(YamlValue::Mapping(pairs))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::upgrade::{TreeSitterUpgrader, Upgrader},
+        content::ContentProviderMap,
+    };
+
+    fn upgrade(root_content: &str) -> Result<String, Box<dyn Error>> {
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        Ok(TreeSitterUpgrader::new(provider).upgrade()?.to_yaml())
+    }
+
+    #[test]
+    fn upgrade_swagger() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"swagger: '2.0'
+info:
+  title: Swagger Petstore
+host: petstore.swagger.io
+basePath: /v1
+produces:
+  - application/json
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: tags
+          in: query
+          type: array
+          items:
+            type: string
+      responses:
+        '200':
+          description: A list of pets
+          schema:
+            type: array
+            items:
+              $ref: '#/definitions/Pet'
+    post:
+      parameters:
+        - $ref: '#/parameters/Pet'
+      responses:
+        '201':
+          description: Created
+parameters:
+  Pet:
+    name: pet
+    in: body
+    schema:
+      $ref: '#/definitions/Pet'
+definitions:
+  Pet:
+    type: object
+    properties:
+      photo:
+        type: file
+"#;
+        let expected = r#"openapi: 3.0.3
+info:
+  title: Swagger Petstore
+servers:
+  - url: https://petstore.swagger.io/v1
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: tags
+          in: query
+          style: form
+          explode: false
+          schema:
+            type: array
+            items:
+              type: string
+      responses:
+        '200':
+          description: A list of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/Pet'
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+      responses:
+        '201':
+          description: Created
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        photo:
+          type: string
+          format: binary
+"#;
+        assert_eq!(upgrade(root_content)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_openapi_3() {
+        let error = upgrade("openapi: 3.0.0\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Expected a Swagger 2.0 document with `swagger: \"2.0\"`"
+        );
+    }
+}
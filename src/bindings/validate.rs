@@ -0,0 +1,1785 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    pattern::Pattern,
+    resolve::{identity, Resolver},
+    schema_check::{check_instance, KNOWN_FORMATS},
+    yaml::{escape_pointer_segment, unescape_pointer_segment, YamlNode, YamlValue},
+};
+
+pub const HTTP_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+const PATH_ITEM_FIELDS: [&str; 5] = ["$ref", "summary", "description", "servers", "parameters"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// The OpenAPI versions whose structure differs enough to matter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecVersion {
+    /// Swagger 2.0, which `upgrade` converts to OpenAPI 3.0.
+    V2_0,
+    V3_0,
+    /// Adds `webhooks`, makes `paths` and operation `responses` optional and
+    /// aligns schemas with JSON Schema, so `type` may be a list and `const`
+    /// is allowed.
+    V3_1,
+}
+
+impl SpecVersion {
+    /// The version declared by the `openapi` key of `root`, or by `swagger`
+    /// for Swagger 2.0. Versions after 3.1 are read as 3.1, anything else
+    /// gives `None`.
+    pub fn detect(root: &YamlNode) -> Option<SpecVersion> {
+        if root.get("swagger").and_then(|version| version.as_str()) == Some("2.0") {
+            return Some(SpecVersion::V2_0);
+        }
+        let version = root.get("openapi")?.as_str()?;
+        let minor = version.strip_prefix("3.")?;
+        match minor == "0" || minor.starts_with("0.") {
+            true => Some(SpecVersion::V3_0),
+            false => Some(SpecVersion::V3_1),
+        }
+    }
+}
+
+pub trait ValidationParser {
+    fn get_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError>;
+
+    /// The examples of media types, parameters, headers and schemas that
+    /// don't match their schema.
+    fn get_example_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterValidationParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterValidationParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn load(&self, file: &Path) -> Result<YamlNode, OpenapiSchemerError> {
+        self.provider
+            .get_document(file.to_path_buf())
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Validate))
+    }
+
+    fn diagnostic(&self, file: &Path, node: &YamlNode, message: String) -> Diagnostic {
+        Diagnostic {
+            file: self.provider.source_path(file),
+            line: node.start.row + 1,
+            column: node.start.column + 1,
+            message,
+        }
+    }
+
+    /// Follows a `$ref` so path items and operations split into
+    /// their own files are validated where they are defined.
+    fn follow_ref(
+        &self,
+        node: &YamlNode,
+        file: &Path,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<Option<(YamlNode, PathBuf)>, OpenapiSchemerError> {
+        let reference = match node.reference() {
+            Some(reference) => reference,
+            None => return Ok(Some((node.to_owned(), file.to_path_buf()))),
+        };
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        match resolver.resolve_ref(file, reference) {
+            Ok(resolved) => Ok(Some(resolved)),
+            Err(error) => {
+                let message = match error.downcast::<OpenapiSchemerError>() {
+                    Ok(circular) => circular.to_string(),
+                    Err(_) => format!("Could not resolve $ref `{}`", reference),
+                };
+                diagnostics.push(self.diagnostic(file, node, message));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reports keys written more than once in a mapping, in every file of the
+    /// spec. YAML parsers keep only one of them, so whatever else was written
+    /// under that key is silently lost.
+    fn validate_duplicate_keys(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut files = self.provider.paths();
+        files.sort_by_key(|file| (file.as_path() != Path::new("#"), file.to_path_buf()));
+        let mut seen = HashSet::new();
+        for file in files {
+            // The root document is also listed under its own path.
+            if !seen.insert(self.provider.source_path(file)) {
+                continue;
+            }
+            // Files that can't be read are reported by the other checks.
+            if let Ok(document) = self.provider.get_source_document(file.to_owned()) {
+                self.duplicate_keys(&document, file, "", diagnostics);
+            }
+        }
+    }
+
+    fn duplicate_keys(
+        &self,
+        node: &YamlNode,
+        file: &Path,
+        pointer: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match &node.value {
+            YamlValue::Mapping(pairs) => {
+                let mut keys = Duplicates::default();
+                for pair in pairs {
+                    let key = pair.key.as_str().unwrap_or_default();
+                    let at = self.diagnostic(file, &pair.key, String::new());
+                    keys.add(key, key.to_string(), at.file, at.line, at.column);
+                    let pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+                    self.duplicate_keys(&pair.value, file, &pointer, diagnostics);
+                }
+                let parent = match pointer.is_empty() {
+                    true => "/",
+                    false => pointer,
+                };
+                keys.report(diagnostics, |key, _, others| {
+                    let others: Vec<String> = others.into_iter().map(|(_, at)| at).collect();
+                    format!(
+                        "Key `{}` of `{}` is also defined at {}",
+                        key,
+                        parent,
+                        others.join(", ")
+                    )
+                });
+            }
+            YamlValue::Sequence(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let pointer = format!("{}/{}", pointer, index);
+                    self.duplicate_keys(item, file, &pointer, diagnostics);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports operationIds used by several operations and paths that only
+    /// differ in the names of their parameters. Each diagnostic points at one
+    /// of them and lists the others. Broken refs are reported elsewhere.
+    fn validate_duplicates(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+
+        let mut operation_ids = Duplicates::default();
+        for operation in resolver.operations().unwrap_or_default() {
+            let node = operation.node(self.provider.as_ref());
+            if let Some(id) = &node.operation_id {
+                operation_ids.add(id, node.describe(), node.file, node.line, node.column);
+            }
+        }
+        operation_ids.report(diagnostics, |id, label, others| {
+            format!(
+                "operationId `{}` of `{}` is also used by {}",
+                id,
+                label,
+                labelled(others)
+            )
+        });
+
+        let mut paths = Duplicates::default();
+        for entry in resolver.entries("/paths").unwrap_or_default() {
+            if entry.name.starts_with("x-") {
+                continue;
+            }
+            let template = template_names(&entry.name)
+                .into_iter()
+                .fold(entry.name.to_owned(), |path, name| {
+                    path.replacen(&format!("{{{}}}", name), "{}", 1)
+                });
+            let file = self.provider.source_path(&entry.key_file);
+            let (line, column) = (entry.key.start.row + 1, entry.key.start.column + 1);
+            paths.add(&template, entry.name, file, line, column);
+        }
+        paths.report(diagnostics, |_, label, others| {
+            format!(
+                "Path `{}` only differs in parameter names from {}",
+                label,
+                labelled(others)
+            )
+        });
+    }
+
+    /// Reports discriminators of component schemas whose mapping points at
+    /// something that isn't one of their schemas, whose property a variant
+    /// doesn't declare as required, and `oneOf` or `anyOf` members they
+    /// can't select. Without `oneOf` or `anyOf` the variants inherit the
+    /// schema through `allOf`, so the schema itself must require the
+    /// property. Broken member refs are reported elsewhere.
+    fn validate_discriminators(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut seen = HashSet::new();
+        for entry in resolver.entries("/components/schemas").unwrap_or_default() {
+            let (schema, file, name) = (&entry.value, &entry.file, &entry.name);
+            let discriminator = match schema.get("discriminator") {
+                Some(discriminator) => discriminator,
+                None => continue,
+            };
+            if !seen.insert(identity(self.provider.as_ref(), file, schema)) {
+                continue;
+            }
+            let property = match discriminator
+                .get("propertyName")
+                .and_then(|property| property.as_str())
+            {
+                Some(property) => property,
+                None => {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        discriminator,
+                        format!(
+                            "Missing required key `propertyName` in the discriminator of `{}`",
+                            name
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            let mut mapped = vec![];
+            let mapping = discriminator
+                .get("mapping")
+                .and_then(|mapping| mapping.as_mapping())
+                .unwrap_or_default();
+            for pair in mapping {
+                let key = pair.key.as_str().unwrap_or_default();
+                let target = pair.value.as_str().unwrap_or_default();
+                // A mapping value is a ref, or the name of a component schema.
+                let reference = match target.contains(['/', '#']) {
+                    true => target.to_string(),
+                    false => format!("#/components/schemas/{}", escape_pointer_segment(target)),
+                };
+                let resolved = resolver
+                    .resolve_ref(file, &reference)
+                    .and_then(|(target, target_file)| resolver.follow(&target, &target_file));
+                match resolved {
+                    Ok((target, target_file)) => {
+                        let id = identity(self.provider.as_ref(), &target_file, &target);
+                        mapped.push((id, key, &pair.value))
+                    }
+                    Err(_) => diagnostics.push(self.diagnostic(
+                        file,
+                        &pair.value,
+                        format!(
+                            "Discriminator mapping `{}` of `{}` points at `{}`, which is not a schema",
+                            key, name, target
+                        ),
+                    )),
+                }
+            }
+
+            let members = ["oneOf", "anyOf"].into_iter().find_map(|keyword| {
+                schema
+                    .get(keyword)
+                    .and_then(|members| members.as_sequence())
+                    .map(|members| (keyword, members))
+            });
+            let (keyword, members) = match members {
+                Some(members) => members,
+                None => {
+                    let label = format!("`{}`", name);
+                    self.check_discriminator_property(
+                        &mut resolver,
+                        (schema, file),
+                        property,
+                        &label,
+                        diagnostics,
+                    );
+                    continue;
+                }
+            };
+            let mut variants = vec![];
+            for member in members {
+                let reference = match member.reference() {
+                    Some(reference) => reference,
+                    None => {
+                        diagnostics.push(self.diagnostic(
+                            file,
+                            member,
+                            format!(
+                                "An inline `{}` member of `{}` can't be selected by its discriminator, refer to a schema instead",
+                                keyword, name
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+                let (variant, variant_file) = match resolver.follow(member, file) {
+                    Ok(variant) => variant,
+                    Err(_) => continue,
+                };
+                let id = identity(self.provider.as_ref(), &variant_file, &variant);
+                let label = format!("Variant `{}` of `{}`", schema_label(reference), name);
+                if !mapped.is_empty() && !mapped.iter().any(|(mapped, _, _)| *mapped == id) {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        member,
+                        format!("{} is not covered by its discriminator mapping", label),
+                    ));
+                }
+                self.check_discriminator_property(
+                    &mut resolver,
+                    (&variant, &variant_file),
+                    property,
+                    &label,
+                    diagnostics,
+                );
+                variants.push(id);
+            }
+            for (id, key, value) in mapped {
+                if !variants.contains(&id) {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        value,
+                        format!(
+                            "Discriminator mapping `{}` of `{}` points at a schema that is not listed in its `{}`",
+                            key, name, keyword
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Reports the variant `label` when it doesn't declare `property` or
+    /// doesn't require it, counting what it inherits through `allOf`.
+    fn check_discriminator_property(
+        &self,
+        resolver: &mut Resolver,
+        (variant, file): (&YamlNode, &Path),
+        property: &str,
+        label: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let mut declared = false;
+        let mut required = false;
+        let mut pending = vec![(variant.to_owned(), file.to_path_buf())];
+        let mut visited = HashSet::new();
+        while let Some((schema, schema_file)) = pending.pop() {
+            let (schema, schema_file) = match resolver.follow(&schema, &schema_file) {
+                Ok(schema) => schema,
+                Err(_) => continue,
+            };
+            if !visited.insert(identity(self.provider.as_ref(), &schema_file, &schema)) {
+                continue;
+            }
+            declared |= schema
+                .get("properties")
+                .and_then(|properties| properties.get(property))
+                .is_some();
+            required |= schema
+                .get("required")
+                .and_then(|required| required.as_sequence())
+                .unwrap_or_default()
+                .iter()
+                .any(|name| name.as_str() == Some(property));
+            let branches = schema
+                .get("allOf")
+                .and_then(|branches| branches.as_sequence())
+                .unwrap_or_default();
+            for branch in branches {
+                pending.push((branch.to_owned(), schema_file.to_owned()));
+            }
+        }
+        let message = match (declared, required) {
+            (false, _) => format!(
+                "{} has no property `{}` for its discriminator",
+                label, property
+            ),
+            (true, false) => format!(
+                "{} does not require its discriminator property `{}`",
+                label, property
+            ),
+            (true, true) => return,
+        };
+        diagnostics.push(self.diagnostic(file, variant, message));
+    }
+
+    /// Reports unknown formats, invalid patterns and inconsistent bounds in
+    /// every schema. Broken refs are reported elsewhere.
+    fn validate_schema_keywords(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if let Ok(found) = SchemaCheck::new(self.provider.as_ref(), SchemaCheckKind::Keywords).run()
+        {
+            diagnostics.extend(found);
+        }
+    }
+
+    fn validate_root(
+        &self,
+        root: &YamlNode,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), OpenapiSchemerError> {
+        let file = PathBuf::from("#");
+        if root.as_mapping().is_none() {
+            diagnostics.push(self.diagnostic(
+                &file,
+                root,
+                "Expected the document to be a mapping".to_string(),
+            ));
+            return Ok(());
+        }
+
+        // Documents that don't declare 3.x are held to the 3.0 rules.
+        let version = match SpecVersion::detect(root) {
+            Some(SpecVersion::V2_0) => {
+                diagnostics.push(
+                    self.diagnostic(
+                        &file,
+                        root.get("swagger").unwrap_or(root),
+                        "Swagger 2.0 is not supported, convert the spec with `upgrade` first"
+                            .to_string(),
+                    ),
+                );
+                return Ok(());
+            }
+            version => version.unwrap_or(SpecVersion::V3_0),
+        };
+        match root.get("openapi") {
+            None => diagnostics.push(self.diagnostic(
+                &file,
+                root,
+                "Missing required key `openapi`".to_string(),
+            )),
+            Some(version) => {
+                if !version.as_str().unwrap_or_default().starts_with("3.") {
+                    diagnostics.push(self.diagnostic(
+                        &file,
+                        version,
+                        "Expected `openapi` to be a 3.x version string".to_string(),
+                    ));
+                }
+            }
+        }
+
+        match root.get("info") {
+            None => diagnostics.push(self.diagnostic(
+                &file,
+                root,
+                "Missing required key `info`".to_string(),
+            )),
+            Some(info) => {
+                let (info, info_file) = match self.follow_ref(info, &file, diagnostics)? {
+                    Some(resolved) => resolved,
+                    None => return Ok(()),
+                };
+                if info.as_mapping().is_none() {
+                    diagnostics.push(self.diagnostic(
+                        &info_file,
+                        &info,
+                        "Expected `info` to be a mapping".to_string(),
+                    ));
+                } else {
+                    for key in ["title", "version"] {
+                        if info.get(key).is_none() {
+                            diagnostics.push(self.diagnostic(
+                                &info_file,
+                                &info,
+                                format!("Missing required key `{}` in `info`", key),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        match (root.get("paths"), version) {
+            (None, SpecVersion::V2_0 | SpecVersion::V3_0) => diagnostics.push(self.diagnostic(
+                &file,
+                root,
+                "Missing required key `paths`".to_string(),
+            )),
+            (None, SpecVersion::V3_1) => {
+                if root.get("components").is_none() && root.get("webhooks").is_none() {
+                    diagnostics.push(self.diagnostic(
+                        &file,
+                        root,
+                        "Expected at least one of `paths`, `components` or `webhooks`".to_string(),
+                    ));
+                }
+            }
+            (Some(paths), _) => {
+                if let Some((paths, paths_file)) = self.follow_ref(paths, &file, diagnostics)? {
+                    self.validate_paths(&paths, &paths_file, version, diagnostics)?;
+                }
+            }
+        }
+
+        match (root.get("webhooks"), version) {
+            (Some(webhooks), SpecVersion::V2_0 | SpecVersion::V3_0) => {
+                diagnostics.push(self.diagnostic(
+                    &file,
+                    webhooks,
+                    "`webhooks` requires OpenAPI 3.1".to_string(),
+                ))
+            }
+            (Some(webhooks), SpecVersion::V3_1) => {
+                if let Some((webhooks, webhooks_file)) =
+                    self.follow_ref(webhooks, &file, diagnostics)?
+                {
+                    self.validate_webhooks(&webhooks, &webhooks_file, diagnostics)?;
+                }
+            }
+            (None, _) => {}
+        }
+        Ok(())
+    }
+
+    /// Every webhook is a path item keyed by its name rather than a path.
+    fn validate_webhooks(
+        &self,
+        webhooks: &YamlNode,
+        file: &Path,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), OpenapiSchemerError> {
+        let pairs = match &webhooks.value {
+            YamlValue::Mapping(pairs) => pairs,
+            _ => {
+                diagnostics.push(self.diagnostic(
+                    file,
+                    webhooks,
+                    "Expected `webhooks` to be a mapping".to_string(),
+                ));
+                return Ok(());
+            }
+        };
+        for pair in pairs {
+            let name = pair.key.as_str().unwrap_or_default();
+            if name.starts_with("x-") {
+                continue;
+            }
+            if let Some((path_item, path_item_file)) =
+                self.follow_ref(&pair.value, file, diagnostics)?
+            {
+                self.validate_path_item(
+                    name,
+                    &path_item,
+                    &path_item_file,
+                    SpecVersion::V3_1,
+                    diagnostics,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_paths(
+        &self,
+        paths: &YamlNode,
+        file: &Path,
+        version: SpecVersion,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), OpenapiSchemerError> {
+        let pairs = match &paths.value {
+            YamlValue::Mapping(pairs) => pairs,
+            _ => {
+                diagnostics.push(self.diagnostic(
+                    file,
+                    paths,
+                    "Expected `paths` to be a mapping".to_string(),
+                ));
+                return Ok(());
+            }
+        };
+        for pair in pairs {
+            let path = pair.key.as_str().unwrap_or_default();
+            if path.starts_with("x-") {
+                continue;
+            }
+            if !path.starts_with('/') {
+                diagnostics.push(self.diagnostic(
+                    file,
+                    &pair.key,
+                    format!("Path `{}` must begin with `/`", path),
+                ));
+            }
+            if let Some((path_item, path_item_file)) =
+                self.follow_ref(&pair.value, file, diagnostics)?
+            {
+                self.validate_path_item(path, &path_item, &path_item_file, version, diagnostics)?;
+                self.validate_path_parameters(path, &path_item, &path_item_file, diagnostics);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every `{name}` of the path template has a path parameter
+    /// on the path item or on each operation, and that every path parameter
+    /// is in the template and required. Broken refs are reported elsewhere.
+    fn validate_path_parameters(
+        &self,
+        path: &str,
+        path_item: &YamlNode,
+        file: &Path,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let template = template_names(path);
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let declared = |resolver: &mut Resolver,
+                        node: &YamlNode,
+                        file: &Path,
+                        diagnostics: &mut Vec<Diagnostic>| {
+            let mut names = vec![];
+            let parameters = node
+                .get("parameters")
+                .and_then(|parameters| parameters.as_sequence())
+                .unwrap_or_default();
+            for parameter in parameters {
+                let (parameter, parameter_file) = match resolver.follow(parameter, file) {
+                    Ok(parameter) => parameter,
+                    Err(_) => continue,
+                };
+                if parameter.get("in").and_then(|location| location.as_str()) != Some("path") {
+                    continue;
+                }
+                let name = parameter
+                    .get("name")
+                    .and_then(|name| name.as_str())
+                    .unwrap_or_default();
+                if !template.contains(&name) {
+                    diagnostics.push(self.diagnostic(
+                        &parameter_file,
+                        &parameter,
+                        format!("Path parameter `{}` is not in the path `{}`", name, path),
+                    ));
+                } else if parameter
+                    .get("required")
+                    .and_then(|required| required.as_str())
+                    != Some("true")
+                {
+                    diagnostics.push(self.diagnostic(
+                        &parameter_file,
+                        &parameter,
+                        format!("Path parameter `{}` must have `required: true`", name),
+                    ));
+                }
+                names.push(name.to_string());
+            }
+            names
+        };
+
+        let path_parameters = declared(&mut resolver, path_item, file, diagnostics);
+        for pair in path_item.as_mapping().unwrap_or_default() {
+            let method = pair.key.as_str().unwrap_or_default();
+            if !HTTP_METHODS.contains(&method) {
+                continue;
+            }
+            let (operation, operation_file) = match resolver.follow(&pair.value, file) {
+                Ok(operation) => operation,
+                Err(_) => continue,
+            };
+            let mut names = declared(&mut resolver, &operation, &operation_file, diagnostics);
+            names.extend(path_parameters.iter().cloned());
+            for name in &template {
+                if !names.iter().any(|declared| declared == name) {
+                    diagnostics.push(self.diagnostic(
+                        file,
+                        &pair.key,
+                        format!(
+                            "Missing path parameter `{}` for operation `{} {}`",
+                            name,
+                            method.to_uppercase(),
+                            path
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_path_item(
+        &self,
+        path: &str,
+        path_item: &YamlNode,
+        file: &Path,
+        version: SpecVersion,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<(), OpenapiSchemerError> {
+        let pairs = match &path_item.value {
+            YamlValue::Mapping(pairs) => pairs,
+            _ => {
+                diagnostics.push(self.diagnostic(
+                    file,
+                    path_item,
+                    format!("Expected path `{}` to be a mapping", path),
+                ));
+                return Ok(());
+            }
+        };
+        for pair in pairs {
+            let key = pair.key.as_str().unwrap_or_default();
+            if HTTP_METHODS.contains(&key) {
+                if let Some((operation, operation_file)) =
+                    self.follow_ref(&pair.value, file, diagnostics)?
+                {
+                    self.validate_operation(
+                        path,
+                        key,
+                        &operation,
+                        &operation_file,
+                        version,
+                        diagnostics,
+                    );
+                }
+            } else if !PATH_ITEM_FIELDS.contains(&key) && !key.starts_with("x-") {
+                diagnostics.push(self.diagnostic(
+                    file,
+                    &pair.key,
+                    format!("Invalid HTTP method `{}` under path `{}`", key, path),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_operation(
+        &self,
+        path: &str,
+        method: &str,
+        operation: &YamlNode,
+        file: &Path,
+        version: SpecVersion,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let name = format!("{} {}", method.to_uppercase(), path);
+        if operation.as_mapping().is_none() {
+            diagnostics.push(self.diagnostic(
+                file,
+                operation,
+                format!("Expected operation `{}` to be a mapping", name),
+            ));
+            return;
+        }
+        if operation.get("operationId").is_none() {
+            diagnostics.push(self.diagnostic(
+                file,
+                operation,
+                format!("Missing `operationId` for operation `{}`", name),
+            ));
+        }
+        match operation.get("responses") {
+            None if version == SpecVersion::V3_1 => {}
+            None => diagnostics.push(self.diagnostic(
+                file,
+                operation,
+                format!("Missing required key `responses` for operation `{}`", name),
+            )),
+            Some(responses) => match responses.as_mapping() {
+                Some(pairs) if !pairs.is_empty() => {
+                    for pair in pairs {
+                        let code = pair.key.as_str().unwrap_or_default();
+                        if !is_response_code(code) && !code.starts_with("x-") {
+                            diagnostics.push(self.diagnostic(
+                                file,
+                                &pair.key,
+                                format!(
+                                    "Invalid response code `{}` for operation `{}`",
+                                    code, name
+                                ),
+                            ));
+                        }
+                    }
+                }
+                _ => diagnostics.push(self.diagnostic(
+                    file,
+                    responses,
+                    format!("Expected at least one response for operation `{}`", name),
+                )),
+            },
+        }
+    }
+}
+
+/// Groups things by a key that should be unique, keeping the order they were
+/// found in.
+#[derive(Default)]
+struct Duplicates {
+    groups: Vec<(String, Vec<(String, Diagnostic)>)>,
+}
+
+impl Duplicates {
+    /// Adds the thing called `label` at `file`, `line` and `column` to the
+    /// group of `key`.
+    fn add(&mut self, key: &str, label: String, file: PathBuf, line: usize, column: usize) {
+        let location = Diagnostic {
+            file,
+            line,
+            column,
+            message: String::new(),
+        };
+        match self.groups.iter_mut().find(|(group, _)| group == key) {
+            Some((_, members)) => members.push((label, location)),
+            None => self.groups.push((key.to_string(), vec![(label, location)])),
+        }
+    }
+
+    /// A diagnostic for each member of a group with more than one, with the
+    /// message `message` gives for the key, the member's label and the labels
+    /// and locations of the other members.
+    fn report(
+        self,
+        diagnostics: &mut Vec<Diagnostic>,
+        message: impl Fn(&str, &str, Vec<(&str, String)>) -> String,
+    ) {
+        for (key, members) in self.groups {
+            if members.len() < 2 {
+                continue;
+            }
+            for (index, (label, location)) in members.iter().enumerate() {
+                let others = members
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != index)
+                    .map(|(_, (label, other))| {
+                        let at =
+                            format!("{}:{}:{}", other.file.display(), other.line, other.column);
+                        (label.as_str(), at)
+                    })
+                    .collect();
+                diagnostics.push(Diagnostic {
+                    message: message(&key, label, others),
+                    ..location.to_owned()
+                });
+            }
+        }
+    }
+}
+
+/// The component a ref to a schema names, or the ref itself.
+fn schema_label(reference: &str) -> String {
+    match reference.strip_prefix("#/components/schemas/") {
+        Some(name) => unescape_pointer_segment(name),
+        None => reference.to_string(),
+    }
+}
+
+/// `label at file:line:column` for each of `others`.
+fn labelled(others: Vec<(&str, String)>) -> String {
+    let others: Vec<String> = others
+        .into_iter()
+        .map(|(label, at)| format!("`{}` at {}", label, at))
+        .collect();
+    others.join(", ")
+}
+
+/// The names between braces in a path template, `petId` for
+/// `/pets/{petId}`.
+fn template_names(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Response codes are either `default`, a status code between 100 and 599 or
+/// a range such as `4XX`.
+fn is_response_code(code: &str) -> bool {
+    if code == "default" {
+        return true;
+    }
+    let bytes = code.as_bytes();
+    if bytes.len() != 3 || !(b'1'..=b'5').contains(&bytes[0]) {
+        return false;
+    }
+    bytes[1..].iter().all(u8::is_ascii_digit) || &bytes[1..] == b"XX"
+}
+
+/// What a `SchemaCheck` checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SchemaCheckKind {
+    /// Each example against its schema.
+    Examples,
+    /// The `format`, `pattern` and bounds of each schema.
+    Keywords,
+}
+
+/// Walks the parts of a spec that can have schemas and examples and checks
+/// them as `kind` says. Parts reached through several refs are checked once.
+struct SchemaCheck<'a> {
+    provider: &'a dyn ContentProvider,
+    resolver: Resolver<'a>,
+    kind: SchemaCheckKind,
+    visited: HashSet<(PathBuf, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> SchemaCheck<'a> {
+    fn new(provider: &'a dyn ContentProvider, kind: SchemaCheckKind) -> Self {
+        Self {
+            provider,
+            resolver: Resolver::new(provider),
+            kind,
+            visited: HashSet::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    fn run(mut self) -> Result<Vec<Diagnostic>> {
+        for operation in self.resolver.operations()? {
+            self.operation(&operation.operation, &operation.file);
+        }
+        for path_item in self.resolver.entries("/paths")? {
+            self.parameters(&path_item.value, &path_item.file);
+        }
+        for entry in self.resolver.entries("/components/parameters")? {
+            self.parameter(&entry.value, &entry.file);
+        }
+        for entry in self.resolver.entries("/components/headers")? {
+            self.parameter(&entry.value, &entry.file);
+        }
+        for entry in self.resolver.entries("/components/requestBodies")? {
+            self.body(&entry.value, &entry.file);
+        }
+        for entry in self.resolver.entries("/components/responses")? {
+            self.body(&entry.value, &entry.file);
+        }
+        for entry in self.resolver.entries("/components/schemas")? {
+            self.schema(&entry.value, &entry.file);
+        }
+        Ok(self.diagnostics)
+    }
+
+    /// `node` with its refs followed, or `None` when it was checked before or
+    /// a ref is broken.
+    fn visit(&mut self, node: &YamlNode, file: &Path) -> Option<(YamlNode, PathBuf)> {
+        let (node, file) = self.resolver.follow(node, file).ok()?;
+        let key = (self.provider.source_path(&file), node.bytes.start);
+        self.visited.insert(key).then_some((node, file))
+    }
+
+    fn operation(&mut self, operation: &YamlNode, file: &Path) {
+        self.parameters(operation, file);
+        if let Some(body) = operation.get("requestBody") {
+            self.body(body, file);
+        }
+        if let Some((responses, responses_file)) = operation
+            .get("responses")
+            .and_then(|responses| self.resolver.follow(responses, file).ok())
+        {
+            for pair in responses.as_mapping().unwrap_or_default() {
+                self.body(&pair.value, &responses_file);
+            }
+        }
+    }
+
+    /// The `parameters` of an operation or path item.
+    fn parameters(&mut self, node: &YamlNode, file: &Path) {
+        let parameters = node
+            .get("parameters")
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default();
+        for parameter in parameters {
+            self.parameter(parameter, file);
+        }
+    }
+
+    /// A parameter or header, which have their examples next to their
+    /// schema or in their `content`.
+    fn parameter(&mut self, parameter: &YamlNode, file: &Path) {
+        if let Some((parameter, file)) = self.visit(parameter, file) {
+            self.examples(&parameter, &file);
+            self.body(&parameter, &file);
+        }
+    }
+
+    /// A request body or response, or the `content` of a parameter.
+    fn body(&mut self, body: &YamlNode, file: &Path) {
+        let (body, file) = match self.resolver.follow(body, file) {
+            Ok(body) => body,
+            Err(_) => return,
+        };
+        if let Some((content, content_file)) = body
+            .get("content")
+            .and_then(|content| self.resolver.follow(content, &file).ok())
+        {
+            for pair in content.as_mapping().unwrap_or_default() {
+                if let Some((media_type, media_type_file)) = self.visit(&pair.value, &content_file)
+                {
+                    self.examples(&media_type, &media_type_file);
+                }
+            }
+        }
+        if let Some(headers) = body.get("headers") {
+            for pair in headers.as_mapping().unwrap_or_default() {
+                self.parameter(&pair.value, &file);
+            }
+        }
+    }
+
+    /// Checks the `example` and `examples` next to the `schema` of `holder`,
+    /// then the schema.
+    fn examples(&mut self, holder: &YamlNode, file: &Path) {
+        let schema = match holder.get("schema") {
+            Some(schema) => schema,
+            None => return,
+        };
+        if self.kind != SchemaCheckKind::Examples {
+            self.schema(schema, file);
+            return;
+        }
+        if let Some(example) = holder.get("example") {
+            self.check(example, file, schema, file);
+        }
+        let examples = holder
+            .get("examples")
+            .and_then(|examples| examples.as_mapping())
+            .unwrap_or_default();
+        for pair in examples {
+            // Examples given by `externalValue` aren't fetched.
+            if let Ok((example, example_file)) = self.resolver.follow(&pair.value, file) {
+                if let Some(value) = example.get("value") {
+                    self.check(value, &example_file, schema, file);
+                }
+            }
+        }
+        self.schema(schema, file);
+    }
+
+    /// Checks the `example` of `schema`, or each of its `examples` since
+    /// OpenAPI 3.1, or its keywords, then does the same for its subschemas.
+    fn schema(&mut self, schema: &YamlNode, file: &Path) {
+        let (schema, file) = match self.visit(schema, file) {
+            Some(schema) => schema,
+            None => return,
+        };
+        match self.kind {
+            SchemaCheckKind::Examples => {
+                if let Some(example) = schema.get("example") {
+                    self.check(example, &file, &schema, &file);
+                }
+                let examples = schema
+                    .get("examples")
+                    .and_then(|examples| examples.as_sequence())
+                    .unwrap_or_default();
+                for example in examples {
+                    self.check(example, &file, &schema, &file);
+                }
+            }
+            SchemaCheckKind::Keywords => self.keywords(&schema, &file),
+        }
+        let mut subschemas = vec![];
+        if let Some(properties) = schema.get("properties") {
+            subschemas.extend(
+                properties
+                    .as_mapping()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|pair| &pair.value),
+            );
+        }
+        for keyword in ["items", "additionalProperties", "not"] {
+            subschemas.extend(
+                schema
+                    .get(keyword)
+                    .filter(|subschema| matches!(subschema.value, YamlValue::Mapping(_))),
+            );
+        }
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            subschemas.extend(
+                schema
+                    .get(keyword)
+                    .and_then(|subschemas| subschemas.as_sequence())
+                    .unwrap_or_default(),
+            );
+        }
+        for subschema in subschemas {
+            self.schema(subschema, &file);
+        }
+    }
+
+    /// Reports a `format` outside the known registry, a `pattern` that isn't
+    /// a valid regular expression, lengths and counts that aren't
+    /// non-negative integers, and lower bounds above their upper bound.
+    fn keywords(&mut self, schema: &YamlNode, file: &Path) {
+        let mut problems = vec![];
+        if let Some(format) = schema.get("format") {
+            let name = format.as_str().unwrap_or_default();
+            if !KNOWN_FORMATS.contains(&name) {
+                problems.push((format, format!("Unknown format `{}`", name)));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern") {
+            let source = pattern.as_str().unwrap_or_default();
+            if let Err(error) = Pattern::check(source) {
+                problems.push((pattern, format!("Invalid pattern `{}`: {}", source, error)));
+            }
+        }
+        for keyword in [
+            "minLength",
+            "maxLength",
+            "minItems",
+            "maxItems",
+            "minProperties",
+            "maxProperties",
+        ] {
+            if let Some(count) = schema.get(keyword) {
+                let count_text = count.as_str().unwrap_or_default();
+                if count_text.parse::<u64>().is_err() {
+                    let message = format!("`{}` should be a non-negative integer", keyword);
+                    problems.push((count, message));
+                }
+            }
+        }
+        let bound = |keyword: &str| {
+            let node = schema.get(keyword)?;
+            let text = node.as_str()?;
+            text.parse::<f64>().ok().map(|value| (node, text, value))
+        };
+        for (lower, upper) in [
+            ("minimum", "maximum"),
+            ("minLength", "maxLength"),
+            ("minItems", "maxItems"),
+            ("minProperties", "maxProperties"),
+        ] {
+            if let (Some((node, min, lowest)), Some((_, max, highest))) =
+                (bound(lower), bound(upper))
+            {
+                if lowest > highest {
+                    let message = format!(
+                        "`{}` of {} is greater than `{}` of {}",
+                        lower, min, upper, max
+                    );
+                    problems.push((node, message));
+                }
+            }
+        }
+        for (node, message) in problems {
+            self.diagnostics.push(Diagnostic {
+                file: self.provider.source_path(file),
+                line: node.start.row + 1,
+                column: node.start.column + 1,
+                message,
+            });
+        }
+    }
+
+    fn check(&mut self, example: &YamlNode, example_file: &Path, schema: &YamlNode, file: &Path) {
+        for violation in check_instance(&mut self.resolver, schema, file, example) {
+            self.diagnostics.push(Diagnostic {
+                file: self.provider.source_path(example_file),
+                line: violation.line,
+                column: violation.column,
+                message: format!("Example does not match its schema: {}", violation.message),
+            });
+        }
+    }
+}
+
+impl ValidationParser for TreeSitterValidationParser {
+    fn get_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError> {
+        let root = self.load(&PathBuf::from("#"))?;
+        let mut diagnostics = vec![];
+        self.validate_root(&root, &mut diagnostics)?;
+        self.validate_duplicates(&mut diagnostics);
+        self.validate_duplicate_keys(&mut diagnostics);
+        self.validate_discriminators(&mut diagnostics);
+        self.validate_schema_keywords(&mut diagnostics);
+        Ok(diagnostics)
+    }
+
+    fn get_example_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError> {
+        SchemaCheck::new(self.provider.as_ref(), SchemaCheckKind::Examples)
+            .run()
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Validate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::validate::{TreeSitterValidationParser, ValidationParser},
+        content::ContentProviderMap,
+    };
+
+    fn messages(contents: Vec<(&str, &str)>) -> Vec<(usize, usize, String)> {
+        let contents = contents
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_owned()))
+            .collect::<HashMap<PathBuf, String>>();
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterValidationParser::new(provider);
+        parser
+            .get_diagnostics()
+            .unwrap()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn get_diagnostics_valid() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          description: A paged array of pets
+        4XX:
+          description: Client error
+        default:
+          description: unexpected error
+"#;
+        assert_eq!(messages(vec![("#", root_content)]), vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_missing_keys() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 2.0
+info:
+  title: Swagger Petstore
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![
+                (
+                    1,
+                    10,
+                    "Expected `openapi` to be a 3.x version string".to_string()
+                ),
+                (3, 3, "Missing required key `version` in `info`".to_string()),
+                (1, 1, "Missing required key `paths`".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_swagger() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"swagger: '2.0'
+info:
+  title: Swagger Petstore
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![(
+                1,
+                10,
+                "Swagger 2.0 is not supported, convert the spec with `upgrade` first".to_string()
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_operations() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  pets:
+    $ref: 'Pets.yaml'
+"#;
+        let pets_content = r#"fetch:
+  operationId: fetchPets
+get:
+  summary: List all pets
+  responses:
+    '200':
+      description: A paged array of pets
+    '600':
+      description: Not a status code
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content), ("Pets.yaml", pets_content)]),
+            vec![
+                (6, 3, "Path `pets` must begin with `/`".to_string()),
+                (
+                    1,
+                    1,
+                    "Invalid HTTP method `fetch` under path `pets`".to_string()
+                ),
+                (
+                    4,
+                    3,
+                    "Missing `operationId` for operation `GET pets`".to_string()
+                ),
+                (
+                    8,
+                    5,
+                    "Invalid response code `600` for operation `GET pets`".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_path_parameters() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  /owners/{ownerId}/pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/OwnerId'
+    get:
+      operationId: showPet
+      parameters:
+        - name: petId
+          in: path
+          required: true
+      responses:
+        '200':
+          description: OK
+    delete:
+      operationId: deletePet
+      parameters:
+        - name: id
+          in: path
+          required: true
+        - name: petId
+          in: path
+      responses:
+        '204':
+          description: Deleted
+    put:
+      operationId: updatePet
+      responses:
+        '204':
+          description: Updated
+components:
+  parameters:
+    OwnerId:
+      name: ownerId
+      in: path
+      required: true
+"##;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![
+                (
+                    21,
+                    11,
+                    "Path parameter `id` is not in the path `/owners/{ownerId}/pets/{petId}`"
+                        .to_string()
+                ),
+                (
+                    24,
+                    11,
+                    "Path parameter `petId` must have `required: true`".to_string()
+                ),
+                (
+                    29,
+                    5,
+                    "Missing path parameter `petId` for operation `PUT /owners/{ownerId}/pets/{petId}`"
+                        .to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_duplicates() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  /pets/{petId}:
+    $ref: 'pets.yaml'
+  /pets/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+    delete:
+      operationId: showPet
+      responses:
+        '204':
+          description: Deleted
+components:
+  schemas:
+    Pet:
+      type: object
+    Pet:
+      type: string
+"##;
+        let pets_content = r#"parameters:
+  - name: petId
+    in: path
+    required: true
+get:
+  operationId: showPet
+  responses:
+    '200':
+      description: OK
+      description: Found
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content), ("pets.yaml", pets_content)]),
+            vec![
+                (
+                    6,
+                    16,
+                    "operationId `showPet` of `GET /pets/{petId}` is also used by `DELETE /pets/{id}` at #:14:20"
+                        .to_string()
+                ),
+                (
+                    14,
+                    20,
+                    "operationId `showPet` of `DELETE /pets/{id}` is also used by `GET /pets/{petId}` at pets.yaml:6:16"
+                        .to_string()
+                ),
+                (
+                    6,
+                    3,
+                    "Path `/pets/{petId}` only differs in parameter names from `/pets/{id}` at #:8:3"
+                        .to_string()
+                ),
+                (
+                    8,
+                    3,
+                    "Path `/pets/{id}` only differs in parameter names from `/pets/{petId}` at #:6:3"
+                        .to_string()
+                ),
+                (
+                    20,
+                    5,
+                    "Key `Pet` of `/components/schemas` is also defined at #:22:5".to_string()
+                ),
+                (
+                    22,
+                    5,
+                    "Key `Pet` of `/components/schemas` is also defined at #:20:5".to_string()
+                ),
+                (
+                    9,
+                    7,
+                    "Key `description` of `/get/responses/200` is also defined at pets.yaml:10:7"
+                        .to_string()
+                ),
+                (
+                    10,
+                    7,
+                    "Key `description` of `/get/responses/200` is also defined at pets.yaml:9:7"
+                        .to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_discriminators() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: '#/components/schemas/Cat'
+        - $ref: 'dog.yaml'
+        - type: object
+      discriminator:
+        propertyName: petType
+        mapping:
+          cat: Cat
+          bird: '#/components/schemas/Bird'
+          fish: Fish
+    Cat:
+      type: object
+      required: [petType]
+      properties:
+        petType:
+          type: string
+    Fish:
+      type: object
+    Animal:
+      type: object
+      properties:
+        kind:
+          type: string
+      discriminator:
+        propertyName: kind
+    Lion:
+      allOf:
+        - $ref: '#/components/schemas/Animal'
+        - required: [kind]
+"##;
+        let dog_content = r#"type: object
+properties:
+  petType:
+    type: string
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content), ("dog.yaml", dog_content)]),
+            vec![
+                (
+                    17,
+                    17,
+                    "Discriminator mapping `bird` of `Pet` points at `#/components/schemas/Bird`, which is not a schema"
+                        .to_string()
+                ),
+                (
+                    11,
+                    11,
+                    "Variant `dog.yaml` of `Pet` is not covered by its discriminator mapping"
+                        .to_string()
+                ),
+                (
+                    1,
+                    1,
+                    "Variant `dog.yaml` of `Pet` does not require its discriminator property `petType`"
+                        .to_string()
+                ),
+                (
+                    12,
+                    11,
+                    "An inline `oneOf` member of `Pet` can't be selected by its discriminator, refer to a schema instead"
+                        .to_string()
+                ),
+                (
+                    18,
+                    17,
+                    "Discriminator mapping `fish` of `Pet` points at a schema that is not listed in its `oneOf`"
+                        .to_string()
+                ),
+                (
+                    28,
+                    7,
+                    "`Animal` does not require its discriminator property `kind`".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_3_1() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.1.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+webhooks:
+  newPet:
+    post:
+      operationId: newPetHook
+  petGone:
+    delete:
+      operationId: petGoneHook
+      responses: {}
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![(
+                12,
+                18,
+                "Expected at least one response for operation `DELETE petGone`".to_string()
+            )]
+        );
+
+        let root_content = r#"openapi: 3.1.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![(
+                1,
+                1,
+                "Expected at least one of `paths`, `components` or `webhooks`".to_string()
+            )]
+        );
+
+        let root_content = r#"openapi: 3.0.3
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths: {}
+webhooks: {}
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![(6, 11, "`webhooks` requires OpenAPI 3.1".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_diagnostics_schema_keywords() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"openapi: 3.0.0
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            format: int23
+            minimum: 10
+            maximum: 1
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id:
+          type: string
+          format: uuid
+          pattern: '^[0-9a-f-]+(?=x)$'
+        name:
+          type: string
+          pattern: '^[a-z'
+          minLength: 5
+          maxLength: 2
+        tags:
+          type: array
+          minItems: -1
+"#;
+        assert_eq!(
+            messages(vec![("#", root_content)]),
+            vec![
+                (14, 21, "Unknown format `int23`".to_string()),
+                (
+                    15,
+                    22,
+                    "`minimum` of 10 is greater than `maximum` of 1".to_string()
+                ),
+                (31, 20, "Invalid pattern `^[a-z`: unclosed `[`".to_string()),
+                (
+                    32,
+                    22,
+                    "`minLength` of 5 is greater than `maxLength` of 2".to_string()
+                ),
+                (
+                    36,
+                    21,
+                    "`minItems` should be a non-negative integer".to_string()
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn get_example_diagnostics() -> Result<(), Box<dyn Error>> {
+        let root_content = r##"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+          example: ten
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+              examples:
+                rex:
+                  $ref: 'examples.yaml#/Rex'
+          headers:
+            X-Rate-Limit:
+              schema:
+                type: integer
+                format: int32
+              example: 99999999999
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        status:
+          type: string
+          enum: [available, sold]
+          example: lost
+"##;
+        let examples_content = r#"Rex:
+  value:
+    status: sold
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("examples.yaml"), examples_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let parser = TreeSitterValidationParser::new(provider);
+        let diagnostics: Vec<(String, usize, String)> = parser
+            .get_example_diagnostics()?
+            .into_iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.file.display().to_string(),
+                    diagnostic.line,
+                    diagnostic.message,
+                )
+            })
+            .collect();
+        let prefix = "Example does not match its schema: ";
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    "#".to_string(),
+                    10,
+                    format!("{}`$` should be of type `integer`, not `string`", prefix)
+                ),
+                (
+                    "examples.yaml".to_string(),
+                    3,
+                    format!("{}`$` is missing the required property `name`", prefix)
+                ),
+                (
+                    "#".to_string(),
+                    37,
+                    format!("{}`$` should be one of `available`, `sold`", prefix)
+                ),
+                (
+                    "#".to_string(),
+                    25,
+                    format!("{}`$` is not a valid `int32`", prefix)
+                ),
+            ]
+        );
+        Ok(())
+    }
+}
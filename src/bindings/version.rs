@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{
+    edit::FileEdits,
+    rename::{file_edits, replace},
+    resolve::Resolver,
+    yaml::{YamlNode, YamlValue},
+};
+
+pub trait VersionParser {
+    /// The `info.version` of the spec, if it has one.
+    fn get_version(&self) -> Result<Option<String>, OpenapiSchemerError>;
+
+    /// The edit that sets `info.version` to `version` in the file it is
+    /// written in.
+    fn get_version_edit(&self, version: &str) -> Result<FileEdits, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterVersionParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterVersionParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// The `info.version` node and the file it is written in, following
+    /// `info` when it is a `$ref`.
+    fn version_node(&self, resolver: &mut Resolver) -> Result<Option<(YamlNode, PathBuf)>> {
+        let root_path = PathBuf::from("#");
+        let info = match resolver.document(&root_path)?.get("info") {
+            Some(info) => info.to_owned(),
+            None => return Ok(None),
+        };
+        let (info, file) = resolver.follow(&info, &root_path)?;
+        Ok(info
+            .get("version")
+            .map(|version| (version.to_owned(), file)))
+    }
+
+    fn version_edit(&self, version: &str) -> Result<FileEdits> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let (node, file) = match self.version_node(&mut resolver)? {
+            Some(found) => found,
+            None => bail!("The spec has no `info.version` to update"),
+        };
+        let scalar = match &node.value {
+            YamlValue::Scalar(scalar) => scalar,
+            _ => bail!("`info.version` is not a string"),
+        };
+        let edit = replace(&node, scalar, version);
+        file_edits(self.provider.as_ref(), Path::new(&file), vec![edit])
+    }
+}
+
+impl VersionParser for TreeSitterVersionParser {
+    fn get_version(&self) -> Result<Option<String>, OpenapiSchemerError> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        self.version_node(&mut resolver)
+            .map(|found| found.and_then(|(node, _)| node.as_str().map(str::to_owned)))
+            .map_err(|error| {
+                OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::VersionSuggest)
+            })
+    }
+
+    fn get_version_edit(&self, version: &str) -> Result<FileEdits, OpenapiSchemerError> {
+        self.version_edit(version).map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::VersionSuggest)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::version::{TreeSitterVersionParser, VersionParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_version_edit() -> Result<(), Box<dyn Error>> {
+        let root_content = "openapi: 3.0.0\ninfo:\n  $ref: 'info.yaml'\n";
+        let info_content = "title: Petstore\nversion: \"1.2.0\" # released\n";
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("info.yaml"), info_content.to_owned()),
+        ]);
+        let parser = TreeSitterVersionParser::new(Box::new(ContentProviderMap::from_map(contents)));
+        assert_eq!(parser.get_version()?, Some("1.2.0".to_string()));
+        let edits = parser.get_version_edit("2.0.0")?;
+        assert_eq!(edits.file, PathBuf::from("info.yaml"));
+        assert_eq!(
+            edits.content,
+            "title: Petstore\nversion: \"2.0.0\" # released\n"
+        );
+        assert_eq!((edits.edits[0].line, edits.edits[0].column), (2, 10));
+
+        let contents = HashMap::from([(PathBuf::from("#"), "openapi: 3.0.0\n".to_owned())]);
+        let parser = TreeSitterVersionParser::new(Box::new(ContentProviderMap::from_map(contents)));
+        assert_eq!(parser.get_version()?, None);
+        assert_eq!(
+            parser.get_version_edit("2.0.0").unwrap_err().to_string(),
+            "The spec has no `info.version` to update"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{content::ContentProvider, error::OpenapiSchemerError};
+
+use super::{resolve::Resolver, validate::HTTP_METHODS};
+
+/// One operation of a webhook under the OpenAPI 3.1 `webhooks` key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookNode {
+    /// The name of the webhook, such as `newPet`.
+    pub text: String,
+    pub method: String,
+    pub operation_id: Option<String>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub trait WebhookParser {
+    fn get_webhook_nodes(&self) -> Result<Vec<WebhookNode>, OpenapiSchemerError>;
+}
+
+pub struct TreeSitterWebhookParser {
+    provider: Box<dyn ContentProvider>,
+}
+
+impl TreeSitterWebhookParser {
+    pub fn new(provider: Box<dyn ContentProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn webhook_nodes(&self) -> Result<Vec<WebhookNode>> {
+        let mut resolver = Resolver::new(self.provider.as_ref());
+        let mut results = vec![];
+        for webhook in resolver.entries("/webhooks")? {
+            for pair in webhook.value.as_mapping().unwrap_or_default() {
+                let method = match pair.key.as_str() {
+                    Some(method) if HTTP_METHODS.contains(&method) => method,
+                    _ => continue,
+                };
+                let (operation, _) = resolver.follow(&pair.value, &webhook.file)?;
+                results.push(WebhookNode {
+                    text: webhook.name.to_owned(),
+                    method: method.to_string(),
+                    operation_id: operation
+                        .get("operationId")
+                        .and_then(|id| id.as_str())
+                        .map(str::to_owned),
+                    file: self.provider.source_path(&webhook.file),
+                    line: pair.key.start.row + 1,
+                    column: pair.key.start.column + 1,
+                });
+            }
+        }
+        Ok(results)
+    }
+}
+
+impl WebhookParser for TreeSitterWebhookParser {
+    fn get_webhook_nodes(&self) -> Result<Vec<WebhookNode>, OpenapiSchemerError> {
+        self.webhook_nodes().map_err(|error| {
+            OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::WebhookList)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::webhook::{TreeSitterWebhookParser, WebhookParser},
+        content::ContentProviderMap,
+    };
+
+    #[test]
+    fn get_webhook_nodes() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+openapi: 3.1.0
+webhooks:
+  newPet:
+    post:
+      operationId: newPetHook
+      responses:
+        '200':
+          description: Received
+  petGone:
+    $ref: 'webhooks.yaml#/PetGone'
+"#;
+        let webhooks_content = r#"
+PetGone:
+  summary: A pet left
+  delete:
+    responses:
+      '204':
+        description: Received
+"#;
+        let contents = HashMap::from([
+            (PathBuf::from("#"), root_content.to_owned()),
+            (PathBuf::from("webhooks.yaml"), webhooks_content.to_owned()),
+        ]);
+        let provider = Box::new(ContentProviderMap::from_map(contents));
+        let nodes = TreeSitterWebhookParser::new(provider).get_webhook_nodes()?;
+        let summary: Vec<(&str, &str, Option<&str>, &str, usize)> = nodes
+            .iter()
+            .map(|node| {
+                (
+                    node.text.as_str(),
+                    node.method.as_str(),
+                    node.operation_id.as_deref(),
+                    node.file.to_str().unwrap_or_default(),
+                    node.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("newPet", "post", Some("newPetHook"), "#", 5),
+                ("petGone", "delete", None, "webhooks.yaml", 4),
+            ]
+        );
+        Ok(())
+    }
+}
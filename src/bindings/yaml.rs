@@ -0,0 +1,1208 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use anyhow::{Error, Result};
+use tree_sitter::{Node, Parser, Point, Tree};
+
+use super::language;
+
+/// An owned copy of a YAML node extracted from a tree-sitter tree. These keep
+/// the position of every node so results can point back at the source
+/// document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct YamlNode {
+    pub value: YamlValue,
+    pub anchor: Option<String>,
+    /// Written after the node by `to_yaml`, such as the file a bundled block
+    /// came from. Comments of the source document are not kept.
+    pub comment: Option<String>,
+    pub start: Point,
+    pub bytes: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum YamlValue {
+    Mapping(Vec<YamlPair>),
+    Sequence(Vec<YamlNode>),
+    Scalar(Scalar),
+    Alias(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YamlPair {
+    pub key: YamlNode,
+    pub value: YamlNode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scalar {
+    pub text: String,
+    pub style: ScalarStyle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarStyle {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    Literal,
+    Folded,
+}
+
+impl YamlNode {
+    pub fn new(value: YamlValue) -> Self {
+        YamlNode {
+            value,
+            anchor: None,
+            comment: None,
+            start: Point::default(),
+            bytes: 0..0,
+        }
+    }
+
+    pub fn null() -> Self {
+        YamlNode::new(YamlValue::Scalar(Scalar {
+            text: String::new(),
+            style: ScalarStyle::Plain,
+        }))
+    }
+
+    /// Creates a string scalar, quoting it only when a plain scalar would be
+    /// read back as something else.
+    pub fn string(text: &str) -> Self {
+        let style = if needs_quotes(text) {
+            ScalarStyle::SingleQuoted
+        } else {
+            ScalarStyle::Plain
+        };
+        YamlNode::new(YamlValue::Scalar(Scalar {
+            text: text.to_string(),
+            style,
+        }))
+    }
+
+    pub fn mapping(pairs: Vec<(&str, YamlNode)>) -> Self {
+        YamlNode::new(YamlValue::Mapping(
+            pairs
+                .into_iter()
+                .map(|(key, value)| YamlPair {
+                    key: YamlNode::string(key),
+                    value,
+                })
+                .collect(),
+        ))
+    }
+
+    pub fn sequence(items: Vec<YamlNode>) -> Self {
+        YamlNode::new(YamlValue::Sequence(items))
+    }
+
+    pub fn integer(value: usize) -> Self {
+        YamlNode::new(YamlValue::Scalar(Scalar {
+            text: value.to_string(),
+            style: ScalarStyle::Plain,
+        }))
+    }
+
+    pub fn boolean(value: bool) -> Self {
+        YamlNode::new(YamlValue::Scalar(Scalar {
+            text: value.to_string(),
+            style: ScalarStyle::Plain,
+        }))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            YamlValue::Scalar(scalar) => Some(&scalar.text),
+            _ => None,
+        }
+    }
+
+    /// The JSON type the node is read as, with plain scalars typed the way
+    /// `to_json` writes them: `null`, `boolean`, `integer`, `number`,
+    /// `string`, `array` or `object`.
+    pub fn json_type(&self) -> &'static str {
+        match &self.value {
+            YamlValue::Mapping(_) => "object",
+            YamlValue::Sequence(_) => "array",
+            YamlValue::Scalar(scalar) if scalar.style == ScalarStyle::Plain => {
+                match scalar.text.as_str() {
+                    "" | "~" | "null" | "Null" | "NULL" => "null",
+                    "true" | "True" | "TRUE" | "false" | "False" | "FALSE" => "boolean",
+                    text if is_json_number(text) && !text.contains(['.', 'e', 'E']) => "integer",
+                    text if is_json_number(text) => "number",
+                    _ => "string",
+                }
+            }
+            YamlValue::Scalar(_) | YamlValue::Alias(_) => "string",
+        }
+    }
+
+    /// Whether the node is an empty value or a plain `null`/`~` scalar.
+    pub fn is_null(&self) -> bool {
+        match &self.value {
+            YamlValue::Scalar(scalar) => {
+                scalar.style == ScalarStyle::Plain
+                    && matches!(scalar.text.as_str(), "" | "~" | "null" | "Null" | "NULL")
+            }
+            _ => false,
+        }
+    }
+
+    pub fn as_mapping(&self) -> Option<&[YamlPair]> {
+        match &self.value {
+            YamlValue::Mapping(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+
+    pub fn as_sequence(&self) -> Option<&[YamlNode]> {
+        match &self.value {
+            YamlValue::Sequence(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Looks up the value for `key` when this node is a mapping.
+    pub fn get(&self, key: &str) -> Option<&YamlNode> {
+        self.as_mapping()?
+            .iter()
+            .find(|pair| pair.key.as_str() == Some(key))
+            .map(|pair| &pair.value)
+    }
+
+    /// Like `get`, for changing the value in place.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut YamlNode> {
+        match &mut self.value {
+            YamlValue::Mapping(pairs) => pairs
+                .iter_mut()
+                .find(|pair| pair.key.as_str() == Some(key))
+                .map(|pair| &mut pair.value),
+            _ => None,
+        }
+    }
+
+    /// Walks a JSON pointer (`/components/schemas/Pet`) down from this node.
+    /// The empty pointer refers to the node itself.
+    pub fn pointer(&self, pointer: &str) -> Option<&YamlNode> {
+        let mut current = self;
+        for segment in pointer_segments(pointer)? {
+            current = current.child(&segment)?;
+        }
+        Some(current)
+    }
+
+    /// A single step of a JSON pointer: the value for a mapping key or the
+    /// item at a sequence index.
+    pub fn child(&self, segment: &str) -> Option<&YamlNode> {
+        match &self.value {
+            YamlValue::Mapping(_) => self.get(segment),
+            YamlValue::Sequence(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    /// Like `child`, for changing the value in place.
+    pub fn child_mut(&mut self, segment: &str) -> Option<&mut YamlNode> {
+        match &mut self.value {
+            YamlValue::Mapping(pairs) => pairs
+                .iter_mut()
+                .find(|pair| pair.key.as_str() == Some(segment))
+                .map(|pair| &mut pair.value),
+            YamlValue::Sequence(items) => items.get_mut(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    /// The `$ref` value when this node is a reference object.
+    pub fn reference(&self) -> Option<&str> {
+        self.get("$ref")?.as_str()
+    }
+
+    /// Every `$ref` value in this node and its descendants, in document order.
+    pub fn references(&self) -> Vec<&str> {
+        let mut references = vec![];
+        if let Some(reference) = self.reference() {
+            references.push(reference);
+        }
+        match &self.value {
+            YamlValue::Mapping(pairs) => {
+                for pair in pairs {
+                    references.extend(pair.value.references());
+                }
+            }
+            YamlValue::Sequence(items) => {
+                for item in items {
+                    references.extend(item.references());
+                }
+            }
+            _ => {}
+        }
+        references
+    }
+
+    /// Renders the node as a block style YAML document.
+    pub fn to_yaml(&self) -> String {
+        let mut out = String::new();
+        if let Some(comment) = &self.comment {
+            out.push_str(&format!("# {}\n", comment));
+        }
+        match &self.value {
+            YamlValue::Mapping(pairs) if !pairs.is_empty() => {
+                if let Some(anchor) = &self.anchor {
+                    out.push_str(&format!("&{}\n", anchor));
+                }
+                emit_mapping(pairs, 0, &mut out)
+            }
+            YamlValue::Sequence(items) if !items.is_empty() => {
+                if let Some(anchor) = &self.anchor {
+                    out.push_str(&format!("&{}\n", anchor));
+                }
+                emit_sequence(items, 0, &mut out)
+            }
+            _ => {
+                out.push_str(emit_inline(self, 0).trim_start());
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Renders the node as pretty printed JSON. Plain scalars are typed the
+    /// way YAML would read them, everything else becomes a string.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        emit_json(self, 0, &mut out);
+        out
+    }
+}
+
+/// `document` with every `*alias` replaced by a copy of the node its anchor is
+/// set on, and the `<<` merge keys of mappings replaced by the pairs they merge
+/// in. Keys written in the mapping itself win over merged ones, and earlier
+/// mappings of a merged list win over later ones. An alias refers to the last
+/// anchor of its name before it, aliases without one are left as they are.
+/// The anchors themselves are dropped, so the document writes out on its own.
+pub fn resolve_aliases(document: &YamlNode) -> YamlNode {
+    resolve_node(document, &mut HashMap::new())
+}
+
+fn resolve_node(node: &YamlNode, anchors: &mut HashMap<String, YamlNode>) -> YamlNode {
+    let value = match &node.value {
+        YamlValue::Alias(name) => {
+            return anchors
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| node.to_owned())
+        }
+        YamlValue::Mapping(pairs) => {
+            let pairs: Vec<YamlPair> = pairs
+                .iter()
+                .map(|pair| YamlPair {
+                    key: resolve_node(&pair.key, anchors),
+                    value: resolve_node(&pair.value, anchors),
+                })
+                .collect();
+            YamlValue::Mapping(merge_pairs(pairs))
+        }
+        YamlValue::Sequence(items) => YamlValue::Sequence(
+            items
+                .iter()
+                .map(|item| resolve_node(item, anchors))
+                .collect(),
+        ),
+        YamlValue::Scalar(_) => node.value.to_owned(),
+    };
+    let resolved = YamlNode {
+        value,
+        anchor: None,
+        ..node.to_owned()
+    };
+    if let Some(anchor) = &node.anchor {
+        anchors.insert(anchor.to_owned(), resolved.to_owned());
+    }
+    resolved
+}
+
+/// `pairs` with its `<<` merge keys replaced by the pairs of the mappings
+/// they name.
+fn merge_pairs(pairs: Vec<YamlPair>) -> Vec<YamlPair> {
+    let is_merge = |pair: &YamlPair| match &pair.key.value {
+        YamlValue::Scalar(scalar) => scalar.style == ScalarStyle::Plain && scalar.text == "<<",
+        _ => false,
+    };
+    if !pairs.iter().any(is_merge) {
+        return pairs;
+    }
+    let mut seen: HashSet<String> = pairs
+        .iter()
+        .filter(|pair| !is_merge(pair))
+        .filter_map(|pair| pair.key.as_str().map(str::to_owned))
+        .collect();
+    let mut merged = vec![];
+    for pair in pairs {
+        if !is_merge(&pair) {
+            merged.push(pair);
+            continue;
+        }
+        let sources = match pair.value.value {
+            YamlValue::Sequence(items) => items,
+            _ => vec![pair.value],
+        };
+        for source in sources {
+            let source_pairs = match source.value {
+                YamlValue::Mapping(source_pairs) => source_pairs,
+                _ => continue,
+            };
+            for source_pair in source_pairs {
+                let key = source_pair.key.as_str().unwrap_or_default().to_string();
+                if seen.insert(key) {
+                    merged.push(source_pair);
+                }
+            }
+        }
+    }
+    merged
+}
+
+pub fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn unescape_pointer_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// Splits a JSON pointer, as found in the fragment of a `$ref`, into its
+/// unescaped segments. Fragments are URI encoded so `%7B` and friends are
+/// decoded as well. Returns `None` for pointers that do not start with `/`.
+pub fn pointer_segments(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(vec![]);
+    }
+    let segments = pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(|segment| unescape_pointer_segment(&percent_decode(segment)))
+        .collect();
+    Some(segments)
+}
+
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = match bytes.get(index + 1..index + 3) {
+            Some(hex) if bytes[index] == b'%' => std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses `content` into a `YamlNode`. Only the first document of a stream is
+/// considered, which is all an OpenAPI file should contain.
+pub fn parse_yaml(content: &str) -> Result<YamlNode> {
+    tree_to_yaml(&parse_tree(content, None)?, content)
+}
+
+/// Parses `content` into a tree-sitter tree. Passing the tree of an earlier
+/// version of `content`, already adjusted with `Tree::edit`, lets tree-sitter
+/// reuse the parts that didn't change.
+pub fn parse_tree(content: &str, old_tree: Option<&Tree>) -> Result<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(language())?;
+    parser
+        .parse(content, old_tree)
+        .ok_or_else(|| Error::msg("Could not parse tree"))
+}
+
+/// Converts `tree`, parsed from `content`, into a `YamlNode`.
+pub fn tree_to_yaml(tree: &Tree, content: &str) -> Result<YamlNode> {
+    match document_root(tree) {
+        Some(root) => convert(root, content.as_bytes()),
+        None => Ok(YamlNode::null()),
+    }
+}
+
+/// The top level node of the first document in `tree`.
+pub fn document_root(tree: &Tree) -> Option<Node<'_>> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for document in root.named_children(&mut cursor) {
+        if document.kind() != "document" {
+            continue;
+        }
+        let mut document_cursor = document.walk();
+        for child in document.named_children(&mut document_cursor) {
+            if child.kind() == "block_node" || child.kind() == "flow_node" {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+fn convert(node: Node, source: &[u8]) -> Result<YamlNode> {
+    let value = match node.kind() {
+        "block_node" | "flow_node" => {
+            let mut anchor = None;
+            let mut content = None;
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                match child.kind() {
+                    "anchor" => {
+                        anchor = Some(text(child, source)?.trim_start_matches('&').to_string())
+                    }
+                    "tag" | "comment" => {}
+                    _ => content = Some(child),
+                }
+            }
+            let mut converted = match content {
+                Some(content) => convert(content, source)?,
+                None => YamlNode::null(),
+            };
+            converted.anchor = anchor;
+            converted.start = node.start_position();
+            converted.bytes = node.byte_range();
+            return Ok(converted);
+        }
+        "block_mapping" => {
+            let mut pairs = vec![];
+            let mut cursor = node.walk();
+            for pair in node.named_children(&mut cursor) {
+                if pair.kind() == "block_mapping_pair" {
+                    pairs.push(convert_pair(pair, source)?);
+                }
+            }
+            YamlValue::Mapping(pairs)
+        }
+        "flow_mapping" => {
+            let mut pairs = vec![];
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                match child.kind() {
+                    "flow_pair" => pairs.push(convert_pair(child, source)?),
+                    "flow_node" => pairs.push(YamlPair {
+                        key: convert(child, source)?,
+                        value: null_at(child.end_position(), child.end_byte()),
+                    }),
+                    _ => {}
+                }
+            }
+            YamlValue::Mapping(pairs)
+        }
+        "block_sequence" => {
+            let mut items = vec![];
+            let mut cursor = node.walk();
+            for item in node.named_children(&mut cursor) {
+                if item.kind() != "block_sequence_item" {
+                    continue;
+                }
+                let mut item_cursor = item.walk();
+                let content = item
+                    .named_children(&mut item_cursor)
+                    .find(|child| child.kind() != "comment");
+                items.push(match content {
+                    Some(content) => convert(content, source)?,
+                    None => null_at(item.end_position(), item.end_byte()),
+                });
+            }
+            YamlValue::Sequence(items)
+        }
+        "flow_sequence" => {
+            let mut items = vec![];
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                match child.kind() {
+                    "flow_node" => items.push(convert(child, source)?),
+                    "flow_pair" => {
+                        let mut item =
+                            YamlNode::new(YamlValue::Mapping(vec![convert_pair(child, source)?]));
+                        item.start = child.start_position();
+                        item.bytes = child.byte_range();
+                        items.push(item);
+                    }
+                    _ => {}
+                }
+            }
+            YamlValue::Sequence(items)
+        }
+        "alias" => YamlValue::Alias(text(node, source)?.trim_start_matches('*').to_string()),
+        "plain_scalar" => YamlValue::Scalar(Scalar {
+            text: fold_lines(text(node, source)?),
+            style: ScalarStyle::Plain,
+        }),
+        "single_quote_scalar" => {
+            let raw = text(node, source)?;
+            let inner = &raw[1..raw.len() - 1];
+            YamlValue::Scalar(Scalar {
+                text: fold_lines(inner).replace("''", "'"),
+                style: ScalarStyle::SingleQuoted,
+            })
+        }
+        "double_quote_scalar" => {
+            let raw = text(node, source)?;
+            YamlValue::Scalar(Scalar {
+                text: unescape_double_quoted(&fold_lines(&raw[1..raw.len() - 1])),
+                style: ScalarStyle::DoubleQuoted,
+            })
+        }
+        "block_scalar" => {
+            let raw = text(node, source)?;
+            YamlValue::Scalar(Scalar {
+                text: decode_block_scalar(raw),
+                style: if raw.starts_with('>') {
+                    ScalarStyle::Folded
+                } else {
+                    ScalarStyle::Literal
+                },
+            })
+        }
+        _ => YamlValue::Scalar(Scalar {
+            text: text(node, source)?.to_string(),
+            style: ScalarStyle::Plain,
+        }),
+    };
+    Ok(YamlNode {
+        value,
+        anchor: None,
+        comment: None,
+        start: node.start_position(),
+        bytes: node.byte_range(),
+    })
+}
+
+fn convert_pair(pair: Node, source: &[u8]) -> Result<YamlPair> {
+    let key = match pair.child_by_field_name("key") {
+        Some(key) => convert(key, source)?,
+        None => null_at(pair.start_position(), pair.start_byte()),
+    };
+    let value = match pair.child_by_field_name("value") {
+        Some(value) => convert(value, source)?,
+        None => null_at(pair.end_position(), pair.end_byte()),
+    };
+    Ok(YamlPair { key, value })
+}
+
+fn null_at(point: Point, byte: usize) -> YamlNode {
+    let mut node = YamlNode::null();
+    node.start = point;
+    node.bytes = byte..byte;
+    node
+}
+
+fn text<'a>(node: Node, source: &'a [u8]) -> Result<&'a str> {
+    Ok(node.utf8_text(source)?)
+}
+
+/// Multi-line flow scalars are folded into a single line, with empty lines
+/// becoming newlines.
+fn fold_lines(raw: &str) -> String {
+    if !raw.contains('\n') {
+        return raw.to_string();
+    }
+    let mut folded = String::new();
+    let mut previous_empty = false;
+    for (index, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            folded.push('\n');
+            previous_empty = true;
+            continue;
+        }
+        if index > 0 && !previous_empty {
+            folded.push(' ');
+        }
+        folded.push_str(line);
+        previous_empty = false;
+    }
+    folded
+}
+
+fn unescape_double_quoted(raw: &str) -> String {
+    let mut result = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('0') => result.push('\0'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    result.push(c);
+                }
+            }
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+fn decode_block_scalar(raw: &str) -> String {
+    let mut lines = raw.lines();
+    let header = lines.next().unwrap_or_default();
+    let literal = header.starts_with('|');
+    let indicators = header.split('#').next().unwrap_or_default();
+    let lines: Vec<&str> = lines.collect();
+    let indent = lines
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| {
+            if line.len() > indent {
+                &line[indent..]
+            } else {
+                line.trim()
+            }
+        })
+        .collect();
+
+    let mut body = String::new();
+    if literal {
+        for line in &lines {
+            body.push_str(line);
+            body.push('\n');
+        }
+    } else {
+        let mut previous_text = false;
+        for line in &lines {
+            if line.is_empty() {
+                body.push('\n');
+                previous_text = false;
+            } else {
+                if previous_text {
+                    body.push(' ');
+                }
+                body.push_str(line);
+                previous_text = true;
+            }
+        }
+        body.push('\n');
+    }
+
+    if indicators.contains('-') {
+        body.trim_end_matches('\n').to_string()
+    } else if indicators.contains('+') {
+        body
+    } else {
+        let trimmed = body.trim_end_matches('\n');
+        if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", trimmed)
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn is_json_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let (integer, rest) = digits.split_at(
+        digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len()),
+    );
+    if integer.is_empty() || (integer.len() > 1 && integer.starts_with('0')) {
+        return false;
+    }
+    rest.is_empty() || (rest.parse::<f64>().is_ok() && text.parse::<f64>().is_ok())
+}
+
+fn emit_json(node: &YamlNode, indent: usize, out: &mut String) {
+    let padding = " ".repeat(indent + 2);
+    match &node.value {
+        YamlValue::Mapping(pairs) if !pairs.is_empty() => {
+            out.push_str("{\n");
+            for (index, pair) in pairs.iter().enumerate() {
+                out.push_str(&padding);
+                out.push_str(&json_string(pair.key.as_str().unwrap_or_default()));
+                out.push_str(": ");
+                emit_json(&pair.value, indent + 2, out);
+                if index + 1 < pairs.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&" ".repeat(indent));
+            out.push('}');
+        }
+        YamlValue::Mapping(_) => out.push_str("{}"),
+        YamlValue::Sequence(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (index, item) in items.iter().enumerate() {
+                out.push_str(&padding);
+                emit_json(item, indent + 2, out);
+                if index + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&" ".repeat(indent));
+            out.push(']');
+        }
+        YamlValue::Sequence(_) => out.push_str("[]"),
+        YamlValue::Alias(name) => out.push_str(&json_string(&format!("*{}", name))),
+        YamlValue::Scalar(scalar) if scalar.style == ScalarStyle::Plain => {
+            match scalar.text.as_str() {
+                "" | "~" | "null" | "Null" | "NULL" => out.push_str("null"),
+                "true" | "True" | "TRUE" => out.push_str("true"),
+                "false" | "False" | "FALSE" => out.push_str("false"),
+                text if is_json_number(text) => out.push_str(text),
+                text => out.push_str(&json_string(text)),
+            }
+        }
+        YamlValue::Scalar(scalar) => out.push_str(&json_string(&scalar.text)),
+    }
+}
+
+fn needs_quotes(text: &str) -> bool {
+    if text.is_empty() || text.trim() != text {
+        return true;
+    }
+    if matches!(
+        text.to_lowercase().as_str(),
+        "null" | "~" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+    ) {
+        return true;
+    }
+    if text.parse::<f64>().is_ok() {
+        return true;
+    }
+    let first = text.chars().next().unwrap_or_default();
+    if "-?:,[]{}#&*!|>'\"%@`".contains(first) {
+        return true;
+    }
+    text.contains(": ") || text.contains(" #") || text.ends_with(':') || text.contains('\n')
+}
+
+/// `text` written as a scalar in the quoting style of `scalar`, for replacing
+/// the value of `scalar` in place. Falls back to single quotes when a plain
+/// scalar would be read back as something else.
+pub fn scalar_like(scalar: &Scalar, text: &str) -> String {
+    let style = match scalar.style {
+        ScalarStyle::SingleQuoted | ScalarStyle::DoubleQuoted => scalar.style,
+        _ if needs_quotes(text) => ScalarStyle::SingleQuoted,
+        _ => ScalarStyle::Plain,
+    };
+    let scalar = Scalar {
+        text: text.to_string(),
+        style,
+    };
+    emit_scalar(&scalar, 0)
+}
+
+fn emit_scalar(scalar: &Scalar, indent: usize) -> String {
+    if scalar.text.contains('\n')
+        || matches!(scalar.style, ScalarStyle::Literal | ScalarStyle::Folded)
+    {
+        let chomp = if !scalar.text.ends_with('\n') {
+            "-"
+        } else if scalar.text.ends_with("\n\n") {
+            "+"
+        } else {
+            ""
+        };
+        let indicator = if scalar.text.starts_with(' ') {
+            "2"
+        } else {
+            ""
+        };
+        let mut out = format!("|{}{}", indicator, chomp);
+        let body = scalar.text.strip_suffix('\n').unwrap_or(&scalar.text);
+        for line in body.split('\n') {
+            out.push('\n');
+            if !line.is_empty() {
+                out.push_str(&" ".repeat(indent + 2));
+                out.push_str(line);
+            }
+        }
+        return out;
+    }
+    match scalar.style {
+        ScalarStyle::Plain => scalar.text.to_owned(),
+        ScalarStyle::SingleQuoted => format!("'{}'", scalar.text.replace('\'', "''")),
+        _ => format!(
+            "\"{}\"",
+            scalar
+                .text
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\t', "\\t")
+                .replace('\r', "\\r")
+        ),
+    }
+}
+
+/// Renders a node that fits on the remainder of the current line, including
+/// empty collections.
+fn emit_inline(node: &YamlNode, indent: usize) -> String {
+    let anchor = match &node.anchor {
+        Some(anchor) => format!(" &{}", anchor),
+        None => String::new(),
+    };
+    let value = match &node.value {
+        YamlValue::Mapping(_) => "{}".to_string(),
+        YamlValue::Sequence(_) => "[]".to_string(),
+        YamlValue::Alias(name) => format!("*{}", name),
+        YamlValue::Scalar(scalar) => emit_scalar(scalar, indent),
+    };
+    if value.is_empty() {
+        anchor
+    } else {
+        format!("{} {}", anchor, value)
+    }
+}
+
+fn emit_key(key: &YamlNode) -> String {
+    match &key.value {
+        YamlValue::Scalar(scalar) if !scalar.text.contains('\n') => emit_scalar(scalar, 0),
+        YamlValue::Scalar(scalar) => format!("\"{}\"", scalar.text.replace('\n', "\\n")),
+        YamlValue::Alias(name) => format!("*{}", name),
+        _ => "null".to_string(),
+    }
+}
+
+fn emit_value_after_indicator(value: &YamlNode, indent: usize, out: &mut String) {
+    let anchor = match &value.anchor {
+        Some(anchor) => format!(" &{}", anchor),
+        None => String::new(),
+    };
+    let comment = match &value.comment {
+        Some(comment) => format!(" # {}", comment),
+        None => String::new(),
+    };
+    match &value.value {
+        YamlValue::Mapping(pairs) if !pairs.is_empty() => {
+            out.push_str(&anchor);
+            out.push_str(&comment);
+            out.push('\n');
+            emit_mapping(pairs, indent + 2, out);
+        }
+        YamlValue::Sequence(items) if !items.is_empty() => {
+            out.push_str(&anchor);
+            out.push_str(&comment);
+            out.push('\n');
+            emit_sequence(items, indent + 2, out);
+        }
+        _ => {
+            let inline = emit_inline(value, indent);
+            // A block scalar's comment belongs on its header line.
+            match inline.split_once('\n') {
+                Some((header, body)) if !comment.is_empty() => {
+                    out.push_str(&format!("{}{}\n{}", header, comment, body))
+                }
+                _ => out.push_str(&format!("{}{}", inline, comment)),
+            }
+            out.push('\n');
+        }
+    }
+}
+
+fn emit_mapping(pairs: &[YamlPair], indent: usize, out: &mut String) {
+    for pair in pairs {
+        out.push_str(&" ".repeat(indent));
+        out.push_str(&emit_key(&pair.key));
+        out.push(':');
+        emit_value_after_indicator(&pair.value, indent, out);
+    }
+}
+
+fn emit_sequence(items: &[YamlNode], indent: usize, out: &mut String) {
+    for item in items {
+        out.push_str(&" ".repeat(indent));
+        out.push('-');
+        match &item.value {
+            YamlValue::Mapping(pairs)
+                if !pairs.is_empty() && item.anchor.is_none() && item.comment.is_none() =>
+            {
+                // The first pair shares the line with the sequence indicator
+                let mut nested = String::new();
+                emit_mapping(pairs, indent + 2, &mut nested);
+                out.push(' ');
+                out.push_str(&nested[indent + 2..]);
+            }
+            _ => emit_value_after_indicator(item, indent, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::{parse_yaml, resolve_aliases, ScalarStyle, YamlValue};
+
+    #[test]
+    fn parse_yaml_positions() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+"#;
+        let root = parse_yaml(content)?;
+        let operation_id = root.pointer("/paths/~1pets/get/operationId").unwrap();
+        assert_eq!(operation_id.as_str(), Some("listPets"));
+        assert_eq!(operation_id.start.row, 4);
+        assert_eq!(operation_id.start.column, 19);
+        Ok(())
+    }
+
+    #[test]
+    fn pointer() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+paths:
+  /pets/{petId}:
+    get:
+      parameters:
+        - name: petId
+"#;
+        let document = parse_yaml(content)?;
+        let name = document.pointer("/paths/~1pets~1%7BpetId%7D/get/parameters/0/name");
+        assert_eq!(name.and_then(|name| name.as_str()), Some("petId"));
+        assert!(document.pointer("").is_some());
+        assert!(document.pointer("paths").is_none());
+        assert!(document.pointer("/paths/missing").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_yaml_scalars() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+single: 'it''s'
+double: "tab\there"
+literal: |
+  first
+    second
+folded: >-
+  one
+  two
+empty:
+flow: {a: 1, b: [x, y]}
+"#;
+        let root = parse_yaml(content)?;
+        assert_eq!(root.get("single").unwrap().as_str(), Some("it's"));
+        assert_eq!(root.get("double").unwrap().as_str(), Some("tab\there"));
+        assert_eq!(
+            root.get("literal").unwrap().as_str(),
+            Some("first\n  second\n")
+        );
+        assert_eq!(root.get("folded").unwrap().as_str(), Some("one two"));
+        assert_eq!(root.get("empty").unwrap().as_str(), Some(""));
+        assert_eq!(root.pointer("/flow/b/1").unwrap().as_str(), Some("y"));
+        match &root.get("folded").unwrap().value {
+            YamlValue::Scalar(scalar) => assert_eq!(scalar.style, ScalarStyle::Folded),
+            _ => panic!("Expected a scalar"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn to_yaml_round_trip() -> Result<(), Box<dyn Error>> {
+        let content = r#"openapi: '3.0.0'
+paths:
+  /pets:
+    get:
+      tags:
+        - pets
+      parameters:
+        - name: limit
+          in: query
+      description: |
+        Multiple
+        lines
+components: {}
+"#;
+        let root = parse_yaml(content)?;
+        assert_eq!(root.to_yaml(), content);
+        assert_eq!(parse_yaml(&root.to_yaml())?, parse_yaml(content)?);
+        Ok(())
+    }
+
+    #[test]
+    fn to_yaml_comments() -> Result<(), Box<dyn Error>> {
+        let content = r#"paths:
+  /pets:
+    get:
+      tags:
+        - pets
+      parameters:
+        - name: limit
+      description: |
+        Multiple
+        lines
+"#;
+        let mut root = parse_yaml(content)?;
+        root.comment = Some("from: openapi.yaml:1".to_string());
+        let comment = |line: usize| Some(format!("from: pets.yaml:{}", line));
+        let pets = root
+            .get_mut("paths")
+            .and_then(|paths| paths.get_mut("/pets"));
+        let operation = pets.and_then(|pets| pets.get_mut("get")).unwrap();
+        operation.comment = comment(1);
+        for (key, line) in [("tags", 2), ("parameters", 4)] {
+            if let Some(YamlValue::Sequence(items)) =
+                operation.get_mut(key).map(|node| &mut node.value)
+            {
+                items[0].comment = comment(line);
+            }
+        }
+        if let Some(description) = operation.get_mut("description") {
+            description.comment = comment(6);
+        }
+        let expected = r#"# from: openapi.yaml:1
+paths:
+  /pets:
+    get: # from: pets.yaml:1
+      tags:
+        - pets # from: pets.yaml:2
+      parameters:
+        - # from: pets.yaml:4
+          name: limit
+      description: | # from: pets.yaml:6
+        Multiple
+        lines
+"#;
+        assert_eq!(root.to_yaml(), expected);
+        assert_eq!(parse_yaml(expected)?.to_yaml(), content);
+        Ok(())
+    }
+
+    #[test]
+    fn to_json() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+openapi: '3.0.0'
+paths:
+  /pets:
+    get:
+      deprecated: false
+      x-rate-limit: 100
+      x-version: 1.0.0
+      x-empty:
+      tags: [pets, "with \"quotes\""]
+"#;
+        let expected = r#"{
+  "openapi": "3.0.0",
+  "paths": {
+    "/pets": {
+      "get": {
+        "deprecated": false,
+        "x-rate-limit": 100,
+        "x-version": "1.0.0",
+        "x-empty": null,
+        "tags": [
+          "pets",
+          "with \"quotes\""
+        ]
+      }
+    }
+  }
+}"#;
+        assert_eq!(parse_yaml(content)?.to_json(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_aliases_and_merge_keys() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+x-defaults:
+  error: &error
+    type: object
+    properties:
+      code: &code
+        type: integer
+  paging: &paging
+    limit: 20
+    cursor: ''
+Error: *error
+Page:
+  limit: 50
+  <<: [*paging, {limit: 10, offset: 0}]
+Code:
+  <<: *code
+  format: int32
+Unknown: *missing
+"#;
+        let expected = r#"{
+  "x-defaults": {
+    "error": {
+      "type": "object",
+      "properties": {
+        "code": {
+          "type": "integer"
+        }
+      }
+    },
+    "paging": {
+      "limit": 20,
+      "cursor": ""
+    }
+  },
+  "Error": {
+    "type": "object",
+    "properties": {
+      "code": {
+        "type": "integer"
+      }
+    }
+  },
+  "Page": {
+    "limit": 50,
+    "cursor": "",
+    "offset": 0
+  },
+  "Code": {
+    "type": "integer",
+    "format": "int32"
+  },
+  "Unknown": "*missing"
+}"#;
+        let resolved = resolve_aliases(&parse_yaml(content)?);
+        assert_eq!(resolved.to_json(), expected);
+        assert_eq!(resolved.get("Error").unwrap().anchor, None);
+        assert_eq!(resolved.pointer("/x-defaults/error").unwrap().anchor, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+#[cfg(unix)]
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    process::Command,
+};
+
+use crate::{
+    bindings::{
+        browse::{BrowseItem, BrowseParser, Pane},
+        fuzzy_score,
+    },
+    error::OpenapiSchemerError,
+};
+
+/// How many lines PageUp and PageDown scroll the preview by.
+const PREVIEW_STEP: usize = 10;
+
+const HELP: &str = "Up/Down select  Tab pane  PgUp/PgDn scroll  Enter print  Esc quit";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Tab,
+    BackTab,
+    Enter,
+    Backspace,
+    Esc,
+    Interrupt,
+    Char(char),
+}
+
+/// Splits what the terminal sent in raw mode into keys. Escape sequences the
+/// browser has no use for are dropped.
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut keys = vec![];
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' => match chars.next_if(|c| *c == '[' || *c == 'O') {
+                None => Some(Key::Esc),
+                Some(_) => {
+                    let mut sequence = String::new();
+                    for c in chars.by_ref() {
+                        sequence.push(c);
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                    match sequence.as_str() {
+                        "A" => Some(Key::Up),
+                        "B" => Some(Key::Down),
+                        "Z" => Some(Key::BackTab),
+                        "5~" => Some(Key::PageUp),
+                        "6~" => Some(Key::PageDown),
+                        _ => None,
+                    }
+                }
+            },
+            '\t' => Some(Key::Tab),
+            '\r' | '\n' => Some(Key::Enter),
+            '\x7f' | '\x08' => Some(Key::Backspace),
+            '\x03' | '\x04' => Some(Key::Interrupt),
+            '\x10' => Some(Key::Up),
+            '\x0e' => Some(Key::Down),
+            c if c.is_control() => None,
+            c => Some(Key::Char(c)),
+        };
+        keys.extend(key);
+    }
+    keys
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Quit,
+    /// Leave the browser and print the name of the selected item.
+    Pick(String),
+}
+
+/// The state of the browser, kept apart from the terminal so it can be
+/// driven by keys and rendered to lines on its own.
+pub struct Browser {
+    items: Vec<BrowseItem>,
+    pane: Pane,
+    query: String,
+    selected: usize,
+    /// The first preview line shown.
+    scroll: usize,
+}
+
+impl Browser {
+    pub fn new(items: Vec<BrowseItem>) -> Self {
+        Self {
+            items,
+            pane: Pane::Paths,
+            query: String::new(),
+            selected: 0,
+            scroll: 0,
+        }
+    }
+
+    /// The items of the current pane that match the query, best match first
+    /// and in document order otherwise.
+    fn matches(&self) -> Vec<&BrowseItem> {
+        let mut matches: Vec<(usize, &BrowseItem)> = self
+            .items
+            .iter()
+            .filter(|item| item.pane == self.pane)
+            .filter_map(|item| fuzzy_score(&self.query, &item.label).map(|score| (score, item)))
+            .collect();
+        matches.sort_by(|(left, _), (right, _)| right.cmp(left));
+        matches.into_iter().map(|(_, item)| item).collect()
+    }
+
+    fn preview(&self) -> Vec<String> {
+        self.matches()
+            .get(self.selected)
+            .map(|item| item.preview.to_yaml().lines().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    fn switch(&mut self, offset: usize) {
+        let index = Pane::ALL.iter().position(|pane| *pane == self.pane);
+        let index = (index.unwrap_or_default() + offset) % Pane::ALL.len();
+        self.pane = Pane::ALL[index];
+        self.select_first();
+    }
+
+    fn select_first(&mut self) {
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    pub fn handle(&mut self, key: Key) -> Action {
+        let count = self.matches().len();
+        match key {
+            Key::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                self.scroll = 0;
+            }
+            Key::Down => {
+                self.selected = (self.selected + 1).min(count.saturating_sub(1));
+                self.scroll = 0;
+            }
+            Key::PageUp => self.scroll = self.scroll.saturating_sub(PREVIEW_STEP),
+            Key::PageDown => {
+                let last = self.preview().len().saturating_sub(1);
+                self.scroll = (self.scroll + PREVIEW_STEP).min(last);
+            }
+            Key::Tab => self.switch(1),
+            Key::BackTab => self.switch(Pane::ALL.len() - 1),
+            Key::Enter => {
+                if let Some(item) = self.matches().get(self.selected) {
+                    return Action::Pick(item.name.to_owned());
+                }
+            }
+            Key::Char(c) => {
+                self.query.push(c);
+                self.select_first();
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.select_first();
+            }
+            Key::Esc if self.query.is_empty() => return Action::Quit,
+            Key::Esc => {
+                self.query.clear();
+                self.select_first();
+            }
+            Key::Interrupt => return Action::Quit,
+        }
+        Action::Continue
+    }
+
+    /// The screen as `height` lines of `width` columns: the panes, the query,
+    /// the matching items next to the preview of the selected one, and a
+    /// line of help.
+    pub fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let mut tabs = String::new();
+        let mut room = width;
+        for pane in Pane::ALL {
+            let count = self.items.iter().filter(|item| item.pane == pane).count();
+            let tab: String = format!(" {} ({}) ", pane.title(), count)
+                .chars()
+                .take(room)
+                .collect();
+            room -= tab.chars().count();
+            match pane == self.pane {
+                true => tabs.push_str(&reverse(&tab)),
+                false => tabs.push_str(&tab),
+            }
+        }
+        tabs.push_str(&" ".repeat(room));
+        let mut lines = vec![
+            tabs,
+            fit(&format!("> {}", self.query), width),
+            "─".repeat(width),
+        ];
+
+        let rows = height.saturating_sub(lines.len() + 1);
+        let list_width = (width * 2 / 5).max(1);
+        let preview_width = width.saturating_sub(list_width + 3);
+        let matches = self.matches();
+        let offset = self.selected.saturating_sub(rows.saturating_sub(1));
+        let preview = self.preview();
+        for row in 0..rows {
+            let entry = match matches.get(offset + row) {
+                Some(item) if offset + row == self.selected => {
+                    reverse(&fit(&item.label, list_width))
+                }
+                Some(item) => fit(&item.label, list_width),
+                None => fit("", list_width),
+            };
+            let text = preview.get(self.scroll + row).map_or("", String::as_str);
+            lines.push(format!("{} │ {}", entry, fit(text, preview_width)));
+        }
+        lines.push(fit(HELP, width));
+        lines.truncate(height);
+        lines
+    }
+}
+
+/// `text` cut or padded with spaces to `width` characters.
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.push_str(&" ".repeat(width - length));
+    fitted
+}
+
+fn reverse(text: &str) -> String {
+    format!("\x1b[7m{}\x1b[0m", text)
+}
+
+/// The controlling terminal in raw mode on the alternate screen. The
+/// previous settings and screen are restored when it is dropped.
+#[cfg(unix)]
+struct Terminal {
+    tty: File,
+    settings: String,
+}
+
+#[cfg(unix)]
+impl Terminal {
+    fn open() -> std::io::Result<Self> {
+        let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        let settings = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        let mut terminal = Self { tty, settings };
+        terminal.write("\x1b[?1049h\x1b[?25l")?;
+        Ok(terminal)
+    }
+
+    /// Rows and columns, or 24 by 80 when `stty` can't tell.
+    fn size(&self) -> (usize, usize) {
+        let size = stty(&["size"]).unwrap_or_default();
+        let mut numbers = size.split_whitespace().map(str::parse::<usize>);
+        match (numbers.next(), numbers.next()) {
+            (Some(Ok(rows)), Some(Ok(columns))) if rows > 0 && columns > 0 => (rows, columns),
+            _ => (24, 80),
+        }
+    }
+
+    fn draw(&mut self, lines: &[String]) -> std::io::Result<()> {
+        let screen: Vec<String> = lines.iter().map(|line| format!("{}\x1b[K", line)).collect();
+        self.write(&format!("\x1b[H{}\x1b[J", screen.join("\r\n")))
+    }
+
+    fn read_keys(&mut self) -> std::io::Result<Vec<Key>> {
+        let mut buffer = [0; 64];
+        let read = self.tty.read(&mut buffer)?;
+        Ok(parse_keys(&buffer[..read]))
+    }
+
+    fn write(&mut self, text: &str) -> std::io::Result<()> {
+        self.tty.write_all(text.as_bytes())?;
+        self.tty.flush()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.write("\x1b[?25h\x1b[?1049l");
+        let _ = stty(&[self.settings.as_str()]);
+    }
+}
+
+/// Runs `stty` against the controlling terminal and returns what it printed.
+#[cfg(unix)]
+fn stty(args: &[&str]) -> std::io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty")?)
+        .output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => Err(std::io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// Opens the browser on the terminal and returns the name of the item picked
+/// with Enter, or `None` when the browser was left without picking one.
+#[cfg(unix)]
+pub fn browse<T: BrowseParser>(parser: T) -> Result<Option<String>, OpenapiSchemerError> {
+    let error = |error: std::io::Error| OpenapiSchemerError::Browse(error.to_string());
+    let mut browser = Browser::new(parser.get_browse_items()?);
+    let mut terminal = Terminal::open().map_err(error)?;
+    loop {
+        let (rows, columns) = terminal.size();
+        terminal
+            .draw(&browser.render(columns, rows))
+            .map_err(error)?;
+        for key in terminal.read_keys().map_err(error)? {
+            match browser.handle(key) {
+                Action::Continue => {}
+                Action::Quit => return Ok(None),
+                Action::Pick(name) => return Ok(Some(name)),
+            }
+        }
+    }
+}
+
+/// The browser drives the terminal through `stty` and `/dev/tty`, which only
+/// Unix systems have.
+#[cfg(not(unix))]
+pub fn browse<T: BrowseParser>(_parser: T) -> Result<Option<String>, OpenapiSchemerError> {
+    Err(OpenapiSchemerError::Browse(
+        "`browse` is only supported on Unix terminals".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bindings::yaml::YamlNode;
+
+    use super::*;
+
+    fn items() -> Vec<BrowseItem> {
+        let item = |pane, name: &str, label: &str, preview| BrowseItem {
+            pane,
+            name: name.to_string(),
+            label: label.to_string(),
+            preview,
+        };
+        let schema = YamlNode::mapping(vec![("type", YamlNode::string("object"))]);
+        vec![
+            item(Pane::Paths, "/pets", "/pets", YamlNode::null()),
+            item(Pane::Paths, "/owners", "/owners", YamlNode::null()),
+            item(
+                Pane::Operations,
+                "listPets",
+                "GET /pets listPets",
+                YamlNode::mapping(vec![("operationId", YamlNode::string("listPets"))]),
+            ),
+            item(Pane::Schemas, "Pet", "Pet", schema),
+        ]
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(
+            parse_keys(b"\x1b[A\x1b[Bp\xc3\xa9\x1b[5~\x1b[Z\t\r\x7f\x1b[1;5C\x1b\x03"),
+            vec![
+                Key::Up,
+                Key::Down,
+                Key::Char('p'),
+                Key::Char('é'),
+                Key::PageUp,
+                Key::BackTab,
+                Key::Tab,
+                Key::Enter,
+                Key::Backspace,
+                Key::Esc,
+                Key::Interrupt,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_handle() {
+        let mut browser = Browser::new(items());
+        assert_eq!(browser.handle(Key::Down), Action::Continue);
+        assert_eq!(browser.handle(Key::Down), Action::Continue);
+        assert_eq!(
+            browser.handle(Key::Enter),
+            Action::Pick("/owners".to_string())
+        );
+
+        browser.handle(Key::Char('o'));
+        browser.handle(Key::Char('w'));
+        assert_eq!(
+            browser.handle(Key::Enter),
+            Action::Pick("/owners".to_string())
+        );
+        browser.handle(Key::Char('x'));
+        assert_eq!(browser.handle(Key::Enter), Action::Continue);
+        assert_eq!(browser.handle(Key::Esc), Action::Continue);
+
+        browser.handle(Key::Tab);
+        assert_eq!(
+            browser.handle(Key::Enter),
+            Action::Pick("listPets".to_string())
+        );
+        browser.handle(Key::BackTab);
+        browser.handle(Key::BackTab);
+        assert_eq!(browser.handle(Key::Enter), Action::Pick("Pet".to_string()));
+        assert_eq!(browser.handle(Key::Esc), Action::Quit);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut browser = Browser::new(items());
+        browser.handle(Key::Tab);
+        browser.handle(Key::Char('l'));
+        assert_eq!(
+            browser.render(30, 6),
+            vec![
+                " Paths (2) \x1b[7m Operations (1) \x1b[0m Sc".to_string(),
+                "> l                           ".to_string(),
+                "─".repeat(30),
+                "\x1b[7mGET /pets li\x1b[0m │ operationId: li".to_string(),
+                "             │                ".to_string(),
+                "Up/Down select  Tab pane  PgUp".to_string(),
+            ]
+        );
+    }
+}
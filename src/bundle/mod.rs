@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{bundle::Bundler, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct BundleResult {
+    document: YamlNode,
+}
+
+impl BundleResult {
+    pub fn new(document: YamlNode) -> BundleResult {
+        BundleResult { document }
+    }
+}
+
+impl Display for BundleResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for BundleResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+pub fn bundle<T: Bundler>(bundler: T) -> Result<BundleResult, OpenapiSchemerError> {
+    let document = bundler.bundle()?;
+    Ok(BundleResult::new(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::bindings::yaml::YamlNode;
+
+    use super::*;
+
+    struct MockBundler {
+        document: YamlNode,
+    }
+    impl MockBundler {
+        fn new(document: YamlNode) -> MockBundler {
+            MockBundler { document }
+        }
+    }
+    impl Bundler for MockBundler {
+        fn bundle(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_bundle() -> Result<(), Box<dyn Error>> {
+        let document = YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.0"))]);
+        let bundler = MockBundler::new(document);
+        let result = bundle(bundler)?;
+        assert_eq!(result.to_string(), "openapi: 3.0.0");
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use clap_complete::Shell;
+
+use crate::{error::OpenapiSchemerError, spec::Spec};
+
+/// The names the hidden `__complete` command lists, one kind per argument
+/// that completion scripts fill in from the spec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompletionKind {
+    Operations,
+    Paths,
+    Schemas,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CompleteResult {
+    names: Vec<String>,
+}
+
+impl CompleteResult {
+    pub fn new(names: Vec<String>) -> CompleteResult {
+        CompleteResult { names }
+    }
+}
+
+impl Display for CompleteResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.names.join("\n"))
+    }
+}
+
+/// The operationIds, paths or schema names of `spec`.
+pub fn complete(spec: &Spec, kind: CompletionKind) -> Result<CompleteResult, OpenapiSchemerError> {
+    let names = match kind {
+        CompletionKind::Operations => spec
+            .operations()?
+            .into_iter()
+            .filter_map(|operation| operation.operation_id)
+            .collect(),
+        CompletionKind::Paths => spec.paths()?.into_iter().map(|path| path.text).collect(),
+        CompletionKind::Schemas => spec
+            .schemas()?
+            .into_iter()
+            .map(|schema| schema.text)
+            .collect(),
+    };
+    Ok(CompleteResult::new(names))
+}
+
+/// The completion script clap generates for `command`. The zsh and fish
+/// scripts also complete the operationIds, paths and schemas of the spec
+/// already typed on the command line, by calling `__complete` with it.
+pub fn script(shell: Shell, command: &clap::Command) -> String {
+    let name = command.get_name().to_string();
+    // Hidden subcommands such as `__complete` are left out of the script.
+    // The bash generator can't handle a `__` in a subcommand name either.
+    let visible = clap::Command::new(name.as_str());
+    // `help` is already added by `Command::new`.
+    let args: Vec<clap::Arg> = command
+        .get_arguments()
+        .filter(|arg| {
+            visible
+                .get_arguments()
+                .all(|known| known.get_id() != arg.get_id())
+        })
+        .cloned()
+        .collect();
+    let mut visible = visible.args(args).subcommands(
+        command
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .cloned(),
+    );
+    let mut out = vec![];
+    clap_complete::generate(shell, &mut visible, &name, &mut out);
+    let mut script = String::from_utf8_lossy(&out).into_owned();
+    match shell {
+        // The zsh script ends by calling the function it defines, so the
+        // wrapper has to be in place before that call.
+        Shell::Zsh => {
+            let call = format!("_{} \"$@\"", name);
+            let index = script.rfind(&call).unwrap_or(script.len());
+            script.insert_str(index, &zsh_hook(&name));
+        }
+        Shell::Fish => script.push_str(&fish_hook(&name)),
+        _ => {}
+    }
+    script
+}
+
+/// The subcommands whose first argument names something in the spec.
+const DYNAMIC: [(&str, &str, CompletionKind); 15] = [
+    ("operation", "show", CompletionKind::Operations),
+    ("operation", "callbacks", CompletionKind::Operations),
+    ("operation", "invoke", CompletionKind::Operations),
+    ("operation", "curl", CompletionKind::Operations),
+    ("operation", "security", CompletionKind::Operations),
+    ("operation", "rename", CompletionKind::Operations),
+    ("path", "show", CompletionKind::Paths),
+    ("path", "rename", CompletionKind::Paths),
+    ("schema", "show", CompletionKind::Schemas),
+    ("schema", "usages", CompletionKind::Schemas),
+    ("schema", "rename", CompletionKind::Schemas),
+    ("schema", "enums", CompletionKind::Schemas),
+    ("schema", "export-jsonschema", CompletionKind::Schemas),
+    ("schema", "resolve", CompletionKind::Schemas),
+    ("schema", "properties", CompletionKind::Schemas),
+];
+
+fn kind_name(kind: CompletionKind) -> String {
+    kind.to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Wraps the generated `_name` function so the argument after
+/// `operation show` and friends is completed from the spec, and everything
+/// else is left to clap's completion.
+fn zsh_hook(name: &str) -> String {
+    let mut cases = String::new();
+    for (command, subcommand, kind) in DYNAMIC {
+        cases.push_str(&format!(
+            "        \"{} {}\") kind={} ;;\n",
+            command,
+            subcommand,
+            kind_name(kind)
+        ));
+    }
+    format!(
+        r#"functions[_{name}_static]=$functions[_{name}]
+_{name}() {{
+    local kind
+    if (( CURRENT == 5 )); then
+        case "${{words[3]}} ${{words[4]}}" in
+{cases}        esac
+    fi
+    if [[ -n $kind ]]; then
+        local -a names
+        names=("${{(@f)$({name} ${{words[2]}} __complete $kind 2>/dev/null)}}")
+        _describe -t names $kind names
+    else
+        _{name}_static "$@"
+    fi
+}}
+
+"#,
+        name = name,
+        cases = cases
+    )
+}
+
+fn fish_hook(name: &str) -> String {
+    let mut lines = String::new();
+    for (command, subcommand, kind) in DYNAMIC {
+        lines.push_str(&format!(
+            "complete -c {name} -n \"__fish_seen_subcommand_from {command}; and __fish_seen_subcommand_from {subcommand}\" -f -a \"({name} (commandline -opc)[2] __complete {kind} 2>/dev/null)\"\n",
+            name = name,
+            command = command,
+            subcommand = subcommand,
+            kind = kind_name(kind)
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::content::ContentProviderMap;
+
+    use super::*;
+
+    #[test]
+    fn test_complete() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      summary: No operationId
+components:
+  schemas:
+    Pet:
+      type: object
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let spec = Spec::new(ContentProviderMap::from_map(contents));
+        assert_eq!(
+            complete(&spec, CompletionKind::Operations)?.to_string(),
+            "listPets"
+        );
+        assert_eq!(complete(&spec, CompletionKind::Paths)?.to_string(), "/pets");
+        assert_eq!(complete(&spec, CompletionKind::Schemas)?.to_string(), "Pet");
+        Ok(())
+    }
+
+    #[test]
+    fn test_script() {
+        let command = clap::Command::new("schemer")
+            .subcommand(clap::Command::new("schema"))
+            .subcommand(clap::Command::new("__complete").hide(true));
+        assert!(!super::script(Shell::Bash, &command).contains("__complete"));
+        let script = super::script(Shell::Zsh, &command);
+        assert!(script.ends_with("        _schemer_static \"$@\"\n    fi\n}\n\n_schemer \"$@\"\n"));
+        let script = super::script(Shell::Fish, &command);
+        assert!(script.contains(
+            "complete -c schemer -n \"__fish_seen_subcommand_from schema; and __fish_seen_subcommand_from show\" -f -a \"(schemer (commandline -opc)[2] __complete schemas 2>/dev/null)\"\n"
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::{
+    content::ContentProvider,
+    error::{ErrorFormat, OpenapiSchemerError},
+    output::OutputFormat,
+};
+
+/// The name of the project config file, looked up in the directory of the
+/// spec and each directory above it.
+pub const CONFIG_FILE: &str = ".openapi-schemer.toml";
+
+/// Project defaults read from `.openapi-schemer.toml`. Flags given on the
+/// command line take precedence over everything set here.
+///
+/// ```toml
+/// format = "json"
+/// error-format = "plain"
+///
+/// [lint.rules]
+/// operation-summary = "off"
+///
+/// [refs]
+/// external = false
+///
+/// [network]
+/// allow = false
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// The file the config was read from.
+    pub path: Option<PathBuf>,
+    pub format: Option<OutputFormat>,
+    pub error_format: Option<ErrorFormat>,
+    /// Lint rules turned on or off, applied before `lint --config` and the
+    /// `--enable` and `--disable` flags.
+    pub lint_rules: Vec<(String, bool)>,
+    /// Whether the spec may refer to other files. Allowed unless set.
+    pub external_refs: Option<bool>,
+    /// Whether commands may send requests, such as `operation invoke`.
+    /// Allowed unless set.
+    pub network: Option<bool>,
+}
+
+impl Config {
+    /// Reads the first config file found in `start` or a directory above it,
+    /// or the default config when there is none.
+    pub fn discover(start: &Path) -> Result<Config, OpenapiSchemerError> {
+        let path = match candidates(start).into_iter().find(|path| path.is_file()) {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+        let content = std::fs::read_to_string(&path).map_err(|error| {
+            OpenapiSchemerError::Config(format!("Could not read `{}`: {}", path.display(), error))
+        })?;
+        let mut config = Config::parse(&content).map_err(|error| {
+            OpenapiSchemerError::Config(format!("Invalid config `{}`: {}", path.display(), error))
+        })?;
+        config.path = Some(path);
+        Ok(config)
+    }
+
+    /// Parses the subset of TOML the config uses: tables, and keys set to
+    /// strings or booleans.
+    pub fn parse(content: &str) -> Result<Config, OpenapiSchemerError> {
+        let mut config = Config::default();
+        let mut table = String::new();
+        for (index, line) in content.lines().enumerate() {
+            let error = |message: String| {
+                OpenapiSchemerError::Config(format!("line {}: {}", index + 1, message))
+            };
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                table = name.trim().to_string();
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("Expected `key = value`, found `{}`", line)))?;
+            let (key, value) = (key.trim(), parse_value(value.trim()).map_err(error)?);
+            let unknown =
+                |format: &str| error(format!("Unknown format `{}` for `{}`", format, key));
+            match (table.as_str(), key, value) {
+                ("", "format", Value::String(format)) => {
+                    let format =
+                        OutputFormat::from_str(&format, true).map_err(|_| unknown(&format))?;
+                    config.format = Some(format);
+                }
+                ("", "error-format", Value::String(format)) => {
+                    let format =
+                        ErrorFormat::from_str(&format, true).map_err(|_| unknown(&format))?;
+                    config.error_format = Some(format);
+                }
+                ("lint.rules", rule, value) => {
+                    let enabled = match value {
+                        Value::Boolean(enabled) => enabled,
+                        Value::String(state) if state == "on" => true,
+                        Value::String(state) if state == "off" => false,
+                        _ => {
+                            return Err(error(format!(
+                                "Expected `on` or `off` for lint rule `{}`",
+                                rule
+                            )))
+                        }
+                    };
+                    config.lint_rules.push((rule.to_string(), enabled));
+                }
+                ("refs", "external", Value::Boolean(allowed)) => {
+                    config.external_refs = Some(allowed)
+                }
+                ("network", "allow", Value::Boolean(allowed)) => config.network = Some(allowed),
+                ("", "format" | "error-format", _) => {
+                    return Err(error(format!("Expected a string for `{}`", key)))
+                }
+                ("refs", "external", _) | ("network", "allow", _) => {
+                    return Err(error(format!(
+                        "Expected true or false for `{}.{}`",
+                        table, key
+                    )))
+                }
+                ("", _, _) => return Err(error(format!("Unknown key `{}`", key))),
+                (_, _, _) => return Err(error(format!("Unknown key `{}.{}`", table, key))),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Where the settings came from, for messages.
+    fn source(&self) -> String {
+        match &self.path {
+            Some(path) => format!("`{}`", path.display()),
+            None => "the config".to_string(),
+        }
+    }
+
+    /// Fails when the config forbids refs to other files and `provider`
+    /// loaded any.
+    pub fn check_refs(&self, provider: &dyn ContentProvider) -> Result<(), OpenapiSchemerError> {
+        if self.external_refs != Some(false) {
+            return Ok(());
+        }
+        let root = provider.source_path(Path::new("#"));
+        let mut external: Vec<&PathBuf> = provider
+            .paths()
+            .into_iter()
+            .filter(|path| path.as_path() != Path::new("#") && **path != root)
+            .collect();
+        external.sort();
+        match external.first() {
+            Some(file) => Err(OpenapiSchemerError::Config(format!(
+                "The spec refers to `{}`, but refs to other files are turned off by `refs.external` in {}",
+                file.display(),
+                self.source()
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails when the config forbids commands from sending requests.
+    pub fn check_network(&self) -> Result<(), OpenapiSchemerError> {
+        match self.network {
+            Some(false) => Err(OpenapiSchemerError::Config(format!(
+                "Sending requests is turned off by `network.allow` in {}",
+                self.source()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Where the config file is looked for from `start`, nearest first.
+pub fn candidates(start: &Path) -> Vec<PathBuf> {
+    start
+        .ancestors()
+        .map(|directory| directory.join(CONFIG_FILE))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Boolean(bool),
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    match text {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => {}
+    }
+    if let Some(literal) = text
+        .strip_prefix('\'')
+        .and_then(|text| text.strip_suffix('\''))
+    {
+        return Ok(Value::String(literal.to_string()));
+    }
+    let quoted = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'));
+    let quoted = quoted.ok_or_else(|| format!("Expected a string or boolean, found `{}`", text))?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        value.push(match c {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some(escaped @ ('"' | '\\')) => escaped,
+                _ => return Err(format!("Unsupported escape in `{}`", text)),
+            },
+            c => c,
+        });
+    }
+    Ok(Value::String(value))
+}
+
+/// `line` without a trailing `#` comment. A `#` inside a string is kept.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '#') => return &line[..index],
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::content::ContentProviderMap;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn Error>> {
+        let content = r#"
+# Defaults for this repository
+format = "json"
+error-format = 'plain'
+
+[lint.rules]
+operation-summary = "off"  # Summaries live in the descriptions
+no-empty-description = true
+
+[refs]
+external = false
+
+[network]
+allow = false
+"#;
+        assert_eq!(
+            Config::parse(content)?,
+            Config {
+                path: None,
+                format: Some(OutputFormat::Json),
+                error_format: Some(ErrorFormat::Plain),
+                lint_rules: vec![
+                    ("operation-summary".to_string(), false),
+                    ("no-empty-description".to_string(), true),
+                ],
+                external_refs: Some(false),
+                network: Some(false),
+            }
+        );
+        assert_eq!(Config::parse("")?, Config::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |content| Config::parse(content).unwrap_err().to_string();
+        assert_eq!(
+            error("format"),
+            "line 1: Expected `key = value`, found `format`"
+        );
+        assert_eq!(
+            error("format = \"xml\""),
+            "line 1: Unknown format `xml` for `format`"
+        );
+        assert_eq!(
+            error("format = true"),
+            "line 1: Expected a string for `format`"
+        );
+        assert_eq!(
+            error("[network]\nallow = 1"),
+            "line 2: Expected a string or boolean, found `1`"
+        );
+        assert_eq!(
+            error("[refs]\nexternal = \"no\""),
+            "line 2: Expected true or false for `refs.external`"
+        );
+        assert_eq!(
+            error("[server]\nurl = \"a\""),
+            "line 2: Unknown key `server.url`"
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(
+            candidates(Path::new("/spec/v1")),
+            vec![
+                PathBuf::from("/spec/v1/.openapi-schemer.toml"),
+                PathBuf::from("/spec/.openapi-schemer.toml"),
+                PathBuf::from("/.openapi-schemer.toml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checks() -> Result<(), Box<dyn Error>> {
+        let contents = HashMap::from([
+            (PathBuf::from("#"), "openapi: 3.0.0".to_string()),
+            (PathBuf::from("pet.yaml"), "type: object".to_string()),
+        ]);
+        let provider = ContentProviderMap::from_map(contents);
+        let config = Config::parse("[refs]\nexternal = false\n[network]\nallow = false")?;
+        assert_eq!(
+            config.check_refs(&provider).unwrap_err().to_string(),
+            "The spec refers to `pet.yaml`, but refs to other files are turned off by \
+             `refs.external` in the config"
+        );
+        assert_eq!(
+            config.check_network().unwrap_err().to_string(),
+            "Sending requests is turned off by `network.allow` in the config"
+        );
+
+        let config = Config::default();
+        assert!(config.check_refs(&provider).is_ok());
+        assert!(config.check_network().is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,491 @@
+use std::{fmt::Display, path::Path};
+
+use anyhow::Context;
+
+use crate::{
+    bindings::{
+        contract::{ContractChecker, ResponseSample},
+        har::{parse_har, HarEntry},
+        serve::{MockRoute, ServeParser},
+        server::ServerParser,
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+    path::{match_template, url_path},
+};
+
+/// The problems found with one recorded exchange.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EntryReport {
+    /// The position of the entry in the HAR file, starting at 1.
+    pub index: usize,
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    /// The operation the entry was matched to, as `GET /pets/{petId}`.
+    pub operation: Option<String>,
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    /// The entries with problems.
+    entries: Vec<EntryReport>,
+    /// Every operation of the spec with whether an entry exercised it.
+    operations: Vec<(String, bool)>,
+}
+
+impl CheckResult {
+    pub fn new(entries: Vec<EntryReport>, operations: Vec<(String, bool)>) -> CheckResult {
+        CheckResult {
+            entries,
+            operations,
+        }
+    }
+
+    fn covered(&self) -> usize {
+        self.operations
+            .iter()
+            .filter(|(_, exercised)| *exercised)
+            .count()
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for entry in &self.entries {
+            for problem in &entry.problems {
+                lines.push(format!(
+                    "entry {}: {} {} -> {}: {}",
+                    entry.index, entry.method, entry.url, entry.status, problem
+                ));
+            }
+        }
+        let total = self.operations.len();
+        let percent = match total {
+            0 => 100,
+            _ => self.covered() * 100 / total,
+        };
+        lines.push(format!(
+            "Covered {} of {} operations ({}%)",
+            self.covered(),
+            total,
+            percent
+        ));
+        let missing: Vec<&str> = self
+            .operations
+            .iter()
+            .filter(|(_, exercised)| !exercised)
+            .map(|(operation, _)| operation.as_str())
+            .collect();
+        if !missing.is_empty() {
+            lines.push("Not exercised:".to_string());
+            lines.extend(missing.iter().map(|operation| format!("  {}", operation)));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for CheckResult {
+    fn to_node(&self) -> YamlNode {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let operation = match &entry.operation {
+                    Some(operation) => YamlNode::string(operation),
+                    None => YamlNode::null(),
+                };
+                YamlNode::mapping(vec![
+                    ("entry", YamlNode::integer(entry.index)),
+                    ("method", YamlNode::string(&entry.method)),
+                    ("url", YamlNode::string(&entry.url)),
+                    ("status", YamlNode::integer(entry.status as usize)),
+                    ("operation", operation),
+                    (
+                        "problems",
+                        YamlNode::sequence(
+                            entry
+                                .problems
+                                .iter()
+                                .map(|problem| YamlNode::string(problem))
+                                .collect(),
+                        ),
+                    ),
+                ])
+            })
+            .collect();
+        let operations = |exercised: bool| {
+            YamlNode::sequence(
+                self.operations
+                    .iter()
+                    .filter(|(_, covered)| *covered == exercised)
+                    .map(|(operation, _)| YamlNode::string(operation))
+                    .collect(),
+            )
+        };
+        YamlNode::mapping(vec![
+            ("entries", YamlNode::sequence(entries)),
+            ("covered", operations(true)),
+            ("missing", operations(false)),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CoverageResult {
+    /// Every operation of the spec with the number of entries sent to it.
+    operations: Vec<(String, usize)>,
+    /// The number of entries that matched no operation.
+    unmatched: usize,
+}
+
+impl CoverageResult {
+    pub fn new(operations: Vec<(String, usize)>, unmatched: usize) -> CoverageResult {
+        CoverageResult {
+            operations,
+            unmatched,
+        }
+    }
+
+    fn covered(&self) -> usize {
+        self.operations.iter().filter(|(_, hits)| *hits > 0).count()
+    }
+
+    /// The share of operations exercised, rounded down to a whole percent.
+    pub fn percent(&self) -> usize {
+        match self.operations.len() {
+            0 => 100,
+            total => self.covered() * 100 / total,
+        }
+    }
+
+    /// Fails when less than `minimum` percent of the operations were
+    /// exercised, for CI thresholds.
+    pub fn check_minimum(&self, minimum: usize) -> Result<(), OpenapiSchemerError> {
+        match self.percent() < minimum {
+            true => Err(OpenapiSchemerError::Coverage(format!(
+                "Coverage of {}% is below the minimum of {}%",
+                self.percent(),
+                minimum
+            ))),
+            false => Ok(()),
+        }
+    }
+}
+
+impl Display for CoverageResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines: Vec<String> = self
+            .operations
+            .iter()
+            .map(|(operation, hits)| format!("{}: {}", operation, hits))
+            .collect();
+        if self.unmatched > 0 {
+            lines.push(format!("{} entries matched no operation", self.unmatched));
+        }
+        lines.push(format!(
+            "Coverage: {}% ({} of {} operations)",
+            self.percent(),
+            self.covered(),
+            self.operations.len()
+        ));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for CoverageResult {
+    fn to_node(&self) -> YamlNode {
+        let operations = self
+            .operations
+            .iter()
+            .map(|(operation, hits)| {
+                YamlNode::mapping(vec![
+                    ("operation", YamlNode::string(operation)),
+                    ("hits", YamlNode::integer(*hits)),
+                ])
+            })
+            .collect();
+        YamlNode::mapping(vec![
+            ("operations", YamlNode::sequence(operations)),
+            ("covered", YamlNode::integer(self.covered())),
+            ("total", YamlNode::integer(self.operations.len())),
+            ("unmatched", YamlNode::integer(self.unmatched)),
+            ("coverage", YamlNode::integer(self.percent())),
+        ])
+    }
+}
+
+pub fn read_har(
+    path: &Path,
+    error: fn(String) -> OpenapiSchemerError,
+) -> Result<Vec<HarEntry>, OpenapiSchemerError> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read HAR `{}`", path.display()))
+        .and_then(|content| parse_har(&content))
+        .map_err(|cause| OpenapiSchemerError::from_anyhow(cause, error))
+}
+
+/// The route `entry` was sent to. The path of each server URL, such as `/v1`,
+/// is tried as a prefix to strip when the path doesn't match as it is.
+fn find_route<'a>(
+    routes: &'a [MockRoute],
+    prefixes: &[String],
+    entry: &HarEntry,
+) -> Result<&'a MockRoute, String> {
+    let path = url_path(&entry.url);
+    let candidates = std::iter::once(path).chain(
+        prefixes
+            .iter()
+            .filter_map(|prefix| path.strip_prefix(prefix.as_str()))
+            .filter(|path| path.starts_with('/')),
+    );
+    for candidate in candidates {
+        let mut matching: Vec<&MockRoute> = routes
+            .iter()
+            .filter(|route| match_template(&route.path, candidate).is_some())
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        matching.sort_by_key(|route| route.path.matches('{').count());
+        return matching
+            .into_iter()
+            .find(|route| route.method.eq_ignore_ascii_case(&entry.method))
+            .ok_or_else(|| {
+                format!(
+                    "Method `{}` is not defined for `{}`",
+                    entry.method, candidate
+                )
+            });
+    }
+    Err(format!("No path matches `{}`", path))
+}
+
+fn describe(route: &MockRoute) -> String {
+    format!("{} {}", route.method.to_uppercase(), route.path)
+}
+
+/// The paths of the server URLs, which requests carry in front of the paths
+/// of the spec.
+fn server_prefixes<S: ServerParser>(server_parser: S) -> Result<Vec<String>, OpenapiSchemerError> {
+    let mut prefixes: Vec<String> = vec![];
+    for server in server_parser.get_server_nodes()? {
+        let prefix = url_path(&server.text).trim_end_matches('/').to_string();
+        if !prefix.is_empty() && !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Checks every recorded exchange against the operation it was sent to: its
+/// status, headers and body. Operations no entry was sent to are reported
+/// as not exercised.
+pub fn check<T: ServeParser, S: ServerParser, C: ContractChecker>(
+    parser: T,
+    server_parser: S,
+    checker: C,
+    entries: Vec<HarEntry>,
+) -> Result<CheckResult, OpenapiSchemerError> {
+    let routes = parser.get_routes()?;
+    let prefixes = server_prefixes(server_parser)?;
+    let mut exercised = vec![false; routes.len()];
+    let mut reports = vec![];
+    for (index, entry) in entries.into_iter().enumerate() {
+        let mut report = EntryReport {
+            index: index + 1,
+            method: entry.method.to_owned(),
+            url: entry.url.to_owned(),
+            status: entry.status,
+            operation: None,
+            problems: vec![],
+        };
+        match find_route(&routes, &prefixes, &entry) {
+            Ok(route) => {
+                let operation = describe(route);
+                if let Some(position) = routes.iter().position(|other| other == route) {
+                    exercised[position] = true;
+                }
+                let response = ResponseSample {
+                    status: entry.status,
+                    headers: entry.response_headers,
+                    body: entry.response_body,
+                    content_type: entry.response_content_type,
+                };
+                let problems = checker
+                    .check_response(&operation, &response)?
+                    .unwrap_or_default();
+                report.problems = problems
+                    .into_iter()
+                    .map(|problem| problem.message)
+                    .collect();
+                report.operation = Some(operation);
+            }
+            Err(problem) => report.problems.push(problem),
+        }
+        if !report.problems.is_empty() {
+            reports.push(report);
+        }
+    }
+    let operations = routes
+        .iter()
+        .zip(exercised)
+        .map(|(route, exercised)| (describe(route), exercised))
+        .collect();
+    Ok(CheckResult::new(reports, operations))
+}
+
+/// Counts the recorded entries sent to each operation of the spec.
+pub fn coverage<T: ServeParser, S: ServerParser>(
+    parser: T,
+    server_parser: S,
+    entries: Vec<HarEntry>,
+) -> Result<CoverageResult, OpenapiSchemerError> {
+    let routes = parser.get_routes()?;
+    let prefixes = server_prefixes(server_parser)?;
+    let mut hits = vec![0; routes.len()];
+    let mut unmatched = 0;
+    for entry in &entries {
+        match find_route(&routes, &prefixes, entry)
+            .ok()
+            .and_then(|route| routes.iter().position(|other| other == route))
+        {
+            Some(position) => hits[position] += 1,
+            None => unmatched += 1,
+        }
+    }
+    let operations = routes
+        .iter()
+        .zip(hits)
+        .map(|(route, hits)| (describe(route), hits))
+        .collect();
+    Ok(CoverageResult::new(operations, unmatched))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::{
+        contract::{ContractProblem, RequestSample},
+        server::ServerNode,
+    };
+
+    use super::*;
+
+    struct MockParser;
+    impl ServeParser for MockParser {
+        fn get_routes(&self) -> Result<Vec<MockRoute>, OpenapiSchemerError> {
+            let route = |method: &str, path: &str| MockRoute {
+                method: method.to_string(),
+                path: path.to_string(),
+                responses: YamlNode::mapping(vec![]),
+            };
+            Ok(vec![
+                route("get", "/pets"),
+                route("post", "/pets"),
+                route("get", "/pets/{petId}"),
+            ])
+        }
+    }
+
+    struct MockServerParser;
+    impl ServerParser for MockServerParser {
+        fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError> {
+            Ok(vec![ServerNode {
+                text: "http://petstore.swagger.io/v1".to_string(),
+                description: None,
+                scope: None,
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 5,
+                column: 10,
+            }])
+        }
+    }
+
+    struct MockChecker;
+    impl ContractChecker for MockChecker {
+        fn check_request(
+            &self,
+            _operation_id: &str,
+            _request: &RequestSample,
+        ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+            Ok(Some(vec![]))
+        }
+
+        fn check_response(
+            &self,
+            operation_id: &str,
+            response: &ResponseSample,
+        ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+            let problems = match response.status {
+                500 => vec![ContractProblem {
+                    message: format!("Status 500 is not documented for `{}`", operation_id),
+                    position: None,
+                }],
+                _ => vec![],
+            };
+            Ok(Some(problems))
+        }
+    }
+
+    fn entry(method: &str, url: &str, status: u16) -> HarEntry {
+        HarEntry {
+            method: method.to_string(),
+            url: url.to_string(),
+            status,
+            response_headers: vec![],
+            response_content_type: None,
+            response_body: None,
+        }
+    }
+
+    #[test]
+    fn test_check() -> Result<(), Box<dyn Error>> {
+        let entries = vec![
+            entry("GET", "http://petstore.swagger.io/v1/pets?limit=5", 200),
+            entry("GET", "http://petstore.swagger.io/v1/pets/1", 500),
+            entry("DELETE", "http://petstore.swagger.io/v1/pets/1", 204),
+            entry("GET", "http://petstore.swagger.io/v1/owners", 200),
+        ];
+        let result = check(MockParser, MockServerParser, MockChecker, entries)?;
+        assert_eq!(
+            result.to_string(),
+            "entry 2: GET http://petstore.swagger.io/v1/pets/1 -> 500: Status 500 is not documented for `GET /pets/{petId}`\n\
+             entry 3: DELETE http://petstore.swagger.io/v1/pets/1 -> 204: Method `DELETE` is not defined for `/pets/1`\n\
+             entry 4: GET http://petstore.swagger.io/v1/owners -> 200: No path matches `/v1/owners`\n\
+             Covered 2 of 3 operations (66%)\n\
+             Not exercised:\n  \
+             POST /pets"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_coverage() -> Result<(), Box<dyn Error>> {
+        let entries = vec![
+            entry("GET", "http://petstore.swagger.io/v1/pets", 200),
+            entry("GET", "http://petstore.swagger.io/v1/pets/1", 200),
+            entry("GET", "http://petstore.swagger.io/v1/pets/2", 404),
+            entry("GET", "http://petstore.swagger.io/v1/owners", 200),
+        ];
+        let result = coverage(MockParser, MockServerParser, entries)?;
+        assert_eq!(
+            result.to_string(),
+            "GET /pets: 1\n\
+             POST /pets: 0\n\
+             GET /pets/{petId}: 2\n\
+             1 entries matched no operation\n\
+             Coverage: 66% (2 of 3 operations)"
+        );
+        assert!(result.check_minimum(60).is_ok());
+        assert_eq!(
+            result.check_minimum(80).unwrap_err().to_string(),
+            "Coverage of 66% is below the minimum of 80%"
+        );
+        Ok(())
+    }
+}
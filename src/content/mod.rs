@@ -1,20 +1,57 @@
-use std::{collections::HashMap, fs::File, io::Read, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    ops::ControlFlow,
+    path::{Component, Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use crate::bindings;
+use anyhow::{Context, Result};
+use tree_sitter::{InputEdit, Point, Tree};
+
+use crate::{
+    bindings::yaml::{parse_tree, parse_yaml, resolve_aliases, tree_to_yaml, YamlNode},
+    error::OpenapiSchemerError,
+};
 
 #[cfg(test)]
 use mocktopus::macros::mockable;
 
 #[cfg_attr(test, mockable)]
 pub trait ContentProvider {
-    fn get_content(&self, path: PathBuf) -> String;
+    /// The content of `path`, or a `ContentLoad` error naming the file when
+    /// it could not be read.
+    fn get_content(&self, path: PathBuf) -> Result<String, OpenapiSchemerError>;
     fn paths(&self) -> Vec<&PathBuf>;
+    /// The parsed document of `path`, with its aliases and merge keys
+    /// resolved. This is the document commands read.
+    fn get_document(&self, path: PathBuf) -> Result<YamlNode> {
+        Ok(resolve_aliases(&self.get_source_document(path)?))
+    }
+    /// The parsed document of `path` as it is written, aliases and merge
+    /// keys included, for edits that splice its text.
+    fn get_source_document(&self, path: PathBuf) -> Result<YamlNode> {
+        let content = self.get_content(path.to_owned())?;
+        parse_document(&path, &content)
+    }
+    /// The location of `path` on disk, used when reporting results back to
+    /// the user.
+    fn source_path(&self, path: &Path) -> PathBuf;
 }
 
 #[cfg_attr(test, mockable)]
+#[derive(Clone, Debug)]
 pub struct ContentProviderMap {
     contents: HashMap<PathBuf, String>,
     root_file: PathBuf,
+    /// Why each referenced file that could not be read is missing, keyed by
+    /// its absolute path.
+    failures: HashMap<PathBuf, String>,
+    /// The documents parsed while loading, keyed like `contents`.
+    documents: HashMap<PathBuf, YamlNode>,
 }
 
 #[cfg_attr(test, mockable)]
@@ -23,6 +60,8 @@ impl ContentProviderMap {
         ContentProviderMap {
             contents: HashMap::new(),
             root_file: PathBuf::from("#"),
+            failures: HashMap::new(),
+            documents: HashMap::new(),
         }
     }
 
@@ -30,45 +69,516 @@ impl ContentProviderMap {
         ContentProviderMap {
             contents,
             root_file: PathBuf::from("#"),
+            failures: HashMap::new(),
+            documents: HashMap::new(),
         }
     }
 
-    pub fn from_open_api_yaml(path: PathBuf) -> Self {
+    /// Loads the spec at `path` along with every file it refers to. Referenced
+    /// files that can't be read are remembered rather than failing the load,
+    /// so commands only fail when they actually need them.
+    pub fn from_open_api_yaml(path: PathBuf) -> Result<Self, OpenapiSchemerError> {
+        Self::from_path(path, None)
+    }
+
+    /// Loads the spec at `path` like `from_open_api_yaml`, parsing its files
+    /// through `cache` so files that haven't changed since the cache last saw
+    /// them aren't parsed again.
+    pub fn from_open_api_yaml_cached(
+        path: PathBuf,
+        cache: &mut ParseCache,
+    ) -> Result<Self, OpenapiSchemerError> {
+        Self::from_path(path, Some(cache))
+    }
+
+    fn from_path(
+        path: PathBuf,
+        cache: Option<&mut ParseCache>,
+    ) -> Result<Self, OpenapiSchemerError> {
+        let load_error = |path: &Path, error: ::std::io::Error| {
+            OpenapiSchemerError::ContentLoad(format!(
+                "Could not read `{}`: {}",
+                path.display(),
+                error
+            ))
+        };
+        let path = canonicalize(path.to_owned()).map_err(|error| load_error(&path, error))?;
+        let content =
+            get_content_for_path(path.to_owned()).map_err(|error| load_error(&path, error))?;
+        Ok(Self::from_root(path, content, Source::FileSystem, cache))
+    }
+
+    /// Loads the spec at `path` and every file it refers to as they were at
+    /// the git `revision`, read from the object database rather than the
+    /// working tree. Files are still keyed by their working tree paths.
+    pub fn from_git_revision(path: PathBuf, revision: &str) -> Result<Self, OpenapiSchemerError> {
+        let path = match path.is_absolute() {
+            true => normalize(&path),
+            false => {
+                let current_dir = ::std::env::current_dir().map_err(|error| {
+                    OpenapiSchemerError::ContentLoad(format!(
+                        "Could not read the current directory: {}",
+                        error
+                    ))
+                })?;
+                normalize(&current_dir.join(path))
+            }
+        };
+        let source = Source::Git { revision };
+        let content = source.read(&path).map_err(|error| {
+            OpenapiSchemerError::ContentLoad(format!(
+                "Could not read `{}` at `{}`: {}",
+                path.display(),
+                revision,
+                error
+            ))
+        })?;
+        Ok(Self::from_root(path, content, source, None))
+    }
+
+    /// Loads a spec read from stdin. It has no location of its own, so the
+    /// files it refers to are looked up relative to `base_dir`.
+    pub fn from_stdin(base_dir: PathBuf) -> Result<Self, OpenapiSchemerError> {
+        let base_dir = canonicalize(base_dir.to_owned()).map_err(|error| {
+            OpenapiSchemerError::ContentLoad(format!(
+                "Could not read `{}`: {}",
+                base_dir.display(),
+                error
+            ))
+        })?;
+        let content = read_stdin().map_err(|error| {
+            OpenapiSchemerError::ContentLoad(format!("Could not read stdin: {}", error))
+        })?;
+        Ok(Self::from_root(
+            base_dir.join("-"),
+            content,
+            Source::FileSystem,
+            None,
+        ))
+    }
+
+    /// Builds the map from the root document's `content`, loading every file
+    /// it refers to relative to `path`. Files are loaded a level of refs at a
+    /// time, each level read and parsed in parallel, through `cache` when
+    /// there is one.
+    fn from_root(
+        path: PathBuf,
+        content: String,
+        source: Source,
+        mut cache: Option<&mut ParseCache>,
+    ) -> Self {
         let mut backing_map: HashMap<PathBuf, String> = HashMap::new();
-        let working_directory = path.parent().unwrap();
-        let content = get_content_for_path(path.to_owned());
-        let refs = bindings::find_refs(&content);
-        let external_refs: Vec<String> = refs
-            .into_iter()
-            .filter(|dollar_ref| !dollar_ref.starts_with("#"))
-            .collect();
+        let mut failures: HashMap<PathBuf, String> = HashMap::new();
+        let mut documents: HashMap<PathBuf, YamlNode> = HashMap::new();
         backing_map.insert(path.to_owned(), content.to_owned());
         backing_map.insert(PathBuf::from("#"), content.to_owned());
-        for reference in external_refs {
-            let mut path = PathBuf::new();
-            path.push(working_directory);
-            path.push(reference);
-            let path = canonicalize(path);
-            let path = path.unwrap();
-            let content = get_content_for_path(path.to_owned());
-            backing_map.insert(path, content);
+
+        // External files can reference further files relative to their own
+        // location, so keep following refs until every file is loaded.
+        let mut pending = vec![(path.to_owned(), content)];
+        while !pending.is_empty() {
+            let parsed = match cache.as_deref_mut() {
+                Some(cache) => cache.parse_all(&pending),
+                None => parallel_map(&pending, |(file, content)| parse_document(file, content)),
+            };
+            // The files the parsed level refers to, along with the first file
+            // referring to each.
+            let mut requested: Vec<(PathBuf, PathBuf)> = vec![];
+            for ((referencing_file, _), document) in pending.into_iter().zip(parsed) {
+                // Documents that fail to parse are reported when a command
+                // asks for them.
+                let document = match document {
+                    Ok(document) => document,
+                    Err(_) => continue,
+                };
+                let directory = referencing_file.parent().unwrap_or(Path::new("/"));
+                let external_refs = document
+                    .references()
+                    .into_iter()
+                    .filter(|dollar_ref| !dollar_ref.starts_with('#'));
+                for reference in external_refs {
+                    let file = reference.split('#').next().unwrap_or_default();
+                    let referenced = normalize(&directory.join(file));
+                    let known = failures.contains_key(&referenced)
+                        || requested
+                            .iter()
+                            .any(|(requested, _)| *requested == referenced);
+                    if !known {
+                        requested.push((referenced, referencing_file.to_owned()));
+                    }
+                }
+                documents.insert(referencing_file, document);
+            }
+            let loaded = parallel_map(&requested, |(referenced, _)| {
+                source.key(referenced.to_owned()).and_then(|path| {
+                    let content = match backing_map.contains_key(&path) {
+                        true => None,
+                        false => Some(source.read(&path)?),
+                    };
+                    Ok((path, content))
+                })
+            });
+            let mut next = vec![];
+            for ((referenced, referencing_file), loaded) in requested.into_iter().zip(loaded) {
+                match loaded {
+                    // Two refs of a level can name the same file by different
+                    // paths, it is only loaded once.
+                    Ok((path, Some(content))) if !backing_map.contains_key(&path) => {
+                        backing_map.insert(path.to_owned(), content.to_owned());
+                        next.push((path, content));
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        let failure = format!(
+                            "Could not read `{}` referenced from `{}`: {}",
+                            referenced.display(),
+                            referencing_file.display(),
+                            error
+                        );
+                        failures.insert(referenced, failure);
+                    }
+                }
+            }
+            pending = next;
+        }
+        if let Some(root) = documents.get(&path).cloned() {
+            documents.insert(PathBuf::from("#"), root);
         }
 
         ContentProviderMap {
             contents: backing_map,
             root_file: path,
+            failures,
+            documents,
+        }
+    }
+
+    /// The key `path` is stored under in `contents`.
+    fn key(&self, path: &Path) -> Result<PathBuf, OpenapiSchemerError> {
+        if path == Path::new("#") {
+            return match self.contents.contains_key(&self.root_file) {
+                true => Ok(self.root_file.to_owned()),
+                false => Err(OpenapiSchemerError::ContentLoad(format!(
+                    "Could not read `{}`",
+                    self.root_file.display()
+                ))),
+            };
+        }
+        if self.contents.contains_key(path) {
+            return Ok(path.to_path_buf());
+        }
+
+        let full_path = normalize(&self.source_path(path));
+        if self.contents.contains_key(&full_path) {
+            return Ok(full_path);
+        }
+        if let Some(canonical) = canonicalize(full_path.to_owned())
+            .ok()
+            .filter(|canonical| self.contents.contains_key(canonical))
+        {
+            return Ok(canonical);
+        }
+        let message = match self.failures.get(&full_path) {
+            Some(failure) => failure.to_owned(),
+            None => format!("Could not read `{}`", full_path.display()),
+        };
+        Err(OpenapiSchemerError::ContentLoad(message))
+    }
+
+    /// Every file of the spec by its absolute path, including the referenced
+    /// files that could not be read, so a watcher notices when they appear.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .contents
+            .keys()
+            .filter(|path| path.as_path() != Path::new("#"))
+            .chain(self.failures.keys())
+            .cloned()
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn lookup(&self, path: &Path) -> Result<&String, OpenapiSchemerError> {
+        self.key(path).map(|key| &self.contents[&key])
+    }
+}
+
+/// Where `ContentProviderMap::from_root` reads the files a spec refers to.
+enum Source<'a> {
+    FileSystem,
+    Git { revision: &'a str },
+}
+
+impl Source<'_> {
+    /// The path a file is stored under. Files of a revision may be gone from
+    /// the working tree, so they can't be canonicalized.
+    fn key(&self, path: PathBuf) -> Result<PathBuf, ::std::io::Error> {
+        match self {
+            Source::FileSystem => canonicalize(path),
+            Source::Git { .. } => Ok(path),
+        }
+    }
+
+    fn read(&self, path: &Path) -> Result<String, ::std::io::Error> {
+        match self {
+            Source::FileSystem => get_content_for_path(path.to_path_buf()),
+            Source::Git { revision } => git_show(revision, path.to_path_buf()),
+        }
+    }
+}
+
+impl Default for ContentProviderMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parsed trees of spec files kept between runs of a command, keyed by file
+/// path. When a file changes, the old tree is edited to match and handed to
+/// tree-sitter so only the changed parts are parsed again. Files that haven't
+/// changed aren't parsed at all.
+#[derive(Default)]
+pub struct ParseCache {
+    files: HashMap<PathBuf, CachedFile>,
+    /// How many times a file has been parsed, from scratch or incrementally.
+    parses: usize,
+}
+
+/// A file as the cache last parsed it.
+struct CachedFile {
+    content: String,
+    tree: Tree,
+    document: YamlNode,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The document for `content`, the current content of `path`.
+    pub fn parse(&mut self, path: &Path, content: &str) -> Result<YamlNode> {
+        let files = [(path.to_path_buf(), content.to_owned())];
+        self.parse_all(&files).remove(0)
+    }
+
+    /// The documents for `files`, paths along with their current content, in
+    /// the order of `files`. The files that changed are parsed in parallel.
+    pub fn parse_all(&mut self, files: &[(PathBuf, String)]) -> Vec<Result<YamlNode>> {
+        let cached = &self.files;
+        let parsed = parallel_map(files, |(path, content)| {
+            reparse(path, content, cached.get(path))
+        });
+        files
+            .iter()
+            .zip(parsed)
+            .map(|((path, _), parsed)| {
+                if let Some(file) = parsed? {
+                    self.parses += 1;
+                    self.files.insert(path.to_owned(), file);
+                }
+                Ok(self.files[path].document.to_owned())
+            })
+            .collect()
+    }
+
+    /// The files the cache holds trees for.
+    pub fn paths(&self) -> Vec<&PathBuf> {
+        self.files.keys().collect()
+    }
+
+    /// How many times a file has been parsed, from scratch or incrementally.
+    pub fn parses(&self) -> usize {
+        self.parses
+    }
+}
+
+/// `path` parsed again from `content`, reusing the tree of `cached`, or
+/// `None` when its content is the same as `cached`.
+fn reparse(path: &Path, content: &str, cached: Option<&CachedFile>) -> Result<Option<CachedFile>> {
+    let tree = match cached {
+        Some(cached) if cached.content == content => return Ok(None),
+        Some(cached) => {
+            let mut tree = cached.tree.to_owned();
+            tree.edit(&input_edit(&cached.content, content));
+            parse_tree(content, Some(&tree))?
+        }
+        None => parse_tree(content, None)?,
+    };
+    let document = tree_to_yaml(&tree, content)
+        .with_context(|| format!("Failed to parse `{}`", path.display()))?;
+    Ok(Some(CachedFile {
+        content: content.to_owned(),
+        tree,
+        document,
+    }))
+}
+
+/// The edit that turns `old` into `new`, covering everything between their
+/// common prefix and suffix.
+fn input_edit(old: &str, new: &str) -> InputEdit {
+    let (old_bytes, new_bytes) = (old.as_bytes(), new.as_bytes());
+    let start = old_bytes
+        .iter()
+        .zip(new_bytes)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old_bytes[start..]
+        .iter()
+        .rev()
+        .zip(new_bytes[start..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_end = old_bytes.len() - suffix;
+    let new_end = new_bytes.len() - suffix;
+    InputEdit {
+        start_byte: start,
+        old_end_byte: old_end,
+        new_end_byte: new_end,
+        start_position: point_at(old_bytes, start),
+        old_end_position: point_at(old_bytes, old_end),
+        new_end_position: point_at(new_bytes, new_end),
+    }
+}
+
+/// The row and column of `offset` in `text`.
+fn point_at(text: &[u8], offset: usize) -> Point {
+    let before = &text[..offset];
+    let row = before.iter().filter(|byte| **byte == b'\n').count();
+    let column = match before.iter().rposition(|byte| *byte == b'\n') {
+        Some(newline) => offset - newline - 1,
+        None => offset,
+    };
+    Point::new(row, column)
+}
+
+/// Blocks until one of `paths` is modified, created or removed, checking
+/// every `interval`.
+pub fn wait_for_change(paths: &[PathBuf], interval: Duration) {
+    let modified = |paths: &[PathBuf]| -> Vec<Option<SystemTime>> {
+        paths
+            .iter()
+            .map(|path| {
+                ::std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect()
+    };
+    let before = modified(paths);
+    while modified(paths) == before {
+        thread::sleep(interval);
+    }
+}
+
+/// Calls `run`, and again each time one of the files it returns changes,
+/// until it breaks. Between runs the files are checked every `interval`.
+pub fn watch_files(interval: Duration, mut run: impl FnMut() -> ControlFlow<(), Vec<PathBuf>>) {
+    while let ControlFlow::Continue(paths) = run() {
+        wait_for_change(&paths, interval);
+    }
+}
+
+/// How many threads `parallel_map` spreads its work over, one per core.
+#[cfg_attr(test, mockable)]
+fn thread_count() -> usize {
+    thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1)
+}
+
+/// Runs `f` on every item, spread over one thread per core, and returns the
+/// results in the order of `items`. Each thread builds its own tree-sitter
+/// parser since they can't be shared.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = thread_count();
+    if items.len() < 2 || threads < 2 {
+        return items.iter().map(f).collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(&f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| ::std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+fn parse_document(path: &Path, content: &str) -> Result<YamlNode> {
+    parse_yaml(content).with_context(|| format!("Failed to parse `{}`", path.display()))
+}
+
+/// Removes `.` and `..` components from `path` without touching the file
+/// system, so paths to files that don't exist can still be compared.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
         }
     }
+    normalized
+}
+
+/// Resolves the file part of a `$ref` found in `from` to a path relative to
+/// the root document's directory. `#` is used for the root document itself.
+pub fn resolve_ref_path(from: &Path, reference: &str) -> PathBuf {
+    let base = if from == Path::new("#") {
+        PathBuf::new()
+    } else {
+        from.parent().map(Path::to_path_buf).unwrap_or_default()
+    };
+    normalize(&base.join(reference))
+}
+
+#[cfg_attr(test, mockable)]
+fn get_content_for_path(path: PathBuf) -> Result<String, ::std::io::Error> {
+    let mut content = String::new();
+    let mut file = File::open(path)?;
+    file.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// The content of `path` at `revision`. Git resolves `./` paths relative to
+/// the directory it runs in, so the file is found wherever the repository
+/// root is.
+#[cfg_attr(test, mockable)]
+fn git_show(revision: &str, path: PathBuf) -> Result<String, ::std::io::Error> {
+    let directory = path.parent().unwrap_or(Path::new("/"));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["cat-file", "blob", &format!("{}:./{}", revision, name)])
+        .output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(::std::io::Error::other(message));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|error| ::std::io::Error::new(::std::io::ErrorKind::InvalidData, error))
 }
 
 #[cfg_attr(test, mockable)]
-fn get_content_for_path(path: PathBuf) -> String {
+fn read_stdin() -> Result<String, ::std::io::Error> {
     let mut content = String::new();
-    let mut file = File::open(&path).unwrap();
-    // TODO error handling
-    file.read_to_string(&mut content)
-        .expect("Unable to read the file");
-    content
+    ::std::io::stdin().read_to_string(&mut content)?;
+    Ok(content)
 }
 
 #[cfg_attr(test, mockable)]
@@ -78,29 +588,45 @@ fn canonicalize(path: PathBuf) -> Result<PathBuf, ::std::io::Error> {
 
 #[cfg_attr(test, mockable)]
 impl ContentProvider for ContentProviderMap {
-    fn get_content(&self, path: PathBuf) -> String {
-        if path == PathBuf::from("#") {
-            return self.contents.get(&self.root_file).unwrap().to_owned();
-        }
-
-        let root_directory = self.root_file.parent().unwrap();
-        let mut full_path = PathBuf::new();
-        full_path.push(root_directory);
-        full_path.push(path);
-        let full_path = canonicalize(full_path).unwrap();
-        return self.contents.get(&full_path).unwrap().to_owned();
+    fn get_content(&self, path: PathBuf) -> Result<String, OpenapiSchemerError> {
+        self.lookup(&path).cloned()
     }
 
     fn paths(&self) -> Vec<&PathBuf> {
         self.contents.keys().collect()
     }
+
+    fn get_source_document(&self, path: PathBuf) -> Result<YamlNode> {
+        let parsed = self
+            .key(&path)
+            .ok()
+            .and_then(|key| self.documents.get(&key));
+        match parsed {
+            Some(document) => Ok(document.to_owned()),
+            None => parse_document(&path, &self.get_content(path.to_owned())?),
+        }
+    }
+
+    fn source_path(&self, path: &Path) -> PathBuf {
+        if path == Path::new("#") {
+            return self.root_file.to_owned();
+        }
+        match self.root_file.parent() {
+            Some(root_directory) => root_directory.join(path),
+            None => path.to_path_buf(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, path::PathBuf};
+    use std::{
+        cell::RefCell, collections::HashMap, ops::ControlFlow, path::PathBuf, rc::Rc, thread,
+        time::Duration,
+    };
 
     use mocktopus::mocking::*;
+    use tree_sitter::Point;
 
     use crate::{
         bindings,
@@ -141,18 +667,18 @@ components:
         message:
           type: string
                 "#;
-        super::get_content_for_path.mock_safe(|_| MockResult::Return(content.to_string()));
+        super::get_content_for_path.mock_safe(|_| MockResult::Return(Ok(content.to_string())));
         super::canonicalize.mock_safe(move |path: PathBuf| MockResult::Return(Ok(path)));
-        bindings::find_refs.mock_safe(|_| MockResult::Return(vec![]));
 
         let fully_qualified_path = PathBuf::from("/test/test.yaml");
         let short_root_path = PathBuf::from("#");
-        let provider = ContentProviderMap::from_open_api_yaml(fully_qualified_path.to_owned());
+        let provider =
+            ContentProviderMap::from_open_api_yaml(fully_qualified_path.to_owned()).unwrap();
         assert_eq!(provider.paths().len(), 2);
         assert!(provider.paths().contains(&&fully_qualified_path));
         assert!(provider.paths().contains(&&short_root_path));
-        assert_eq!(provider.get_content(fully_qualified_path), content);
-        assert_eq!(provider.get_content(short_root_path), content);
+        assert_eq!(provider.get_content(fully_qualified_path).unwrap(), content);
+        assert_eq!(provider.get_content(short_root_path).unwrap(), content);
     }
 
     #[test]
@@ -199,26 +725,235 @@ paths:
         ]);
         super::get_content_for_path.mock_safe(move |path: PathBuf| {
             let s = content_map.get(&path).unwrap();
-            MockResult::Return(s.to_owned())
+            MockResult::Return(Ok(s.to_owned()))
         });
 
         super::canonicalize.mock_safe(move |path: PathBuf| MockResult::Return(Ok(path)));
+        // Mocks only apply to the thread that sets them, so the files are
+        // read on this one.
+        super::thread_count.mock_safe(|| MockResult::Return(1));
 
-        bindings::find_refs.mock_safe(|_| {
-            MockResult::Return(vec![
-                "resources/pets.yaml".to_owned(),
-                "resources/pet.yaml".to_owned(),
-            ])
-        });
-
-        let provider = ContentProviderMap::from_open_api_yaml(root_path.to_owned());
+        let provider = ContentProviderMap::from_open_api_yaml(root_path.to_owned()).unwrap();
         assert_eq!(provider.paths().len(), 4);
         assert!(provider.paths().contains(&&root_path));
         assert!(provider.paths().contains(&&short_root_path));
         assert!(provider.paths().contains(&&pets_path));
         assert!(provider.paths().contains(&&pet_path));
-        assert_eq!(provider.get_content(root_path), root_content);
-        assert_eq!(provider.get_content(pets_path), pets_content);
-        assert_eq!(provider.get_content(pet_path), pet_content);
+        assert_eq!(provider.get_content(root_path).unwrap(), root_content);
+        assert_eq!(provider.get_content(pets_path).unwrap(), pets_content);
+        assert_eq!(provider.get_content(pet_path).unwrap(), pet_content);
+    }
+
+    #[test]
+    fn missing_external_ref() {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+"#;
+        let root_path = PathBuf::from("/test/test.yaml");
+        super::get_content_for_path
+            .mock_safe(move |_| MockResult::Return(Ok(root_content.to_owned())));
+        super::canonicalize.mock_safe(move |path: PathBuf| {
+            if path.ends_with("pets.yaml") {
+                return MockResult::Return(Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "No such file or directory",
+                )));
+            }
+            MockResult::Return(Ok(path))
+        });
+
+        let provider = ContentProviderMap::from_open_api_yaml(root_path).unwrap();
+        assert_eq!(provider.paths().len(), 2);
+        let error = provider
+            .get_content(PathBuf::from("resources/pets.yaml"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not read `/test/resources/pets.yaml` referenced from `/test/test.yaml`: No such file or directory"
+        );
+    }
+
+    #[test]
+    fn from_stdin() {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: 'resources/pets.yaml'
+"#;
+        let pets_content = r#"
+# resources/pets.yaml
+"#;
+        super::read_stdin.mock_safe(move || MockResult::Return(Ok(root_content.to_owned())));
+        super::get_content_for_path
+            .mock_safe(move |_| MockResult::Return(Ok(pets_content.to_owned())));
+        super::canonicalize.mock_safe(move |path: PathBuf| MockResult::Return(Ok(path)));
+
+        let provider = ContentProviderMap::from_stdin(PathBuf::from("/test")).unwrap();
+        assert_eq!(provider.paths().len(), 3);
+        assert_eq!(
+            provider.get_content(PathBuf::from("#")).unwrap(),
+            root_content
+        );
+        assert_eq!(
+            provider
+                .get_content(PathBuf::from("resources/pets.yaml"))
+                .unwrap(),
+            pets_content
+        );
+        assert_eq!(
+            provider.source_path(&PathBuf::from("#")),
+            PathBuf::from("/test/-")
+        );
+    }
+
+    #[test]
+    fn from_git_revision() {
+        let root_content = r#"
+paths:
+  /pets:
+    $ref: '../resources/pets.yaml'
+"#;
+        let pets_content = r#"
+# resources/pets.yaml
+"#;
+        super::git_show.mock_safe(move |revision: &str, path: PathBuf| {
+            assert_eq!(revision, "HEAD~1");
+            let content = match path.to_str() {
+                Some("/test/spec/test.yaml") => root_content,
+                Some("/test/resources/pets.yaml") => pets_content,
+                _ => panic!("unexpected path {}", path.display()),
+            };
+            MockResult::Return(Ok(content.to_owned()))
+        });
+
+        let provider =
+            ContentProviderMap::from_git_revision(PathBuf::from("/test/spec/test.yaml"), "HEAD~1")
+                .unwrap();
+        assert_eq!(provider.paths().len(), 3);
+        assert_eq!(
+            provider
+                .get_content(PathBuf::from("../resources/pets.yaml"))
+                .unwrap(),
+            pets_content
+        );
+        assert_eq!(
+            provider.source_path(&PathBuf::from("#")),
+            PathBuf::from("/test/spec/test.yaml")
+        );
+    }
+
+    #[test]
+    fn parse_cache_reparses_changed_content() {
+        let path = PathBuf::from("/test/test.yaml");
+        let before = "paths:\n  /pets:\n    get:\n      summary: List pets\n";
+        let after =
+            "paths:\n  /pets:\n    get:\n      summary: |\n        List every pet\n  /owners: {}\n";
+        let mut cache = super::ParseCache::new();
+        cache.parse(&path, before).unwrap();
+        let incremental = cache.parse(&path, after).unwrap();
+        assert_eq!(incremental, bindings::yaml::parse_yaml(after).unwrap());
+        assert_eq!(cache.paths(), vec![&path]);
+    }
+
+    #[test]
+    fn from_open_api_yaml_cached() {
+        let root_path = PathBuf::from("/test/test.yaml");
+        let pets_path = PathBuf::from("/test/resources/pets.yaml");
+        let contents = Rc::new(RefCell::new(HashMap::from([
+            (
+                root_path.to_owned(),
+                "paths:\n  /pets:\n    $ref: 'resources/pets.yaml'\n".to_owned(),
+            ),
+            (
+                pets_path.to_owned(),
+                "get:\n  summary: List pets\n".to_owned(),
+            ),
+        ])));
+        let files = contents.clone();
+        super::get_content_for_path.mock_safe(move |path: PathBuf| {
+            MockResult::Return(Ok(files.borrow()[&path].to_owned()))
+        });
+        super::canonicalize.mock_safe(move |path: PathBuf| MockResult::Return(Ok(path)));
+
+        let mut cache = super::ParseCache::new();
+        ContentProviderMap::from_open_api_yaml_cached(root_path.to_owned(), &mut cache).unwrap();
+        assert_eq!(cache.parses(), 2);
+        ContentProviderMap::from_open_api_yaml_cached(root_path.to_owned(), &mut cache).unwrap();
+        assert_eq!(cache.parses(), 2);
+
+        contents.borrow_mut().insert(
+            pets_path.to_owned(),
+            "get:\n  summary: List every pet\n".to_owned(),
+        );
+        let provider =
+            ContentProviderMap::from_open_api_yaml_cached(root_path, &mut cache).unwrap();
+        assert_eq!(cache.parses(), 3);
+        let document = provider.get_document(pets_path).unwrap();
+        assert_eq!(
+            document
+                .pointer("/get/summary")
+                .and_then(|summary| summary.as_str()),
+            Some("List every pet")
+        );
+    }
+
+    #[test]
+    fn parallel_map() {
+        let items: Vec<usize> = (0..100).collect();
+        let doubled = super::parallel_map(&items, |item| item * 2);
+        assert_eq!(doubled, (0..200).step_by(2).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn watch_files() {
+        let directory =
+            std::env::temp_dir().join(format!("openapi-schemer-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let spec = directory.join("openapi.yaml");
+        std::fs::write(&spec, "openapi: 3.0.0\n").unwrap();
+        let mut runs = vec![];
+        super::watch_files(Duration::from_millis(10), || {
+            runs.push(std::fs::read_to_string(&spec).unwrap());
+            if runs.len() == 2 {
+                return ControlFlow::Break(());
+            }
+            // Changed once the run is over and the file is being watched.
+            let changed = spec.to_owned();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                std::fs::write(changed, "openapi: 3.1.0\n")
+            });
+            ControlFlow::Continue(vec![spec.to_owned()])
+        });
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(runs, vec!["openapi: 3.0.0\n", "openapi: 3.1.0\n"]);
+    }
+
+    #[test]
+    fn input_edit() {
+        let edit = super::input_edit("a: 1\nb: 2\nc: 3\n", "a: 1\nb: 22\nc: 3\n");
+        assert_eq!(edit.start_byte, 9);
+        assert_eq!(edit.old_end_byte, 9);
+        assert_eq!(edit.new_end_byte, 10);
+        assert_eq!(edit.start_position, Point::new(1, 4));
+        assert_eq!(edit.new_end_position, Point::new(1, 5));
+    }
+
+    #[test]
+    fn resolve_ref_path() {
+        assert_eq!(
+            super::resolve_ref_path(&PathBuf::from("#"), "resources/pets.yaml"),
+            PathBuf::from("resources/pets.yaml")
+        );
+        assert_eq!(
+            super::resolve_ref_path(&PathBuf::from("resources/pets.yaml"), "../schemas/Pet.yaml"),
+            PathBuf::from("schemas/Pet.yaml")
+        );
+        assert_eq!(
+            super::resolve_ref_path(&PathBuf::from("resources/pets.yaml"), "./pet.yaml"),
+            PathBuf::from("resources/pet.yaml")
+        );
     }
 }
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+
+use crate::{
+    bindings::{
+        convert::ConvertParser,
+        yaml::{ScalarStyle, YamlNode, YamlValue},
+    },
+    error::OpenapiSchemerError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ConvertFormat {
+    Json,
+    Yaml,
+}
+
+/// The spec written in another format. Keys keep the order they were
+/// written in and comments are lost.
+#[derive(Debug, PartialEq)]
+pub struct ConvertResult {
+    document: YamlNode,
+    to: ConvertFormat,
+}
+
+impl ConvertResult {
+    pub fn new(document: YamlNode, to: ConvertFormat) -> ConvertResult {
+        ConvertResult { document, to }
+    }
+}
+
+impl Display for ConvertResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to {
+            ConvertFormat::Json => write!(f, "{}", self.document.to_json()),
+            ConvertFormat::Yaml => {
+                let mut document = self.document.to_owned();
+                unquote(&mut document);
+                write!(f, "{}", document.to_yaml().trim_end())
+            }
+        }
+    }
+}
+
+/// Drops the double quotes JSON puts around every string wherever the
+/// string reads the same without them.
+fn unquote(node: &mut YamlNode) {
+    match &mut node.value {
+        YamlValue::Mapping(pairs) => {
+            for pair in pairs {
+                unquote(&mut pair.key);
+                unquote(&mut pair.value);
+            }
+        }
+        YamlValue::Sequence(items) => items.iter_mut().for_each(unquote),
+        YamlValue::Scalar(scalar) if scalar.style == ScalarStyle::DoubleQuoted => {
+            if let YamlValue::Scalar(string) = YamlNode::string(&scalar.text).value {
+                scalar.style = string.style;
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn convert<T: ConvertParser>(
+    parser: T,
+    to: ConvertFormat,
+) -> Result<ConvertResult, OpenapiSchemerError> {
+    let document = parser.get_document()?;
+    Ok(ConvertResult::new(document, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::{
+        bindings::{convert::TreeSitterConvertParser, yaml::parse_yaml},
+        content::ContentProviderMap,
+    };
+
+    use super::*;
+
+    struct MockParser {
+        document: YamlNode,
+    }
+    impl ConvertParser for MockParser {
+        fn get_document(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_convert() -> Result<(), Box<dyn Error>> {
+        let document = parse_yaml("openapi: 3.0.0\npaths:\n  /pets: {}\ninfo:\n  version: '1'\n")?;
+        let parser = MockParser {
+            document: document.to_owned(),
+        };
+        assert_eq!(
+            convert(parser, ConvertFormat::Json)?.to_string(),
+            "{\n  \"openapi\": \"3.0.0\",\n  \"paths\": {\n    \"/pets\": {}\n  },\n  \"info\": {\n    \"version\": \"1\"\n  }\n}"
+        );
+        let parser = MockParser { document };
+        assert_eq!(
+            convert(parser, ConvertFormat::Yaml)?.to_string(),
+            "openapi: 3.0.0\npaths:\n  /pets: {}\ninfo:\n  version: '1'"
+        );
+
+        let document = parse_yaml("{\"id\": \"1\", \"name\": \"Rex\", \"tags\": [\"a: b\"]}")?;
+        let parser = MockParser { document };
+        assert_eq!(
+            convert(parser, ConvertFormat::Yaml)?.to_string(),
+            "id: '1'\nname: Rex\ntags:\n  - 'a: b'"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_aliases() -> Result<(), Box<dyn Error>> {
+        let contents = HashMap::from([(
+            PathBuf::from("#"),
+            "base: &base\n  type: object\npet:\n  <<: *base\n  title: Pet\nlist: *base\n"
+                .to_string(),
+        )]);
+        let parser =
+            TreeSitterConvertParser::new(Box::new(ContentProviderMap::from_map(contents)), false);
+        assert_eq!(
+            convert(parser, ConvertFormat::Json)?.to_string(),
+            "{\n  \"base\": {\n    \"type\": \"object\"\n  },\n  \"pet\": {\n    \"type\": \"object\",\n    \"title\": \"Pet\"\n  },\n  \"list\": {\n    \"type\": \"object\"\n  }\n}"
+        );
+        Ok(())
+    }
+}
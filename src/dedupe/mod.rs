@@ -0,0 +1,179 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        dedupe::{Deduplicator, Duplicates},
+        edit::FileEdits,
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{write_file, OutputFormatter},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DedupeResult {
+    duplicates: Vec<Duplicates>,
+}
+
+impl DedupeResult {
+    pub fn new(duplicates: Vec<Duplicates>) -> DedupeResult {
+        DedupeResult { duplicates }
+    }
+}
+
+impl Display for DedupeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.duplicates.is_empty() {
+            return write!(f, "No duplicate schemas found");
+        }
+        let mut lines = vec![];
+        for duplicates in &self.duplicates {
+            lines.push(format!(
+                "{}: {} identical schemas",
+                duplicates.name,
+                duplicates.schemas.len()
+            ));
+            for schema in &duplicates.schemas {
+                lines.push(format!(
+                    "  {}:{}:{}: {}",
+                    schema.file.display(),
+                    schema.line,
+                    schema.column,
+                    schema.pointer
+                ));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for DedupeResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.duplicates
+                .iter()
+                .map(|duplicates| {
+                    let schemas = duplicates
+                        .schemas
+                        .iter()
+                        .map(|schema| {
+                            YamlNode::mapping(vec![
+                                ("pointer", YamlNode::string(&schema.pointer)),
+                                ("file", YamlNode::string(&schema.file.display().to_string())),
+                                ("line", YamlNode::integer(schema.line)),
+                                ("column", YamlNode::integer(schema.column)),
+                            ])
+                        })
+                        .collect();
+                    YamlNode::mapping(vec![
+                        ("name", YamlNode::string(&duplicates.name)),
+                        ("schemas", YamlNode::sequence(schemas)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The root document after `dedupe --apply`, along with the other files of
+/// the spec whose refs pointed at removed schemas.
+#[derive(Debug, PartialEq)]
+pub struct ApplyResult {
+    document: YamlNode,
+    files: Vec<FileEdits>,
+}
+
+impl ApplyResult {
+    pub fn new(document: YamlNode, files: Vec<FileEdits>) -> ApplyResult {
+        ApplyResult { document, files }
+    }
+
+    /// Writes the other changed files over the ones they were read from.
+    pub fn write_files(&self) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            write_file(&file.file, &file.content).map_err(OpenapiSchemerError::Dedupe)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ApplyResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ApplyResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+pub fn dedupe<T: Deduplicator>(deduplicator: T) -> Result<DedupeResult, OpenapiSchemerError> {
+    Ok(DedupeResult::new(deduplicator.get_duplicates()?))
+}
+
+pub fn apply<T: Deduplicator>(deduplicator: T) -> Result<ApplyResult, OpenapiSchemerError> {
+    let deduped = deduplicator.dedupe()?;
+    Ok(ApplyResult::new(deduped.document, deduped.files))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::dedupe::{Deduped, DuplicateSchema};
+
+    use super::*;
+
+    struct MockDeduplicator {
+        duplicates: Vec<Duplicates>,
+    }
+    impl Deduplicator for MockDeduplicator {
+        fn get_duplicates(&self) -> Result<Vec<Duplicates>, OpenapiSchemerError> {
+            Ok(self.duplicates.to_owned())
+        }
+
+        fn dedupe(&self) -> Result<Deduped, OpenapiSchemerError> {
+            Ok(Deduped {
+                document: YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.0"))]),
+                files: vec![],
+            })
+        }
+    }
+
+    #[test]
+    fn test_dedupe() -> Result<(), Box<dyn Error>> {
+        let schema = |name: Option<&str>, pointer: &str, line| DuplicateSchema {
+            name: name.map(str::to_owned),
+            pointer: pointer.to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 7,
+        };
+        let deduplicator = MockDeduplicator {
+            duplicates: vec![Duplicates {
+                name: "Person".to_string(),
+                schemas: vec![
+                    schema(Some("Person"), "/components/schemas/Person", 40),
+                    schema(Some("Human"), "/components/schemas/Human", 47),
+                ],
+            }],
+        };
+        assert_eq!(
+            dedupe(deduplicator)?.to_string(),
+            "Person: 2 identical schemas\n  \
+             /spec/openapi.yaml:40:7: /components/schemas/Person\n  \
+             /spec/openapi.yaml:47:7: /components/schemas/Human"
+        );
+
+        let deduplicator = MockDeduplicator { duplicates: vec![] };
+        assert_eq!(
+            dedupe(deduplicator)?.to_string(),
+            "No duplicate schemas found"
+        );
+        let deduplicator = MockDeduplicator { duplicates: vec![] };
+        assert_eq!(apply(deduplicator)?.to_string(), "openapi: 3.0.0");
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        deprecated::{DeprecatedNode, DeprecationParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct ListResult {
+    nodes: Vec<DeprecatedNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(nodes: Vec<DeprecatedNode>, locations: bool) -> ListResult {
+        ListResult { nodes, locations }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.nodes
+            .sort_by(|a, b| (a.kind.name(), &a.name).cmp(&(b.kind.name(), &b.name)));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.nodes.is_empty() {
+            return write!(f, "Nothing is deprecated");
+        }
+        let lines: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let entry = ListEntry {
+                    name: format!("{} {}", node.kind.name(), node.name),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.nodes
+                .iter()
+                .map(|node| {
+                    YamlNode::mapping(vec![
+                        ("kind", YamlNode::string(node.kind.name())),
+                        ("name", YamlNode::string(&node.name)),
+                        ("file", YamlNode::string(&node.file.display().to_string())),
+                        ("line", YamlNode::integer(node.line)),
+                        ("column", YamlNode::integer(node.column)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Lists everything marked `deprecated: true`. With `fail_if_used` any
+/// operation that is still current but uses a deprecated schema is reported
+/// as an error instead.
+pub fn list<T: DeprecationParser>(
+    parser: T,
+    locations: bool,
+    fail_if_used: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    if fail_if_used {
+        let usages: Vec<String> = parser
+            .get_deprecated_usages()?
+            .iter()
+            .map(|usage| {
+                format!(
+                    "{}:{}:{}: `{}` uses deprecated schema `{}`",
+                    usage.file.display(),
+                    usage.line,
+                    usage.column,
+                    usage.operation,
+                    usage.schema
+                )
+            })
+            .collect();
+        if !usages.is_empty() {
+            return Err(OpenapiSchemerError::DeprecatedList(usages.join("\n")));
+        }
+    }
+    Ok(ListResult::new(parser.get_deprecated_nodes()?, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::deprecated::{DeprecatedKind, DeprecatedUsage};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<DeprecatedNode>,
+        usages: Vec<DeprecatedUsage>,
+    }
+    impl DeprecationParser for MockParser {
+        fn get_deprecated_nodes(&self) -> Result<Vec<DeprecatedNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_deprecated_usages(&self) -> Result<Vec<DeprecatedUsage>, OpenapiSchemerError> {
+            Ok(self.usages.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let node = |kind, name: &str, line| DeprecatedNode {
+            kind,
+            name: name.to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 7,
+        };
+        let parser = || MockParser {
+            nodes: vec![
+                node(DeprecatedKind::Operation, "DELETE /pets", 9),
+                node(DeprecatedKind::Parameter, "limit in query", 20),
+                node(DeprecatedKind::Schema, "Pet.tag", 31),
+            ],
+            usages: vec![],
+        };
+        assert_eq!(
+            list(parser(), false, true)?.to_string(),
+            "operation DELETE /pets\nparameter limit in query\nschema Pet.tag"
+        );
+        assert_eq!(
+            list(parser(), true, false)?.to_string(),
+            "/spec/openapi.yaml:9:7: operation DELETE /pets\n/spec/openapi.yaml:20:7: parameter limit in query\n/spec/openapi.yaml:31:7: schema Pet.tag"
+        );
+        let empty = MockParser {
+            nodes: vec![],
+            usages: vec![],
+        };
+        assert_eq!(
+            list(empty, false, false)?.to_string(),
+            "Nothing is deprecated"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_fail_if_used() {
+        let usage = |operation: &str, line| DeprecatedUsage {
+            operation: operation.to_string(),
+            schema: "Pet".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 20,
+        };
+        let parser = || MockParser {
+            nodes: vec![],
+            usages: vec![usage("GET /pets", 5), usage("POST /pets", 14)],
+        };
+        assert_eq!(
+            list(parser(), false, true).unwrap_err().to_string(),
+            "/spec/openapi.yaml:5:20: `GET /pets` uses deprecated schema `Pet`\n/spec/openapi.yaml:14:20: `POST /pets` uses deprecated schema `Pet`"
+        );
+        assert!(list(parser(), false, false).is_ok());
+    }
+}
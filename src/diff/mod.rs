@@ -0,0 +1,548 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        diff::{DiffParser, OperationSnapshot, SchemaSnapshot, SpecSnapshot},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+    Deprecated,
+}
+
+impl Change {
+    fn name(&self) -> &'static str {
+        match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Changed => "changed",
+            Change::Deprecated => "deprecated",
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Change::Added => "+",
+            Change::Removed => "-",
+            Change::Changed => "~",
+            Change::Deprecated => "!",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub change: Change,
+    /// What changed: `path`, `operation`, `parameter`, `schema` or
+    /// `property`.
+    pub kind: &'static str,
+    pub name: String,
+    /// How a changed item differs, such as `type integer -> string`.
+    pub detail: Option<String>,
+    /// Whether clients written against the old spec may fail against the
+    /// new one, such as when something is removed or a parameter becomes
+    /// required.
+    pub breaking: bool,
+}
+
+impl DiffEntry {
+    fn new(change: Change, kind: &'static str, name: String) -> DiffEntry {
+        DiffEntry {
+            change,
+            kind,
+            name,
+            detail: None,
+            breaking: change == Change::Removed,
+        }
+    }
+}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.change.symbol(), self.kind, self.name)?;
+        match &self.detail {
+            Some(detail) => write!(f, ": {}", detail),
+            None => Ok(()),
+        }
+    }
+}
+
+impl OutputFormatter for DiffEntry {
+    fn to_node(&self) -> YamlNode {
+        let mut pairs = vec![
+            ("change", YamlNode::string(self.change.name())),
+            ("kind", YamlNode::string(self.kind)),
+            ("name", YamlNode::string(&self.name)),
+        ];
+        if let Some(detail) = &self.detail {
+            pairs.push(("detail", YamlNode::string(detail)));
+        }
+        YamlNode::mapping(pairs)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DiffResult {
+    entries: Vec<DiffEntry>,
+}
+
+impl DiffResult {
+    pub fn new(entries: Vec<DiffEntry>) -> DiffResult {
+        DiffResult { entries }
+    }
+}
+
+impl Display for DiffResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "No differences found");
+        }
+        let lines: Vec<String> = self.entries.iter().map(DiffEntry::to_string).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for DiffResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.entries.iter().map(DiffEntry::to_node).collect())
+    }
+}
+
+/// Reports the names only in `old` as removed and the ones only in `new` as
+/// added, in the order they appear in each spec.
+fn added_and_removed(
+    kind: &'static str,
+    old: &[String],
+    new: &[String],
+    entries: &mut Vec<DiffEntry>,
+) {
+    for name in old.iter().filter(|name| !new.contains(name)) {
+        entries.push(DiffEntry::new(Change::Removed, kind, name.to_owned()));
+    }
+    for name in new.iter().filter(|name| !old.contains(name)) {
+        entries.push(DiffEntry::new(Change::Added, kind, name.to_owned()));
+    }
+}
+
+fn compare_operations(
+    old: &OperationSnapshot,
+    new: &OperationSnapshot,
+    entries: &mut Vec<DiffEntry>,
+) {
+    let names = |operation: &OperationSnapshot| -> Vec<String> {
+        operation
+            .parameters
+            .iter()
+            .map(|parameter| format!("{} in {}", parameter.describe(), operation.text))
+            .collect()
+    };
+    let start = entries.len();
+    added_and_removed("parameter", &names(old), &names(new), entries);
+    // Clients of the old spec don't send parameters that are new to them.
+    for entry in &mut entries[start..] {
+        entry.breaking |= entry.change == Change::Added
+            && new.parameters.iter().any(|parameter| {
+                parameter.required
+                    && format!("{} in {}", parameter.describe(), new.text) == entry.name
+            });
+    }
+    for parameter in &new.parameters {
+        let previous = match old
+            .parameters
+            .iter()
+            .find(|previous| previous.describe() == parameter.describe())
+        {
+            Some(previous) => previous,
+            None => continue,
+        };
+        let mut details = vec![];
+        if previous.required != parameter.required {
+            details.push(format!(
+                "required {} -> {}",
+                previous.required, parameter.required
+            ));
+        }
+        if previous.schema_type != parameter.schema_type {
+            details.push(format!(
+                "type {} -> {}",
+                previous.schema_type, parameter.schema_type
+            ));
+        }
+        if !details.is_empty() {
+            entries.push(DiffEntry {
+                change: Change::Changed,
+                kind: "parameter",
+                name: format!("{} in {}", parameter.describe(), new.text),
+                detail: Some(details.join(", ")),
+                breaking: (parameter.required && !previous.required)
+                    || previous.schema_type != parameter.schema_type,
+            });
+        }
+    }
+}
+
+fn compare_schemas(old: &SchemaSnapshot, new: &SchemaSnapshot, entries: &mut Vec<DiffEntry>) {
+    let names = |schema: &SchemaSnapshot| -> Vec<String> {
+        schema
+            .properties
+            .iter()
+            .map(|(property, _)| format!("{}.{}", schema.name, property))
+            .collect()
+    };
+    added_and_removed("property", &names(old), &names(new), entries);
+    for (property, property_type) in &new.properties {
+        let previous = old
+            .properties
+            .iter()
+            .find(|(previous, _)| previous == property);
+        if let Some((_, previous_type)) = previous {
+            if previous_type != property_type {
+                entries.push(DiffEntry {
+                    change: Change::Changed,
+                    kind: "property",
+                    name: format!("{}.{}", new.name, property),
+                    detail: Some(format!("type {} -> {}", previous_type, property_type)),
+                    breaking: true,
+                });
+            }
+        }
+    }
+}
+
+pub(crate) fn compare(old: &SpecSnapshot, new: &SpecSnapshot) -> Vec<DiffEntry> {
+    let mut entries = vec![];
+    added_and_removed("path", &old.paths, &new.paths, &mut entries);
+
+    let operations = |snapshot: &SpecSnapshot| -> Vec<String> {
+        snapshot
+            .operations
+            .iter()
+            .map(|operation| operation.text.to_owned())
+            .collect()
+    };
+    added_and_removed(
+        "operation",
+        &operations(old),
+        &operations(new),
+        &mut entries,
+    );
+    for operation in &new.operations {
+        if let Some(previous) = old
+            .operations
+            .iter()
+            .find(|previous| previous.text == operation.text)
+        {
+            compare_operations(previous, operation, &mut entries);
+            if operation.deprecated && !previous.deprecated {
+                entries.push(DiffEntry::new(
+                    Change::Deprecated,
+                    "operation",
+                    operation.text.to_owned(),
+                ));
+            }
+        }
+    }
+
+    let schemas = |snapshot: &SpecSnapshot| -> Vec<String> {
+        snapshot
+            .schemas
+            .iter()
+            .map(|schema| schema.name.to_owned())
+            .collect()
+    };
+    added_and_removed("schema", &schemas(old), &schemas(new), &mut entries);
+    for schema in &new.schemas {
+        if let Some(previous) = old
+            .schemas
+            .iter()
+            .find(|previous| previous.name == schema.name)
+        {
+            compare_schemas(previous, schema, &mut entries);
+            if schema.deprecated && !previous.deprecated {
+                entries.push(DiffEntry::new(
+                    Change::Deprecated,
+                    "schema",
+                    schema.name.to_owned(),
+                ));
+            }
+        }
+    }
+    entries
+}
+
+pub fn diff<T: DiffParser>(old: T, new: T) -> Result<DiffResult, OpenapiSchemerError> {
+    let old = old.get_snapshot()?;
+    let new = new.get_snapshot()?;
+    Ok(DiffResult::new(compare(&old, &new)))
+}
+
+/// The sections of a changelog in the order they are written.
+const CHANGELOG_SECTIONS: [(Change, &str); 4] = [
+    (Change::Added, "Added"),
+    (Change::Changed, "Changed"),
+    (Change::Deprecated, "Deprecated"),
+    (Change::Removed, "Removed"),
+];
+/// The kinds of entries a changelog lists, endpoints before schemas. Paths
+/// are left out as their operations are listed.
+const CHANGELOG_KINDS: [(&str, &str); 4] = [
+    ("operation", "Endpoint"),
+    ("parameter", "Parameter"),
+    ("schema", "Schema"),
+    ("property", "Property"),
+];
+
+/// The differences between two versions of a spec as Markdown release notes.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChangelogResult {
+    entries: Vec<DiffEntry>,
+}
+
+impl ChangelogResult {
+    pub fn new(entries: Vec<DiffEntry>) -> ChangelogResult {
+        ChangelogResult { entries }
+    }
+
+    /// The entries of a section, grouped by kind.
+    fn section(&self, change: Change) -> Vec<(&'static str, &DiffEntry)> {
+        let mut entries = vec![];
+        for (kind, label) in CHANGELOG_KINDS {
+            entries.extend(
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.change == change && entry.kind == kind)
+                    .map(|entry| (label, entry)),
+            );
+        }
+        entries
+    }
+}
+
+impl Display for ChangelogResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut sections = vec![];
+        for (change, heading) in CHANGELOG_SECTIONS {
+            let entries = self.section(change);
+            if entries.is_empty() {
+                continue;
+            }
+            let mut lines = vec![format!("## {}", heading), String::new()];
+            for (label, entry) in entries {
+                let line = format!("- {} `{}`", label, entry.name);
+                lines.push(match &entry.detail {
+                    Some(detail) => format!("{}: {}", line, detail),
+                    None => line,
+                });
+            }
+            sections.push(lines.join("\n"));
+        }
+        match sections.is_empty() {
+            true => write!(f, "No changes"),
+            false => write!(f, "{}", sections.join("\n\n")),
+        }
+    }
+}
+
+impl OutputFormatter for ChangelogResult {
+    fn to_node(&self) -> YamlNode {
+        let sections = CHANGELOG_SECTIONS
+            .iter()
+            .map(|(change, _)| {
+                let entries = self
+                    .section(*change)
+                    .into_iter()
+                    .map(|(_, entry)| {
+                        let mut pairs = vec![
+                            ("kind", YamlNode::string(entry.kind)),
+                            ("name", YamlNode::string(&entry.name)),
+                        ];
+                        if let Some(detail) = &entry.detail {
+                            pairs.push(("detail", YamlNode::string(detail)));
+                        }
+                        YamlNode::mapping(pairs)
+                    })
+                    .collect();
+                (change.name(), YamlNode::sequence(entries))
+            })
+            .collect();
+        YamlNode::mapping(sections)
+    }
+}
+
+pub fn changelog<T: DiffParser>(old: T, new: T) -> Result<ChangelogResult, OpenapiSchemerError> {
+    let old = old.get_snapshot()?;
+    let new = new.get_snapshot()?;
+    Ok(ChangelogResult::new(compare(&old, &new)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{bindings::diff::ParameterSnapshot, output::OutputFormat};
+
+    use super::*;
+
+    struct MockParser {
+        snapshot: SpecSnapshot,
+    }
+    impl DiffParser for MockParser {
+        fn get_snapshot(&self) -> Result<SpecSnapshot, OpenapiSchemerError> {
+            Ok(self.snapshot.to_owned())
+        }
+    }
+
+    fn limit(required: bool) -> ParameterSnapshot {
+        ParameterSnapshot {
+            name: "limit".to_string(),
+            location: "query".to_string(),
+            required,
+            schema_type: "integer".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff() -> Result<(), Box<dyn Error>> {
+        let old = MockParser {
+            snapshot: SpecSnapshot {
+                paths: vec!["/pets".to_string(), "/pets/{petId}".to_string()],
+                operations: vec![
+                    OperationSnapshot {
+                        text: "GET /pets".to_string(),
+                        parameters: vec![limit(false)],
+                        deprecated: false,
+                    },
+                    OperationSnapshot {
+                        text: "GET /pets/{petId}".to_string(),
+                        parameters: vec![],
+                        deprecated: false,
+                    },
+                ],
+                schemas: vec![SchemaSnapshot {
+                    name: "Pet".to_string(),
+                    properties: vec![
+                        ("id".to_string(), "integer".to_string()),
+                        ("tag".to_string(), "string".to_string()),
+                    ],
+                    deprecated: false,
+                }],
+            },
+        };
+        let new = MockParser {
+            snapshot: SpecSnapshot {
+                paths: vec!["/pets".to_string(), "/owners".to_string()],
+                operations: vec![
+                    OperationSnapshot {
+                        text: "GET /pets".to_string(),
+                        parameters: vec![limit(true)],
+                        deprecated: true,
+                    },
+                    OperationSnapshot {
+                        text: "GET /owners".to_string(),
+                        parameters: vec![],
+                        deprecated: false,
+                    },
+                ],
+                schemas: vec![SchemaSnapshot {
+                    name: "Pet".to_string(),
+                    properties: vec![
+                        ("id".to_string(), "string".to_string()),
+                        ("name".to_string(), "string".to_string()),
+                    ],
+                    deprecated: false,
+                }],
+            },
+        };
+        let result = diff(old, new)?;
+        assert_eq!(
+            result.to_string(),
+            "- path /pets/{petId}\n\
+             + path /owners\n\
+             - operation GET /pets/{petId}\n\
+             + operation GET /owners\n\
+             ~ parameter limit (query) in GET /pets: required false -> true\n\
+             ! operation GET /pets\n\
+             - property Pet.tag\n\
+             + property Pet.name\n\
+             ~ property Pet.id: type integer -> string"
+        );
+        assert!(result
+            .format(OutputFormat::Json)
+            .starts_with("[\n  {\n    \"change\": \"removed\",\n    \"kind\": \"path\","));
+
+        let old = MockParser {
+            snapshot: SpecSnapshot::default(),
+        };
+        let new = MockParser {
+            snapshot: SpecSnapshot::default(),
+        };
+        assert_eq!(diff(old, new)?.to_string(), "No differences found");
+        Ok(())
+    }
+
+    #[test]
+    fn test_changelog() -> Result<(), Box<dyn Error>> {
+        let snapshot = |operations: Vec<(&str, bool)>, properties: Vec<&str>| SpecSnapshot {
+            paths: vec!["/pets".to_string()],
+            operations: operations
+                .into_iter()
+                .map(|(text, deprecated)| OperationSnapshot {
+                    text: text.to_string(),
+                    parameters: vec![],
+                    deprecated,
+                })
+                .collect(),
+            schemas: vec![SchemaSnapshot {
+                name: "Pet".to_string(),
+                properties: properties
+                    .into_iter()
+                    .map(|name| (name.to_string(), "string".to_string()))
+                    .collect(),
+                deprecated: false,
+            }],
+        };
+        let old = MockParser {
+            snapshot: snapshot(
+                vec![("GET /pets", false), ("DELETE /pets", false)],
+                vec!["tag"],
+            ),
+        };
+        let new = MockParser {
+            snapshot: snapshot(
+                vec![("GET /pets", true), ("POST /pets", false)],
+                vec!["name"],
+            ),
+        };
+        let result = changelog(old, new)?;
+        assert_eq!(
+            result.to_string(),
+            "## Added\n\n\
+             - Endpoint `POST /pets`\n\
+             - Property `Pet.name`\n\n\
+             ## Deprecated\n\n\
+             - Endpoint `GET /pets`\n\n\
+             ## Removed\n\n\
+             - Endpoint `DELETE /pets`\n\
+             - Property `Pet.tag`"
+        );
+        assert!(result
+            .format(OutputFormat::Json)
+            .contains("\"changed\": [],\n  \"deprecated\": [\n"));
+
+        let unchanged = || MockParser {
+            snapshot: SpecSnapshot::default(),
+        };
+        assert_eq!(
+            changelog(unchanged(), unchanged())?.to_string(),
+            "No changes"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::docs::{DocsBody, DocsOperation, DocsPage, DocsParser, DocsType},
+    error::OpenapiSchemerError,
+};
+
+/// The page every `docs html` export is rendered into. `{{title}}`,
+/// `{{nav}}` and `{{content}}` are replaced with the escaped spec contents.
+const TEMPLATE: &str = include_str!("template.html");
+
+/// A standalone HTML page documenting the spec. Styles are inlined so the
+/// page needs no network access to display.
+#[derive(Debug)]
+pub struct HtmlResult {
+    page: DocsPage,
+}
+
+impl HtmlResult {
+    pub fn new(page: DocsPage) -> HtmlResult {
+        HtmlResult { page }
+    }
+
+    fn nav(&self) -> String {
+        let mut nav = vec![format!(
+            "<a href=\"#top\"><strong>{}</strong></a>",
+            escape(&self.page.title)
+        )];
+        if !self.page.operations.is_empty() {
+            nav.push("<h2>Operations</h2>\n<ul>".to_string());
+            for operation in &self.page.operations {
+                let node = &operation.operation;
+                nav.push(format!(
+                    "<li><a href=\"#{}\">{} {}</a></li>",
+                    operation_anchor(operation),
+                    node.method.to_uppercase(),
+                    escape(&node.path)
+                ));
+            }
+            nav.push("</ul>".to_string());
+        }
+        let sections = [
+            (
+                "Schemas",
+                "schema",
+                self.page
+                    .schemas
+                    .iter()
+                    .map(|schema| &schema.name)
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                "Examples",
+                "example",
+                self.page
+                    .examples
+                    .iter()
+                    .map(|example| &example.name)
+                    .collect(),
+            ),
+        ];
+        for (heading, prefix, names) in sections {
+            if names.is_empty() {
+                continue;
+            }
+            nav.push(format!("<h2>{}</h2>\n<ul>", heading));
+            for name in names {
+                nav.push(format!(
+                    "<li><a href=\"#{}-{}\">{}</a></li>",
+                    prefix,
+                    slug(name),
+                    escape(name)
+                ));
+            }
+            nav.push("</ul>".to_string());
+        }
+        nav.join("\n")
+    }
+
+    fn content(&self) -> String {
+        let mut content = vec![format!("<h1 id=\"top\">{}</h1>", escape(&self.page.title))];
+        if let Some(version) = &self.page.version {
+            content.push(format!(
+                "<p class=\"muted\">Version {}</p>",
+                escape(version)
+            ));
+        }
+        if let Some(description) = &self.page.description {
+            content.push(paragraph(description));
+        }
+        if !self.page.operations.is_empty() {
+            content.push("<h2>Operations</h2>".to_string());
+            content.extend(self.page.operations.iter().map(operation_section));
+        }
+        if !self.page.schemas.is_empty() {
+            content.push("<h2>Schemas</h2>".to_string());
+            for schema in &self.page.schemas {
+                let mut section = vec![
+                    format!("<section id=\"schema-{}\">", slug(&schema.name)),
+                    format!("<h3>{}</h3>", escape(&schema.name)),
+                ];
+                section.extend(schema.description.as_deref().map(paragraph));
+                section.push(format!(
+                    "<pre><code>{}</code></pre>",
+                    escape(schema.schema.to_yaml().trim_end())
+                ));
+                section.push("</section>".to_string());
+                content.push(section.join("\n"));
+            }
+        }
+        if !self.page.examples.is_empty() {
+            content.push("<h2>Examples</h2>".to_string());
+            for example in &self.page.examples {
+                let mut section = vec![
+                    format!("<section id=\"example-{}\">", slug(&example.name)),
+                    format!("<h3>{}</h3>", escape(&example.name)),
+                ];
+                section.extend(example.summary.as_deref().map(paragraph));
+                section.push(format!(
+                    "<pre><code>{}</code></pre>",
+                    escape(&example.value.to_json())
+                ));
+                section.push("</section>".to_string());
+                content.push(section.join("\n"));
+            }
+        }
+        content.join("\n")
+    }
+}
+
+impl Display for HtmlResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values = [
+            ("title", escape(&self.page.title)),
+            ("nav", self.nav()),
+            ("content", self.content()),
+        ];
+        write!(f, "{}", render(&values).trim_end())
+    }
+}
+
+/// Fills in the placeholders of `TEMPLATE`. Only the template is scanned, so
+/// a spec that happens to contain `{{nav}}` is written as it is.
+fn render(values: &[(&str, String)]) -> String {
+    let mut html = String::with_capacity(TEMPLATE.len());
+    let mut rest = TEMPLATE;
+    while let Some((before, after)) = rest.split_once("{{") {
+        let (name, after) = after.split_once("}}").unwrap_or((after, ""));
+        html.push_str(before);
+        if let Some((_, value)) = values.iter().find(|(key, _)| *key == name) {
+            html.push_str(value);
+        }
+        rest = after;
+    }
+    html.push_str(rest);
+    html
+}
+
+fn operation_section(operation: &DocsOperation) -> String {
+    let node = &operation.operation;
+    let class = match node.deprecated {
+        true => "operation deprecated-operation",
+        false => "operation",
+    };
+    let mut section = vec![
+        format!(
+            "<section class=\"{}\" id=\"{}\">",
+            class,
+            operation_anchor(operation)
+        ),
+        format!(
+            "<h3><span class=\"method {}\">{}</span> <code>{}</code></h3>",
+            escape(&node.method),
+            node.method.to_uppercase(),
+            escape(&node.path)
+        ),
+    ];
+    let mut details = vec![];
+    if let Some(operation_id) = &node.operation_id {
+        details.push(format!("<code>{}</code>", escape(operation_id)));
+    }
+    if !node.tags.is_empty() {
+        details.push(escape(&node.tags.join(", ")));
+    }
+    if node.deprecated {
+        details.push("<span class=\"deprecated\">Deprecated</span>".to_string());
+    }
+    if !details.is_empty() {
+        section.push(format!("<p class=\"muted\">{}</p>", details.join(" · ")));
+    }
+    if let Some(summary) = &node.summary {
+        section.push(format!("<p><strong>{}</strong></p>", escape(summary)));
+    }
+    section.extend(operation.description.as_deref().map(paragraph));
+    if !operation.parameters.is_empty() {
+        section.push("<h4>Parameters</h4>".to_string());
+        section.push(
+            "<table>\n<tr><th>Name</th><th>In</th><th>Type</th><th>Required</th><th>Description</th></tr>"
+                .to_string(),
+        );
+        for parameter in &operation.parameters {
+            section.push(format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&parameter.name),
+                escape(&parameter.location),
+                parameter.schema.as_ref().map(type_html).unwrap_or_default(),
+                match parameter.required {
+                    true => "yes",
+                    false => "no",
+                },
+                escape(parameter.description.as_deref().unwrap_or_default())
+            ));
+        }
+        section.push("</table>".to_string());
+    }
+    if let Some(body) = &operation.request_body {
+        section.push("<h4>Request body</h4>".to_string());
+        section.push(body_html(body));
+    }
+    if !operation.responses.is_empty() {
+        section.push("<h4>Responses</h4>".to_string());
+        for response in &operation.responses {
+            section.push(format!("<h5><code>{}</code></h5>", escape(&response.name)));
+            section.push(body_html(response));
+        }
+    }
+    section.push("</section>".to_string());
+    section.join("\n")
+}
+
+fn body_html(body: &DocsBody) -> String {
+    let mut html = vec![];
+    html.extend(body.description.as_deref().map(paragraph));
+    for media in &body.media_types {
+        let schema = match &media.schema {
+            Some(schema) => format!(": {}", type_html(schema)),
+            None => String::new(),
+        };
+        html.push(format!(
+            "<p><code>{}</code>{}</p>",
+            escape(&media.name),
+            schema
+        ));
+        if let Some(example) = &media.example {
+            html.push(format!(
+                "<pre><code>{}</code></pre>",
+                escape(&example.to_json())
+            ));
+        }
+    }
+    html.join("\n")
+}
+
+/// The type of a schema, linking to the component it refers to.
+fn type_html(schema: &DocsType) -> String {
+    let name = match schema.component {
+        true => format!(
+            "<a href=\"#schema-{}\">{}</a>",
+            slug(&schema.name),
+            escape(&schema.name)
+        ),
+        false => escape(&schema.name),
+    };
+    match schema.array {
+        true => format!("array of {}", name),
+        false => name,
+    }
+}
+
+fn paragraph(text: &str) -> String {
+    format!(
+        "<p>{}</p>",
+        escape(text.trim()).replace("\n\n", "</p>\n<p>")
+    )
+}
+
+fn operation_anchor(operation: &DocsOperation) -> String {
+    let node = &operation.operation;
+    format!("operation-{}{}", node.method, slug(&node.path))
+}
+
+/// `text` reduced to the characters that are safe in an anchor, with
+/// anything else replaced by `-`.
+fn slug(text: &str) -> String {
+    text.chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c,
+            false => '-',
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn html<T: DocsParser>(parser: T) -> Result<HtmlResult, OpenapiSchemerError> {
+    let page = parser.get_docs_page()?;
+    Ok(HtmlResult::new(page))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::{
+        docs::{DocsBody, DocsMediaType, DocsOperation, DocsSchema},
+        yaml::YamlNode,
+        OperationNode,
+    };
+
+    use super::*;
+
+    struct MockDocsParser {
+        page: DocsPage,
+    }
+    impl DocsParser for MockDocsParser {
+        fn get_docs_page(&self) -> Result<DocsPage, OpenapiSchemerError> {
+            Ok(self.page.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_html() -> Result<(), Box<dyn Error>> {
+        let operation = OperationNode {
+            text: "showPetById".to_string(),
+            operation_id: Some("showPetById".to_string()),
+            method: "get".to_string(),
+            path: "/pets/{petId}".to_string(),
+            summary: Some("Info for a <specific> pet".to_string()),
+            tags: vec!["pets".to_string()],
+            deprecated: false,
+            file: PathBuf::from("#"),
+            line: 5,
+            column: 20,
+        };
+        let page = DocsPage {
+            title: "Petstore".to_string(),
+            version: Some("1.0.0".to_string()),
+            description: None,
+            operations: vec![DocsOperation {
+                operation,
+                description: None,
+                parameters: vec![],
+                request_body: None,
+                responses: vec![DocsBody {
+                    name: "200".to_string(),
+                    description: Some("A pet".to_string()),
+                    media_types: vec![DocsMediaType {
+                        name: "application/json".to_string(),
+                        schema: Some(DocsType {
+                            array: false,
+                            name: "Pet".to_string(),
+                            component: true,
+                        }),
+                        example: Some(YamlNode::mapping(vec![("name", YamlNode::string("Rex"))])),
+                    }],
+                }],
+            }],
+            schemas: vec![DocsSchema {
+                name: "Pet".to_string(),
+                description: None,
+                schema: YamlNode::mapping(vec![("type", YamlNode::string("object"))]),
+            }],
+            examples: vec![],
+        };
+        let html = html(MockDocsParser { page })?.to_string();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>"));
+        assert!(html.contains("<title>Petstore</title>"));
+        assert!(
+            html.contains("<li><a href=\"#operation-get-pets--petId-\">GET /pets/{petId}</a></li>")
+        );
+        assert!(html.contains("<p><strong>Info for a &lt;specific&gt; pet</strong></p>"));
+        assert!(html.contains(
+            "<p><code>application/json</code>: <a href=\"#schema-Pet\">Pet</a></p>\n<pre><code>{\n  &quot;name&quot;: &quot;Rex&quot;\n}</code></pre>"
+        ));
+        assert!(html.contains(
+            "<section id=\"schema-Pet\">\n<h3>Pet</h3>\n<pre><code>type: object</code></pre>\n</section>"
+        ));
+        assert!(!html.contains("{{"));
+        assert!(!html.contains("<h2>Examples</h2>"));
+        Ok(())
+    }
+}
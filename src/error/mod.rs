@@ -1,10 +1,96 @@
 use std::fmt;
 
+use clap::ValueEnum;
+
+use crate::bindings::yaml::YamlNode;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    Plain,
+    Json,
+}
+
 #[derive(Debug)]
 pub enum OpenapiSchemerError {
+    Browse(String),
+    Bundle(String),
+    CircularRef(String),
+    Config(String),
+    ConformanceCheck(String),
+    ContentLoad(String),
+    Convert(String),
+    Coverage(String),
+    Dedupe(String),
+    DeprecatedList(String),
+    Diff(String),
+    DocsHtml(String),
+    ExampleList(String),
+    ExampleShow(String),
+    ExportGraphql(String),
+    ExportHttpFile(String),
+    ExportProto(String),
+    ExtensionList(String),
+    Filter(String),
+    Flatten(String),
+    Generate(String),
+    Get(String),
+    HeaderList(String),
+    HeaderShow(String),
+    Http(String),
+    Keys(String),
+    LinkList(String),
+    LinkShow(String),
+    Lint(String),
+    OperationCallbacks(String),
+    OperationContentTypes(String),
+    OperationCurl(String),
+    OperationInvoke(String),
     OperationList(String),
+    OperationRename(String),
+    OperationResponses(String),
+    OperationSecurity(String),
+    OperationShow(String),
+    OverlayApply(String),
+    ParameterList(String),
+    ParameterShow(String),
     PathList(String),
+    PathMatch(String),
+    PathRename(String),
+    PathShow(String),
+    Query(String),
+    RefsCheck(String),
+    RefsList(String),
+    RefsResolve(String),
+    RefsTo(String),
+    ResponseList(String),
+    ResponseShow(String),
+    SchemaAuditRequired(String),
+    SchemaEnums(String),
+    SchemaExportJsonSchema(String),
     SchemaList(String),
+    SchemaProperties(String),
+    SchemaRename(String),
+    SchemaResolve(String),
+    SchemaShow(String),
+    SchemaUsages(String),
+    Search(String),
+    SecurityAudit(String),
+    SecurityList(String),
+    SecurityScopes(String),
+    Serve(String),
+    ServerList(String),
+    Split(String),
+    Stats(String),
+    TagList(String),
+    TagShow(String),
+    Unused(String),
+    Upgrade(String),
+    Validate(String),
+    ValidateRequest(String),
+    ValidateResponse(String),
+    VersionSuggest(String),
+    Watch(String),
+    WebhookList(String),
 }
 
 impl std::error::Error for OpenapiSchemerError {}
@@ -12,9 +98,218 @@ impl std::error::Error for OpenapiSchemerError {}
 impl fmt::Display for OpenapiSchemerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            OpenapiSchemerError::Browse(cause) => cause.fmt(f),
+            OpenapiSchemerError::Bundle(cause) => cause.fmt(f),
+            OpenapiSchemerError::CircularRef(cause) => cause.fmt(f),
+            OpenapiSchemerError::Config(cause) => cause.fmt(f),
+            OpenapiSchemerError::ConformanceCheck(cause) => cause.fmt(f),
+            OpenapiSchemerError::ContentLoad(cause) => cause.fmt(f),
+            OpenapiSchemerError::Convert(cause) => cause.fmt(f),
+            OpenapiSchemerError::Coverage(cause) => cause.fmt(f),
+            OpenapiSchemerError::Dedupe(cause) => cause.fmt(f),
+            OpenapiSchemerError::DeprecatedList(cause) => cause.fmt(f),
+            OpenapiSchemerError::Diff(cause) => cause.fmt(f),
+            OpenapiSchemerError::DocsHtml(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExampleList(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExampleShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExportGraphql(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExportHttpFile(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExportProto(cause) => cause.fmt(f),
+            OpenapiSchemerError::ExtensionList(cause) => cause.fmt(f),
+            OpenapiSchemerError::Filter(cause) => cause.fmt(f),
+            OpenapiSchemerError::Flatten(cause) => cause.fmt(f),
+            OpenapiSchemerError::Generate(cause) => cause.fmt(f),
+            OpenapiSchemerError::Get(cause) => cause.fmt(f),
+            OpenapiSchemerError::HeaderList(cause) => cause.fmt(f),
+            OpenapiSchemerError::HeaderShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::Http(cause) => cause.fmt(f),
+            OpenapiSchemerError::Keys(cause) => cause.fmt(f),
+            OpenapiSchemerError::LinkList(cause) => cause.fmt(f),
+            OpenapiSchemerError::LinkShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::Lint(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationCallbacks(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationContentTypes(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationCurl(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationInvoke(cause) => cause.fmt(f),
             OpenapiSchemerError::OperationList(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationRename(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationResponses(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationSecurity(cause) => cause.fmt(f),
+            OpenapiSchemerError::OperationShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::OverlayApply(cause) => cause.fmt(f),
+            OpenapiSchemerError::ParameterList(cause) => cause.fmt(f),
+            OpenapiSchemerError::ParameterShow(cause) => cause.fmt(f),
             OpenapiSchemerError::PathList(cause) => cause.fmt(f),
+            OpenapiSchemerError::PathMatch(cause) => cause.fmt(f),
+            OpenapiSchemerError::PathRename(cause) => cause.fmt(f),
+            OpenapiSchemerError::PathShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::Query(cause) => cause.fmt(f),
+            OpenapiSchemerError::RefsCheck(cause) => cause.fmt(f),
+            OpenapiSchemerError::RefsList(cause) => cause.fmt(f),
+            OpenapiSchemerError::RefsResolve(cause) => cause.fmt(f),
+            OpenapiSchemerError::RefsTo(cause) => cause.fmt(f),
+            OpenapiSchemerError::ResponseList(cause) => cause.fmt(f),
+            OpenapiSchemerError::ResponseShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaAuditRequired(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaEnums(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaExportJsonSchema(cause) => cause.fmt(f),
             OpenapiSchemerError::SchemaList(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaProperties(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaRename(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaResolve(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::SchemaUsages(cause) => cause.fmt(f),
+            OpenapiSchemerError::Search(cause) => cause.fmt(f),
+            OpenapiSchemerError::SecurityAudit(cause) => cause.fmt(f),
+            OpenapiSchemerError::SecurityList(cause) => cause.fmt(f),
+            OpenapiSchemerError::SecurityScopes(cause) => cause.fmt(f),
+            OpenapiSchemerError::Serve(cause) => cause.fmt(f),
+            OpenapiSchemerError::ServerList(cause) => cause.fmt(f),
+            OpenapiSchemerError::Split(cause) => cause.fmt(f),
+            OpenapiSchemerError::Stats(cause) => cause.fmt(f),
+            OpenapiSchemerError::TagList(cause) => cause.fmt(f),
+            OpenapiSchemerError::TagShow(cause) => cause.fmt(f),
+            OpenapiSchemerError::Unused(cause) => cause.fmt(f),
+            OpenapiSchemerError::Upgrade(cause) => cause.fmt(f),
+            OpenapiSchemerError::Validate(cause) => cause.fmt(f),
+            OpenapiSchemerError::ValidateRequest(cause) => cause.fmt(f),
+            OpenapiSchemerError::ValidateResponse(cause) => cause.fmt(f),
+            OpenapiSchemerError::VersionSuggest(cause) => cause.fmt(f),
+            OpenapiSchemerError::Watch(cause) => cause.fmt(f),
+            OpenapiSchemerError::WebhookList(cause) => cause.fmt(f),
         }
     }
 }
+
+impl OpenapiSchemerError {
+    /// The name of the variant and the exit code the process ends with. Exit
+    /// codes are part of the CLI's interface, so existing ones never change
+    /// and new variants take the next free code.
+    fn kind(&self) -> (&'static str, u8) {
+        match self {
+            OpenapiSchemerError::Browse(_) => ("Browse", 44),
+            OpenapiSchemerError::Bundle(_) => ("Bundle", 10),
+            OpenapiSchemerError::CircularRef(_) => ("CircularRef", 11),
+            OpenapiSchemerError::Config(_) => ("Config", 47),
+            OpenapiSchemerError::ConformanceCheck(_) => ("ConformanceCheck", 83),
+            OpenapiSchemerError::ContentLoad(_) => ("ContentLoad", 33),
+            OpenapiSchemerError::Convert(_) => ("Convert", 41),
+            OpenapiSchemerError::Coverage(_) => ("Coverage", 84),
+            OpenapiSchemerError::Dedupe(_) => ("Dedupe", 59),
+            OpenapiSchemerError::DeprecatedList(_) => ("DeprecatedList", 63),
+            OpenapiSchemerError::Diff(_) => ("Diff", 12),
+            OpenapiSchemerError::DocsHtml(_) => ("DocsHtml", 67),
+            OpenapiSchemerError::ExampleList(_) => ("ExampleList", 35),
+            OpenapiSchemerError::ExampleShow(_) => ("ExampleShow", 36),
+            OpenapiSchemerError::ExportGraphql(_) => ("ExportGraphql", 86),
+            OpenapiSchemerError::ExportHttpFile(_) => ("ExportHttpFile", 68),
+            OpenapiSchemerError::ExportProto(_) => ("ExportProto", 85),
+            OpenapiSchemerError::ExtensionList(_) => ("ExtensionList", 74),
+            OpenapiSchemerError::Filter(_) => ("Filter", 75),
+            OpenapiSchemerError::Flatten(_) => ("Flatten", 53),
+            OpenapiSchemerError::Generate(_) => ("Generate", 69),
+            OpenapiSchemerError::Get(_) => ("Get", 61),
+            OpenapiSchemerError::HeaderList(_) => ("HeaderList", 48),
+            OpenapiSchemerError::HeaderShow(_) => ("HeaderShow", 49),
+            OpenapiSchemerError::Http(_) => ("Http", 38),
+            OpenapiSchemerError::Keys(_) => ("Keys", 62),
+            OpenapiSchemerError::LinkList(_) => ("LinkList", 50),
+            OpenapiSchemerError::LinkShow(_) => ("LinkShow", 51),
+            OpenapiSchemerError::Lint(_) => ("Lint", 13),
+            OpenapiSchemerError::OperationCallbacks(_) => ("OperationCallbacks", 52),
+            OpenapiSchemerError::OperationContentTypes(_) => ("OperationContentTypes", 65),
+            OpenapiSchemerError::OperationCurl(_) => ("OperationCurl", 40),
+            OpenapiSchemerError::OperationInvoke(_) => ("OperationInvoke", 39),
+            OpenapiSchemerError::OperationList(_) => ("OperationList", 14),
+            OpenapiSchemerError::OperationRename(_) => ("OperationRename", 55),
+            OpenapiSchemerError::OperationResponses(_) => ("OperationResponses", 66),
+            OpenapiSchemerError::OperationSecurity(_) => ("OperationSecurity", 72),
+            OpenapiSchemerError::OperationShow(_) => ("OperationShow", 15),
+            OpenapiSchemerError::OverlayApply(_) => ("OverlayApply", 76),
+            OpenapiSchemerError::ParameterList(_) => ("ParameterList", 16),
+            OpenapiSchemerError::ParameterShow(_) => ("ParameterShow", 17),
+            OpenapiSchemerError::PathList(_) => ("PathList", 18),
+            OpenapiSchemerError::PathMatch(_) => ("PathMatch", 80),
+            OpenapiSchemerError::PathRename(_) => ("PathRename", 57),
+            OpenapiSchemerError::PathShow(_) => ("PathShow", 19),
+            OpenapiSchemerError::Query(_) => ("Query", 60),
+            OpenapiSchemerError::RefsCheck(_) => ("RefsCheck", 20),
+            OpenapiSchemerError::RefsList(_) => ("RefsList", 21),
+            OpenapiSchemerError::RefsResolve(_) => ("RefsResolve", 78),
+            OpenapiSchemerError::RefsTo(_) => ("RefsTo", 79),
+            OpenapiSchemerError::ResponseList(_) => ("ResponseList", 22),
+            OpenapiSchemerError::ResponseShow(_) => ("ResponseShow", 23),
+            OpenapiSchemerError::SchemaAuditRequired(_) => ("SchemaAuditRequired", 88),
+            OpenapiSchemerError::SchemaEnums(_) => ("SchemaEnums", 64),
+            OpenapiSchemerError::SchemaExportJsonSchema(_) => ("SchemaExportJsonSchema", 70),
+            OpenapiSchemerError::SchemaList(_) => ("SchemaList", 24),
+            OpenapiSchemerError::SchemaProperties(_) => ("SchemaProperties", 87),
+            OpenapiSchemerError::SchemaRename(_) => ("SchemaRename", 56),
+            OpenapiSchemerError::SchemaResolve(_) => ("SchemaResolve", 71),
+            OpenapiSchemerError::SchemaShow(_) => ("SchemaShow", 25),
+            OpenapiSchemerError::SchemaUsages(_) => ("SchemaUsages", 54),
+            OpenapiSchemerError::Search(_) => ("Search", 45),
+            OpenapiSchemerError::SecurityAudit(_) => ("SecurityAudit", 26),
+            OpenapiSchemerError::SecurityList(_) => ("SecurityList", 27),
+            OpenapiSchemerError::SecurityScopes(_) => ("SecurityScopes", 73),
+            OpenapiSchemerError::Serve(_) => ("Serve", 37),
+            OpenapiSchemerError::ServerList(_) => ("ServerList", 28),
+            OpenapiSchemerError::Split(_) => ("Split", 58),
+            OpenapiSchemerError::Stats(_) => ("Stats", 46),
+            OpenapiSchemerError::TagList(_) => ("TagList", 29),
+            OpenapiSchemerError::TagShow(_) => ("TagShow", 30),
+            OpenapiSchemerError::Unused(_) => ("Unused", 31),
+            OpenapiSchemerError::Upgrade(_) => ("Upgrade", 43),
+            OpenapiSchemerError::Validate(_) => ("Validate", 32),
+            OpenapiSchemerError::ValidateRequest(_) => ("ValidateRequest", 81),
+            OpenapiSchemerError::ValidateResponse(_) => ("ValidateResponse", 82),
+            OpenapiSchemerError::VersionSuggest(_) => ("VersionSuggest", 77),
+            OpenapiSchemerError::Watch(_) => ("Watch", 34),
+            OpenapiSchemerError::WebhookList(_) => ("WebhookList", 42),
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        self.kind().1
+    }
+
+    pub fn format(&self, format: ErrorFormat) -> String {
+        match format {
+            ErrorFormat::Plain => format!("Failed: {}", self),
+            ErrorFormat::Json => YamlNode::mapping(vec![
+                ("error", YamlNode::string(self.kind().0)),
+                ("code", YamlNode::integer(self.exit_code() as usize)),
+                ("message", YamlNode::string(&self.to_string())),
+            ])
+            .to_json(),
+        }
+    }
+
+    /// Converts an error from the resolver into the error of the command that
+    /// hit it. Errors that already carry their own variant, such as
+    /// `CircularRef`, are kept as they are.
+    pub fn from_anyhow(error: anyhow::Error, variant: fn(String) -> Self) -> Self {
+        match error.downcast::<OpenapiSchemerError>() {
+            Ok(error) => error,
+            Err(error) => variant(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        let error = OpenapiSchemerError::SchemaShow("Could not find schema `Dog`".to_string());
+        assert_eq!(
+            error.format(ErrorFormat::Plain),
+            "Failed: Could not find schema `Dog`"
+        );
+        assert_eq!(
+            error.format(ErrorFormat::Json),
+            "{\n  \"error\": \"SchemaShow\",\n  \"code\": 25,\n  \"message\": \"Could not find schema `Dog`\"\n}"
+        );
+    }
+}
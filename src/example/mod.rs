@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        example::{ExampleNode, ExampleParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    examples: Vec<ExampleNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(examples: Vec<ExampleNode>, locations: bool) -> ListResult {
+        ListResult {
+            examples,
+            locations,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.examples.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .examples
+            .iter()
+            .map(|example| {
+                let entry = ListEntry {
+                    name: example.pointer.to_owned(),
+                    file: example.file.to_owned(),
+                    line: example.line,
+                    column: example.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let examples = self
+            .examples
+            .iter()
+            .map(|example| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&example.text)),
+                    ("pointer", YamlNode::string(&example.pointer)),
+                    (
+                        "summary",
+                        example
+                            .summary
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    (
+                        "file",
+                        YamlNode::string(&example.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(example.line)),
+                    ("column", YamlNode::integer(example.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(examples)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    example: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(example: YamlNode) -> ShowResult {
+        ShowResult { example }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        self.example.to_owned()
+    }
+}
+
+pub fn list<T: ExampleParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_example_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+pub fn show<T: ExampleParser>(parser: T, pointer: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_example(pointer)? {
+        Some(example) => Ok(ShowResult::new(example)),
+        None => Err(OpenapiSchemerError::ExampleShow(format!(
+            "Could not find example `{}`",
+            pointer
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<ExampleNode>,
+        example: Option<YamlNode>,
+    }
+    impl ExampleParser for MockParser {
+        fn get_example_nodes(&self) -> Result<Vec<ExampleNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_example(&self, _pointer: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.example.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            nodes: vec![ExampleNode {
+                text: "Pet".to_string(),
+                pointer: "#/components/examples/Pet".to_string(),
+                summary: None,
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 4,
+                column: 5,
+            }],
+            example: None,
+        };
+        assert_eq!(
+            list(parser, true)?.to_string(),
+            "/spec/openapi.yaml:4:5: #/components/examples/Pet"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            nodes: vec![],
+            example: Some(YamlNode::mapping(vec![("value", YamlNode::integer(10))])),
+        };
+        assert_eq!(
+            show(parser, "#/components/examples/Limit")?.to_string(),
+            "value: 10"
+        );
+
+        let parser = MockParser {
+            nodes: vec![],
+            example: None,
+        };
+        let error = show(parser, "#/components/examples/Missing").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not find example `#/components/examples/Missing`"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,1752 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    bindings::{
+        docs::{DocsBody, DocsOperation, DocsParser, DocsType},
+        flatten::pascal_case,
+        generate::{ComponentSchema, GenerateParser, SchemaRef, COMPONENT_PREFIX},
+        security::{RequiredScheme, SecurityParser},
+        server::ServerParser,
+        template::words,
+        yaml::YamlNode,
+        OperationNode, OperationParser,
+    },
+    error::OpenapiSchemerError,
+    http::HttpRequest,
+    operation::{build_request, default_server},
+    output::write_file,
+};
+
+/// How the requests of `export httpfile` are spread over files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum HttpFileSplit {
+    /// One file per tag, named after the first tag of each operation
+    Tag,
+    /// One file per operation, named after its operationId
+    Operation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Requests for the operations of the spec in the `.http` format read by the
+/// HTTP clients of VS Code and JetBrains IDEs.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HttpFileResult {
+    files: Vec<HttpFile>,
+    split: Option<HttpFileSplit>,
+}
+
+impl HttpFileResult {
+    pub fn new(files: Vec<HttpFile>, split: Option<HttpFileSplit>) -> HttpFileResult {
+        HttpFileResult { files, split }
+    }
+
+    /// Writes the requests to the file `output`, or when they are split
+    /// into several files, into the directory `output`.
+    pub fn write(&self, output: &Path) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            let path = match self.split {
+                Some(_) => output.join(&file.path),
+                None => output.to_path_buf(),
+            };
+            write_file(&path, &file.content).map_err(OpenapiSchemerError::ExportHttpFile)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for HttpFileResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let files: Vec<&str> = self
+            .files
+            .iter()
+            .map(|file| file.content.trim_end())
+            .collect();
+        write!(f, "{}", files.join("\n\n"))
+    }
+}
+
+/// The requests of one file and the variables they use.
+struct HttpFileBuilder {
+    path: PathBuf,
+    variables: Vec<String>,
+    requests: Vec<String>,
+}
+
+impl HttpFileBuilder {
+    fn build(self, base_url: &str) -> HttpFile {
+        let mut lines = vec![format!("@baseUrl = {}", base_url)];
+        lines.extend(
+            self.variables
+                .iter()
+                .map(|variable| format!("@{} =", variable)),
+        );
+        let mut content = lines.join("\n");
+        for request in self.requests {
+            content.push_str("\n\n");
+            content.push_str(&request);
+        }
+        content.push('\n');
+        HttpFile {
+            path: self.path,
+            content,
+        }
+    }
+}
+
+/// Writes a request for every operation with an `operationId`. Required
+/// parameters become `{{name}}` variables, bodies are scaffolded from the
+/// request body's example or schema and the credentials of the first
+/// security requirement are sent as `{{scheme}}` variables declared at the
+/// top of each file. Requests go to `{{baseUrl}}`, which is `server` or the
+/// first top level server of the spec, unless the spec declares another
+/// server for the operation.
+pub fn httpfile<T: OperationParser, U: ServerParser, V: SecurityParser>(
+    parser: T,
+    server_parser: U,
+    security_parser: V,
+    split: Option<HttpFileSplit>,
+    server: Option<String>,
+) -> Result<HttpFileResult, OpenapiSchemerError> {
+    let servers = server_parser.get_server_nodes()?;
+    let security = security_parser.get_operation_security()?;
+    let base_url = server.to_owned().unwrap_or_else(|| {
+        servers
+            .iter()
+            .find(|server| server.scope.is_none())
+            .map(|server| server.text.to_owned())
+            .unwrap_or_else(|| "http://localhost".to_string())
+    });
+    let mut builders: Vec<HttpFileBuilder> = vec![];
+    for node in parser.get_operation_nodes()? {
+        let operation_id = match &node.operation_id {
+            Some(operation_id) => operation_id,
+            None => continue,
+        };
+        let definition = parser.get_operation(operation_id)?.ok_or_else(|| {
+            OpenapiSchemerError::ExportHttpFile(format!(
+                "Could not find operation `{}`",
+                operation_id
+            ))
+        })?;
+        let mut request = build_request(&definition, "", &[], None, true)
+            .map_err(|error| OpenapiSchemerError::ExportHttpFile(error.to_string()))?;
+        let schemes = security
+            .iter()
+            .find(|security| security.operation.operation_id == node.operation_id)
+            .and_then(|security| security.requirements.first());
+        let variables = authorize(&mut request, schemes.map(Vec::as_slice).unwrap_or_default());
+        let base = match server {
+            Some(_) => None,
+            None => default_server(&servers, &definition).filter(|server| *server != base_url),
+        };
+
+        let path = match split {
+            None => PathBuf::from("requests.http"),
+            Some(HttpFileSplit::Tag) => {
+                let tag = node.tags.first().map(String::as_str).unwrap_or("untagged");
+                PathBuf::from(format!("{}.http", file_name(tag)))
+            }
+            Some(HttpFileSplit::Operation) => {
+                PathBuf::from(format!("{}.http", file_name(operation_id)))
+            }
+        };
+        let index = match builders.iter().position(|builder| builder.path == path) {
+            Some(index) => index,
+            None => {
+                builders.push(HttpFileBuilder {
+                    path,
+                    variables: vec![],
+                    requests: vec![],
+                });
+                builders.len() - 1
+            }
+        };
+        let builder = &mut builders[index];
+        for variable in variables {
+            if !builder.variables.contains(&variable) {
+                builder.variables.push(variable);
+            }
+        }
+        builder
+            .requests
+            .push(request_text(&node, &request, base.as_deref()));
+    }
+    let files = builders
+        .into_iter()
+        .map(|builder| builder.build(&base_url))
+        .collect();
+    Ok(HttpFileResult::new(files, split))
+}
+
+/// Adds the credentials of `schemes` to `request` and returns the names of
+/// the variables they are read from.
+fn authorize(request: &mut HttpRequest, schemes: &[RequiredScheme]) -> Vec<String> {
+    let mut variables = vec![];
+    for required in schemes {
+        let scheme = match &required.scheme {
+            Some(scheme) => scheme,
+            None => continue,
+        };
+        let text = |key: &str| {
+            scheme
+                .get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let variable = variable_name(&required.name);
+        let value = format!("{{{{{}}}}}", variable);
+        match (text("type").as_str(), text("in").as_str()) {
+            ("apiKey", "header") => request.headers.push((text("name"), value)),
+            ("apiKey", "query") => {
+                let separator = match request.url.contains('?') {
+                    true => '&',
+                    false => '?',
+                };
+                request.url = format!("{}{}{}={}", request.url, separator, text("name"), value);
+            }
+            ("apiKey", "cookie") => {
+                let cookie = format!("{}={}", text("name"), value);
+                match request
+                    .headers
+                    .iter_mut()
+                    .find(|(name, _)| name == "Cookie")
+                {
+                    Some((_, cookies)) => *cookies = format!("{}; {}", cookies, cookie),
+                    None => request.headers.push(("Cookie".to_string(), cookie)),
+                }
+            }
+            ("http", _) => {
+                let kind = match text("scheme").to_lowercase().as_str() {
+                    "basic" => "Basic".to_string(),
+                    "bearer" | "" => "Bearer".to_string(),
+                    other => other.to_string(),
+                };
+                let authorization = format!("{} {}", kind, value);
+                request
+                    .headers
+                    .push(("Authorization".to_string(), authorization));
+            }
+            ("oauth2", _) | ("openIdConnect", _) => request
+                .headers
+                .push(("Authorization".to_string(), format!("Bearer {}", value))),
+            _ => continue,
+        }
+        variables.push(variable);
+    }
+    variables
+}
+
+/// `request` as an entry of a `.http` file, sent to `base` or otherwise to
+/// `{{baseUrl}}`.
+fn request_text(node: &OperationNode, request: &HttpRequest, base: Option<&str>) -> String {
+    let mut lines = vec![format!("### {}", node.text)];
+    if let Some(summary) = &node.summary {
+        lines.push(format!("# {}", summary));
+    }
+    lines.push(format!(
+        "{} {}{}",
+        request.method.to_uppercase(),
+        base.unwrap_or("{{baseUrl}}").trim_end_matches('/'),
+        variables(&request.url)
+    ));
+    for (name, value) in &request.headers {
+        lines.push(format!("{}: {}", name, variables(value)));
+    }
+    if let Some(body) = &request.body {
+        lines.push(String::new());
+        lines.push(body.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Turns the `{name}` placeholders of required parameters into the
+/// `{{name}}` variables of `.http` files. Text that already is a variable is
+/// kept as it is.
+fn variables(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        if after.starts_with('{') {
+            let end = after.find("}}").map_or(after.len(), |end| end + 2);
+            result.push_str(&rest[..start + 1 + end]);
+            rest = &after[end..];
+            continue;
+        }
+        match after.find('}') {
+            Some(end) if !after[..end].contains(['{', ' ']) => {
+                result.push_str(&rest[..start]);
+                result.push_str(&format!("{{{{{}}}}}", &after[..end]));
+                rest = &after[end + 1..];
+            }
+            _ => {
+                result.push_str(&rest[..start + 1]);
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// The name of a security scheme as a variable, with anything but letters,
+/// digits and `_` replaced by `_`.
+fn variable_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
+
+/// `name` safe to use as a file name.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(
+            |c| match c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect()
+}
+
+/// Protocol Buffers definitions for the component schemas of the spec,
+/// written as the contents of a proto3 `.proto` file.
+#[derive(Debug, PartialEq)]
+pub struct ProtoResult {
+    schemas: Vec<ComponentSchema>,
+    package: Option<String>,
+}
+
+impl ProtoResult {
+    pub fn new(schemas: Vec<ComponentSchema>, package: Option<String>) -> ProtoResult {
+        ProtoResult { schemas, package }
+    }
+}
+
+impl Display for ProtoResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = ProtoBuilder {
+            components: &self.schemas,
+            imports: vec![],
+            aliases: vec![],
+        };
+        let definitions: Vec<String> = self
+            .schemas
+            .iter()
+            .map(|component| builder.definition(component))
+            .collect();
+        let mut sections = vec![
+            "// Generated by openapi-schemer from the component schemas of the spec.\n\
+             syntax = \"proto3\";"
+                .to_string(),
+        ];
+        if let Some(package) = &self.package {
+            sections.push(format!("package {};", package));
+        }
+        builder.imports.sort();
+        builder.imports.dedup();
+        if !builder.imports.is_empty() {
+            let imports: Vec<String> = builder
+                .imports
+                .iter()
+                .map(|import| format!("import \"{}\";", import))
+                .collect();
+            sections.push(imports.join("\n"));
+        }
+        sections.extend(definitions);
+        write!(f, "{}", sections.join("\n\n"))
+    }
+}
+
+/// The type of a field, before it is written with `repeated` or as a map.
+enum ProtoType {
+    Single(String),
+    Repeated(String),
+    Map(String),
+}
+
+/// Renders component schemas as proto3 definitions, collecting the files of
+/// the well-known types they use.
+struct ProtoBuilder<'a> {
+    components: &'a [ComponentSchema],
+    imports: Vec<&'static str>,
+    /// The components being inlined by `component_type`, so a component
+    /// that leads back to itself is referred to by its message instead.
+    aliases: Vec<String>,
+}
+
+impl ProtoBuilder<'_> {
+    fn definition(&mut self, component: &ComponentSchema) -> String {
+        let lines = self.declaration(&message_name(&component.name), &component.schema);
+        format!(
+            "{}{}",
+            proto_comment(&component.schema, ""),
+            lines.join("\n")
+        )
+    }
+
+    /// An `enum` for a schema with an `enum`, a message with a `oneof` for
+    /// `oneOf` and `anyOf`, a message with a field per property for objects,
+    /// including those of `allOf`, and otherwise a message wrapping the
+    /// value in a single field.
+    fn declaration(&mut self, name: &str, schema: &YamlNode) -> Vec<String> {
+        if let Some(values) = enum_values(schema) {
+            return enum_lines(name, &values);
+        }
+        let mut nested = vec![];
+        let mut fields = vec![];
+        let members = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(key).and_then(YamlNode::as_sequence));
+        if let Some(members) = members {
+            let mut names: Vec<String> = vec![];
+            let mut choices = vec![];
+            for (index, member) in members.iter().enumerate() {
+                let nested_name = format!("Option{}", index + 1);
+                let kind = self.single_type(member, &nested_name, &mut nested);
+                let mut field = match member.reference() {
+                    Some(reference) => field_name(reference.rsplit('/').next().unwrap_or_default()),
+                    None if kind == nested_name => format!("option_{}", index + 1),
+                    None => format!(
+                        "{}_value",
+                        field_name(kind.rsplit('.').next().unwrap_or_default())
+                    ),
+                };
+                if names.contains(&field) {
+                    field = format!("{}_{}", field, index + 1);
+                }
+                choices.push(format!("    {} {} = {};", kind, field, index + 1));
+                names.push(field);
+            }
+            fields.push("  oneof value {".to_string());
+            fields.extend(choices);
+            fields.push("  }".to_string());
+        } else {
+            let mut properties = vec![];
+            let mut required = vec![];
+            collect_properties(
+                self.components,
+                schema,
+                &mut properties,
+                &mut required,
+                &mut vec![],
+            );
+            if properties.is_empty() {
+                let kind = self.value_type(schema, "Value", &mut nested);
+                let field = match kind {
+                    ProtoType::Single(_) => "value",
+                    _ => "values",
+                };
+                fields.push(format!("  {} = 1;", field_declaration(kind, field, true)));
+            }
+            for (index, (property, value)) in properties.iter().enumerate() {
+                let kind = self.value_type(value, &message_name(property), &mut nested);
+                let options = match value.get("deprecated").and_then(YamlNode::as_str) {
+                    Some("true") => " [deprecated = true]",
+                    _ => "",
+                };
+                fields.push(format!(
+                    "{}  {} = {}{};",
+                    proto_comment(value, "  "),
+                    field_declaration(kind, &field_name(property), required.contains(property)),
+                    index + 1,
+                    options
+                ));
+            }
+        }
+        let mut lines = vec![format!("message {} {{", name)];
+        for definition in nested {
+            lines.extend(definition.iter().map(|line| match line.is_empty() {
+                true => String::new(),
+                false => format!("  {}", line),
+            }));
+            lines.push(String::new());
+        }
+        lines.extend(
+            fields
+                .iter()
+                .flat_map(|field| field.lines().map(str::to_owned)),
+        );
+        lines.push("}".to_string());
+        lines
+    }
+
+    /// The type of `schema`. Inline enums and objects become definitions
+    /// named `nested_name` in `nested`.
+    fn value_type(
+        &mut self,
+        schema: &YamlNode,
+        nested_name: &str,
+        nested: &mut Vec<Vec<String>>,
+    ) -> ProtoType {
+        if let Some(reference) = SchemaRef::of(schema) {
+            return match reference {
+                SchemaRef::Component(name) => self.component_type(name),
+                SchemaRef::Recursive => ProtoType::Single(self.well_known("Value")),
+            };
+        }
+        let composed = ["allOf", "oneOf", "anyOf", "properties"]
+            .iter()
+            .any(|key| schema.get(key).is_some());
+        if enum_values(schema).is_some() || composed {
+            nested.push(self.declaration(nested_name, schema));
+            return ProtoType::Single(nested_name.to_string());
+        }
+        let format = schema.get("format").and_then(YamlNode::as_str);
+        let kind = match schema.get("type") {
+            Some(types) => types
+                .as_sequence()
+                .unwrap_or(std::slice::from_ref(types))
+                .iter()
+                .filter_map(YamlNode::as_str)
+                .find(|kind| *kind != "null"),
+            None if schema.get("additionalProperties").is_some() => Some("object"),
+            None => None,
+        };
+        let scalar = match (kind, format) {
+            (Some("string"), Some("byte" | "binary")) => "bytes",
+            (Some("string"), Some("date-time")) => return ProtoType::Single(self.timestamp()),
+            (Some("string"), _) => "string",
+            (Some("integer"), Some("int32")) => "int32",
+            (Some("integer"), _) => "int64",
+            (Some("number"), Some("float")) => "float",
+            (Some("number"), _) => "double",
+            (Some("boolean"), _) => "bool",
+            (Some("array"), _) => {
+                return match schema.get("items") {
+                    Some(items) => {
+                        ProtoType::Repeated(self.single_type(items, nested_name, nested))
+                    }
+                    None => ProtoType::Single(self.well_known("ListValue")),
+                }
+            }
+            (Some("object"), _) => {
+                return match schema.get("additionalProperties") {
+                    Some(values) if values.as_mapping().is_some_and(|pairs| !pairs.is_empty()) => {
+                        ProtoType::Map(self.single_type(values, nested_name, nested))
+                    }
+                    _ => ProtoType::Single(self.well_known("Struct")),
+                }
+            }
+            _ => return ProtoType::Single(self.well_known("Value")),
+        };
+        ProtoType::Single(scalar.to_string())
+    }
+
+    /// The type of `schema` where `repeated` and maps aren't allowed, such as
+    /// the items of an array or the members of a `oneof`.
+    fn single_type(
+        &mut self,
+        schema: &YamlNode,
+        nested_name: &str,
+        nested: &mut Vec<Vec<String>>,
+    ) -> String {
+        match self.value_type(schema, nested_name, nested) {
+            ProtoType::Single(kind) => kind,
+            ProtoType::Repeated(_) => self.well_known("ListValue"),
+            ProtoType::Map(_) => self.well_known("Struct"),
+        }
+    }
+
+    /// The type a `$ref` to the component `name` leads to. Components that
+    /// are a plain scalar or array are inlined, any other is its message.
+    fn component_type(&mut self, name: &str) -> ProtoType {
+        let message = ProtoType::Single(message_name(name));
+        let schema = match self.component(name) {
+            Some(component) => component.schema.to_owned(),
+            None => return message,
+        };
+        let inlined = schema.reference().is_none()
+            && enum_values(&schema).is_none()
+            && [
+                "allOf",
+                "oneOf",
+                "anyOf",
+                "properties",
+                "additionalProperties",
+            ]
+            .iter()
+            .all(|key| schema.get(key).is_none())
+            && schema
+                .get("type")
+                .and_then(YamlNode::as_str)
+                .is_some_and(|kind| kind != "object");
+        if !inlined || self.aliases.iter().any(|alias| alias == name) {
+            return message;
+        }
+        self.aliases.push(name.to_string());
+        let kind = self.value_type(&schema, &message_name(name), &mut vec![]);
+        self.aliases.pop();
+        kind
+    }
+
+    fn component(&self, name: &str) -> Option<&ComponentSchema> {
+        self.components
+            .iter()
+            .find(|component| component.name == name)
+    }
+
+    fn well_known(&mut self, name: &str) -> String {
+        self.imports.push("google/protobuf/struct.proto");
+        format!("google.protobuf.{}", name)
+    }
+
+    fn timestamp(&mut self) -> String {
+        self.imports.push("google/protobuf/timestamp.proto");
+        "google.protobuf.Timestamp".to_string()
+    }
+}
+
+/// The properties of `schema` and of the members of its `allOf`, with the
+/// names of those that are required. A property written again replaces
+/// the earlier one.
+fn collect_properties(
+    components: &[ComponentSchema],
+    schema: &YamlNode,
+    properties: &mut Vec<(String, YamlNode)>,
+    required: &mut Vec<String>,
+    visited: &mut Vec<String>,
+) {
+    if let Some(name) = schema
+        .reference()
+        .and_then(|reference| reference.strip_prefix(COMPONENT_PREFIX))
+    {
+        if visited.iter().any(|visited| visited == name) {
+            return;
+        }
+        visited.push(name.to_string());
+        if let Some(component) = components.iter().find(|component| component.name == name) {
+            collect_properties(components, &component.schema, properties, required, visited);
+        }
+        return;
+    }
+    for member in schema
+        .get("allOf")
+        .and_then(YamlNode::as_sequence)
+        .unwrap_or_default()
+    {
+        collect_properties(components, member, properties, required, visited);
+    }
+    for pair in schema
+        .get("properties")
+        .and_then(YamlNode::as_mapping)
+        .unwrap_or_default()
+    {
+        let name = pair.key.as_str().unwrap_or_default().to_string();
+        match properties
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some(property) => property.1 = pair.value.to_owned(),
+            None => properties.push((name, pair.value.to_owned())),
+        }
+    }
+    required.extend(
+        schema
+            .get("required")
+            .and_then(YamlNode::as_sequence)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| name.as_str().map(str::to_owned)),
+    );
+}
+
+/// The values of the `enum` of `schema`, leaving out `null`.
+fn enum_values(schema: &YamlNode) -> Option<Vec<String>> {
+    let values = schema.get("enum").and_then(YamlNode::as_sequence)?;
+    Some(
+        values
+            .iter()
+            .filter(|value| value.json_type() != "null")
+            .filter_map(|value| value.as_str().map(str::to_owned))
+            .collect(),
+    )
+}
+
+/// An `enum` whose values are prefixed with its name, starting with the
+/// `_UNSPECIFIED` value proto3 requires to be 0.
+fn enum_lines(name: &str, values: &[String]) -> Vec<String> {
+    let prefix = words(name).join("_").to_uppercase();
+    let mut constants = vec![format!("{}_UNSPECIFIED", prefix)];
+    for (index, value) in values.iter().enumerate() {
+        let mut constant = match words(value).join("_").to_uppercase() {
+            suffix if suffix.is_empty() => format!("{}_VALUE_{}", prefix, index + 1),
+            suffix => format!("{}_{}", prefix, suffix),
+        };
+        if constants.contains(&constant) {
+            constant = format!("{}_{}", constant, index + 1);
+        }
+        constants.push(constant);
+    }
+    let mut lines = vec![format!("enum {} {{", name)];
+    lines.extend(
+        constants
+            .iter()
+            .enumerate()
+            .map(|(number, constant)| format!("  {} = {};", constant, number)),
+    );
+    lines.push("}".to_string());
+    lines
+}
+
+/// A field declaration without its number. Fields that aren't required are
+/// `optional` unless they are `repeated` or maps, which can't be.
+fn field_declaration(kind: ProtoType, name: &str, required: bool) -> String {
+    match kind {
+        ProtoType::Single(kind) if required => format!("{} {}", kind, name),
+        ProtoType::Single(kind) => format!("optional {} {}", kind, name),
+        ProtoType::Repeated(kind) => format!("repeated {} {}", kind, name),
+        ProtoType::Map(kind) => format!("map<string, {}> {}", kind, name),
+    }
+}
+
+/// A component or property name as a message name, such as `PetStatus` for
+/// `pet-status`.
+fn message_name(name: &str) -> String {
+    let name = pascal_case(name);
+    match name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        true => format!("_{}", name),
+        false => name,
+    }
+}
+
+/// A property name as a field name, such as `pet_id` for `petId`.
+fn field_name(name: &str) -> String {
+    let name = words(name).join("_");
+    match name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        true => format!("field_{}", name),
+        false => name,
+    }
+}
+
+/// The `description` of `schema` as `//` comments ending in a newline.
+fn proto_comment(schema: &YamlNode, pad: &str) -> String {
+    schema
+        .get("description")
+        .and_then(YamlNode::as_str)
+        .unwrap_or_default()
+        .trim()
+        .lines()
+        .map(|line| format!("{}// {}\n", pad, line.trim_end()).replace("// \n", "//\n"))
+        .collect()
+}
+
+fn is_package(package: &str) -> bool {
+    package.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Maps the component schemas to proto3 messages and enums, best-effort
+/// for the parts of JSON Schema protobuf can't express: `oneOf` and `anyOf`
+/// become a `oneof`, `allOf` merges the properties of its members, objects
+/// with only `additionalProperties` become maps and anything untyped
+/// becomes a `google.protobuf.Value`.
+pub fn proto<T: GenerateParser>(
+    parser: T,
+    package: Option<String>,
+) -> Result<ProtoResult, OpenapiSchemerError> {
+    if let Some(package) = package.as_deref().filter(|package| !is_package(package)) {
+        return Err(OpenapiSchemerError::ExportProto(format!(
+            "`{}` is not a valid package name, expected something like `petstore.v1`",
+            package
+        )));
+    }
+    Ok(ProtoResult::new(parser.get_component_schemas()?, package))
+}
+
+/// GraphQL type definitions for the component schemas of the spec, and
+/// optionally `Query` and `Mutation` fields for its operations, written as
+/// SDL.
+#[derive(Debug)]
+pub struct GraphqlResult {
+    schemas: Vec<ComponentSchema>,
+    operations: Vec<DocsOperation>,
+}
+
+impl GraphqlResult {
+    pub fn new(schemas: Vec<ComponentSchema>, operations: Vec<DocsOperation>) -> GraphqlResult {
+        GraphqlResult {
+            schemas,
+            operations,
+        }
+    }
+}
+
+impl Display for GraphqlResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = GraphqlBuilder {
+            components: &self.schemas,
+            definitions: vec![],
+            inputs: vec![],
+            json: false,
+            aliases: vec![],
+        };
+        for component in &self.schemas {
+            builder.definition(component);
+        }
+        let mut roots = vec![];
+        for (root, method) in [("Query", "get"), ("Mutation", "post")] {
+            let fields: Vec<String> = self
+                .operations
+                .iter()
+                .filter(|operation| operation.operation.method.eq_ignore_ascii_case(method))
+                .filter_map(|operation| builder.operation_field(operation))
+                .collect();
+            if !fields.is_empty() {
+                roots.push(format!("type {} {{\n{}\n}}", root, fields.join("\n")));
+            }
+        }
+        // Input types are only known once the operations are written, and
+        // can refer to further input types in turn.
+        let mut written = 0;
+        while written < builder.inputs.len() {
+            let name = builder.inputs[written].to_owned();
+            if let Some(component) = builder.component(&name) {
+                let schema = component.schema.to_owned();
+                let index = builder.definitions.len();
+                let lines = builder.object(&format!("{}Input", name), &schema, true);
+                builder.definitions.insert(
+                    index,
+                    format!("{}{}", graphql_description(&schema, ""), lines),
+                );
+            }
+            written += 1;
+        }
+        let mut sections = vec![
+            "# Generated by openapi-schemer from the component schemas of the spec.".to_string(),
+        ];
+        if builder.json {
+            sections.push(
+                "\"Any JSON value, for schemas GraphQL can't express.\"\nscalar JSON".to_string(),
+            );
+        }
+        sections.extend(builder.definitions);
+        sections.extend(roots);
+        write!(f, "{}", sections.join("\n\n"))
+    }
+}
+
+/// Renders component schemas as GraphQL types, collecting the inline types
+/// and the input types they lead to.
+struct GraphqlBuilder<'a> {
+    components: &'a [ComponentSchema],
+    definitions: Vec<String>,
+    /// The components that need an input type, in the order they were found.
+    inputs: Vec<String>,
+    /// Whether the `JSON` scalar is used.
+    json: bool,
+    /// The components being inlined by `component_type`.
+    aliases: Vec<String>,
+}
+
+impl GraphqlBuilder<'_> {
+    /// Adds an `enum`, a `union` or a `type` for `component`. Components that
+    /// are a scalar, an array or anything else GraphQL has no type for are
+    /// inlined where they are used instead.
+    fn definition(&mut self, component: &ComponentSchema) {
+        let name = message_name(&component.name);
+        let schema = &component.schema;
+        let description = graphql_description(schema, "");
+        let index = self.definitions.len();
+        let definition = if let Some(values) = enum_values(schema) {
+            enum_definition(&name, &values)
+        } else if let Some(members) = self.union_members(schema) {
+            format!("union {} = {}", name, members.join(" | "))
+        } else if is_graphql_object(schema) {
+            self.object(&name, schema, false)
+        } else {
+            return;
+        };
+        self.definitions
+            .insert(index, format!("{}{}", description, definition));
+    }
+
+    /// The members of a `oneOf` or `anyOf` made only of refs to object
+    /// components, which GraphQL can write as a union.
+    fn union_members(&self, schema: &YamlNode) -> Option<Vec<String>> {
+        let members = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(key).and_then(YamlNode::as_sequence))?;
+        members
+            .iter()
+            .map(|member| {
+                let name = member.reference()?.strip_prefix(COMPONENT_PREFIX)?;
+                let component = self.component(name)?;
+                match is_graphql_object(&component.schema) {
+                    true => Some(message_name(name)),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    /// A `type`, or an `input` when `input` is set, with a field for every
+    /// property of `schema`, including those of `allOf`.
+    fn object(&mut self, name: &str, schema: &YamlNode, input: bool) -> String {
+        let mut properties = vec![];
+        let mut required = vec![];
+        collect_properties(
+            self.components,
+            schema,
+            &mut properties,
+            &mut required,
+            &mut vec![],
+        );
+        let mut fields = vec![];
+        for (property, value) in &properties {
+            let kind = self.field_type(
+                value,
+                &format!(
+                    "{}{}",
+                    name.trim_end_matches("Input"),
+                    message_name(property)
+                ),
+                input,
+                required.contains(property),
+            );
+            let deprecated = match value.get("deprecated").and_then(YamlNode::as_str) {
+                Some("true") if !input => " @deprecated",
+                _ => "",
+            };
+            fields.push(format!(
+                "{}  {}: {}{}",
+                graphql_description(value, "  "),
+                graphql_field_name(property),
+                kind,
+                deprecated
+            ));
+        }
+        if fields.is_empty() {
+            // GraphQL types need at least one field
+            self.json = true;
+            fields.push("  value: JSON".to_string());
+        }
+        let keyword = match input {
+            true => "input",
+            false => "type",
+        };
+        format!("{} {} {{\n{}\n}}", keyword, name, fields.join("\n"))
+    }
+
+    /// The type of a field, non-null when it is `required` and not
+    /// `nullable`.
+    fn field_type(
+        &mut self,
+        schema: &YamlNode,
+        nested_name: &str,
+        input: bool,
+        required: bool,
+    ) -> String {
+        let kind = self.named_type(schema, nested_name, input);
+        match required && !is_graphql_nullable(schema) {
+            true => format!("{}!", kind),
+            false => kind,
+        }
+    }
+
+    /// The type of `schema` without its nullability. Inline enums and
+    /// objects become types named `nested_name`.
+    fn named_type(&mut self, schema: &YamlNode, nested_name: &str, input: bool) -> String {
+        if let Some(reference) = SchemaRef::of(schema) {
+            return match reference {
+                SchemaRef::Component(name) => self.component_type(name, input),
+                SchemaRef::Recursive => self.json(),
+            };
+        }
+        if let Some(values) = enum_values(schema) {
+            self.nested(enum_definition(nested_name, &values));
+            return nested_name.to_string();
+        }
+        if is_graphql_object(schema) {
+            let name = match input {
+                true => format!("{}Input", nested_name),
+                false => nested_name.to_string(),
+            };
+            let index = self.definitions.len();
+            let definition = self.object(&name, schema, input);
+            self.definitions.insert(index, definition);
+            return name;
+        }
+        let kind = match schema.get("type") {
+            Some(types) => types
+                .as_sequence()
+                .unwrap_or(std::slice::from_ref(types))
+                .iter()
+                .filter_map(YamlNode::as_str)
+                .find(|kind| *kind != "null"),
+            None => None,
+        };
+        match kind {
+            Some("string") => "String".to_string(),
+            Some("integer") => "Int".to_string(),
+            Some("number") => "Float".to_string(),
+            Some("boolean") => "Boolean".to_string(),
+            Some("array") => match schema.get("items") {
+                Some(items) => format!("[{}]", self.field_type(items, nested_name, input, true)),
+                None => format!("[{}]", self.json()),
+            },
+            _ => self.json(),
+        }
+    }
+
+    /// The type a `$ref` to the component `name` leads to, noting the input
+    /// types that have to be written.
+    fn component_type(&mut self, name: &str, input: bool) -> String {
+        let schema = match self.component(name) {
+            Some(component) => component.schema.to_owned(),
+            None => return self.json(),
+        };
+        if enum_values(&schema).is_some() {
+            return message_name(name);
+        }
+        if is_graphql_object(&schema) {
+            if !input {
+                return message_name(name);
+            }
+            if !self.inputs.iter().any(|input| input == name) {
+                self.inputs.push(name.to_string());
+            }
+            return format!("{}Input", message_name(name));
+        }
+        if self.union_members(&schema).is_some() && !input {
+            return message_name(name);
+        }
+        if self.aliases.iter().any(|alias| alias == name) {
+            return self.json();
+        }
+        self.aliases.push(name.to_string());
+        let kind = self.named_type(&schema, &message_name(name), input);
+        self.aliases.pop();
+        kind
+    }
+
+    /// A field of `Query` or `Mutation` for an operation with an
+    /// `operationId`, taking its path and query parameters and, as `input`,
+    /// its request body, and returning the body of its first successful
+    /// response.
+    fn operation_field(&mut self, operation: &DocsOperation) -> Option<String> {
+        let operation_id = operation.operation.operation_id.as_deref()?;
+        let mut arguments = vec![];
+        for parameter in &operation.parameters {
+            if !matches!(parameter.location.as_str(), "path" | "query") {
+                continue;
+            }
+            let schema = docs_schema(parameter.schema.as_ref());
+            let nested_name = format!(
+                "{}{}",
+                message_name(operation_id),
+                message_name(&parameter.name)
+            );
+            let kind = self.field_type(&schema, &nested_name, true, parameter.required);
+            arguments.push(format!("{}: {}", graphql_field_name(&parameter.name), kind));
+        }
+        let body_schema = |body: &DocsBody| {
+            body.media_types
+                .iter()
+                .find_map(|media| media.schema.as_ref())
+                .map(|kind| docs_schema(Some(kind)))
+        };
+        if let Some(schema) = operation.request_body.as_ref().and_then(body_schema) {
+            let nested_name = format!("{}Body", message_name(operation_id));
+            let kind = self.field_type(&schema, &nested_name, true, true);
+            arguments.push(format!("input: {}", kind));
+        }
+        let response = operation
+            .responses
+            .iter()
+            .find(|response| response.name.starts_with('2'));
+        let returns = match response.and_then(body_schema) {
+            Some(schema) => {
+                let nested_name = format!("{}Response", message_name(operation_id));
+                self.named_type(&schema, &nested_name, false)
+            }
+            None => "Boolean".to_string(),
+        };
+        let arguments = match arguments.is_empty() {
+            true => String::new(),
+            false => format!("({})", arguments.join(", ")),
+        };
+        let description = operation
+            .operation
+            .summary
+            .as_ref()
+            .or(operation.description.as_ref())
+            .map(|text| description_lines(text, "  "))
+            .unwrap_or_default();
+        let deprecated = match operation.operation.deprecated {
+            true => " @deprecated",
+            false => "",
+        };
+        Some(format!(
+            "{}  {}{}: {}{}",
+            description,
+            graphql_field_name(operation_id),
+            arguments,
+            returns,
+            deprecated
+        ))
+    }
+
+    /// Adds an inline enum, unless the type or input type of another schema
+    /// has already added the same one.
+    fn nested(&mut self, definition: String) {
+        if !self.definitions.contains(&definition) {
+            self.definitions.push(definition);
+        }
+    }
+
+    fn json(&mut self) -> String {
+        self.json = true;
+        "JSON".to_string()
+    }
+
+    fn component(&self, name: &str) -> Option<&ComponentSchema> {
+        self.components
+            .iter()
+            .find(|component| component.name == name)
+    }
+}
+
+/// The schema a `DocsType` describes, as a `$ref` to its component or a
+/// plain `type`.
+fn docs_schema(kind: Option<&DocsType>) -> YamlNode {
+    let kind = match kind {
+        Some(kind) => kind,
+        None => return YamlNode::mapping(vec![]),
+    };
+    let schema = match kind.component {
+        true => YamlNode::mapping(vec![(
+            "$ref",
+            YamlNode::string(&format!("{}{}", COMPONENT_PREFIX, kind.name)),
+        )]),
+        false => YamlNode::mapping(vec![("type", YamlNode::string(&kind.name))]),
+    };
+    match kind.array {
+        true => YamlNode::mapping(vec![("type", YamlNode::string("array")), ("items", schema)]),
+        false => schema,
+    }
+}
+
+/// Whether `schema` has properties, written directly or through `allOf`.
+fn is_graphql_object(schema: &YamlNode) -> bool {
+    schema.get("properties").is_some() || schema.get("allOf").is_some()
+}
+
+fn is_graphql_nullable(schema: &YamlNode) -> bool {
+    schema.get("nullable").and_then(YamlNode::as_str) == Some("true")
+        || schema
+            .get("type")
+            .and_then(YamlNode::as_sequence)
+            .is_some_and(|types| types.iter().any(|kind| kind.as_str() == Some("null")))
+}
+
+fn enum_definition(name: &str, values: &[String]) -> String {
+    let mut constants: Vec<String> = vec![];
+    for (index, value) in values.iter().enumerate() {
+        let mut constant = words(value).join("_").to_uppercase();
+        if constant.is_empty() || constant.starts_with(|c: char| c.is_ascii_digit()) {
+            constant = format!("_{}", constant);
+        }
+        if constants.contains(&constant) {
+            constant = format!("{}_{}", constant, index + 1);
+        }
+        constants.push(constant);
+    }
+    let lines: Vec<String> = constants
+        .iter()
+        .map(|constant| format!("  {}", constant))
+        .collect();
+    format!("enum {} {{\n{}\n}}", name, lines.join("\n"))
+}
+
+/// A property or operationId as a field name, such as `petId` for `pet_id`.
+fn graphql_field_name(name: &str) -> String {
+    let words = words(name);
+    let mut field = words.first().cloned().unwrap_or_default();
+    field.extend(words.iter().skip(1).map(|word| pascal_case(word)));
+    match field.starts_with(|c: char| c.is_ascii_digit()) || field.is_empty() {
+        true => format!("_{}", field),
+        false => field,
+    }
+}
+
+/// The `description` of `schema` as a GraphQL description ending in a
+/// newline, or nothing when it has none.
+fn graphql_description(schema: &YamlNode, pad: &str) -> String {
+    schema
+        .get("description")
+        .and_then(YamlNode::as_str)
+        .map(|description| description_lines(description, pad))
+        .unwrap_or_default()
+}
+
+fn description_lines(text: &str, pad: &str) -> String {
+    let lines: Vec<&str> = text.trim().lines().map(str::trim_end).collect();
+    match lines.len() {
+        0 => String::new(),
+        1 => format!(
+            "{}\"{}\"\n",
+            pad,
+            lines[0].replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+        _ => {
+            let body: Vec<String> = lines
+                .iter()
+                .map(|line| {
+                    format!("{}{}", pad, line.replace("\"\"\"", "\\\"\"\""))
+                        .trim_end()
+                        .to_string()
+                })
+                .collect();
+            format!("{}\"\"\"\n{}\n{}\"\"\"\n", pad, body.join("\n"), pad)
+        }
+    }
+}
+
+/// Maps the component schemas to GraphQL types: objects become `type`s,
+/// enums `enum`s and a `oneOf` of object components a `union`. Scalars and
+/// arrays are inlined and anything else becomes the `JSON` scalar. With
+/// `operations`, GET operations become `Query` fields and POST operations
+/// `Mutation` fields, using input types mirroring the components they take.
+pub fn graphql<T: GenerateParser, U: DocsParser>(
+    parser: T,
+    docs_parser: U,
+    operations: bool,
+) -> Result<GraphqlResult, OpenapiSchemerError> {
+    let operations = match operations {
+        true => docs_parser.get_docs_page()?.operations,
+        false => vec![],
+    };
+    Ok(GraphqlResult::new(
+        parser.get_component_schemas()?,
+        operations,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::{
+        docs::{DocsMediaType, DocsPage, DocsParameter},
+        security::{OperationSecurity, RequiredScheme, ScopeNode, SecuritySchemeNode},
+        server::ServerNode,
+        yaml::parse_yaml,
+        OperationDefinition, OperationNode,
+    };
+
+    use super::*;
+
+    struct MockParser {
+        definitions: Vec<OperationDefinition>,
+    }
+    impl MockParser {
+        fn node(definition: &OperationDefinition) -> OperationNode {
+            let text = |key: &str| {
+                definition
+                    .operation
+                    .get(key)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_owned)
+            };
+            OperationNode {
+                text: text("operationId").unwrap_or_default(),
+                operation_id: text("operationId"),
+                method: definition.method.to_owned(),
+                path: definition.path.to_owned(),
+                summary: text("summary"),
+                tags: vec![text("x-tag").unwrap_or_default()],
+                deprecated: false,
+                file: PathBuf::from("#"),
+                line: 1,
+                column: 1,
+            }
+        }
+    }
+    impl OperationParser for MockParser {
+        fn get_operation_nodes(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+            Ok(self.definitions.iter().map(MockParser::node).collect())
+        }
+
+        fn get_operation(
+            &self,
+            operation_id: &str,
+        ) -> Result<Option<OperationDefinition>, OpenapiSchemerError> {
+            Ok(self
+                .definitions
+                .iter()
+                .find(|definition| {
+                    MockParser::node(definition).operation_id.as_deref() == Some(operation_id)
+                })
+                .cloned())
+        }
+    }
+    impl ServerParser for MockParser {
+        fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError> {
+            Ok(vec![ServerNode {
+                text: "https://petstore.example.com/v1".to_string(),
+                description: None,
+                scope: None,
+                file: PathBuf::from("#"),
+                line: 1,
+                column: 1,
+            }])
+        }
+    }
+    impl SecurityParser for MockParser {
+        fn get_security_scheme_nodes(
+            &self,
+        ) -> Result<Vec<SecuritySchemeNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+
+        fn get_unsecured_operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+
+        fn get_operation_security(&self) -> Result<Vec<OperationSecurity>, OpenapiSchemerError> {
+            let scheme = parse_yaml("type: apiKey\nin: header\nname: X-API-Key").unwrap();
+            Ok(vec![OperationSecurity {
+                operation: MockParser::node(&self.definitions[1]),
+                requirements: vec![vec![RequiredScheme {
+                    name: "api-key".to_string(),
+                    scheme: Some(scheme),
+                    scopes: vec![],
+                }]],
+                inherited: false,
+            }])
+        }
+
+        fn get_declared_scopes(&self) -> Result<Vec<ScopeNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+    }
+
+    fn parser() -> Result<MockParser, Box<dyn Error>> {
+        let definition = |method: &str, path: &str, operation: &str| -> Result<_, Box<dyn Error>> {
+            Ok(OperationDefinition {
+                method: method.to_string(),
+                path: path.to_string(),
+                operation: parse_yaml(operation)?,
+            })
+        };
+        Ok(MockParser {
+            definitions: vec![
+                definition(
+                    "get",
+                    "/pets/{petId}",
+                    r#"
+operationId: showPetById
+summary: Info for a specific pet
+x-tag: pets
+parameters:
+  - name: petId
+    in: path
+    required: true
+"#,
+                )?,
+                definition(
+                    "post",
+                    "/pets",
+                    r#"
+operationId: createPets
+x-tag: pets
+requestBody:
+  content:
+    application/json:
+      example:
+        name: Rex
+"#,
+                )?,
+                definition("get", "/health", "operationId: health\nx-tag: ops")?,
+                definition("get", "/nameless", "summary: Skipped")?,
+            ],
+        })
+    }
+
+    #[test]
+    fn test_httpfile() -> Result<(), Box<dyn Error>> {
+        let result = httpfile(parser()?, parser()?, parser()?, None, None)?;
+        assert_eq!(
+            result.to_string(),
+            r#"@baseUrl = https://petstore.example.com/v1
+@api_key =
+
+### showPetById
+# Info for a specific pet
+GET {{baseUrl}}/pets/{{petId}}
+
+### createPets
+POST {{baseUrl}}/pets
+Content-Type: application/json
+X-API-Key: {{api_key}}
+
+{
+  "name": "Rex"
+}
+
+### health
+GET {{baseUrl}}/health"#
+        );
+
+        let result = httpfile(
+            parser()?,
+            parser()?,
+            parser()?,
+            Some(HttpFileSplit::Tag),
+            Some("http://localhost:8080".to_string()),
+        )?;
+        let paths: Vec<&PathBuf> = result.files.iter().map(|file| &file.path).collect();
+        assert_eq!(
+            paths,
+            vec![&PathBuf::from("pets.http"), &PathBuf::from("ops.http")]
+        );
+        assert_eq!(
+            result.files[1].content,
+            "@baseUrl = http://localhost:8080\n\n### health\nGET {{baseUrl}}/health\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_variables() {
+        assert_eq!(variables("/pets/{petId}?a={b}"), "/pets/{{petId}}?a={{b}}");
+        assert_eq!(variables("{{baseUrl}}/{id}"), "{{baseUrl}}/{{id}}");
+        assert_eq!(variables("{not a placeholder}"), "{not a placeholder}");
+    }
+
+    struct MockGenerateParser {
+        schemas: Vec<ComponentSchema>,
+    }
+    impl GenerateParser for MockGenerateParser {
+        fn get_component_schemas(&self) -> Result<Vec<ComponentSchema>, OpenapiSchemerError> {
+            Ok(self.schemas.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_proto() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let parser = MockGenerateParser {
+            schemas: vec![
+                schema(
+                    "Pet",
+                    r##"
+description: A pet in the store
+type: object
+required: [id, name]
+properties:
+  id:
+    type: integer
+    format: int32
+  name:
+    type: string
+  status:
+    $ref: "#/components/schemas/pet-status"
+  bornAt:
+    type: string
+    format: date-time
+    deprecated: true
+  labels:
+    $ref: "#/components/schemas/Labels"
+  owner:
+    type: object
+    properties:
+      name:
+        type: string
+"##,
+                )?,
+                schema("pet-status", "type: string\nenum: [available, sold]")?,
+                schema("Labels", "type: array\nitems:\n  type: string")?,
+                schema(
+                    "Animal",
+                    r##"
+oneOf:
+  - $ref: "#/components/schemas/Pet"
+  - type: object
+    additionalProperties:
+      type: number
+  - type: string
+"##,
+                )?,
+                schema(
+                    "Dog",
+                    r##"
+allOf:
+  - $ref: "#/components/schemas/Pet"
+  - type: object
+    required: [bark]
+    properties:
+      bark:
+        type: [boolean, "null"]
+      extra: {}
+"##,
+                )?,
+            ],
+        };
+        assert_eq!(
+            proto(parser, Some("petstore.v1".to_string()))?.to_string(),
+            r#"// Generated by openapi-schemer from the component schemas of the spec.
+syntax = "proto3";
+
+package petstore.v1;
+
+import "google/protobuf/struct.proto";
+import "google/protobuf/timestamp.proto";
+
+// A pet in the store
+message Pet {
+  message Owner {
+    optional string name = 1;
+  }
+
+  int32 id = 1;
+  string name = 2;
+  optional PetStatus status = 3;
+  optional google.protobuf.Timestamp born_at = 4 [deprecated = true];
+  repeated string labels = 5;
+  optional Owner owner = 6;
+}
+
+enum PetStatus {
+  PET_STATUS_UNSPECIFIED = 0;
+  PET_STATUS_AVAILABLE = 1;
+  PET_STATUS_SOLD = 2;
+}
+
+message Labels {
+  repeated string values = 1;
+}
+
+message Animal {
+  oneof value {
+    Pet pet = 1;
+    google.protobuf.Struct struct_value = 2;
+    string string_value = 3;
+  }
+}
+
+message Dog {
+  message Owner {
+    optional string name = 1;
+  }
+
+  int32 id = 1;
+  string name = 2;
+  optional PetStatus status = 3;
+  optional google.protobuf.Timestamp born_at = 4 [deprecated = true];
+  repeated string labels = 5;
+  optional Owner owner = 6;
+  bool bark = 7;
+  optional google.protobuf.Value extra = 8;
+}"#
+        );
+        let parser = MockGenerateParser { schemas: vec![] };
+        assert_eq!(
+            proto(parser, Some("pet-store".to_string()))
+                .unwrap_err()
+                .to_string(),
+            "`pet-store` is not a valid package name, expected something like `petstore.v1`"
+        );
+        Ok(())
+    }
+
+    struct MockDocsParser {
+        page: DocsPage,
+    }
+    impl DocsParser for MockDocsParser {
+        fn get_docs_page(&self) -> Result<DocsPage, OpenapiSchemerError> {
+            Ok(self.page.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_graphql() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let schemas = vec![
+            schema(
+                "Pet",
+                r##"
+type: object
+required: [name]
+properties:
+  name:
+    type: string
+    description: The "name"
+  status:
+    type: string
+    enum: [available, sold]
+  owner:
+    $ref: "#/components/schemas/Owner"
+  tags:
+    $ref: "#/components/schemas/Tags"
+"##,
+            )?,
+            schema(
+                "Owner",
+                "type: object\nproperties:\n  email_address:\n    type: string",
+            )?,
+            schema("Tags", "type: array\nitems:\n  type: string")?,
+            schema(
+                "Animal",
+                r##"
+oneOf:
+  - $ref: "#/components/schemas/Pet"
+  - $ref: "#/components/schemas/Owner"
+"##,
+            )?,
+        ];
+        let component = |name: &str| DocsType {
+            array: false,
+            name: name.to_string(),
+            component: true,
+        };
+        let body = |name: &str, kind: DocsType| DocsBody {
+            name: name.to_string(),
+            description: None,
+            media_types: vec![DocsMediaType {
+                name: "application/json".to_string(),
+                schema: Some(kind),
+                example: None,
+            }],
+        };
+        let operation = |operation_id: &str, method: &str, path: &str| DocsOperation {
+            operation: OperationNode {
+                text: operation_id.to_string(),
+                operation_id: Some(operation_id.to_string()),
+                method: method.to_string(),
+                path: path.to_string(),
+                summary: None,
+                tags: vec![],
+                deprecated: false,
+                file: PathBuf::from("#"),
+                line: 1,
+                column: 1,
+            },
+            description: None,
+            parameters: vec![],
+            request_body: None,
+            responses: vec![],
+        };
+        let mut get = operation("getPet", "get", "/pets/{id}");
+        get.operation.summary = Some("Info for a pet".to_string());
+        get.operation.deprecated = true;
+        for (name, location) in [("id", "path"), ("X-Trace", "header")] {
+            get.parameters.push(DocsParameter {
+                name: name.to_string(),
+                location: location.to_string(),
+                required: true,
+                description: None,
+                schema: Some(DocsType {
+                    array: false,
+                    name: "integer".to_string(),
+                    component: false,
+                }),
+            });
+        }
+        get.responses.push(body("200", component("Animal")));
+        let mut create = operation("create_pet", "post", "/pets");
+        create.request_body = Some(body("request", component("Pet")));
+        create.responses.push(body("201", component("Pet")));
+        let page = DocsPage {
+            title: "Petstore".to_string(),
+            version: None,
+            description: None,
+            operations: vec![get, create, operation("deletePet", "delete", "/pets/{id}")],
+            schemas: vec![],
+            examples: vec![],
+        };
+        let parser = MockGenerateParser {
+            schemas: schemas.to_owned(),
+        };
+        let docs_parser = MockDocsParser {
+            page: page.to_owned(),
+        };
+        assert_eq!(
+            graphql(parser, docs_parser, true)?.to_string(),
+            r#"# Generated by openapi-schemer from the component schemas of the spec.
+
+type Pet {
+  "The \"name\""
+  name: String!
+  status: PetStatus
+  owner: Owner
+  tags: [String!]
+}
+
+enum PetStatus {
+  AVAILABLE
+  SOLD
+}
+
+type Owner {
+  emailAddress: String
+}
+
+union Animal = Pet | Owner
+
+input PetInput {
+  "The \"name\""
+  name: String!
+  status: PetStatus
+  owner: OwnerInput
+  tags: [String!]
+}
+
+input OwnerInput {
+  emailAddress: String
+}
+
+type Query {
+  "Info for a pet"
+  getPet(id: Int!): Animal @deprecated
+}
+
+type Mutation {
+  createPet(input: PetInput!): Pet
+}"#
+        );
+        let parser = MockGenerateParser {
+            schemas: vec![schema("Anything", "type: object\nproperties: {}")?],
+        };
+        assert_eq!(
+            graphql(parser, MockDocsParser { page }, false)?.to_string(),
+            r#"# Generated by openapi-schemer from the component schemas of the spec.
+
+"Any JSON value, for schemas GraphQL can't express."
+scalar JSON
+
+type Anything {
+  value: JSON
+}"#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        extension::{ExtensionNode, ExtensionParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+/// The extensions of the spec grouped by their key, in the order each key
+/// first appears.
+#[derive(Debug, PartialEq)]
+pub struct ListResult {
+    groups: Vec<(String, Vec<ExtensionNode>)>,
+}
+
+impl ListResult {
+    pub fn new(groups: Vec<(String, Vec<ExtensionNode>)>) -> ListResult {
+        ListResult { groups }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.groups.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+}
+
+/// A value as written, with values that aren't scalars on one line.
+fn value_text(value: &YamlNode) -> String {
+    match value.as_str() {
+        Some(text) => text.to_string(),
+        None => value
+            .to_json()
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(" "),
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for (key, extensions) in &self.groups {
+            lines.push(format!("{} ({})", key, extensions.len()));
+            for extension in extensions {
+                let entry = ListEntry {
+                    name: format!(
+                        "{}: {}",
+                        match extension.pointer.is_empty() {
+                            true => "/",
+                            false => &extension.pointer,
+                        },
+                        value_text(&extension.value)
+                    ),
+                    file: extension.file.to_owned(),
+                    line: extension.line,
+                    column: extension.column,
+                };
+                lines.push(format!("  {}", entry.with_location()));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let groups = self
+            .groups
+            .iter()
+            .map(|(key, extensions)| {
+                let extensions = extensions
+                    .iter()
+                    .map(|extension| {
+                        YamlNode::mapping(vec![
+                            ("pointer", YamlNode::string(&extension.pointer)),
+                            ("value", extension.value.to_owned()),
+                            (
+                                "file",
+                                YamlNode::string(&extension.file.display().to_string()),
+                            ),
+                            ("line", YamlNode::integer(extension.line)),
+                            ("column", YamlNode::integer(extension.column)),
+                        ])
+                    })
+                    .collect();
+                YamlNode::mapping(vec![
+                    ("key", YamlNode::string(key)),
+                    ("extensions", YamlNode::sequence(extensions)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(groups)
+    }
+}
+
+/// Lists the extensions of the spec, or only those called `key`.
+pub fn list<T: ExtensionParser>(
+    parser: T,
+    key: Option<&str>,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let mut groups: Vec<(String, Vec<ExtensionNode>)> = vec![];
+    for extension in parser.get_extension_nodes()? {
+        if key.is_some_and(|key| key != extension.key) {
+            continue;
+        }
+        match groups.iter_mut().find(|(key, _)| *key == extension.key) {
+            Some((_, extensions)) => extensions.push(extension),
+            None => groups.push((extension.key.to_owned(), vec![extension])),
+        }
+    }
+    Ok(ListResult::new(groups))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        extensions: Vec<ExtensionNode>,
+    }
+    impl ExtensionParser for MockParser {
+        fn get_extension_nodes(&self) -> Result<Vec<ExtensionNode>, OpenapiSchemerError> {
+            Ok(self.extensions.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let extension = |key: &str, pointer: &str, value: YamlNode, line| ExtensionNode {
+            key: key.to_string(),
+            value,
+            pointer: pointer.to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 7,
+        };
+        let parser = || MockParser {
+            extensions: vec![
+                extension(
+                    "x-internal",
+                    "/paths/~1admin/get",
+                    YamlNode::boolean(true),
+                    12,
+                ),
+                extension(
+                    "x-rate-limit",
+                    "/paths/~1pets/get",
+                    YamlNode::mapping(vec![("limit", YamlNode::integer(100))]),
+                    20,
+                ),
+                extension("x-internal", "", YamlNode::boolean(false), 2),
+            ],
+        };
+        assert_eq!(
+            list(parser(), None)?.to_string(),
+            "x-internal (2)\n  \
+             /spec/openapi.yaml:12:7: /paths/~1admin/get: true\n  \
+             /spec/openapi.yaml:2:7: /: false\n\
+             x-rate-limit (1)\n  \
+             /spec/openapi.yaml:20:7: /paths/~1pets/get: { \"limit\": 100 }"
+        );
+        assert_eq!(list(parser(), Some("x-rate-limit"))?.groups[0].1.len(), 1);
+        assert_eq!(list(parser(), Some("x-missing"))?.to_string(), "");
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{filter::Filterer, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct FilterResult {
+    document: YamlNode,
+}
+
+impl FilterResult {
+    pub fn new(document: YamlNode) -> FilterResult {
+        FilterResult { document }
+    }
+}
+
+impl Display for FilterResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for FilterResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+pub fn filter<T: Filterer>(filterer: T) -> Result<FilterResult, OpenapiSchemerError> {
+    let document = filterer.filter()?;
+    Ok(FilterResult::new(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    struct MockFilterer {
+        document: YamlNode,
+    }
+    impl Filterer for MockFilterer {
+        fn filter(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_filter() -> Result<(), Box<dyn Error>> {
+        let document = YamlNode::mapping(vec![("paths", YamlNode::mapping(vec![]))]);
+        let result = filter(MockFilterer { document })?;
+        assert_eq!(result.to_string(), "paths: {}");
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{flatten::Flattener, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct FlattenResult {
+    document: YamlNode,
+}
+
+impl FlattenResult {
+    pub fn new(document: YamlNode) -> FlattenResult {
+        FlattenResult { document }
+    }
+}
+
+impl Display for FlattenResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for FlattenResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+pub fn flatten<T: Flattener>(flattener: T) -> Result<FlattenResult, OpenapiSchemerError> {
+    let document = flattener.flatten()?;
+    Ok(FlattenResult::new(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    struct MockFlattener {
+        document: YamlNode,
+    }
+    impl Flattener for MockFlattener {
+        fn flatten(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_flatten() -> Result<(), Box<dyn Error>> {
+        let document = YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.3"))]);
+        let flattener = MockFlattener { document };
+        let result = flatten(flattener)?;
+        assert_eq!(result.to_string(), "openapi: 3.0.3");
+        assert_eq!(result.to_node().to_json(), "{\n  \"openapi\": \"3.0.3\"\n}");
+        Ok(())
+    }
+}
@@ -0,0 +1,862 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    bindings::{
+        docs::{docs_type, DocsBody, DocsPage, DocsParser, DocsType},
+        generate::{ComponentSchema, GenerateParser, SchemaRef},
+        template::Template,
+        yaml::{YamlNode, YamlPair, YamlValue},
+    },
+    error::OpenapiSchemerError,
+    output::{write_file, OutputFormatter},
+};
+
+/// TypeScript declarations for the component schemas of the spec, written as
+/// the contents of a `.d.ts` file.
+#[derive(Debug, PartialEq)]
+pub struct TypeScriptResult {
+    schemas: Vec<ComponentSchema>,
+}
+
+impl TypeScriptResult {
+    pub fn new(schemas: Vec<ComponentSchema>) -> TypeScriptResult {
+        TypeScriptResult { schemas }
+    }
+}
+
+impl Display for TypeScriptResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut declarations = vec![
+            "// Generated by openapi-schemer from the component schemas of the spec.".to_string(),
+        ];
+        declarations.extend(self.schemas.iter().map(declaration));
+        write!(f, "{}", declarations.join("\n\n"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// The files rendered from the templates of `generate client`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClientResult {
+    files: Vec<GeneratedFile>,
+}
+
+impl ClientResult {
+    pub fn new(files: Vec<GeneratedFile>) -> ClientResult {
+        ClientResult { files }
+    }
+
+    /// Writes the files under `directory`.
+    pub fn write(&self, directory: &Path) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            write_file(&directory.join(&file.path), &file.content)
+                .map_err(OpenapiSchemerError::Generate)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ClientResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .files
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ClientResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.files
+                .iter()
+                .map(|file| YamlNode::string(&file.path.display().to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// An `interface` for a plain object schema, otherwise a `type` alias.
+fn declaration(component: &ComponentSchema) -> String {
+    let schema = &component.schema;
+    let name = type_name(&component.name);
+    let doc = doc_comment(schema, "");
+    let composed = ["allOf", "oneOf", "anyOf", "enum", "const"]
+        .iter()
+        .any(|key| schema.get(key).is_some());
+    let declaration = match is_object(schema) && !composed && !is_nullable(schema) {
+        true => format!(
+            "export interface {} {{\n{}\n}}",
+            name,
+            object_members(schema, 1).join("\n")
+        ),
+        false => format!("export type {} = {};", name, type_expression(schema, 0)),
+    };
+    format!("{}{}", doc, declaration)
+}
+
+/// The TypeScript type of `schema`, with nested object types indented for
+/// the depth `indent`.
+fn type_expression(schema: &YamlNode, indent: usize) -> String {
+    if let Some(reference) = SchemaRef::of(schema) {
+        return match reference {
+            SchemaRef::Component(name) => type_name(name),
+            SchemaRef::Recursive => "unknown".to_string(),
+        };
+    }
+    let mut terms = vec![];
+    if let Some(value) = schema.get("const") {
+        terms.push(literal(value));
+    } else if let Some(values) = schema.get("enum").and_then(|values| values.as_sequence()) {
+        terms.push(union(values.iter().map(literal).collect()));
+    } else if let Some(types) = schema.get("type").and_then(|types| types.as_sequence()) {
+        let types = types
+            .iter()
+            .filter_map(|kind| kind.as_str())
+            .map(|kind| typed_expression(schema, kind, indent))
+            .collect();
+        terms.push(union(types));
+    } else if let Some(kind) = schema.get("type").and_then(|kind| kind.as_str()) {
+        terms.push(typed_expression(schema, kind, indent));
+    } else if is_object(schema) {
+        terms.push(object_expression(schema, indent));
+    }
+    for (key, join) in [("allOf", " & "), ("oneOf", " | "), ("anyOf", " | ")] {
+        let members = schema.get(key).and_then(|members| members.as_sequence());
+        let members: Vec<String> = members
+            .unwrap_or_default()
+            .iter()
+            .map(|member| type_expression(member, indent))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        match join {
+            " & " => terms.extend(members),
+            _ => terms.push(union(members)),
+        }
+    }
+    let expression = match terms.len() {
+        0 => "unknown".to_string(),
+        1 => terms.remove(0),
+        _ => terms
+            .iter()
+            .map(|term| parenthesize(term, " | "))
+            .collect::<Vec<String>>()
+            .join(" & "),
+    };
+    match is_nullable(schema) && expression != "null" {
+        true => union(vec![expression, "null".to_string()]),
+        false => expression,
+    }
+}
+
+/// The TypeScript type of `schema` as the JSON Schema type `kind`.
+fn typed_expression(schema: &YamlNode, kind: &str, indent: usize) -> String {
+    match kind {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => match schema.get("items") {
+            Some(items) => format!("{}[]", parenthesize(&type_expression(items, indent), " ")),
+            None => "unknown[]".to_string(),
+        },
+        "object" => object_expression(schema, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// An object literal type, or a `Record` when the object only has
+/// `additionalProperties`.
+fn object_expression(schema: &YamlNode, indent: usize) -> String {
+    let has_properties = schema
+        .get("properties")
+        .and_then(|properties| properties.as_mapping())
+        .is_some_and(|properties| !properties.is_empty());
+    if !has_properties {
+        let values = match schema.get("additionalProperties") {
+            Some(values) if values.as_mapping().is_some_and(|pairs| !pairs.is_empty()) => {
+                type_expression(values, indent)
+            }
+            _ => "unknown".to_string(),
+        };
+        return format!("Record<string, {}>", values);
+    }
+    format!(
+        "{{\n{}\n{}}}",
+        object_members(schema, indent + 1).join("\n"),
+        "  ".repeat(indent)
+    )
+}
+
+/// The properties of an object schema as the lines of an interface body.
+/// Properties that aren't `required` are optional.
+fn object_members(schema: &YamlNode, indent: usize) -> Vec<String> {
+    let pad = "  ".repeat(indent);
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(|required| required.as_sequence())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|name| name.as_str())
+        .collect();
+    let properties = schema
+        .get("properties")
+        .and_then(|properties| properties.as_mapping())
+        .unwrap_or_default();
+    let mut members = vec![];
+    for pair in properties {
+        let name = pair.key.as_str().unwrap_or_default();
+        let optional = match required.contains(&name) {
+            true => "",
+            false => "?",
+        };
+        members.push(format!(
+            "{}{}{}{}: {};",
+            doc_comment(&pair.value, &pad),
+            pad,
+            property_name(name),
+            optional,
+            type_expression(&pair.value, indent)
+        ));
+    }
+    let index = match schema.get("additionalProperties") {
+        Some(values) if values.as_str() == Some("false") => None,
+        Some(values) if properties.is_empty() && values.as_mapping().is_some() => {
+            Some(type_expression(values, indent))
+        }
+        Some(_) => Some("unknown".to_string()),
+        None if properties.is_empty() => Some("unknown".to_string()),
+        None => None,
+    };
+    if let Some(index) = index {
+        members.push(format!("{}[key: string]: {};", pad, index));
+    }
+    members
+}
+
+fn is_object(schema: &YamlNode) -> bool {
+    match schema.get("type") {
+        Some(kind) => kind.as_str() == Some("object"),
+        None => schema.get("properties").is_some() || schema.get("additionalProperties").is_some(),
+    }
+}
+
+fn is_nullable(schema: &YamlNode) -> bool {
+    schema
+        .get("nullable")
+        .and_then(|nullable| nullable.as_str())
+        == Some("true")
+}
+
+/// `members` joined into a union, leaving out repeated members.
+fn union(members: Vec<String>) -> String {
+    let mut unique: Vec<String> = vec![];
+    for member in members {
+        if !unique.contains(&member) {
+            unique.push(member);
+        }
+    }
+    match unique.is_empty() {
+        true => "never".to_string(),
+        false => unique.join(" | "),
+    }
+}
+
+/// `expression` in parentheses when it contains `separator`.
+fn parenthesize(expression: &str, separator: &str) -> String {
+    match expression.contains(separator) && !expression.starts_with('{') {
+        true => format!("({})", expression),
+        false => expression.to_string(),
+    }
+}
+
+/// An enum or const value as a literal type.
+fn literal(value: &YamlNode) -> String {
+    match value.json_type() {
+        "string" | "integer" | "number" | "boolean" | "null" => value.to_json(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// The `description` and `deprecated` flag of `schema` as a JSDoc comment
+/// ending in a newline, or nothing when it has neither.
+fn doc_comment(schema: &YamlNode, pad: &str) -> String {
+    let mut lines: Vec<String> = schema
+        .get("description")
+        .and_then(|description| description.as_str())
+        .unwrap_or_default()
+        .trim()
+        .lines()
+        .map(|line| line.trim_end().replace("*/", "*\\/"))
+        .collect();
+    if schema
+        .get("deprecated")
+        .and_then(|deprecated| deprecated.as_str())
+        == Some("true")
+    {
+        lines.push("@deprecated".to_string());
+    }
+    match lines.len() {
+        0 => String::new(),
+        1 => format!("{}/** {} */\n", pad, lines[0]),
+        _ => {
+            let body: Vec<String> = lines
+                .iter()
+                .map(|line| format!("{} * {}", pad, line).trim_end().to_string())
+                .collect();
+            format!("{}/**\n{}\n{} */\n", pad, body.join("\n"), pad)
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// A component name as a type name. Names that aren't identifiers, such as
+/// `pet-status`, are written in PascalCase.
+fn type_name(name: &str) -> String {
+    if is_identifier(name) {
+        return name.to_string();
+    }
+    let pascal: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first.into_iter().chain(chars).collect::<String>()
+        })
+        .collect();
+    match pascal.starts_with(|c: char| c.is_ascii_digit()) || pascal.is_empty() {
+        true => format!("_{}", pascal),
+        false => pascal,
+    }
+}
+
+fn property_name(name: &str) -> String {
+    match is_identifier(name) {
+        true => name.to_string(),
+        false => YamlNode::string(name).to_json(),
+    }
+}
+
+/// The context every client template is rendered with:
+///
+/// - `title`, `version` and `description` from `info`
+/// - `operations`, each with `operationId`, `name`, `method`, `path`,
+///   `summary`, `description`, `tags`, `deprecated`, `parameters` (`name`,
+///   `in`, `required`, `description`, `type`), `requestBody` and `responses`
+///   (`code`, `description`, `contentType`, `type`)
+/// - `schemas`, each with `name`, `description`, its `schema` and its
+///   `properties` (`name`, `required`, `description`, `type`)
+///
+/// Every `type` has the `name` of the schema type or of the component it
+/// refers to, whether it is a `component` and whether it is an `array` of
+/// that.
+fn client_context(page: &DocsPage, schemas: &[ComponentSchema]) -> YamlNode {
+    let text = |text: &Option<String>| match text {
+        Some(text) => YamlNode::string(text),
+        None => YamlNode::null(),
+    };
+    let operations = page
+        .operations
+        .iter()
+        .map(|operation| {
+            let node = &operation.operation;
+            let parameters = operation
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    YamlNode::mapping(vec![
+                        ("name", YamlNode::string(&parameter.name)),
+                        ("in", YamlNode::string(&parameter.location)),
+                        ("required", YamlNode::boolean(parameter.required)),
+                        ("description", text(&parameter.description)),
+                        ("type", optional_type_node(parameter.schema.as_ref())),
+                    ])
+                })
+                .collect();
+            let responses = operation.responses.iter().map(body_node).collect();
+            YamlNode::mapping(vec![
+                ("operationId", text(&node.operation_id)),
+                ("name", YamlNode::string(&node.text)),
+                ("method", YamlNode::string(&node.method)),
+                ("path", YamlNode::string(&node.path)),
+                ("summary", text(&node.summary)),
+                ("description", text(&operation.description)),
+                (
+                    "tags",
+                    YamlNode::sequence(node.tags.iter().map(|tag| YamlNode::string(tag)).collect()),
+                ),
+                ("deprecated", YamlNode::boolean(node.deprecated)),
+                ("parameters", YamlNode::sequence(parameters)),
+                (
+                    "requestBody",
+                    operation
+                        .request_body
+                        .as_ref()
+                        .map(body_node)
+                        .unwrap_or_else(YamlNode::null),
+                ),
+                ("responses", YamlNode::sequence(responses)),
+            ])
+        })
+        .collect();
+    let schemas = schemas
+        .iter()
+        .map(|component| {
+            let schema = &component.schema;
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(|required| required.as_sequence())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|name| name.as_str())
+                .collect();
+            let properties = schema
+                .get("properties")
+                .and_then(|properties| properties.as_mapping())
+                .unwrap_or_default()
+                .iter()
+                .map(|pair| {
+                    let name = pair.key.as_str().unwrap_or_default();
+                    let description = pair
+                        .value
+                        .get("description")
+                        .and_then(|description| description.as_str())
+                        .map(str::to_owned);
+                    YamlNode::mapping(vec![
+                        ("name", YamlNode::string(name)),
+                        ("required", YamlNode::boolean(required.contains(&name))),
+                        ("description", text(&description)),
+                        ("type", type_node(&docs_type(&pair.value))),
+                    ])
+                })
+                .collect();
+            let description = schema
+                .get("description")
+                .and_then(|description| description.as_str())
+                .map(str::to_owned);
+            YamlNode::mapping(vec![
+                ("name", YamlNode::string(&component.name)),
+                ("description", text(&description)),
+                ("schema", schema.to_owned()),
+                ("properties", YamlNode::sequence(properties)),
+            ])
+        })
+        .collect();
+    YamlNode::mapping(vec![
+        ("title", YamlNode::string(&page.title)),
+        ("version", text(&page.version)),
+        ("description", text(&page.description)),
+        ("operations", YamlNode::sequence(operations)),
+        ("schemas", YamlNode::sequence(schemas)),
+    ])
+}
+
+/// A request body or response with the first of its media types.
+fn body_node(body: &DocsBody) -> YamlNode {
+    let media = body.media_types.first();
+    let description = match &body.description {
+        Some(description) => YamlNode::string(description),
+        None => YamlNode::null(),
+    };
+    YamlNode::mapping(vec![
+        ("code", YamlNode::string(&body.name)),
+        ("description", description),
+        (
+            "contentType",
+            media
+                .map(|media| YamlNode::string(&media.name))
+                .unwrap_or_else(YamlNode::null),
+        ),
+        (
+            "type",
+            optional_type_node(media.and_then(|media| media.schema.as_ref())),
+        ),
+    ])
+}
+
+fn type_node(kind: &DocsType) -> YamlNode {
+    YamlNode::mapping(vec![
+        ("name", YamlNode::string(&kind.name)),
+        ("component", YamlNode::boolean(kind.component)),
+        ("array", YamlNode::boolean(kind.array)),
+    ])
+}
+
+fn optional_type_node(kind: Option<&DocsType>) -> YamlNode {
+    kind.map(type_node).unwrap_or_else(YamlNode::null)
+}
+
+/// Renders `templates`, given by their path within the template directory.
+/// Templates under `operations/` are rendered once for every operation with
+/// it as `operation`, and those under `schemas/` once for every schema with
+/// it as `schema`. Paths are rendered as templates too, and lose a `.hbs`
+/// extension.
+fn render_templates(
+    templates: &[(PathBuf, String)],
+    context: &YamlNode,
+) -> Result<Vec<GeneratedFile>, String> {
+    let mut files: Vec<GeneratedFile> = vec![];
+    for (path, source) in templates {
+        let display = path.display().to_string();
+        let error = |message: String| format!("Template `{}`: {}", display, message);
+        let path_template =
+            Template::new(display.strip_suffix(".hbs").unwrap_or(&display)).map_err(error)?;
+        let template = Template::new(source).map_err(error)?;
+        let each = match path
+            .components()
+            .next()
+            .and_then(|first| first.as_os_str().to_str())
+        {
+            Some("operations") if path.components().count() > 1 => {
+                Some(("operations", "operation"))
+            }
+            Some("schemas") if path.components().count() > 1 => Some(("schemas", "schema")),
+            _ => None,
+        };
+        let contexts = match each {
+            Some((list, item)) => context
+                .get(list)
+                .and_then(|items| items.as_sequence())
+                .unwrap_or_default()
+                .iter()
+                .map(|value| {
+                    let mut context = context.to_owned();
+                    if let YamlValue::Mapping(pairs) = &mut context.value {
+                        pairs.push(YamlPair {
+                            key: YamlNode::string(item),
+                            value: value.to_owned(),
+                        });
+                    }
+                    context
+                })
+                .collect(),
+            None => vec![context.to_owned()],
+        };
+        for context in contexts {
+            let file = GeneratedFile {
+                path: PathBuf::from(path_template.render(&context)),
+                content: template.render(&context),
+            };
+            if files.iter().any(|written| written.path == file.path) {
+                return Err(error(format!(
+                    "`{}` is written more than once",
+                    file.path.display()
+                )));
+            }
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
+/// TypeScript declarations for every component schema.
+pub fn typescript<T: GenerateParser>(parser: T) -> Result<TypeScriptResult, OpenapiSchemerError> {
+    Ok(TypeScriptResult::new(parser.get_component_schemas()?))
+}
+
+/// Every file below `directory` with its path relative to it, in a stable
+/// order.
+pub fn read_templates(directory: &Path) -> Result<Vec<(PathBuf, String)>, OpenapiSchemerError> {
+    let mut templates = vec![];
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(current) = directories.pop() {
+        let error = |error: std::io::Error| {
+            OpenapiSchemerError::Generate(format!("{}: {}", current.display(), error))
+        };
+        let mut entries = std::fs::read_dir(&current)
+            .map_err(error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(error)?;
+        entries.sort_by_key(|entry| entry.path());
+        for entry in entries {
+            let path = entry.path();
+            if path.is_dir() {
+                directories.push(path);
+                continue;
+            }
+            let content = std::fs::read_to_string(&path).map_err(|error| {
+                OpenapiSchemerError::Generate(format!("{}: {}", path.display(), error))
+            })?;
+            let relative = path.strip_prefix(directory).unwrap_or(&path).to_path_buf();
+            templates.push((relative, content));
+        }
+    }
+    templates.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(templates)
+}
+
+/// Renders the client `templates` with the operations and component schemas
+/// of the spec. See `client_context` for what the templates can refer to.
+pub fn client<T: DocsParser, U: GenerateParser>(
+    docs_parser: T,
+    generate_parser: U,
+    templates: &[(PathBuf, String)],
+) -> Result<ClientResult, OpenapiSchemerError> {
+    let page = docs_parser.get_docs_page()?;
+    let schemas = generate_parser.get_component_schemas()?;
+    let context = client_context(&page, &schemas);
+    let files = render_templates(templates, &context).map_err(OpenapiSchemerError::Generate)?;
+    Ok(ClientResult::new(files))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::bindings::{
+        docs::{DocsMediaType, DocsOperation, DocsParameter},
+        yaml::parse_yaml,
+        OperationNode,
+    };
+
+    use super::*;
+
+    struct MockParser {
+        schemas: Vec<ComponentSchema>,
+    }
+    impl GenerateParser for MockParser {
+        fn get_component_schemas(&self) -> Result<Vec<ComponentSchema>, OpenapiSchemerError> {
+            Ok(self.schemas.to_owned())
+        }
+    }
+
+    struct MockDocsParser {
+        page: DocsPage,
+    }
+    impl DocsParser for MockDocsParser {
+        fn get_docs_page(&self) -> Result<DocsPage, OpenapiSchemerError> {
+            Ok(self.page.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_typescript() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let parser = MockParser {
+            schemas: vec![
+                schema(
+                    "Pet",
+                    r##"
+description: A pet in the store
+type: object
+required: [id, name]
+properties:
+  id:
+    type: integer
+  name:
+    type: string
+  status:
+    $ref: "#/components/schemas/pet-status"
+  x-rating:
+    type: number
+    nullable: true
+    deprecated: true
+  tags:
+    type: array
+    items:
+      oneOf:
+        - type: string
+        - type: object
+          properties:
+            label:
+              type: string
+"##,
+                )?,
+                schema("pet-status", "type: string\nenum: [available, sold, 1]")?,
+                schema(
+                    "Animal",
+                    r##"
+oneOf:
+  - $ref: "#/components/schemas/Pet"
+  - type: object
+    additionalProperties:
+      type: string
+"##,
+                )?,
+                schema(
+                    "Dog",
+                    r##"
+allOf:
+  - $ref: "#/components/schemas/Animal"
+  - type: object
+    properties:
+      bark:
+        type: [string, "null"]
+"##,
+                )?,
+            ],
+        };
+        assert_eq!(
+            typescript(parser)?.to_string(),
+            r#"// Generated by openapi-schemer from the component schemas of the spec.
+
+/** A pet in the store */
+export interface Pet {
+  id: number;
+  name: string;
+  status?: PetStatus;
+  /** @deprecated */
+  "x-rating"?: number | null;
+  tags?: (string | {
+    label?: string;
+  })[];
+}
+
+export type PetStatus = "available" | "sold" | 1;
+
+export type Animal = Pet | Record<string, string>;
+
+export type Dog = Animal & {
+  bark?: string | null;
+};"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_client() -> Result<(), Box<dyn Error>> {
+        let operation = |operation_id: &str, method: &str, path: &str| DocsOperation {
+            operation: OperationNode {
+                text: operation_id.to_string(),
+                operation_id: Some(operation_id.to_string()),
+                method: method.to_string(),
+                path: path.to_string(),
+                summary: None,
+                tags: vec!["pets".to_string()],
+                deprecated: false,
+                file: PathBuf::from("#"),
+                line: 1,
+                column: 1,
+            },
+            description: None,
+            parameters: vec![],
+            request_body: None,
+            responses: vec![],
+        };
+        let pet = DocsType {
+            array: false,
+            name: "Pet".to_string(),
+            component: true,
+        };
+        let mut show = operation("showPetById", "get", "/pets/{petId}");
+        show.parameters.push(DocsParameter {
+            name: "petId".to_string(),
+            location: "path".to_string(),
+            required: true,
+            description: None,
+            schema: Some(DocsType {
+                array: false,
+                name: "string".to_string(),
+                component: false,
+            }),
+        });
+        show.responses.push(DocsBody {
+            name: "200".to_string(),
+            description: Some("A pet".to_string()),
+            media_types: vec![DocsMediaType {
+                name: "application/json".to_string(),
+                schema: Some(pet),
+                example: None,
+            }],
+        });
+        let page = DocsPage {
+            title: "Petstore".to_string(),
+            version: None,
+            description: None,
+            operations: vec![show, operation("listPets", "get", "/pets")],
+            schemas: vec![],
+            examples: vec![],
+        };
+        let parser = MockParser {
+            schemas: vec![ComponentSchema {
+                name: "Pet".to_string(),
+                schema: parse_yaml("type: object\nrequired: [id]\nproperties:\n  id:\n    type: integer\n  tag:\n    $ref: '#/components/schemas/Tag'")?,
+            }],
+        };
+        let templates = vec![
+            (
+                PathBuf::from("index.ts.hbs"),
+                "// {{title}}\n{{#each operations}}\nexport * from './{{camelCase operationId}}';\n{{/each}}\n".to_string(),
+            ),
+            (
+                PathBuf::from("operations/{{camelCase operation.operationId}}.ts"),
+                r#"export const {{operation.name}} = (
+{{#each operation.parameters}}
+  {{name}}: {{type.name}},
+{{/each}}
+) => `{{upperCase operation.method}} {{operation.path}}`;
+{{#each operation.responses}}
+// {{code}}: {{#if type.component}}{{type.name}}{{else}}unknown{{/if}}
+{{/each}}
+"#
+                .to_string(),
+            ),
+            (
+                PathBuf::from("schemas/{{schema.name}}.ts"),
+                "{{#each schema.properties}}\n{{name}}{{#unless required}}?{{/unless}}: {{type.name}}\n{{/each}}".to_string(),
+            ),
+        ];
+        let result = client(
+            MockDocsParser {
+                page: page.to_owned(),
+            },
+            parser,
+            &templates,
+        )?;
+        assert_eq!(
+            result.to_string(),
+            "index.ts\noperations/showPetById.ts\noperations/listPets.ts\nschemas/Pet.ts"
+        );
+        assert_eq!(
+            result.files[0].content,
+            "// Petstore\nexport * from './showPetById';\nexport * from './listPets';\n"
+        );
+        assert_eq!(
+            result.files[1].content,
+            "export const showPetById = (\n  petId: string,\n) => `GET /pets/{petId}`;\n// 200: Pet\n"
+        );
+        assert_eq!(result.files[3].content, "id: integer\ntag?: Tag\n");
+
+        let templates = vec![(PathBuf::from("operations/client.ts"), String::new())];
+        let parser = MockParser { schemas: vec![] };
+        let error = client(MockDocsParser { page }, parser, &templates).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Template `operations/client.ts`: `operations/client.ts` is written more than once"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("Pet"), "Pet");
+        assert_eq!(type_name("pet-status"), "PetStatus");
+        assert_eq!(type_name("v1.Pet_list"), "V1PetList");
+        assert_eq!(type_name("2fa"), "_2fa");
+    }
+}
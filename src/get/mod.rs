@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{get::GetParser, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct GetResult {
+    node: YamlNode,
+}
+
+impl GetResult {
+    pub fn new(node: YamlNode) -> GetResult {
+        GetResult { node }
+    }
+}
+
+impl Display for GetResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.node.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for GetResult {
+    fn to_node(&self) -> YamlNode {
+        self.node.to_owned()
+    }
+}
+
+pub fn get<T: GetParser>(
+    parser: T,
+    pointer: &str,
+    inline: bool,
+) -> Result<GetResult, OpenapiSchemerError> {
+    match parser.get_node(pointer, inline)? {
+        Some(node) => Ok(GetResult::new(node)),
+        None => Err(OpenapiSchemerError::Get(format!(
+            "Could not find `{}`",
+            pointer
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::output::OutputFormat;
+
+    use super::*;
+
+    struct MockParser {
+        node: Option<YamlNode>,
+    }
+    impl GetParser for MockParser {
+        fn get_node(
+            &self,
+            _pointer: &str,
+            _inline: bool,
+        ) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.node.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            node: Some(YamlNode::mapping(vec![(
+                "operationId",
+                YamlNode::string("listPets"),
+            )])),
+        };
+        let result = get(parser, "/paths/~1pets/get", false)?;
+        assert_eq!(result.to_string(), "operationId: listPets");
+        assert_eq!(
+            result.format(OutputFormat::Json),
+            "{\n  \"operationId\": \"listPets\"\n}"
+        );
+
+        let parser = MockParser { node: None };
+        assert_eq!(
+            get(parser, "/paths/~1dogs", false).unwrap_err().to_string(),
+            "Could not find `/paths/~1dogs`"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        header::{HeaderNode, HeaderParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    headers: Vec<HeaderNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(headers: Vec<HeaderNode>, locations: bool) -> ListResult {
+        ListResult { headers, locations }
+    }
+
+    /// `X-Rate-Limit (integer, required)`, leaving out what the header
+    /// doesn't say.
+    fn line(header: &HeaderNode) -> String {
+        let details: Vec<&str> = header
+            .schema_type
+            .as_deref()
+            .into_iter()
+            .chain(header.required.then_some("required"))
+            .collect();
+        match details.is_empty() {
+            true => header.text.to_owned(),
+            false => format!("{} ({})", header.text, details.join(", ")),
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.headers.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .headers
+            .iter()
+            .map(|header| match self.locations {
+                true => ListEntry {
+                    name: ListResult::line(header),
+                    file: header.file.to_owned(),
+                    line: header.line,
+                    column: header.column,
+                }
+                .with_location(),
+                false => ListResult::line(header),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&header.text)),
+                    (
+                        "type",
+                        header
+                            .schema_type
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    ("required", YamlNode::boolean(header.required)),
+                    ("file", YamlNode::string(&header.file.display().to_string())),
+                    ("line", YamlNode::integer(header.line)),
+                    ("column", YamlNode::integer(header.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(headers)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    name: String,
+    header: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(name: String, header: YamlNode) -> ShowResult {
+        ShowResult { name, header }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(self.name.as_str(), self.header.to_owned())])
+    }
+}
+
+pub fn list<T: HeaderParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_header_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+pub fn show<T: HeaderParser>(parser: T, name: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_header(name)? {
+        Some(header) => Ok(ShowResult::new(name.to_string(), header)),
+        None => Err(OpenapiSchemerError::HeaderShow(format!(
+            "Could not find header `{}`",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<HeaderNode>,
+        header: Option<YamlNode>,
+    }
+    impl HeaderParser for MockParser {
+        fn get_header_nodes(&self) -> Result<Vec<HeaderNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_header(&self, _name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.header.to_owned())
+        }
+    }
+
+    fn parser() -> MockParser {
+        let header = |text: &str, schema_type: Option<&str>, required, line| HeaderNode {
+            text: text.to_string(),
+            schema_type: schema_type.map(str::to_owned),
+            required,
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 5,
+        };
+        MockParser {
+            nodes: vec![
+                header("X-Rate-Limit", Some("integer"), true, 4),
+                header("X-Request-Id", Some("string"), false, 8),
+                header("X-Trace", None, false, 12),
+            ],
+            header: None,
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            list(parser(), false)?.to_string(),
+            "X-Rate-Limit (integer, required)\nX-Request-Id (string)\nX-Trace"
+        );
+        assert_eq!(
+            list(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:4:5: X-Rate-Limit (integer, required)\n\
+             /spec/openapi.yaml:8:5: X-Request-Id (string)\n\
+             /spec/openapi.yaml:12:5: X-Trace"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = parser();
+        parser.header = Some(YamlNode::mapping(vec![(
+            "required",
+            YamlNode::boolean(true),
+        )]));
+        assert_eq!(
+            show(parser, "X-Rate-Limit")?.to_string(),
+            "X-Rate-Limit:\n  required: true"
+        );
+        let error = show(self::parser(), "missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find header `missing`");
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use crate::error::OpenapiSchemerError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// The value of the header `name`, compared case insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends `request` over HTTP/1.1 and waits for the whole response. Only
+/// plain `http://` URLs can be reached, there is no TLS support.
+pub fn send(request: &HttpRequest) -> Result<HttpResponse, OpenapiSchemerError> {
+    let to_error = |error: std::io::Error| {
+        OpenapiSchemerError::Http(format!("Request to `{}` failed: {}", request.url, error))
+    };
+    let (host, port, target) = split_url(&request.url)?;
+    let stream = TcpStream::connect((host.as_str(), port)).map_err(to_error)?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .map_err(to_error)?;
+
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        request.method.to_uppercase(),
+        target,
+        host_header(&host, port)
+    );
+    for (name, value) in &request.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let body = request.body.as_deref().unwrap_or_default();
+    if request.body.is_some() {
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    (&stream).write_all(head.as_bytes()).map_err(to_error)?;
+    (&stream).write_all(body.as_bytes()).map_err(to_error)?;
+    read_response(BufReader::new(&stream)).map_err(to_error)
+}
+
+/// Splits `http://host:port/path?query` into the host, the port and the
+/// request target. An IPv6 host is written in brackets, such as
+/// `http://[::1]:8080/`, and is returned without them.
+fn split_url(url: &str) -> Result<(String, u16, String), OpenapiSchemerError> {
+    let rest = match url.split_once("://") {
+        Some(("http", rest)) => rest,
+        Some((scheme, _)) => {
+            return Err(OpenapiSchemerError::Http(format!(
+                "Only http:// servers can be reached, not {}://",
+                scheme
+            )))
+        }
+        None => {
+            return Err(OpenapiSchemerError::Http(format!(
+                "`{}` is not an absolute http:// URL",
+                url
+            )))
+        }
+    };
+    let (authority, target) = match rest.find(['/', '?']) {
+        Some(index) => (&rest[..index], rest[index..].to_string()),
+        None => (rest, String::new()),
+    };
+    let target = match target.starts_with('/') {
+        true => target,
+        false => format!("/{}", target),
+    };
+    let invalid_port = || OpenapiSchemerError::Http(format!("Invalid port in `{}`", url));
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(rest) => {
+            let (host, rest) = rest.split_once(']').ok_or_else(|| {
+                OpenapiSchemerError::Http(format!("Unclosed `[` in the host of `{}`", url))
+            })?;
+            match rest.strip_prefix(':') {
+                Some(port) => (host, port.parse().map_err(|_| invalid_port())?),
+                None if rest.is_empty() => (host, 80),
+                None => return Err(invalid_port()),
+            }
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid_port())?),
+            None => (authority, 80),
+        },
+    };
+    Ok((host.to_string(), port, target))
+}
+
+/// The value of the `Host` header for `host` and `port`, which leaves out
+/// the default port.
+fn host_header(host: &str, port: u16) -> String {
+    let host = match host.contains(':') {
+        true => format!("[{}]", host),
+        false => host.to_string(),
+    };
+    match port {
+        80 => host,
+        port => format!("{}:{}", host, port),
+    }
+}
+
+fn read_response(mut reader: impl BufRead) -> std::io::Result<HttpResponse> {
+    let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let mut parts = status_line.trim_end().splitn(3, ' ');
+    let status = parts
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid("Malformed status line"))?;
+    let reason = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = vec![];
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let mut response = HttpResponse {
+        status,
+        reason,
+        headers,
+        body: String::new(),
+    };
+
+    let mut body = vec![];
+    let chunked = response
+        .header("transfer-encoding")
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"));
+    let length = response
+        .header("content-length")
+        .and_then(|length| length.parse::<u64>().ok());
+    match (chunked, length) {
+        (true, _) => loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = size.split(';').next().unwrap_or_default().trim();
+            let size =
+                usize::from_str_radix(size, 16).map_err(|_| invalid("Malformed chunk size"))?;
+            if size == 0 {
+                break;
+            }
+            let mut chunk = vec![0; size];
+            reader.read_exact(&mut chunk)?;
+            body.extend(chunk);
+            // The line break that ends every chunk
+            reader.read_line(&mut String::new())?;
+        },
+        (false, Some(length)) => {
+            reader.take(length).read_to_end(&mut body)?;
+        }
+        (false, None) => {
+            reader.read_to_end(&mut body)?;
+        }
+    }
+    response.body = String::from_utf8_lossy(&body).into_owned();
+    Ok(response)
+}
+
+/// Percent encodes everything but the characters RFC 3986 leaves unreserved,
+/// for use in a path segment or query string.
+pub fn encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_split_url() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            split_url("http://localhost:8080/v1/pets?limit=1")?,
+            (
+                "localhost".to_string(),
+                8080,
+                "/v1/pets?limit=1".to_string()
+            )
+        );
+        assert_eq!(
+            split_url("http://example.com")?,
+            ("example.com".to_string(), 80, "/".to_string())
+        );
+        assert_eq!(
+            split_url("http://[::1]:8080/pets")?,
+            ("::1".to_string(), 8080, "/pets".to_string())
+        );
+        assert_eq!(
+            split_url("http://[::1]")?,
+            ("::1".to_string(), 80, "/".to_string())
+        );
+        assert_eq!(
+            split_url("http://[::1]8080").unwrap_err().to_string(),
+            "Invalid port in `http://[::1]8080`"
+        );
+        assert_eq!(
+            split_url("https://example.com").unwrap_err().to_string(),
+            "Only http:// servers can be reached, not https://"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_host_header() {
+        assert_eq!(host_header("example.com", 80), "example.com");
+        assert_eq!(host_header("localhost", 8080), "localhost:8080");
+        assert_eq!(host_header("::1", 8080), "[::1]:8080");
+        assert_eq!(host_header("::1", 80), "[::1]");
+    }
+
+    #[test]
+    fn test_send() -> Result<(), Box<dyn Error>> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let address = listener.local_addr()?;
+        let url = format!("http://{}/pets?name=Rex", address);
+        let server = thread::spawn(move || -> std::io::Result<String> {
+            let (stream, _) = listener.accept()?;
+            let mut reader = BufReader::new(&stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request)? > 2 {}
+            let mut body = [0; 2];
+            reader.read_exact(&mut body)?;
+            request.push_str(&String::from_utf8_lossy(&body));
+            (&stream).write_all(
+                b"HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n{\"id\r\n3\r\n\":1\r\n1\r\n}\r\n0\r\n\r\n",
+            )?;
+            Ok(request)
+        });
+        let response = send(&HttpRequest {
+            method: "post".to_string(),
+            url,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: Some("{}".to_string()),
+        })?;
+        let request = server.join().unwrap()?;
+        assert!(request.starts_with(&format!(
+            "POST /pets?name=Rex HTTP/1.1\r\nHost: {}\r\n",
+            address
+        )));
+        assert!(request.contains("Content-Type: application/json\r\nContent-Length: 2\r\n"));
+        assert!(request.ends_with("\r\n\r\n{}"));
+        assert_eq!(
+            (response.status, response.reason.as_str()),
+            (201, "Created")
+        );
+        assert_eq!(response.body, "{\"id\":1}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("Rex & Co/2"), "Rex%20%26%20Co%2F2");
+    }
+}
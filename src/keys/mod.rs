@@ -0,0 +1,160 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        keys::{KeyNode, KeyParser},
+        yaml::{YamlNode, YamlValue},
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct KeysResult {
+    keys: Vec<KeyNode>,
+    locations: bool,
+}
+
+impl KeysResult {
+    pub fn new(keys: Vec<KeyNode>, locations: bool) -> KeysResult {
+        KeysResult { keys, locations }
+    }
+}
+
+/// A one line description of `value`, its text for scalars.
+fn summary(value: &YamlNode) -> String {
+    let count = |count: usize, name: &str| match count {
+        1 => format!("1 {}", name),
+        count => format!("{} {}s", count, name),
+    };
+    if let Some(reference) = value.reference() {
+        return format!("$ref {}", reference);
+    }
+    match &value.value {
+        YamlValue::Mapping(pairs) => format!("{{{}}}", count(pairs.len(), "key")),
+        YamlValue::Sequence(items) => format!("[{}]", count(items.len(), "item")),
+        YamlValue::Scalar(scalar) => scalar.text.to_owned(),
+        YamlValue::Alias(name) => format!("*{}", name),
+    }
+}
+
+impl Display for KeysResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.keys.is_empty() {
+            return write!(f, "No keys found");
+        }
+        let lines: Vec<String> = self
+            .keys
+            .iter()
+            .map(|key| {
+                let entry = ListEntry {
+                    name: format!("{}: {}", key.key, summary(&key.value)),
+                    file: key.file.to_owned(),
+                    line: key.line,
+                    column: key.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for KeysResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.keys
+                .iter()
+                .map(|key| {
+                    YamlNode::mapping(vec![
+                        ("key", YamlNode::string(&key.key)),
+                        ("value", key.value.to_owned()),
+                        ("file", YamlNode::string(&key.file.display().to_string())),
+                        ("line", YamlNode::integer(key.line)),
+                        ("column", YamlNode::integer(key.column)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+pub fn keys<T: KeyParser>(
+    parser: T,
+    path: &str,
+    locations: bool,
+) -> Result<KeysResult, OpenapiSchemerError> {
+    match parser.get_keys(path)? {
+        Some(keys) => Ok(KeysResult::new(keys, locations)),
+        None => Err(OpenapiSchemerError::Keys(format!(
+            "Could not find `{}`",
+            path
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        keys: Option<Vec<KeyNode>>,
+    }
+    impl KeyParser for MockParser {
+        fn get_keys(&self, _path: &str) -> Result<Option<Vec<KeyNode>>, OpenapiSchemerError> {
+            Ok(self.keys.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_keys() -> Result<(), Box<dyn Error>> {
+        let key = |key: &str, value: YamlNode, line| KeyNode {
+            key: key.to_string(),
+            value,
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 5,
+        };
+        let nodes = vec![
+            key(
+                "Pet",
+                YamlNode::mapping(vec![("type", YamlNode::string("object"))]),
+                4,
+            ),
+            key(
+                "Pets",
+                YamlNode::mapping(vec![("$ref", YamlNode::string("pets.yaml"))]),
+                6,
+            ),
+            key("Tags", YamlNode::sequence(vec![]), 7),
+            key("Name", YamlNode::string("string"), 8),
+        ];
+        let parser = MockParser {
+            keys: Some(nodes.to_owned()),
+        };
+        assert_eq!(
+            keys(parser, "components.schemas", false)?.to_string(),
+            "Pet: {1 key}\nPets: $ref pets.yaml\nTags: [0 items]\nName: string"
+        );
+        let parser = MockParser {
+            keys: Some(nodes[..1].to_vec()),
+        };
+        assert_eq!(
+            keys(parser, "components.schemas", true)?.to_string(),
+            "/spec/openapi.yaml:4:5: Pet: {1 key}"
+        );
+        let parser = MockParser { keys: None };
+        assert_eq!(
+            keys(parser, "components.links", false)
+                .unwrap_err()
+                .to_string(),
+            "Could not find `components.links`"
+        );
+        Ok(())
+    }
+}
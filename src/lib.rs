@@ -0,0 +1,105 @@
+//! Inspect OpenAPI specs, including ones split across several files.
+//!
+//! [`Spec`] covers the common lookups. The parser traits it is built on, such
+//! as [`OperationParser`], are exported for anything more specific:
+//!
+//! ```no_run
+//! let spec = openapi_schemer::Spec::load("openapi.yaml")?;
+//! for operation in spec.operations()? {
+//!     println!("{}", operation.text);
+//! }
+//! # Ok::<(), openapi_schemer::error::OpenapiSchemerError>(())
+//! ```
+
+pub mod bindings;
+pub mod browse;
+pub mod bundle;
+pub mod complete;
+pub mod config;
+pub mod conformance;
+pub mod content;
+pub mod convert;
+pub mod dedupe;
+pub mod deprecated;
+pub mod diff;
+pub mod docs;
+pub mod error;
+pub mod example;
+pub mod export;
+pub mod extension;
+pub mod filter;
+pub mod flatten;
+pub mod generate;
+pub mod get;
+pub mod header;
+pub mod http;
+pub mod keys;
+pub mod link;
+pub mod lint;
+pub mod operation;
+pub mod output;
+pub mod overlay;
+pub mod parameter;
+pub mod path;
+pub mod query;
+pub mod refs;
+pub mod response;
+pub mod schema;
+pub mod search;
+pub mod security;
+pub mod serve;
+pub mod server;
+pub mod spec;
+pub mod split;
+pub mod stats;
+pub mod tag;
+pub mod unused;
+pub mod upgrade;
+pub mod validate;
+pub mod version;
+pub mod webhook;
+
+pub use bindings::{
+    browse::BrowseParser,
+    bundle::Bundler,
+    callback::CallbackParser,
+    content_type::ContentTypeParser,
+    contract::ContractChecker,
+    convert::ConvertParser,
+    dedupe::Deduplicator,
+    deprecated::DeprecationParser,
+    diff::DiffParser,
+    docs::DocsParser,
+    example::ExampleParser,
+    extension::ExtensionParser,
+    filter::Filterer,
+    flatten::Flattener,
+    generate::GenerateParser,
+    get::GetParser,
+    header::HeaderParser,
+    keys::KeyParser,
+    link::LinkParser,
+    lint::LintParser,
+    overlay::OverlayApplier,
+    parameter::ParameterParser,
+    path::PathParser,
+    query::QueryParser,
+    refs::RefParser,
+    rename::{OperationRenamer, PathRenamer, SchemaRenamer},
+    response::ResponseParser,
+    schema::SchemaParser,
+    search::SearchParser,
+    security::SecurityParser,
+    serve::ServeParser,
+    server::ServerParser,
+    split::Splitter,
+    stats::StatsParser,
+    tag::TagParser,
+    unused::UnusedParser,
+    upgrade::Upgrader,
+    validate::ValidationParser,
+    version::VersionParser,
+    webhook::WebhookParser,
+    OperationParser,
+};
+pub use spec::Spec;
@@ -0,0 +1,188 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        link::{LinkNode, LinkParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    links: Vec<LinkNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(links: Vec<LinkNode>, locations: bool) -> ListResult {
+        ListResult { links, locations }
+    }
+
+    /// `GetPetById -> showPetById`, or `<none>` for a link without a target.
+    fn line(link: &LinkNode) -> String {
+        format!(
+            "{} -> {}",
+            link.text,
+            link.target.as_deref().unwrap_or("<none>")
+        )
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.links.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .links
+            .iter()
+            .map(|link| match self.locations {
+                true => ListEntry {
+                    name: ListResult::line(link),
+                    file: link.file.to_owned(),
+                    line: link.line,
+                    column: link.column,
+                }
+                .with_location(),
+                false => ListResult::line(link),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let links = self
+            .links
+            .iter()
+            .map(|link| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&link.text)),
+                    (
+                        "target",
+                        link.target
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    ("file", YamlNode::string(&link.file.display().to_string())),
+                    ("line", YamlNode::integer(link.line)),
+                    ("column", YamlNode::integer(link.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(links)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    name: String,
+    link: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(name: String, link: YamlNode) -> ShowResult {
+        ShowResult { name, link }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(self.name.as_str(), self.link.to_owned())])
+    }
+}
+
+pub fn list<T: LinkParser>(parser: T, locations: bool) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_link_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+pub fn show<T: LinkParser>(parser: T, name: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_link(name)? {
+        Some(link) => Ok(ShowResult::new(name.to_string(), link)),
+        None => Err(OpenapiSchemerError::LinkShow(format!(
+            "Could not find link `{}`",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<LinkNode>,
+        link: Option<YamlNode>,
+    }
+    impl LinkParser for MockParser {
+        fn get_link_nodes(&self) -> Result<Vec<LinkNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_link(&self, _name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.link.to_owned())
+        }
+    }
+
+    fn parser() -> MockParser {
+        let link = |text: &str, target: Option<&str>, line| LinkNode {
+            text: text.to_string(),
+            target: target.map(str::to_owned),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 5,
+        };
+        MockParser {
+            nodes: vec![
+                link("GetPetById", Some("showPetById"), 4),
+                link("Unfinished", None, 9),
+            ],
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            list(parser(), false)?.to_string(),
+            "GetPetById -> showPetById\nUnfinished -> <none>"
+        );
+        assert_eq!(
+            list(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:4:5: GetPetById -> showPetById\n\
+             /spec/openapi.yaml:9:5: Unfinished -> <none>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = parser();
+        parser.link = Some(YamlNode::mapping(vec![(
+            "operationId",
+            YamlNode::string("showPetById"),
+        )]));
+        assert_eq!(
+            show(parser, "GetPetById")?.to_string(),
+            "GetPetById:\n  operationId: showPetById"
+        );
+        let error = show(self::parser(), "missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find link `missing`");
+        Ok(())
+    }
+}
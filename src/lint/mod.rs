@@ -0,0 +1,377 @@
+use std::{fmt::Display, path::Path};
+
+use anyhow::Context;
+
+use crate::{
+    bindings::{
+        edit::FileEdits,
+        lint::{rules, LintDiagnostic, LintParser, LintRule},
+        ruleset::Ruleset,
+        yaml::{parse_yaml, YamlNode},
+    },
+    error::OpenapiSchemerError,
+    output::{write_file, OutputFormatter},
+};
+
+/// Which lint rules run. Every rule starts enabled, then the rules of the
+/// project config, the ruleset, the config file and finally the
+/// `--enable`/`--disable` flags are applied on top.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintConfig {
+    enabled: Vec<String>,
+    /// The built-in rules and the rules defined by the ruleset.
+    known: Vec<String>,
+}
+
+impl LintConfig {
+    pub fn new(
+        defaults: &[(String, bool)],
+        ruleset: Option<&Ruleset>,
+        config: Option<&Path>,
+        enable: Vec<String>,
+        disable: Vec<String>,
+    ) -> Result<LintConfig, OpenapiSchemerError> {
+        let mut known: Vec<String> = rules().iter().map(|rule| rule.name().to_string()).collect();
+        for rule in ruleset
+            .map(|ruleset| ruleset.rules.as_slice())
+            .unwrap_or_default()
+        {
+            if known.iter().any(|name| name == rule.name()) {
+                return Err(OpenapiSchemerError::Lint(format!(
+                    "Rule `{}` of the ruleset is already a built-in rule",
+                    rule.name()
+                )));
+            }
+            known.push(rule.name().to_string());
+        }
+        let mut lint_config = LintConfig {
+            enabled: known.to_owned(),
+            known,
+        };
+        for (name, enabled) in defaults {
+            lint_config.set(name, *enabled)?;
+        }
+        for (name, enabled) in ruleset
+            .map(|ruleset| ruleset.toggles.as_slice())
+            .unwrap_or_default()
+        {
+            lint_config.set(name, *enabled)?;
+        }
+        if let Some(config) = config {
+            let content = std::fs::read_to_string(config)
+                .with_context(|| format!("Failed to read lint config `{}`", config.display()))
+                .map_err(|error| {
+                    OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Lint)
+                })?;
+            lint_config.apply_config(&content)?;
+        }
+        for name in enable {
+            lint_config.set(&name, true)?;
+        }
+        for name in disable {
+            lint_config.set(&name, false)?;
+        }
+        Ok(lint_config)
+    }
+
+    /// Applies a config file of the form:
+    ///
+    /// ```yaml
+    /// rules:
+    ///   operation-summary: off
+    /// ```
+    fn apply_config(&mut self, content: &str) -> Result<(), OpenapiSchemerError> {
+        let config = parse_yaml(content)
+            .with_context(|| "Failed to parse lint config")
+            .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Lint))?;
+        let rules = config
+            .get("rules")
+            .and_then(|rules| rules.as_mapping())
+            .unwrap_or_default();
+        for pair in rules {
+            let name = pair.key.as_str().unwrap_or_default();
+            let enabled = match pair.value.as_str() {
+                Some("on") | Some("true") => true,
+                Some("off") | Some("false") => false,
+                _ => {
+                    return Err(OpenapiSchemerError::Lint(format!(
+                        "Expected `on` or `off` for lint rule `{}`",
+                        name
+                    )))
+                }
+            };
+            self.set(name, enabled)?;
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, name: &str, enabled: bool) -> Result<(), OpenapiSchemerError> {
+        if !self.known.iter().any(|known| known == name) {
+            return Err(OpenapiSchemerError::Lint(format!(
+                "Unknown lint rule `{}`",
+                name
+            )));
+        }
+        self.enabled.retain(|enabled| enabled != name);
+        if enabled {
+            self.enabled.push(name.to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintResult {
+    diagnostics: Vec<LintDiagnostic>,
+    /// The problems `--fix` fixed, left out of `diagnostics`.
+    fixed: Vec<LintDiagnostic>,
+    /// The files changed by `--fix`, written by `write`.
+    files: Vec<FileEdits>,
+}
+
+impl LintResult {
+    pub fn new(diagnostics: Vec<LintDiagnostic>) -> LintResult {
+        LintResult {
+            diagnostics,
+            fixed: vec![],
+            files: vec![],
+        }
+    }
+
+    /// Writes the files changed by `--fix` in place.
+    pub fn write(&self) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            write_file(&file.file, &file.content).map_err(OpenapiSchemerError::Lint)?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(diagnostic: &LintDiagnostic, severity: &str) -> String {
+    format!(
+        "{}:{}:{}: {}: {} [{}]",
+        diagnostic.file.display(),
+        diagnostic.line,
+        diagnostic.column,
+        severity,
+        diagnostic.message,
+        diagnostic.rule
+    )
+}
+
+impl Display for LintResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines: Vec<String> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| describe(diagnostic, &diagnostic.severity.to_string()))
+            .collect();
+        lines.extend(
+            self.fixed
+                .iter()
+                .map(|diagnostic| describe(diagnostic, "fixed")),
+        );
+        if !self.fixed.is_empty() {
+            lines.push(format!(
+                "Fixed {} {} in {} {}",
+                self.fixed.len(),
+                if self.fixed.len() == 1 {
+                    "problem"
+                } else {
+                    "problems"
+                },
+                self.files.len(),
+                if self.files.len() == 1 {
+                    "file"
+                } else {
+                    "files"
+                }
+            ));
+        }
+        if lines.is_empty() {
+            return write!(f, "No problems found");
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for LintResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.diagnostics
+                .iter()
+                .chain(&self.fixed)
+                .map(|diagnostic| {
+                    YamlNode::mapping(vec![
+                        ("rule", YamlNode::string(&diagnostic.rule)),
+                        (
+                            "severity",
+                            YamlNode::string(&diagnostic.severity.to_string()),
+                        ),
+                        (
+                            "file",
+                            YamlNode::string(&diagnostic.file.display().to_string()),
+                        ),
+                        ("line", YamlNode::integer(diagnostic.line)),
+                        ("column", YamlNode::integer(diagnostic.column)),
+                        ("message", YamlNode::string(&diagnostic.message)),
+                        ("fixable", YamlNode::boolean(diagnostic.fixable)),
+                        ("fixed", YamlNode::boolean(self.fixed.contains(diagnostic))),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Reads a Spectral style ruleset, see `Ruleset`.
+pub fn read_ruleset(path: &Path) -> Result<Ruleset, OpenapiSchemerError> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read ruleset `{}`", path.display()))
+        .and_then(|content| Ruleset::parse(&content))
+        .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::Lint))
+}
+
+/// Lints the spec. With `fix` the problems that can be fixed safely are fixed
+/// in the returned result, which `LintResult::write` writes out. A fix that
+/// overlaps an earlier one is only made by running again.
+pub fn lint<T: LintParser>(
+    parser: T,
+    config: &LintConfig,
+    fix: bool,
+) -> Result<LintResult, OpenapiSchemerError> {
+    let diagnostics = parser.get_lint_diagnostics(&config.enabled)?;
+    if !fix {
+        return Ok(LintResult::new(diagnostics));
+    }
+    let files = parser.get_lint_fixes(&config.enabled)?;
+    let (fixed, diagnostics) = diagnostics
+        .into_iter()
+        .partition(|diagnostic| diagnostic.fixable);
+    Ok(LintResult {
+        diagnostics,
+        fixed,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::lint::Severity;
+
+    use super::*;
+
+    struct MockParser;
+    impl LintParser for MockParser {
+        fn get_lint_diagnostics(
+            &self,
+            enabled: &[String],
+        ) -> Result<Vec<LintDiagnostic>, OpenapiSchemerError> {
+            Ok(enabled
+                .iter()
+                .map(|rule| LintDiagnostic {
+                    rule: rule.to_owned(),
+                    severity: Severity::Warning,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 4,
+                    column: 7,
+                    message: "Problem".to_string(),
+                    fixable: rule == "nullable-style",
+                })
+                .collect())
+        }
+
+        fn get_lint_fixes(
+            &self,
+            enabled: &[String],
+        ) -> Result<Vec<FileEdits>, OpenapiSchemerError> {
+            Ok(enabled
+                .iter()
+                .filter(|rule| *rule == "nullable-style")
+                .map(|_| FileEdits {
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    edits: vec![],
+                    content: "openapi: 3.1.0\n".to_string(),
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_config() -> Result<(), Box<dyn Error>> {
+        let mut config = LintConfig::new(
+            &[],
+            None,
+            None,
+            vec![],
+            vec!["operation-summary".to_string()],
+        )?;
+        assert!(!config.enabled.contains(&"operation-summary".to_string()));
+        assert!(config.enabled.contains(&"no-empty-description".to_string()));
+
+        config.apply_config("rules:\n  no-empty-description: off\n  operation-summary: on\n")?;
+        assert!(config.enabled.contains(&"operation-summary".to_string()));
+        assert!(!config.enabled.contains(&"no-empty-description".to_string()));
+
+        let error =
+            LintConfig::new(&[], None, None, vec!["missing".to_string()], vec![]).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown lint rule `missing`");
+
+        let ruleset = Ruleset::parse(
+            "rules:\n  operation-summary: off\n  info-title:\n    severity: off\n    given: $.info\n    then:\n      field: title\n      function: truthy\n",
+        )?;
+        let config = LintConfig::new(&[], Some(&ruleset), None, vec![], vec![])?;
+        assert!(!config.enabled.contains(&"operation-summary".to_string()));
+        assert!(!config.enabled.contains(&"info-title".to_string()));
+        let config = LintConfig::new(
+            &[],
+            Some(&ruleset),
+            None,
+            vec!["info-title".to_string()],
+            vec![],
+        )?;
+        assert!(config.enabled.contains(&"info-title".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lint() -> Result<(), Box<dyn Error>> {
+        let config = LintConfig {
+            enabled: vec!["operation-summary".to_string()],
+            known: vec![],
+        };
+        assert_eq!(
+            lint(MockParser, &config, false)?.to_string(),
+            "/spec/openapi.yaml:4:7: warning: Problem [operation-summary]"
+        );
+        let config = LintConfig {
+            enabled: vec![],
+            known: vec![],
+        };
+        assert_eq!(
+            lint(MockParser, &config, false)?.to_string(),
+            "No problems found"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lint_fix() -> Result<(), Box<dyn Error>> {
+        let config = LintConfig {
+            enabled: vec![
+                "operation-summary".to_string(),
+                "nullable-style".to_string(),
+            ],
+            known: vec![],
+        };
+        let result = lint(MockParser, &config, true)?;
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(
+            result.to_string(),
+            "/spec/openapi.yaml:4:7: warning: Problem [operation-summary]\n/spec/openapi.yaml:4:7: fixed: Problem [nullable-style]\nFixed 1 problem in 1 file"
+        );
+        Ok(())
+    }
+}
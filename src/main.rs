@@ -1,32 +1,422 @@
-use bindings::{
-    operation::TreeSitterOperationParser, path::TreeSitterPathParser,
-    schema::TreeSitterSchemaParser,
-};
-use clap::{Args, Parser, Subcommand};
-use content::ContentProviderMap;
+use std::{ops::ControlFlow, process::ExitCode, time::Duration};
 
-mod bindings;
-mod content;
-mod error;
-mod operation;
-mod path;
-mod schema;
+use clap::{Args, CommandFactory, ErrorKind, Parser, Subcommand};
+use clap_complete::Shell;
+use openapi_schemer::{
+    bindings::{
+        browse::TreeSitterBrowseParser,
+        bundle::{BundleOptions, RefRewrite, TreeSitterBundler},
+        callback::TreeSitterCallbackParser,
+        content_type::TreeSitterContentTypeParser,
+        contract::TreeSitterContractChecker,
+        convert::TreeSitterConvertParser,
+        dedupe::TreeSitterDeduplicator,
+        deprecated::TreeSitterDeprecationParser,
+        diff::TreeSitterDiffParser,
+        docs::TreeSitterDocsParser,
+        example::TreeSitterExampleParser,
+        extension::TreeSitterExtensionParser,
+        filter::{FilterOptions, TreeSitterFilterer},
+        flatten::TreeSitterFlattener,
+        generate::TreeSitterGenerateParser,
+        get::TreeSitterGetParser,
+        header::TreeSitterHeaderParser,
+        keys::TreeSitterKeyParser,
+        link::TreeSitterLinkParser,
+        lint::TreeSitterLintParser,
+        operation::TreeSitterOperationParser,
+        overlay::TreeSitterOverlayApplier,
+        parameter::TreeSitterParameterParser,
+        path::TreeSitterPathParser,
+        query::TreeSitterQueryParser,
+        refs::TreeSitterRefParser,
+        rename::{
+            PathRename, TreeSitterOperationRenamer, TreeSitterPathRenamer, TreeSitterSchemaRenamer,
+        },
+        response::TreeSitterResponseParser,
+        schema::TreeSitterSchemaParser,
+        search::TreeSitterSearchParser,
+        security::TreeSitterSecurityParser,
+        serve::TreeSitterServeParser,
+        server::TreeSitterServerParser,
+        split::TreeSitterSplitter,
+        stats::TreeSitterStatsParser,
+        tag::TreeSitterTagParser,
+        unused::TreeSitterUnusedParser,
+        upgrade::TreeSitterUpgrader,
+        validate::TreeSitterValidationParser,
+        version::TreeSitterVersionParser,
+        webhook::TreeSitterWebhookParser,
+        OperationFilter,
+    },
+    browse, bundle,
+    complete::{self, CompletionKind},
+    config::Config,
+    conformance,
+    content::{watch_files, ContentProvider, ContentProviderMap, ParseCache},
+    convert::{self, ConvertFormat},
+    dedupe, deprecated, diff, docs,
+    error::{ErrorFormat, OpenapiSchemerError},
+    example,
+    export::{self, HttpFileSplit},
+    extension, filter, flatten, generate, get, header, keys, link, lint,
+    operation::{self, InvokeOptions, ResponsesLayout},
+    output::{
+        write_file, ListFormatter, ListWriter, OutputFormat, OutputFormatter, SortOrder, SortedList,
+    },
+    overlay, parameter, path, query, refs, response,
+    schema::{self, ComponentSection},
+    search, security, serve, server, split, stats, tag, unused, upgrade, validate, version,
+    webhook, Spec,
+};
 
 #[derive(Parser, Debug)]
+#[clap(
+    subcommand_negates_reqs = true,
+    override_usage = "openapi-schemer [OPTIONS] <INPUT> <SUBCOMMAND>\n    openapi-schemer completions <SHELL>"
+)]
 struct Cli {
     #[clap(parse(from_os_str), value_name = "INPUT", value_hint = clap::ValueHint::DirPath, required = true)]
     input: Option<std::path::PathBuf>,
 
+    /// Where refs are resolved from when the spec is read from stdin with `-`
+    #[clap(long, value_name = "DIR", global = true, default_value = ".")]
+    base_dir: std::path::PathBuf,
+
+    /// How results are printed [default: plain]
+    #[clap(long, value_enum, global = true)]
+    format: Option<OutputFormat>,
+
+    /// The order list commands print their entries in
+    #[clap(long, value_enum, global = true, default_value = "document")]
+    sort: SortOrder,
+
+    /// How errors are printed to stderr [default: plain]
+    #[clap(long, value_enum, global = true)]
+    error_format: Option<ErrorFormat>,
+
+    /// Don't read defaults from a `.openapi-schemer.toml` next to or above
+    /// INPUT
+    #[clap(long, global = true)]
+    no_config: bool,
+
+    /// Don't print errors, only exit with their exit code
+    #[clap(short, long, global = true)]
+    quiet: bool,
+
+    /// Run the command again whenever a file of the spec changes
+    #[clap(short, long, global = true)]
+    watch: bool,
+
     #[clap(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// Browse paths, operations and schemas in the terminal and print the
+    /// name picked with Enter
+    Browse,
+    /// Inline every external $ref into a single document
+    Bundle(Bundle),
+    /// Write Markdown release notes of what changed in the spec since an
+    /// older version of it
+    Changelog(Changelog),
+    /// Print a completion script for a shell
+    Completions {
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+    /// List the names completion scripts offer for an argument
+    #[clap(name = "__complete", hide = true)]
+    Complete {
+        #[clap(value_enum)]
+        kind: CompletionKind,
+    },
+    /// Check recorded HTTP traffic against the spec
+    Conformance(Conformance),
+    /// Write the spec as JSON or YAML, keeping its keys in order
+    Convert(Convert),
+    /// Count the requests in a HAR file sent to each operation of the spec
+    Coverage(Coverage),
+    /// Find object schemas that are identical apart from their key order and
+    /// documentation, and optionally consolidate them into one component
+    Dedupe(Dedupe),
+    Deprecated(Deprecated),
+    /// Compare the spec against a newer version of it
+    Diff(Diff),
+    Docs(Docs),
+    Example(Example),
+    Export(Export),
+    Extensions(Extensions),
+    /// Remove the operations with the given tags or extensions, for example
+    /// to publish a spec without its internal operations
+    Filter(Filter),
+    /// Move the inline schemas of operations into `components/schemas`
+    Flatten(Flatten),
+    Generate(Generate),
+    /// Print the node at a JSON pointer, following the refs on the way
+    Get(Get),
+    Header(Header),
+    /// List the keys below a dotted path such as `components.schemas`,
+    /// following refs on the way
+    Keys(Keys),
+    /// Check the spec against a configurable set of style rules
+    Lint(Lint),
+    Link(Link),
     #[clap(arg_required_else_help = true)]
     Operation(Operation),
+    Overlay(Overlay),
+    Parameter(Parameter),
     Path(Path),
+    /// Run a tree-sitter query against the source of the spec and print its
+    /// captures
+    Query(Query),
+    Refs(Refs),
+    Response(Response),
     Schema(Schema),
+    /// Search operationIds, paths, schema names, parameter names and
+    /// descriptions across the files of the spec
+    Search {
+        /// The text to look for. Names match fuzzily, descriptions have to
+        /// contain every word
+        query: String,
+    },
+    Security(Security),
+    /// Answer requests with the examples and schemas of the spec's responses
+    Serve(Serve),
+    Server(Server),
+    /// Write each path item and component schema of a single-file spec into a
+    /// file of its own, referred to from the root document
+    Split(Split),
+    /// Count the paths, operations, schemas, refs and files of the spec
+    Stats,
+    Tag(Tag),
+    /// Find components under `components` that nothing refers to
+    Unused(Unused),
+    /// Convert a Swagger 2.0 spec to OpenAPI 3.0
+    Upgrade(Upgrade),
+    /// Check the spec against the OpenAPI 3.0 or 3.1 structure, or a request
+    /// or response against the spec
+    Validate(Validate),
+    Version(Version),
+    /// Validate the spec whenever one of its files changes and run a command
+    /// each time it is valid
+    Watch(WatchSpec),
+    Webhook(Webhook),
+}
+
+#[derive(Debug, Args)]
+struct Bundle {
+    /// Write the bundled document to this file instead of stdout
+    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+
+    /// Replace every ref by a copy of its target, internal refs included, up
+    /// to this many refs deep
+    #[clap(long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+
+    /// Leave refs within the file they are written in out of --max-depth
+    #[clap(long, requires = "max-depth")]
+    keep_internal_refs: bool,
+
+    /// Where the targets of refs to other files are written
+    #[clap(long, value_enum, value_name = "MODE", default_value = "inline")]
+    rewrite_refs_to: RefRewrite,
+
+    /// Mark each inlined block with a `# from: file:line` comment in YAML
+    /// output
+    #[clap(long)]
+    provenance: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Conformance {
+    #[clap(subcommand)]
+    command: ConformanceCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConformanceCommands {
+    /// Check the status, headers and body of every response in a HAR file
+    /// against its operation, and report the operations never exercised
+    #[clap(arg_required_else_help = true)]
+    Check {
+        /// The HAR file, as exported by browsers and proxies
+        #[clap(parse(from_os_str))]
+        har: std::path::PathBuf,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Convert {
+    /// The format to write the spec in
+    #[clap(long, value_enum, value_name = "FORMAT")]
+    to: ConvertFormat,
+
+    /// Convert the whole spec bundled into one document instead of only INPUT
+    #[clap(long)]
+    bundle: bool,
+
+    /// Write the converted document to this file instead of stdout
+    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct Changelog {
+    /// The older version, either a spec file or a git revision of INPUT such
+    /// as `v1.2.0`
+    #[clap(long, value_name = "REV_OR_FILE")]
+    since: String,
+}
+
+#[derive(Debug, Args)]
+struct Diff {
+    /// The spec to compare INPUT against
+    #[clap(
+        parse(from_os_str),
+        value_name = "NEW",
+        required_unless_present = "git"
+    )]
+    new: Option<std::path::PathBuf>,
+
+    /// Compare INPUT as it was at this git revision, such as `HEAD~1`,
+    /// against INPUT as it is now
+    #[clap(long, value_name = "REV", conflicts_with = "new")]
+    git: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Deprecated {
+    #[clap(subcommand)]
+    command: DeprecatedCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum DeprecatedCommands {
+    /// List the operations, parameters and schemas marked `deprecated: true`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+        /// Fail when an operation that is not deprecated uses a deprecated
+        /// schema
+        #[clap(long)]
+        fail_if_used: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Docs {
+    #[clap(subcommand)]
+    command: DocsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum DocsCommands {
+    /// Write a standalone HTML page documenting the paths, operations,
+    /// schemas and examples of the spec
+    Html {
+        /// Write the page to this file instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Example {
+    #[clap(subcommand)]
+    command: ExampleCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ExampleCommands {
+    /// List the named examples of components, media types and parameters
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show an example with its `$ref` followed
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// Where the example is defined, as printed by `example list`
+        pointer: String,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Header {
+    #[clap(subcommand)]
+    command: HeaderCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum HeaderCommands {
+    /// List the reusable headers under `components/headers`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show the definition of a reusable header
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the header under `components/headers`
+        name: String,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Lint {
+    /// A YAML file with a `rules` mapping of rule names to `on` or `off`
+    #[clap(long, parse(from_os_str), value_name = "FILE")]
+    config: Option<std::path::PathBuf>,
+    /// A Spectral style ruleset whose `rules` turn rules on or off or define
+    /// new ones with `given`, `then` and `severity`
+    #[clap(long, parse(from_os_str), value_name = "FILE")]
+    ruleset: Option<std::path::PathBuf>,
+    /// Enable a rule, overriding the config file
+    #[clap(long, value_name = "RULE")]
+    enable: Vec<String>,
+    /// Disable a rule, overriding the config file
+    #[clap(long, value_name = "RULE")]
+    disable: Vec<String>,
+    /// Rewrite the files of the spec in place to fix the problems that can
+    /// be fixed safely
+    #[clap(long)]
+    fix: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Link {
+    #[clap(subcommand)]
+    command: LinkCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum LinkCommands {
+    /// List the reusable links under `components/links` and the operations
+    /// they point at
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show the definition of a reusable link
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the link under `components/links`
+        name: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -39,7 +429,163 @@ struct Operation {
 #[derive(Debug, Subcommand)]
 enum OperationCommands {
     /// List the operations for a spec
-    List,
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+        /// Only print the operationId of each operation
+        #[clap(long)]
+        ids_only: bool,
+        /// Fail when an operation has no operationId instead of listing it
+        #[clap(long)]
+        require_operation_id: bool,
+        /// Only list operations under paths matching this glob, e.g. `/pets/*`
+        #[clap(long, value_name = "GLOB")]
+        path: Option<String>,
+        /// Only list operations for this HTTP method
+        #[clap(long)]
+        method: Option<String>,
+        /// Only list operations with this tag
+        #[clap(long)]
+        tag: Option<String>,
+    },
+    /// Show the resolved definition of an operation
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The operationId of the operation to show
+        operation_id: String,
+    },
+    /// List the callbacks an operation declares and their operations
+    #[clap(arg_required_else_help = true)]
+    Callbacks {
+        /// The operationId of the operation
+        operation_id: String,
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// List the media types of the request body and responses of every
+    /// operation, flagging operations without `application/json` or with
+    /// bodies that offer different media types
+    ContentTypes {
+        /// Prefix each operation with the file, line and column it is defined
+        /// at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// List the response codes of every operation, or compare them in a
+    /// matrix. `--format csv` and `--format tsv` always print the matrix
+    Responses {
+        /// Print a table of operations against every response code used in
+        /// the spec
+        #[clap(long)]
+        matrix: bool,
+        /// Prefix each operation with the file, line and column it is defined
+        /// at
+        #[clap(long, conflicts_with = "matrix")]
+        locations: bool,
+    },
+    /// Show the security requirements that apply to an operation, with the
+    /// schemes and scopes each one needs
+    #[clap(arg_required_else_help = true)]
+    Security {
+        /// The operationId of the operation
+        #[clap(required_unless_present = "all", conflicts_with = "all")]
+        operation_id: Option<String>,
+        /// Show the requirements of every operation
+        #[clap(long)]
+        all: bool,
+    },
+    /// Print curl command lines for an operation
+    #[clap(arg_required_else_help = true)]
+    Curl {
+        /// The operationId of the operation
+        #[clap(required_unless_present = "all", conflicts_with = "all")]
+        operation_id: Option<String>,
+        /// Print a command for every operation with an operationId
+        #[clap(long)]
+        all: bool,
+        /// The base URL to use instead of the spec's servers
+        #[clap(long, value_name = "URL")]
+        server: Option<String>,
+        /// A path, query, header or cookie parameter, left as a `{name}`
+        /// placeholder otherwise
+        #[clap(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+    },
+    /// Send a request for an operation and print the response
+    #[clap(arg_required_else_help = true)]
+    Invoke {
+        /// The operationId of the operation to send
+        operation_id: String,
+        /// The http:// base URL to send to instead of the spec's servers
+        #[clap(long, value_name = "URL")]
+        server: Option<String>,
+        /// A path, query, header or cookie parameter
+        #[clap(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// The request body, instead of one scaffolded from the spec
+        #[clap(long)]
+        body: Option<String>,
+        /// Print the status line and headers of the response
+        #[clap(short, long)]
+        include: bool,
+    },
+    /// Rename an operationId along with the links and callbacks that use it
+    #[clap(arg_required_else_help = true)]
+    Rename {
+        /// The operationId of the operation to rename
+        operation_id: String,
+        /// The operationId to rename it to
+        new_operation_id: String,
+        /// Print the files and lines that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Overlay {
+    #[clap(subcommand)]
+    command: OverlayCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum OverlayCommands {
+    /// Apply the actions of an OpenAPI Overlay document to the spec
+    Apply {
+        /// The overlay document
+        #[clap(parse(from_os_str), value_name = "OVERLAY")]
+        overlay: std::path::PathBuf,
+
+        /// Write the resulting document to this file instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Parameter {
+    #[clap(subcommand)]
+    command: ParameterCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ParameterCommands {
+    /// List the reusable parameters under `components/parameters`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show the definition of a reusable parameter
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the parameter under `components/parameters`
+        name: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -52,7 +598,97 @@ struct Path {
 #[derive(Debug, Subcommand)]
 enum PathCommands {
     /// List the paths for a spec
-    List,
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+        /// Show each path as a tree of its methods and their operationIds
+        #[clap(long, conflicts_with = "locations")]
+        tree: bool,
+    },
+    /// Find the paths a concrete URL maps to and the values of their parameters
+    #[clap(arg_required_else_help = true)]
+    Match {
+        /// The URL or path, e.g. `/pets/123/toys/9`
+        url: String,
+    },
+    /// Show the methods defined for a path
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The path as written under `paths`, e.g. `/pets/{petId}`
+        path: String,
+    },
+    /// Rename a path, or prefix every path, along with the refs pointing into it
+    #[clap(arg_required_else_help = true)]
+    Rename {
+        /// The path as written under `paths`, e.g. `/pets/{petId}`
+        #[clap(required_unless_present = "add-prefix", requires = "new-path")]
+        path: Option<String>,
+        /// The path to rename it to
+        new_path: Option<String>,
+        /// Put a prefix in front of every path instead, e.g. `/v2`
+        #[clap(long, value_name = "PREFIX", conflicts_with = "path")]
+        add_prefix: Option<String>,
+        /// Print the files and lines that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+        /// Write the changed files under DIR instead of in place, at the same
+        /// place relative to the spec
+        #[clap(long, value_name = "DIR", conflicts_with = "dry-run")]
+        output_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Refs {
+    #[clap(subcommand)]
+    command: RefsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum RefsCommands {
+    /// List every `$ref` with where it is written and what it points at
+    List {
+        /// Print the references as a Graphviz digraph
+        #[clap(long)]
+        dot: bool,
+    },
+    /// Report every `$ref` that points at a missing file or key
+    Check,
+    /// List every `$ref` that points at a node, however its path is written
+    To {
+        /// The node, as a pointer such as `#/components/schemas/Pet`
+        pointer: String,
+    },
+    /// Print where the `$ref` at a position points, for jump-to-definition
+    Resolve {
+        /// The position of the `$ref`, as `file:line:column`
+        position: String,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Response {
+    #[clap(subcommand)]
+    command: ResponseCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ResponseCommands {
+    /// List the reusable responses under `components/responses`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show the definition of a reusable response
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the response under `components/responses`
+        name: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -64,51 +700,1649 @@ struct Schema {
 
 #[derive(Debug, Subcommand)]
 enum SchemaCommands {
-    /// List the schemas
-    List,
+    /// List the schemas, or the entries of another section of `components`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+        /// The section of `components` to list
+        #[clap(long, value_enum, default_value = "schemas")]
+        section: ComponentSection,
+        /// Show each schema as a tree of its properties and their types
+        #[clap(long, conflicts_with = "locations")]
+        tree: bool,
+    },
+    /// Show the definition of a schema
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the schema under `components/schemas`
+        name: String,
+        /// Replace every `$ref` in the schema with its target
+        #[clap(long)]
+        resolve: bool,
+    },
+    /// Show a schema with the components it refers to inlined, optionally
+    /// merging `allOf` into the shape consumers must satisfy
+    #[clap(arg_required_else_help = true)]
+    Resolve {
+        /// The name of the schema under `components/schemas`
+        name: String,
+        /// Merge the branches of every `allOf` and annotate each `oneOf` and
+        /// `anyOf` variant with the component it is
+        #[clap(long)]
+        merge_allof: bool,
+    },
+    /// List the properties of a schema with their type, format and whether
+    /// they are required or nullable, following refs and `allOf`
+    #[clap(arg_required_else_help = true)]
+    Properties {
+        /// The name of the schema under `components/schemas`
+        name: String,
+    },
+    /// List the operations that refer to a schema, directly or through
+    /// other refs
+    #[clap(arg_required_else_help = true)]
+    Usages {
+        /// The name of the schema under `components/schemas`
+        name: String,
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// List the values allowed by the `enum` and `const` of every schema
+    /// and its properties
+    Enums {
+        /// Only list the enums of this schema, following its refs
+        name: Option<String>,
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Report schemas whose `required` lists undeclared properties, that
+    /// require none of their properties, or that require `readOnly`
+    /// properties in a request body
+    AuditRequired {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Write a component schema as a standalone JSON Schema (draft
+    /// 2020-12) document, with the components it refers to under `$defs`
+    #[clap(arg_required_else_help = true)]
+    ExportJsonschema {
+        /// The name of the schema under `components/schemas`
+        #[clap(required_unless_present = "all")]
+        name: Option<String>,
+        /// Export every component schema
+        #[clap(long, conflicts_with = "name")]
+        all: bool,
+        /// Write the document to this file, or with `--all` a `Name.json`
+        /// document per schema into this directory, instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Rename a schema along with every $ref to it
+    #[clap(arg_required_else_help = true)]
+    Rename {
+        /// The name of the schema under `components/schemas`
+        name: String,
+        /// The name to rename it to
+        new_name: String,
+        /// Print the files and lines that would change without writing them
+        #[clap(long)]
+        dry_run: bool,
+    },
 }
 
-fn main() {
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Security {
+    #[clap(subcommand)]
+    command: SecurityCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SecurityCommands {
+    /// List the security schemes under `components/securitySchemes`
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Report operations that have no security requirement
+    Audit {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// List the scopes of every oauth2 scheme with the operations requiring
+    /// them, flagging scopes that are never required or never declared
+    Scopes {
+        /// Prefix each scope with the file, line and column it is declared at
+        #[clap(long)]
+        locations: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Serve {
+    /// The port to listen on at 127.0.0.1, or 0 for any free port
+    #[clap(short, long, default_value = "8080")]
+    port: u16,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Server {
+    #[clap(subcommand)]
+    command: ServerCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ServerCommands {
+    /// List the top level servers and any path or operation overrides
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Tag {
+    #[clap(subcommand)]
+    command: TagCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum TagCommands {
+    /// List the tags that are declared or used by operations
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+    /// Show a tag's description and the operations that use it
+    #[clap(arg_required_else_help = true)]
+    Show {
+        /// The name of the tag
+        name: String,
+    },
+    /// List the operations annotated with a tag
+    #[clap(arg_required_else_help = true)]
+    Operations {
+        /// The name of the tag
+        name: String,
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Unused {
+    /// Prefix each entry with the file, line and column it is defined at
+    #[clap(long)]
+    locations: bool,
+}
+
+#[derive(Debug, Args)]
+struct Coverage {
+    /// The HAR file with the recorded traffic, as exported by browsers and
+    /// proxies
+    #[clap(long, parse(from_os_str), value_name = "FILE")]
+    har: std::path::PathBuf,
+    /// Fail when less than this percentage of the operations was exercised,
+    /// from 0 to 100
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(0..=100)
+    )]
+    min_coverage: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+struct Dedupe {
+    /// Print the spec with each group of identical schemas replaced by refs
+    /// to a single component schema. Refs to the removed schemas in the
+    /// other files of the spec are rewritten in place
+    #[clap(long)]
+    apply: bool,
+
+    /// Write the deduplicated document to this file instead of stdout
+    #[clap(
+        short,
+        long,
+        parse(from_os_str),
+        value_name = "FILE",
+        requires = "apply"
+    )]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Export {
+    #[clap(subcommand)]
+    command: ExportCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ExportCommands {
+    /// Write requests for every operation with an operationId as a `.http`
+    /// file for the HTTP clients of VS Code and JetBrains IDEs
+    Httpfile {
+        /// Write one file per tag or per operation into the directory given
+        /// with `--output`
+        #[clap(long, value_enum, requires = "output")]
+        split: Option<HttpFileSplit>,
+        /// The base URL to use instead of the spec's servers
+        #[clap(long, value_name = "URL")]
+        server: Option<String>,
+        /// Write the requests to this file, or with `--split` this directory,
+        /// instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Write the component schemas as GraphQL types, and optionally the
+    /// operations as `Query` and `Mutation` fields
+    Graphql {
+        /// Add GET operations as `Query` fields and POST operations as
+        /// `Mutation` fields
+        #[clap(long)]
+        operations: bool,
+        /// Write the definitions to this file instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Write the component schemas as proto3 messages and enums, best-effort
+    /// for `oneOf`, `allOf` and maps
+    Proto {
+        /// The package the definitions are declared in, such as `petstore.v1`
+        #[clap(long, value_name = "PACKAGE")]
+        package: Option<String>,
+        /// Write the definitions to this file instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Flatten {
+    /// Write the flattened document to this file instead of stdout
+    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct Query {
+    /// The query as an s-expression, such as
+    /// `(block_mapping_pair key: (flow_node) @key)`
+    #[clap(
+        long,
+        value_name = "QUERY",
+        required_unless_present = "file",
+        conflicts_with = "file"
+    )]
+    query: Option<String>,
+    /// Read the query from a `.scm` file instead
+    #[clap(long, parse(from_os_str), value_name = "FILE")]
+    file: Option<std::path::PathBuf>,
+    /// Query every file the spec refers to, not only the root document
+    #[clap(long)]
+    all_files: bool,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Extensions {
+    #[clap(subcommand)]
+    command: ExtensionsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ExtensionsCommands {
+    /// List the vendor extensions (`x-` keys) of every file of the spec,
+    /// grouped by key with their locations and values
+    List {
+        /// Only list the extensions with this key, such as `x-internal`
+        #[clap(long)]
+        key: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Filter {
+    /// Remove the operations with this tag, and the tag itself
+    #[clap(long = "drop-tag", value_name = "TAG")]
+    drop_tags: Vec<String>,
+
+    /// Remove the operations and path items that set this extension to
+    /// anything but `false`
+    #[clap(long = "drop-ext", value_name = "KEY")]
+    drop_extensions: Vec<String>,
+
+    /// Write the filtered document to this file instead of stdout
+    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Generate {
+    #[clap(subcommand)]
+    command: GenerateCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum GenerateCommands {
+    /// Render a client from the templates of a directory, once for the
+    /// spec, for every operation (`operations/`) or for every schema
+    /// (`schemas/`)
+    Client {
+        /// The directory of Handlebars templates to render
+        #[clap(long, parse(from_os_str), value_name = "DIR")]
+        template: std::path::PathBuf,
+        /// The directory to write the rendered files into
+        #[clap(short, long, parse(from_os_str), value_name = "DIR")]
+        output: std::path::PathBuf,
+    },
+    /// Write TypeScript declarations for every component schema
+    Typescript {
+        /// Write the declarations to this file, such as `types.d.ts`,
+        /// instead of stdout
+        #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Get {
+    /// A JSON pointer such as `/paths/~1pets/get`, or `file#/pointer` for
+    /// another file of the spec
+    pointer: String,
+    /// Replace the refs below the node with what they point at
+    #[clap(long)]
+    inline: bool,
+}
+
+#[derive(Debug, Args)]
+struct Keys {
+    /// The keys leading to a mapping or list, separated by dots. Leave it out
+    /// for the keys of the root document
+    #[clap(default_value = "")]
+    path: String,
+    /// Prefix each entry with the file, line and column it is defined at
+    #[clap(long)]
+    locations: bool,
+}
+
+#[derive(Debug, Args)]
+struct Split {
+    /// The directory to write the files into, the root document at its top
+    #[clap(parse(from_os_str), value_name = "DIR")]
+    directory: std::path::PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct Upgrade {
+    /// Write the upgraded document to this file instead of stdout
+    #[clap(short, long, parse(from_os_str), value_name = "FILE")]
+    output: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Validate {
+    #[clap(subcommand)]
+    command: Option<ValidateCommands>,
+    /// Also check the examples of media types, parameters, headers and
+    /// schemas against their schemas
+    #[clap(long)]
+    examples: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum ValidateCommands {
+    /// Check a request's parameters and body against an operation
+    #[clap(arg_required_else_help = true)]
+    Request {
+        /// The operationId of the operation the request was sent to
+        #[clap(long, value_name = "ID")]
+        operation: String,
+        /// A path, query, header or cookie parameter
+        #[clap(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// A file with the JSON or YAML body
+        #[clap(long, parse(from_os_str), value_name = "FILE")]
+        body: Option<std::path::PathBuf>,
+        /// The media type of the body, `application/json` by default
+        #[clap(long, value_name = "MEDIA_TYPE")]
+        content_type: Option<String>,
+    },
+    /// Check a response's status, headers and body against an operation
+    #[clap(arg_required_else_help = true)]
+    Response {
+        /// The operationId of the operation that answered
+        #[clap(long, value_name = "ID")]
+        operation: String,
+        /// The status code of the response
+        #[clap(long)]
+        status: u16,
+        /// A response header
+        #[clap(long = "header", value_name = "NAME=VALUE")]
+        headers: Vec<String>,
+        /// A file with the JSON or YAML body
+        #[clap(long, parse(from_os_str), value_name = "FILE")]
+        body: Option<std::path::PathBuf>,
+        /// The media type of the body, `application/json` by default
+        #[clap(long, value_name = "MEDIA_TYPE")]
+        content_type: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct WatchSpec {
+    /// The command to run through the shell while the spec is valid, such
+    /// as `make regen`
+    #[clap(long, value_name = "COMMAND")]
+    exec: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Version {
+    #[clap(subcommand)]
+    command: VersionCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum VersionCommands {
+    /// Suggest a major, minor or patch bump of `info.version` from the
+    /// changes since an older version of the spec
+    Suggest {
+        /// The older version, either a spec file or a git revision of INPUT
+        /// such as `v1.2.0`
+        #[clap(long, value_name = "REV_OR_FILE")]
+        since: String,
+        /// Write the suggested version into the `info.version` of INPUT
+        #[clap(long)]
+        apply: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+struct Webhook {
+    #[clap(subcommand)]
+    command: WebhookCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum WebhookCommands {
+    /// List the operations of the webhooks an OpenAPI 3.1 spec declares
+    List {
+        /// Prefix each entry with the file, line and column it is defined at
+        #[clap(long)]
+        locations: bool,
+    },
+}
+
+fn main() -> ExitCode {
     let args = Cli::parse();
+    if let Commands::Completions { shell } = args.command {
+        print!("{}", complete::script(shell, &Cli::command()));
+        return ExitCode::SUCCESS;
+    }
+
+    let quiet = args.quiet;
+    let config = match args.no_config {
+        true => Ok(Config::default()),
+        false => Config::discover(&config_directory(&args)),
+    };
+    let (result, error_format) = match config {
+        Ok(config) => {
+            let error_format = args.error_format.or(config.error_format);
+            let result = match args.watch {
+                true => watch(args, &config),
+                false => run(args, &config, None),
+            };
+            (result, error_format)
+        }
+        Err(err) => (Err(err), args.error_format),
+    };
+    let error_format = error_format.unwrap_or(ErrorFormat::Plain);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            if !quiet {
+                eprintln!("{}", err.format(error_format));
+            }
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+/// Runs the command each time a file of the spec changes. The parsed trees
+/// are kept between runs so changed files are re-parsed incrementally.
+fn watch(args: Cli, config: &Config) -> Result<(), OpenapiSchemerError> {
+    if let Commands::Watch(_) = args.command {
+        return Err(OpenapiSchemerError::Watch(
+            "`watch` already reruns its commands and can't be used with `--watch`".to_string(),
+        ));
+    }
+    let input = args.input.to_owned().unwrap_or_default();
+    if input == std::path::Path::new("-") {
+        return Err(OpenapiSchemerError::Watch(
+            "Can't watch a spec read from stdin".to_string(),
+        ));
+    }
+    let quiet = args.quiet;
+    let error_format = args.error_format.or(config.error_format);
+    let error_format = error_format.unwrap_or(ErrorFormat::Plain);
+    let mut cache = ParseCache::new();
+    let mut args = Some(args);
+    watch_files(Duration::from_millis(500), || {
+        // Clap can't clone the parsed arguments, so later runs parse them
+        // again from the command line.
+        let args = match args.take() {
+            Some(args) => args,
+            None => {
+                println!();
+                Cli::parse()
+            }
+        };
+        if let Err(err) = run(args, config, Some(&mut cache)) {
+            if !quiet {
+                eprintln!("{}", err.format(error_format));
+            }
+        }
+        let mut paths: Vec<std::path::PathBuf> = cache
+            .paths()
+            .into_iter()
+            .filter(|path| path.as_path() != std::path::Path::new("#"))
+            .cloned()
+            .collect();
+        paths.push(input.to_owned());
+        ControlFlow::Continue(paths)
+    });
+    Ok(())
+}
+
+/// The directory the config file is looked for from: the one INPUT is in, or
+/// `--base-dir` for a spec read from stdin.
+fn config_directory(args: &Cli) -> std::path::PathBuf {
+    let directory = match args.input.as_deref() {
+        Some(input) if input != std::path::Path::new("-") => match input.parent() {
+            Some(parent) if parent != std::path::Path::new("") => parent.to_path_buf(),
+            _ => std::path::PathBuf::from("."),
+        },
+        _ => args.base_dir.to_owned(),
+    };
+    directory.canonicalize().unwrap_or(directory)
+}
+
+/// Prints a list an item at a time through a buffered stdout, so the output of
+/// a huge spec starts before all of it is formatted. Output piped into a
+/// command that stops reading, such as `head`, ends quietly.
+fn print_list<T: ListFormatter>(result: T, format: OutputFormat) {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    let written = result
+        .write(format, &mut stdout)
+        .and_then(|_| std::io::Write::flush(&mut stdout));
+    check_printed(written);
+}
+
+/// Prints a list as `stream` finds its items, so neither the items nor their
+/// text are ever all in memory. Only for the formats `ListWriter` streams.
+fn stream_list(
+    format: OutputFormat,
+    stream: impl FnOnce(&mut ListWriter) -> Result<(), OpenapiSchemerError>,
+) -> Result<(), OpenapiSchemerError> {
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    let mut list = ListWriter::new(format, &mut stdout);
+    stream(&mut list)?;
+    let written = list
+        .finish()
+        .and_then(|_| std::io::Write::flush(&mut stdout));
+    check_printed(written);
+    Ok(())
+}
+
+/// Panics when printing failed, unless the output was piped into a command
+/// that stopped reading.
+fn check_printed(written: std::io::Result<()>) {
+    if let Err(err) = written {
+        if err.kind() != std::io::ErrorKind::BrokenPipe {
+            panic!("failed printing to stdout: {}", err);
+        }
+    }
+}
 
+/// Loads the spec at `input`, or from stdin when `input` is `-`. The files of
+/// a spec on disk are parsed through `cache` when one is given. Fails when the
+/// spec refers to other files and `config` forbids it.
+fn load(
+    input: std::path::PathBuf,
+    base_dir: &std::path::Path,
+    config: &Config,
+    cache: Option<&mut ParseCache>,
+) -> Result<ContentProviderMap, OpenapiSchemerError> {
+    let provider = match (input.to_str(), cache) {
+        (Some("-"), _) => ContentProviderMap::from_stdin(base_dir.to_path_buf()),
+        (_, Some(cache)) => ContentProviderMap::from_open_api_yaml_cached(input, cache),
+        (_, None) => ContentProviderMap::from_open_api_yaml(input),
+    }?;
+    config.check_refs(&provider)?;
+    Ok(provider)
+}
+
+/// Runs `command` through the shell, failing when it can't be started or
+/// exits unsuccessfully.
+fn run_command(command: &str) -> Result<(), OpenapiSchemerError> {
+    let (shell, flag) = match cfg!(windows) {
+        true => ("cmd", "/C"),
+        false => ("sh", "-c"),
+    };
+    let status = std::process::Command::new(shell)
+        .args([flag, command])
+        .status()
+        .map_err(|err| {
+            OpenapiSchemerError::Watch(format!("Could not run `{}`: {}", command, err))
+        })?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(OpenapiSchemerError::Watch(format!(
+            "`{}` failed with {}",
+            command, status
+        ))),
+    }
+}
+
+/// Loads INPUT as it was at the git `revision`.
+fn load_revision(
+    input: std::path::PathBuf,
+    revision: &str,
+    config: &Config,
+) -> Result<ContentProviderMap, OpenapiSchemerError> {
+    let provider = ContentProviderMap::from_git_revision(input, revision)?;
+    config.check_refs(&provider)?;
+    Ok(provider)
+}
+
+fn run(
+    args: Cli,
+    config: &Config,
+    mut cache: Option<&mut ParseCache>,
+) -> Result<(), OpenapiSchemerError> {
+    let base_dir = args.base_dir;
+    let format = args.format.or(config.format).unwrap_or(OutputFormat::Plain);
+    let error_format = args.error_format.or(config.error_format);
+    let error_format = error_format.unwrap_or(ErrorFormat::Plain);
+    let sort = args.sort;
+    let quiet = args.quiet;
     match args.input {
-        None => unreachable!("Clap requires input"),
-        Some(_) => match args.command {
+        // Only `completions` goes without INPUT and it never gets here.
+        None => Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "The following required arguments were not provided:\n    <INPUT>",
+            )
+            .exit(),
+        Some(input) => match args.command {
+            Commands::Browse => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterBrowseParser::new(Box::new(provider));
+                browse::browse(parser).map(|picked| {
+                    if let Some(name) = picked {
+                        println!("{}", name);
+                    }
+                })
+            }
+            Commands::Bundle(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let options = BundleOptions {
+                    max_depth: subcommand.max_depth,
+                    keep_internal_refs: subcommand.keep_internal_refs,
+                    rewrite_refs: subcommand.rewrite_refs_to,
+                    provenance: subcommand.provenance,
+                };
+                let bundler = TreeSitterBundler::new(Box::new(provider)).with_options(options);
+                bundle::bundle(bundler).and_then(|result| match subcommand.output {
+                    Some(output) => {
+                        let content = format!("{}\n", result.format(format));
+                        ::std::fs::write(&output, content)
+                            .map_err(|err| OpenapiSchemerError::Bundle(err.to_string()))
+                    }
+                    None => {
+                        println!("{}", result.format(format));
+                        Ok(())
+                    }
+                })
+            }
+            Commands::Changelog(subcommand) => {
+                let since = std::path::PathBuf::from(&subcommand.since);
+                let old_provider = match since.is_file() {
+                    true => load(since, &base_dir, config, cache.as_deref_mut())?,
+                    false => load_revision(input.to_owned(), &subcommand.since, config)?,
+                };
+                let new_provider = load(input, &base_dir, config, cache)?;
+                let old_parser = TreeSitterDiffParser::new(Box::new(old_provider));
+                let new_parser = TreeSitterDiffParser::new(Box::new(new_provider));
+                diff::changelog(old_parser, new_parser)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Conformance(subcommand) => match subcommand.command {
+                ConformanceCommands::Check { har } => {
+                    let entries =
+                        conformance::read_har(&har, OpenapiSchemerError::ConformanceCheck)?;
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterServeParser::new(Box::new(provider.clone()));
+                    let server_parser = TreeSitterServerParser::new(Box::new(provider.clone()));
+                    let checker = TreeSitterContractChecker::new(Box::new(provider));
+                    conformance::check(parser, server_parser, checker, entries)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Convert(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterConvertParser::new(Box::new(provider), subcommand.bundle);
+                convert::convert(parser, subcommand.to).and_then(|result| match subcommand.output {
+                    Some(output) => ::std::fs::write(&output, format!("{}\n", result))
+                        .map_err(|err| OpenapiSchemerError::Convert(err.to_string())),
+                    None => {
+                        println!("{}", result);
+                        Ok(())
+                    }
+                })
+            }
+            Commands::Completions { .. } => unreachable!("Handled before the spec is loaded"),
+            Commands::Complete { kind } => {
+                let provider = load(input, &base_dir, config, cache)?;
+                complete::complete(&Spec::new(provider), kind).map(|result| println!("{}", result))
+            }
+            Commands::Diff(subcommand) => {
+                let (old_provider, new_provider) = match (subcommand.git, subcommand.new) {
+                    (Some(revision), _) => {
+                        let old_provider = load_revision(input.to_owned(), &revision, config)?;
+                        (old_provider, load(input, &base_dir, config, cache)?)
+                    }
+                    (None, new) => (
+                        load(input, &base_dir, config, cache.as_deref_mut())?,
+                        load(new.unwrap_or_default(), &base_dir, config, cache)?,
+                    ),
+                };
+                let old_parser = TreeSitterDiffParser::new(Box::new(old_provider));
+                let new_parser = TreeSitterDiffParser::new(Box::new(new_provider));
+                diff::diff(old_parser, new_parser)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Deprecated(subcommand) => match subcommand.command {
+                DeprecatedCommands::List {
+                    locations,
+                    fail_if_used,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterDeprecationParser::new(Box::new(provider));
+                    deprecated::list(parser, locations, fail_if_used)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Docs(subcommand) => match subcommand.command {
+                DocsCommands::Html { output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterDocsParser::new(Box::new(provider));
+                    docs::html(parser).and_then(|result| match output {
+                        Some(output) => write_file(&output, &format!("{}\n", result))
+                            .map_err(OpenapiSchemerError::DocsHtml),
+                        None => {
+                            println!("{}", result);
+                            Ok(())
+                        }
+                    })
+                }
+            },
+            Commands::Example(subcommand) => match subcommand.command {
+                ExampleCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterExampleParser::new(Box::new(provider));
+                    example::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                ExampleCommands::Show { pointer } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterExampleParser::new(Box::new(provider));
+                    example::show(parser, &pointer)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Export(subcommand) => match subcommand.command {
+                ExportCommands::Httpfile {
+                    split,
+                    server,
+                    output,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let server_parser = TreeSitterServerParser::new(Box::new(provider.clone()));
+                    let security_parser = TreeSitterSecurityParser::new(Box::new(provider.clone()));
+                    let parser = TreeSitterOperationParser::new(Box::new(provider));
+                    export::httpfile(parser, server_parser, security_parser, split, server)
+                        .and_then(|result| match output {
+                            Some(output) => result.write(&output),
+                            None => {
+                                println!("{}", result);
+                                Ok(())
+                            }
+                        })
+                }
+                ExportCommands::Graphql { operations, output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let docs_parser = TreeSitterDocsParser::new(Box::new(provider.clone()));
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    export::graphql(parser, docs_parser, operations).and_then(|result| match output
+                    {
+                        Some(output) => write_file(&output, &format!("{}\n", result))
+                            .map_err(OpenapiSchemerError::ExportGraphql),
+                        None => {
+                            println!("{}", result);
+                            Ok(())
+                        }
+                    })
+                }
+                ExportCommands::Proto { package, output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    export::proto(parser, package).and_then(|result| match output {
+                        Some(output) => write_file(&output, &format!("{}\n", result))
+                            .map_err(OpenapiSchemerError::ExportProto),
+                        None => {
+                            println!("{}", result);
+                            Ok(())
+                        }
+                    })
+                }
+            },
+            Commands::Extensions(subcommand) => match subcommand.command {
+                ExtensionsCommands::List { key } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterExtensionParser::new(Box::new(provider));
+                    extension::list(parser, key.as_deref())
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Filter(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let options = FilterOptions {
+                    tags: subcommand.drop_tags,
+                    extensions: subcommand.drop_extensions,
+                };
+                let filterer = TreeSitterFilterer::new(Box::new(provider)).with_options(options);
+                filter::filter(filterer).and_then(|result| match subcommand.output {
+                    Some(output) => {
+                        let content = format!("{}\n", result.format(format));
+                        ::std::fs::write(&output, content)
+                            .map_err(|err| OpenapiSchemerError::Filter(err.to_string()))
+                    }
+                    None => {
+                        println!("{}", result.format(format));
+                        Ok(())
+                    }
+                })
+            }
+            Commands::Coverage(subcommand) => {
+                let entries =
+                    conformance::read_har(&subcommand.har, OpenapiSchemerError::Coverage)?;
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterServeParser::new(Box::new(provider.clone()));
+                let server_parser = TreeSitterServerParser::new(Box::new(provider));
+                conformance::coverage(parser, server_parser, entries).and_then(|result| {
+                    println!("{}", result.format(format));
+                    match subcommand.min_coverage {
+                        Some(minimum) => result.check_minimum(minimum),
+                        None => Ok(()),
+                    }
+                })
+            }
+            Commands::Dedupe(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let deduplicator = TreeSitterDeduplicator::new(Box::new(provider));
+                if !subcommand.apply {
+                    return dedupe::dedupe(deduplicator)
+                        .map(|result| println!("{}", result.format(format)));
+                }
+                let result = dedupe::apply(deduplicator)?;
+                result.write_files()?;
+                match subcommand.output {
+                    Some(output) => {
+                        let content = format!("{}\n", result.format(format));
+                        ::std::fs::write(&output, content)
+                            .map_err(|err| OpenapiSchemerError::Dedupe(err.to_string()))
+                    }
+                    None => {
+                        println!("{}", result.format(format));
+                        Ok(())
+                    }
+                }
+            }
+            Commands::Flatten(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let flattener = TreeSitterFlattener::new(Box::new(provider));
+                flatten::flatten(flattener).and_then(|result| match subcommand.output {
+                    Some(output) => {
+                        let content = format!("{}\n", result.format(format));
+                        ::std::fs::write(&output, content)
+                            .map_err(|err| OpenapiSchemerError::Flatten(err.to_string()))
+                    }
+                    None => {
+                        println!("{}", result.format(format));
+                        Ok(())
+                    }
+                })
+            }
+            Commands::Generate(subcommand) => match subcommand.command {
+                GenerateCommands::Client { template, output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let docs_parser = TreeSitterDocsParser::new(Box::new(provider.clone()));
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    generate::read_templates(&template)
+                        .and_then(|templates| generate::client(docs_parser, parser, &templates))
+                        .and_then(|result| {
+                            result.write(&output)?;
+                            println!("{}", result.format(format));
+                            Ok(())
+                        })
+                }
+                GenerateCommands::Typescript { output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    generate::typescript(parser).and_then(|result| match output {
+                        Some(output) => write_file(&output, &format!("{}\n", result))
+                            .map_err(OpenapiSchemerError::Generate),
+                        None => {
+                            println!("{}", result);
+                            Ok(())
+                        }
+                    })
+                }
+            },
+            Commands::Get(options) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterGetParser::new(Box::new(provider));
+                get::get(parser, &options.pointer, options.inline)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Header(subcommand) => match subcommand.command {
+                HeaderCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterHeaderParser::new(Box::new(provider));
+                    header::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                HeaderCommands::Show { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterHeaderParser::new(Box::new(provider));
+                    header::show(parser, &name).map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Keys(options) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterKeyParser::new(Box::new(provider));
+                keys::keys(parser, &options.path, options.locations)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Lint(options) => {
+                if input.to_str() == Some("-") && options.fix {
+                    return Err(OpenapiSchemerError::Lint(
+                        "A spec read from stdin can't be fixed in place".to_string(),
+                    ));
+                }
+                let provider = load(input, &base_dir, config, cache)?;
+                let ruleset = options
+                    .ruleset
+                    .as_deref()
+                    .map(lint::read_ruleset)
+                    .transpose()?;
+                let result = lint::LintConfig::new(
+                    &config.lint_rules,
+                    ruleset.as_ref(),
+                    options.config.as_deref(),
+                    options.enable,
+                    options.disable,
+                )
+                .and_then(|config| {
+                    let custom = ruleset.map(|ruleset| ruleset.rules).unwrap_or_default();
+                    let parser = TreeSitterLintParser::new(Box::new(provider)).with_rules(custom);
+                    lint::lint(parser, &config, options.fix)
+                });
+                result
+                    .and_then(|result| result.write().map(|_| result))
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Link(subcommand) => match subcommand.command {
+                LinkCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterLinkParser::new(Box::new(provider));
+                    link::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                LinkCommands::Show { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterLinkParser::new(Box::new(provider));
+                    link::show(parser, &name).map(|result| println!("{}", result.format(format)))
+                }
+            },
             Commands::Operation(subcommand) => match subcommand.command {
-                OperationCommands::List => {
-                    let path = ::std::fs::canonicalize(args.input.unwrap()).unwrap();
-                    let provider = ContentProviderMap::from_open_api_yaml(path);
+                OperationCommands::List {
+                    locations,
+                    ids_only,
+                    require_operation_id,
+                    path,
+                    method,
+                    tag,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let filter = OperationFilter { path, method, tag };
+                    if sort == SortOrder::Document
+                        && !require_operation_id
+                        && ListWriter::streams(format)
+                    {
+                        let parser =
+                            TreeSitterOperationParser::new(Box::new(provider)).with_filter(filter);
+                        return stream_list(format, |list| {
+                            operation::stream(parser, locations, ids_only, list)
+                        });
+                    }
+                    // Only the tabular formats show whether operations need
+                    // credentials.
+                    let security_parser = matches!(format, OutputFormat::Csv | OutputFormat::Tsv)
+                        .then(|| TreeSitterSecurityParser::new(Box::new(provider.clone())));
+                    let parser =
+                        TreeSitterOperationParser::new(Box::new(provider)).with_filter(filter);
+                    operation::list(
+                        parser,
+                        security_parser,
+                        locations,
+                        ids_only,
+                        require_operation_id,
+                    )
+                    .map(|result| result.sorted(sort))
+                    .map(|result| print_list(result, format))
+                }
+                OperationCommands::Show { operation_id } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterOperationParser::new(Box::new(provider));
+                    operation::show(parser, &operation_id)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Callbacks {
+                    operation_id,
+                    locations,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterCallbackParser::new(Box::new(provider));
+                    operation::callbacks(parser, &operation_id, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Responses { matrix, locations } => {
+                    let layout = match matrix {
+                        true => ResponsesLayout::Matrix,
+                        false => ResponsesLayout::List,
+                    };
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterResponseParser::new(Box::new(provider));
+                    operation::responses(parser, layout, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::ContentTypes { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterContentTypeParser::new(Box::new(provider));
+                    operation::content_types(parser, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Security {
+                    operation_id,
+                    all: _,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSecurityParser::new(Box::new(provider));
+                    operation::security(parser, operation_id.as_deref())
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Curl {
+                    operation_id,
+                    all: _,
+                    server,
+                    params,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let server_parser = TreeSitterServerParser::new(Box::new(provider.clone()));
+                    let parser = TreeSitterOperationParser::new(Box::new(provider));
+                    operation::curl(
+                        parser,
+                        server_parser,
+                        operation_id.as_deref(),
+                        server,
+                        &params,
+                    )
+                    .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Invoke {
+                    operation_id,
+                    server,
+                    params,
+                    body,
+                    include,
+                } => {
+                    config.check_network()?;
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let server_parser = TreeSitterServerParser::new(Box::new(provider.clone()));
                     let parser = TreeSitterOperationParser::new(Box::new(provider));
-                    match operation::list(parser) {
-                        Ok(result) => println!("{}", result),
-                        Err(err) => eprintln!("Failed: {}", err),
+                    let options = InvokeOptions {
+                        server,
+                        params,
+                        body,
+                        include,
+                    };
+                    operation::invoke(parser, server_parser, &operation_id, options)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                OperationCommands::Rename {
+                    operation_id,
+                    new_operation_id,
+                    dry_run,
+                } => {
+                    if input.to_str() == Some("-") && !dry_run {
+                        return Err(OpenapiSchemerError::OperationRename(
+                            "A spec read from stdin can only be renamed with `--dry-run`"
+                                .to_string(),
+                        ));
                     }
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let renamer = TreeSitterOperationRenamer::new(Box::new(provider));
+                    operation::rename(renamer, &operation_id, &new_operation_id).and_then(
+                        |result| {
+                            if !dry_run {
+                                result.write(OpenapiSchemerError::OperationRename)?;
+                            }
+                            println!("{}", result.format(format));
+                            Ok(())
+                        },
+                    )
+                }
+            },
+            Commands::Overlay(subcommand) => match subcommand.command {
+                OverlayCommands::Apply {
+                    overlay: overlay_path,
+                    output,
+                } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let applier = TreeSitterOverlayApplier::new(
+                        Box::new(provider),
+                        overlay::read_overlay(&overlay_path)?,
+                    );
+                    overlay::apply(applier).and_then(|result| match output {
+                        Some(output) => {
+                            let content = format!("{}\n", result.format(format));
+                            ::std::fs::write(&output, content)
+                                .map_err(|err| OpenapiSchemerError::OverlayApply(err.to_string()))
+                        }
+                        None => {
+                            println!("{}", result.format(format));
+                            Ok(())
+                        }
+                    })
+                }
+            },
+            Commands::Parameter(subcommand) => match subcommand.command {
+                ParameterCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterParameterParser::new(Box::new(provider));
+                    parameter::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                ParameterCommands::Show { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterParameterParser::new(Box::new(provider));
+                    parameter::show(parser, &name)
+                        .map(|result| println!("{}", result.format(format)))
                 }
             },
             Commands::Path(subcommand) => match subcommand.command {
-                PathCommands::List => {
-                    let path = ::std::fs::canonicalize(args.input.unwrap()).unwrap();
-                    let provider = ContentProviderMap::from_open_api_yaml(path);
+                PathCommands::List { locations, tree } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterPathParser::new(Box::new(provider));
+                    if tree {
+                        return path::tree(parser)
+                            .map(|result| result.sorted(sort))
+                            .map(|result| println!("{}", result.format(format)));
+                    }
+                    if sort == SortOrder::Document && ListWriter::streams(format) {
+                        return stream_list(format, |list| path::stream(parser, locations, list));
+                    }
+                    path::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| print_list(result, format))
+                }
+                PathCommands::Match { url } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterPathParser::new(Box::new(provider));
+                    path::matches(parser, &url).map(|result| println!("{}", result.format(format)))
+                }
+                PathCommands::Show { path } => {
+                    let provider = load(input, &base_dir, config, cache)?;
                     let parser = TreeSitterPathParser::new(Box::new(provider));
-                    match path::list(parser) {
-                        Ok(result) => println!("{}", result),
-                        Err(err) => eprintln!("Failed: {}", err),
+                    path::show(parser, &path).map(|result| println!("{}", result.format(format)))
+                }
+                PathCommands::Rename {
+                    path,
+                    new_path,
+                    add_prefix,
+                    dry_run,
+                    output_dir,
+                } => {
+                    if input.to_str() == Some("-") && !dry_run {
+                        return Err(OpenapiSchemerError::PathRename(
+                            "A spec read from stdin can only be renamed with `--dry-run`"
+                                .to_string(),
+                        ));
                     }
+                    let rename = match (path, new_path, add_prefix) {
+                        (_, _, Some(prefix)) => PathRename::Prefix(prefix),
+                        (Some(from), Some(to), None) => PathRename::Path { from, to },
+                        _ => unreachable!("clap requires a path and a new path or a prefix"),
+                    };
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let root_file = provider.source_path(std::path::Path::new("#"));
+                    let root = root_file.parent().unwrap_or(&root_file).to_path_buf();
+                    let renamer = TreeSitterPathRenamer::new(Box::new(provider));
+                    path::rename(renamer, &rename).and_then(|result| {
+                        match (&output_dir, dry_run) {
+                            (Some(directory), _) => result.write_under(
+                                &root,
+                                directory,
+                                OpenapiSchemerError::PathRename,
+                            )?,
+                            (None, false) => result.write(OpenapiSchemerError::PathRename)?,
+                            (None, true) => {}
+                        }
+                        println!("{}", result.format(format));
+                        Ok(())
+                    })
+                }
+            },
+            Commands::Query(options) => {
+                let source = match (options.query, options.file) {
+                    (Some(source), _) => source,
+                    (None, Some(file)) => query::read_query(&file)?,
+                    (None, None) => unreachable!("clap requires --query or --file"),
+                };
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterQueryParser::new(Box::new(provider));
+                query::query(parser, &source, options.all_files)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Refs(subcommand) => match subcommand.command {
+                RefsCommands::List { dot } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterRefParser::new(Box::new(provider));
+                    refs::list(parser, dot)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                RefsCommands::Check => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterRefParser::new(Box::new(provider));
+                    refs::check(parser).map(|result| println!("{}", result.format(format)))
+                }
+                RefsCommands::To { pointer } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterRefParser::new(Box::new(provider));
+                    refs::to(parser, &pointer).map(|result| println!("{}", result.format(format)))
+                }
+                RefsCommands::Resolve { position } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterRefParser::new(Box::new(provider));
+                    refs::resolve(parser, &position)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Response(subcommand) => match subcommand.command {
+                ResponseCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterResponseParser::new(Box::new(provider));
+                    response::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                ResponseCommands::Show { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterResponseParser::new(Box::new(provider));
+                    response::show(parser, &name)
+                        .map(|result| println!("{}", result.format(format)))
                 }
             },
             Commands::Schema(subcommand) => match subcommand.command {
-                SchemaCommands::List => {
-                    let path = ::std::fs::canonicalize(args.input.unwrap()).unwrap();
-                    let provider = ContentProviderMap::from_open_api_yaml(path);
+                SchemaCommands::List {
+                    locations,
+                    section,
+                    tree,
+                } => {
+                    if tree && section != ComponentSection::Schemas {
+                        return Err(OpenapiSchemerError::SchemaList(
+                            "`--tree` only applies to the `schemas` section".to_string(),
+                        ));
+                    }
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSchemaParser::new(Box::new(provider));
+                    if tree {
+                        return schema::tree(parser)
+                            .map(|result| result.sorted(sort))
+                            .map(|result| println!("{}", result.format(format)));
+                    }
+                    if sort == SortOrder::Document && ListWriter::streams(format) {
+                        return stream_list(format, |list| {
+                            schema::stream(parser, section, locations, list)
+                        });
+                    }
+                    schema::list(parser, section, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| print_list(result, format))
+                }
+                SchemaCommands::Show { name, resolve } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSchemaParser::new(Box::new(provider));
+                    schema::show(parser, &name, resolve)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SchemaCommands::ExportJsonschema { name, all, output } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    schema::export_json_schema(parser, name.as_deref(), all).and_then(|result| {
+                        match output {
+                            Some(output) => result.write(&output),
+                            None => {
+                                println!("{}", result);
+                                Ok(())
+                            }
+                        }
+                    })
+                }
+                SchemaCommands::Resolve { name, merge_allof } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    schema::resolve(parser, &name, merge_allof)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SchemaCommands::Properties { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterGenerateParser::new(Box::new(provider));
+                    schema::properties(parser, &name)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SchemaCommands::Usages { name, locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSchemaParser::new(Box::new(provider));
+                    schema::usages(parser, &name, locations)
+                        .map(|result| print_list(result, format))
+                }
+                SchemaCommands::Enums { name, locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
                     let parser = TreeSitterSchemaParser::new(Box::new(provider));
-                    match schema::list(parser) {
-                        Ok(result) => println!("{}", result),
-                        Err(err) => eprintln!("Failed: {}", err),
+                    schema::enums(parser, name.as_deref(), locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SchemaCommands::AuditRequired { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSchemaParser::new(Box::new(provider));
+                    schema::audit_required(parser, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SchemaCommands::Rename {
+                    name,
+                    new_name,
+                    dry_run,
+                } => {
+                    if input.to_str() == Some("-") && !dry_run {
+                        return Err(OpenapiSchemerError::SchemaRename(
+                            "A spec read from stdin can only be renamed with `--dry-run`"
+                                .to_string(),
+                        ));
+                    }
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let renamer = TreeSitterSchemaRenamer::new(Box::new(provider));
+                    schema::rename(renamer, &name, &new_name).and_then(|result| {
+                        if !dry_run {
+                            result.write(OpenapiSchemerError::SchemaRename)?;
+                        }
+                        println!("{}", result.format(format));
+                        Ok(())
+                    })
+                }
+            },
+            Commands::Search { query } => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterSearchParser::new(Box::new(provider));
+                search::search(parser, &query).map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Security(subcommand) => match subcommand.command {
+                SecurityCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSecurityParser::new(Box::new(provider));
+                    security::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SecurityCommands::Audit { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSecurityParser::new(Box::new(provider));
+                    security::audit(parser, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                SecurityCommands::Scopes { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterSecurityParser::new(Box::new(provider));
+                    security::scopes(parser, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Serve(options) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterServeParser::new(Box::new(provider));
+                serve::serve(parser, options.port)
+            }
+            Commands::Server(subcommand) => match subcommand.command {
+                ServerCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterServerParser::new(Box::new(provider));
+                    server::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Split(subcommand) => {
+                let root = match input.to_str() {
+                    Some("-") => None,
+                    _ => input.file_name().map(std::path::PathBuf::from),
+                };
+                let provider = load(input, &base_dir, config, cache)?;
+                let mut splitter = TreeSitterSplitter::new(Box::new(provider));
+                if let Some(root) = root {
+                    splitter = splitter.with_root(root);
+                }
+                split::split(splitter).and_then(|result| {
+                    result.write(&subcommand.directory)?;
+                    println!("{}", result.format(format));
+                    Ok(())
+                })
+            }
+            Commands::Stats => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterStatsParser::new(Box::new(provider));
+                stats::stats(parser).map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Tag(subcommand) => match subcommand.command {
+                TagCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterTagParser::new(Box::new(provider));
+                    tag::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                TagCommands::Show { name } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterTagParser::new(Box::new(provider));
+                    tag::show(parser, &name).map(|result| println!("{}", result.format(format)))
+                }
+                TagCommands::Operations { name, locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterTagParser::new(Box::new(provider));
+                    tag::operations(parser, &name, locations)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Unused(options) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let parser = TreeSitterUnusedParser::new(Box::new(provider));
+                unused::unused(parser, options.locations)
+                    .map(|result| println!("{}", result.format(format)))
+            }
+            Commands::Upgrade(subcommand) => {
+                let provider = load(input, &base_dir, config, cache)?;
+                let upgrader = TreeSitterUpgrader::new(Box::new(provider));
+                upgrade::upgrade(upgrader).and_then(|result| match subcommand.output {
+                    Some(output) => {
+                        let content = format!("{}\n", result.format(format));
+                        ::std::fs::write(&output, content)
+                            .map_err(|err| OpenapiSchemerError::Upgrade(err.to_string()))
+                    }
+                    None => {
+                        println!("{}", result.format(format));
+                        Ok(())
                     }
+                })
+            }
+            Commands::Validate(subcommand) => match subcommand.command {
+                None => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterValidationParser::new(Box::new(provider));
+                    validate::validate(parser, subcommand.examples)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                Some(ValidateCommands::Request {
+                    operation,
+                    params,
+                    body,
+                    content_type,
+                }) => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let checker = TreeSitterContractChecker::new(Box::new(provider));
+                    validate::request(checker, &operation, &params, body.as_deref(), content_type)
+                        .map(|result| println!("{}", result.format(format)))
+                }
+                Some(ValidateCommands::Response {
+                    operation,
+                    status,
+                    headers,
+                    body,
+                    content_type,
+                }) => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let checker = TreeSitterContractChecker::new(Box::new(provider));
+                    validate::response(
+                        checker,
+                        &operation,
+                        status,
+                        &headers,
+                        body.as_deref(),
+                        content_type,
+                    )
+                    .map(|result| println!("{}", result.format(format)))
+                }
+            },
+            Commands::Version(subcommand) => match subcommand.command {
+                VersionCommands::Suggest { since, apply } => {
+                    if input.to_str() == Some("-") && apply {
+                        return Err(OpenapiSchemerError::VersionSuggest(
+                            "A spec read from stdin can't be updated with `--apply`".to_string(),
+                        ));
+                    }
+                    let since_path = std::path::PathBuf::from(&since);
+                    let old_provider = match since_path.is_file() {
+                        true => load(since_path, &base_dir, config, cache.as_deref_mut())?,
+                        false => load_revision(input.to_owned(), &since, config)?,
+                    };
+                    let new_provider = load(input, &base_dir, config, cache)?;
+                    version::suggest(
+                        TreeSitterDiffParser::new(Box::new(old_provider.clone())),
+                        TreeSitterDiffParser::new(Box::new(new_provider.clone())),
+                        TreeSitterVersionParser::new(Box::new(old_provider)),
+                    )
+                    .and_then(|result| {
+                        if apply {
+                            result.write(&TreeSitterVersionParser::new(Box::new(new_provider)))?;
+                        }
+                        println!("{}", result.format(format));
+                        Ok(())
+                    })
+                }
+            },
+            Commands::Watch(subcommand) => {
+                if input == std::path::Path::new("-") {
+                    return Err(OpenapiSchemerError::Watch(
+                        "Can't watch a spec read from stdin".to_string(),
+                    ));
+                }
+                let report = |err: OpenapiSchemerError| {
+                    if !quiet {
+                        eprintln!("{}", err.format(error_format));
+                    }
+                };
+                let mut own_cache = ParseCache::new();
+                let cache = cache.unwrap_or(&mut own_cache);
+                let mut first = true;
+                watch_files(Duration::from_millis(500), || {
+                    if !std::mem::take(&mut first) {
+                        println!();
+                    }
+                    let mut paths = vec![input.to_owned()];
+                    let result = load(input.to_owned(), &base_dir, config, Some(&mut *cache))
+                        .and_then(|provider| {
+                            paths.extend(provider.files());
+                            let parser = TreeSitterValidationParser::new(Box::new(provider));
+                            validate::validate(parser, false)
+                        });
+                    match result {
+                        Ok(result) => {
+                            println!("{}", result.format(format));
+                            if let (true, Some(command)) = (result.is_valid(), &subcommand.exec) {
+                                if let Err(err) = run_command(command) {
+                                    report(err);
+                                }
+                            }
+                        }
+                        Err(err) => report(err),
+                    }
+                    ControlFlow::Continue(paths)
+                });
+                Ok(())
+            }
+            Commands::Webhook(subcommand) => match subcommand.command {
+                WebhookCommands::List { locations } => {
+                    let provider = load(input, &base_dir, config, cache)?;
+                    let parser = TreeSitterWebhookParser::new(Box::new(provider));
+                    webhook::list(parser, locations)
+                        .map(|result| result.sorted(sort))
+                        .map(|result| println!("{}", result.format(format)))
                 }
             },
         },
     }
-
-    ()
 }
@@ -1,62 +1,2168 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-use crate::{bindings::OperationParser, error::OpenapiSchemerError};
+use crate::{
+    bindings::{
+        callback::{CallbackNode, CallbackParser},
+        content_type::{ContentTypeNode, ContentTypeParser},
+        edit::FileEdits,
+        rename::OperationRenamer,
+        response::{OperationResponses, ResponseParser},
+        security::{OperationSecurity, RequiredScheme, SecurityParser},
+        serve::media_body,
+        server::{ServerNode, ServerParser},
+        yaml::{YamlNode, YamlPair, YamlValue},
+        OperationDefinition, OperationNode, OperationParser,
+    },
+    error::OpenapiSchemerError,
+    http::{encode, send, HttpRequest, HttpResponse},
+    output::{write_file, ListEntry, ListFormatter, ListWriter, OutputFormatter, SortedList},
+    serve::render_media,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+const MISSING_ID: &str = "<none>";
+
+#[derive(Debug)]
 pub struct ListResult {
-    entries: Vec<String>,
+    operations: Vec<OperationNode>,
+    /// Whether each operation needs credentials, in the order of
+    /// `operations`, when it is known.
+    authenticated: Vec<bool>,
+    locations: bool,
+    ids_only: bool,
 }
 
 impl ListResult {
-    pub fn new(list: Vec<String>) -> ListResult {
-        ListResult { entries: list }
+    pub fn new(operations: Vec<OperationNode>, locations: bool, ids_only: bool) -> ListResult {
+        ListResult {
+            operations,
+            authenticated: vec![],
+            locations,
+            ids_only,
+        }
+    }
+
+    /// Sets whether each operation needs credentials, which the tabular
+    /// formats show.
+    pub fn with_authenticated(mut self, authenticated: Vec<bool>) -> ListResult {
+        self.authenticated = authenticated;
+        self
+    }
+
+    /// The line of the plain output for `operation`, prefixed with its
+    /// location when `locations` is set.
+    fn entry_line(&self, operation: &OperationNode) -> String {
+        match self.locations {
+            true => ListEntry {
+                name: self.line(operation),
+                file: operation.file.to_owned(),
+                line: operation.line,
+                column: operation.column,
+            }
+            .with_location(),
+            false => self.line(operation),
+        }
+    }
+
+    /// `GET /pets -> listPets`, or only the `operationId` with `ids_only`.
+    /// Operations without an `operationId` are shown as `<none>`.
+    fn line(&self, operation: &OperationNode) -> String {
+        let name = operation.describe();
+        if self.ids_only {
+            return match &operation.operation_id {
+                Some(id) => id.to_owned(),
+                None => format!("{} ({})", MISSING_ID, name),
+            };
+        }
+        let line = format!(
+            "{} -> {}",
+            name,
+            operation.operation_id.as_deref().unwrap_or(MISSING_ID)
+        );
+        match operation.deprecated {
+            true => format!("{} (deprecated)", line),
+            false => line,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        // The credentials of each operation move along with it.
+        let mut operations: Vec<(OperationNode, Option<bool>)> = self
+            .operations
+            .drain(..)
+            .enumerate()
+            .map(|(index, operation)| (operation, self.authenticated.get(index).copied()))
+            .collect();
+        operations.sort_by(|a, b| a.0.text.cmp(&b.0.text));
+        self.authenticated = operations.iter().filter_map(|(_, known)| *known).collect();
+        self.operations = operations
+            .into_iter()
+            .map(|(operation, _)| operation)
+            .collect();
     }
 }
 
 impl Display for ListResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.entries.join("\n"))
+        let lines: Vec<String> = self.lines().collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.nodes().collect())
+    }
+
+    /// The inventory of the API surface for spreadsheets.
+    fn rows(&self) -> Vec<Vec<(String, String)>> {
+        self.operations
+            .iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                let authenticated = self.authenticated.get(index);
+                [
+                    ("method", operation.method.to_uppercase()),
+                    ("path", operation.path.to_owned()),
+                    (
+                        "operationId",
+                        operation.operation_id.to_owned().unwrap_or_default(),
+                    ),
+                    ("tags", operation.tags.join(", ")),
+                    ("summary", operation.summary.to_owned().unwrap_or_default()),
+                    ("deprecated", operation.deprecated.to_string()),
+                    (
+                        "has-auth",
+                        authenticated.map(bool::to_string).unwrap_or_default(),
+                    ),
+                ]
+                .into_iter()
+                .map(|(column, value)| (column.to_string(), value))
+                .collect()
+            })
+            .collect()
+    }
+}
+
+impl ListFormatter for ListResult {
+    fn lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(
+            self.operations
+                .iter()
+                .map(|operation| self.entry_line(operation)),
+        )
+    }
+
+    fn nodes(&self) -> Box<dyn Iterator<Item = YamlNode> + '_> {
+        Box::new(self.operations.iter().map(entry_node))
+    }
+}
+
+/// The structured representation of an operation of `operation list`.
+fn entry_node(operation: &OperationNode) -> YamlNode {
+    let mut pairs = vec![
+        ("name", YamlNode::string(&operation.text)),
+        (
+            "operationId",
+            operation
+                .operation_id
+                .as_deref()
+                .map_or_else(YamlNode::null, YamlNode::string),
+        ),
+        ("method", YamlNode::string(&operation.method)),
+        ("path", YamlNode::string(&operation.path)),
+    ];
+    if let Some(summary) = &operation.summary {
+        pairs.push(("summary", YamlNode::string(summary)));
+    }
+    pairs.extend([
+        (
+            "tags",
+            YamlNode::sequence(
+                operation
+                    .tags
+                    .iter()
+                    .map(|tag| YamlNode::string(tag))
+                    .collect(),
+            ),
+        ),
+        ("deprecated", YamlNode::boolean(operation.deprecated)),
+        (
+            "file",
+            YamlNode::string(&operation.file.display().to_string()),
+        ),
+        ("line", YamlNode::integer(operation.line)),
+        ("column", YamlNode::integer(operation.column)),
+    ]);
+    YamlNode::mapping(pairs)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    definition: OperationDefinition,
+}
+
+impl ShowResult {
+    pub fn new(definition: OperationDefinition) -> ShowResult {
+        ShowResult { definition }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(
+            self.definition.path.as_str(),
+            YamlNode::mapping(vec![(
+                self.definition.method.as_str(),
+                self.definition.operation.to_owned(),
+            )]),
+        )])
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CallbacksResult {
+    callbacks: Vec<CallbackNode>,
+    locations: bool,
+}
+
+impl CallbacksResult {
+    pub fn new(callbacks: Vec<CallbackNode>, locations: bool) -> CallbacksResult {
+        CallbacksResult {
+            callbacks,
+            locations,
+        }
+    }
+
+    /// `onData: POST {$request.query.callbackUrl}/data -> onDataCallback`,
+    /// with `<none>` in place of a missing operationId.
+    fn line(&self, callback: &CallbackNode) -> String {
+        format!(
+            "{}: {} {} -> {}",
+            callback.text,
+            callback.method.to_uppercase(),
+            callback.expression,
+            callback.operation_id.as_deref().unwrap_or(MISSING_ID)
+        )
+    }
+}
+
+impl Display for CallbacksResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .callbacks
+            .iter()
+            .map(|callback| match self.locations {
+                true => ListEntry {
+                    name: self.line(callback),
+                    file: callback.file.to_owned(),
+                    line: callback.line,
+                    column: callback.column,
+                }
+                .with_location(),
+                false => self.line(callback),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for CallbacksResult {
+    fn to_node(&self) -> YamlNode {
+        let callbacks = self
+            .callbacks
+            .iter()
+            .map(|callback| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&callback.text)),
+                    ("expression", YamlNode::string(&callback.expression)),
+                    ("method", YamlNode::string(&callback.method)),
+                    (
+                        "operationId",
+                        callback
+                            .operation_id
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    (
+                        "file",
+                        YamlNode::string(&callback.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(callback.line)),
+                    ("column", YamlNode::integer(callback.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(callbacks)
+    }
+}
+
+const JSON: &str = "application/json";
+
+#[derive(Debug)]
+pub struct ContentTypesResult {
+    operations: Vec<ContentTypeNode>,
+    locations: bool,
+}
+
+impl ContentTypesResult {
+    pub fn new(operations: Vec<ContentTypeNode>, locations: bool) -> ContentTypesResult {
+        ContentTypesResult {
+            operations,
+            locations,
+        }
+    }
+
+    /// What stands out about the media types of `operation`: none of them
+    /// being `application/json`, or the request body and responses not all
+    /// offering the same ones.
+    fn flags(operation: &ContentTypeNode) -> Vec<&'static str> {
+        let mut flags = vec![];
+        let bodies = &operation.bodies;
+        if bodies.is_empty() {
+            return flags;
+        }
+        if !bodies
+            .iter()
+            .any(|body| body.media_types.iter().any(|media_type| media_type == JSON))
+        {
+            flags.push("missing application/json");
+        }
+        let sorted = |media_types: &[String]| {
+            let mut media_types = media_types.to_vec();
+            media_types.sort();
+            media_types
+        };
+        let first = sorted(&bodies[0].media_types);
+        if bodies[1..]
+            .iter()
+            .any(|body| sorted(&body.media_types) != first)
+        {
+            flags.push("inconsistent media types");
+        }
+        flags
+    }
+
+    /// `POST /pets -> createPets`, followed by its flags in parentheses.
+    fn line(&self, operation: &ContentTypeNode) -> String {
+        let node = &operation.operation;
+        let line = format!(
+            "{} -> {}",
+            node.describe(),
+            node.operation_id.as_deref().unwrap_or(MISSING_ID)
+        );
+        let flags = Self::flags(operation);
+        match flags.is_empty() {
+            true => line,
+            false => format!("{} ({})", line, flags.join(", ")),
+        }
+    }
+}
+
+impl Display for ContentTypesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for operation in &self.operations {
+            let node = &operation.operation;
+            lines.push(match self.locations {
+                true => ListEntry {
+                    name: self.line(operation),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                }
+                .with_location(),
+                false => self.line(operation),
+            });
+            for body in &operation.bodies {
+                lines.push(format!("  {}: {}", body.name, body.media_types.join(", ")));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
     }
 }
 
-pub fn list<T: OperationParser>(parser: T) -> Result<ListResult, OpenapiSchemerError> {
-    let nodes = parser.get_operation_nodes()?;
-    let node_texts = nodes
+impl OutputFormatter for ContentTypesResult {
+    fn to_node(&self) -> YamlNode {
+        let operations = self
+            .operations
+            .iter()
+            .map(|operation| {
+                let node = &operation.operation;
+                let bodies = operation
+                    .bodies
+                    .iter()
+                    .map(|body| {
+                        let media_types = body
+                            .media_types
+                            .iter()
+                            .map(|media_type| YamlNode::string(media_type))
+                            .collect();
+                        (body.name.as_str(), YamlNode::sequence(media_types))
+                    })
+                    .collect();
+                let flags = Self::flags(operation)
+                    .into_iter()
+                    .map(YamlNode::string)
+                    .collect();
+                YamlNode::mapping(vec![
+                    ("operation", YamlNode::string(&node.describe())),
+                    (
+                        "operationId",
+                        node.operation_id
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    ("mediaTypes", YamlNode::mapping(bodies)),
+                    ("flags", YamlNode::sequence(flags)),
+                    ("file", YamlNode::string(&node.file.display().to_string())),
+                    ("line", YamlNode::integer(node.line)),
+                    ("column", YamlNode::integer(node.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(operations)
+    }
+}
+
+/// How `operation responses` prints the status codes of each operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponsesLayout {
+    /// A line per operation with its codes.
+    List,
+    /// A table of operations against every code used in the spec.
+    Matrix,
+}
+
+#[derive(Debug)]
+pub struct ResponsesResult {
+    operations: Vec<OperationResponses>,
+    layout: ResponsesLayout,
+    locations: bool,
+}
+
+impl ResponsesResult {
+    pub fn new(
+        operations: Vec<OperationResponses>,
+        layout: ResponsesLayout,
+        locations: bool,
+    ) -> ResponsesResult {
+        ResponsesResult {
+            operations,
+            layout,
+            locations,
+        }
+    }
+
+    /// Every code used by an operation, in numeric order with ranges such as
+    /// `4XX` after their codes and `default` last.
+    fn codes(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = vec![];
+        for code in self
+            .operations
+            .iter()
+            .flat_map(|responses| &responses.codes)
+        {
+            if !codes.contains(&code.as_str()) {
+                codes.push(code);
+            }
+        }
+        codes.sort_by_key(|code| (*code == "default", code.to_ascii_uppercase()));
+        codes
+    }
+
+    fn list(&self) -> Vec<String> {
+        self.operations
+            .iter()
+            .map(|responses| {
+                let operation = &responses.operation;
+                let line = format!(
+                    "{} -> {}: {}",
+                    operation.describe(),
+                    operation.operation_id.as_deref().unwrap_or(MISSING_ID),
+                    responses.codes.join(", ")
+                );
+                match self.locations {
+                    true => ListEntry {
+                        name: line,
+                        file: operation.file.to_owned(),
+                        line: operation.line,
+                        column: operation.column,
+                    }
+                    .with_location(),
+                    false => line,
+                }
+            })
+            .collect()
+    }
+
+    /// The header and a row per operation, with `x` where the operation has
+    /// a response for the code.
+    fn matrix_rows(&self, codes: &[&str]) -> Vec<Vec<String>> {
+        let mut header = vec!["operation".to_string()];
+        header.extend(codes.iter().map(|code| code.to_string()));
+        let mut rows = vec![header];
+        for responses in &self.operations {
+            let mut row = vec![responses.operation.describe()];
+            row.extend(codes.iter().map(|code| {
+                match responses.codes.iter().any(|declared| declared == code) {
+                    true => "x".to_string(),
+                    false => String::new(),
+                }
+            }));
+            rows.push(row);
+        }
+        rows
+    }
+
+    fn matrix(&self) -> Vec<String> {
+        let rows = self.matrix_rows(&self.codes());
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+impl Display for ResponsesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = match self.layout {
+            ResponsesLayout::List => self.list(),
+            ResponsesLayout::Matrix => self.matrix(),
+        };
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ResponsesResult {
+    fn to_node(&self) -> YamlNode {
+        let codes = self.codes();
+        let operations = self
+            .operations
+            .iter()
+            .map(|responses| {
+                let operation = &responses.operation;
+                let declared = match self.layout {
+                    ResponsesLayout::List => YamlNode::sequence(
+                        responses
+                            .codes
+                            .iter()
+                            .map(|code| YamlNode::string(code))
+                            .collect(),
+                    ),
+                    ResponsesLayout::Matrix => YamlNode::mapping(
+                        codes
+                            .iter()
+                            .map(|code| {
+                                let declared =
+                                    responses.codes.iter().any(|declared| declared == code);
+                                (*code, YamlNode::boolean(declared))
+                            })
+                            .collect(),
+                    ),
+                };
+                YamlNode::mapping(vec![
+                    ("operation", YamlNode::string(&operation.describe())),
+                    (
+                        "operationId",
+                        operation
+                            .operation_id
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    ("responses", declared),
+                    (
+                        "file",
+                        YamlNode::string(&operation.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(operation.line)),
+                    ("column", YamlNode::integer(operation.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(operations)
+    }
+
+    /// The matrix of operations against codes, whatever the layout.
+    fn rows(&self) -> Vec<Vec<(String, String)>> {
+        let mut rows = self.matrix_rows(&self.codes()).into_iter();
+        let header = rows.next().unwrap_or_default();
+        rows.map(|row| header.iter().cloned().zip(row).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RenameResult {
+    files: Vec<FileEdits>,
+}
+
+impl RenameResult {
+    pub fn new(files: Vec<FileEdits>) -> RenameResult {
+        RenameResult { files }
+    }
+
+    /// Writes the renamed files over the ones they were read from, failing
+    /// with `error` when one cannot be written.
+    pub fn write(
+        &self,
+        error: fn(String) -> OpenapiSchemerError,
+    ) -> Result<(), OpenapiSchemerError> {
+        self.write_files(|file| Ok(file.to_path_buf()), error)
+    }
+
+    /// Writes the renamed files under `directory` instead, each at its path
+    /// relative to `root`, the directory of the spec.
+    pub fn write_under(
+        &self,
+        root: &Path,
+        directory: &Path,
+        error: fn(String) -> OpenapiSchemerError,
+    ) -> Result<(), OpenapiSchemerError> {
+        self.write_files(
+            |file| match file.strip_prefix(root) {
+                Ok(relative) if relative.is_relative() => Ok(directory.join(relative)),
+                _ => Err(format!(
+                    "`{}` is outside of `{}`, the directory of the spec",
+                    file.display(),
+                    root.display()
+                )),
+            },
+            error,
+        )
+    }
+
+    fn write_files(
+        &self,
+        target: impl Fn(&Path) -> Result<PathBuf, String>,
+        error: fn(String) -> OpenapiSchemerError,
+    ) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            let path = target(&file.file).map_err(error)?;
+            write_file(&path, &file.content).map_err(error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for RenameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .files
+            .iter()
+            .flat_map(|file| {
+                file.edits.iter().map(|edit| {
+                    ListEntry {
+                        name: format!("{} -> {}", edit.old, edit.new),
+                        file: file.file.to_owned(),
+                        line: edit.line,
+                        column: edit.column,
+                    }
+                    .with_location()
+                })
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for RenameResult {
+    fn to_node(&self) -> YamlNode {
+        let changes = self
+            .files
+            .iter()
+            .flat_map(|file| {
+                file.edits.iter().map(|edit| {
+                    YamlNode::mapping(vec![
+                        ("from", YamlNode::string(&edit.old)),
+                        ("to", YamlNode::string(&edit.new)),
+                        ("file", YamlNode::string(&file.file.display().to_string())),
+                        ("line", YamlNode::integer(edit.line)),
+                        ("column", YamlNode::integer(edit.column)),
+                    ])
+                })
+            })
+            .collect();
+        YamlNode::sequence(changes)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvokeResult {
+    response: HttpResponse,
+    /// Whether the status line and headers are printed before the body.
+    include: bool,
+}
+
+impl InvokeResult {
+    pub fn new(response: HttpResponse, include: bool) -> InvokeResult {
+        InvokeResult { response, include }
+    }
+}
+
+impl Display for InvokeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.include {
+            writeln!(
+                f,
+                "HTTP/1.1 {} {}",
+                self.response.status, self.response.reason
+            )?;
+            for (name, value) in &self.response.headers {
+                writeln!(f, "{}: {}", name, value)?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "{}", self.response.body.trim_end())
+    }
+}
+
+impl OutputFormatter for InvokeResult {
+    fn to_node(&self) -> YamlNode {
+        let headers = self
+            .response
+            .headers
+            .iter()
+            .map(|(name, value)| YamlPair {
+                key: YamlNode::string(name),
+                value: YamlNode::string(value),
+            })
+            .collect();
+        YamlNode::mapping(vec![
+            ("status", YamlNode::integer(self.response.status.into())),
+            ("headers", YamlNode::new(YamlValue::Mapping(headers))),
+            ("body", YamlNode::string(&self.response.body)),
+        ])
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CurlResult {
+    /// The `operationId` and curl command line of each operation.
+    snippets: Vec<(String, String)>,
+    /// Whether each command is headed by its `operationId`.
+    labelled: bool,
+}
+
+impl CurlResult {
+    pub fn new(snippets: Vec<(String, String)>, labelled: bool) -> CurlResult {
+        CurlResult { snippets, labelled }
+    }
+}
+
+impl Display for CurlResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let snippets: Vec<String> = self
+            .snippets
+            .iter()
+            .map(|(operation_id, command)| match self.labelled {
+                true => format!("# {}\n{}", operation_id, command),
+                false => command.to_owned(),
+            })
+            .collect();
+        write!(f, "{}", snippets.join("\n\n"))
+    }
+}
+
+impl OutputFormatter for CurlResult {
+    fn to_node(&self) -> YamlNode {
+        let snippets = self
+            .snippets
+            .iter()
+            .map(|(operation_id, command)| {
+                YamlNode::mapping(vec![
+                    ("operationId", YamlNode::string(operation_id)),
+                    ("command", YamlNode::string(command)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(snippets)
+    }
+}
+
+#[derive(Debug)]
+pub struct SecurityResult {
+    operations: Vec<OperationSecurity>,
+    /// Whether each operation is headed by its name.
+    labelled: bool,
+}
+
+impl SecurityResult {
+    pub fn new(operations: Vec<OperationSecurity>, labelled: bool) -> SecurityResult {
+        SecurityResult {
+            operations,
+            labelled,
+        }
+    }
+
+    /// One line per requirement, any of which is enough to call the
+    /// operation, listing the schemes it needs.
+    fn lines(security: &OperationSecurity) -> Vec<String> {
+        let mut lines = vec![];
+        if security.inherited && !security.requirements.is_empty() {
+            lines.push("Inherited from the top level `security`".to_string());
+        }
+        if security.requirements.is_empty() {
+            lines.push("No security requirements".to_string());
+        }
+        for requirement in &security.requirements {
+            let schemes: Vec<String> = requirement.iter().map(scheme_text).collect();
+            let line = match schemes.is_empty() {
+                true => "(anonymous)".to_string(),
+                false => schemes.join(" and "),
+            };
+            lines.push(format!("- {}", line));
+        }
+        lines
+    }
+}
+
+/// `petstore_auth: oauth2 (implicit) with scopes read:pets, write:pets`
+fn scheme_text(required: &RequiredScheme) -> String {
+    let scheme = match &required.scheme {
+        Some(scheme) => scheme,
+        None => return format!("{}: undeclared scheme", required.name),
+    };
+    let text = |key: &str| scheme.get(key).and_then(|value| value.as_str());
+    let scheme_type = text("type").unwrap_or_default();
+    let details = match scheme_type {
+        "apiKey" => format!(
+            " in {} `{}`",
+            text("in").unwrap_or_default(),
+            text("name").unwrap_or_default()
+        ),
+        "http" => {
+            let format = text("bearerFormat")
+                .map(|format| format!(" ({})", format))
+                .unwrap_or_default();
+            format!(" {}{}", text("scheme").unwrap_or_default(), format)
+        }
+        "oauth2" => {
+            let flows: Vec<&str> = scheme
+                .get("flows")
+                .and_then(|flows| flows.as_mapping())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|flow| flow.key.as_str())
+                .collect();
+            format!(" ({})", flows.join(", "))
+        }
+        "openIdConnect" => format!(" {}", text("openIdConnectUrl").unwrap_or_default()),
+        _ => String::new(),
+    };
+    let scopes = match required.scopes.is_empty() {
+        true => String::new(),
+        false => format!(" with scopes {}", required.scopes.join(", ")),
+    };
+    format!("{}: {}{}{}", required.name, scheme_type, details, scopes)
+}
+
+impl Display for SecurityResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sections: Vec<String> = self
+            .operations
+            .iter()
+            .map(|security| {
+                let mut lines = SecurityResult::lines(security);
+                if self.labelled {
+                    let operation = &security.operation;
+                    let name = operation.operation_id.as_deref().unwrap_or(MISSING_ID);
+                    lines.insert(0, format!("# {} ({})", name, operation.describe()));
+                }
+                lines.join("\n")
+            })
+            .collect();
+        write!(f, "{}", sections.join("\n\n"))
+    }
+}
+
+impl OutputFormatter for SecurityResult {
+    fn to_node(&self) -> YamlNode {
+        let operations = self
+            .operations
+            .iter()
+            .map(|security| {
+                let operation = &security.operation;
+                let requirements = security
+                    .requirements
+                    .iter()
+                    .map(|requirement| {
+                        YamlNode::sequence(
+                            requirement
+                                .iter()
+                                .map(|required| {
+                                    YamlNode::mapping(vec![
+                                        ("name", YamlNode::string(&required.name)),
+                                        (
+                                            "scheme",
+                                            required
+                                                .scheme
+                                                .to_owned()
+                                                .unwrap_or_else(YamlNode::null),
+                                        ),
+                                        (
+                                            "scopes",
+                                            YamlNode::sequence(
+                                                required
+                                                    .scopes
+                                                    .iter()
+                                                    .map(|scope| YamlNode::string(scope))
+                                                    .collect(),
+                                            ),
+                                        ),
+                                    ])
+                                })
+                                .collect(),
+                        )
+                    })
+                    .collect();
+                YamlNode::mapping(vec![
+                    (
+                        "operationId",
+                        operation
+                            .operation_id
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    ("method", YamlNode::string(&operation.method)),
+                    ("path", YamlNode::string(&operation.path)),
+                    ("inherited", YamlNode::boolean(security.inherited)),
+                    ("requirements", YamlNode::sequence(requirements)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(operations)
+    }
+}
+
+/// How `invoke` builds its request.
+#[derive(Clone, Debug, Default)]
+pub struct InvokeOptions {
+    /// The base URL to send the request to instead of the spec's servers.
+    pub server: Option<String>,
+    /// `name=value` pairs for the operation's path, query, header and
+    /// cookie parameters.
+    pub params: Vec<String>,
+    /// The request body, which is otherwise scaffolded from the request
+    /// body's example or schema.
+    pub body: Option<String>,
+    /// Print the status line and headers of the response as well.
+    pub include: bool,
+}
+
+/// Lists the operations of the spec. With `require_operation_id` any
+/// operation without an `operationId` is reported as an error instead. An
+/// operation needs credentials when every security requirement that applies
+/// to it names a scheme. This is only looked up with a `security_parser`,
+/// since only the tabular formats show it.
+pub fn list<T: OperationParser, U: SecurityParser>(
+    parser: T,
+    security_parser: Option<U>,
+    locations: bool,
+    ids_only: bool,
+    require_operation_id: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let operations = parser.get_operation_nodes()?;
+    if require_operation_id {
+        let missing: Vec<String> = operations
+            .iter()
+            .filter(|operation| operation.operation_id.is_none())
+            .map(|operation| {
+                format!(
+                    "{}:{}:{}: Missing `operationId` for operation `{}`",
+                    operation.file.display(),
+                    operation.line,
+                    operation.column,
+                    operation.describe()
+                )
+            })
+            .collect();
+        if !missing.is_empty() {
+            return Err(OpenapiSchemerError::OperationList(missing.join("\n")));
+        }
+    }
+    let result = ListResult::new(operations, locations, ids_only);
+    let security = match security_parser {
+        Some(security_parser) => security_parser.get_operation_security()?,
+        None => return Ok(result),
+    };
+    let authenticated = result
+        .operations
+        .iter()
+        .map(|operation| {
+            security
+                .iter()
+                .find(|security| {
+                    security.operation.path == operation.path
+                        && security
+                            .operation
+                            .method
+                            .eq_ignore_ascii_case(&operation.method)
+                })
+                .is_some_and(|security| {
+                    !security.requirements.is_empty()
+                        && security
+                            .requirements
+                            .iter()
+                            .all(|requirement| !requirement.is_empty())
+                })
+        })
+        .collect();
+    Ok(result.with_authenticated(authenticated))
+}
+
+/// Writes the operations of the spec to `list` as they are found, the same
+/// entries `list` returns in document order.
+pub fn stream<T: OperationParser>(
+    parser: T,
+    locations: bool,
+    ids_only: bool,
+    list: &mut ListWriter,
+) -> Result<(), OpenapiSchemerError> {
+    let result = ListResult::new(vec![], locations, ids_only);
+    parser.for_each_operation_node(&mut |operation| {
+        list.item(|| result.entry_line(&operation), || entry_node(&operation))
+    })
+}
+
+pub fn show<T: OperationParser>(
+    parser: T,
+    operation_id: &str,
+) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_operation(operation_id)? {
+        Some(definition) => Ok(ShowResult::new(definition)),
+        None => Err(OpenapiSchemerError::OperationShow(format!(
+            "Could not find operation `{}`",
+            operation_id
+        ))),
+    }
+}
+
+/// Lists the operations the operation `operation_id` declares under
+/// `callbacks`, one entry per method of each callback expression.
+pub fn callbacks<T: CallbackParser>(
+    parser: T,
+    operation_id: &str,
+    locations: bool,
+) -> Result<CallbacksResult, OpenapiSchemerError> {
+    match parser.get_callback_nodes(operation_id)? {
+        Some(callbacks) => Ok(CallbacksResult::new(callbacks, locations)),
+        None => Err(OpenapiSchemerError::OperationCallbacks(format!(
+            "Could not find operation `{}`",
+            operation_id
+        ))),
+    }
+}
+
+/// Lists the media types of the request body and responses of every
+/// operation.
+pub fn content_types<T: ContentTypeParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ContentTypesResult, OpenapiSchemerError> {
+    Ok(ContentTypesResult::new(
+        parser.get_content_type_nodes()?,
+        locations,
+    ))
+}
+
+/// Lists the response codes of every operation, or lays them out as a
+/// matrix of operations against codes.
+pub fn responses<T: ResponseParser>(
+    parser: T,
+    layout: ResponsesLayout,
+    locations: bool,
+) -> Result<ResponsesResult, OpenapiSchemerError> {
+    Ok(ResponsesResult::new(
+        parser.get_operation_responses()?,
+        layout,
+        locations,
+    ))
+}
+
+/// Renames the operation `operation_id` to `new_operation_id` along with the
+/// links to it. Nothing is written until `RenameResult::write`.
+pub fn rename<T: OperationRenamer>(
+    renamer: T,
+    operation_id: &str,
+    new_operation_id: &str,
+) -> Result<RenameResult, OpenapiSchemerError> {
+    match renamer.get_rename_edits(operation_id, new_operation_id)? {
+        Some(files) => Ok(RenameResult::new(files)),
+        None => Err(OpenapiSchemerError::OperationRename(format!(
+            "Could not find operation `{}`",
+            operation_id
+        ))),
+    }
+}
+
+/// Sends a request for the operation `operation_id` and returns the
+/// response. Without `options.server` the request goes to the servers the
+/// spec declares for the operation.
+pub fn invoke<T: OperationParser, U: ServerParser>(
+    parser: T,
+    server_parser: U,
+    operation_id: &str,
+    options: InvokeOptions,
+) -> Result<InvokeResult, OpenapiSchemerError> {
+    let definition = match parser.get_operation(operation_id)? {
+        Some(definition) => definition,
+        None => {
+            return Err(OpenapiSchemerError::OperationInvoke(format!(
+                "Could not find operation `{}`",
+                operation_id
+            )))
+        }
+    };
+    let server = match options.server {
+        Some(server) => server,
+        None => {
+            default_server(&server_parser.get_server_nodes()?, &definition).ok_or_else(|| {
+                OpenapiSchemerError::OperationInvoke(format!(
+                    "No servers are declared for `{}`, pass one with `--server`",
+                    operation_id
+                ))
+            })?
+        }
+    };
+    let request = build_request(&definition, &server, &options.params, options.body, false)?;
+    Ok(InvokeResult::new(send(&request)?, options.include))
+}
+
+/// curl command lines for `operation_id`, or every operation with an
+/// `operationId` when it is `None`. Required parameters missing from
+/// `params` are left as `{name}` placeholders, and requests go to `server`,
+/// the servers the spec declares for the operation or a `{server}`
+/// placeholder. With every operation only the `params` each one declares
+/// are used.
+pub fn curl<T: OperationParser, U: ServerParser>(
+    parser: T,
+    server_parser: U,
+    operation_id: Option<&str>,
+    server: Option<String>,
+    params: &[String],
+) -> Result<CurlResult, OpenapiSchemerError> {
+    let operation_ids = match operation_id {
+        Some(operation_id) => vec![operation_id.to_string()],
+        None => parser
+            .get_operation_nodes()?
+            .into_iter()
+            .filter_map(|operation| operation.operation_id)
+            .collect(),
+    };
+    let servers = match server {
+        Some(_) => vec![],
+        None => server_parser.get_server_nodes()?,
+    };
+    let all = operation_id.is_none();
+    let mut snippets = vec![];
+    for operation_id in operation_ids {
+        let definition = parser.get_operation(&operation_id)?.ok_or_else(|| {
+            OpenapiSchemerError::OperationCurl(format!(
+                "Could not find operation `{}`",
+                operation_id
+            ))
+        })?;
+        let server = server
+            .to_owned()
+            .or_else(|| default_server(&servers, &definition))
+            .unwrap_or_else(|| "{server}".to_string());
+        let declared: Vec<String> = definition
+            .operation
+            .get("parameters")
+            .and_then(|parameters| parameters.as_sequence())
+            .unwrap_or_default()
+            .iter()
+            .map(|parameter| parameter_text(parameter, "name"))
+            .collect();
+        let params: Vec<String> = params
+            .iter()
+            .filter(|param| {
+                let param_name = param.split_once('=').map(|(param_name, _)| param_name);
+                !all || declared.iter().any(|name| param_name == Some(name))
+            })
+            .cloned()
+            .collect();
+        let request = build_request(&definition, &server, &params, None, true)?;
+        snippets.push((operation_id, curl_command(&request)));
+    }
+    Ok(CurlResult::new(snippets, all))
+}
+
+/// The security requirements that apply to the operation `operation_id`, or
+/// to every operation when it is `None`, after the operation's own
+/// `security` replaces the top level one.
+pub fn security<T: SecurityParser>(
+    parser: T,
+    operation_id: Option<&str>,
+) -> Result<SecurityResult, OpenapiSchemerError> {
+    let operations = parser.get_operation_security()?;
+    let operation_id = match operation_id {
+        Some(operation_id) => operation_id,
+        None => return Ok(SecurityResult::new(operations, true)),
+    };
+    let operations: Vec<OperationSecurity> = operations
         .into_iter()
-        .map(|node| node.text.to_string())
+        .filter(|security| security.operation.operation_id.as_deref() == Some(operation_id))
         .collect();
-    Ok(ListResult::new(node_texts))
+    if operations.is_empty() {
+        return Err(OpenapiSchemerError::OperationSecurity(format!(
+            "Could not find operation `{}`",
+            operation_id
+        )));
+    }
+    Ok(SecurityResult::new(operations, false))
+}
+
+/// `request` as a curl command line, one option per line.
+fn curl_command(request: &HttpRequest) -> String {
+    let mut command = String::from("curl");
+    if !request.method.eq_ignore_ascii_case("get") {
+        command.push_str(&format!(" -X {}", request.method.to_uppercase()));
+    }
+    let mut lines = vec![format!("{} {}", command, shell_quote(&request.url))];
+    for (name, value) in &request.headers {
+        lines.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = &request.body {
+        lines.push(format!("--data {}", shell_quote(body)));
+    }
+    lines.join(" \\\n  ")
+}
+
+/// `text` in single quotes for a POSIX shell.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// The first server declared for the operation itself, then for its path,
+/// then for the whole spec.
+pub fn default_server(servers: &[ServerNode], definition: &OperationDefinition) -> Option<String> {
+    let operation = format!("{} {}", definition.method.to_uppercase(), definition.path);
+    [
+        Some(operation.as_str()),
+        Some(definition.path.as_str()),
+        None,
+    ]
+    .iter()
+    .find_map(|scope| {
+        servers
+            .iter()
+            .find(|server| server.scope.as_deref() == *scope)
+    })
+    .map(|server| server.text.to_owned())
+}
+
+/// The request for `definition` sent to `server`. Each of `params` is
+/// placed wherever the operation declares it. A required parameter that is
+/// not given is an error, or with `placeholders` is left as `{name}` for
+/// the reader to fill in.
+pub fn build_request(
+    definition: &OperationDefinition,
+    server: &str,
+    params: &[String],
+    body: Option<String>,
+    placeholders: bool,
+) -> Result<HttpRequest, OpenapiSchemerError> {
+    let error = |message: String| match placeholders {
+        true => OpenapiSchemerError::OperationCurl(message),
+        false => OpenapiSchemerError::OperationInvoke(message),
+    };
+    let name = format!("{} {}", definition.method.to_uppercase(), definition.path);
+    let operation = &definition.operation;
+    let parameters = operation
+        .get("parameters")
+        .and_then(|parameters| parameters.as_sequence())
+        .unwrap_or_default();
+
+    let mut values = vec![];
+    for param in params {
+        let (param_name, value) = param
+            .split_once('=')
+            .ok_or_else(|| error(format!("Expected `name=value` but found `{}`", param)))?;
+        if !parameters
+            .iter()
+            .any(|parameter| parameter_text(parameter, "name") == param_name)
+        {
+            return Err(error(format!(
+                "No parameter `{}` is declared for `{}`",
+                param_name, name
+            )));
+        }
+        values.push((param_name, value));
+    }
+
+    let mut path = definition.path.to_owned();
+    let (mut query, mut headers, mut cookies) = (vec![], vec![], vec![]);
+    for parameter in parameters {
+        let param_name = parameter_text(parameter, "name");
+        let location = parameter_text(parameter, "in");
+        let required = location == "path" || parameter_text(parameter, "required") == "true";
+        let given = values.iter().find(|(given, _)| *given == param_name);
+        // The value as written and as it goes into the URL
+        let (value, encoded) = match given {
+            Some((_, value)) => (value.to_string(), encode(value)),
+            None if required && placeholders => {
+                let placeholder = format!("{{{}}}", param_name);
+                (placeholder.to_owned(), placeholder)
+            }
+            None if required => {
+                return Err(error(format!(
+                    "Missing required {} parameter `{}`, pass it with `--param {}=VALUE`",
+                    location, param_name, param_name
+                )))
+            }
+            None => continue,
+        };
+        match location.as_str() {
+            "path" => path = path.replace(&format!("{{{}}}", param_name), &encoded),
+            "query" => query.push(format!("{}={}", encode(&param_name), encoded)),
+            "header" => headers.push((param_name, value)),
+            "cookie" => cookies.push(format!("{}={}", param_name, value)),
+            _ => {}
+        }
+    }
+    if !cookies.is_empty() {
+        headers.push(("Cookie".to_string(), cookies.join("; ")));
+    }
+
+    let media = operation
+        .get("requestBody")
+        .and_then(|request_body| request_body.get("content"))
+        .and_then(|content| content.as_mapping())
+        .and_then(|content| content.first());
+    let content_type = media
+        .and_then(|media| media.key.as_str())
+        .unwrap_or("application/json");
+    let body = match body {
+        Some(body) => Some(body),
+        None => media
+            .and_then(|media| media_body(&media.value))
+            .map(|body| render_media(content_type, &body)),
+    };
+    if body.is_some() {
+        headers.push(("Content-Type".to_string(), content_type.to_string()));
+    }
+
+    let mut url = format!("{}{}", server.trim_end_matches('/'), path);
+    if !query.is_empty() {
+        url = format!("{}?{}", url, query.join("&"));
+    }
+    Ok(HttpRequest {
+        method: definition.method.to_owned(),
+        url,
+        headers,
+        body,
+    })
+}
+
+fn parameter_text(parameter: &YamlNode, key: &str) -> String {
+    parameter
+        .get(key)
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string()
 }
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
+    use std::{collections::HashMap, error::Error, path::PathBuf};
 
-    use crate::bindings::{self, OperationNode};
+    use crate::{
+        bindings::{
+            self, content_type::BodyNode, edit::TextEdit, operation::TreeSitterOperationParser,
+            response::ResponseNode,
+        },
+        content::ContentProviderMap,
+        output::{OutputFormat, SortOrder},
+    };
 
     use super::*;
 
     struct MockParser {
         nodes: Vec<OperationNode>,
+        definition: Option<OperationDefinition>,
     }
     impl MockParser {
         fn new(nodes: Vec<OperationNode>) -> MockParser {
-            MockParser { nodes }
+            MockParser {
+                nodes,
+                definition: None,
+            }
         }
     }
     impl OperationParser for MockParser {
         fn get_operation_nodes(&self) -> Result<Vec<bindings::OperationNode>, OpenapiSchemerError> {
             Ok(self.nodes.to_owned())
         }
+
+        fn get_operation(
+            &self,
+            _operation_id: &str,
+        ) -> Result<Option<OperationDefinition>, OpenapiSchemerError> {
+            Ok(self.definition.to_owned())
+        }
     }
 
     #[test]
     fn test_list() -> Result<(), Box<dyn Error>> {
-        let parser = MockParser::new(vec![OperationNode {
-            text: "test1".to_string(),
-        }]);
-        let result = list(parser)?;
-        assert_eq!(result, ListResult::new(vec!["test1".to_string()]));
+        let parser = || {
+            MockParser::new(vec![
+                OperationNode {
+                    text: "listPets".to_string(),
+                    operation_id: Some("listPets".to_string()),
+                    method: "get".to_string(),
+                    path: "/pets".to_string(),
+                    summary: Some("List all pets".to_string()),
+                    tags: vec!["pets".to_string()],
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 5,
+                    ..Default::default()
+                },
+                OperationNode {
+                    text: "POST /pets".to_string(),
+                    method: "post".to_string(),
+                    path: "/pets".to_string(),
+                    deprecated: true,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 8,
+                    column: 5,
+                    ..Default::default()
+                },
+            ])
+        };
+        let security = || MockSecurityParser {
+            operations: parser()
+                .nodes
+                .into_iter()
+                .zip([false, true])
+                .map(|(operation, optional)| OperationSecurity {
+                    operation,
+                    requirements: vec![
+                        vec![RequiredScheme {
+                            name: "api_key".to_string(),
+                            scheme: None,
+                            scopes: vec![],
+                        }],
+                        match optional {
+                            true => vec![],
+                            false => vec![RequiredScheme {
+                                name: "oauth".to_string(),
+                                scheme: None,
+                                scopes: vec!["read".to_string()],
+                            }],
+                        },
+                    ],
+                    inherited: true,
+                })
+                .collect(),
+        };
+        assert_eq!(
+            list(parser(), Some(security()), false, false, false)?.to_string(),
+            "GET /pets -> listPets\nPOST /pets -> <none> (deprecated)"
+        );
+        assert_eq!(
+            list(parser(), Some(security()), false, true, false)?.to_string(),
+            "listPets\n<none> (POST /pets)"
+        );
+        assert_eq!(
+            list(parser(), Some(security()), true, false, false)?.to_string(),
+            "/spec/openapi.yaml:3:5: GET /pets -> listPets\n\
+             /spec/openapi.yaml:8:5: POST /pets -> <none> (deprecated)"
+        );
+        assert!(list(parser(), Some(security()), false, false, false)?
+            .format(OutputFormat::Json)
+            .starts_with(
+                "[\n  {\n    \"name\": \"listPets\",\n    \"operationId\": \"listPets\",\n    \"method\": \"get\",\n    \"path\": \"/pets\",\n    \"summary\": \"List all pets\""
+            ));
+        assert_eq!(
+            list(parser(), Some(security()), false, false, false)?.format(OutputFormat::Csv),
+            "method,path,operationId,tags,summary,deprecated,has-auth\n\
+             GET,/pets,listPets,pets,List all pets,false,true\n\
+             POST,/pets,,,,true,false"
+        );
+        assert_eq!(
+            list(parser(), None::<MockSecurityParser>, false, false, false)?
+                .format(OutputFormat::Csv),
+            "method,path,operationId,tags,summary,deprecated,has-auth\n\
+             GET,/pets,listPets,pets,List all pets,false,\n\
+             POST,/pets,,,,true,"
+        );
+        assert_eq!(
+            list(parser(), Some(security()), false, false, false)?
+                .sorted(SortOrder::Alpha)
+                .format(OutputFormat::Csv),
+            "method,path,operationId,tags,summary,deprecated,has-auth\n\
+             POST,/pets,,,,true,false\n\
+             GET,/pets,listPets,pets,List all pets,false,true"
+        );
+        let error = list(parser(), Some(security()), false, false, true).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "/spec/openapi.yaml:8:5: Missing `operationId` for operation `POST /pets`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> Result<(), Box<dyn Error>> {
+        let parser = || {
+            MockParser::new(vec![
+                OperationNode {
+                    text: "listPets".to_string(),
+                    operation_id: Some("listPets".to_string()),
+                    method: "get".to_string(),
+                    path: "/pets".to_string(),
+                    tags: vec!["pets".to_string()],
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 5,
+                    ..Default::default()
+                },
+                OperationNode {
+                    text: "POST /pets".to_string(),
+                    method: "post".to_string(),
+                    path: "/pets".to_string(),
+                    deprecated: true,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 8,
+                    column: 5,
+                    ..Default::default()
+                },
+            ])
+        };
+        for (format, locations, ids_only) in [
+            (OutputFormat::Plain, false, false),
+            (OutputFormat::Plain, true, false),
+            (OutputFormat::Plain, false, true),
+            (OutputFormat::Json, false, false),
+            (OutputFormat::Yaml, false, false),
+        ] {
+            let mut streamed = vec![];
+            let mut writer = ListWriter::new(format, &mut streamed);
+            stream(parser(), locations, ids_only, &mut writer)?;
+            writer.finish()?;
+            let mut written = vec![];
+            list(
+                parser(),
+                None::<MockSecurityParser>,
+                locations,
+                ids_only,
+                false,
+            )?
+            .write(format, &mut written)?;
+            assert_eq!(String::from_utf8(streamed)?, String::from_utf8(written)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_aliases() -> Result<(), Box<dyn Error>> {
+        let contents = HashMap::from([(
+            PathBuf::from("#"),
+            "paths:\n  /pets:\n    get: &get\n      operationId: listPets\n      tags: [pets]\n    \
+             post:\n      <<: *get\n      operationId: createPet\n"
+                .to_string(),
+        )]);
+        let parser =
+            TreeSitterOperationParser::new(Box::new(ContentProviderMap::from_map(contents)));
+        assert_eq!(
+            list(parser, None::<MockSecurityParser>, false, false, false)?.to_string(),
+            "GET /pets -> listPets\nPOST /pets -> createPet"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.definition = Some(OperationDefinition {
+            path: "/pets".to_string(),
+            method: "get".to_string(),
+            operation: YamlNode::mapping(vec![("operationId", YamlNode::string("listPets"))]),
+        });
+        let result = show(parser, "listPets")?;
+        assert_eq!(
+            result.to_string(),
+            "/pets:\n  get:\n    operationId: listPets"
+        );
+
+        let parser = MockParser::new(vec![]);
+        let error = show(parser, "missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find operation `missing`");
+        Ok(())
+    }
+
+    struct MockCallbackParser {
+        callbacks: Option<Vec<CallbackNode>>,
+    }
+    impl CallbackParser for MockCallbackParser {
+        fn get_callback_nodes(
+            &self,
+            _operation_id: &str,
+        ) -> Result<Option<Vec<CallbackNode>>, OpenapiSchemerError> {
+            Ok(self.callbacks.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_callbacks() -> Result<(), Box<dyn Error>> {
+        let callback = |method: &str, operation_id: Option<&str>, line| CallbackNode {
+            text: "onData".to_string(),
+            expression: "{$request.query.callbackUrl}/data".to_string(),
+            method: method.to_string(),
+            operation_id: operation_id.map(str::to_owned),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 13,
+        };
+        let parser = || MockCallbackParser {
+            callbacks: Some(vec![
+                callback("post", Some("onDataCallback"), 10),
+                callback("delete", None, 15),
+            ]),
+        };
+        assert_eq!(
+            callbacks(parser(), "subscribe", false)?.to_string(),
+            "onData: POST {$request.query.callbackUrl}/data -> onDataCallback\n\
+             onData: DELETE {$request.query.callbackUrl}/data -> <none>"
+        );
+        assert_eq!(
+            callbacks(parser(), "subscribe", true)?.to_string(),
+            "/spec/openapi.yaml:10:13: onData: POST {$request.query.callbackUrl}/data -> onDataCallback\n\
+             /spec/openapi.yaml:15:13: onData: DELETE {$request.query.callbackUrl}/data -> <none>"
+        );
+
+        let parser = MockCallbackParser { callbacks: None };
+        let error = callbacks(parser, "missing", false).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find operation `missing`");
+        Ok(())
+    }
+
+    struct MockSecurityParser {
+        operations: Vec<OperationSecurity>,
+    }
+    impl SecurityParser for MockSecurityParser {
+        fn get_security_scheme_nodes(
+            &self,
+        ) -> Result<Vec<bindings::security::SecuritySchemeNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+
+        fn get_unsecured_operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+
+        fn get_operation_security(&self) -> Result<Vec<OperationSecurity>, OpenapiSchemerError> {
+            Ok(self.operations.to_owned())
+        }
+
+        fn get_declared_scopes(
+            &self,
+        ) -> Result<Vec<bindings::security::ScopeNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_security() -> Result<(), Box<dyn Error>> {
+        let operation = |operation_id: &str, method: &str| OperationNode {
+            text: operation_id.to_string(),
+            operation_id: Some(operation_id.to_string()),
+            method: method.to_string(),
+            path: "/pets".to_string(),
+            summary: None,
+            tags: vec![],
+            deprecated: false,
+            file: PathBuf::from("#"),
+            line: 1,
+            column: 1,
+        };
+        let required = |name: &str, scheme: Option<&str>, scopes: &[&str]| RequiredScheme {
+            name: name.to_string(),
+            scheme: scheme.map(|scheme| bindings::yaml::parse_yaml(scheme).unwrap()),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        };
+        let parser = || MockSecurityParser {
+            operations: vec![
+                OperationSecurity {
+                    operation: operation("listPets", "get"),
+                    requirements: vec![
+                        vec![
+                            required(
+                                "api_key",
+                                Some("type: apiKey\nin: header\nname: X-API-Key"),
+                                &[],
+                            ),
+                            required(
+                                "token",
+                                Some("type: http\nscheme: bearer\nbearerFormat: JWT"),
+                                &[],
+                            ),
+                        ],
+                        vec![required(
+                            "petstore_auth",
+                            Some("type: oauth2\nflows:\n  implicit: {}"),
+                            &["read:pets", "write:pets"],
+                        )],
+                        vec![],
+                    ],
+                    inherited: true,
+                },
+                OperationSecurity {
+                    operation: operation("createPets", "post"),
+                    requirements: vec![vec![required("legacy", None, &[])]],
+                    inherited: false,
+                },
+            ],
+        };
+        assert_eq!(
+            security(parser(), Some("listPets"))?.to_string(),
+            "Inherited from the top level `security`\n\
+             - api_key: apiKey in header `X-API-Key` and token: http bearer (JWT)\n\
+             - petstore_auth: oauth2 (implicit) with scopes read:pets, write:pets\n\
+             - (anonymous)"
+        );
+        assert_eq!(
+            security(parser(), None)?.to_string().split("\n\n").nth(1),
+            Some("# createPets (POST /pets)\n- legacy: undeclared scheme")
+        );
+        let error = security(parser(), Some("missing")).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find operation `missing`");
+        Ok(())
+    }
+
+    struct MockContentTypeParser {
+        operations: Vec<ContentTypeNode>,
+    }
+    impl ContentTypeParser for MockContentTypeParser {
+        fn get_content_type_nodes(&self) -> Result<Vec<ContentTypeNode>, OpenapiSchemerError> {
+            Ok(self.operations.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_content_types() -> Result<(), Box<dyn Error>> {
+        let operation = |method: &str, operation_id: Option<&str>, line, bodies: Vec<BodyNode>| {
+            ContentTypeNode {
+                operation: OperationNode {
+                    text: String::new(),
+                    operation_id: operation_id.map(str::to_owned),
+                    method: method.to_string(),
+                    path: "/pets".to_string(),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line,
+                    column: 20,
+                    ..Default::default()
+                },
+                bodies,
+            }
+        };
+        let body = |name: &str, media_types: &[&str]| BodyNode {
+            name: name.to_string(),
+            media_types: media_types
+                .iter()
+                .map(|media_type| media_type.to_string())
+                .collect(),
+        };
+        let parser = || MockContentTypeParser {
+            operations: vec![
+                operation(
+                    "get",
+                    Some("listPets"),
+                    5,
+                    vec![body("200", &["application/json"])],
+                ),
+                operation(
+                    "post",
+                    Some("createPets"),
+                    12,
+                    vec![
+                        body("request", &["application/xml", "application/json"]),
+                        body("201", &["application/json", "application/xml"]),
+                    ],
+                ),
+                operation(
+                    "put",
+                    None,
+                    20,
+                    vec![
+                        body("request", &["application/xml"]),
+                        body("default", &["text/plain"]),
+                    ],
+                ),
+                operation("delete", Some("deletePets"), 30, vec![]),
+            ],
+        };
+        assert_eq!(
+            content_types(parser(), false)?.to_string(),
+            "GET /pets -> listPets\n  200: application/json\n\
+             POST /pets -> createPets\n  request: application/xml, application/json\n  201: application/json, application/xml\n\
+             PUT /pets -> <none> (missing application/json, inconsistent media types)\n  request: application/xml\n  default: text/plain\n\
+             DELETE /pets -> deletePets"
+        );
+        assert!(content_types(parser(), true)?.to_string().starts_with(
+            "/spec/openapi.yaml:5:20: GET /pets -> listPets\n  200: application/json\n"
+        ));
+        Ok(())
+    }
+
+    struct MockResponseParser {
+        operations: Vec<OperationResponses>,
+    }
+    impl ResponseParser for MockResponseParser {
+        fn get_response_nodes(&self) -> Result<Vec<ResponseNode>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+
+        fn get_response(&self, _name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(None)
+        }
+
+        fn get_operation_responses(&self) -> Result<Vec<OperationResponses>, OpenapiSchemerError> {
+            Ok(self.operations.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_responses() -> Result<(), Box<dyn Error>> {
+        let operation = |method: &str, path: &str, operation_id: &str, line, codes: &[&str]| {
+            OperationResponses {
+                operation: OperationNode {
+                    text: operation_id.to_string(),
+                    operation_id: Some(operation_id.to_string()),
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line,
+                    column: 20,
+                    ..Default::default()
+                },
+                codes: codes.iter().map(|code| code.to_string()).collect(),
+            }
+        };
+        let parser = || MockResponseParser {
+            operations: vec![
+                operation("get", "/pets", "listPets", 5, &["200", "default"]),
+                operation("post", "/pets", "createPets", 12, &["201", "4XX", "400"]),
+                operation("get", "/pets/{petId}", "showPetById", 20, &["200", "404"]),
+            ],
+        };
+        assert_eq!(
+            responses(parser(), ResponsesLayout::List, true)?.to_string(),
+            "/spec/openapi.yaml:5:20: GET /pets -> listPets: 200, default\n\
+             /spec/openapi.yaml:12:20: POST /pets -> createPets: 201, 4XX, 400\n\
+             /spec/openapi.yaml:20:20: GET /pets/{petId} -> showPetById: 200, 404"
+        );
+        assert_eq!(
+            responses(parser(), ResponsesLayout::Matrix, false)?.to_string(),
+            "operation          200  201  400  404  4XX  default\n\
+             GET /pets          x                        x\n\
+             POST /pets              x    x         x\n\
+             GET /pets/{petId}  x              x"
+        );
+        assert_eq!(
+            responses(parser(), ResponsesLayout::List, false)?.format(OutputFormat::Csv),
+            "operation,200,201,400,404,4XX,default\n\
+             GET /pets,x,,,,,x\n\
+             POST /pets,,x,x,,x,\n\
+             GET /pets/{petId},x,,,x,,"
+        );
+        let json = responses(parser(), ResponsesLayout::Matrix, false)?.to_node();
+        assert_eq!(
+            json.pointer("/0/responses/default")
+                .and_then(|node| node.as_str()),
+            Some("true")
+        );
+        assert_eq!(
+            json.pointer("/0/responses/404")
+                .and_then(|node| node.as_str()),
+            Some("false")
+        );
+        Ok(())
+    }
+
+    struct MockRenamer {
+        files: Option<Vec<FileEdits>>,
+    }
+    impl OperationRenamer for MockRenamer {
+        fn get_rename_edits(
+            &self,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+            Ok(self.files.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_rename() -> Result<(), Box<dyn Error>> {
+        let edit = |line, column| TextEdit {
+            bytes: 0..0,
+            old: "listPets".to_string(),
+            new: "listAll".to_string(),
+            text: "listAll".to_string(),
+            line,
+            column,
+        };
+        let renamer = MockRenamer {
+            files: Some(vec![
+                FileEdits {
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    edits: vec![edit(12, 20), edit(30, 28)],
+                    content: String::new(),
+                },
+                FileEdits {
+                    file: PathBuf::from("/spec/links.yaml"),
+                    edits: vec![edit(4, 18)],
+                    content: String::new(),
+                },
+            ]),
+        };
+        let result = rename(renamer, "listPets", "listAll")?;
+        assert_eq!(
+            result.to_string(),
+            "/spec/openapi.yaml:12:20: listPets -> listAll\n\
+             /spec/openapi.yaml:30:28: listPets -> listAll\n\
+             /spec/links.yaml:4:18: listPets -> listAll"
+        );
+        assert_eq!(
+            result.format(OutputFormat::Yaml),
+            "- from: listPets\n  to: listAll\n  file: /spec/openapi.yaml\n  line: 12\n  column: 20\n\
+             - from: listPets\n  to: listAll\n  file: /spec/openapi.yaml\n  line: 30\n  column: 28\n\
+             - from: listPets\n  to: listAll\n  file: /spec/links.yaml\n  line: 4\n  column: 18"
+        );
+
+        let renamer = MockRenamer { files: None };
+        let error = rename(renamer, "missing", "listAll").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find operation `missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_request() -> Result<(), Box<dyn Error>> {
+        let definition = OperationDefinition {
+            path: "/pets/{petId}".to_string(),
+            method: "put".to_string(),
+            operation: bindings::yaml::parse_yaml(
+                r#"
+parameters:
+  - name: petId
+    in: path
+    required: true
+  - name: dry run
+    in: query
+  - name: X-Request-Id
+    in: header
+  - name: session
+    in: cookie
+    required: true
+requestBody:
+  content:
+    application/json:
+      schema:
+        properties:
+          name:
+            type: string
+"#,
+            )?,
+        };
+        let params = |params: &[&str]| -> Vec<String> {
+            params.iter().map(|param| param.to_string()).collect()
+        };
+        let request = build_request(
+            &definition,
+            "http://localhost:8080/v1/",
+            &params(&[
+                "petId=7",
+                "dry run=yes please",
+                "X-Request-Id=1",
+                "session=abc",
+            ]),
+            None,
+            false,
+        )?;
+        assert_eq!(
+            request.url,
+            "http://localhost:8080/v1/pets/7?dry%20run=yes%20please"
+        );
+        assert_eq!(
+            request.headers,
+            vec![
+                ("X-Request-Id".to_string(), "1".to_string()),
+                ("Cookie".to_string(), "session=abc".to_string()),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ]
+        );
+        assert_eq!(
+            request.body.as_deref(),
+            Some("{\n  \"name\": \"string\"\n}")
+        );
+
+        let error = build_request(
+            &definition,
+            "http://localhost",
+            &params(&["petId=7"]),
+            None,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Missing required cookie parameter `session`, pass it with `--param session=VALUE`"
+        );
+        let error = build_request(
+            &definition,
+            "http://localhost",
+            &params(&["owner=1"]),
+            None,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No parameter `owner` is declared for `PUT /pets/{petId}`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_server() {
+        let server = |text: &str, scope: Option<&str>| ServerNode {
+            text: text.to_string(),
+            description: None,
+            scope: scope.map(str::to_owned),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 1,
+            column: 1,
+        };
+        let definition = OperationDefinition {
+            path: "/pets".to_string(),
+            method: "post".to_string(),
+            operation: YamlNode::mapping(vec![]),
+        };
+        let servers = vec![
+            server("http://api.example.com", None),
+            server("http://pets.example.com", Some("/pets")),
+        ];
+        assert_eq!(
+            default_server(&servers, &definition).as_deref(),
+            Some("http://pets.example.com")
+        );
+        let servers = vec![
+            server("http://api.example.com", None),
+            server("http://uploads.example.com", Some("POST /pets")),
+        ];
+        assert_eq!(
+            default_server(&servers, &definition).as_deref(),
+            Some("http://uploads.example.com")
+        );
+        assert_eq!(default_server(&[], &definition), None);
+    }
+
+    struct MockServerParser {
+        servers: Vec<ServerNode>,
+    }
+    impl ServerParser for MockServerParser {
+        fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError> {
+            Ok(self.servers.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_curl() -> Result<(), Box<dyn Error>> {
+        let parser = || -> Result<MockParser, Box<dyn Error>> {
+            let mut parser = MockParser::new(vec![OperationNode {
+                operation_id: Some("updatePet".to_string()),
+                ..Default::default()
+            }]);
+            parser.definition = Some(OperationDefinition {
+                path: "/pets/{petId}".to_string(),
+                method: "put".to_string(),
+                operation: bindings::yaml::parse_yaml(
+                    r#"
+parameters:
+  - name: petId
+    in: path
+  - name: X-Trace
+    in: header
+    required: true
+requestBody:
+  content:
+    application/json:
+      example:
+        name: Rex's
+"#,
+                )?,
+            });
+            Ok(parser)
+        };
+        let servers = || MockServerParser {
+            servers: vec![ServerNode {
+                text: "http://api.example.com".to_string(),
+                description: None,
+                scope: None,
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 1,
+                column: 1,
+            }],
+        };
+        let params = vec!["petId=7".to_string(), "owner=1".to_string()];
+
+        let result = curl(parser()?, servers(), None, None, &params)?;
+        assert_eq!(
+            result.to_string(),
+            "# updatePet\n\
+             curl -X PUT 'http://api.example.com/pets/7' \\\n  \
+             -H 'X-Trace: {X-Trace}' \\\n  \
+             -H 'Content-Type: application/json' \\\n  \
+             --data '{\n  \"name\": \"Rex'\\''s\"\n}'"
+        );
+
+        let result = curl(
+            parser()?,
+            servers(),
+            Some("updatePet"),
+            Some("http://localhost".to_string()),
+            &[],
+        )?;
+        assert!(result
+            .to_string()
+            .starts_with("curl -X PUT 'http://localhost/pets/{petId}' \\\n"));
+
+        let error = curl(parser()?, servers(), Some("updatePet"), None, &params).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No parameter `owner` is declared for `PUT /pets/{petId}`"
+        );
         Ok(())
     }
 }
@@ -0,0 +1,547 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::bindings::yaml::YamlNode;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Plain,
+    Json,
+    Yaml,
+    Csv,
+    Tsv,
+}
+
+/// The order list commands print their entries in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// The order they are written in the spec
+    Document,
+    /// Alphabetical order of their names
+    Alpha,
+}
+
+/// List results whose entries can be printed in another order than the one
+/// they are written in.
+pub trait SortedList: Sized {
+    /// Sorts the entries by name. Entries with the same name keep their
+    /// order.
+    fn sort_by_name(&mut self);
+
+    fn sorted(mut self, order: SortOrder) -> Self {
+        if order == SortOrder::Alpha {
+            self.sort_by_name();
+        }
+        self
+    }
+}
+
+/// Results that can be printed for people (`Display`) as well as for scripts
+/// and editors.
+pub trait OutputFormatter: Display {
+    /// The structured representation used by the machine readable formats.
+    fn to_node(&self) -> YamlNode;
+
+    /// The rows of the tabular formats as column names and values. By
+    /// default every item of a list is a row, and any other result a single
+    /// row.
+    fn rows(&self) -> Vec<Vec<(String, String)>> {
+        match self.to_node().as_sequence() {
+            Some(items) => items.iter().map(row).collect(),
+            None => vec![row(&self.to_node())],
+        }
+    }
+
+    fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Plain => self.to_string(),
+            OutputFormat::Json => self.to_node().to_json(),
+            OutputFormat::Yaml => self.to_node().to_yaml().trim_end().to_string(),
+            OutputFormat::Csv => table(&self.rows(), ','),
+            OutputFormat::Tsv => table(&self.rows(), '\t'),
+        }
+    }
+}
+
+/// Results made of many items, such as the list commands, which can be written
+/// an item at a time. Huge specs are then printed as the items are turned into
+/// text instead of after all of them are joined into one string.
+pub trait ListFormatter: OutputFormatter {
+    /// The plain output, a line per item.
+    fn lines(&self) -> Box<dyn Iterator<Item = String> + '_>;
+
+    /// The structured representation, a node per item.
+    fn nodes(&self) -> Box<dyn Iterator<Item = YamlNode> + '_>;
+
+    /// Writes the same text as `format` followed by a newline to `writer`.
+    fn write(&self, format: OutputFormat, writer: &mut dyn Write) -> io::Result<()> {
+        if !ListWriter::streams(format) {
+            return writeln!(writer, "{}", self.format(format));
+        }
+        let mut list = ListWriter::new(format, writer);
+        match format {
+            OutputFormat::Plain => self.lines().for_each(|line| list.line(line)),
+            _ => self.nodes().for_each(|node| list.node(node)),
+        }
+        list.finish()
+    }
+}
+
+/// Writes the items of a list as they are found, so the whole list is never
+/// held in memory. Writing stops at the first error, which `finish` returns.
+pub struct ListWriter<'a> {
+    format: OutputFormat,
+    writer: &'a mut dyn Write,
+    empty: bool,
+    error: Option<io::Error>,
+}
+
+impl<'a> ListWriter<'a> {
+    /// Whether lists can be written an item at a time in `format`. The header
+    /// of the tabular formats needs the columns of every row, so tables are
+    /// written at once.
+    pub fn streams(format: OutputFormat) -> bool {
+        !matches!(format, OutputFormat::Csv | OutputFormat::Tsv)
+    }
+
+    /// A writer of a list in `format`, which must be one that `streams`.
+    pub fn new(format: OutputFormat, writer: &'a mut dyn Write) -> Self {
+        Self {
+            format,
+            writer,
+            empty: true,
+            error: None,
+        }
+    }
+
+    /// Writes an item, its `line` in the plain format and its `node` in the
+    /// others. Only the one that is written is built.
+    pub fn item(&mut self, line: impl FnOnce() -> String, node: impl FnOnce() -> YamlNode) {
+        match self.format {
+            OutputFormat::Plain => self.line(line()),
+            _ => self.node(node()),
+        }
+    }
+
+    fn line(&mut self, line: String) {
+        self.write_with(|writer, _| writeln!(writer, "{}", line));
+    }
+
+    fn node(&mut self, node: YamlNode) {
+        let format = self.format;
+        self.write_with(|writer, first| match format {
+            OutputFormat::Json => {
+                // An item is written the way it is nested in the list,
+                // without the brackets of the list around it.
+                let item = YamlNode::sequence(vec![node]).to_json();
+                let item = &item[2..item.len() - 2];
+                write!(writer, "{}{}", if first { "[\n" } else { ",\n" }, item)
+            }
+            _ => write!(writer, "{}", YamlNode::sequence(vec![node]).to_yaml()),
+        });
+    }
+
+    fn write_with(&mut self, write: impl FnOnce(&mut dyn Write, bool) -> io::Result<()>) {
+        if self.error.is_none() {
+            self.error = write(&mut *self.writer, self.empty).err();
+            self.empty = false;
+        }
+    }
+
+    /// Ends the list, returning the first error met while writing it.
+    pub fn finish(self) -> io::Result<()> {
+        let ListWriter {
+            format,
+            writer,
+            empty,
+            error,
+        } = self;
+        if let Some(error) = error {
+            return Err(error);
+        }
+        match (format, empty) {
+            (OutputFormat::Plain, true) => writeln!(writer),
+            (OutputFormat::Json, true) | (OutputFormat::Yaml, true) => writeln!(writer, "[]"),
+            (OutputFormat::Json, false) => writeln!(writer, "\n]"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A mapping as a row with a column per key, anything else as a row with a
+/// single `value` column.
+fn row(node: &YamlNode) -> Vec<(String, String)> {
+    match node.as_mapping() {
+        Some(pairs) => pairs
+            .iter()
+            .map(|pair| {
+                let key = pair.key.as_str().unwrap_or_default().to_string();
+                (key, cell(&pair.value))
+            })
+            .collect(),
+        None => vec![("value".to_string(), cell(node))],
+    }
+}
+
+/// Scalars as their text, lists of scalars joined with commas and anything
+/// else as JSON.
+fn cell(node: &YamlNode) -> String {
+    if node.json_type() == "null" {
+        return String::new();
+    }
+    if let Some(text) = node.as_str() {
+        return text.to_string();
+    }
+    let items = node.as_sequence().and_then(|items| {
+        items
+            .iter()
+            .map(YamlNode::as_str)
+            .collect::<Option<Vec<&str>>>()
+    });
+    match items {
+        Some(items) => items.join(", "),
+        None => node.to_json(),
+    }
+}
+
+/// `rows` as a header followed by a line per row, with the columns of every
+/// row in the order they first appear. CSV cells are quoted when needed,
+/// TSV cells have their tabs and line breaks replaced by spaces.
+fn table(rows: &[Vec<(String, String)>], separator: char) -> String {
+    let mut columns: Vec<&str> = vec![];
+    for (column, _) in rows.iter().flatten() {
+        if !columns.contains(&column.as_str()) {
+            columns.push(column);
+        }
+    }
+    if columns.is_empty() {
+        return String::new();
+    }
+    let escape = |text: &str| match separator {
+        ',' => csv_field(text),
+        _ => text.replace(['\t', '\n', '\r'], " "),
+    };
+    let line = |cells: Vec<String>| cells.join(&separator.to_string());
+    let mut lines = vec![line(columns.iter().map(|column| escape(column)).collect())];
+    for row in rows {
+        let cells = columns
+            .iter()
+            .map(|column| {
+                row.iter()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, value)| escape(value))
+                    .unwrap_or_default()
+            })
+            .collect();
+        lines.push(line(cells));
+    }
+    lines.join("\n")
+}
+
+/// Writes `content` to `path`, creating the directories on the way there.
+pub fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    let created = match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent),
+        None => Ok(()),
+    };
+    created
+        .and_then(|_| std::fs::write(path, content))
+        .map_err(|cause| format!("Could not write `{}`: {}", path.display(), cause))
+}
+
+/// A single named result of a list command and where it was defined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListEntry {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ListEntry {
+    pub fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![
+            ("name", YamlNode::string(&self.name)),
+            ("file", YamlNode::string(&self.file.display().to_string())),
+            ("line", YamlNode::integer(self.line)),
+            ("column", YamlNode::integer(self.column)),
+        ])
+    }
+
+    /// The line of the plain output, prefixed with its location when
+    /// `locations` is set.
+    pub fn line(&self, locations: bool) -> String {
+        match locations {
+            true => self.with_location(),
+            false => self.name.to_owned(),
+        }
+    }
+
+    /// The entry prefixed with `file:line:column:` like compiler diagnostics,
+    /// which most editors can jump to.
+    pub fn with_location(&self) -> String {
+        format!(
+            "{}:{}:{}: {}",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.name
+        )
+    }
+}
+
+/// An entry of a tree result and the entries nested below it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new(label: &str, children: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            label: label.to_string(),
+            children,
+        }
+    }
+
+    pub fn leaf(label: &str) -> TreeNode {
+        TreeNode::new(label, vec![])
+    }
+
+    fn to_node(&self) -> YamlNode {
+        let mut pairs = vec![("name", YamlNode::string(&self.label))];
+        if !self.children.is_empty() {
+            let children = self.children.iter().map(TreeNode::to_node).collect();
+            pairs.push(("children", YamlNode::sequence(children)));
+        }
+        YamlNode::mapping(pairs)
+    }
+
+    /// The lines of the children, drawn below their parent with box-drawing
+    /// characters. `prefix` continues the lines of the ancestors.
+    fn child_lines(&self, prefix: &str, lines: &mut Vec<String>) {
+        for (index, child) in self.children.iter().enumerate() {
+            let last = index + 1 == self.children.len();
+            let (branch, indent) = match last {
+                true => ("└── ", "    "),
+                false => ("├── ", "│   "),
+            };
+            lines.push(format!("{}{}{}", prefix, branch, child.label));
+            child.child_lines(&format!("{}{}", prefix, indent), lines);
+        }
+    }
+}
+
+/// The `--tree` output of list commands, each entry followed by what is
+/// nested below it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TreeResult {
+    roots: Vec<TreeNode>,
+}
+
+impl TreeResult {
+    pub fn new(roots: Vec<TreeNode>) -> TreeResult {
+        TreeResult { roots }
+    }
+}
+
+impl SortedList for TreeResult {
+    /// Only the entries themselves are sorted, what is nested below them
+    /// keeps the order it is written in.
+    fn sort_by_name(&mut self) {
+        self.roots.sort_by(|a, b| a.label.cmp(&b.label));
+    }
+}
+
+impl Display for TreeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for root in &self.roots {
+            lines.push(root.label.to_owned());
+            root.child_lines("", &mut lines);
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for TreeResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.roots.iter().map(TreeNode::to_node).collect())
+    }
+}
+
+/// `field` quoted when it contains a comma, quote or line break.
+pub fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fmt::Display, path::PathBuf};
+
+    use crate::bindings::yaml::YamlNode;
+
+    use super::*;
+
+    struct Entries(Vec<ListEntry>);
+
+    impl Display for Entries {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let names: Vec<&str> = self.0.iter().map(|entry| entry.name.as_str()).collect();
+            write!(f, "{}", names.join("\n"))
+        }
+    }
+
+    impl OutputFormatter for Entries {
+        fn to_node(&self) -> YamlNode {
+            YamlNode::sequence(self.0.iter().map(ListEntry::to_node).collect())
+        }
+    }
+
+    impl ListFormatter for Entries {
+        fn lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+            Box::new(self.0.iter().map(|entry| entry.name.to_owned()))
+        }
+
+        fn nodes(&self) -> Box<dyn Iterator<Item = YamlNode> + '_> {
+            Box::new(self.0.iter().map(ListEntry::to_node))
+        }
+    }
+
+    #[test]
+    fn test_format() -> Result<(), Box<dyn Error>> {
+        let entries = Entries(vec![ListEntry {
+            name: "listPets".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 12,
+            column: 19,
+        }]);
+        assert_eq!(entries.format(OutputFormat::Plain), "listPets");
+        assert_eq!(
+            entries.format(OutputFormat::Yaml),
+            "- name: listPets\n  file: /spec/openapi.yaml\n  line: 12\n  column: 19"
+        );
+        assert_eq!(
+            entries.format(OutputFormat::Json),
+            r#"[
+  {
+    "name": "listPets",
+    "file": "/spec/openapi.yaml",
+    "line": 12,
+    "column": 19
+  }
+]"#
+        );
+        assert_eq!(
+            entries.format(OutputFormat::Csv),
+            "name,file,line,column\nlistPets,/spec/openapi.yaml,12,19"
+        );
+        let row = |name: &str| vec![("name".to_string(), name.to_string())];
+        assert_eq!(
+            table(&[row("a, \"b\""), row("c\td")], ','),
+            "name\n\"a, \"\"b\"\"\"\nc\td"
+        );
+        assert_eq!(table(&[row("a, b"), row("c\td")], '\t'), "name\na, b\nc d");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree() -> Result<(), Box<dyn Error>> {
+        let result = TreeResult::new(vec![
+            TreeNode::new(
+                "/pets",
+                vec![
+                    TreeNode::new("get", vec![TreeNode::leaf("listPets")]),
+                    TreeNode::new("post", vec![TreeNode::leaf("createPets")]),
+                ],
+            ),
+            TreeNode::leaf("/health"),
+        ]);
+        assert_eq!(
+            result.to_string(),
+            "/pets\n├── get\n│   └── listPets\n└── post\n    └── createPets\n/health"
+        );
+        assert_eq!(
+            result.sorted(SortOrder::Alpha).format(OutputFormat::Yaml),
+            r#"- name: /health
+- name: /pets
+  children:
+    - name: get
+      children:
+        - name: listPets
+    - name: post
+      children:
+        - name: createPets"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write() -> Result<(), Box<dyn Error>> {
+        let entry = |name: &str, line| ListEntry {
+            name: name.to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 7,
+        };
+        let lists = [
+            Entries(vec![]),
+            Entries(vec![entry("listPets", 12)]),
+            Entries(vec![entry("listPets", 12), entry("showPetById", 30)]),
+        ];
+        for entries in lists {
+            for format in [
+                OutputFormat::Plain,
+                OutputFormat::Json,
+                OutputFormat::Yaml,
+                OutputFormat::Csv,
+                OutputFormat::Tsv,
+            ] {
+                let mut written = vec![];
+                entries.write(format, &mut written)?;
+                assert_eq!(
+                    String::from_utf8(written)?,
+                    format!("{}\n", entries.format(format))
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_writer() -> Result<(), Box<dyn Error>> {
+        let mut written = vec![];
+        let mut list = ListWriter::new(OutputFormat::Plain, &mut written);
+        list.item(|| "listPets".to_string(), || unreachable!());
+        list.finish()?;
+        assert_eq!(String::from_utf8(written)?, "listPets\n");
+
+        struct Closed;
+        impl Write for Closed {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut closed = Closed;
+        let mut list = ListWriter::new(OutputFormat::Json, &mut closed);
+        list.item(String::new, YamlNode::null);
+        list.item(String::new, YamlNode::null);
+        let error = list.finish().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        Ok(())
+    }
+}
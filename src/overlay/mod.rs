@@ -0,0 +1,78 @@
+use std::{fmt::Display, path::Path};
+
+use anyhow::Context;
+
+use crate::{
+    bindings::{
+        overlay::{Overlay, OverlayApplier},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct ApplyResult {
+    document: YamlNode,
+}
+
+impl ApplyResult {
+    pub fn new(document: YamlNode) -> ApplyResult {
+        ApplyResult { document }
+    }
+}
+
+impl Display for ApplyResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ApplyResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+/// Reads an OpenAPI Overlay document, see `Overlay`.
+pub fn read_overlay(path: &Path) -> Result<Overlay, OpenapiSchemerError> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read overlay `{}`", path.display()))
+        .and_then(|content| Overlay::parse(&content))
+        .map_err(|error| OpenapiSchemerError::from_anyhow(error, OpenapiSchemerError::OverlayApply))
+}
+
+pub fn apply<T: OverlayApplier>(applier: T) -> Result<ApplyResult, OpenapiSchemerError> {
+    let document = applier.apply()?;
+    Ok(ApplyResult::new(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    struct MockApplier {
+        document: YamlNode,
+    }
+    impl OverlayApplier for MockApplier {
+        fn apply(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn Error>> {
+        let document = YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.3"))]);
+        let result = apply(MockApplier { document })?;
+        assert_eq!(result.to_string(), "openapi: 3.0.3");
+        assert_eq!(
+            read_overlay(Path::new("missing-overlay.yaml"))
+                .unwrap_err()
+                .to_string(),
+            "Failed to read overlay `missing-overlay.yaml`"
+        );
+        Ok(())
+    }
+}
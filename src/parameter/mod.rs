@@ -0,0 +1,200 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        parameter::{ParameterNode, ParameterParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    parameters: Vec<ParameterNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(parameters: Vec<ParameterNode>, locations: bool) -> ListResult {
+        ListResult {
+            parameters,
+            locations,
+        }
+    }
+
+    fn entry(parameter: &ParameterNode) -> ListEntry {
+        ListEntry {
+            name: parameter.text.to_owned(),
+            file: parameter.file.to_owned(),
+            line: parameter.line,
+            column: parameter.column,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.parameters.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let mut entry = ListResult::entry(parameter);
+                entry.name = match parameter.required {
+                    true => format!("{} ({}, required)", parameter.text, parameter.location),
+                    false => format!("{} ({})", parameter.text, parameter.location),
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&parameter.text)),
+                    ("parameter", YamlNode::string(&parameter.name)),
+                    ("in", YamlNode::string(&parameter.location)),
+                    ("required", YamlNode::boolean(parameter.required)),
+                    (
+                        "file",
+                        YamlNode::string(&parameter.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(parameter.line)),
+                    ("column", YamlNode::integer(parameter.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(parameters)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    name: String,
+    parameter: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(name: String, parameter: YamlNode) -> ShowResult {
+        ShowResult { name, parameter }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(self.name.as_str(), self.parameter.to_owned())])
+    }
+}
+
+pub fn list<T: ParameterParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_parameter_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+pub fn show<T: ParameterParser>(parser: T, name: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_parameter(name)? {
+        Some(parameter) => Ok(ShowResult::new(name.to_string(), parameter)),
+        None => Err(OpenapiSchemerError::ParameterShow(format!(
+            "Could not find parameter `{}`",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::output::OutputFormat;
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<ParameterNode>,
+        parameter: Option<YamlNode>,
+    }
+    impl ParameterParser for MockParser {
+        fn get_parameter_nodes(&self) -> Result<Vec<ParameterNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_parameter(&self, _name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.parameter.to_owned())
+        }
+    }
+
+    fn parser() -> MockParser {
+        MockParser {
+            nodes: vec![
+                ParameterNode {
+                    text: "limit".to_string(),
+                    name: "limit".to_string(),
+                    location: "query".to_string(),
+                    required: false,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 4,
+                    column: 5,
+                },
+                ParameterNode {
+                    text: "PetId".to_string(),
+                    name: "petId".to_string(),
+                    location: "path".to_string(),
+                    required: true,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 8,
+                    column: 5,
+                },
+            ],
+            parameter: None,
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let result = list(parser(), false)?;
+        assert_eq!(result.to_string(), "limit (query)\nPetId (path, required)");
+        assert_eq!(
+            list(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:4:5: limit (query)\n/spec/openapi.yaml:8:5: PetId (path, required)"
+        );
+        assert!(result
+            .format(OutputFormat::Yaml)
+            .starts_with("- name: limit\n  parameter: limit\n  in: query\n  required: false\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = parser();
+        parser.parameter = Some(YamlNode::mapping(vec![("in", YamlNode::string("query"))]));
+        assert_eq!(show(parser, "limit")?.to_string(), "limit:\n  in: query");
+        let error = show(self::parser(), "missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find parameter `missing`");
+        Ok(())
+    }
+}
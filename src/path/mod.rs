@@ -1,63 +1,603 @@
 use anyhow::Result;
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
-use crate::{bindings::path::PathParser, error::OpenapiSchemerError};
+use crate::{
+    bindings::{
+        path::{PathMethod, PathParser},
+        rename::{PathRename, PathRenamer},
+        yaml::{percent_decode, YamlNode},
+    },
+    error::OpenapiSchemerError,
+    operation::RenameResult,
+    output::{
+        ListEntry, ListFormatter, ListWriter, OutputFormatter, SortedList, TreeNode, TreeResult,
+    },
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct ListResult {
-    entries: Vec<String>,
+    entries: Vec<ListEntry>,
+    locations: bool,
 }
 
 impl ListResult {
-    pub fn new(list: Vec<String>) -> ListResult {
-        ListResult { entries: list }
+    pub fn new(list: Vec<ListEntry>, locations: bool) -> ListResult {
+        ListResult {
+            entries: list,
+            locations,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
     }
 }
 
 impl Display for ListResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.entries.join("\n"))
+        let lines: Vec<String> = self.lines().collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.nodes().collect())
+    }
+}
+
+impl ListFormatter for ListResult {
+    fn lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.entries.iter().map(|entry| entry.line(self.locations)))
+    }
+
+    fn nodes(&self) -> Box<dyn Iterator<Item = YamlNode> + '_> {
+        Box::new(self.entries.iter().map(ListEntry::to_node))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ShowResult {
+    path: String,
+    methods: Vec<PathMethod>,
+}
+
+impl ShowResult {
+    pub fn new(path: String, methods: Vec<PathMethod>) -> ShowResult {
+        ShowResult { path, methods }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for method in &self.methods {
+            lines.push(format!(
+                "{} {}",
+                method.method.to_uppercase(),
+                method.operation_id.as_deref().unwrap_or("-")
+            ));
+            if let Some(summary) = &method.summary {
+                lines.push(format!("  summary: {}", summary));
+            }
+            if !method.tags.is_empty() {
+                lines.push(format!("  tags: {}", method.tags.join(", ")));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
     }
 }
 
-pub fn list<T: PathParser>(parser: T) -> Result<ListResult, OpenapiSchemerError> {
-    let nodes = parser.get_path_nodes().unwrap();
-    let node_texts = nodes
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| {
+                let optional = |value: &Option<String>| match value {
+                    Some(value) => YamlNode::string(value),
+                    None => YamlNode::null(),
+                };
+                YamlNode::mapping(vec![
+                    ("method", YamlNode::string(&method.method)),
+                    ("operationId", optional(&method.operation_id)),
+                    ("summary", optional(&method.summary)),
+                    (
+                        "tags",
+                        YamlNode::sequence(
+                            method
+                                .tags
+                                .iter()
+                                .map(|tag| YamlNode::string(tag))
+                                .collect(),
+                        ),
+                    ),
+                ])
+            })
+            .collect();
+        YamlNode::mapping(vec![
+            ("path", YamlNode::string(&self.path)),
+            ("methods", YamlNode::sequence(methods)),
+        ])
+    }
+}
+
+/// A path template that a concrete path maps to, with the value of each of
+/// its parameters.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PathMatch {
+    pub path: String,
+    pub parameters: Vec<(String, String)>,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchResult {
+    matches: Vec<PathMatch>,
+}
+
+impl MatchResult {
+    pub fn new(matches: Vec<PathMatch>) -> MatchResult {
+        MatchResult { matches }
+    }
+}
+
+impl Display for MatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        for found in &self.matches {
+            lines.push(found.path.to_owned());
+            for (name, value) in &found.parameters {
+                lines.push(format!("  {}: {}", name, value));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for MatchResult {
+    fn to_node(&self) -> YamlNode {
+        let matches = self
+            .matches
+            .iter()
+            .map(|found| {
+                let parameters = found
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), YamlNode::string(value)))
+                    .collect();
+                YamlNode::mapping(vec![
+                    ("path", YamlNode::string(&found.path)),
+                    ("parameters", YamlNode::mapping(parameters)),
+                    ("file", YamlNode::string(&found.file.display().to_string())),
+                    ("line", YamlNode::integer(found.line)),
+                    ("column", YamlNode::integer(found.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(matches)
+    }
+}
+
+/// The parameter values `path` gives the template, if it matches. Each
+/// `{name}` stands for a non-empty part of one segment, so templates like
+/// `/files/{name}.{extension}` match as well.
+pub(crate) fn match_template(template: &str, path: &str) -> Option<Vec<(String, String)>> {
+    let template: Vec<&str> = template.split('/').collect();
+    let segments: Vec<&str> = path.split('/').collect();
+    if template.len() != segments.len() {
+        return None;
+    }
+    let mut parameters = vec![];
+    for (expected, segment) in template.iter().zip(segments) {
+        let mut parts = expected.split('{');
+        let mut rest = segment.strip_prefix(parts.next().unwrap_or_default())?;
+        let mut parts = parts.peekable();
+        while let Some(part) = parts.next() {
+            let (name, literal) = part.split_once('}')?;
+            let end = match (literal.is_empty(), parts.peek().is_none()) {
+                (true, true) => rest.len(),
+                // Two parameters without text between them can't be told
+                // apart.
+                (true, false) => return None,
+                (false, _) => rest.find(literal)?,
+            };
+            if end == 0 {
+                return None;
+            }
+            parameters.push((name.to_string(), percent_decode(&rest[..end])));
+            rest = &rest[end + literal.len()..];
+        }
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+    Some(parameters)
+}
+
+/// The path of `url`, without its scheme, host, query and fragment.
+pub(crate) fn url_path(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|index| &rest[index..]).unwrap_or("/"),
+        None => path,
+    }
+}
+
+/// The path templates `url` maps to, with the fewest parameters first so
+/// `/pets/mine` comes before `/pets/{petId}`. The scheme, host, query and
+/// fragment of `url` are ignored.
+pub fn matches<T: PathParser>(parser: T, url: &str) -> Result<MatchResult, OpenapiSchemerError> {
+    let path = url_path(url);
+    let mut matches: Vec<PathMatch> = parser
+        .get_path_nodes()?
         .into_iter()
-        .map(|node| node.text.to_string())
+        .filter_map(|node| {
+            let parameters = match_template(&node.text, path)?;
+            Some(PathMatch {
+                path: node.text,
+                parameters,
+                file: node.file,
+                line: node.line,
+                column: node.column,
+            })
+        })
         .collect();
-    Ok(ListResult::new(node_texts))
+    if matches.is_empty() {
+        return Err(OpenapiSchemerError::PathMatch(format!(
+            "No path matches `{}`",
+            path
+        )));
+    }
+    matches.sort_by_key(|found| found.parameters.len());
+    Ok(MatchResult::new(matches))
+}
+
+pub fn list<T: PathParser>(parser: T, locations: bool) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_path_nodes()?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(ListResult::new(entries, locations))
+}
+
+/// Writes the paths of the spec to `list` as they are found, the same entries
+/// `list` returns in document order.
+pub fn stream<T: PathParser>(
+    parser: T,
+    locations: bool,
+    list: &mut ListWriter,
+) -> Result<(), OpenapiSchemerError> {
+    parser.for_each_path_node(&mut |node| {
+        let entry = ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        };
+        list.item(|| entry.line(locations), || entry.to_node())
+    })
+}
+
+/// The paths with their methods below them, and the operationId of each
+/// method below it.
+pub fn tree<T: PathParser>(parser: T) -> Result<TreeResult, OpenapiSchemerError> {
+    let nodes = parser.get_path_nodes()?;
+    let mut roots = vec![];
+    for node in nodes {
+        let methods = parser
+            .get_path_methods(&node.text)
+            .map_err(|error| OpenapiSchemerError::PathList(error.to_string()))?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|method| {
+                let operation_id = method.operation_id.as_deref().map(TreeNode::leaf);
+                TreeNode::new(
+                    &method.method.to_uppercase(),
+                    operation_id.into_iter().collect(),
+                )
+            })
+            .collect();
+        roots.push(TreeNode::new(&node.text, methods));
+    }
+    Ok(TreeResult::new(roots))
+}
+
+pub fn show<T: PathParser>(parser: T, path: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_path_methods(path)? {
+        Some(methods) => Ok(ShowResult::new(path.to_string(), methods)),
+        None => Err(OpenapiSchemerError::PathShow(format!(
+            "Could not find path `{}`",
+            path
+        ))),
+    }
+}
+
+pub fn rename<T: PathRenamer>(
+    renamer: T,
+    rename: &PathRename,
+) -> Result<RenameResult, OpenapiSchemerError> {
+    match (renamer.get_rename_edits(rename)?, rename) {
+        (Some(files), _) => Ok(RenameResult::new(files)),
+        (None, PathRename::Path { from, .. }) => Err(OpenapiSchemerError::PathRename(format!(
+            "Could not find path `{}`",
+            from
+        ))),
+        (None, PathRename::Prefix(_)) => Ok(RenameResult::new(vec![])),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
+    use std::{collections::HashMap, error::Error, path::PathBuf};
 
-    use crate::bindings::path::PathNode;
+    use crate::{
+        bindings::{
+            edit::{FileEdits, TextEdit},
+            path::{PathNode, TreeSitterPathParser},
+        },
+        content::ContentProviderMap,
+        output::OutputFormat,
+    };
 
     use super::*;
 
     struct MockParser {
         nodes: Vec<PathNode>,
+        methods: Option<Vec<PathMethod>>,
     }
     impl MockParser {
         fn new(nodes: Vec<PathNode>) -> MockParser {
-            MockParser { nodes }
+            MockParser {
+                nodes,
+                methods: None,
+            }
         }
     }
     impl PathParser for MockParser {
         fn get_path_nodes(&self) -> Result<Vec<PathNode>, OpenapiSchemerError> {
             Ok(self.nodes.to_owned())
         }
+
+        fn get_path_methods(
+            &self,
+            _path: &str,
+        ) -> Result<Option<Vec<PathMethod>>, OpenapiSchemerError> {
+            Ok(self.methods.to_owned())
+        }
     }
 
     #[test]
     fn test_list() -> Result<(), Box<dyn Error>> {
         let parser = MockParser::new(vec![PathNode {
             text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        let result = list(parser, false)?;
+        assert_eq!(
+            result,
+            ListResult::new(
+                vec![ListEntry {
+                    name: "test1".to_string(),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 5,
+                }],
+                false
+            )
+        );
+        assert_eq!(result.to_string(), "test1");
+
+        let parser = MockParser::new(vec![PathNode {
+            text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        assert_eq!(
+            list(parser, true)?.to_string(),
+            "/spec/openapi.yaml:3:5: test1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> Result<(), Box<dyn Error>> {
+        let parser = || {
+            let node = |text: &str, line| PathNode {
+                text: text.to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line,
+                column: 3,
+            };
+            MockParser::new(vec![node("/pets", 4), node("/pets/{petId}", 8)])
+        };
+        for (format, locations) in [
+            (OutputFormat::Plain, false),
+            (OutputFormat::Plain, true),
+            (OutputFormat::Json, false),
+            (OutputFormat::Yaml, false),
+        ] {
+            let mut streamed = vec![];
+            let mut writer = ListWriter::new(format, &mut streamed);
+            stream(parser(), locations, &mut writer)?;
+            writer.finish()?;
+            let mut written = vec![];
+            list(parser(), locations)?.write(format, &mut written)?;
+            assert_eq!(String::from_utf8(streamed)?, String::from_utf8(written)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_unloadable_ref() {
+        let contents = HashMap::from([(
+            PathBuf::from("#"),
+            "paths:\n  $ref: missing.yaml\n".to_string(),
+        )]);
+        let parser = TreeSitterPathParser::new(Box::new(ContentProviderMap::from_map(contents)));
+        let error = list(parser, false).unwrap_err();
+        assert!(matches!(error, OpenapiSchemerError::ContentLoad(_)));
+    }
+
+    #[test]
+    fn test_list_aliases() -> Result<(), Box<dyn Error>> {
+        let contents = HashMap::from([(
+            PathBuf::from("#"),
+            "shared: &shared\n  /health:\n    get: {}\npaths:\n  <<: *shared\n  /pets:\n    get: {}\n"
+                .to_string(),
+        )]);
+        let parser = TreeSitterPathParser::new(Box::new(ContentProviderMap::from_map(contents)));
+        assert_eq!(list(parser, false)?.to_string(), "/health\n/pets");
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches() -> Result<(), Box<dyn Error>> {
+        let parser = || {
+            let node = |text: &str, line| PathNode {
+                text: text.to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line,
+                column: 3,
+            };
+            MockParser::new(vec![
+                node("/pets/{petId}/toys/{toyId}", 4),
+                node("/pets/{petId}", 8),
+                node("/pets/mine", 12),
+                node("/files/{name}.{extension}", 16),
+            ])
+        };
+        assert_eq!(
+            matches(parser(), "/pets/123/toys/9")?.to_string(),
+            "/pets/{petId}/toys/{toyId}\n  petId: 123\n  toyId: 9"
+        );
+        assert_eq!(
+            matches(parser(), "https://api.example.com/pets/mine?limit=10")?.to_string(),
+            "/pets/mine\n/pets/{petId}\n  petId: mine"
+        );
+        assert_eq!(
+            matches(parser(), "/files/my%20report.tar.gz")?.to_string(),
+            "/files/{name}.{extension}\n  name: my report\n  extension: tar.gz"
+        );
+        assert_eq!(
+            matches(parser(), "/pets/").unwrap_err().to_string(),
+            "No path matches `/pets/`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.methods = Some(vec![
+            PathMethod {
+                method: "get".to_string(),
+                operation_id: Some("listPets".to_string()),
+                summary: Some("List all pets".to_string()),
+                tags: vec!["pets".to_string()],
+            },
+            PathMethod {
+                method: "post".to_string(),
+                operation_id: None,
+                summary: None,
+                tags: vec![],
+            },
+        ]);
+        let result = show(parser, "/pets")?;
+        assert_eq!(
+            result.to_string(),
+            "GET listPets\n  summary: List all pets\n  tags: pets\nPOST -"
+        );
+
+        let parser = MockParser::new(vec![]);
+        let error = show(parser, "/missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find path `/missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![PathNode {
+            text: "/pets".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 3,
         }]);
-        let result = list(parser)?;
-        assert_eq!(result, ListResult::new(vec!["test1".to_string()]));
+        parser.methods = Some(vec![
+            PathMethod {
+                method: "get".to_string(),
+                operation_id: Some("listPets".to_string()),
+                summary: None,
+                tags: vec![],
+            },
+            PathMethod {
+                method: "post".to_string(),
+                operation_id: None,
+                summary: None,
+                tags: vec![],
+            },
+        ]);
+        assert_eq!(
+            tree(parser)?.to_string(),
+            "/pets\n├── GET\n│   └── listPets\n└── POST"
+        );
+        Ok(())
+    }
+
+    struct MockRenamer {
+        files: Option<Vec<FileEdits>>,
+    }
+    impl PathRenamer for MockRenamer {
+        fn get_rename_edits(
+            &self,
+            _rename: &PathRename,
+        ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+            Ok(self.files.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_rename() -> Result<(), Box<dyn Error>> {
+        let renamer = MockRenamer {
+            files: Some(vec![FileEdits {
+                file: PathBuf::from("/spec/openapi.yaml"),
+                edits: vec![TextEdit {
+                    bytes: 0..0,
+                    old: "/pets".to_string(),
+                    new: "/v2/pets".to_string(),
+                    text: "/v2/pets".to_string(),
+                    line: 4,
+                    column: 3,
+                }],
+                content: String::new(),
+            }]),
+        };
+        let prefix = PathRename::Prefix("/v2".to_string());
+        assert_eq!(
+            rename(renamer, &prefix)?.to_string(),
+            "/spec/openapi.yaml:4:3: /pets -> /v2/pets"
+        );
+
+        let renamer = MockRenamer { files: None };
+        let path = PathRename::Path {
+            from: "/missing".to_string(),
+            to: "/found".to_string(),
+        };
+        let error = rename(renamer, &path).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find path `/missing`");
         Ok(())
     }
 }
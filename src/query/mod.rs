@@ -0,0 +1,151 @@
+use std::{fmt::Display, path::Path};
+
+use crate::{
+    bindings::{
+        query::{QueryCapture, QueryParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QueryResult {
+    captures: Vec<QueryCapture>,
+}
+
+impl QueryResult {
+    pub fn new(captures: Vec<QueryCapture>) -> QueryResult {
+        QueryResult { captures }
+    }
+}
+
+impl Display for QueryResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.captures.is_empty() {
+            return write!(f, "No captures found");
+        }
+        // Captures spanning several lines are cut down to their first one,
+        // the structured formats keep the whole text.
+        let lines: Vec<String> = self
+            .captures
+            .iter()
+            .map(|capture| {
+                let mut text = capture.text.lines().next().unwrap_or_default().to_string();
+                if capture.text.contains('\n') {
+                    text.push_str(" ...");
+                }
+                format!(
+                    "{}:{}:{}: @{} {}",
+                    capture.file.display(),
+                    capture.line,
+                    capture.column,
+                    capture.name,
+                    text
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for QueryResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.captures
+                .iter()
+                .map(|capture| {
+                    YamlNode::mapping(vec![
+                        ("capture", YamlNode::string(&capture.name)),
+                        ("text", YamlNode::string(&capture.text)),
+                        (
+                            "file",
+                            YamlNode::string(&capture.file.display().to_string()),
+                        ),
+                        ("line", YamlNode::integer(capture.line)),
+                        ("column", YamlNode::integer(capture.column)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Reads the query of `query --file`.
+pub fn read_query(path: &Path) -> Result<String, OpenapiSchemerError> {
+    std::fs::read_to_string(path).map_err(|error| {
+        OpenapiSchemerError::Query(format!(
+            "Failed to read query `{}`: {}",
+            path.display(),
+            error
+        ))
+    })
+}
+
+pub fn query<T: QueryParser>(
+    parser: T,
+    source: &str,
+    all_files: bool,
+) -> Result<QueryResult, OpenapiSchemerError> {
+    Ok(QueryResult::new(parser.get_captures(source, all_files)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::output::OutputFormat;
+
+    use super::*;
+
+    struct MockParser;
+    impl QueryParser for MockParser {
+        fn get_captures(
+            &self,
+            source: &str,
+            all_files: bool,
+        ) -> Result<Vec<QueryCapture>, OpenapiSchemerError> {
+            if source.is_empty() {
+                return Ok(vec![]);
+            }
+            let capture = |text: &str, line| QueryCapture {
+                name: "value".to_string(),
+                text: text.to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line,
+                column: 7,
+            };
+            let mut captures = vec![capture("listPets", 5)];
+            if all_files {
+                captures.push(capture("type: object\nproperties: {}", 12));
+            }
+            Ok(captures)
+        }
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            query(MockParser, "(flow_node) @value", true)?.to_string(),
+            "/spec/openapi.yaml:5:7: @value listPets\n\
+             /spec/openapi.yaml:12:7: @value type: object ..."
+        );
+        assert_eq!(
+            query(MockParser, "(flow_node) @value", false)?.format(OutputFormat::Json),
+            r#"[
+  {
+    "capture": "value",
+    "text": "listPets",
+    "file": "/spec/openapi.yaml",
+    "line": 5,
+    "column": 7
+  }
+]"#
+        );
+        assert_eq!(
+            query(MockParser, "", false)?.to_string(),
+            "No captures found"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,458 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{
+    bindings::{
+        refs::{BrokenRefNode, RefNode, RefParser, RefTarget},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    refs: Vec<RefNode>,
+    dot: bool,
+}
+
+impl ListResult {
+    pub fn new(refs: Vec<RefNode>, dot: bool) -> ListResult {
+        ListResult { refs, dot }
+    }
+
+    /// The refs as a Graphviz digraph from the object holding each `$ref` to
+    /// its target.
+    fn to_dot(&self) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut lines = vec!["digraph refs {".to_string()];
+        for node in &self.refs {
+            lines.push(format!(
+                "  {} -> {};",
+                quote(&node.source),
+                quote(&node.target)
+            ));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.refs.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.dot {
+            return write!(f, "{}", self.to_dot());
+        }
+        let lines: Vec<String> = self
+            .refs
+            .iter()
+            .map(|node| {
+                let kind = match node.external {
+                    true => "external",
+                    false => "internal",
+                };
+                ListEntry {
+                    name: format!("{} -> {} ({})", node.text, node.target, kind),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                }
+                .with_location()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let refs = self
+            .refs
+            .iter()
+            .map(|node| {
+                YamlNode::mapping(vec![
+                    ("ref", YamlNode::string(&node.text)),
+                    ("source", YamlNode::string(&node.source)),
+                    ("target", YamlNode::string(&node.target)),
+                    ("external", YamlNode::boolean(node.external)),
+                    ("file", YamlNode::string(&node.file.display().to_string())),
+                    ("line", YamlNode::integer(node.line)),
+                    ("column", YamlNode::integer(node.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(refs)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    refs: Vec<BrokenRefNode>,
+}
+
+impl CheckResult {
+    pub fn new(refs: Vec<BrokenRefNode>) -> CheckResult {
+        CheckResult { refs }
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.refs.is_empty() {
+            return write!(f, "No broken refs found");
+        }
+        let lines: Vec<String> = self
+            .refs
+            .iter()
+            .map(|node| {
+                ListEntry {
+                    name: node.reason.to_owned(),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                }
+                .with_location()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for CheckResult {
+    fn to_node(&self) -> YamlNode {
+        let refs = self
+            .refs
+            .iter()
+            .map(|node| {
+                YamlNode::mapping(vec![
+                    ("ref", YamlNode::string(&node.text)),
+                    ("reason", YamlNode::string(&node.reason)),
+                    ("file", YamlNode::string(&node.file.display().to_string())),
+                    ("line", YamlNode::integer(node.line)),
+                    ("column", YamlNode::integer(node.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(refs)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ToResult {
+    pointer: String,
+    refs: Vec<RefNode>,
+}
+
+impl ToResult {
+    pub fn new(pointer: String, refs: Vec<RefNode>) -> ToResult {
+        ToResult { pointer, refs }
+    }
+}
+
+impl Display for ToResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.refs.is_empty() {
+            return write!(f, "No refs to `{}` found", self.pointer);
+        }
+        let lines: Vec<String> = self
+            .refs
+            .iter()
+            .map(|node| {
+                ListEntry {
+                    name: format!("{} ($ref `{}`)", node.source, node.text),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                }
+                .with_location()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ToResult {
+    fn to_node(&self) -> YamlNode {
+        let refs = self
+            .refs
+            .iter()
+            .map(|node| {
+                YamlNode::mapping(vec![
+                    ("ref", YamlNode::string(&node.text)),
+                    ("source", YamlNode::string(&node.source)),
+                    ("file", YamlNode::string(&node.file.display().to_string())),
+                    ("line", YamlNode::integer(node.line)),
+                    ("column", YamlNode::integer(node.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(refs)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ResolveResult {
+    target: RefTarget,
+}
+
+impl ResolveResult {
+    pub fn new(target: RefTarget) -> ResolveResult {
+        ResolveResult { target }
+    }
+}
+
+impl Display for ResolveResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entry = ListEntry {
+            name: self.target.target.to_owned(),
+            file: self.target.file.to_owned(),
+            line: self.target.line,
+            column: self.target.column,
+        };
+        write!(f, "{}", entry.with_location())
+    }
+}
+
+impl OutputFormatter for ResolveResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![
+            ("ref", YamlNode::string(&self.target.text)),
+            ("target", YamlNode::string(&self.target.target)),
+            (
+                "file",
+                YamlNode::string(&self.target.file.display().to_string()),
+            ),
+            ("line", YamlNode::integer(self.target.line)),
+            ("column", YamlNode::integer(self.target.column)),
+        ])
+    }
+}
+
+pub fn list<T: RefParser>(parser: T, dot: bool) -> Result<ListResult, OpenapiSchemerError> {
+    let refs = parser.get_ref_nodes()?;
+    Ok(ListResult::new(refs, dot))
+}
+
+pub fn check<T: RefParser>(parser: T) -> Result<CheckResult, OpenapiSchemerError> {
+    let refs = parser.get_broken_refs()?;
+    Ok(CheckResult::new(refs))
+}
+
+/// Every `$ref` that points at `pointer`, such as `#/components/schemas/Pet`.
+pub fn to<T: RefParser>(parser: T, pointer: &str) -> Result<ToResult, OpenapiSchemerError> {
+    let refs = parser.get_refs_to(pointer)?;
+    Ok(ToResult::new(pointer.to_string(), refs))
+}
+
+/// Where the `$ref` at `position`, written as `file:line:column`, points.
+pub fn resolve<T: RefParser>(
+    parser: T,
+    position: &str,
+) -> Result<ResolveResult, OpenapiSchemerError> {
+    let invalid = || {
+        OpenapiSchemerError::RefsResolve(format!(
+            "Expected a position like `openapi.yaml:12:15`, got `{}`",
+            position
+        ))
+    };
+    let mut parts = position.rsplitn(3, ':');
+    let column = parts.next().and_then(|column| column.parse::<usize>().ok());
+    let line = parts.next().and_then(|line| line.parse::<usize>().ok());
+    let (file, line, column) = match (parts.next(), line, column) {
+        (Some(file), Some(line), Some(column)) if !file.is_empty() => (file, line, column),
+        _ => return Err(invalid()),
+    };
+    match parser.get_ref_target(&PathBuf::from(file), line, column)? {
+        Some(target) => Ok(ResolveResult::new(target)),
+        None => Err(OpenapiSchemerError::RefsResolve(format!(
+            "No `$ref` at `{}`",
+            position
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        path::{Path, PathBuf},
+    };
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<RefNode>,
+        refs: Vec<BrokenRefNode>,
+        target: Option<RefTarget>,
+    }
+    impl RefParser for MockParser {
+        fn get_ref_nodes(&self) -> Result<Vec<RefNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_broken_refs(&self) -> Result<Vec<BrokenRefNode>, OpenapiSchemerError> {
+            Ok(self.refs.to_owned())
+        }
+
+        fn get_ref_target(
+            &self,
+            file: &Path,
+            line: usize,
+            column: usize,
+        ) -> Result<Option<RefTarget>, OpenapiSchemerError> {
+            assert_eq!((file, line, column), (Path::new("openapi.yaml"), 12, 15));
+            Ok(self.target.to_owned())
+        }
+
+        fn get_refs_to(&self, pointer: &str) -> Result<Vec<RefNode>, OpenapiSchemerError> {
+            assert_eq!(pointer, "#/components/schemas/Pet");
+            Ok(self.nodes.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let parser = || MockParser {
+            nodes: vec![
+                RefNode {
+                    text: "resources/pets.yaml".to_string(),
+                    source: "/spec/openapi.yaml#/paths/~1pets".to_string(),
+                    target: "/spec/resources/pets.yaml".to_string(),
+                    external: true,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 4,
+                    column: 11,
+                },
+                RefNode {
+                    text: "#/components/schemas/Pet".to_string(),
+                    source: "/spec/openapi.yaml#/components/schemas/Pets/items".to_string(),
+                    target: "/spec/openapi.yaml#/components/schemas/Pet".to_string(),
+                    external: false,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 12,
+                    column: 15,
+                },
+            ],
+            refs: vec![],
+            target: None,
+        };
+        assert_eq!(
+            list(parser(), false)?.to_string(),
+            "/spec/openapi.yaml:4:11: resources/pets.yaml -> /spec/resources/pets.yaml (external)\n\
+             /spec/openapi.yaml:12:15: #/components/schemas/Pet -> /spec/openapi.yaml#/components/schemas/Pet (internal)"
+        );
+        assert_eq!(
+            list(parser(), true)?.to_string(),
+            "digraph refs {\n  \
+             \"/spec/openapi.yaml#/paths/~1pets\" -> \"/spec/resources/pets.yaml\";\n  \
+             \"/spec/openapi.yaml#/components/schemas/Pets/items\" -> \"/spec/openapi.yaml#/components/schemas/Pet\";\n\
+             }"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            nodes: vec![],
+            refs: vec![BrokenRefNode {
+                text: "#/components/schemas/Dog".to_string(),
+                reason: "$ref `#/components/schemas/Dog` points at a key that does not exist"
+                    .to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 12,
+                column: 17,
+            }],
+            target: None,
+        };
+        assert_eq!(
+            check(parser)?.to_string(),
+            "/spec/openapi.yaml:12:17: $ref `#/components/schemas/Dog` points at a key that does not exist"
+        );
+
+        let parser = MockParser {
+            nodes: vec![],
+            refs: vec![],
+            target: None,
+        };
+        assert_eq!(check(parser)?.to_string(), "No broken refs found");
+        Ok(())
+    }
+
+    #[test]
+    fn test_to() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            nodes: vec![RefNode {
+                text: "../openapi.yaml#/components/schemas/Pet".to_string(),
+                source:
+                    "/spec/resources/pets.yaml#/get/responses/200/content/application~1json/schema"
+                        .to_string(),
+                target: "/spec/openapi.yaml#/components/schemas/Pet".to_string(),
+                external: true,
+                file: PathBuf::from("/spec/resources/pets.yaml"),
+                line: 7,
+                column: 19,
+            }],
+            refs: vec![],
+            target: None,
+        };
+        assert_eq!(
+            to(parser, "#/components/schemas/Pet")?.to_string(),
+            "/spec/resources/pets.yaml:7:19: /spec/resources/pets.yaml#/get/responses/200/content/application~1json/schema ($ref `../openapi.yaml#/components/schemas/Pet`)"
+        );
+
+        let parser = MockParser {
+            nodes: vec![],
+            refs: vec![],
+            target: None,
+        };
+        assert_eq!(
+            to(parser, "#/components/schemas/Pet")?.to_string(),
+            "No refs to `#/components/schemas/Pet` found"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve() -> Result<(), Box<dyn Error>> {
+        let parser = || MockParser {
+            nodes: vec![],
+            refs: vec![],
+            target: Some(RefTarget {
+                text: "#/components/schemas/Pet".to_string(),
+                target: "/spec/openapi.yaml#/components/schemas/Pet".to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 20,
+                column: 5,
+            }),
+        };
+        assert_eq!(
+            resolve(parser(), "openapi.yaml:12:15")?.to_string(),
+            "/spec/openapi.yaml:20:5: /spec/openapi.yaml#/components/schemas/Pet"
+        );
+        assert_eq!(
+            resolve(parser(), "openapi.yaml:12")
+                .unwrap_err()
+                .to_string(),
+            "Expected a position like `openapi.yaml:12:15`, got `openapi.yaml:12`"
+        );
+
+        let parser = MockParser {
+            nodes: vec![],
+            refs: vec![],
+            target: None,
+        };
+        assert_eq!(
+            resolve(parser, "openapi.yaml:12:15")
+                .unwrap_err()
+                .to_string(),
+            "No `$ref` at `openapi.yaml:12:15`"
+        );
+        Ok(())
+    }
+}
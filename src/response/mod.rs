@@ -0,0 +1,186 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{response::ResponseParser, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    entries: Vec<ListEntry>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(list: Vec<ListEntry>, locations: bool) -> ListResult {
+        ListResult {
+            entries: list,
+            locations,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| match self.locations {
+                true => entry.with_location(),
+                false => entry.name.to_owned(),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.entries.iter().map(ListEntry::to_node).collect())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    name: String,
+    response: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(name: String, response: YamlNode) -> ShowResult {
+        ShowResult { name, response }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(self.name.as_str(), self.response.to_owned())])
+    }
+}
+
+pub fn list<T: ResponseParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_response_nodes()?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(ListResult::new(entries, locations))
+}
+
+pub fn show<T: ResponseParser>(parser: T, name: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_response(name)? {
+        Some(response) => Ok(ShowResult::new(name.to_string(), response)),
+        None => Err(OpenapiSchemerError::ResponseShow(format!(
+            "Could not find response `{}`",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::response::{OperationResponses, ResponseNode};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<ResponseNode>,
+        response: Option<YamlNode>,
+    }
+    impl MockParser {
+        fn new(nodes: Vec<ResponseNode>) -> MockParser {
+            MockParser {
+                nodes,
+                response: None,
+            }
+        }
+    }
+    impl ResponseParser for MockParser {
+        fn get_response_nodes(&self) -> Result<Vec<ResponseNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+
+        fn get_response(&self, _name: &str) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.response.to_owned())
+        }
+
+        fn get_operation_responses(&self) -> Result<Vec<OperationResponses>, OpenapiSchemerError> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser::new(vec![ResponseNode {
+            text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        let result = list(parser, false)?;
+        assert_eq!(
+            result,
+            ListResult::new(
+                vec![ListEntry {
+                    name: "test1".to_string(),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 5,
+                }],
+                false
+            )
+        );
+        assert_eq!(result.to_string(), "test1");
+
+        let parser = MockParser::new(vec![ResponseNode {
+            text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        assert_eq!(
+            list(parser, true)?.to_string(),
+            "/spec/openapi.yaml:3:5: test1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.response = Some(YamlNode::mapping(vec![(
+            "description",
+            YamlNode::string("Not found"),
+        )]));
+        let result = show(parser, "NotFound")?;
+        assert_eq!(result.to_string(), "NotFound:\n  description: Not found");
+
+        let parser = MockParser::new(vec![]);
+        let error = show(parser, "Missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find response `Missing`");
+        Ok(())
+    }
+}
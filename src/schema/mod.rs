@@ -1,52 +1,1201 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::Path};
 
-use crate::{bindings::schema::SchemaParser, error::OpenapiSchemerError};
+use clap::ValueEnum;
+
+use crate::{
+    bindings::{
+        diff::describe_type,
+        generate::{ComponentSchema, GenerateParser},
+        rename::SchemaRenamer,
+        schema::{EnumNode, RequiredProblem, SchemaParser},
+        yaml::{unescape_pointer_segment, YamlNode, YamlPair, YamlValue},
+    },
+    error::OpenapiSchemerError,
+    operation,
+    output::{
+        write_file, ListEntry, ListFormatter, ListWriter, OutputFormatter, SortedList, TreeNode,
+        TreeResult,
+    },
+};
+
+/// The dialect every exported JSON Schema document declares.
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The keywords whose value is a schema, a list of schemas or a mapping of
+/// names to schemas. Everything else, such as `enum` or `default`, is data.
+const SCHEMA_KEYWORDS: [&str; 5] = [
+    "additionalProperties",
+    "items",
+    "not",
+    "contains",
+    "propertyNames",
+];
+const SCHEMA_LIST_KEYWORDS: [&str; 4] = ["allOf", "oneOf", "anyOf", "prefixItems"];
+const SCHEMA_MAP_KEYWORDS: [&str; 3] = ["properties", "patternProperties", "$defs"];
+
+/// The sections of `components` that `schema list` can list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "camelCase")]
+pub enum ComponentSection {
+    Schemas,
+    Parameters,
+    Responses,
+    Headers,
+    RequestBodies,
+    SecuritySchemes,
+    Links,
+    Callbacks,
+}
+
+impl ComponentSection {
+    /// The key of the section under `components`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ComponentSection::Schemas => "schemas",
+            ComponentSection::Parameters => "parameters",
+            ComponentSection::Responses => "responses",
+            ComponentSection::Headers => "headers",
+            ComponentSection::RequestBodies => "requestBodies",
+            ComponentSection::SecuritySchemes => "securitySchemes",
+            ComponentSection::Links => "links",
+            ComponentSection::Callbacks => "callbacks",
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct ListResult {
-    entries: Vec<String>,
+    entries: Vec<ListEntry>,
+    locations: bool,
 }
 
 impl ListResult {
-    pub fn new(list: Vec<String>) -> ListResult {
-        ListResult { entries: list }
+    pub fn new(list: Vec<ListEntry>, locations: bool) -> ListResult {
+        ListResult {
+            entries: list,
+            locations,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
     }
 }
 
 impl Display for ListResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.entries.join("\n"))
+        let lines: Vec<String> = self.lines().collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.nodes().collect())
+    }
+}
+
+impl ListFormatter for ListResult {
+    fn lines(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.entries.iter().map(|entry| entry.line(self.locations)))
+    }
+
+    fn nodes(&self) -> Box<dyn Iterator<Item = YamlNode> + '_> {
+        Box::new(self.entries.iter().map(ListEntry::to_node))
+    }
+}
+
+/// A property of a schema as `schema properties` lists it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SchemaProperty {
+    pub name: String,
+    pub schema_type: String,
+    pub format: Option<String>,
+    pub required: bool,
+    pub nullable: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PropertiesResult {
+    properties: Vec<SchemaProperty>,
+}
+
+impl PropertiesResult {
+    pub fn new(properties: Vec<SchemaProperty>) -> PropertiesResult {
+        PropertiesResult { properties }
+    }
+}
+
+impl Display for PropertiesResult {
+    /// A table with a column per field, padded so the columns line up.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |value: bool| match value {
+            true => "yes".to_string(),
+            false => "no".to_string(),
+        };
+        let mut rows = vec![[
+            "name",
+            "type",
+            "format",
+            "required",
+            "nullable",
+            "description",
+        ]
+        .map(str::to_owned)];
+        for property in &self.properties {
+            rows.push([
+                property.name.to_owned(),
+                property.schema_type.to_owned(),
+                property.format.to_owned().unwrap_or_default(),
+                yes_no(property.required),
+                yes_no(property.nullable),
+                property.description.to_owned().unwrap_or_default(),
+            ]);
+        }
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for PropertiesResult {
+    fn to_node(&self) -> YamlNode {
+        let optional = |value: &Option<String>| match value {
+            Some(value) => YamlNode::string(value),
+            None => YamlNode::null(),
+        };
+        YamlNode::sequence(
+            self.properties
+                .iter()
+                .map(|property| {
+                    YamlNode::mapping(vec![
+                        ("name", YamlNode::string(&property.name)),
+                        ("type", YamlNode::string(&property.schema_type)),
+                        ("format", optional(&property.format)),
+                        ("required", YamlNode::boolean(property.required)),
+                        ("nullable", YamlNode::boolean(property.nullable)),
+                        ("description", optional(&property.description)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShowResult {
+    name: String,
+    schema: YamlNode,
+}
+
+impl ShowResult {
+    pub fn new(name: String, schema: YamlNode) -> ShowResult {
+        ShowResult { name, schema }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![(self.name.as_str(), self.schema.to_owned())])
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EnumsResult {
+    enums: Vec<EnumNode>,
+    locations: bool,
+}
+
+impl EnumsResult {
+    pub fn new(enums: Vec<EnumNode>, locations: bool) -> EnumsResult {
+        EnumsResult { enums, locations }
+    }
+}
+
+/// An enum value as written, with values that aren't scalars on one line.
+fn value_text(value: &YamlNode) -> String {
+    match value.as_str() {
+        Some(text) => text.to_string(),
+        None => value
+            .to_json()
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(" "),
+    }
+}
+
+impl Display for EnumsResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.enums.is_empty() {
+            return write!(f, "No enums found");
+        }
+        let lines: Vec<String> = self
+            .enums
+            .iter()
+            .map(|node| {
+                let values: Vec<String> = node.values.iter().map(value_text).collect();
+                let entry = ListEntry {
+                    name: format!("{}: {}", node.name, values.join(", ")),
+                    file: node.file.to_owned(),
+                    line: node.line,
+                    column: node.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for EnumsResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.enums
+                .iter()
+                .map(|node| {
+                    YamlNode::mapping(vec![
+                        ("name", YamlNode::string(&node.name)),
+                        ("values", YamlNode::sequence(node.values.to_owned())),
+                        ("file", YamlNode::string(&node.file.display().to_string())),
+                        ("line", YamlNode::integer(node.line)),
+                        ("column", YamlNode::integer(node.column)),
+                    ])
+                })
+                .collect(),
+        )
     }
 }
 
-pub fn list<T: SchemaParser>(parser: T) -> Result<ListResult, OpenapiSchemerError> {
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuditRequiredResult {
+    problems: Vec<RequiredProblem>,
+    locations: bool,
+}
+
+impl AuditRequiredResult {
+    pub fn new(problems: Vec<RequiredProblem>, locations: bool) -> AuditRequiredResult {
+        AuditRequiredResult {
+            problems,
+            locations,
+        }
+    }
+}
+
+impl Display for AuditRequiredResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.problems.is_empty() {
+            return write!(f, "No problems found");
+        }
+        let lines: Vec<String> = self
+            .problems
+            .iter()
+            .map(|problem| {
+                let entry = ListEntry {
+                    name: format!("{}: {}", problem.schema, problem.message),
+                    file: problem.file.to_owned(),
+                    line: problem.line,
+                    column: problem.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for AuditRequiredResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.problems
+                .iter()
+                .map(|problem| {
+                    YamlNode::mapping(vec![
+                        ("schema", YamlNode::string(&problem.schema)),
+                        ("message", YamlNode::string(&problem.message)),
+                        (
+                            "file",
+                            YamlNode::string(&problem.file.display().to_string()),
+                        ),
+                        ("line", YamlNode::integer(problem.line)),
+                        ("column", YamlNode::integer(problem.column)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Standalone JSON Schema documents of component schemas, each with the
+/// components it refers to under `$defs`.
+#[derive(Debug, PartialEq)]
+pub struct JsonSchemaResult {
+    documents: Vec<(String, YamlNode)>,
+    all: bool,
+}
+
+impl JsonSchemaResult {
+    pub fn new(documents: Vec<(String, YamlNode)>, all: bool) -> JsonSchemaResult {
+        JsonSchemaResult { documents, all }
+    }
+
+    /// Writes the document to the file `output`, or with `--all` every
+    /// document to `Name.json` in the directory `output`.
+    pub fn write(&self, output: &Path) -> Result<(), OpenapiSchemerError> {
+        for (name, document) in &self.documents {
+            let path = match self.all {
+                true => output.join(format!("{}.json", name)),
+                false => output.to_path_buf(),
+            };
+            write_file(&path, &format!("{}\n", document.to_json()))
+                .map_err(OpenapiSchemerError::SchemaExportJsonSchema)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for JsonSchemaResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let documents: Vec<String> = self
+            .documents
+            .iter()
+            .map(|(_, document)| document.to_json())
+            .collect();
+        write!(f, "{}", documents.join("\n"))
+    }
+}
+
+/// The JSON Schema document of the component `name`. Refs to other
+/// components point into `$defs`, which holds every component reached from
+/// `name`.
+fn json_schema_document(name: &str, schemas: &[ComponentSchema]) -> YamlNode {
+    let mut reached = vec![name.to_string()];
+    let mut index = 0;
+    while index < reached.len() {
+        let schema = schemas.iter().find(|schema| schema.name == reached[index]);
+        for reference in schema
+            .map(|schema| schema.schema.references())
+            .unwrap_or_default()
+        {
+            if let Some(target) = component_name(reference) {
+                if !reached.contains(&target) {
+                    reached.push(target);
+                }
+            }
+        }
+        index += 1;
+    }
+    let pair = |key: &str, value: YamlNode| YamlPair {
+        key: YamlNode::string(key),
+        value,
+    };
+    let mut pairs = vec![
+        pair("$schema", YamlNode::string(JSON_SCHEMA_DIALECT)),
+        pair("title", YamlNode::string(name)),
+    ];
+    let mut definitions = vec![];
+    for schema in schemas {
+        let converted = json_schema(&schema.schema, name);
+        if schema.name == name {
+            match converted.value {
+                YamlValue::Mapping(converted) => pairs.extend(
+                    converted
+                        .into_iter()
+                        .filter(|pair| pair.key.as_str() != Some("title")),
+                ),
+                // A boolean schema can't carry the keywords of a document.
+                _ => pairs.push(pair("allOf", YamlNode::sequence(vec![converted]))),
+            }
+        } else if reached.contains(&schema.name) {
+            definitions.push(pair(&schema.name, converted));
+        }
+    }
+    if !definitions.is_empty() {
+        pairs.push(pair(
+            "$defs",
+            YamlNode::new(YamlValue::Mapping(definitions)),
+        ));
+    }
+    YamlNode::new(YamlValue::Mapping(pairs))
+}
+
+/// The value of the keyword `key` with every schema in it converted, or
+/// `None` when `key` doesn't hold schemas.
+fn subschemas(
+    key: &str,
+    value: &YamlNode,
+    convert: &mut dyn FnMut(&YamlNode) -> YamlNode,
+) -> Option<YamlNode> {
+    if SCHEMA_KEYWORDS.contains(&key) {
+        return Some(convert(value));
+    }
+    let converted = match &value.value {
+        YamlValue::Sequence(items) if SCHEMA_LIST_KEYWORDS.contains(&key) => {
+            YamlNode::sequence(items.iter().map(&mut *convert).collect())
+        }
+        YamlValue::Mapping(entries) if SCHEMA_MAP_KEYWORDS.contains(&key) => {
+            YamlNode::new(YamlValue::Mapping(
+                entries
+                    .iter()
+                    .map(|entry| YamlPair {
+                        key: entry.key.to_owned(),
+                        value: convert(&entry.value),
+                    })
+                    .collect(),
+            ))
+        }
+        _ => return None,
+    };
+    Some(converted)
+}
+
+/// The component a ref written as `#/components/schemas/Name` leads to.
+fn component_name(reference: &str) -> Option<String> {
+    reference
+        .strip_prefix("#/components/schemas/")
+        .filter(|name| !name.contains('/'))
+        .map(unescape_pointer_segment)
+}
+
+/// `schema` converted from the OpenAPI dialect: `nullable` adds `null` to
+/// the allowed types, `example` becomes `examples`, boolean
+/// `exclusiveMinimum` and `exclusiveMaximum` take the value of the bound they
+/// apply to and refs to components point into `$defs`, or at the document
+/// itself for `root`.
+fn json_schema(schema: &YamlNode, root: &str) -> YamlNode {
+    let pairs = match &schema.value {
+        YamlValue::Mapping(pairs) => pairs,
+        _ => return schema.to_owned(),
+    };
+    if let Some(reference) = schema.reference() {
+        let reference = match component_name(reference) {
+            Some(name) if name == root => "#".to_string(),
+            Some(name) => format!("#/$defs/{}", name.replace('~', "~0").replace('/', "~1")),
+            None => reference.to_string(),
+        };
+        return YamlNode::mapping(vec![("$ref", YamlNode::string(&reference))]);
+    }
+    let flag = |key: &str| {
+        schema
+            .get(key)
+            .and_then(|value| value.as_str())
+            .map(|value| value == "true")
+    };
+    let mut converted = vec![];
+    for pair in pairs {
+        let key = pair.key.as_str().unwrap_or_default();
+        let value = match key {
+            "nullable" => continue,
+            "example" => {
+                converted.push(YamlPair {
+                    key: YamlNode::string("examples"),
+                    value: YamlNode::sequence(vec![pair.value.to_owned()]),
+                });
+                continue;
+            }
+            "exclusiveMinimum" | "exclusiveMaximum" if flag(key).is_some() => continue,
+            "minimum" | "maximum" => {
+                let exclusive = format!("exclusive{}{}", key[..1].to_uppercase(), &key[1..]);
+                if flag(&exclusive) == Some(true) {
+                    converted.push(YamlPair {
+                        key: YamlNode::string(&exclusive),
+                        value: pair.value.to_owned(),
+                    });
+                    continue;
+                }
+                pair.value.to_owned()
+            }
+            key => subschemas(key, &pair.value, &mut |schema| json_schema(schema, root))
+                .unwrap_or_else(|| pair.value.to_owned()),
+        };
+        converted.push(YamlPair {
+            key: pair.key.to_owned(),
+            value,
+        });
+    }
+    if flag("nullable") == Some(true) {
+        let null = YamlNode::string("null");
+        let mut typed = false;
+        for pair in converted.iter_mut() {
+            match pair.key.as_str() {
+                Some("type") => {
+                    typed = true;
+                    match &mut pair.value.value {
+                        YamlValue::Sequence(types) => {
+                            if !types.iter().any(|kind| kind.as_str() == Some("null")) {
+                                types.push(null.to_owned());
+                            }
+                        }
+                        _ => {
+                            pair.value =
+                                YamlNode::sequence(vec![pair.value.to_owned(), null.to_owned()])
+                        }
+                    }
+                }
+                Some("enum") => {
+                    if let YamlValue::Sequence(values) = &mut pair.value.value {
+                        if !values.iter().any(YamlNode::is_null) {
+                            values.push(YamlNode::null());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !typed {
+            return YamlNode::mapping(vec![(
+                "anyOf",
+                YamlNode::sequence(vec![
+                    YamlNode::new(YamlValue::Mapping(converted)),
+                    YamlNode::mapping(vec![("type", null)]),
+                ]),
+            )]);
+        }
+    }
+    YamlNode::new(YamlValue::Mapping(converted))
+}
+
+/// Inlines the components a schema refers to and, when `merge` is set,
+/// merges `allOf` branches into the schema that lists them.
+struct Flattener<'a> {
+    schemas: &'a [ComponentSchema],
+    merge: bool,
+    /// The components currently being inlined, so recursive schemas keep
+    /// their ref instead of repeating.
+    stack: Vec<String>,
+}
+
+impl Flattener<'_> {
+    fn flatten(&mut self, schema: &YamlNode) -> YamlNode {
+        if let Some(name) = schema.reference().and_then(component_name) {
+            let target = self.schemas.iter().find(|schema| schema.name == name);
+            return match target {
+                Some(target) if !self.stack.contains(&name) => {
+                    self.stack.push(name);
+                    let flattened = self.flatten(&target.schema);
+                    self.stack.pop();
+                    flattened
+                }
+                _ => schema.to_owned(),
+            };
+        }
+        let pairs = match &schema.value {
+            YamlValue::Mapping(pairs) => pairs,
+            _ => return schema.to_owned(),
+        };
+        let mut flattened = vec![];
+        for pair in pairs {
+            let key = pair.key.as_str().unwrap_or_default();
+            let value = match (key, &pair.value.value) {
+                ("oneOf" | "anyOf", YamlValue::Sequence(variants)) if self.merge => {
+                    YamlNode::sequence(
+                        variants
+                            .iter()
+                            .enumerate()
+                            .map(|(index, variant)| self.variant(schema, key, index, variant))
+                            .collect(),
+                    )
+                }
+                _ => subschemas(key, &pair.value, &mut |schema| self.flatten(schema))
+                    .unwrap_or_else(|| pair.value.to_owned()),
+            };
+            flattened.push(YamlPair {
+                key: pair.key.to_owned(),
+                value,
+            });
+        }
+        match self.merge {
+            true => merge_all_of(flattened),
+            false => YamlNode::new(YamlValue::Mapping(flattened)),
+        }
+    }
+
+    /// A variant of the `oneOf` or `anyOf` of `parent`, flattened and
+    /// annotated with `x-variant`, the component it is or where it is listed,
+    /// and with `x-discriminator-value` when `parent` has a discriminator.
+    fn variant(
+        &mut self,
+        parent: &YamlNode,
+        key: &str,
+        index: usize,
+        variant: &YamlNode,
+    ) -> YamlNode {
+        let name = variant.reference().and_then(component_name);
+        let mut annotations = vec![YamlPair {
+            key: YamlNode::string("x-variant"),
+            value: YamlNode::string(
+                &name
+                    .to_owned()
+                    .unwrap_or_else(|| format!("{}[{}]", key, index)),
+            ),
+        }];
+        if let Some(discriminator) = parent.get("discriminator") {
+            let mapped = discriminator
+                .get("mapping")
+                .and_then(|mapping| mapping.as_mapping())
+                .unwrap_or_default()
+                .iter()
+                .find(|pair| {
+                    let target = pair.value.as_str().unwrap_or_default();
+                    name.is_some()
+                        && component_name(target).or_else(|| Some(target.to_string())) == name
+                })
+                .and_then(|pair| pair.key.as_str().map(str::to_owned));
+            if let Some(value) = mapped.or_else(|| name.to_owned()) {
+                annotations.push(YamlPair {
+                    key: YamlNode::string("x-discriminator-value"),
+                    value: YamlNode::string(&value),
+                });
+            }
+        }
+        let flattened = self.flatten(variant);
+        match flattened.value {
+            YamlValue::Mapping(pairs) if flattened.reference().is_none() => {
+                annotations.extend(pairs);
+            }
+            _ => annotations.push(YamlPair {
+                key: YamlNode::string("allOf"),
+                value: YamlNode::sequence(vec![flattened]),
+            }),
+        }
+        YamlNode::new(YamlValue::Mapping(annotations))
+    }
+}
+
+/// Merges the branches of the `allOf` among `pairs` into them. Properties
+/// and `required` are combined, the tighter of two bounds is kept and the
+/// first title or description wins. Whatever can't be merged, such as two
+/// different types or a recursive ref, stays in `allOf`.
+fn merge_all_of(pairs: Vec<YamlPair>) -> YamlNode {
+    let mut merged = vec![];
+    let mut branches = vec![];
+    for pair in pairs {
+        match (pair.key.as_str(), &pair.value.value) {
+            (Some("allOf"), YamlValue::Sequence(items)) => branches.extend(items.to_owned()),
+            _ => merged.push(pair),
+        }
+    }
+    let mut remaining = vec![];
+    for branch in branches {
+        let pairs = match &branch.value {
+            YamlValue::Mapping(pairs) if branch.reference().is_none() => pairs,
+            _ => {
+                remaining.push(branch);
+                continue;
+            }
+        };
+        let mut conflicts = vec![];
+        for pair in pairs {
+            let key = pair.key.as_str().unwrap_or_default();
+            let existing = merged
+                .iter()
+                .position(|merged: &YamlPair| merged.key.as_str() == Some(key));
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    merged.push(pair.to_owned());
+                    continue;
+                }
+            };
+            let current = &merged[index].value;
+            let value = match key {
+                _ if current.to_json() == pair.value.to_json() => continue,
+                "title" | "description" | "example" | "examples" | "default" | "externalDocs" => {
+                    continue
+                }
+                "properties" => merge_properties(current, &pair.value),
+                "required" => {
+                    let mut required = current.as_sequence().unwrap_or_default().to_vec();
+                    for name in pair.value.as_sequence().unwrap_or_default() {
+                        if !required
+                            .iter()
+                            .any(|required| required.as_str() == name.as_str())
+                        {
+                            required.push(name.to_owned());
+                        }
+                    }
+                    YamlNode::sequence(required)
+                }
+                "minimum" | "minLength" | "minItems" | "minProperties" => {
+                    tighter(current, &pair.value, f64::max)
+                }
+                "maximum" | "maxLength" | "maxItems" | "maxProperties" => {
+                    tighter(current, &pair.value, f64::min)
+                }
+                _ => {
+                    conflicts.push(pair.to_owned());
+                    continue;
+                }
+            };
+            merged[index].value = value;
+        }
+        if !conflicts.is_empty() {
+            remaining.push(YamlNode::new(YamlValue::Mapping(conflicts)));
+        }
+    }
+    if !remaining.is_empty() {
+        merged.push(YamlPair {
+            key: YamlNode::string("allOf"),
+            value: YamlNode::sequence(remaining),
+        });
+    }
+    YamlNode::new(YamlValue::Mapping(merged))
+}
+
+/// The properties of two merged schemas, with a property both declare
+/// merged as well.
+fn merge_properties(current: &YamlNode, other: &YamlNode) -> YamlNode {
+    let mut properties = current.as_mapping().unwrap_or_default().to_vec();
+    for pair in other.as_mapping().unwrap_or_default() {
+        match properties
+            .iter_mut()
+            .find(|property| property.key.as_str() == pair.key.as_str())
+        {
+            Some(property) => {
+                property.value = merge_all_of(vec![YamlPair {
+                    key: YamlNode::string("allOf"),
+                    value: YamlNode::sequence(vec![
+                        property.value.to_owned(),
+                        pair.value.to_owned(),
+                    ]),
+                }]);
+            }
+            None => properties.push(pair.to_owned()),
+        }
+    }
+    YamlNode::new(YamlValue::Mapping(properties))
+}
+
+/// The bound `pick` prefers, or `current` when either isn't a number.
+fn tighter(current: &YamlNode, other: &YamlNode, pick: fn(f64, f64) -> f64) -> YamlNode {
+    let number = |node: &YamlNode| node.as_str().and_then(|text| text.parse::<f64>().ok());
+    match (number(current), number(other)) {
+        (Some(a), Some(b)) if a != b && pick(a, b) == b => other.to_owned(),
+        _ => current.to_owned(),
+    }
+}
+
+pub fn list<T: SchemaParser>(
+    parser: T,
+    section: ComponentSection,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_component_nodes(section.key())?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(ListResult::new(entries, locations))
+}
+
+/// Writes the entries of `section` to `list` as they are found, the same
+/// entries `list` returns in document order.
+pub fn stream<T: SchemaParser>(
+    parser: T,
+    section: ComponentSection,
+    locations: bool,
+    list: &mut ListWriter,
+) -> Result<(), OpenapiSchemerError> {
+    parser.for_each_component_node(section.key(), &mut |node| {
+        let entry = ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        };
+        list.item(|| entry.line(locations), || entry.to_node())
+    })
+}
+
+/// The component schemas with their properties below them, and the type of
+/// each property below it.
+pub fn tree<T: SchemaParser>(parser: T) -> Result<TreeResult, OpenapiSchemerError> {
     let nodes = parser.get_schema_nodes()?;
-    let node_texts = nodes
+    let mut roots = vec![];
+    for node in nodes {
+        let schema = parser
+            .get_schema(&node.text, false)
+            .map_err(|error| OpenapiSchemerError::SchemaList(error.to_string()))?;
+        let properties = schema
+            .as_ref()
+            .and_then(|schema| schema.get("properties"))
+            .and_then(YamlNode::as_mapping)
+            .unwrap_or_default()
+            .iter()
+            .map(|pair| {
+                let name = pair.key.as_str().unwrap_or_default();
+                TreeNode::new(name, vec![TreeNode::leaf(&tree_type(&pair.value))])
+            })
+            .collect();
+        roots.push(TreeNode::new(&node.text, properties));
+    }
+    Ok(TreeResult::new(roots))
+}
+
+/// The type of a property as `describe_type` writes it, with refs to
+/// components shortened to their names and arrays showing their items, such
+/// as `array<Pet>`.
+fn tree_type(schema: &YamlNode) -> String {
+    if let Some(name) = schema.reference().and_then(component_name) {
+        return name;
+    }
+    let described = describe_type(Some(schema));
+    match schema.get("items") {
+        Some(items) if described == "array" => format!("array<{}>", tree_type(items)),
+        _ => described,
+    }
+}
+
+pub fn show<T: SchemaParser>(
+    parser: T,
+    name: &str,
+    resolve: bool,
+) -> Result<ShowResult, OpenapiSchemerError> {
+    match parser.get_schema(name, resolve)? {
+        Some(schema) => Ok(ShowResult::new(name.to_string(), schema)),
+        None => Err(OpenapiSchemerError::SchemaShow(format!(
+            "Could not find schema `{}`",
+            name
+        ))),
+    }
+}
+
+/// The operations that use the schema `name`, listed like `operation list`.
+pub fn usages<T: SchemaParser>(
+    parser: T,
+    name: &str,
+    locations: bool,
+) -> Result<operation::ListResult, OpenapiSchemerError> {
+    match parser.get_schema_usages(name)? {
+        Some(operations) => Ok(operation::ListResult::new(operations, locations, false)),
+        None => Err(OpenapiSchemerError::SchemaUsages(format!(
+            "Could not find schema `{}`",
+            name
+        ))),
+    }
+}
+
+/// The enums of every component schema, or of the schema `name` with its
+/// refs followed.
+pub fn enums<T: SchemaParser>(
+    parser: T,
+    name: Option<&str>,
+    locations: bool,
+) -> Result<EnumsResult, OpenapiSchemerError> {
+    match parser.get_schema_enums(name)? {
+        Some(enums) => Ok(EnumsResult::new(enums, locations)),
+        None => Err(OpenapiSchemerError::SchemaEnums(format!(
+            "Could not find schema `{}`",
+            name.unwrap_or_default()
+        ))),
+    }
+}
+
+/// The mistakes in the `required` of the component schemas.
+pub fn audit_required<T: SchemaParser>(
+    parser: T,
+    locations: bool,
+) -> Result<AuditRequiredResult, OpenapiSchemerError> {
+    let problems = parser.get_required_problems()?;
+    Ok(AuditRequiredResult::new(problems, locations))
+}
+
+/// The JSON Schema document of the component schema `name`, or with `all`
+/// of every component schema.
+pub fn export_json_schema<T: GenerateParser>(
+    parser: T,
+    name: Option<&str>,
+    all: bool,
+) -> Result<JsonSchemaResult, OpenapiSchemerError> {
+    let schemas = parser.get_component_schemas()?;
+    let names: Vec<&str> = match name {
+        Some(name) if !all => match schemas.iter().any(|schema| schema.name == name) {
+            true => vec![name],
+            false => {
+                return Err(OpenapiSchemerError::SchemaExportJsonSchema(format!(
+                    "Could not find schema `{}`",
+                    name
+                )))
+            }
+        },
+        _ => schemas.iter().map(|schema| schema.name.as_str()).collect(),
+    };
+    let documents = names
+        .into_iter()
+        .map(|name| (name.to_string(), json_schema_document(name, &schemas)))
+        .collect();
+    Ok(JsonSchemaResult::new(documents, all))
+}
+
+/// The component schema `name` with the components it refers to inlined,
+/// and with `merge_all_of` its `allOf` branches merged and its `oneOf` and
+/// `anyOf` variants annotated.
+pub fn resolve<T: GenerateParser>(
+    parser: T,
+    name: &str,
+    merge_all_of: bool,
+) -> Result<ShowResult, OpenapiSchemerError> {
+    let schemas = parser.get_component_schemas()?;
+    let schema = schemas
+        .iter()
+        .find(|schema| schema.name == name)
+        .ok_or_else(|| {
+            OpenapiSchemerError::SchemaResolve(format!("Could not find schema `{}`", name))
+        })?;
+    let mut flattener = Flattener {
+        schemas: &schemas,
+        merge: merge_all_of,
+        stack: vec![name.to_string()],
+    };
+    let flattened = flattener.flatten(&schema.schema);
+    Ok(ShowResult::new(name.to_string(), flattened))
+}
+
+/// The properties of the schema `name`, including those of the members of
+/// its `allOf`. A property that is a ref is described by the component it
+/// leads to, its type being the name of that component.
+pub fn properties<T: GenerateParser>(
+    parser: T,
+    name: &str,
+) -> Result<PropertiesResult, OpenapiSchemerError> {
+    let schemas = parser.get_component_schemas()?;
+    let schema = schemas
+        .iter()
+        .find(|schema| schema.name == name)
+        .ok_or_else(|| {
+            OpenapiSchemerError::SchemaProperties(format!("Could not find schema `{}`", name))
+        })?;
+    let merged = merge_members(&schemas, &schema.schema, &mut vec![name.to_string()]);
+    let required: Vec<&str> = merged
+        .get("required")
+        .and_then(YamlNode::as_sequence)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(YamlNode::as_str)
+        .collect();
+    let properties = merged
+        .get("properties")
+        .and_then(YamlNode::as_mapping)
+        .unwrap_or_default()
+        .iter()
+        .map(|pair| {
+            let (property, schema) = (pair.key.as_str().unwrap_or_default(), &pair.value);
+            let target = schema
+                .reference()
+                .and_then(component_name)
+                .and_then(|name| schemas.iter().find(|schema| schema.name == name))
+                .map(|target| &target.schema)
+                .unwrap_or(schema);
+            let text = |key: &str| {
+                schema
+                    .get(key)
+                    .or_else(|| target.get(key))
+                    .and_then(YamlNode::as_str)
+                    .map(str::to_owned)
+            };
+            SchemaProperty {
+                name: property.to_string(),
+                schema_type: property_type(schema),
+                format: text("format"),
+                nullable: text("nullable").as_deref() == Some("true")
+                    || schema_types(target).contains(&"null"),
+                description: text("description"),
+                required: required.contains(&property),
+            }
+        })
+        .collect();
+    Ok(PropertiesResult::new(properties))
+}
+
+/// `schema` with the members of its `allOf` merged into it, following refs
+/// to the components they are. The properties themselves keep their refs.
+fn merge_members(
+    schemas: &[ComponentSchema],
+    schema: &YamlNode,
+    stack: &mut Vec<String>,
+) -> YamlNode {
+    if let Some(name) = schema.reference().and_then(component_name) {
+        let target = schemas.iter().find(|schema| schema.name == name);
+        return match target {
+            Some(target) if !stack.contains(&name) => {
+                stack.push(name);
+                let merged = merge_members(schemas, &target.schema, stack);
+                stack.pop();
+                merged
+            }
+            _ => schema.to_owned(),
+        };
+    }
+    let pairs = match &schema.value {
+        YamlValue::Mapping(pairs) => pairs,
+        _ => return schema.to_owned(),
+    };
+    let pairs = pairs
+        .iter()
+        .map(|pair| match (pair.key.as_str(), &pair.value.value) {
+            (Some("allOf"), YamlValue::Sequence(members)) => YamlPair {
+                key: pair.key.to_owned(),
+                value: YamlNode::sequence(
+                    members
+                        .iter()
+                        .map(|member| merge_members(schemas, member, stack))
+                        .collect(),
+                ),
+            },
+            _ => pair.to_owned(),
+        })
+        .collect();
+    merge_all_of(pairs)
+}
+
+/// The type of a property: the name of the component a ref leads to, the
+/// types other than `null`, or `array<...>` with the type of the items.
+fn property_type(schema: &YamlNode) -> String {
+    if let Some(name) = schema.reference().and_then(component_name) {
+        return name;
+    }
+    let types: Vec<&str> = schema_types(schema)
         .into_iter()
-        .map(|node| node.text.to_string())
+        .filter(|name| *name != "null")
         .collect();
-    Ok(ListResult::new(node_texts))
+    match (types.as_slice(), schema.get("items")) {
+        (["array"], Some(items)) => format!("array<{}>", property_type(items)),
+        ([], _) => "any".to_string(),
+        _ => types.join("|"),
+    }
+}
+
+/// The `type` of `schema`, which 3.1 allows to be a list.
+fn schema_types(schema: &YamlNode) -> Vec<&str> {
+    match schema.get("type") {
+        Some(types) => match types.as_sequence() {
+            Some(types) => types.iter().filter_map(YamlNode::as_str).collect(),
+            None => types.as_str().into_iter().collect(),
+        },
+        None => vec![],
+    }
+}
+
+/// Renames the component schema `name` to `new_name` along with the refs to
+/// it, listed like `operation rename`. Nothing is written until
+/// `RenameResult::write`.
+pub fn rename<T: SchemaRenamer>(
+    renamer: T,
+    name: &str,
+    new_name: &str,
+) -> Result<operation::RenameResult, OpenapiSchemerError> {
+    match renamer.get_rename_edits(name, new_name)? {
+        Some(files) => Ok(operation::RenameResult::new(files)),
+        None => Err(OpenapiSchemerError::SchemaRename(format!(
+            "Could not find schema `{}`",
+            name
+        ))),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
+    use std::{error::Error, path::PathBuf};
 
-    use crate::bindings::schema::SchemaNode;
+    use crate::bindings::{
+        edit::{FileEdits, TextEdit},
+        schema::{EnumNode, SchemaNode},
+        yaml::parse_yaml,
+        OperationNode,
+    };
 
     use super::*;
 
     struct MockParser {
         nodes: Vec<SchemaNode>,
+        schema: Option<YamlNode>,
+        usages: Option<Vec<OperationNode>>,
+        enums: Option<Vec<EnumNode>>,
+        required_problems: Vec<RequiredProblem>,
     }
     impl MockParser {
         fn new(nodes: Vec<SchemaNode>) -> MockParser {
-            MockParser { nodes }
+            MockParser {
+                nodes,
+                schema: None,
+                usages: None,
+                enums: None,
+                required_problems: vec![],
+            }
         }
     }
     impl SchemaParser for MockParser {
-        fn get_schema_nodes(&self) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
-            Ok(self.nodes.to_owned())
+        fn get_component_nodes(
+            &self,
+            section: &str,
+        ) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
+            match section {
+                "schemas" => Ok(self.nodes.to_owned()),
+                _ => Ok(vec![]),
+            }
+        }
+
+        fn get_schema(
+            &self,
+            _name: &str,
+            _resolve: bool,
+        ) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+            Ok(self.schema.to_owned())
+        }
+
+        fn get_schema_usages(
+            &self,
+            _name: &str,
+        ) -> Result<Option<Vec<OperationNode>>, OpenapiSchemerError> {
+            Ok(self.usages.to_owned())
+        }
+
+        fn get_schema_enums(
+            &self,
+            _name: Option<&str>,
+        ) -> Result<Option<Vec<EnumNode>>, OpenapiSchemerError> {
+            Ok(self.enums.to_owned())
+        }
+
+        fn get_required_problems(&self) -> Result<Vec<RequiredProblem>, OpenapiSchemerError> {
+            Ok(self.required_problems.to_owned())
         }
     }
 
@@ -54,9 +1203,483 @@ mod tests {
     fn test_list() -> Result<(), Box<dyn Error>> {
         let parser = MockParser::new(vec![SchemaNode {
             text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
         }]);
-        let result = list(parser)?;
-        assert_eq!(result, ListResult::new(vec!["test1".to_string()]));
+        let result = list(parser, ComponentSection::Schemas, false)?;
+        assert_eq!(
+            result,
+            ListResult::new(
+                vec![ListEntry {
+                    name: "test1".to_string(),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 5,
+                }],
+                false
+            )
+        );
+        assert_eq!(result.to_string(), "test1");
+
+        let parser = MockParser::new(vec![SchemaNode {
+            text: "test1".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        assert_eq!(
+            list(parser, ComponentSection::Schemas, true)?.to_string(),
+            "/spec/openapi.yaml:3:5: test1"
+        );
+
+        let parser = MockParser::new(vec![]);
+        assert_eq!(
+            list(parser, ComponentSection::RequestBodies, false)?.to_string(),
+            ""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.schema = Some(YamlNode::mapping(vec![(
+            "type",
+            YamlNode::string("string"),
+        )]));
+        let result = show(parser, "Tag", false)?;
+        assert_eq!(result.to_string(), "Tag:\n  type: string");
+
+        let parser = MockParser::new(vec![]);
+        let error = show(parser, "Missing", false).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![SchemaNode {
+            text: "Pet".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+        }]);
+        parser.schema = Some(parse_yaml(
+            r#"type: object
+properties:
+  id:
+    type: integer
+    format: int64
+  owner:
+    $ref: '#/components/schemas/Owner'
+  tags:
+    type: array
+    items:
+      type: string
+"#,
+        )?);
+        assert_eq!(
+            tree(parser)?.to_string(),
+            r#"Pet
+├── id
+│   └── integer/int64
+├── owner
+│   └── Owner
+└── tags
+    └── array<string>"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_usages() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.usages = Some(vec![OperationNode {
+            text: "listPets".to_string(),
+            operation_id: Some("listPets".to_string()),
+            method: "get".to_string(),
+            path: "/pets".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 6,
+            column: 20,
+            ..Default::default()
+        }]);
+        let result = usages(parser, "Pet", true)?;
+        assert_eq!(
+            result.to_string(),
+            "/spec/openapi.yaml:6:20: GET /pets -> listPets"
+        );
+
+        let parser = MockParser::new(vec![]);
+        let error = usages(parser, "Missing", false).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_enums() -> Result<(), Box<dyn Error>> {
+        let node = |name: &str, values: Vec<YamlNode>, line| EnumNode {
+            name: name.to_string(),
+            values,
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 11,
+        };
+        let mut parser = MockParser::new(vec![]);
+        parser.enums = Some(vec![
+            node(
+                "Pet.status",
+                vec![YamlNode::string("available"), YamlNode::string("sold")],
+                12,
+            ),
+            node(
+                "Pet.size",
+                vec![YamlNode::integer(1), YamlNode::integer(2)],
+                20,
+            ),
+        ]);
+        assert_eq!(
+            enums(parser, None, true)?.to_string(),
+            "/spec/openapi.yaml:12:11: Pet.status: available, sold\n/spec/openapi.yaml:20:11: Pet.size: 1, 2"
+        );
+
+        let mut parser = MockParser::new(vec![]);
+        parser.enums = Some(vec![]);
+        assert_eq!(enums(parser, None, false)?.to_string(), "No enums found");
+
+        let parser = MockParser::new(vec![]);
+        let error = enums(parser, Some("Missing"), false).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_required() -> Result<(), Box<dyn Error>> {
+        let mut parser = MockParser::new(vec![]);
+        parser.required_problems = vec![RequiredProblem {
+            schema: "Pet".to_string(),
+            message: "`required` lists `nickname`, which is not one of its properties".to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 14,
+            column: 11,
+        }];
+        assert_eq!(
+            audit_required(parser, true)?.to_string(),
+            "/spec/openapi.yaml:14:11: Pet: `required` lists `nickname`, which is not one of its properties"
+        );
+
+        let parser = MockParser::new(vec![]);
+        assert_eq!(
+            audit_required(parser, false)?.to_string(),
+            "No problems found"
+        );
+        Ok(())
+    }
+
+    struct MockRenamer {
+        files: Option<Vec<FileEdits>>,
+    }
+    impl SchemaRenamer for MockRenamer {
+        fn get_rename_edits(
+            &self,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Option<Vec<FileEdits>>, OpenapiSchemerError> {
+            Ok(self.files.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_rename() -> Result<(), Box<dyn Error>> {
+        let renamer = MockRenamer {
+            files: Some(vec![FileEdits {
+                file: PathBuf::from("/spec/openapi.yaml"),
+                edits: vec![TextEdit {
+                    bytes: 0..0,
+                    old: "Pet".to_string(),
+                    new: "Animal".to_string(),
+                    text: "Animal".to_string(),
+                    line: 84,
+                    column: 5,
+                }],
+                content: String::new(),
+            }]),
+        };
+        assert_eq!(
+            rename(renamer, "Pet", "Animal")?.to_string(),
+            "/spec/openapi.yaml:84:5: Pet -> Animal"
+        );
+
+        let renamer = MockRenamer { files: None };
+        let error = rename(renamer, "Missing", "Animal").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
+        Ok(())
+    }
+
+    struct MockGenerateParser {
+        schemas: Vec<ComponentSchema>,
+    }
+    impl GenerateParser for MockGenerateParser {
+        fn get_component_schemas(&self) -> Result<Vec<ComponentSchema>, OpenapiSchemerError> {
+            Ok(self.schemas.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_export_json_schema() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let parser = || -> Result<MockGenerateParser, Box<dyn Error>> {
+            Ok(MockGenerateParser {
+                schemas: vec![
+                    schema(
+                        "Pet",
+                        r##"
+type: object
+nullable: true
+example:
+  nullable: true
+properties:
+  age:
+    type: integer
+    minimum: 0
+    exclusiveMinimum: true
+  status:
+    $ref: '#/components/schemas/Status'
+  parent:
+    $ref: '#/components/schemas/Pet'
+"##,
+                    )?,
+                    schema("Status", "enum: [available, sold]\nnullable: true")?,
+                    schema("Unused", "type: string")?,
+                ],
+            })
+        };
+        let result = export_json_schema(parser()?, Some("Pet"), false)?;
+        assert_eq!(
+            result.to_string(),
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Pet",
+  "type": [
+    "object",
+    "null"
+  ],
+  "examples": [
+    {
+      "nullable": true
+    }
+  ],
+  "properties": {
+    "age": {
+      "type": "integer",
+      "exclusiveMinimum": 0
+    },
+    "status": {
+      "$ref": "#/$defs/Status"
+    },
+    "parent": {
+      "$ref": "#"
+    }
+  },
+  "$defs": {
+    "Status": {
+      "anyOf": [
+        {
+          "enum": [
+            "available",
+            "sold",
+            null
+          ]
+        },
+        {
+          "type": "null"
+        }
+      ]
+    }
+  }
+}"##
+        );
+
+        let result = export_json_schema(parser()?, None, true)?;
+        assert_eq!(result.documents.len(), 3);
+        assert!(export_json_schema(parser()?, Some("Missing"), false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let parser = || -> Result<MockGenerateParser, Box<dyn Error>> {
+            Ok(MockGenerateParser {
+                schemas: vec![
+                    schema(
+                        "Base",
+                        r##"
+type: object
+required: [id]
+properties:
+  id:
+    type: string
+    maxLength: 64
+  parent:
+    $ref: '#/components/schemas/Base'
+"##,
+                    )?,
+                    schema(
+                        "Cat",
+                        r##"
+description: A cat
+allOf:
+  - $ref: '#/components/schemas/Base'
+  - type: object
+    required: [lives]
+    properties:
+      id:
+        maxLength: 32
+      lives:
+        type: integer
+  - type: string
+"##,
+                    )?,
+                    schema(
+                        "Pet",
+                        r##"
+oneOf:
+  - $ref: '#/components/schemas/Cat'
+  - type: string
+discriminator:
+  propertyName: kind
+  mapping:
+    cat: '#/components/schemas/Cat'
+"##,
+                    )?,
+                ],
+            })
+        };
+        let result = resolve(parser()?, "Cat", true)?;
+        assert_eq!(
+            result.to_string(),
+            r##"Cat:
+  description: A cat
+  type: object
+  required:
+    - id
+    - lives
+  properties:
+    id:
+      type: string
+      maxLength: 32
+    parent:
+      $ref: '#/components/schemas/Base'
+    lives:
+      type: integer
+  allOf:
+    - type: string"##
+        );
+
+        let result = resolve(parser()?, "Pet", true)?;
+        let variants = result
+            .schema
+            .get("oneOf")
+            .and_then(|node| node.as_sequence());
+        let annotation = |index: usize, key: &str| {
+            variants
+                .and_then(|variants| variants[index].get(key))
+                .and_then(|value| value.as_str())
+        };
+        assert_eq!(annotation(0, "x-variant"), Some("Cat"));
+        assert_eq!(annotation(0, "x-discriminator-value"), Some("cat"));
+        assert_eq!(annotation(0, "description"), Some("A cat"));
+        assert_eq!(annotation(1, "x-variant"), Some("oneOf[1]"));
+        assert_eq!(annotation(1, "x-discriminator-value"), None);
+
+        let result = resolve(parser()?, "Cat", false)?;
+        assert_eq!(
+            result
+                .schema
+                .pointer("/allOf/0/properties/id/maxLength")
+                .and_then(|node| node.as_str()),
+            Some("64")
+        );
+        let error = resolve(parser()?, "Missing", true).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_properties() -> Result<(), Box<dyn Error>> {
+        let schema = |name: &str, yaml: &str| -> Result<ComponentSchema, Box<dyn Error>> {
+            Ok(ComponentSchema {
+                name: name.to_string(),
+                schema: parse_yaml(yaml)?,
+            })
+        };
+        let parser = MockGenerateParser {
+            schemas: vec![
+                schema(
+                    "Base",
+                    r##"
+type: object
+required: [id]
+properties:
+  id:
+    type: string
+    format: uuid
+    description: The identifier
+"##,
+                )?,
+                schema(
+                    "Owner",
+                    r##"
+type: object
+nullable: true
+description: Who owns the pet
+"##,
+                )?,
+                schema(
+                    "Pet",
+                    r##"
+allOf:
+  - $ref: '#/components/schemas/Base'
+  - type: object
+    required: [name]
+    properties:
+      name:
+        type: [string, 'null']
+      owner:
+        $ref: '#/components/schemas/Owner'
+      tags:
+        type: array
+        items:
+          type: string
+"##,
+                )?,
+            ],
+        };
+        let result = properties(parser, "Pet")?;
+        assert_eq!(
+            result.to_string(),
+            "name   type           format  required  nullable  description
+id     string         uuid    yes       no        The identifier
+name   string                 yes       yes
+owner  Owner                  no        yes       Who owns the pet
+tags   array<string>          no        no"
+        );
+
+        let parser = MockGenerateParser { schemas: vec![] };
+        let error = properties(parser, "Missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find schema `Missing`");
         Ok(())
     }
 }
@@ -0,0 +1,170 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        fuzzy_score,
+        search::{SearchEntry, SearchKind, SearchParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter},
+};
+
+/// How many characters of a description are printed.
+const DESCRIPTION_WIDTH: usize = 60;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    matches: Vec<SearchEntry>,
+}
+
+impl SearchResult {
+    pub fn new(matches: Vec<SearchEntry>) -> SearchResult {
+        SearchResult { matches }
+    }
+
+    /// `operation listPets`, with long descriptions cut short.
+    fn line(entry: &SearchEntry) -> String {
+        let full = entry.text.trim_end();
+        let first_line = full.lines().next().unwrap_or_default();
+        let mut text: String = first_line.chars().take(DESCRIPTION_WIDTH).collect();
+        if text.len() < full.len() {
+            text.push_str("...");
+        }
+        format!("{} {}", entry.kind.name(), text)
+    }
+}
+
+impl Display for SearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .matches
+            .iter()
+            .map(|entry| {
+                ListEntry {
+                    name: Self::line(entry),
+                    file: entry.file.to_owned(),
+                    line: entry.line,
+                    column: entry.column,
+                }
+                .with_location()
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for SearchResult {
+    fn to_node(&self) -> YamlNode {
+        let matches = self
+            .matches
+            .iter()
+            .map(|entry| {
+                YamlNode::mapping(vec![
+                    ("kind", YamlNode::string(entry.kind.name())),
+                    ("text", YamlNode::string(&entry.text)),
+                    ("file", YamlNode::string(&entry.file.display().to_string())),
+                    ("line", YamlNode::integer(entry.line)),
+                    ("column", YamlNode::integer(entry.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(matches)
+    }
+}
+
+/// How well `entry` matches `query`, or `None` when it doesn't. Names match
+/// fuzzily while descriptions have to contain every word of the query, since
+/// almost any long text contains a short query as a subsequence. Names that
+/// equal the query rank above everything else.
+fn rank(query: &str, entry: &SearchEntry) -> Option<(bool, usize)> {
+    if entry.kind == SearchKind::Description {
+        let text = entry.text.to_lowercase();
+        let mut words = query.split_whitespace().map(str::to_lowercase);
+        if !words.all(|word| text.contains(&word)) {
+            return None;
+        }
+    }
+    let exact = entry.kind != SearchKind::Description && entry.text.eq_ignore_ascii_case(query);
+    fuzzy_score(query, &entry.text).map(|score| (exact, score))
+}
+
+/// The entries matching `query`, best match first and in the order of the
+/// spec otherwise.
+pub fn search<T: SearchParser>(
+    parser: T,
+    query: &str,
+) -> Result<SearchResult, OpenapiSchemerError> {
+    let mut matches: Vec<((bool, usize), SearchEntry)> = parser
+        .get_search_entries()?
+        .into_iter()
+        .filter_map(|entry| rank(query.trim(), &entry).map(|rank| (rank, entry)))
+        .collect();
+    matches.sort_by(|(left, _), (right, _)| right.cmp(left));
+    if matches.is_empty() {
+        return Err(OpenapiSchemerError::Search(format!(
+            "Nothing in the spec matches `{}`",
+            query
+        )));
+    }
+    Ok(SearchResult::new(
+        matches.into_iter().map(|(_, entry)| entry).collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        entries: Vec<SearchEntry>,
+    }
+    impl SearchParser for MockParser {
+        fn get_search_entries(&self) -> Result<Vec<SearchEntry>, OpenapiSchemerError> {
+            Ok(self.entries.to_owned())
+        }
+    }
+
+    fn entries() -> Vec<SearchEntry> {
+        let entry = |kind, text: &str, line| SearchEntry {
+            kind,
+            text: text.to_string(),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 7,
+        };
+        vec![
+            entry(SearchKind::Operation, "listPets", 6),
+            entry(SearchKind::Path, "/pets", 4),
+            entry(SearchKind::Schema, "Pets", 20),
+            entry(SearchKind::Description, "Returns every pet\nin pages", 7),
+            entry(SearchKind::Description, "Paginated results", 12),
+        ]
+    }
+
+    #[test]
+    fn test_search() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser { entries: entries() };
+        assert_eq!(
+            search(parser, "pets")?.to_string(),
+            "/spec/openapi.yaml:20:7: schema Pets\n\
+             /spec/openapi.yaml:6:7: operation listPets\n\
+             /spec/openapi.yaml:4:7: path /pets"
+        );
+        let parser = MockParser { entries: entries() };
+        assert_eq!(
+            search(parser, "pet")?.to_string(),
+            "/spec/openapi.yaml:6:7: operation listPets\n\
+             /spec/openapi.yaml:4:7: path /pets\n\
+             /spec/openapi.yaml:20:7: schema Pets\n\
+             /spec/openapi.yaml:7:7: description Returns every pet..."
+        );
+
+        let parser = MockParser { entries: entries() };
+        let error = search(parser, "owner").unwrap_err();
+        assert_eq!(error.to_string(), "Nothing in the spec matches `owner`");
+        Ok(())
+    }
+}
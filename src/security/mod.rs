@@ -0,0 +1,436 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        security::{ScopeNode, SecurityParser, SecuritySchemeNode},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    schemes: Vec<SecuritySchemeNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(schemes: Vec<SecuritySchemeNode>, locations: bool) -> ListResult {
+        ListResult { schemes, locations }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.schemes.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .schemes
+            .iter()
+            .map(|scheme| {
+                let entry = ListEntry {
+                    name: format!("{} ({})", scheme.text, scheme.scheme_type),
+                    file: scheme.file.to_owned(),
+                    line: scheme.line,
+                    column: scheme.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let schemes = self
+            .schemes
+            .iter()
+            .map(|scheme| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&scheme.text)),
+                    ("type", YamlNode::string(&scheme.scheme_type)),
+                    ("file", YamlNode::string(&scheme.file.display().to_string())),
+                    ("line", YamlNode::integer(scheme.line)),
+                    ("column", YamlNode::integer(scheme.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(schemes)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuditResult {
+    entries: Vec<ListEntry>,
+    locations: bool,
+}
+
+impl AuditResult {
+    pub fn new(entries: Vec<ListEntry>, locations: bool) -> AuditResult {
+        AuditResult { entries, locations }
+    }
+}
+
+impl Display for AuditResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "Every operation has a security requirement");
+        }
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| match self.locations {
+                true => entry.with_location(),
+                false => entry.name.to_owned(),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for AuditResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.entries.iter().map(ListEntry::to_node).collect())
+    }
+}
+
+/// A scope of an oauth2 security scheme and the operations that require it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScopeUsage {
+    pub scheme: String,
+    pub scope: String,
+    /// Where the scope is declared, or `None` when operations require a
+    /// scope their scheme doesn't declare.
+    pub declaration: Option<ScopeNode>,
+    pub operations: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScopesResult {
+    scopes: Vec<ScopeUsage>,
+    locations: bool,
+}
+
+impl ScopesResult {
+    pub fn new(scopes: Vec<ScopeUsage>, locations: bool) -> ScopesResult {
+        ScopesResult { scopes, locations }
+    }
+}
+
+impl Display for ScopesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .scopes
+            .iter()
+            .map(|usage| {
+                let operations = match usage.operations.is_empty() {
+                    true => "never required".to_string(),
+                    false => usage.operations.join(", "),
+                };
+                let line = format!("{} {}: {}", usage.scheme, usage.scope, operations);
+                match &usage.declaration {
+                    Some(declaration) if self.locations => ListEntry {
+                        name: line,
+                        file: declaration.file.to_owned(),
+                        line: declaration.line,
+                        column: declaration.column,
+                    }
+                    .with_location(),
+                    Some(_) => line,
+                    None => format!("{} (undeclared)", line),
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ScopesResult {
+    fn to_node(&self) -> YamlNode {
+        let scopes = self
+            .scopes
+            .iter()
+            .map(|usage| {
+                let declaration = usage.declaration.as_ref();
+                let location = |value: Option<YamlNode>| value.unwrap_or_else(YamlNode::null);
+                YamlNode::mapping(vec![
+                    ("scheme", YamlNode::string(&usage.scheme)),
+                    ("scope", YamlNode::string(&usage.scope)),
+                    ("declared", YamlNode::boolean(declaration.is_some())),
+                    (
+                        "description",
+                        location(
+                            declaration
+                                .and_then(|declaration| declaration.description.as_deref())
+                                .map(YamlNode::string),
+                        ),
+                    ),
+                    (
+                        "operations",
+                        YamlNode::sequence(
+                            usage
+                                .operations
+                                .iter()
+                                .map(|operation| YamlNode::string(operation))
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        "file",
+                        location(declaration.map(|declaration| {
+                            YamlNode::string(&declaration.file.display().to_string())
+                        })),
+                    ),
+                    (
+                        "line",
+                        location(
+                            declaration.map(|declaration| YamlNode::integer(declaration.line)),
+                        ),
+                    ),
+                    (
+                        "column",
+                        location(
+                            declaration.map(|declaration| YamlNode::integer(declaration.column)),
+                        ),
+                    ),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(scopes)
+    }
+}
+
+pub fn list<T: SecurityParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_security_scheme_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+pub fn audit<T: SecurityParser>(
+    parser: T,
+    locations: bool,
+) -> Result<AuditResult, OpenapiSchemerError> {
+    let nodes = parser.get_unsecured_operations()?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(AuditResult::new(entries, locations))
+}
+
+/// Every scope declared by an oauth2 security scheme with the operations
+/// that require it, followed by the scopes operations require that their
+/// scheme doesn't declare. Operations are named by their operationId, or by
+/// method and path without one.
+pub fn scopes<T: SecurityParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ScopesResult, OpenapiSchemerError> {
+    let mut scopes: Vec<ScopeUsage> = parser
+        .get_declared_scopes()?
+        .into_iter()
+        .map(|declaration| ScopeUsage {
+            scheme: declaration.scheme.to_owned(),
+            scope: declaration.text.to_owned(),
+            declaration: Some(declaration),
+            operations: vec![],
+        })
+        .collect();
+    for security in parser.get_operation_security()? {
+        let operation = &security.operation;
+        let name = operation
+            .operation_id
+            .to_owned()
+            .unwrap_or_else(|| operation.describe());
+        for required in security.requirements.iter().flatten() {
+            // Only oauth2 schemes declare their scopes.
+            let oauth2 = required
+                .scheme
+                .as_ref()
+                .and_then(|scheme| scheme.get("type"))
+                .and_then(|kind| kind.as_str())
+                == Some("oauth2");
+            if !oauth2 {
+                continue;
+            }
+            for scope in &required.scopes {
+                let index = scopes
+                    .iter()
+                    .position(|usage| usage.scheme == required.name && usage.scope == *scope);
+                let usage = match index {
+                    Some(index) => &mut scopes[index],
+                    None => {
+                        scopes.push(ScopeUsage {
+                            scheme: required.name.to_owned(),
+                            scope: scope.to_owned(),
+                            declaration: None,
+                            operations: vec![],
+                        });
+                        scopes.last_mut().unwrap()
+                    }
+                };
+                if !usage.operations.contains(&name) {
+                    usage.operations.push(name.to_owned());
+                }
+            }
+        }
+    }
+    Ok(ScopesResult::new(scopes, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::{
+        security::{OperationSecurity, RequiredScheme},
+        yaml::parse_yaml,
+        OperationNode,
+    };
+
+    use super::*;
+
+    struct MockParser {
+        schemes: Vec<SecuritySchemeNode>,
+        operations: Vec<OperationNode>,
+        scopes: Vec<ScopeNode>,
+        security: Vec<OperationSecurity>,
+    }
+    impl SecurityParser for MockParser {
+        fn get_security_scheme_nodes(
+            &self,
+        ) -> Result<Vec<SecuritySchemeNode>, OpenapiSchemerError> {
+            Ok(self.schemes.to_owned())
+        }
+
+        fn get_unsecured_operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+            Ok(self.operations.to_owned())
+        }
+
+        fn get_operation_security(&self) -> Result<Vec<OperationSecurity>, OpenapiSchemerError> {
+            Ok(self.security.to_owned())
+        }
+
+        fn get_declared_scopes(&self) -> Result<Vec<ScopeNode>, OpenapiSchemerError> {
+            Ok(self.scopes.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            schemes: vec![SecuritySchemeNode {
+                text: "ApiKey".to_string(),
+                scheme_type: "apiKey".to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 4,
+                column: 5,
+            }],
+            operations: vec![],
+            scopes: vec![],
+            security: vec![],
+        };
+        assert_eq!(list(parser, false)?.to_string(), "ApiKey (apiKey)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            schemes: vec![],
+            operations: vec![OperationNode {
+                text: "GET /pets (listPets)".to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 9,
+                column: 20,
+                ..Default::default()
+            }],
+            scopes: vec![],
+            security: vec![],
+        };
+        assert_eq!(
+            audit(parser, true)?.to_string(),
+            "/spec/openapi.yaml:9:20: GET /pets (listPets)"
+        );
+
+        let parser = MockParser {
+            schemes: vec![],
+            operations: vec![],
+            scopes: vec![],
+            security: vec![],
+        };
+        assert_eq!(
+            audit(parser, false)?.to_string(),
+            "Every operation has a security requirement"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_scopes() -> Result<(), Box<dyn Error>> {
+        let scope = |text: &str, line| ScopeNode {
+            scheme: "OAuth".to_string(),
+            text: text.to_string(),
+            description: None,
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 13,
+        };
+        let required = |name: &str, kind: &str, scopes: &[&str]| RequiredScheme {
+            name: name.to_string(),
+            scheme: Some(parse_yaml(&format!("type: {}", kind)).unwrap()),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        };
+        let operation = |operation_id: Option<&str>, requirements| OperationSecurity {
+            operation: OperationNode {
+                operation_id: operation_id.map(str::to_owned),
+                method: "get".to_string(),
+                path: "/pets".to_string(),
+                ..Default::default()
+            },
+            requirements,
+            inherited: false,
+        };
+        let parser = MockParser {
+            schemes: vec![],
+            operations: vec![],
+            scopes: vec![scope("read:pets", 11), scope("write:pets", 12)],
+            security: vec![
+                operation(
+                    Some("listPets"),
+                    vec![
+                        vec![required("OAuth", "oauth2", &["read:pets"])],
+                        vec![required("Oidc", "openIdConnect", &["openid"])],
+                    ],
+                ),
+                operation(
+                    None,
+                    vec![vec![required("OAuth", "oauth2", &["read:pets", "admin"])]],
+                ),
+            ],
+        };
+        assert_eq!(
+            scopes(parser, true)?.to_string(),
+            "/spec/openapi.yaml:11:13: OAuth read:pets: listPets, GET /pets\n\
+             /spec/openapi.yaml:12:13: OAuth write:pets: never required\n\
+             OAuth admin: GET /pets (undeclared)"
+        );
+        Ok(())
+    }
+}
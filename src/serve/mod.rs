@@ -0,0 +1,287 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    bindings::{
+        serve::{MockResponse, MockRoute, ServeParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    path::match_template,
+};
+
+/// How long a client may take to send its request before the connection is
+/// dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answers requests on `127.0.0.1:port` with the responses the spec
+/// declares, until the process is stopped. Port `0` picks a free port. Each
+/// connection is answered on its own thread, so a slow client doesn't hold
+/// up the others. Each request is logged as `GET /pets -> 200`.
+pub fn serve<T: ServeParser>(parser: T, port: u16) -> Result<(), OpenapiSchemerError> {
+    let routes = parser.get_routes()?;
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|error| {
+        OpenapiSchemerError::Serve(format!("Could not listen on port {}: {}", port, error))
+    })?;
+    let address = listener
+        .local_addr()
+        .map_err(|error| OpenapiSchemerError::Serve(error.to_string()))?;
+    println!("Serving {} operations on http://{}", routes.len(), address);
+    thread::scope(|scope| {
+        for stream in listener.incoming().flatten() {
+            let routes = &routes;
+            scope.spawn(move || {
+                // A client that goes away or stalls mid request only loses
+                // its own response.
+                if let Ok(line) = handle(stream, routes, READ_TIMEOUT) {
+                    println!("{}", line);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Reads one request from `stream` and writes back its response. Every
+/// response closes the connection, so keep-alive is never needed. Reading
+/// fails once the client sends nothing for `timeout`.
+fn handle(stream: TcpStream, routes: &[MockRoute], timeout: Duration) -> std::io::Result<String> {
+    stream.set_read_timeout(Some(timeout))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let (mut accept, mut length) = (None, 0);
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let (name, value) = match header.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => continue,
+        };
+        if name.eq_ignore_ascii_case("accept") {
+            accept = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            length = value.parse().unwrap_or_default();
+        }
+    }
+    // The request body is never looked at, but it is read so the client is
+    // not cut off while still sending it.
+    std::io::copy(&mut reader.by_ref().take(length), &mut std::io::sink())?;
+
+    let mut parts = request_line.split_whitespace();
+    let (response, request) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (
+            respond(routes, method, target, accept.as_deref()),
+            format!("{} {}", method, target),
+        ),
+        _ => (
+            message(400, "Malformed request line"),
+            request_line.trim().to_string(),
+        ),
+    };
+    let body = render(&response);
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+    if let Some(content_type) = &response.content_type {
+        head.push_str(&format!("Content-Type: {}\r\n", content_type));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    (&stream).write_all(head.as_bytes())?;
+    (&stream).write_all(body.as_bytes())?;
+    Ok(format!("{} -> {}", request, response.status))
+}
+
+/// The response to `method` on `target`. A path matching several templates
+/// is routed to the one with the fewest parameters, so `/pets/mine` wins
+/// over `/pets/{petId}`.
+pub fn respond(
+    routes: &[MockRoute],
+    method: &str,
+    target: &str,
+    accept: Option<&str>,
+) -> MockResponse {
+    let path = target.split('?').next().unwrap_or_default();
+    let mut matching: Vec<&MockRoute> = routes
+        .iter()
+        .filter(|route| match_template(&route.path, path).is_some())
+        .collect();
+    if matching.is_empty() {
+        return message(404, &format!("No path matches `{}`", path));
+    }
+    matching.sort_by_key(|route| route.path.matches('{').count());
+    match matching
+        .iter()
+        .find(|route| route.method.eq_ignore_ascii_case(method))
+    {
+        Some(route) => route.respond(accept),
+        None => message(
+            405,
+            &format!("Method `{}` is not defined for `{}`", method, path),
+        ),
+    }
+}
+
+/// A JSON `{"message": ...}` response for requests the spec doesn't answer.
+fn message(status: u16, message: &str) -> MockResponse {
+    MockResponse {
+        status,
+        content_type: Some("application/json".to_string()),
+        body: Some(YamlNode::mapping(vec![(
+            "message",
+            YamlNode::string(message),
+        )])),
+    }
+}
+
+/// The body of `response` written in its media type.
+pub fn render(response: &MockResponse) -> String {
+    match &response.body {
+        Some(body) => render_media(response.content_type.as_deref().unwrap_or_default(), body),
+        None => String::new(),
+    }
+}
+
+/// `body` written as `content_type`. JSON and YAML media types are rendered
+/// as such, anything else only makes sense for scalars.
+pub fn render_media(content_type: &str, body: &YamlNode) -> String {
+    if content_type.contains("json") {
+        body.to_json()
+    } else if content_type.contains("yaml") {
+        body.to_yaml()
+    } else {
+        match body.as_str() {
+            Some(text) => text.to_string(),
+            None => body.to_yaml(),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+    };
+
+    use crate::bindings::yaml::parse_yaml;
+
+    use super::*;
+
+    fn routes() -> Result<Vec<MockRoute>, Box<dyn Error>> {
+        let route =
+            |method: &str, path: &str, responses: &str| -> Result<MockRoute, Box<dyn Error>> {
+                Ok(MockRoute {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    responses: parse_yaml(responses)?,
+                })
+            };
+        Ok(vec![
+            route(
+                "get",
+                "/pets/{petId}",
+                "'200':\n  content:\n    application/json:\n      example:\n        id: 1\n",
+            )?,
+            route(
+                "get",
+                "/pets/mine",
+                "'200':\n  content:\n    text/plain:\n      example: Rex\n",
+            )?,
+            route(
+                "get",
+                "/files/{name}.{extension}",
+                "'200':\n  content:\n    text/plain:\n      example: report\n",
+            )?,
+        ])
+    }
+
+    #[test]
+    fn test_respond() -> Result<(), Box<dyn Error>> {
+        let routes = routes()?;
+        let response = respond(&routes, "GET", "/pets/1?expand=owner", None);
+        assert_eq!(
+            (response.status, render(&response)),
+            (200, "{\n  \"id\": 1\n}".to_string())
+        );
+        let response = respond(&routes, "GET", "/pets/mine", None);
+        assert_eq!(render(&response), "Rex");
+        let response = respond(&routes, "GET", "/files/report.pdf", None);
+        assert_eq!(render(&response), "report");
+        assert_eq!(respond(&routes, "GET", "/files/report", None).status, 404);
+        assert_eq!(respond(&routes, "GET", "/pets/", None).status, 404);
+
+        let response = respond(&routes, "GET", "/owners", None);
+        assert_eq!(response.status, 404);
+        assert_eq!(
+            render(&response),
+            "{\n  \"message\": \"No path matches `/owners`\"\n}"
+        );
+        assert_eq!(respond(&routes, "DELETE", "/pets/1", None).status, 405);
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle() -> Result<(), Box<dyn Error>> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let mut client = TcpStream::connect(listener.local_addr()?)?;
+        client
+            .write_all(b"GET /pets/mine HTTP/1.1\r\nHost: localhost\r\nAccept: text/*\r\n\r\n")?;
+        let (server, _) = listener.accept()?;
+        let line = handle(server, &routes()?, READ_TIMEOUT)?;
+        assert_eq!(line, "GET /pets/mine -> 200");
+
+        let mut response = String::new();
+        client.read_to_string(&mut response)?;
+        assert_eq!(
+            response,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 3\r\nConnection: close\r\n\r\nRex"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_timeout() -> Result<(), Box<dyn Error>> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let mut client = TcpStream::connect(listener.local_addr()?)?;
+        client.write_all(b"GET /pets/mine HTTP/1.1\r\n")?;
+        let (server, _) = listener.accept()?;
+        let error = handle(server, &routes()?, Duration::from_millis(50)).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+        ));
+        Ok(())
+    }
+}
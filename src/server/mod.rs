@@ -0,0 +1,131 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        server::{ServerNode, ServerParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    servers: Vec<ServerNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(servers: Vec<ServerNode>, locations: bool) -> ListResult {
+        ListResult { servers, locations }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.servers.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .servers
+            .iter()
+            .map(|server| {
+                let name = match &server.scope {
+                    Some(scope) => format!("{} ({})", server.text, scope),
+                    None => server.text.to_owned(),
+                };
+                let entry = ListEntry {
+                    name,
+                    file: server.file.to_owned(),
+                    line: server.line,
+                    column: server.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let optional = |value: &Option<String>| match value {
+            Some(value) => YamlNode::string(value),
+            None => YamlNode::null(),
+        };
+        let servers = self
+            .servers
+            .iter()
+            .map(|server| {
+                YamlNode::mapping(vec![
+                    ("url", YamlNode::string(&server.text)),
+                    ("description", optional(&server.description)),
+                    ("scope", optional(&server.scope)),
+                    ("file", YamlNode::string(&server.file.display().to_string())),
+                    ("line", YamlNode::integer(server.line)),
+                    ("column", YamlNode::integer(server.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(servers)
+    }
+}
+
+pub fn list<T: ServerParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_server_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<ServerNode>,
+    }
+    impl ServerParser for MockParser {
+        fn get_server_nodes(&self) -> Result<Vec<ServerNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            nodes: vec![
+                ServerNode {
+                    text: "https://api.example.com/v1".to_string(),
+                    description: None,
+                    scope: None,
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 3,
+                    column: 10,
+                },
+                ServerNode {
+                    text: "https://pets.example.com".to_string(),
+                    description: None,
+                    scope: Some("/pets".to_string()),
+                    file: PathBuf::from("/spec/openapi.yaml"),
+                    line: 9,
+                    column: 14,
+                },
+            ],
+        };
+        assert_eq!(
+            list(parser, false)?.to_string(),
+            "https://api.example.com/v1\nhttps://pets.example.com (/pets)"
+        );
+        Ok(())
+    }
+}
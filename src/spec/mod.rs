@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use crate::{
+    bindings::{
+        operation::TreeSitterOperationParser,
+        path::{PathNode, PathParser, TreeSitterPathParser},
+        schema::{SchemaNode, SchemaParser, TreeSitterSchemaParser},
+        OperationNode, OperationParser,
+    },
+    content::ContentProviderMap,
+    error::OpenapiSchemerError,
+};
+
+/// A loaded spec along with every file it refers to.
+#[derive(Clone, Debug)]
+pub struct Spec {
+    provider: ContentProviderMap,
+}
+
+impl Spec {
+    /// Loads the spec at `path`, following refs into other files.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Spec, OpenapiSchemerError> {
+        ContentProviderMap::from_open_api_yaml(path.into()).map(Spec::new)
+    }
+
+    pub fn new(provider: ContentProviderMap) -> Spec {
+        Spec { provider }
+    }
+
+    pub fn operations(&self) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+        TreeSitterOperationParser::new(Box::new(self.provider.to_owned())).get_operation_nodes()
+    }
+
+    pub fn paths(&self) -> Result<Vec<PathNode>, OpenapiSchemerError> {
+        TreeSitterPathParser::new(Box::new(self.provider.to_owned())).get_path_nodes()
+    }
+
+    pub fn schemas(&self) -> Result<Vec<SchemaNode>, OpenapiSchemerError> {
+        TreeSitterSchemaParser::new(Box::new(self.provider.to_owned())).get_schema_nodes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, error::Error, path::PathBuf};
+
+    use crate::content::ContentProviderMap;
+
+    use super::Spec;
+
+    #[test]
+    fn spec() -> Result<(), Box<dyn Error>> {
+        let root_content = r#"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+components:
+  schemas:
+    Pet:
+      type: object
+"#;
+        let contents = HashMap::from([(PathBuf::from("#"), root_content.to_owned())]);
+        let spec = Spec::new(ContentProviderMap::from_map(contents));
+        let names = |texts: Vec<String>| texts.join(",");
+        assert_eq!(
+            names(
+                spec.operations()?
+                    .into_iter()
+                    .map(|node| node.text)
+                    .collect()
+            ),
+            "listPets"
+        );
+        assert_eq!(
+            names(spec.paths()?.into_iter().map(|node| node.text).collect()),
+            "/pets"
+        );
+        assert_eq!(
+            names(spec.schemas()?.into_iter().map(|node| node.text).collect()),
+            "Pet"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::{fmt::Display, path::Path};
+
+use crate::{
+    bindings::{
+        split::{SplitFile, Splitter},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{write_file, OutputFormatter},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct SplitResult {
+    files: Vec<SplitFile>,
+}
+
+impl SplitResult {
+    pub fn new(files: Vec<SplitFile>) -> SplitResult {
+        SplitResult { files }
+    }
+
+    /// Writes the files under `directory`. Files ending in `.json` are
+    /// written as JSON, the others as YAML.
+    pub fn write(&self, directory: &Path) -> Result<(), OpenapiSchemerError> {
+        for file in &self.files {
+            let content = match file
+                .path
+                .extension()
+                .and_then(|extension| extension.to_str())
+            {
+                Some("json") => format!("{}\n", file.document.to_json()),
+                _ => file.document.to_yaml(),
+            };
+            write_file(&directory.join(&file.path), &content)
+                .map_err(OpenapiSchemerError::Split)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for SplitResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .files
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for SplitResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.files
+                .iter()
+                .map(|file| YamlNode::string(&file.path.display().to_string()))
+                .collect(),
+        )
+    }
+}
+
+pub fn split<T: Splitter>(splitter: T) -> Result<SplitResult, OpenapiSchemerError> {
+    Ok(SplitResult::new(splitter.split()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::output::OutputFormat;
+
+    use super::*;
+
+    struct MockSplitter {
+        files: Vec<SplitFile>,
+    }
+    impl Splitter for MockSplitter {
+        fn split(&self) -> Result<Vec<SplitFile>, OpenapiSchemerError> {
+            Ok(self.files.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn Error>> {
+        let splitter = MockSplitter {
+            files: vec![
+                SplitFile {
+                    path: PathBuf::from("openapi.yaml"),
+                    document: YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.0"))]),
+                },
+                SplitFile {
+                    path: PathBuf::from("paths/pets.yaml"),
+                    document: YamlNode::mapping(vec![]),
+                },
+            ],
+        };
+        let result = split(splitter)?;
+        assert_eq!(result.to_string(), "openapi.yaml\npaths/pets.yaml");
+        assert_eq!(
+            result.format(OutputFormat::Json),
+            "[\n  \"openapi.yaml\",\n  \"paths/pets.yaml\"\n]"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        stats::{Stats, StatsParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatsResult {
+    stats: Stats,
+}
+
+impl StatsResult {
+    pub fn new(stats: Stats) -> StatsResult {
+        StatsResult { stats }
+    }
+
+    /// The share of operations with a description, rounded to a whole
+    /// percent. A spec without operations has none described.
+    fn described_percent(&self) -> usize {
+        match self.stats.operations() {
+            0 => 0,
+            operations => (self.stats.described_operations * 200 + operations) / (operations * 2),
+        }
+    }
+}
+
+impl Display for StatsResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stats = &self.stats;
+        let methods: Vec<String> = stats
+            .methods
+            .iter()
+            .map(|(method, count)| format!("{} {}", method.to_uppercase(), count))
+            .collect();
+        let mut lines = vec![
+            format!("Paths: {}", stats.paths),
+            match methods.is_empty() {
+                true => "Operations: 0".to_string(),
+                false => format!(
+                    "Operations: {} ({})",
+                    stats.operations(),
+                    methods.join(", ")
+                ),
+            },
+            format!(
+                "Operations with descriptions: {} of {} ({}%)",
+                stats.described_operations,
+                stats.operations(),
+                self.described_percent()
+            ),
+            format!("Schemas: {}", stats.schemas),
+            format!(
+                "Refs: {} internal, {} external",
+                stats.internal_refs, stats.external_refs
+            ),
+            format!("Files: {}", stats.files),
+        ];
+        if let Some((name, depth)) = &stats.deepest_schema {
+            lines.push(format!("Deepest schema: {} ({} levels)", name, depth));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for StatsResult {
+    fn to_node(&self) -> YamlNode {
+        let stats = &self.stats;
+        let methods = stats
+            .methods
+            .iter()
+            .map(|(method, count)| (method.as_str(), YamlNode::integer(*count)))
+            .collect();
+        let deepest_schema = match &stats.deepest_schema {
+            Some((name, depth)) => YamlNode::mapping(vec![
+                ("name", YamlNode::string(name)),
+                ("depth", YamlNode::integer(*depth)),
+            ]),
+            None => YamlNode::null(),
+        };
+        YamlNode::mapping(vec![
+            ("paths", YamlNode::integer(stats.paths)),
+            (
+                "operations",
+                YamlNode::mapping(vec![
+                    ("total", YamlNode::integer(stats.operations())),
+                    ("methods", YamlNode::mapping(methods)),
+                    ("described", YamlNode::integer(stats.described_operations)),
+                    (
+                        "describedPercent",
+                        YamlNode::integer(self.described_percent()),
+                    ),
+                ]),
+            ),
+            ("schemas", YamlNode::integer(stats.schemas)),
+            (
+                "refs",
+                YamlNode::mapping(vec![
+                    ("internal", YamlNode::integer(stats.internal_refs)),
+                    ("external", YamlNode::integer(stats.external_refs)),
+                ]),
+            ),
+            ("files", YamlNode::integer(stats.files)),
+            ("deepestSchema", deepest_schema),
+        ])
+    }
+}
+
+pub fn stats<T: StatsParser>(parser: T) -> Result<StatsResult, OpenapiSchemerError> {
+    Ok(StatsResult::new(parser.get_stats()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    struct MockParser {
+        stats: Stats,
+    }
+    impl StatsParser for MockParser {
+        fn get_stats(&self) -> Result<Stats, OpenapiSchemerError> {
+            Ok(self.stats.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_stats() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser {
+            stats: Stats {
+                paths: 2,
+                methods: vec![("get".to_string(), 2), ("post".to_string(), 1)],
+                described_operations: 2,
+                schemas: 3,
+                internal_refs: 4,
+                external_refs: 1,
+                files: 2,
+                deepest_schema: Some(("Pets".to_string(), 3)),
+            },
+        };
+        let result = stats(parser)?;
+        assert_eq!(
+            result.to_string(),
+            "Paths: 2\n\
+             Operations: 3 (GET 2, POST 1)\n\
+             Operations with descriptions: 2 of 3 (67%)\n\
+             Schemas: 3\n\
+             Refs: 4 internal, 1 external\n\
+             Files: 2\n\
+             Deepest schema: Pets (3 levels)"
+        );
+
+        let parser = MockParser {
+            stats: Stats::default(),
+        };
+        assert_eq!(
+            stats(parser)?.to_string(),
+            "Paths: 0\n\
+             Operations: 0\n\
+             Operations with descriptions: 0 of 0 (0%)\n\
+             Schemas: 0\n\
+             Refs: 0 internal, 0 external\n\
+             Files: 0"
+        );
+        Ok(())
+    }
+}
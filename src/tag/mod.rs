@@ -0,0 +1,211 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        tag::{TagNode, TagParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    entries: Vec<ListEntry>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(list: Vec<ListEntry>, locations: bool) -> ListResult {
+        ListResult {
+            entries: list,
+            locations,
+        }
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.entries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| match self.locations {
+                true => entry.with_location(),
+                false => entry.name.to_owned(),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(self.entries.iter().map(ListEntry::to_node).collect())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ShowResult {
+    tag: TagNode,
+    operations: Vec<String>,
+}
+
+impl ShowResult {
+    pub fn new(tag: TagNode, operations: Vec<String>) -> ShowResult {
+        ShowResult { tag, operations }
+    }
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_node().to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for ShowResult {
+    fn to_node(&self) -> YamlNode {
+        let description = match &self.tag.description {
+            Some(description) => YamlNode::string(description),
+            None => YamlNode::null(),
+        };
+        YamlNode::mapping(vec![
+            ("name", YamlNode::string(&self.tag.text)),
+            ("description", description),
+            ("declared", YamlNode::boolean(self.tag.declared)),
+            (
+                "operations",
+                YamlNode::sequence(
+                    self.operations
+                        .iter()
+                        .map(|operation| YamlNode::string(operation))
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+pub fn list<T: TagParser>(parser: T, locations: bool) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_tag_nodes()?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(ListResult::new(entries, locations))
+}
+
+pub fn show<T: TagParser>(parser: T, name: &str) -> Result<ShowResult, OpenapiSchemerError> {
+    let tag = parser
+        .get_tag_nodes()?
+        .into_iter()
+        .find(|node| node.text == name)
+        .ok_or_else(|| OpenapiSchemerError::TagShow(format!("Could not find tag `{}`", name)))?;
+    let operations = parser
+        .get_tag_operations(name)?
+        .into_iter()
+        .map(|node| node.text)
+        .collect();
+    Ok(ShowResult::new(tag, operations))
+}
+
+pub fn operations<T: TagParser>(
+    parser: T,
+    name: &str,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_tag_operations(name)?;
+    let entries = nodes
+        .into_iter()
+        .map(|node| ListEntry {
+            name: node.text,
+            file: node.file,
+            line: node.line,
+            column: node.column,
+        })
+        .collect();
+    Ok(ListResult::new(entries, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::OperationNode;
+
+    use super::*;
+
+    struct MockParser {
+        tags: Vec<TagNode>,
+        operations: Vec<OperationNode>,
+    }
+    impl TagParser for MockParser {
+        fn get_tag_nodes(&self) -> Result<Vec<TagNode>, OpenapiSchemerError> {
+            Ok(self.tags.to_owned())
+        }
+
+        fn get_tag_operations(
+            &self,
+            _tag: &str,
+        ) -> Result<Vec<OperationNode>, OpenapiSchemerError> {
+            Ok(self.operations.to_owned())
+        }
+    }
+
+    fn parser() -> MockParser {
+        MockParser {
+            tags: vec![TagNode {
+                text: "pets".to_string(),
+                description: Some("Everything about your pets".to_string()),
+                declared: true,
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 3,
+                column: 11,
+            }],
+            operations: vec![OperationNode {
+                text: "listPets".to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 9,
+                column: 20,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        assert_eq!(list(parser(), false)?.to_string(), "pets");
+        assert_eq!(
+            list(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:3:11: pets"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_show() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            show(parser(), "pets")?.to_string(),
+            "name: pets\ndescription: Everything about your pets\ndeclared: true\noperations:\n  - listPets"
+        );
+        let error = show(parser(), "missing").unwrap_err();
+        assert_eq!(error.to_string(), "Could not find tag `missing`");
+        Ok(())
+    }
+
+    #[test]
+    fn test_operations() -> Result<(), Box<dyn Error>> {
+        assert_eq!(operations(parser(), "pets", false)?.to_string(), "listPets");
+        Ok(())
+    }
+}
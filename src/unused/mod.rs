@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        unused::{UnusedNode, UnusedParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnusedResult {
+    components: Vec<UnusedNode>,
+    locations: bool,
+}
+
+impl UnusedResult {
+    pub fn new(components: Vec<UnusedNode>, locations: bool) -> UnusedResult {
+        UnusedResult {
+            components,
+            locations,
+        }
+    }
+}
+
+impl Display for UnusedResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.components.is_empty() {
+            return write!(f, "Every component is referenced");
+        }
+        let lines: Vec<String> = self
+            .components
+            .iter()
+            .map(|component| {
+                let entry = ListEntry {
+                    name: format!("{} ({})", component.text, component.kind),
+                    file: component.file.to_owned(),
+                    line: component.line,
+                    column: component.column,
+                };
+                match self.locations {
+                    true => entry.with_location(),
+                    false => entry.name,
+                }
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for UnusedResult {
+    fn to_node(&self) -> YamlNode {
+        let components = self
+            .components
+            .iter()
+            .map(|component| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&component.text)),
+                    ("kind", YamlNode::string(&component.kind)),
+                    (
+                        "file",
+                        YamlNode::string(&component.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(component.line)),
+                    ("column", YamlNode::integer(component.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(components)
+    }
+}
+
+pub fn unused<T: UnusedParser>(
+    parser: T,
+    locations: bool,
+) -> Result<UnusedResult, OpenapiSchemerError> {
+    let components = parser.get_unused_components()?;
+    Ok(UnusedResult::new(components, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        components: Vec<UnusedNode>,
+    }
+    impl UnusedParser for MockParser {
+        fn get_unused_components(&self) -> Result<Vec<UnusedNode>, OpenapiSchemerError> {
+            Ok(self.components.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_unused() -> Result<(), Box<dyn Error>> {
+        let parser = || MockParser {
+            components: vec![UnusedNode {
+                text: "Owner".to_string(),
+                kind: "schema".to_string(),
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 30,
+                column: 5,
+            }],
+        };
+        assert_eq!(unused(parser(), false)?.to_string(), "Owner (schema)");
+        assert_eq!(
+            unused(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:30:5: Owner (schema)"
+        );
+
+        let parser = MockParser { components: vec![] };
+        assert_eq!(
+            unused(parser, false)?.to_string(),
+            "Every component is referenced"
+        );
+        Ok(())
+    }
+}
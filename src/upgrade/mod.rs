@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{upgrade::Upgrader, yaml::YamlNode},
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct UpgradeResult {
+    document: YamlNode,
+}
+
+impl UpgradeResult {
+    pub fn new(document: YamlNode) -> UpgradeResult {
+        UpgradeResult { document }
+    }
+}
+
+impl Display for UpgradeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document.to_yaml().trim_end())
+    }
+}
+
+impl OutputFormatter for UpgradeResult {
+    fn to_node(&self) -> YamlNode {
+        self.document.to_owned()
+    }
+}
+
+pub fn upgrade<T: Upgrader>(upgrader: T) -> Result<UpgradeResult, OpenapiSchemerError> {
+    let document = upgrader.upgrade()?;
+    Ok(UpgradeResult::new(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    struct MockUpgrader {
+        document: YamlNode,
+    }
+    impl Upgrader for MockUpgrader {
+        fn upgrade(&self) -> Result<YamlNode, OpenapiSchemerError> {
+            Ok(self.document.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_upgrade() -> Result<(), Box<dyn Error>> {
+        let document = YamlNode::mapping(vec![("openapi", YamlNode::string("3.0.3"))]);
+        let upgrader = MockUpgrader { document };
+        let result = upgrade(upgrader)?;
+        assert_eq!(result.to_string(), "openapi: 3.0.3");
+        assert_eq!(result.to_node().to_json(), "{\n  \"openapi\": \"3.0.3\"\n}");
+        Ok(())
+    }
+}
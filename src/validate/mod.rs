@@ -0,0 +1,387 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{
+    bindings::{
+        contract::{ContractChecker, ContractProblem, RequestSample, ResponseSample},
+        validate::{Diagnostic, ValidationParser},
+        yaml::{parse_yaml, YamlNode},
+    },
+    error::OpenapiSchemerError,
+    output::OutputFormatter,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ValidateResult {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidateResult {
+    pub fn new(diagnostics: Vec<Diagnostic>) -> ValidateResult {
+        ValidateResult { diagnostics }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+impl Display for ValidateResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.diagnostics.is_empty() {
+            return write!(f, "No problems found");
+        }
+        let lines: Vec<String> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                format!(
+                    "{}:{}:{}: {}",
+                    diagnostic.file.display(),
+                    diagnostic.line,
+                    diagnostic.column,
+                    diagnostic.message
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ValidateResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    YamlNode::mapping(vec![
+                        (
+                            "file",
+                            YamlNode::string(&diagnostic.file.display().to_string()),
+                        ),
+                        ("line", YamlNode::integer(diagnostic.line)),
+                        ("column", YamlNode::integer(diagnostic.column)),
+                        ("message", YamlNode::string(&diagnostic.message)),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContractResult {
+    /// The file the body was read from, where problems with it are located.
+    body_file: Option<PathBuf>,
+    problems: Vec<ContractProblem>,
+}
+
+impl ContractResult {
+    pub fn new(body_file: Option<PathBuf>, problems: Vec<ContractProblem>) -> ContractResult {
+        ContractResult {
+            body_file,
+            problems,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    fn location(&self, problem: &ContractProblem) -> Option<(PathBuf, usize, usize)> {
+        let (line, column) = problem.position?;
+        Some((self.body_file.to_owned()?, line, column))
+    }
+}
+
+impl Display for ContractResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.problems.is_empty() {
+            return write!(f, "No problems found");
+        }
+        let lines: Vec<String> = self
+            .problems
+            .iter()
+            .map(|problem| match self.location(problem) {
+                Some((file, line, column)) => format!(
+                    "{}:{}:{}: {}",
+                    file.display(),
+                    line,
+                    column,
+                    problem.message
+                ),
+                None => problem.message.to_owned(),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ContractResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::sequence(
+            self.problems
+                .iter()
+                .map(|problem| {
+                    let mut pairs = vec![("message", YamlNode::string(&problem.message))];
+                    if let Some((file, line, column)) = self.location(problem) {
+                        pairs.push(("file", YamlNode::string(&file.display().to_string())));
+                        pairs.push(("line", YamlNode::integer(line)));
+                        pairs.push(("column", YamlNode::integer(column)));
+                    }
+                    YamlNode::mapping(pairs)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Splits `name=value` arguments.
+fn name_values(
+    arguments: &[String],
+    error: fn(String) -> OpenapiSchemerError,
+) -> Result<Vec<(String, String)>, OpenapiSchemerError> {
+    arguments
+        .iter()
+        .map(|argument| {
+            argument
+                .split_once('=')
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .ok_or_else(|| error(format!("Expected `name=value` but found `{}`", argument)))
+        })
+        .collect()
+}
+
+fn read_body(
+    path: Option<&Path>,
+    error: fn(String) -> OpenapiSchemerError,
+) -> Result<Option<YamlNode>, OpenapiSchemerError> {
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read body `{}`", path.display()))
+        .and_then(|content| parse_yaml(&content).context("Failed to parse body"))
+        .map(Some)
+        .map_err(|cause| OpenapiSchemerError::from_anyhow(cause, error))
+}
+
+/// Checks a request to the operation with `operation_id`: its `name=value`
+/// parameters and the JSON or YAML body in the file `body`.
+pub fn request<T: ContractChecker>(
+    checker: T,
+    operation_id: &str,
+    params: &[String],
+    body: Option<&Path>,
+    content_type: Option<String>,
+) -> Result<ContractResult, OpenapiSchemerError> {
+    let request = RequestSample {
+        params: name_values(params, OpenapiSchemerError::ValidateRequest)?,
+        body: read_body(body, OpenapiSchemerError::ValidateRequest)?,
+        content_type,
+    };
+    match checker.check_request(operation_id, &request)? {
+        Some(problems) => Ok(ContractResult::new(body.map(Path::to_path_buf), problems)),
+        None => Err(OpenapiSchemerError::ValidateRequest(format!(
+            "Could not find operation `{}`",
+            operation_id
+        ))),
+    }
+}
+
+/// Checks a response of the operation with `operation_id`: its status, its
+/// `name=value` headers and the JSON or YAML body in the file `body`.
+pub fn response<T: ContractChecker>(
+    checker: T,
+    operation_id: &str,
+    status: u16,
+    headers: &[String],
+    body: Option<&Path>,
+    content_type: Option<String>,
+) -> Result<ContractResult, OpenapiSchemerError> {
+    let response = ResponseSample {
+        status,
+        headers: name_values(headers, OpenapiSchemerError::ValidateResponse)?,
+        body: read_body(body, OpenapiSchemerError::ValidateResponse)?,
+        content_type,
+    };
+    match checker.check_response(operation_id, &response)? {
+        Some(problems) => Ok(ContractResult::new(body.map(Path::to_path_buf), problems)),
+        None => Err(OpenapiSchemerError::ValidateResponse(format!(
+            "Could not find operation `{}`",
+            operation_id
+        ))),
+    }
+}
+
+/// Checks the structure of the spec and, with `examples`, whether its
+/// examples match their schemas.
+pub fn validate<T: ValidationParser>(
+    parser: T,
+    examples: bool,
+) -> Result<ValidateResult, OpenapiSchemerError> {
+    let mut diagnostics = parser.get_diagnostics()?;
+    if examples {
+        diagnostics.extend(parser.get_example_diagnostics()?);
+    }
+    Ok(ValidateResult::new(diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        diagnostics: Vec<Diagnostic>,
+        example_diagnostics: Vec<Diagnostic>,
+    }
+    impl MockParser {
+        fn new(diagnostics: Vec<Diagnostic>) -> MockParser {
+            MockParser {
+                diagnostics,
+                example_diagnostics: vec![],
+            }
+        }
+    }
+    impl ValidationParser for MockParser {
+        fn get_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError> {
+            Ok(self.diagnostics.to_owned())
+        }
+
+        fn get_example_diagnostics(&self) -> Result<Vec<Diagnostic>, OpenapiSchemerError> {
+            Ok(self.example_diagnostics.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser::new(vec![Diagnostic {
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line: 3,
+            column: 5,
+            message: "Missing required key `paths`".to_string(),
+        }]);
+        let result = validate(parser, false)?;
+        assert_eq!(
+            result.to_string(),
+            "/spec/openapi.yaml:3:5: Missing required key `paths`"
+        );
+        assert!(!result.is_valid());
+        assert!(validate(MockParser::new(vec![]), false)?.is_valid());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_examples() -> Result<(), Box<dyn Error>> {
+        let parser = || {
+            let mut parser = MockParser::new(vec![]);
+            parser.example_diagnostics = vec![Diagnostic {
+                file: PathBuf::from("/spec/openapi.yaml"),
+                line: 40,
+                column: 19,
+                message: "Example does not match its schema: `$.id` should be of type `integer`, not `string`".to_string(),
+            }];
+            parser
+        };
+        assert_eq!(validate(parser(), false)?.to_string(), "No problems found");
+        assert_eq!(
+            validate(parser(), true)?.to_string(),
+            "/spec/openapi.yaml:40:19: Example does not match its schema: `$.id` should be of type `integer`, not `string`"
+        );
+        Ok(())
+    }
+
+    struct MockChecker {
+        problems: Option<Vec<ContractProblem>>,
+    }
+    impl ContractChecker for MockChecker {
+        fn check_request(
+            &self,
+            operation_id: &str,
+            request: &RequestSample,
+        ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+            assert_eq!(operation_id, "listPets");
+            assert_eq!(
+                request.params,
+                vec![("limit".to_string(), "ten".to_string())]
+            );
+            Ok(self.problems.to_owned())
+        }
+
+        fn check_response(
+            &self,
+            operation_id: &str,
+            response: &ResponseSample,
+        ) -> Result<Option<Vec<ContractProblem>>, OpenapiSchemerError> {
+            assert_eq!((operation_id, response.status), ("listPets", 500));
+            Ok(self.problems.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_request() -> Result<(), Box<dyn Error>> {
+        let checker = MockChecker {
+            problems: Some(vec![
+                ContractProblem {
+                    message: "`limit` should be of type `integer`, not `string`".to_string(),
+                    position: None,
+                },
+                ContractProblem {
+                    message: "`$.age` should be of type `integer`, not `string`".to_string(),
+                    position: Some((2, 10)),
+                },
+            ]),
+        };
+        let params = vec!["limit=ten".to_string()];
+        let result = request(checker, "listPets", &params, None, None)?;
+        assert_eq!(
+            result.to_string(),
+            "`limit` should be of type `integer`, not `string`\n\
+             `$.age` should be of type `integer`, not `string`"
+        );
+        assert!(!result.is_valid());
+
+        let checker = MockChecker { problems: None };
+        assert_eq!(
+            request(checker, "listPets", &params, None, None)
+                .unwrap_err()
+                .to_string(),
+            "Could not find operation `listPets`"
+        );
+        let checker = MockChecker { problems: None };
+        assert_eq!(
+            request(checker, "listPets", &["limit".to_string()], None, None)
+                .unwrap_err()
+                .to_string(),
+            "Expected `name=value` but found `limit`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_response() -> Result<(), Box<dyn Error>> {
+        let checker = MockChecker {
+            problems: Some(vec![]),
+        };
+        assert_eq!(
+            response(checker, "listPets", 500, &[], None, None)?.to_string(),
+            "No problems found"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_no_diagnostics() -> Result<(), Box<dyn Error>> {
+        let parser = MockParser::new(vec![]);
+        let result = validate(parser, true)?;
+        assert_eq!(result.to_string(), "No problems found");
+        Ok(())
+    }
+}
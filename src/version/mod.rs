@@ -0,0 +1,260 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{diff::DiffParser, edit::FileEdits, version::VersionParser, yaml::YamlNode},
+    diff::{compare, DiffEntry},
+    error::OpenapiSchemerError,
+    output::{write_file, OutputFormatter},
+};
+
+/// The part of a semantic version a set of changes calls for bumping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl Bump {
+    fn name(&self) -> &'static str {
+        match self {
+            Bump::Major => "major",
+            Bump::Minor => "minor",
+            Bump::Patch => "patch",
+        }
+    }
+
+    /// `version` bumped, keeping a leading `v` and dropping any pre-release
+    /// or build suffix.
+    fn apply(&self, version: &str) -> Option<String> {
+        let (prefix, number) = match version.strip_prefix('v') {
+            Some(number) => ("v", number),
+            None => ("", version),
+        };
+        let core = number.split(['-', '+']).next().unwrap_or_default();
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        let (major, minor, patch) = match parts.as_slice() {
+            [major, minor, patch] => (*major, *minor, *patch),
+            _ => return None,
+        };
+        let (major, minor, patch) = match self {
+            Bump::Major => (major + 1, 0, 0),
+            Bump::Minor => (major, minor + 1, 0),
+            Bump::Patch => (major, minor, patch + 1),
+        };
+        Some(format!("{}{}.{}.{}", prefix, major, minor, patch))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SuggestResult {
+    bump: Bump,
+    from: String,
+    version: String,
+    /// The changes that call for `bump`: the breaking ones for a major bump,
+    /// every one for a minor bump.
+    reasons: Vec<DiffEntry>,
+}
+
+impl SuggestResult {
+    /// The edit setting `info.version` of the new spec to `version`.
+    pub fn edits<V: VersionParser>(
+        &self,
+        new_version: &V,
+    ) -> Result<FileEdits, OpenapiSchemerError> {
+        new_version.get_version_edit(&self.version)
+    }
+
+    /// Writes the new `info.version` over the file it is written in.
+    pub fn write<V: VersionParser>(&self, new_version: &V) -> Result<(), OpenapiSchemerError> {
+        let edits = self.edits(new_version)?;
+        write_file(&edits.file, &edits.content).map_err(OpenapiSchemerError::VersionSuggest)
+    }
+}
+
+impl Display for SuggestResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![format!(
+            "{}: {} -> {}",
+            self.bump.name(),
+            self.from,
+            self.version
+        )];
+        lines.extend(self.reasons.iter().map(|entry| format!("  {}", entry)));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for SuggestResult {
+    fn to_node(&self) -> YamlNode {
+        YamlNode::mapping(vec![
+            ("bump", YamlNode::string(self.bump.name())),
+            ("from", YamlNode::string(&self.from)),
+            ("version", YamlNode::string(&self.version)),
+            (
+                "reasons",
+                YamlNode::sequence(self.reasons.iter().map(DiffEntry::to_node).collect()),
+            ),
+        ])
+    }
+}
+
+/// Suggests the next `info.version` of `new` from what changed since `old`:
+/// a major bump for breaking changes, a minor one for anything else the
+/// diff reports, such as additions and deprecations, and a patch otherwise.
+/// The version bumped is the one of `old`.
+pub fn suggest<T: DiffParser, V: VersionParser>(
+    old: T,
+    new: T,
+    old_version: V,
+) -> Result<SuggestResult, OpenapiSchemerError> {
+    let from = old_version.get_version()?.ok_or_else(|| {
+        OpenapiSchemerError::VersionSuggest("The older spec has no `info.version`".to_string())
+    })?;
+    let entries = compare(&old.get_snapshot()?, &new.get_snapshot()?);
+    let (bump, reasons) = match entries.iter().any(|entry| entry.breaking) {
+        true => (
+            Bump::Major,
+            entries.into_iter().filter(|entry| entry.breaking).collect(),
+        ),
+        false if !entries.is_empty() => (Bump::Minor, entries),
+        false => (Bump::Patch, vec![]),
+    };
+    let version = bump.apply(&from).ok_or_else(|| {
+        OpenapiSchemerError::VersionSuggest(format!(
+            "`{}` is not a semantic version such as `1.2.0`",
+            from
+        ))
+    })?;
+    Ok(SuggestResult {
+        bump,
+        from,
+        version,
+        reasons,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use crate::bindings::diff::{OperationSnapshot, ParameterSnapshot, SpecSnapshot};
+
+    use super::*;
+
+    struct MockParser {
+        snapshot: SpecSnapshot,
+        version: Option<String>,
+    }
+    impl DiffParser for MockParser {
+        fn get_snapshot(&self) -> Result<SpecSnapshot, OpenapiSchemerError> {
+            Ok(self.snapshot.to_owned())
+        }
+    }
+    impl VersionParser for MockParser {
+        fn get_version(&self) -> Result<Option<String>, OpenapiSchemerError> {
+            Ok(self.version.to_owned())
+        }
+        fn get_version_edit(&self, version: &str) -> Result<FileEdits, OpenapiSchemerError> {
+            if self.version.is_none() {
+                return Err(OpenapiSchemerError::VersionSuggest(
+                    "The spec has no `info.version` to update".to_string(),
+                ));
+            }
+            Ok(FileEdits {
+                file: PathBuf::from("/spec/openapi.yaml"),
+                edits: vec![],
+                content: format!("version: {}\n", version),
+            })
+        }
+    }
+
+    fn parser(operations: Vec<(&str, Vec<(&str, bool)>)>, version: Option<&str>) -> MockParser {
+        let operations = operations
+            .into_iter()
+            .map(|(text, parameters)| OperationSnapshot {
+                text: text.to_string(),
+                parameters: parameters
+                    .into_iter()
+                    .map(|(name, required)| ParameterSnapshot {
+                        name: name.to_string(),
+                        location: "query".to_string(),
+                        required,
+                        schema_type: "string".to_string(),
+                    })
+                    .collect(),
+                deprecated: false,
+            })
+            .collect();
+        MockParser {
+            snapshot: SpecSnapshot {
+                paths: vec![],
+                operations,
+                schemas: vec![],
+            },
+            version: version.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn test_suggest() -> Result<(), Box<dyn Error>> {
+        let suggest_for = |old: Vec<(&str, Vec<(&str, bool)>)>,
+                           new: Vec<(&str, Vec<(&str, bool)>)>,
+                           version: &str| {
+            suggest(
+                parser(old.to_owned(), None),
+                parser(new.to_owned(), None),
+                parser(vec![], Some(version)),
+            )
+        };
+        let result = suggest_for(
+            vec![("GET /pets", vec![])],
+            vec![("GET /pets", vec![("limit", true), ("tag", false)])],
+            "1.4.2",
+        )?;
+        assert_eq!(
+            result.to_string(),
+            "major: 1.4.2 -> 2.0.0\n  + parameter limit (query) in GET /pets"
+        );
+        assert_eq!(
+            result.edits(&parser(vec![], Some("1.4.2")))?.content,
+            "version: 2.0.0\n"
+        );
+        // Only writing the suggestion needs a version in the new spec.
+        assert_eq!(
+            result.edits(&parser(vec![], None)).unwrap_err().to_string(),
+            "The spec has no `info.version` to update"
+        );
+
+        let result = suggest_for(
+            vec![("GET /pets", vec![("limit", true)])],
+            vec![
+                ("GET /pets", vec![("limit", false)]),
+                ("POST /pets", vec![]),
+            ],
+            "v1.4.2-beta.1",
+        )?;
+        assert_eq!(
+            result.to_string(),
+            "minor: v1.4.2-beta.1 -> v1.5.0\n  \
+             + operation POST /pets\n  \
+             ~ parameter limit (query) in GET /pets: required true -> false"
+        );
+
+        let unchanged = vec![("GET /pets", vec![])];
+        assert_eq!(
+            suggest_for(unchanged.to_owned(), unchanged.to_owned(), "1.4.2")?.to_string(),
+            "patch: 1.4.2 -> 1.4.3"
+        );
+        assert_eq!(
+            suggest_for(unchanged.to_owned(), unchanged, "latest")
+                .unwrap_err()
+                .to_string(),
+            "`latest` is not a semantic version such as `1.2.0`"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use crate::{
+    bindings::{
+        webhook::{WebhookNode, WebhookParser},
+        yaml::YamlNode,
+    },
+    error::OpenapiSchemerError,
+    output::{ListEntry, OutputFormatter, SortedList},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListResult {
+    webhooks: Vec<WebhookNode>,
+    locations: bool,
+}
+
+impl ListResult {
+    pub fn new(webhooks: Vec<WebhookNode>, locations: bool) -> ListResult {
+        ListResult {
+            webhooks,
+            locations,
+        }
+    }
+
+    /// `POST newPet -> newPetHook`, or `<none>` in place of a missing
+    /// operationId.
+    fn line(&self, webhook: &WebhookNode) -> String {
+        format!(
+            "{} {} -> {}",
+            webhook.method.to_uppercase(),
+            webhook.text,
+            webhook.operation_id.as_deref().unwrap_or("<none>")
+        )
+    }
+}
+
+impl SortedList for ListResult {
+    fn sort_by_name(&mut self) {
+        self.webhooks.sort_by(|a, b| a.text.cmp(&b.text));
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .webhooks
+            .iter()
+            .map(|webhook| match self.locations {
+                true => ListEntry {
+                    name: self.line(webhook),
+                    file: webhook.file.to_owned(),
+                    line: webhook.line,
+                    column: webhook.column,
+                }
+                .with_location(),
+                false => self.line(webhook),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl OutputFormatter for ListResult {
+    fn to_node(&self) -> YamlNode {
+        let webhooks = self
+            .webhooks
+            .iter()
+            .map(|webhook| {
+                YamlNode::mapping(vec![
+                    ("name", YamlNode::string(&webhook.text)),
+                    ("method", YamlNode::string(&webhook.method)),
+                    (
+                        "operationId",
+                        webhook
+                            .operation_id
+                            .as_deref()
+                            .map_or_else(YamlNode::null, YamlNode::string),
+                    ),
+                    (
+                        "file",
+                        YamlNode::string(&webhook.file.display().to_string()),
+                    ),
+                    ("line", YamlNode::integer(webhook.line)),
+                    ("column", YamlNode::integer(webhook.column)),
+                ])
+            })
+            .collect();
+        YamlNode::sequence(webhooks)
+    }
+}
+
+pub fn list<T: WebhookParser>(
+    parser: T,
+    locations: bool,
+) -> Result<ListResult, OpenapiSchemerError> {
+    let nodes = parser.get_webhook_nodes()?;
+    Ok(ListResult::new(nodes, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+
+    struct MockParser {
+        nodes: Vec<WebhookNode>,
+    }
+    impl WebhookParser for MockParser {
+        fn get_webhook_nodes(&self) -> Result<Vec<WebhookNode>, OpenapiSchemerError> {
+            Ok(self.nodes.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_list() -> Result<(), Box<dyn Error>> {
+        let webhook = |text: &str, method: &str, operation_id: Option<&str>, line| WebhookNode {
+            text: text.to_string(),
+            method: method.to_string(),
+            operation_id: operation_id.map(str::to_owned),
+            file: PathBuf::from("/spec/openapi.yaml"),
+            line,
+            column: 5,
+        };
+        let nodes = vec![
+            webhook("newPet", "post", Some("newPetHook"), 4),
+            webhook("petGone", "delete", None, 9),
+        ];
+        let parser = MockParser {
+            nodes: nodes.to_owned(),
+        };
+        assert_eq!(
+            list(parser, false)?.to_string(),
+            "POST newPet -> newPetHook\nDELETE petGone -> <none>"
+        );
+        let parser = MockParser { nodes };
+        assert_eq!(
+            list(parser, true)?.to_string(),
+            "/spec/openapi.yaml:4:5: POST newPet -> newPetHook\n\
+             /spec/openapi.yaml:9:5: DELETE petGone -> <none>"
+        );
+        Ok(())
+    }
+}